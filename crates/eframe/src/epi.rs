@@ -40,6 +40,40 @@ pub type EventLoopBuilderHook = Box<dyn FnOnce(&mut EventLoopBuilder<UserEvent>)
 #[cfg(any(feature = "glow", feature = "wgpu"))]
 pub type WindowBuilderHook = Box<dyn FnOnce(egui::ViewportBuilder) -> egui::ViewportBuilder>;
 
+/// Shows the native web views that the ui asks for with [`egui::WebView`].
+///
+/// Set one with [`NativeOptions::webview_backend`].
+/// The `egui_webview` crate has one that uses [`wry`](https://docs.rs/wry).
+///
+/// eframe gives the keyboard focus back to egui when the user clicks outside of the web views.
+/// Web views are shown in the root viewport and in deferred viewports, but not in immediate viewports.
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "webview")]
+pub trait WebViewBackend {
+    /// Create, move and close the web views of the window, to match what its ui showed this frame.
+    ///
+    /// Called after each frame of each viewport, also when it showed no web views.
+    fn update(
+        &mut self,
+        window: &winit::window::Window,
+        pixels_per_point: f32,
+        webviews: &[egui::WebViewOutput],
+    );
+
+    /// The user clicked on egui in this window, so move the keyboard focus from its web views back to egui.
+    fn focus_parent(&mut self, window_id: winit::window::WindowId);
+
+    /// Close the web views of all windows except these, since the other windows have been closed.
+    fn retain_windows(&mut self, window_ids: &[winit::window::WindowId]);
+
+    /// If set, eframe will call [`Self::update`] again after this long, even if nothing happens.
+    ///
+    /// Useful if the web views need the event loop to be run regularly.
+    fn update_interval(&self) -> Option<std::time::Duration> {
+        None
+    }
+}
+
 /// This is how your app is created.
 ///
 /// You can use the [`CreationContext`] to setup egui, restore state, setup OpenGL things, etc.
@@ -149,6 +183,59 @@ pub trait App {
     /// On native the path is picked using [`crate::storage_dir`].
     fn save(&mut self, _storage: &mut dyn Storage) {}
 
+    /// Called when the graphics context was lost and has been re-created,
+    /// e.g. when an Android app is resumed after being suspended.
+    ///
+    /// All GPU resources you created yourself (textures, buffers, shaders, …) are gone,
+    /// so re-create them here using e.g. [`Frame::gl`].
+    ///
+    /// egui re-uploads its own textures, but textures you created with
+    /// [`egui::Context::load_texture`] must also be re-created
+    /// (see [`egui::Context::reupload_textures`]).
+    fn on_recreate_gpu(&mut self, _frame: &mut Frame) {}
+
+    /// Called when a new viewport (native window) has been added,
+    /// with the [`egui::ViewportBuilder`] it was created from.
+    ///
+    /// Use this to allocate resources that belong to that window,
+    /// e.g. a 3D scene or a file watcher, and free them in [`Self::on_viewport_destroyed`].
+    ///
+    /// This is called at the end of the frame in which the viewport was first shown
+    /// (with [`egui::Context::show_viewport_deferred`] or [`egui::Context::show_viewport_immediate`]).
+    /// It is not called for the root viewport.
+    ///
+    /// Only called on native.
+    fn on_viewport_created(
+        &mut self,
+        _viewport_id: egui::ViewportId,
+        _builder: &egui::ViewportBuilder,
+    ) {
+    }
+
+    /// Called when a viewport has been removed, because it was not shown during the last frame.
+    ///
+    /// Every call to [`Self::on_viewport_created`] is matched by a call to this,
+    /// except for the viewports that are still open when the app exits (see [`Self::on_exit`]).
+    ///
+    /// Only called on native.
+    fn on_viewport_destroyed(&mut self, _viewport_id: egui::ViewportId) {}
+
+    /// Called on startup for each viewport that was open when the app was last saved,
+    /// if [`NativeOptions::restore_viewports`] is set.
+    ///
+    /// The `builder` has the title, position and size the viewport had.
+    /// To reopen the viewport, show it from [`Self::update`] like before,
+    /// using `viewport_id` and `builder`
+    /// (e.g. with [`egui::Context::show_viewport_deferred`]).
+    ///
+    /// Called before the first call to [`Self::update`]. Only called on native.
+    fn recreate_viewport(
+        &mut self,
+        _viewport_id: egui::ViewportId,
+        _builder: egui::ViewportBuilder,
+    ) {
+    }
+
     /// Called once on shutdown, after [`Self::save`].
     ///
     /// If you need to abort an exit check `ctx.input(|i| i.viewport().close_requested())`
@@ -254,6 +341,11 @@ pub struct NativeOptions {
     /// but if you are embedding 3D in egui you may want to turn on multisampling.
     pub multisampling: u16,
 
+    /// How egui's colors are turned into pixels, by both the `glow` and `wgpu` renderers.
+    ///
+    /// This overrides `egui_wgpu::WgpuConfiguration::color_management`.
+    pub color_management: egui::epaint::ColorManagement,
+
     /// Sets the number of bits in the depth buffer.
     ///
     /// `egui` doesn't need the depth buffer, so the default value is 0.
@@ -338,9 +430,114 @@ pub struct NativeOptions {
     #[cfg(feature = "wgpu")]
     pub wgpu_options: egui_wgpu::WgpuConfiguration,
 
+    /// Paint each deferred viewport (see [`egui::Context::show_viewport_deferred`]) on its own thread.
+    ///
+    /// The ui of all viewports still runs on the main thread,
+    /// but waiting for vsync in one window no longer holds up the others.
+    /// Viewports that take a screenshot are painted on the main thread for that frame.
+    ///
+    /// Default: `false`. Only supported by the wgpu renderer.
+    #[cfg(feature = "wgpu")]
+    pub render_thread_per_viewport: bool,
+
+    /// Shows the native web views of [`egui::WebView`].
+    /// Without one, [`egui::WebView`] only shows its URL.
+    ///
+    /// Note: A [`NativeOptions`] clone will not include the `webview_backend`.
+    #[cfg(feature = "webview")]
+    pub webview_backend: Option<Box<dyn WebViewBackend>>,
+
     /// Controls whether or not the native window position and size will be
     /// persisted (only if the "persistence" feature is enabled).
     pub persist_window: bool,
+
+    /// On Mac: let the app cancel quitting with Cmd-Q, see [`Frame::close_requested`].
+    ///
+    /// Normally Cmd-Q quits the app right away (after calling [`App::save`] and [`App::on_exit`]).
+    /// When this is set, Cmd-Q is instead treated like a click on the close button of the main window.
+    ///
+    /// This turns off the default app menu that `winit` creates (which is where Cmd-Q comes from),
+    /// so the app will have no menu bar items, unless you add your own.
+    ///
+    /// Default: `false`. Has no effect on other platforms.
+    pub intercept_quit: bool,
+
+    /// Remember which viewports (native windows) were open when the app closed,
+    /// and reopen them on the next start.
+    ///
+    /// The title, position and size of each viewport is saved, and
+    /// [`App::recreate_viewport`] is called for each of them on startup.
+    ///
+    /// Only works if the "persistence" feature is enabled.
+    ///
+    /// Default: `false`.
+    pub restore_viewports: bool,
+
+    /// Only allow one instance of the app to run at a time.
+    ///
+    /// Set this to an id that is unique to your app, e.g. `Some("com.example.my_app".to_owned())`.
+    ///
+    /// When the app is started while another instance with the same id is running,
+    /// the new instance sends its command line arguments to the running one and exits right away.
+    /// The running instance gets them as an [`egui::Event::InstanceArgs`],
+    /// and its main window is brought to the front.
+    ///
+    /// Arguments that are relative paths to existing files are made absolute before they are sent.
+    ///
+    /// The instances talk to each other over an endpoint that only the current user can access:
+    /// a Unix domain socket in `$XDG_RUNTIME_DIR` (or `$TMPDIR`/`$HOME`), or a named pipe on Windows.
+    /// The new instance only exits once the running one has acknowledged its arguments.
+    ///
+    /// Default: `None`.
+    pub single_instance: Option<String>,
+
+    /// The custom URL schemes handled by the app, e.g. `vec!["myapp".to_owned()]`.
+    ///
+    /// When a URL using one of these schemes is opened, e.g. `myapp://oauth/callback?code=…`,
+    /// the app gets it as an [`egui::Event::DeepLink`] and its main window is brought to the front.
+    /// This is what you need for OAuth redirects and links to documents in your app.
+    ///
+    /// The schemes must also be registered with the OS, see [`crate::register_url_scheme`].
+    ///
+    /// On Windows and Linux the URL is passed as a command line argument,
+    /// so you most likely want to set [`Self::single_instance`] too,
+    /// so that the URL goes to the running instance instead of starting a new one.
+    ///
+    /// Default: empty.
+    pub url_schemes: Vec<String>,
+
+    /// Is this a document-based app that can be asked to open files?
+    ///
+    /// If so, the files the OS asks the app to open, e.g. when the user double-clicks
+    /// a document associated with the app, are sent to the app as [`egui::Event::OpenFile`]
+    /// and its main window is brought to the front.
+    /// This works both for the files the app is started with and for files opened while it is running.
+    ///
+    /// On Mac this includes files dropped on the dock icon.
+    /// On Windows and Linux the files are passed as command line arguments,
+    /// so you most likely want to set [`Self::single_instance`] too.
+    ///
+    /// Associating file types with the app is done by its installer (or `Info.plist` on Mac).
+    ///
+    /// Default: `false`.
+    pub open_files: bool,
+
+    /// Start a [`puffin_http`] server on this address when the app starts, e.g. `"127.0.0.1:8585"`,
+    /// and turn on the profiler.
+    ///
+    /// Connect to it with `puffin_viewer --url 127.0.0.1:8585`.
+    /// Each frame of the profiler is the painting of one viewport,
+    /// tagged with its [`egui::ViewportId`].
+    ///
+    /// Default: `None`.
+    #[cfg(feature = "puffin_http")]
+    pub puffin_server_addr: Option<String>,
+
+    /// The `AndroidApp` passed to your `android_main`.
+    ///
+    /// This is needed to run on Android, and to show the on-screen keyboard when editing text.
+    #[cfg(all(target_os = "android", any(feature = "glow", feature = "wgpu")))]
+    pub android_app: Option<winit::platform::android::activity::AndroidApp>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -355,9 +552,21 @@ impl Clone for NativeOptions {
             #[cfg(any(feature = "glow", feature = "wgpu"))]
             window_builder: None, // Skip any builder callbacks if cloning
 
+            #[cfg(feature = "webview")]
+            webview_backend: None, // Can't be cloned
+
             #[cfg(feature = "wgpu")]
             wgpu_options: self.wgpu_options.clone(),
 
+            single_instance: self.single_instance.clone(),
+            url_schemes: self.url_schemes.clone(),
+
+            #[cfg(feature = "puffin_http")]
+            puffin_server_addr: self.puffin_server_addr.clone(),
+
+            #[cfg(all(target_os = "android", any(feature = "glow", feature = "wgpu")))]
+            android_app: self.android_app.clone(),
+
             ..*self
         }
     }
@@ -371,6 +580,7 @@ impl Default for NativeOptions {
 
             vsync: true,
             multisampling: 0,
+            color_management: Default::default(),
             depth_buffer: 0,
             stencil_buffer: 0,
             hardware_acceleration: HardwareAcceleration::Preferred,
@@ -396,7 +606,27 @@ impl Default for NativeOptions {
             #[cfg(feature = "wgpu")]
             wgpu_options: egui_wgpu::WgpuConfiguration::default(),
 
+            #[cfg(feature = "wgpu")]
+            render_thread_per_viewport: false,
+
+            #[cfg(feature = "webview")]
+            webview_backend: None,
+
             persist_window: true,
+
+            intercept_quit: false,
+
+            restore_viewports: false,
+
+            single_instance: None,
+            url_schemes: vec![],
+            open_files: false,
+
+            #[cfg(feature = "puffin_http")]
+            puffin_server_addr: None,
+
+            #[cfg(all(target_os = "android", any(feature = "glow", feature = "wgpu")))]
+            android_app: None,
         }
     }
 }
@@ -439,6 +669,16 @@ pub struct WebOptions {
     ///
     /// By default the max size is [`egui::Vec2::INFINITY`], i.e. unlimited.
     pub max_size_points: egui::Vec2,
+
+    /// Expose the UI to screen readers by mirroring the [AccessKit](https://accesskit.dev/) tree
+    /// into invisible DOM elements with ARIA roles and labels.
+    ///
+    /// This has a cost, since the whole tree is built and diffed each frame,
+    /// and browsers don't tell us whether or not a screen reader is running.
+    ///
+    /// Default: `false`.
+    #[cfg(feature = "accesskit")]
+    pub accesskit: bool,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -456,6 +696,9 @@ impl Default for WebOptions {
             wgpu_options: egui_wgpu::WgpuConfiguration::default(),
 
             max_size_points: egui::Vec2::INFINITY,
+
+            #[cfg(feature = "accesskit")]
+            accesskit: false,
         }
     }
 }
@@ -597,6 +840,44 @@ pub struct Frame {
     #[cfg(feature = "wgpu")]
     pub(crate) wgpu_render_state: Option<egui_wgpu::RenderState>,
 
+    /// Files picked with [`Self::pick_file`], waiting to be passed on to egui.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) picked_files: std::rc::Rc<std::cell::RefCell<Vec<egui::DroppedFile>>>,
+
+    /// Used to wake up the app when a picked file has been loaded.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) needs_repaint: std::sync::Arc<crate::web::NeedRepaint>,
+
+    /// The deferred `beforeinstallprompt` event, if the browser offers to install the app.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) install_prompt: Option<web_sys::Event>,
+
+    /// Did the user ask to close the app this frame?
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) close_requested: bool,
+
+    /// Was [`Self::cancel_close`] called this frame?
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) close_canceled: bool,
+
+    /// Set with [`Self::set_taskbar_progress`], sent to the root viewport at the end of the frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) taskbar_progress: Option<egui::TaskbarProgress>,
+
+    /// Set with [`Self::set_msaa`], applied by the painter at the end of the frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) msaa_requests: Vec<(egui::ViewportId, u16)>,
+
+    /// Set with [`Self::set_dock_badge`], applied at the end of the frame.
+    ///
+    /// An empty string removes the badge.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) dock_badge: Option<String>,
+
+    /// Set with [`Self::set_dock_menu`], applied at the end of the frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) dock_menu: Option<Vec<DockMenuItem>>,
+
     /// Raw platform window handle
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) raw_window_handle: RawWindowHandle,
@@ -635,6 +916,101 @@ impl Frame {
         cfg!(target_arch = "wasm32")
     }
 
+    /// Did the user ask to close the app this frame?
+    ///
+    /// This is the case when the close button of the main window (the root viewport) was clicked,
+    /// when [`egui::ViewportCommand::Close`] was sent to it,
+    /// and when the user quit the app with Cmd-Q on Mac (if [`NativeOptions::intercept_quit`] is set).
+    ///
+    /// The app will close at the end of this frame, unless you call [`Self::cancel_close`],
+    /// e.g. to ask the user to save their unsaved changes first:
+    ///
+    /// ```
+    /// # struct MyApp { unsaved_changes: bool, show_save_dialog: bool }
+    /// # impl MyApp {
+    /// fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+    ///     if frame.close_requested() && self.unsaved_changes {
+    ///         frame.cancel_close();
+    ///         self.show_save_dialog = true;
+    ///     }
+    ///     // …
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Other viewports are closed by no longer showing them, so they can't be canceled.
+    /// Check `ctx.input(|i| i.viewport().close_requested())` in their callback instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn close_requested(&self) -> bool {
+        self.close_requested
+    }
+
+    /// Keep the app running even though [`Self::close_requested`] is `true`.
+    ///
+    /// This is the same as sending [`egui::ViewportCommand::CancelClose`] to the root viewport.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cancel_close(&mut self) {
+        self.close_canceled = true;
+    }
+
+    /// Show the progress of a task on the taskbar entry of the main window.
+    ///
+    /// This is the same as sending [`egui::ViewportCommand::TaskbarProgress`] to the root viewport.
+    /// egui already does that for the tasks reported with [`egui::Context::progress`],
+    /// so turn off [`egui::Options::taskbar_progress`] if you want to control it yourself.
+    ///
+    /// Supported on Windows and Linux.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_taskbar_progress(&mut self, progress: egui::TaskbarProgress) {
+        self.taskbar_progress = Some(progress);
+    }
+
+    /// Change the level of multisampling anti-aliasing (MSAA) of a viewport,
+    /// e.g. from a quality setting of your app.
+    ///
+    /// `samples` is like [`NativeOptions::multisampling`], which sets it for all viewports at startup:
+    /// a power-of-two, with `0` turning it off.
+    /// It takes effect from the next frame of the viewport.
+    ///
+    /// With `glow` the viewport is then painted into a multisampled framebuffer (this needs OpenGL 3.0),
+    /// which means that anything you paint directly with OpenGL in [`App::update`] is painted over.
+    /// Use an [`egui::PaintCallback`] instead.
+    /// [`NativeOptions::multisampling`] still applies to the window itself,
+    /// since with `glow` it is part of the OpenGL context, which can't be changed after startup.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_msaa(&mut self, viewport_id: egui::ViewportId, samples: u16) {
+        self.msaa_requests.push((viewport_id, samples));
+    }
+
+    /// Show a short text, e.g. the number of unread messages, on the icon of the app,
+    /// or remove it with `None`.
+    ///
+    /// * On Mac this is the badge of the dock icon.
+    /// * On Linux only numbers are shown, by the docks and launchers that support
+    ///   the Unity launcher API (KDE, Dash to Dock, Plank, …).
+    ///   Any other text hides the badge.
+    /// * Not supported on Windows.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub fn set_dock_badge(&mut self, badge: Option<&str>) {
+        self.dock_badge = Some(badge.unwrap_or_default().to_owned());
+    }
+
+    /// Set the items of the dock menu (Mac) or the tasks of the jump list (Windows) of the app.
+    ///
+    /// When the user picks an item, the app gets its [`DockMenuItem::args`]:
+    /// * On Mac they are sent to the running app as an [`egui::Event::InstanceArgs`].
+    /// * On Windows a new instance of the app is started with them as command line arguments,
+    ///   so you most likely want to set [`NativeOptions::single_instance`] too,
+    ///   so that they are sent to the running instance as an [`egui::Event::InstanceArgs`] instead.
+    ///
+    /// On Linux the actions of an app are listed in its `.desktop` file, which is up to its installer.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub fn set_dock_menu(&mut self, items: &[DockMenuItem]) {
+        self.dock_menu = Some(items.to_vec());
+    }
+
     /// Information about the integration.
     pub fn info(&self) -> &IntegrationInfo {
         &self.info
@@ -676,6 +1052,151 @@ impl Frame {
     pub fn wgpu_render_state(&self) -> Option<&egui_wgpu::RenderState> {
         self.wgpu_render_state.as_ref()
     }
+
+    /// Open the file picker of the browser, letting the user select a file.
+    ///
+    /// `accept` is a comma-separated list of file extensions and/or MIME types
+    /// (e.g. `".png,image/jpeg"`), or an empty string to allow any file.
+    ///
+    /// The picked file is loaded in the background and then shows up
+    /// in [`egui::RawInput::dropped_files`], just as if it had been dropped onto the canvas:
+    ///
+    /// ``` no_run
+    /// # let ctx = eframe::egui::Context::default();
+    /// ctx.input(|i| {
+    ///     for file in &i.raw.dropped_files {
+    ///         // `file.bytes` contains the contents of the file.
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// Only available on web. On native, use a file dialog crate like [`rfd`](https://docs.rs/rfd) instead.
+    #[cfg(target_arch = "wasm32")]
+    pub fn pick_file(&self, accept: &str) -> Result<(), String> {
+        let picked_files = self.picked_files.clone();
+        let needs_repaint = self.needs_repaint.clone();
+        crate::web::pick_file(accept, move |file| {
+            picked_files.borrow_mut().push(file);
+            needs_repaint.repaint_asap();
+        })
+        .ok_or_else(|| "Failed to open the file picker".to_owned())
+    }
+
+    /// Let the user download `bytes` as a file called `file_name`.
+    ///
+    /// On web the browser decides where to put it, and may ask the user first.
+    ///
+    /// On native the file is written to the download directory of the user,
+    /// which requires the `persistence` feature. Existing files are not overwritten.
+    ///
+    /// ``` no_run
+    /// eframe::Frame::download(b"Hello!", "hello.txt").unwrap();
+    /// ```
+    pub fn download(bytes: &[u8], file_name: &str) -> Result<(), String> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            crate::web::download(bytes, file_name).map_err(|err| {
+                format!(
+                    "Failed to download {file_name:?}: {}",
+                    crate::web::string_from_js_value(&err)
+                )
+            })
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), feature = "persistence"))]
+        {
+            crate::native::file_storage::download(bytes, file_name)
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), not(feature = "persistence")))]
+        {
+            let _ = bytes;
+            Err(format!(
+                "Can't download {file_name:?}: eframe was compiled without the `persistence` feature"
+            ))
+        }
+    }
+
+    /// Does the browser offer to install this web app (as a PWA)?
+    ///
+    /// This requires a web app manifest and a service worker, and is currently
+    /// only supported by Chromium-based browsers.
+    /// If `true`, you can show an "Install" button that calls [`Self::show_install_prompt`].
+    #[cfg(target_arch = "wasm32")]
+    pub fn can_install(&self) -> bool {
+        self.install_prompt.is_some()
+    }
+
+    /// Show the install prompt of the browser, if [`Self::can_install`].
+    ///
+    /// This must be called in response to user input (e.g. a button click),
+    /// and can only be done once per offer from the browser.
+    #[cfg(target_arch = "wasm32")]
+    pub fn show_install_prompt(&mut self) {
+        if let Some(event) = self.install_prompt.take() {
+            if let Err(err) = crate::web::show_install_prompt(&event) {
+                log::error!(
+                    "Failed to show install prompt: {}",
+                    crate::web::string_from_js_value(&err)
+                );
+            }
+        }
+    }
+
+    /// Put arbitrary data of the given MIME type on the clipboard,
+    /// e.g. `"image/png"` or `"text/html"`.
+    ///
+    /// This uses the asynchronous Clipboard API, which most browsers only allow
+    /// in response to user input (e.g. a button click) and on secure (`https`) pages.
+    /// Which MIME types are supported depends on the browser.
+    ///
+    /// For plain text, use [`egui::PlatformOutput::copied_text`] (e.g. via [`egui::Context::copy_text`]) instead.
+    ///
+    /// Only supported on web. On native this returns an error.
+    pub fn copy_to_clipboard(mime: &str, bytes: &[u8]) -> Result<(), String> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            crate::web::set_clipboard_data(mime, bytes).map_err(|err| {
+                format!(
+                    "Failed to copy {mime:?} to the clipboard: {}",
+                    crate::web::string_from_js_value(&err)
+                )
+            })
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = bytes;
+            Err(format!(
+                "Copying {mime:?} to the clipboard is only supported on web"
+            ))
+        }
+    }
+}
+
+/// An item in the dock menu (Mac) or jump list (Windows) of the app, see [`Frame::set_dock_menu`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DockMenuItem {
+    /// The text of the item.
+    pub title: String,
+
+    /// The command line arguments the app gets when the item is picked, e.g. `["--new-window"]`.
+    pub args: Vec<String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DockMenuItem {
+    /// An item with this title, giving the app these command line arguments when picked.
+    pub fn new(
+        title: impl Into<String>,
+        args: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
 }
 
 /// Information about the web environment (if applicable).
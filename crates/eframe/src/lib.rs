@@ -177,9 +177,26 @@ mod native;
 #[cfg(feature = "persistence")]
 pub use native::file_storage::storage_dir;
 
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+pub use native::url_scheme::register_url_scheme;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod icon_data;
 
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(not(target_os = "ios"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+pub use native::run::PumpedApp;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+pub use native::multi_app::MultiApp;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+pub use native::embed;
+
 /// This is how you start a native (desktop) app.
 ///
 /// The first argument is name of your app, which is a an identifier
@@ -270,6 +287,67 @@ pub fn run_native(
 
 // ----------------------------------------------------------------------------
 
+/// Start a native app that is driven by your own main loop, e.g. that of an audio host,
+/// a game engine or a plugin runtime, instead of taking over the thread like [`run_native`] does.
+///
+/// The arguments are the same as for [`run_native`].
+/// Call [`PumpedApp::pump`] regularly to handle the events of the windows and repaint them.
+///
+/// This owns the [`winit`] event loop, of which there can only be one per process,
+/// so it cannot be combined with [`run_native`].
+///
+/// ``` no_run
+/// # struct MyEguiApp {}
+/// # impl eframe::App for MyEguiApp {
+/// #     fn update(&mut self, _ctx: &egui::Context, _frame: &mut eframe::Frame) {}
+/// # }
+/// fn main() -> eframe::Result<()> {
+///     let native_options = eframe::NativeOptions::default();
+///     let mut app = eframe::run_pumped("MyApp", native_options, Box::new(|_cc| Box::new(MyEguiApp {})))?;
+///
+///     loop {
+///         // … do the work of your own loop …
+///
+///         let timeout = Some(std::time::Duration::from_millis(16));
+///         if let std::ops::ControlFlow::Break(result) = app.pump(timeout) {
+///             return result;
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Errors
+/// This function can fail if we fail to create the event loop.
+/// Errors setting up the graphics context are returned by [`PumpedApp::pump`].
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(not(target_os = "ios"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+pub fn run_pumped(
+    app_name: &str,
+    mut native_options: NativeOptions,
+    app_creator: AppCreator,
+) -> Result<PumpedApp> {
+    if native_options.viewport.title.is_none() {
+        native_options.viewport.title = Some(app_name.to_owned());
+    }
+
+    match native_options.renderer {
+        #[cfg(feature = "glow")]
+        Renderer::Glow => {
+            log::debug!("Using the glow renderer");
+            native::run::pumped_glow(app_name, native_options, app_creator)
+        }
+
+        #[cfg(feature = "wgpu")]
+        Renderer::Wgpu => {
+            log::debug!("Using the wgpu renderer");
+            native::run::pumped_wgpu(app_name, native_options, app_creator)
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// The simplest way to get started when writing a native app.
 ///
 /// This does NOT support persistence. For that you need to use [`run_native`].
@@ -390,6 +468,15 @@ mod profiling_scopes {
         };
     }
     pub(crate) use profile_scope;
+
+    /// A `tracing` span for feature "tracing", entered until the end of the current scope.
+    macro_rules! trace_span {
+        ($($arg: tt)*) => {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!($($arg)*).entered();
+        };
+    }
+    pub(crate) use trace_span;
 }
 
 #[allow(unused_imports)]
@@ -0,0 +1,196 @@
+//! Handlers for the Apple events Mac sends when the app is asked to open URLs or files.
+//!
+//! winit does not expose these, so we install our own handlers with the `NSAppleEventManager`.
+
+#![allow(unsafe_code)]
+
+use std::{
+    ffi::CStr,
+    os::raw::c_char,
+    path::PathBuf,
+    sync::{Mutex, Once},
+};
+
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Class, Object, Sel},
+    sel, sel_impl,
+};
+use winit::event_loop::EventLoopProxy;
+
+use super::winit_integration::UserEvent;
+
+/// The class and id of the Apple event sent when a URL is opened: `'GURL'`.
+const K_AE_GET_URL: u32 = u32::from_be_bytes(*b"GURL");
+
+/// The class of the Apple event sent when documents are opened: `'aevt'`.
+const K_CORE_EVENT_CLASS: u32 = u32::from_be_bytes(*b"aevt");
+
+/// The id of the Apple event sent when documents are opened: `'odoc'`.
+const K_AE_OPEN_DOCUMENTS: u32 = u32::from_be_bytes(*b"odoc");
+
+/// The parameter holding the URL or the list of files: `'----'`.
+const KEY_DIRECT_OBJECT: u32 = u32::from_be_bytes(*b"----");
+
+/// A file URL descriptor: `'furl'`.
+const TYPE_FILE_URL: u32 = u32::from_be_bytes(*b"furl");
+
+/// Where to send the URLs and files. Replaced each time the app is run.
+static PROXY: Mutex<Option<EventLoopProxy<UserEvent>>> = Mutex::new(None);
+
+/// Send [`UserEvent::DeepLink`] for every URL the app is asked to open.
+pub fn handle_urls(proxy: EventLoopProxy<UserEvent>) {
+    static INSTALL: Once = Once::new();
+    set_proxy(proxy);
+    INSTALL.call_once(|| {
+        install_handler(
+            sel!(handleGetURLEvent:withReplyEvent:),
+            K_AE_GET_URL,
+            K_AE_GET_URL,
+        );
+    });
+}
+
+/// Send [`UserEvent::OpenFile`] for every file the app is asked to open,
+/// e.g. by double-clicking a document or dropping it on the dock icon.
+pub fn handle_open_files(proxy: EventLoopProxy<UserEvent>) {
+    static INSTALL: Once = Once::new();
+    set_proxy(proxy);
+    INSTALL.call_once(|| {
+        install_handler(
+            sel!(handleOpenDocumentsEvent:withReplyEvent:),
+            K_CORE_EVENT_CLASS,
+            K_AE_OPEN_DOCUMENTS,
+        );
+    });
+}
+
+pub(super) fn set_proxy(proxy: EventLoopProxy<UserEvent>) {
+    *PROXY.lock().unwrap_or_else(|err| err.into_inner()) = Some(proxy);
+}
+
+pub(super) fn send_event(event: UserEvent) {
+    if let Some(proxy) = &*PROXY.lock().unwrap_or_else(|err| err.into_inner()) {
+        proxy.send_event(event).ok();
+    }
+}
+
+/// The class whose methods handle the Apple events.
+fn handler_class() -> &'static Class {
+    static DECLARE: Once = Once::new();
+    DECLARE.call_once(|| {
+        let Some(mut decl) = ClassDecl::new("EframeAppleEventHandler", class!(NSObject)) else {
+            return;
+        };
+        // SAFETY: the methods have the signatures the selectors call for.
+        unsafe {
+            decl.add_method(
+                sel!(handleGetURLEvent:withReplyEvent:),
+                handle_get_url_event as extern "C" fn(&Object, Sel, *mut Object, *mut Object),
+            );
+            decl.add_method(
+                sel!(handleOpenDocumentsEvent:withReplyEvent:),
+                handle_open_documents_event
+                    as extern "C" fn(&Object, Sel, *mut Object, *mut Object),
+            );
+        }
+        decl.register();
+    });
+    class!(EframeAppleEventHandler)
+}
+
+fn install_handler(selector: Sel, event_class: u32, event_id: u32) {
+    // SAFETY: we hand an instance of our handler class to the shared `NSAppleEventManager`.
+    // The instance is leaked on purpose, since the event manager does not retain it.
+    unsafe {
+        let handler: *mut Object = msg_send![handler_class(), new];
+        let manager: *mut Object = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+        let () = msg_send![manager,
+            setEventHandler: handler
+            andSelector: selector
+            forEventClass: event_class
+            andEventID: event_id];
+    }
+}
+
+/// Read an `NSString` returned by the Objective-C runtime.
+///
+/// SAFETY: `string` must be null or point to an `NSString`.
+unsafe fn string_from_ns_string(string: *mut Object) -> Option<String> {
+    if string.is_null() {
+        return None;
+    }
+    let utf8: *const c_char = msg_send![string, UTF8String];
+    if utf8.is_null() {
+        return None;
+    }
+    Some(CStr::from_ptr(utf8).to_string_lossy().into_owned())
+}
+
+extern "C" fn handle_get_url_event(
+    _this: &Object,
+    _cmd: Sel,
+    event: *mut Object,
+    _reply: *mut Object,
+) {
+    // SAFETY: `event` is an `NSAppleEventDescriptor`, and we check every returned pointer for null.
+    let url = unsafe {
+        let descriptor: *mut Object =
+            msg_send![event, paramDescriptorForKeyword: KEY_DIRECT_OBJECT];
+        if descriptor.is_null() {
+            return;
+        }
+        string_from_ns_string(msg_send![descriptor, stringValue])
+    };
+
+    if let Some(url) = url {
+        log::debug!("Got deep link {url:?}");
+        send_event(UserEvent::DeepLink(url));
+    }
+}
+
+extern "C" fn handle_open_documents_event(
+    _this: &Object,
+    _cmd: Sel,
+    event: *mut Object,
+    _reply: *mut Object,
+) {
+    let mut paths = vec![];
+
+    // SAFETY: `event` is an `NSAppleEventDescriptor`, and we check every returned pointer for null.
+    unsafe {
+        let list: *mut Object = msg_send![event, paramDescriptorForKeyword: KEY_DIRECT_OBJECT];
+        if list.is_null() {
+            return;
+        }
+        let count: isize = msg_send![list, numberOfItems];
+        for i in 1..=count {
+            // The list is one-based.
+            let item: *mut Object = msg_send![list, descriptorAtIndex: i];
+            if item.is_null() {
+                continue;
+            }
+            let file_url: *mut Object = msg_send![item, coerceToDescriptorType: TYPE_FILE_URL];
+            if file_url.is_null() {
+                continue;
+            }
+            let data: *mut Object = msg_send![file_url, data];
+            let url: *mut Object = msg_send![class!(NSURL),
+                URLWithDataRepresentation: data
+                relativeToURL: std::ptr::null_mut::<Object>()];
+            if url.is_null() {
+                continue;
+            }
+            if let Some(path) = string_from_ns_string(msg_send![url, path]) {
+                paths.push(PathBuf::from(path));
+            }
+        }
+    }
+
+    for path in paths {
+        log::debug!("Asked to open {path:?}");
+        send_event(UserEvent::OpenFile(path));
+    }
+}
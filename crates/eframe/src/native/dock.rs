@@ -0,0 +1,462 @@
+//! The badge and menu of the app icon in the dock (Mac) or taskbar (Windows, Linux),
+//! see [`crate::Frame::set_dock_badge`] and [`crate::Frame::set_dock_menu`].
+//!
+//! * On Mac we set the badge label of the `NSDockTile`, and answer `applicationDockMenu:`
+//!   from a subclass of the app delegate of winit.
+//! * On Windows we build the jump list with the `ICustomDestinationList` COM interface.
+//! * On Linux [`egui_winit::set_taskbar_badge_count`] shows the badge, if it is a number.
+
+use winit::event_loop::EventLoop;
+
+use super::winit_integration::UserEvent;
+use crate::epi::DockMenuItem;
+
+/// Start listening for clicks in the dock menu.
+pub fn start(event_loop: &EventLoop<UserEvent>) {
+    #[cfg(target_os = "macos")]
+    super::apple_events::set_proxy(event_loop.create_proxy());
+    #[cfg(not(target_os = "macos"))]
+    let _ = event_loop;
+}
+
+pub fn set_badge(badge: Option<&str>) {
+    #[cfg(target_os = "macos")]
+    mac::set_badge(badge);
+
+    // Only numbers can be shown, so any other text hides the badge:
+    #[cfg(not(target_os = "macos"))]
+    egui_winit::set_taskbar_badge_count(badge.and_then(|badge| badge.trim().parse().ok()));
+}
+
+pub fn set_menu(items: &[DockMenuItem]) {
+    #[cfg(target_os = "macos")]
+    mac::set_menu(items);
+
+    #[cfg(target_os = "windows")]
+    windows::set_jump_list(items);
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let _ = items;
+}
+
+#[cfg(target_os = "macos")]
+#[allow(unsafe_code)]
+mod mac {
+    use std::cell::{Cell, RefCell};
+    use std::sync::Once;
+
+    use cocoa::{
+        appkit::NSApp,
+        base::{id, nil},
+        foundation::NSString,
+    };
+    use objc::{
+        class,
+        declare::ClassDecl,
+        msg_send,
+        runtime::{Class, Object, Sel},
+        sel, sel_impl,
+    };
+
+    use super::super::{apple_events::send_event, winit_integration::UserEvent};
+    use crate::epi::DockMenuItem;
+
+    thread_local! {
+        /// Read by AppKit on the main thread whenever the dock menu is opened.
+        static ITEMS: RefCell<Vec<DockMenuItem>> = RefCell::new(vec![]);
+
+        /// Menu items don't retain their target, so we keep one alive forever.
+        static MENU_TARGET: Cell<id> = Cell::new(nil);
+    }
+
+    extern "C" {
+        /// Part of the Objective-C runtime, which `objc` links to, but does not bind.
+        fn object_setClass(object: id, class: *const Class) -> *const Class;
+    }
+
+    pub fn set_badge(badge: Option<&str>) {
+        // SAFETY: we only call methods of `NSApplication` and `NSDockTile` that exist,
+        // on the main thread (where the app is updated).
+        unsafe {
+            let app = NSApp();
+            if app.is_null() {
+                return;
+            }
+            let dock_tile: id = msg_send![app, dockTile];
+            let label = match badge {
+                Some(badge) => NSString::alloc(nil).init_str(badge),
+                None => nil,
+            };
+            let () = msg_send![dock_tile, setBadgeLabel: label];
+        }
+    }
+
+    pub fn set_menu(items: &[DockMenuItem]) {
+        ITEMS.with(|cell| *cell.borrow_mut() = items.to_vec());
+
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(install_dock_menu);
+    }
+
+    /// AppKit asks the app delegate for the dock menu, and winit owns the delegate.
+    ///
+    /// So we turn the delegate into an instance of a subclass of its class,
+    /// which only adds `applicationDockMenu:`. The class of winit is left untouched.
+    fn install_dock_menu() {
+        // SAFETY: the subclass adds no instance variables, so the delegate keeps its layout,
+        // and the method has the signature the selector calls for.
+        unsafe {
+            let delegate: id = msg_send![NSApp(), delegate];
+            if delegate.is_null() {
+                log::warn!("The app has no delegate, so it can't have a dock menu");
+                return;
+            }
+
+            let Some(mut decl) = ClassDecl::new("EframeAppDelegate", (*delegate).class()) else {
+                log::warn!("Failed to declare the app delegate for the dock menu");
+                return;
+            };
+            decl.add_method(
+                sel!(applicationDockMenu:),
+                application_dock_menu as extern "C" fn(&Object, Sel, id) -> id,
+            );
+            object_setClass(delegate, decl.register());
+        }
+    }
+
+    /// The class of the target of the dock menu items.
+    fn menu_target_class() -> &'static Class {
+        static DECLARE: Once = Once::new();
+        DECLARE.call_once(|| {
+            let Some(mut decl) = ClassDecl::new("EframeDockMenuTarget", class!(NSObject)) else {
+                return;
+            };
+            // SAFETY: the method has the signature the selector calls for.
+            unsafe {
+                decl.add_method(
+                    sel!(dockMenuItemClicked:),
+                    dock_menu_item_clicked as extern "C" fn(&Object, Sel, id),
+                );
+            }
+            decl.register();
+        });
+        class!(EframeDockMenuTarget)
+    }
+
+    extern "C" fn application_dock_menu(_this: &Object, _cmd: Sel, _app: id) -> id {
+        ITEMS.with(|cell| {
+            let items = cell.borrow();
+            if items.is_empty() {
+                return nil;
+            }
+
+            // SAFETY: we build an autoreleased `NSMenu`, as AppKit expects.
+            unsafe {
+                let target = MENU_TARGET.with(|target| {
+                    if target.get().is_null() {
+                        target.set(msg_send![menu_target_class(), new]);
+                    }
+                    target.get()
+                });
+                let menu: id = msg_send![class!(NSMenu), new];
+                let menu: id = msg_send![menu, autorelease];
+                for (i, item) in items.iter().enumerate() {
+                    let title = NSString::alloc(nil).init_str(&item.title);
+                    let key_equivalent = NSString::alloc(nil).init_str("");
+                    let menu_item: id = msg_send![menu,
+                        addItemWithTitle: title
+                        action: sel!(dockMenuItemClicked:)
+                        keyEquivalent: key_equivalent];
+                    let () = msg_send![menu_item, setTarget: target];
+                    let () = msg_send![menu_item, setTag: i as isize];
+                }
+                menu
+            }
+        })
+    }
+
+    extern "C" fn dock_menu_item_clicked(_this: &Object, _cmd: Sel, sender: id) {
+        // SAFETY: `sender` is one of the `NSMenuItem`s made by `application_dock_menu`.
+        let index: isize = unsafe { msg_send![sender, tag] };
+        let args = ITEMS.with(|cell| {
+            let items = cell.borrow();
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| items.get(index))
+                .map(|item| item.args.clone())
+        });
+        if let Some(args) = args {
+            log::debug!("Dock menu item picked: {args:?}");
+            send_event(UserEvent::InstanceArgs(args));
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+#[allow(unsafe_code, dead_code, non_snake_case)]
+mod windows {
+    use std::{
+        ffi::OsStr,
+        os::windows::ffi::OsStrExt as _,
+        path::Path,
+        ptr::{null_mut, NonNull},
+    };
+
+    use winapi::{
+        ctypes::c_void,
+        shared::{
+            guiddef::REFIID,
+            minwindef::UINT,
+            winerror::{HRESULT, SUCCEEDED},
+            wtypes::VT_LPWSTR,
+            wtypesbase::CLSCTX_INPROC_SERVER,
+        },
+        um::{
+            combaseapi::{CoCreateInstance, CoInitializeEx},
+            objbase::COINIT_APARTMENTTHREADED,
+            propidl::PROPVARIANT,
+            propkey::PKEY_Title,
+            propsys::IPropertyStore,
+            shobjidl_core::{EnumerableObjectCollection, IShellLinkW, ShellLink},
+            unknwnbase::{IUnknown, IUnknownVtbl},
+            winnt::LPCWSTR,
+        },
+        Class, Interface, RIDL,
+    };
+
+    use crate::epi::DockMenuItem;
+
+    // `winapi` has no bindings for the jump list, so we declare them with its own macro:
+
+    RIDL! {#[uuid(0x77f10cf0, 0x3db5, 0x4966, 0xb5, 0x20, 0xb7, 0xc5, 0x4f, 0xd3, 0x5e, 0xd6)]
+    class DestinationList;}
+
+    RIDL! {#[uuid(0x92ca9dcd, 0x5622, 0x4bba, 0xa8, 0x05, 0x5e, 0x9f, 0x54, 0x1b, 0xd8, 0xc9)]
+    interface IObjectArray(IObjectArrayVtbl): IUnknown(IUnknownVtbl) {
+        fn GetCount(
+            pcObjects: *mut UINT,
+        ) -> HRESULT,
+        fn GetAt(
+            uiIndex: UINT,
+            riid: REFIID,
+            ppv: *mut *mut c_void,
+        ) -> HRESULT,
+    }}
+
+    RIDL! {#[uuid(0x5632b1a4, 0xe38a, 0x400a, 0x92, 0x8a, 0xd4, 0xcd, 0x63, 0x23, 0x02, 0x95)]
+    interface IObjectCollection(IObjectCollectionVtbl): IObjectArray(IObjectArrayVtbl) {
+        fn AddObject(
+            punk: *mut IUnknown,
+        ) -> HRESULT,
+        fn AddFromArray(
+            poaSource: *mut IObjectArray,
+        ) -> HRESULT,
+        fn RemoveObjectAt(
+            uiIndex: UINT,
+        ) -> HRESULT,
+        fn Clear() -> HRESULT,
+    }}
+
+    RIDL! {#[uuid(0x6332debf, 0x87b5, 0x4670, 0x90, 0xc0, 0x5e, 0x57, 0xb4, 0x08, 0xa4, 0x9e)]
+    interface ICustomDestinationList(ICustomDestinationListVtbl): IUnknown(IUnknownVtbl) {
+        fn SetAppID(
+            pszAppID: LPCWSTR,
+        ) -> HRESULT,
+        fn BeginList(
+            pcMinSlots: *mut UINT,
+            riid: REFIID,
+            ppv: *mut *mut c_void,
+        ) -> HRESULT,
+        fn AppendCategory(
+            pszCategory: LPCWSTR,
+            poa: *mut IObjectArray,
+        ) -> HRESULT,
+        fn AppendKnownCategory(
+            category: i32,
+        ) -> HRESULT,
+        fn AddUserTasks(
+            poa: *mut IObjectArray,
+        ) -> HRESULT,
+        fn CommitList() -> HRESULT,
+        fn GetRemovedDestinations(
+            riid: REFIID,
+            ppv: *mut *mut c_void,
+        ) -> HRESULT,
+        fn DeleteList(
+            pszAppID: LPCWSTR,
+        ) -> HRESULT,
+        fn AbortList() -> HRESULT,
+    }}
+
+    /// An owned COM interface pointer, released on drop.
+    struct ComPtr<T: Interface>(NonNull<T>);
+
+    impl<T: Interface> ComPtr<T> {
+        /// SAFETY: `object` must be null or an owned reference to a `T`.
+        unsafe fn from_raw(object: *mut c_void) -> Option<Self> {
+            NonNull::new(object.cast()).map(Self)
+        }
+
+        /// SAFETY: COM must be initialized on this thread.
+        unsafe fn create<C: Class>() -> Option<Self> {
+            let mut object = null_mut();
+            let hr = CoCreateInstance(
+                &C::uuidof(),
+                null_mut(),
+                CLSCTX_INPROC_SERVER,
+                &T::uuidof(),
+                &mut object,
+            );
+            if SUCCEEDED(hr) {
+                Self::from_raw(object)
+            } else {
+                None
+            }
+        }
+
+        fn as_unknown(&self) -> &IUnknown {
+            // SAFETY: every COM interface starts with the `IUnknown` vtable.
+            unsafe { &*self.0.as_ptr().cast::<IUnknown>() }
+        }
+
+        fn as_ptr<U>(&self) -> *mut U {
+            self.0.as_ptr().cast()
+        }
+
+        fn query_interface<U: Interface>(&self) -> Option<ComPtr<U>> {
+            let mut object = null_mut();
+            // SAFETY: on success we get an owned reference to a `U`.
+            unsafe {
+                let hr = self.as_unknown().QueryInterface(&U::uuidof(), &mut object);
+                if SUCCEEDED(hr) {
+                    ComPtr::from_raw(object)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    impl<T: Interface> std::ops::Deref for ComPtr<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            // SAFETY: we hold a reference to the object, so it is alive.
+            unsafe { self.0.as_ref() }
+        }
+    }
+
+    impl<T: Interface> Drop for ComPtr<T> {
+        fn drop(&mut self) {
+            // SAFETY: we own one reference to the object.
+            unsafe {
+                self.as_unknown().Release();
+            }
+        }
+    }
+
+    fn wide(text: impl AsRef<OsStr>) -> Vec<u16> {
+        text.as_ref().encode_wide().chain(Some(0)).collect()
+    }
+
+    /// Quote an argument the way the C runtime of Windows splits the command line.
+    fn quote_arg(arg: &str) -> String {
+        if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+            return arg.to_owned();
+        }
+        let mut quoted = String::from('"');
+        let mut backslashes = 0;
+        for c in arg.chars() {
+            match c {
+                '\\' => backslashes += 1,
+                '"' => {
+                    quoted.extend(std::iter::repeat('\\').take(2 * backslashes + 1));
+                    backslashes = 0;
+                }
+                _ => {
+                    quoted.extend(std::iter::repeat('\\').take(backslashes));
+                    backslashes = 0;
+                }
+            }
+            if c != '\\' {
+                quoted.push(c);
+            }
+        }
+        quoted.extend(std::iter::repeat('\\').take(2 * backslashes));
+        quoted.push('"');
+        quoted
+    }
+
+    pub fn set_jump_list(items: &[DockMenuItem]) {
+        let exe = match std::env::current_exe() {
+            Ok(exe) => exe,
+            Err(err) => {
+                log::warn!("Failed to find the executable for the jump list: {err}");
+                return;
+            }
+        };
+
+        // SAFETY: we follow the COM rules: the objects are only used on this thread,
+        // and released (by `ComPtr`) before returning.
+        unsafe {
+            // winit already initialized COM on this thread, so this is usually a no-op:
+            CoInitializeEx(null_mut(), COINIT_APARTMENTTHREADED);
+
+            let Some(list) = ComPtr::<ICustomDestinationList>::create::<DestinationList>() else {
+                log::warn!("Failed to create ICustomDestinationList");
+                return;
+            };
+
+            let mut min_slots = 0;
+            let mut removed = null_mut();
+            if !SUCCEEDED(list.BeginList(&mut min_slots, &IObjectArray::uuidof(), &mut removed)) {
+                log::warn!("Failed to begin the jump list");
+                return;
+            }
+            drop(ComPtr::<IObjectArray>::from_raw(removed));
+
+            if !items.is_empty() {
+                if let Some(tasks) = create_tasks(&exe, items) {
+                    list.AddUserTasks(tasks.as_ptr());
+                }
+            }
+
+            if !SUCCEEDED(list.CommitList()) {
+                log::warn!("Failed to commit the jump list");
+            }
+        }
+    }
+
+    /// SAFETY: COM must be initialized on this thread.
+    unsafe fn create_tasks(
+        exe: &Path,
+        items: &[DockMenuItem],
+    ) -> Option<ComPtr<IObjectCollection>> {
+        let tasks = ComPtr::<IObjectCollection>::create::<EnumerableObjectCollection>()?;
+        let exe = wide(exe);
+
+        for item in items {
+            let Some(link) = ComPtr::<IShellLinkW>::create::<ShellLink>() else {
+                continue;
+            };
+            let args: Vec<String> = item.args.iter().map(|arg| quote_arg(arg)).collect();
+            link.SetPath(exe.as_ptr());
+            link.SetArguments(wide(args.join(" ")).as_ptr());
+            link.SetIconLocation(exe.as_ptr(), 0);
+
+            let Some(properties) = link.query_interface::<IPropertyStore>() else {
+                continue;
+            };
+            let mut title = wide(&item.title);
+            let mut value: PROPVARIANT = std::mem::zeroed();
+            value.vt = VT_LPWSTR as u16;
+            *value.data.pwszVal_mut() = title.as_mut_ptr();
+            properties.SetValue(&PKEY_Title, &value);
+            properties.Commit();
+
+            tasks.AddObject(link.as_ptr());
+        }
+
+        Some(tasks)
+    }
+}
@@ -0,0 +1,208 @@
+//! Embed an eframe [`App`] in a window and event loop that you own, e.g. those of a game engine.
+//!
+//! [`EmbeddedApp`] gives you the parts of eframe that don't depend on how the window is created
+//! and painted: persistence (of the app and egui memory), following the system theme,
+//! closing logic and accessibility.
+//! You feed it input, and it gives you an [`egui::FullOutput`] to paint with your own renderer,
+//! e.g. `egui-wgpu` or `egui_glow`.
+//!
+//! ``` no_run
+//! # let window: &winit::window::Window = todo!();
+//! # fn gather_input() -> egui::RawInput { todo!() }
+//! # fn paint(_: Vec<egui::ClippedPrimitive>, _: egui::TexturesDelta) {}
+//! # fn my_app(_cc: &eframe::CreationContext<'_>) -> Box<dyn eframe::App> { todo!() }
+//! let mut app = eframe::embed::EmbeddedApp::new(
+//!     "my_engine_plugin",
+//!     &eframe::NativeOptions::default(),
+//!     window, // Or anything else with raw window handles
+//!     None,
+//!     Box::new(my_app),
+//! );
+//!
+//! while !app.should_close() {
+//!     let full_output = app.run(gather_input());
+//!     let clipped_primitives = app.egui_ctx().tessellate(full_output.shapes, full_output.pixels_per_point);
+//!     paint(clipped_primitives, full_output.textures_delta);
+//!     app.maybe_autosave();
+//! }
+//! app.save_and_exit();
+//! ```
+
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+
+use super::{epi_integration, epi_integration::EpiIntegration, winit_integration};
+use crate::{epi, App, AppCreator, NativeOptions, Theme};
+
+/// An eframe [`App`] running in a window you created yourself. See the [module docs](self).
+pub struct EmbeddedApp {
+    integration: EpiIntegration,
+    app: Box<dyn App>,
+}
+
+impl EmbeddedApp {
+    /// Create the app, restoring its state from storage if the `persistence` feature is enabled.
+    ///
+    /// The storage is named after [`egui::ViewportBuilder::app_id`], or else `app_name`.
+    ///
+    /// The [`NativeOptions`] that are about the window and event loop are ignored,
+    /// since you create those yourself.
+    /// The app gets no `gl` or `wgpu_render_state` in its [`crate::CreationContext`].
+    pub fn new(
+        app_name: &str,
+        native_options: &NativeOptions,
+        window: &(impl HasRawWindowHandle + HasRawDisplayHandle),
+        system_theme: Option<Theme>,
+        app_creator: AppCreator,
+    ) -> Self {
+        crate::profile_function!();
+
+        let storage = epi_integration::create_storage(
+            native_options
+                .viewport
+                .app_id
+                .as_deref()
+                .unwrap_or(app_name),
+        );
+        let egui_ctx = winit_integration::create_egui_context(storage.as_deref());
+
+        // We only have the one window, so show other viewports as egui windows within it:
+        egui_ctx.set_embed_viewports(true);
+
+        let system_theme = system_theme.filter(|_| native_options.follow_system_theme);
+        let theme = system_theme.unwrap_or(native_options.default_theme);
+        egui_ctx.set_visuals(theme.egui_visuals());
+
+        let integration = EpiIntegration::new(
+            egui_ctx.clone(),
+            window,
+            system_theme,
+            app_name,
+            native_options,
+            storage,
+            #[cfg(feature = "glow")]
+            None,
+            #[cfg(feature = "wgpu")]
+            None,
+            #[cfg(feature = "webview")]
+            None, // We don't own the window
+        );
+
+        let cc = epi::CreationContext {
+            egui_ctx,
+            integration_info: integration.frame.info().clone(),
+            storage: integration.frame.storage(),
+            #[cfg(feature = "glow")]
+            gl: None,
+            #[cfg(feature = "wgpu")]
+            wgpu_render_state: None,
+            raw_display_handle: window.raw_display_handle(),
+            raw_window_handle: window.raw_window_handle(),
+        };
+        let app = {
+            crate::profile_scope!("user_app_creator");
+            app_creator(&cc)
+        };
+
+        Self { integration, app }
+    }
+
+    /// The context of the app, e.g. for [`egui::Context::tessellate`].
+    pub fn egui_ctx(&self) -> &egui::Context {
+        &self.integration.egui_ctx
+    }
+
+    /// The app, if you need to reach it.
+    pub fn app(&mut self) -> &mut dyn App {
+        self.app.as_mut()
+    }
+
+    /// Give a [`winit`] window event to the app, if you use [`winit`] and [`egui_winit`].
+    ///
+    /// This also handles theme changes, and on Mac Cmd-Q if [`NativeOptions::intercept_quit`] is set.
+    pub fn on_window_event(
+        &mut self,
+        window: &winit::window::Window,
+        egui_winit: &mut egui_winit::State,
+        event: &winit::event::WindowEvent,
+    ) -> egui_winit::EventResponse {
+        self.integration.on_window_event(window, egui_winit, event)
+    }
+
+    /// Start [`accesskit`](https://accesskit.dev/) support, if you use [`winit`] and [`egui_winit`].
+    ///
+    /// The action requests of screen readers are sent to your event loop,
+    /// and should be given to [`egui_winit::State::on_accesskit_action_request`].
+    #[cfg(feature = "accesskit")]
+    pub fn init_accesskit<E: From<egui_winit::accesskit_winit::ActionRequestEvent> + Send>(
+        &self,
+        egui_winit: &mut egui_winit::State,
+        window: &winit::window::Window,
+        event_loop_proxy: winit::event_loop::EventLoopProxy<E>,
+    ) {
+        self.integration
+            .init_accesskit(egui_winit, window, event_loop_proxy);
+    }
+
+    /// The theme of the operating system changed.
+    ///
+    /// Ignored unless [`NativeOptions::follow_system_theme`] is set.
+    /// With [`Self::on_window_event`] this is done for you.
+    pub fn on_system_theme_changed(&mut self, theme: Theme) {
+        self.integration.set_system_theme(theme);
+    }
+
+    /// Run the app for one frame, calling [`App::update`].
+    ///
+    /// Paint the returned shapes with [`egui::Context::tessellate`] and your renderer,
+    /// and carry out the [`egui::PlatformOutput`] and viewport commands you support,
+    /// e.g. with [`egui_winit::State::handle_platform_output`].
+    ///
+    /// Only the root viewport is supported, so other viewports are embedded in it,
+    /// see [`egui::Context::set_embed_viewports`].
+    pub fn run(&mut self, raw_input: egui::RawInput) -> egui::FullOutput {
+        crate::profile_function!();
+
+        self.integration.pre_update();
+        let full_output = self.integration.update(self.app.as_mut(), None, raw_input);
+        self.integration.post_update();
+
+        if !self.integration.frame.msaa_requests.is_empty() {
+            self.integration.frame.msaa_requests.clear();
+            log::warn!("Changing the multisampling is not supported for embedded apps");
+        }
+
+        full_output
+    }
+
+    /// The user or the app asked to close, and it wasn't canceled.
+    ///
+    /// Call [`Self::save_and_exit`] and close your window.
+    pub fn should_close(&self) -> bool {
+        self.integration.should_close()
+    }
+
+    /// Save the app state if [`App::auto_save_interval`] has passed since last time.
+    ///
+    /// Call this once per frame.
+    pub fn maybe_autosave(&mut self) {
+        self.integration.maybe_autosave(self.app.as_mut(), None);
+    }
+
+    /// Save the app state now.
+    pub fn save(&mut self) {
+        self.integration.save(self.app.as_mut(), None);
+    }
+
+    /// Save the app state, and call [`App::on_exit`].
+    pub fn save_and_exit(mut self) {
+        crate::profile_function!();
+
+        self.save();
+
+        #[cfg(feature = "glow")]
+        self.app.on_exit(None);
+
+        #[cfg(not(feature = "glow"))]
+        self.app.on_exit();
+    }
+}
@@ -4,9 +4,9 @@ use std::time::Instant;
 
 use winit::event_loop::EventLoopWindowTarget;
 
-use raw_window_handle::{HasRawDisplayHandle as _, HasRawWindowHandle as _};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 
-use egui::{DeferredViewportUiCallback, NumExt as _, ViewportBuilder, ViewportId};
+use egui::{DeferredViewportUiCallback, NumExt as _, ViewportBuilder, ViewportId, ViewportIdSet};
 use egui_winit::{EventResponse, WindowSettings};
 
 use crate::{epi, Theme};
@@ -47,6 +47,14 @@ pub fn viewport_builder<E>(
         viewport_builder.inner_size
     };
 
+    // A minimum size larger than every monitor would make the window impossible to fit on any of them:
+    #[cfg(not(target_os = "ios"))]
+    if let Some(min_inner_size) = viewport_builder.min_inner_size {
+        let largest_monitor_size = largest_monitor_point_size(egui_zoom_factor, event_loop);
+        viewport_builder =
+            viewport_builder.with_min_inner_size(min_inner_size.at_most(largest_monitor_size));
+    }
+
     #[cfg(not(target_os = "ios"))]
     if native_options.centered {
         crate::profile_scope!("center");
@@ -111,6 +119,49 @@ fn largest_monitor_point_size<E>(
 // ----------------------------------------------------------------------------
 
 /// For loading/saving app state and/or egui memory to disk.
+/// Tell the app about the viewports that were added or removed during a frame,
+/// see [`epi::App::on_viewport_created`] and [`epi::App::on_viewport_destroyed`].
+///
+/// `viewports_before` are the ids of the viewports at the start of the frame,
+/// and `viewports_after` the viewports that are left at the end of it.
+pub fn notify_viewport_changes<'a>(
+    app: &mut dyn epi::App,
+    viewports_before: &ViewportIdSet,
+    viewports_after: impl Iterator<Item = (ViewportId, &'a ViewportBuilder)>,
+) {
+    let mut remaining = ViewportIdSet::default();
+    for (viewport_id, builder) in viewports_after {
+        if !viewports_before.contains(&viewport_id) {
+            app.on_viewport_created(viewport_id, builder);
+        }
+        remaining.insert(viewport_id);
+    }
+    for &viewport_id in viewports_before {
+        if !remaining.contains(&viewport_id) {
+            app.on_viewport_destroyed(viewport_id);
+        }
+    }
+}
+
+/// A viewport that was open when the app was saved, see [`crate::NativeOptions::restore_viewports`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+struct SavedViewport {
+    id: ViewportId,
+    title: Option<String>,
+    window: WindowSettings,
+}
+
+impl SavedViewport {
+    fn into_builder(self) -> (ViewportId, ViewportBuilder) {
+        let mut builder = ViewportBuilder::default();
+        if let Some(title) = self.title {
+            builder = builder.with_title(title);
+        }
+        (self.id, self.window.initialize_viewport_builder(builder))
+    }
+}
+
 pub fn create_storage(_app_name: &str) -> Option<Box<dyn epi::Storage>> {
     #[cfg(feature = "persistence")]
     if let Some(storage) = super::file_storage::FileStorage::from_app_id(_app_name) {
@@ -136,25 +187,58 @@ pub struct EpiIntegration {
     /// When set, it is time to close the native window.
     close: bool,
 
+    /// See [`crate::NativeOptions::intercept_quit`].
+    intercept_quit: bool,
+
+    /// The user pressed Cmd-Q, and the app hasn't been asked about it yet.
+    quit_requested: bool,
+
+    /// See [`crate::NativeOptions::restore_viewports`].
+    restore_viewports: bool,
+
+    /// Viewports from the previous session, to pass to [`epi::App::recreate_viewport`].
+    viewports_to_restore: Vec<SavedViewport>,
+
+    /// The viewports that are open right now (except the root), so we can save them.
+    open_viewports: Vec<SavedViewport>,
+
     can_drag_window: bool,
     follow_system_theme: bool,
     #[cfg(feature = "persistence")]
     persist_window: bool,
     app_icon_setter: super::app_icon::AppTitleIconSetter,
+
+    #[cfg(feature = "gamepad")]
+    gamepads: egui_winit::gamepad::Gamepads,
+
+    /// See [`crate::NativeOptions::webview_backend`].
+    #[cfg(feature = "webview")]
+    webview_backend: Option<Box<dyn epi::WebViewBackend>>,
+
+    /// Kept alive while the app runs, see [`crate::NativeOptions::puffin_server_addr`].
+    #[cfg(feature = "puffin_http")]
+    _puffin_server: Option<puffin_http::Server>,
 }
 
 impl EpiIntegration {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         egui_ctx: egui::Context,
-        window: &winit::window::Window,
+        window: &(impl HasRawWindowHandle + HasRawDisplayHandle),
         system_theme: Option<Theme>,
         app_name: &str,
         native_options: &crate::NativeOptions,
         storage: Option<Box<dyn epi::Storage>>,
         #[cfg(feature = "glow")] gl: Option<std::sync::Arc<glow::Context>>,
         #[cfg(feature = "wgpu")] wgpu_render_state: Option<egui_wgpu::RenderState>,
+        #[cfg(feature = "webview")] webview_backend: Option<Box<dyn epi::WebViewBackend>>,
     ) -> Self {
+        let viewports_to_restore = if native_options.restore_viewports {
+            load_viewports(storage.as_deref()).unwrap_or_default()
+        } else {
+            vec![]
+        };
+
         let frame = epi::Frame {
             info: epi::IntegrationInfo {
                 system_theme,
@@ -165,6 +249,12 @@ impl EpiIntegration {
             gl,
             #[cfg(feature = "wgpu")]
             wgpu_render_state,
+            close_requested: false,
+            close_canceled: false,
+            taskbar_progress: None,
+            msaa_requests: Vec::new(),
+            dock_badge: None,
+            dock_menu: None,
             raw_display_handle: window.raw_display_handle(),
             raw_window_handle: window.raw_window_handle(),
         };
@@ -184,12 +274,30 @@ impl EpiIntegration {
             Some(icon),
         );
 
+        #[cfg(feature = "persistence")]
+        record_or_replay_input_from_env(&egui_ctx);
+
+        #[cfg(feature = "puffin_http")]
+        let _puffin_server = native_options
+            .puffin_server_addr
+            .as_deref()
+            .and_then(start_puffin_server);
+
         Self {
             frame,
             last_auto_save: Instant::now(),
+            #[cfg(feature = "gamepad")]
+            gamepads: egui_winit::gamepad::Gamepads::new(egui_ctx.clone()),
+            #[cfg(feature = "webview")]
+            webview_backend,
             egui_ctx,
             pending_full_output: Default::default(),
             close: false,
+            intercept_quit: native_options.intercept_quit,
+            quit_requested: false,
+            restore_viewports: native_options.restore_viewports,
+            viewports_to_restore,
+            open_viewports: vec![],
             can_drag_window: false,
             follow_system_theme: native_options.follow_system_theme,
             #[cfg(feature = "persistence")]
@@ -198,6 +306,8 @@ impl EpiIntegration {
             beginning: Instant::now(),
             is_first_frame: true,
             frame_start: Instant::now(),
+            #[cfg(feature = "puffin_http")]
+            _puffin_server,
         }
     }
 
@@ -237,6 +347,19 @@ impl EpiIntegration {
 
         use winit::event::{ElementState, MouseButton, WindowEvent};
 
+        #[cfg(feature = "webview")]
+        if let (
+            Some(webview_backend),
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                ..
+            },
+        ) = (&mut self.webview_backend, event)
+        {
+            // The web views get their own mouse input, so the click was on egui:
+            webview_backend.focus_parent(window.id());
+        }
+
         match event {
             WindowEvent::Destroyed => {
                 log::debug!("Received WindowEvent::Destroyed");
@@ -247,10 +370,23 @@ impl EpiIntegration {
                 state: ElementState::Pressed,
                 ..
             } => self.can_drag_window = true,
-            WindowEvent::ThemeChanged(winit_theme) if self.follow_system_theme => {
-                let theme = theme_from_winit_theme(*winit_theme);
-                self.frame.info.system_theme = Some(theme);
-                self.egui_ctx.set_visuals(theme.egui_visuals());
+            WindowEvent::KeyboardInput { event, .. }
+                if self.intercept_quit
+                    && cfg!(target_os = "macos")
+                    && event.state == ElementState::Pressed
+                    && egui_winit.egui_input().modifiers.mac_cmd
+                    && event.logical_key == winit::keyboard::Key::Character("q".into()) =>
+            {
+                log::debug!("Cmd-Q pressed - asking the app to close");
+                self.quit_requested = true;
+                self.egui_ctx.request_repaint_of(ViewportId::ROOT);
+                return EventResponse {
+                    repaint: true,
+                    consumed: true,
+                };
+            }
+            WindowEvent::ThemeChanged(winit_theme) => {
+                self.set_system_theme(theme_from_winit_theme(*winit_theme));
             }
             _ => {}
         }
@@ -258,6 +394,17 @@ impl EpiIntegration {
         egui_winit.on_window_event(window, event)
     }
 
+    /// The theme of the operating system changed.
+    ///
+    /// Ignored unless [`crate::NativeOptions::follow_system_theme`] is set.
+    pub fn set_system_theme(&mut self, theme: Theme) {
+        if !self.follow_system_theme {
+            return;
+        }
+        self.frame.info.system_theme = Some(theme);
+        self.egui_ctx.set_visuals(theme.egui_visuals());
+    }
+
     pub fn pre_update(&mut self) {
         self.frame_start = Instant::now();
         self.app_icon_setter.update();
@@ -272,11 +419,32 @@ impl EpiIntegration {
         viewport_ui_cb: Option<&DeferredViewportUiCallback>,
         mut raw_input: egui::RawInput,
     ) -> egui::FullOutput {
+        crate::trace_span!("update", viewport = ?raw_input.viewport_id);
+
         raw_input.time = Some(self.beginning.elapsed().as_secs_f64());
 
-        let close_requested = raw_input.viewport().close_requested();
+        #[cfg(feature = "gamepad")]
+        if viewport_ui_cb.is_none() {
+            // Gamepads aren't tied to a window, so they always control the root viewport.
+            raw_input.events.extend(self.gamepads.take_events());
+        }
 
-        let full_output = self.egui_ctx.run(raw_input, |egui_ctx| {
+        let is_root_viewport = viewport_ui_cb.is_none();
+        let close_requested = is_root_viewport
+            && (raw_input.viewport().close_requested() || std::mem::take(&mut self.quit_requested));
+        if is_root_viewport {
+            self.frame.close_requested = close_requested;
+            self.frame.close_canceled = false;
+        }
+
+        if is_root_viewport {
+            for saved in std::mem::take(&mut self.viewports_to_restore) {
+                let (viewport_id, builder) = saved.into_builder();
+                app.recreate_viewport(viewport_id, builder);
+            }
+        }
+
+        let mut full_output = self.egui_ctx.run(raw_input, |egui_ctx| {
             if let Some(viewport_ui_cb) = viewport_ui_cb {
                 // Child viewport
                 crate::profile_scope!("viewport_callback");
@@ -287,15 +455,30 @@ impl EpiIntegration {
             }
         });
 
-        let is_root_viewport = viewport_ui_cb.is_none();
-        if is_root_viewport && close_requested {
-            let canceled = full_output.viewport_output[&ViewportId::ROOT]
-                .commands
-                .contains(&egui::ViewportCommand::CancelClose);
+        if let Some(progress) = self.frame.taskbar_progress.take() {
+            if let Some(root_output) = full_output.viewport_output.get_mut(&ViewportId::ROOT) {
+                root_output
+                    .commands
+                    .push(egui::ViewportCommand::TaskbarProgress(progress));
+            }
+        }
+
+        if let Some(badge) = self.frame.dock_badge.take() {
+            super::dock::set_badge(Some(badge.as_str()).filter(|badge| !badge.is_empty()));
+        }
+        if let Some(items) = self.frame.dock_menu.take() {
+            super::dock::set_menu(&items);
+        }
+
+        if close_requested {
+            let canceled = self.frame.close_canceled
+                || full_output.viewport_output[&ViewportId::ROOT]
+                    .commands
+                    .contains(&egui::ViewportCommand::CancelClose);
             if canceled {
-                log::debug!("Closing of root viewport canceled with ViewportCommand::CancelClose");
+                log::debug!("Closing of root viewport canceled");
             } else {
-                log::debug!("Closing root viewport (ViewportCommand::CancelClose was not sent)");
+                log::debug!("Closing root viewport (the close was not canceled)");
                 self.close = true;
             }
         }
@@ -304,6 +487,33 @@ impl EpiIntegration {
         std::mem::take(&mut self.pending_full_output)
     }
 
+    /// Show the web views that the ui of the viewport asked for, see [`crate::WebViewBackend`].
+    #[cfg(feature = "webview")]
+    pub fn update_webviews(
+        &mut self,
+        viewport_id: ViewportId,
+        window: &winit::window::Window,
+        pixels_per_point: f32,
+        webviews: &[egui::WebViewOutput],
+    ) {
+        if let Some(webview_backend) = &mut self.webview_backend {
+            crate::profile_function!();
+            webview_backend.update(window, pixels_per_point, webviews);
+            if let Some(interval) = webview_backend.update_interval() {
+                self.egui_ctx
+                    .request_repaint_after_for(interval, viewport_id);
+            }
+        }
+    }
+
+    /// Close the web views of the viewports that have been closed.
+    #[cfg(feature = "webview")]
+    pub fn retain_webviews(&mut self, window_ids: &[winit::window::WindowId]) {
+        if let Some(webview_backend) = &mut self.webview_backend {
+            webview_backend.retain_windows(window_ids);
+        }
+    }
+
     pub fn post_update(&mut self) {
         let frame_time = self.frame_start.elapsed().as_secs_f64() as f32;
         self.frame.info.cpu_usage = Some(frame_time);
@@ -332,6 +542,26 @@ impl EpiIntegration {
         }
     }
 
+    /// Remember the viewports that are currently open,
+    /// so they can be saved for [`crate::NativeOptions::restore_viewports`].
+    pub fn record_open_viewports<'a>(
+        &mut self,
+        viewports: impl Iterator<Item = (ViewportId, &'a ViewportBuilder, &'a winit::window::Window)>,
+    ) {
+        if !self.restore_viewports {
+            return;
+        }
+        let zoom_factor = self.egui_ctx.zoom_factor();
+        self.open_viewports = viewports
+            .filter(|(id, _, _)| *id != ViewportId::ROOT)
+            .map(|(id, builder, window)| SavedViewport {
+                id,
+                title: builder.title.clone(),
+                window: WindowSettings::from_window(zoom_factor, window),
+            })
+            .collect();
+    }
+
     #[allow(clippy::unused_self)]
     pub fn save(&mut self, _app: &mut dyn epi::App, _window: Option<&winit::window::Window>) {
         #[cfg(feature = "persistence")]
@@ -341,13 +571,27 @@ impl EpiIntegration {
             if let Some(window) = _window {
                 if self.persist_window {
                     crate::profile_scope!("native_window");
-                    epi::set_value(
-                        storage,
-                        STORAGE_WINDOW_KEY,
-                        &WindowSettings::from_window(self.egui_ctx.zoom_factor(), window),
-                    );
+                    let window_settings =
+                        WindowSettings::from_window(self.egui_ctx.zoom_factor(), window);
+                    epi::set_value(storage, STORAGE_WINDOW_KEY, &window_settings);
+
+                    // Also remember where the window was for this particular monitor setup:
+                    if let Some(monitor_layout) = window_settings.monitor_layout() {
+                        let mut per_monitor_layout: WindowSettingsPerMonitorLayout =
+                            epi::get_value(storage, STORAGE_WINDOW_PER_MONITOR_LAYOUT_KEY)
+                                .unwrap_or_default();
+                        per_monitor_layout.insert(monitor_layout, window_settings);
+                        epi::set_value(
+                            storage,
+                            STORAGE_WINDOW_PER_MONITOR_LAYOUT_KEY,
+                            &per_monitor_layout,
+                        );
+                    }
                 }
             }
+            if self.restore_viewports {
+                epi::set_value(storage, STORAGE_VIEWPORTS_KEY, &self.open_viewports);
+            }
             if _app.persist_egui_memory() {
                 crate::profile_scope!("egui_memory");
                 self.egui_ctx
@@ -364,6 +608,39 @@ impl EpiIntegration {
     }
 }
 
+/// Support `EFRAME_RECORD_INPUT=path` and `EFRAME_REPLAY_INPUT=path`,
+/// see [`egui::Context::record_input`] and [`egui::Context::replay_input`].
+#[cfg(feature = "persistence")]
+fn record_or_replay_input_from_env(egui_ctx: &egui::Context) {
+    if let Ok(path) = std::env::var("EFRAME_REPLAY_INPUT") {
+        match egui_ctx.replay_input(&path) {
+            Ok(()) => log::info!("Replaying input from {path:?}"),
+            Err(err) => log::error!("Failed to replay input from {path:?}: {err}"),
+        }
+    }
+    if let Ok(path) = std::env::var("EFRAME_RECORD_INPUT") {
+        match egui_ctx.record_input(&path) {
+            Ok(()) => log::info!("Recording input to {path:?}"),
+            Err(err) => log::error!("Failed to record input to {path:?}: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "puffin_http")]
+fn start_puffin_server(addr: &str) -> Option<puffin_http::Server> {
+    puffin::set_scopes_on(true);
+    match puffin_http::Server::new(addr) {
+        Ok(server) => {
+            log::info!("Started puffin server - connect with: puffin_viewer --url {addr}");
+            Some(server)
+        }
+        Err(err) => {
+            log::error!("Failed to start puffin server on {addr}: {err}");
+            None
+        }
+    }
+}
+
 fn load_default_egui_icon() -> egui::IconData {
     crate::profile_function!();
     crate::icon_data::from_png_bytes(&include_bytes!("../../data/icon.png")[..]).unwrap()
@@ -375,11 +652,44 @@ const STORAGE_EGUI_MEMORY_KEY: &str = "egui";
 #[cfg(feature = "persistence")]
 const STORAGE_WINDOW_KEY: &str = "window";
 
-pub fn load_window_settings(_storage: Option<&dyn epi::Storage>) -> Option<WindowSettings> {
+#[cfg(feature = "persistence")]
+const STORAGE_VIEWPORTS_KEY: &str = "viewports";
+
+/// The window settings of the root viewport, by [`egui_winit::monitor_layout_hash`].
+#[cfg(feature = "persistence")]
+const STORAGE_WINDOW_PER_MONITOR_LAYOUT_KEY: &str = "window_per_monitor_layout";
+
+#[cfg(feature = "persistence")]
+type WindowSettingsPerMonitorLayout = std::collections::BTreeMap<u64, WindowSettings>;
+
+fn load_viewports(_storage: Option<&dyn epi::Storage>) -> Option<Vec<SavedViewport>> {
     crate::profile_function!();
     #[cfg(feature = "persistence")]
     {
-        epi::get_value(_storage?, STORAGE_WINDOW_KEY)
+        epi::get_value(_storage?, STORAGE_VIEWPORTS_KEY)
+    }
+    #[cfg(not(feature = "persistence"))]
+    None
+}
+
+/// Load the window settings saved for the given [`egui_winit::monitor_layout_hash`].
+///
+/// If the window was never used with these monitors, we fall back to where it was last,
+/// which [`WindowSettings::clamp_position_to_monitors`] then moves onto a visible monitor.
+pub fn load_window_settings(
+    _storage: Option<&dyn epi::Storage>,
+    _monitor_layout: u64,
+) -> Option<WindowSettings> {
+    crate::profile_function!();
+    #[cfg(feature = "persistence")]
+    {
+        let storage = _storage?;
+        epi::get_value::<WindowSettingsPerMonitorLayout>(
+            storage,
+            STORAGE_WINDOW_PER_MONITOR_LAYOUT_KEY,
+        )
+        .and_then(|mut per_monitor_layout| per_monitor_layout.remove(&_monitor_layout))
+        .or_else(|| epi::get_value(storage, STORAGE_WINDOW_KEY))
     }
     #[cfg(not(feature = "persistence"))]
     None
@@ -19,6 +19,30 @@ pub fn storage_dir(app_id: &str) -> Option<PathBuf> {
         .map(|proj_dirs| proj_dirs.data_dir().to_path_buf())
 }
 
+/// Write `bytes` to a new file called `file_name` in the download directory of the user.
+///
+/// See [`crate::Frame::download`].
+pub(crate) fn download(bytes: &[u8], file_name: &str) -> Result<(), String> {
+    let dir = directories_next::UserDirs::new()
+        .and_then(|dirs| dirs.download_dir().map(Path::to_path_buf))
+        .ok_or("Failed to find the download directory")?;
+
+    // Only use the last component, so the file can't end up outside the download directory:
+    let path = Path::new(file_name)
+        .file_name()
+        .map(|name| dir.join(name))
+        .ok_or_else(|| format!("Invalid file name {file_name:?}"))?;
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(bytes))
+        .map_err(|err| format!("Failed to write {path:?}: {err}"))?;
+    log::debug!("Downloaded {path:?}");
+    Ok(())
+}
+
 // ----------------------------------------------------------------------------
 
 /// A key-value store backed by a [RON](https://github.com/ron-rs/ron) file on disk.
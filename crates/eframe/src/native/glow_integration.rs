@@ -36,35 +36,34 @@ use crate::{
 };
 
 use super::{
-    winit_integration::{EventResult, UserEvent, WinitApp},
+    winit_integration::{bring_to_front, EventResult, UserEvent, WinitApp},
     *,
 };
 
-// Note: that the current Glutin API design tightly couples the GL context with
-// the Window which means it's not practically possible to just destroy the
-// window and re-create a new window while continuing to use the same GL context.
-//
-// For now this means it's not possible to support Android as well as we can with
-// wgpu because we're basically forced to destroy and recreate _everything_ when
-// the application suspends and resumes.
-//
-// There is work in progress to improve the Glutin API so it has a separate Surface
-// API that would allow us to just destroy a Window/Surface when suspending, see:
-// https://github.com/rust-windowing/glutin/pull/1435
+// Note: on Android all windows and surfaces are destroyed when the application suspends,
+// and re-created when it resumes. The GL context usually survives this, but if it doesn't
+// we create a new one, along with a new painter, and let the app know with
+// `App::on_recreate_gpu` so it can re-create its own GPU resources.
 
 // ----------------------------------------------------------------------------
 // Types:
 
 pub struct GlowWinitApp {
     repaint_proxy: Arc<egui::mutex::Mutex<EventLoopProxy<UserEvent>>>,
+
+    /// See [`WinitApp::set_app_index`].
+    app_index: usize,
+
     app_name: String,
     native_options: NativeOptions,
     running: Option<GlowWinitRunning>,
 
-    // Note that since this `AppCreator` is FnOnce we are currently unable to support
-    // re-initializing the `GlowWinitRunning` state on Android if the application
-    // suspends and resumes.
+    // Note that since this `AppCreator` is FnOnce we never re-create the app.
+    // Instead the `GlowWinitRunning` state survives suspend/resume on Android.
     app_creator: Option<AppCreator>,
+
+    /// Deep links and files to open, waiting for the root viewport to be created.
+    pending_events: Vec<egui::Event>,
 }
 
 /// State that is initialized when the application is first starts running via
@@ -110,6 +109,9 @@ struct GlutinWindowContext {
     window_from_viewport: ViewportIdMap<WindowId>,
 
     focused_viewport: Option<ViewportId>,
+
+    /// Set when the GL context was lost (e.g. on Android resume) and we had to create a new one.
+    gl_context_recreated: bool,
 }
 
 struct Viewport {
@@ -119,6 +121,9 @@ struct Viewport {
     info: ViewportInfo,
     screenshot_requested: bool,
 
+    /// Set with [`crate::Frame::set_msaa`], see [`egui_glow::Painter::set_msaa_samples`].
+    msaa_samples: u32,
+
     /// The user-callback that shows the ui.
     /// None for immediate viewports.
     viewport_ui_cb: Option<Arc<DeferredViewportUiCallback>>,
@@ -140,12 +145,40 @@ impl GlowWinitApp {
         app_creator: AppCreator,
     ) -> Self {
         crate::profile_function!();
+        let pending_events = winit_integration::startup_events(&native_options, event_loop);
         Self {
             repaint_proxy: Arc::new(egui::mutex::Mutex::new(event_loop.create_proxy())),
+            app_index: 0,
             app_name: app_name.to_owned(),
             native_options,
             running: None,
             app_creator: Some(app_creator),
+            pending_events,
+        }
+    }
+
+    /// Give the pending events to the root viewport, if it has been created.
+    fn deliver_pending_events(&mut self) -> Option<Rc<Window>> {
+        let running = self.running.as_ref()?;
+        let mut glutin = running.glutin.borrow_mut();
+        let viewport = glutin.viewports.get_mut(&ViewportId::ROOT)?;
+        let (Some(window), Some(egui_winit)) = (&viewport.window, &mut viewport.egui_winit) else {
+            return None;
+        };
+        for event in self.pending_events.drain(..) {
+            egui_winit.push_event(event);
+        }
+        Some(window.clone())
+    }
+
+    /// The app was asked to open a URL or file: tell the app and show the root viewport.
+    fn on_open_request(&mut self, event: egui::Event) -> EventResult {
+        self.pending_events.push(event);
+        if let Some(window) = self.deliver_pending_events() {
+            bring_to_front(&window);
+            EventResult::RepaintNext(window.id())
+        } else {
+            EventResult::Wait
         }
     }
 
@@ -158,7 +191,10 @@ impl GlowWinitApp {
     ) -> Result<(GlutinWindowContext, egui_glow::Painter)> {
         crate::profile_function!();
 
-        let window_settings = epi_integration::load_window_settings(storage);
+        let window_settings = epi_integration::load_window_settings(
+            storage,
+            egui_winit::monitor_layout_hash(event_loop.available_monitors()),
+        );
 
         let winit_window_builder = epi_integration::viewport_builder(
             egui_ctx.zoom_factor(),
@@ -191,7 +227,8 @@ impl GlowWinitApp {
             }))
         };
 
-        let painter = egui_glow::Painter::new(gl, "", native_options.shader_version)?;
+        let mut painter = egui_glow::Painter::new(gl, "", native_options.shader_version)?;
+        painter.set_color_management(native_options.color_management);
 
         Ok((glutin_window_context, painter))
     }
@@ -231,6 +268,8 @@ impl GlowWinitApp {
         let system_theme =
             winit_integration::system_theme(&glutin.window(ViewportId::ROOT), &self.native_options);
 
+        #[cfg(feature = "webview")]
+        let webview_backend = self.native_options.webview_backend.take();
         let integration = EpiIntegration::new(
             egui_ctx,
             &glutin.window(ViewportId::ROOT),
@@ -241,10 +280,13 @@ impl GlowWinitApp {
             Some(gl.clone()),
             #[cfg(feature = "wgpu")]
             None,
+            #[cfg(feature = "webview")]
+            webview_backend,
         );
 
         {
             let event_loop_proxy = self.repaint_proxy.clone();
+            let app_index = self.app_index;
             integration
                 .egui_ctx
                 .set_request_repaint_callback(move |info| {
@@ -254,6 +296,7 @@ impl GlowWinitApp {
                     event_loop_proxy
                         .lock()
                         .send_event(UserEvent::RequestRepaint {
+                            app_index,
                             viewport_id: info.viewport_id,
                             when,
                             frame_nr,
@@ -276,6 +319,15 @@ impl GlowWinitApp {
             }
         }
 
+        #[cfg(target_os = "android")]
+        if let Some(egui_winit) = glutin
+            .viewports
+            .get_mut(&ViewportId::ROOT)
+            .and_then(|viewport| viewport.egui_winit.as_mut())
+        {
+            egui_winit.set_android_app(self.native_options.android_app.clone());
+        }
+
         let theme = system_theme.unwrap_or(self.native_options.default_theme);
         integration.egui_ctx.set_visuals(theme.egui_visuals());
 
@@ -352,6 +404,10 @@ impl GlowWinitApp {
 }
 
 impl WinitApp for GlowWinitApp {
+    fn set_app_index(&mut self, app_index: usize) {
+        self.app_index = app_index;
+    }
+
     fn frame_nr(&self, viewport_id: ViewportId) -> u64 {
         self.running
             .as_ref()
@@ -390,6 +446,11 @@ impl WinitApp for GlowWinitApp {
             .and_then(|r| r.glutin.borrow().window_from_viewport.get(&id).copied())
     }
 
+    fn viewport_id_from_window_id(&self, window_id: WindowId) -> Option<ViewportId> {
+        let glutin = self.running.as_ref()?.glutin.try_borrow().ok()?;
+        glutin.viewport_from_window.get(&window_id).copied()
+    }
+
     fn save_and_destroy(&mut self) {
         if let Some(mut running) = self.running.take() {
             crate::profile_function!();
@@ -428,16 +489,21 @@ impl WinitApp for GlowWinitApp {
 
                 let running = if let Some(running) = &mut self.running {
                     // Not the first resume event. Create all outstanding windows.
-                    running
-                        .glutin
-                        .borrow_mut()
-                        .initialize_all_windows(event_loop);
+                    let gl_context_recreated = {
+                        let mut glutin = running.glutin.borrow_mut();
+                        glutin.initialize_all_windows(event_loop);
+                        std::mem::take(&mut glutin.gl_context_recreated)
+                    };
+                    if gl_context_recreated {
+                        running.on_gl_context_recreated(self.native_options.shader_version)?;
+                    }
                     running
                 } else {
                     // First resume event. Created our root window etc.
                     self.init_run_state(event_loop)?
                 };
                 let window_id = running.glutin.borrow().window_from_viewport[&ViewportId::ROOT];
+                self.deliver_pending_events();
                 EventResult::RepaintNow(window_id)
             }
 
@@ -456,6 +522,51 @@ impl WinitApp for GlowWinitApp {
                 }
             }
 
+            winit::event::Event::DeviceEvent {
+                event: winit::event::DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                if let Some(running) = &self.running {
+                    let mut glutin = running.glutin.borrow_mut();
+                    // Device events are not tied to a window, so send them to the focused viewport:
+                    if let Some(viewport_id) = glutin.focused_viewport {
+                        if let Some(viewport) = glutin.viewports.get_mut(&viewport_id) {
+                            if let (Some(window), Some(egui_winit)) =
+                                (&viewport.window, &mut viewport.egui_winit)
+                            {
+                                egui_winit.on_mouse_motion(*delta);
+                                return Ok(EventResult::RepaintNext(window.id()));
+                            }
+                        }
+                    }
+                }
+                EventResult::Wait
+            }
+
+            winit::event::Event::UserEvent(UserEvent::InstanceArgs(args)) => {
+                if let Some(running) = &self.running {
+                    let mut glutin = running.glutin.borrow_mut();
+                    if let Some(viewport) = glutin.viewports.get_mut(&ViewportId::ROOT) {
+                        if let (Some(window), Some(egui_winit)) =
+                            (&viewport.window, &mut viewport.egui_winit)
+                        {
+                            egui_winit.push_event(egui::Event::InstanceArgs(args.clone()));
+                            bring_to_front(window);
+                            return Ok(EventResult::RepaintNext(window.id()));
+                        }
+                    }
+                }
+                EventResult::Wait
+            }
+
+            winit::event::Event::UserEvent(UserEvent::DeepLink(url)) => {
+                self.on_open_request(egui::Event::DeepLink(url.clone()))
+            }
+
+            winit::event::Event::UserEvent(UserEvent::OpenFile(path)) => {
+                self.on_open_request(egui::Event::OpenFile(path.clone()))
+            }
+
             #[cfg(feature = "accesskit")]
             winit::event::Event::UserEvent(UserEvent::AccessKitActionRequest(
                 accesskit_winit::ActionRequestEvent { request, window_id },
@@ -483,6 +594,38 @@ impl WinitApp for GlowWinitApp {
 }
 
 impl GlowWinitRunning {
+    /// The old GL context was lost, so everything on the GPU is gone.
+    ///
+    /// Create a new painter for the new context, and re-upload all textures.
+    #[allow(unsafe_code)]
+    fn on_gl_context_recreated(
+        &mut self,
+        shader_version: Option<egui_glow::ShaderVersion>,
+    ) -> Result<()> {
+        crate::profile_function!();
+
+        let gl = unsafe {
+            let glutin = self.glutin.borrow();
+            Arc::new(glow::Context::from_loader_function(|s| {
+                let s = std::ffi::CString::new(s)
+                    .expect("failed to construct C string from string for gl proc address");
+                glutin.get_proc_address(&s)
+            }))
+        };
+
+        let mut new_painter = egui_glow::Painter::new(gl.clone(), "", shader_version)?;
+        new_painter.set_color_management(self.painter.borrow().color_management());
+        let mut old_painter = std::mem::replace(&mut *self.painter.borrow_mut(), new_painter);
+        // The GL objects of the old painter died with the old context, so there is nothing to destroy:
+        old_painter.forget_gl_objects();
+
+        self.integration.frame.gl = Some(gl);
+        self.integration.egui_ctx.reupload_textures();
+        self.app.on_recreate_gpu(&mut self.integration.frame);
+
+        Ok(())
+    }
+
     fn run_ui_and_paint(
         &mut self,
         event_loop: &EventLoopWindowTarget<UserEvent>,
@@ -503,6 +646,14 @@ impl GlowWinitRunning {
         #[cfg(feature = "puffin")]
         puffin::GlobalProfiler::lock().new_frame();
 
+        // Each profiler frame is the painting of one viewport:
+        crate::profile_scope!("viewport", format!("{viewport_id:?}"));
+        crate::trace_span!(
+            "frame",
+            viewport = ?viewport_id,
+            frame_nr = self.integration.egui_ctx.frame_nr_for(viewport_id)
+        );
+
         {
             let glutin = self.glutin.borrow();
             let viewport = &glutin.viewports[&viewport_id];
@@ -519,6 +670,12 @@ impl GlowWinitRunning {
             }
         }
 
+        let mut timings = egui::FrameTimings::default();
+        let input_start = Instant::now();
+
+        let viewports_before: ViewportIdSet =
+            self.glutin.borrow().viewports.keys().copied().collect();
+
         let (raw_input, viewport_ui_cb) = {
             let mut glutin = self.glutin.borrow_mut();
             let egui_ctx = glutin.egui_ctx.clone();
@@ -542,12 +699,23 @@ impl GlowWinitRunning {
             (raw_input, viewport_ui_cb)
         };
 
+        timings.input = input_start.elapsed().as_secs_f32();
+
         let clear_color = self
             .app
             .clear_color(&self.integration.egui_ctx.style().visuals);
 
-        let has_many_viewports = self.glutin.borrow().viewports.len() > 1;
-        let clear_before_update = !has_many_viewports; // HACK: for some reason, an early clear doesn't "take" on Mac with multiple viewports.
+        let (has_many_viewports, msaa_samples) = {
+            let glutin = self.glutin.borrow();
+            let msaa_samples = glutin
+                .viewports
+                .get(&viewport_id)
+                .map_or(0, |viewport| viewport.msaa_samples);
+            (glutin.viewports.len() > 1, msaa_samples)
+        };
+        // HACK: for some reason, an early clear doesn't "take" on Mac with multiple viewports.
+        // The multisampled framebuffer is only bound while painting, so we can't clear it early either.
+        let clear_before_update = !has_many_viewports && msaa_samples <= 1;
 
         if clear_before_update {
             // clear before we call update, so users can paint between clear-color and egui windows:
@@ -566,18 +734,20 @@ impl GlowWinitRunning {
 
             change_gl_context(current_gl_context, gl_surface);
 
-            self.painter
-                .borrow()
-                .clear(screen_size_in_pixels, clear_color);
+            let mut painter = self.painter.borrow_mut();
+            painter.set_msaa_samples(msaa_samples);
+            painter.clear(screen_size_in_pixels, clear_color);
         }
 
         // ------------------------------------------------------------
         // The update function, which could call immediate viewports,
         // so make sure we don't hold any locks here required by the immediate viewports rendeer.
 
+        let update_start = Instant::now();
         let full_output =
             self.integration
                 .update(self.app.as_mut(), viewport_ui_cb.as_deref(), raw_input);
+        timings.update = update_start.elapsed().as_secs_f32();
 
         // ------------------------------------------------------------
 
@@ -606,16 +776,38 @@ impl GlowWinitRunning {
             ..
         } = &mut *glutin;
 
+        integration.post_update();
+        for (viewport_id, samples) in integration.frame.msaa_requests.drain(..) {
+            if let Some(viewport) = viewports.get_mut(&viewport_id) {
+                viewport.msaa_samples = samples.into();
+            }
+        }
+
         let viewport = viewports.get_mut(&viewport_id).unwrap();
         viewport.info.events.clear(); // they should have been processed
         let window = viewport.window.as_ref().unwrap();
         let gl_surface = viewport.gl_surface.as_ref().unwrap();
         let egui_winit = viewport.egui_winit.as_mut().unwrap();
+        #[cfg(feature = "webview")]
+        integration.update_webviews(
+            viewport_id,
+            window,
+            pixels_per_point,
+            &platform_output.webviews,
+        );
+        {
+            crate::trace_span!("platform_output");
+            egui_winit.handle_platform_output(window, platform_output);
+        }
 
-        integration.post_update();
-        egui_winit.handle_platform_output(window, platform_output);
+        let tessellate_start = Instant::now();
+        let clipped_primitives = {
+            crate::trace_span!("tessellate");
+            integration.egui_ctx.tessellate(shapes, pixels_per_point)
+        };
+        timings.tessellate = tessellate_start.elapsed().as_secs_f32();
 
-        let clipped_primitives = integration.egui_ctx.tessellate(shapes, pixels_per_point);
+        let paint_start = Instant::now();
 
         // We may need to switch contexts again, because of immediate viewports:
         change_gl_context(current_gl_context, gl_surface);
@@ -623,15 +815,19 @@ impl GlowWinitRunning {
         let screen_size_in_pixels: [u32; 2] = window.inner_size().into();
 
         if !clear_before_update {
+            painter.set_msaa_samples(viewport.msaa_samples);
             painter.clear(screen_size_in_pixels, clear_color);
         }
 
-        painter.paint_and_update_textures(
-            screen_size_in_pixels,
-            pixels_per_point,
-            &clipped_primitives,
-            &textures_delta,
-        );
+        {
+            crate::trace_span!("paint");
+            painter.paint_and_update_textures(
+                screen_size_in_pixels,
+                pixels_per_point,
+                &clipped_primitives,
+                &textures_delta,
+            );
+        }
 
         {
             let screenshot_requested = std::mem::take(&mut viewport.screenshot_requested);
@@ -648,6 +844,9 @@ impl GlowWinitRunning {
             integration.post_rendering(window);
         }
 
+        timings.paint = paint_start.elapsed().as_secs_f32();
+        let swap_start = Instant::now();
+
         {
             crate::profile_scope!("swap_buffers");
             if let Err(err) = gl_surface.swap_buffers(
@@ -658,6 +857,14 @@ impl GlowWinitRunning {
                 log::error!("swap_buffers failed: {err}");
             }
         }
+        egui_winit.on_frame_presented();
+
+        timings.swap = swap_start.elapsed().as_secs_f32();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(?timings, "Painted frame");
+        integration
+            .egui_ctx
+            .report_frame_timings(viewport_id, timings);
 
         // give it time to settle:
         #[cfg(feature = "__screenshot")]
@@ -678,6 +885,26 @@ impl GlowWinitRunning {
 
         glutin.handle_viewport_output(event_loop, &integration.egui_ctx, viewport_output);
 
+        epi_integration::notify_viewport_changes(
+            app.as_mut(),
+            &viewports_before,
+            glutin
+                .viewports
+                .iter()
+                .map(|(id, viewport)| (*id, &viewport.builder)),
+        );
+        integration.record_open_viewports(glutin.viewports.iter().filter_map(|(id, viewport)| {
+            Some((*id, &viewport.builder, viewport.window.as_deref()?))
+        }));
+        #[cfg(feature = "webview")]
+        integration.retain_webviews(
+            &glutin
+                .viewport_from_window
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+        );
+
         if integration.should_close() {
             EventResult::Exit
         } else {
@@ -808,6 +1035,41 @@ fn change_gl_context(
     *current_gl_context = Some(not_current.make_current(gl_surface).unwrap());
 }
 
+/// Create a GL context. If a core context cannot be created, try a GL ES context as fallback.
+#[allow(unsafe_code)]
+unsafe fn create_gl_context(
+    gl_config: &glutin::config::Config,
+    raw_window_handle: Option<raw_window_handle::RawWindowHandle>,
+) -> Result<glutin::context::NotCurrentContext> {
+    let context_attributes =
+        glutin::context::ContextAttributesBuilder::new().build(raw_window_handle);
+    let fallback_context_attributes = glutin::context::ContextAttributesBuilder::new()
+        .with_context_api(glutin::context::ContextApi::Gles(None))
+        .build(raw_window_handle);
+
+    let gl_context_result = unsafe {
+        crate::profile_scope!("create_context");
+        gl_config
+            .display()
+            .create_context(gl_config, &context_attributes)
+    };
+
+    match gl_context_result {
+        Ok(it) => Ok(it),
+        Err(err) => {
+            log::warn!("Failed to create context using default context attributes {context_attributes:?} due to error: {err}");
+            log::debug!(
+                "Retrying with fallback context attributes: {fallback_context_attributes:?}"
+            );
+            Ok(unsafe {
+                gl_config
+                    .display()
+                    .create_context(gl_config, &fallback_context_attributes)?
+            })
+        }
+    }
+}
+
 impl GlutinWindowContext {
     #[allow(unsafe_code)]
     unsafe fn new(
@@ -901,35 +1163,8 @@ impl GlutinWindowContext {
         let raw_window_handle = window.as_ref().map(|w| w.raw_window_handle());
         log::debug!("creating gl context using raw window handle: {raw_window_handle:?}");
 
-        // create gl context. if core context cannot be created, try gl es context as fallback.
-        let context_attributes =
-            glutin::context::ContextAttributesBuilder::new().build(raw_window_handle);
-        let fallback_context_attributes = glutin::context::ContextAttributesBuilder::new()
-            .with_context_api(glutin::context::ContextApi::Gles(None))
-            .build(raw_window_handle);
-
-        let gl_context_result = unsafe {
-            crate::profile_scope!("create_context");
-            gl_config
-                .display()
-                .create_context(&gl_config, &context_attributes)
-        };
-
-        let gl_context = match gl_context_result {
-            Ok(it) => it,
-            Err(err) => {
-                log::warn!("Failed to create context using default context attributes {context_attributes:?} due to error: {err}");
-                log::debug!(
-                    "Retrying with fallback context attributes: {fallback_context_attributes:?}"
-                );
-                unsafe {
-                    gl_config
-                        .display()
-                        .create_context(&gl_config, &fallback_context_attributes)?
-                }
-            }
-        };
-        let not_current_gl_context = Some(gl_context);
+        let not_current_gl_context =
+            Some(unsafe { create_gl_context(&gl_config, raw_window_handle)? });
 
         let mut viewport_from_window = HashMap::default();
         let mut window_from_viewport = ViewportIdMap::default();
@@ -950,6 +1185,7 @@ impl GlutinWindowContext {
                 builder: viewport_builder,
                 info,
                 screenshot_requested: false,
+                msaa_samples: 0,
                 viewport_ui_cb: None,
                 gl_surface: None,
                 window: window.map(Rc::new),
@@ -973,6 +1209,7 @@ impl GlutinWindowContext {
             max_texture_side: None,
             window_from_viewport,
             focused_viewport: Some(ViewportId::ROOT),
+            gl_context_recreated: false,
         };
 
         slf.initialize_window(ViewportId::ROOT, event_loop)?;
@@ -1075,7 +1312,17 @@ impl GlutinWindowContext {
                         .make_not_current()
                         .unwrap()
                 };
-            let current_gl_context = not_current_gl_context.make_current(&gl_surface)?;
+            let current_gl_context = match not_current_gl_context.make_current(&gl_surface) {
+                Ok(current_gl_context) => current_gl_context,
+                Err(err) if err.error_kind() == glutin::error::ErrorKind::ContextLost => {
+                    log::warn!("The GL context was lost ({err}). Creating a new one.");
+                    self.gl_context_recreated = true;
+                    let raw_window_handle = Some(window.raw_window_handle());
+                    unsafe { create_gl_context(&self.gl_config, raw_window_handle)? }
+                        .make_current(&gl_surface)?
+                }
+                Err(err) => return Err(err.into()),
+            };
 
             // try setting swap interval. but its not absolutely necessary, so don't panic on failure.
             log::trace!("made context current. setting swap interval for surface");
@@ -1105,6 +1352,9 @@ impl GlutinWindowContext {
             viewport.gl_surface = None;
             viewport.window = None;
         }
+        // The windows get new ids when they are re-created on resume:
+        self.viewport_from_window.clear();
+        self.window_from_viewport.clear();
         if let Some(current) = self.current_gl_context.take() {
             log::debug!("context is current, so making it non-current");
             self.not_current_gl_context = Some(current.make_not_current()?);
@@ -1245,6 +1495,7 @@ fn initialize_or_update_viewport<'vp>(
                 builder,
                 info: Default::default(),
                 screenshot_requested: false,
+                msaa_samples: 0,
                 viewport_ui_cb,
                 window: None,
                 egui_winit: None,
@@ -1305,6 +1556,8 @@ fn render_immediate_viewport(
         viewport_ui_cb,
     } = immediate_viewport;
 
+    crate::trace_span!("frame", viewport = ?ids.this, immediate = true);
+
     let viewport_id = ids.this;
 
     {
@@ -1433,6 +1686,7 @@ fn render_immediate_viewport(
             log::error!("swap_buffers failed: {err}");
         }
     }
+    egui_winit.on_frame_presented();
 
     egui_winit.handle_platform_output(window, platform_output);
 
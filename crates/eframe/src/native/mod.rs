@@ -1,6 +1,14 @@
 mod app_icon;
+#[cfg(target_os = "macos")]
+mod apple_events;
+pub(crate) mod dock;
+pub mod embed;
 mod epi_integration;
+pub(crate) mod multi_app;
+mod open_files;
 pub mod run;
+mod single_instance;
+pub(crate) mod url_scheme;
 
 /// File storage which can be used by native backends.
 #[cfg(feature = "persistence")]
@@ -11,5 +19,8 @@ pub(crate) mod winit_integration;
 #[cfg(feature = "glow")]
 mod glow_integration;
 
+#[cfg(feature = "wgpu")]
+mod render_thread_pool;
+
 #[cfg(feature = "wgpu")]
 mod wgpu_integration;
@@ -0,0 +1,297 @@
+//! Several independent apps sharing one event loop, see [`MultiApp`].
+
+use std::rc::Rc;
+
+use winit::{
+    event_loop::{EventLoop, EventLoopWindowTarget},
+    window::{Window, WindowId},
+};
+
+use egui::ViewportId;
+
+use super::{
+    epi_integration::EpiIntegration,
+    winit_integration::{EventResult, UserEvent, WinitApp},
+};
+use crate::{epi, Result};
+
+/// Run several independent eframe apps in one process, sharing a single event loop.
+///
+/// Each app has its own [`egui::Context`], windows and storage (based on its app name),
+/// as if it was started with [`crate::run_native`] on its own.
+/// This is useful for e.g. plugin hosts, where each plugin has its own app.
+///
+/// ``` no_run
+/// # fn plugin_app(_cc: &eframe::CreationContext<'_>) -> Box<dyn eframe::App> { todo!() }
+/// fn main() -> eframe::Result<()> {
+///     eframe::MultiApp::default()
+///         .with_app("first_plugin", eframe::NativeOptions::default(), Box::new(plugin_app))
+///         .with_app("second_plugin", eframe::NativeOptions::default(), Box::new(plugin_app))
+///         .run()
+/// }
+/// ```
+///
+/// The event loop is created with the [`epi::NativeOptions`] of the first app,
+/// so e.g. [`epi::NativeOptions::event_loop_builder`] and [`epi::NativeOptions::run_and_return`]
+/// are only used for that one.
+/// [`epi::NativeOptions::single_instance`] is not supported.
+#[derive(Default)]
+pub struct MultiApp {
+    apps: Vec<(String, epi::NativeOptions, epi::AppCreator)>,
+}
+
+impl MultiApp {
+    /// Add an app, with the same arguments as [`crate::run_native`].
+    #[inline]
+    pub fn with_app(
+        mut self,
+        app_name: &str,
+        native_options: epi::NativeOptions,
+        app_creator: epi::AppCreator,
+    ) -> Self {
+        self.apps
+            .push((app_name.to_owned(), native_options, app_creator));
+        self
+    }
+
+    /// Run all the apps until the last of them exits.
+    ///
+    /// # Errors
+    /// This fails if we fail to create the event loop, or to set up the graphics context of one of the apps.
+    pub fn run(self) -> Result<()> {
+        let mut apps = self.apps.into_iter();
+        let Some((first_name, mut first_options, first_creator)) = apps.next() else {
+            return Ok(());
+        };
+        let rest: Vec<_> = apps.collect();
+
+        #[cfg(not(target_os = "ios"))]
+        if first_options.run_and_return {
+            return super::run::with_event_loop(first_options, |event_loop, first_options| {
+                let multi_app = MultiWinitApp::new(
+                    event_loop,
+                    std::iter::once((first_name, first_options, first_creator)).chain(rest),
+                );
+                super::run::run_and_return(event_loop, multi_app)
+            })?;
+        }
+
+        let event_loop = super::run::create_event_loop(&mut first_options)?;
+        let multi_app = MultiWinitApp::new(
+            &event_loop,
+            std::iter::once((first_name, first_options, first_creator)).chain(rest),
+        );
+        super::run::run_and_exit(event_loop, multi_app)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Multiplexes the events of the event loop to the [`WinitApp`] that owns the window.
+struct MultiWinitApp {
+    /// By app index. `None` once that app has exited.
+    apps: Vec<Option<Box<dyn WinitApp>>>,
+}
+
+impl MultiWinitApp {
+    fn new(
+        event_loop: &EventLoop<UserEvent>,
+        apps: impl Iterator<Item = (String, epi::NativeOptions, epi::AppCreator)>,
+    ) -> Self {
+        let apps = apps
+            .enumerate()
+            .map(|(app_index, (app_name, mut native_options, app_creator))| {
+                if native_options.viewport.title.is_none() {
+                    native_options.viewport.title = Some(app_name.clone());
+                }
+                let mut app = create_winit_app(event_loop, &app_name, native_options, app_creator);
+                app.set_app_index(app_index);
+                Some(app)
+            })
+            .collect();
+        Self { apps }
+    }
+
+    fn running_apps(&self) -> impl Iterator<Item = &dyn WinitApp> {
+        self.apps.iter().flatten().map(|app| app.as_ref())
+    }
+
+    /// The index of the app that owns this window.
+    fn app_of_window(&self, window_id: WindowId) -> Option<usize> {
+        self.apps.iter().position(|app| {
+            app.as_ref()
+                .map_or(false, |app| app.window(window_id).is_some())
+        })
+    }
+
+    /// Give an event to one of the apps.
+    ///
+    /// An error only stops that app, since the others are independent of it.
+    fn on_app_event(
+        &mut self,
+        app_index: usize,
+        event_loop: &EventLoopWindowTarget<UserEvent>,
+        event: &winit::event::Event<UserEvent>,
+    ) -> EventResult {
+        let Some(app) = &mut self.apps[app_index] else {
+            return EventResult::Wait;
+        };
+        let event_result = app.on_event(event_loop, event).unwrap_or_else(|err| {
+            log::error!("Exiting app {app_index} because of error: {err} during event {event:?}");
+            EventResult::Exit
+        });
+        match event_result {
+            EventResult::Exit => self.exit_app(app_index),
+            event_result => event_result,
+        }
+    }
+
+    /// An app wants to exit: destroy it, and exit the event loop if it was the last one.
+    fn exit_app(&mut self, app_index: usize) -> EventResult {
+        if let Some(mut app) = self.apps[app_index].take() {
+            log::debug!("App {app_index} exited - saving its state…");
+            app.save_and_destroy();
+        }
+        if self.apps.iter().all(Option::is_none) {
+            EventResult::Exit
+        } else {
+            EventResult::Wait
+        }
+    }
+}
+
+fn create_winit_app(
+    event_loop: &EventLoop<UserEvent>,
+    app_name: &str,
+    native_options: epi::NativeOptions,
+    app_creator: epi::AppCreator,
+) -> Box<dyn WinitApp> {
+    match native_options.renderer {
+        #[cfg(feature = "glow")]
+        epi::Renderer::Glow => Box::new(super::glow_integration::GlowWinitApp::new(
+            event_loop,
+            app_name,
+            native_options,
+            app_creator,
+        )),
+
+        #[cfg(feature = "wgpu")]
+        epi::Renderer::Wgpu => Box::new(super::wgpu_integration::WgpuWinitApp::new(
+            event_loop,
+            app_name,
+            native_options,
+            app_creator,
+        )),
+    }
+}
+
+impl WinitApp for MultiWinitApp {
+    fn set_app_index(&mut self, _app_index: usize) {}
+
+    fn frame_nr(&self, viewport_id: ViewportId) -> u64 {
+        self.running_apps()
+            .find(|app| app.window_id_from_viewport_id(viewport_id).is_some())
+            .map_or(0, |app| app.frame_nr(viewport_id))
+    }
+
+    fn is_focused(&self, window_id: WindowId) -> bool {
+        self.running_apps().any(|app| app.is_focused(window_id))
+    }
+
+    fn integration(&self) -> Option<&EpiIntegration> {
+        self.running_apps().find_map(|app| app.integration())
+    }
+
+    fn window(&self, window_id: WindowId) -> Option<Rc<Window>> {
+        self.running_apps().find_map(|app| app.window(window_id))
+    }
+
+    fn window_id_from_viewport_id(&self, id: ViewportId) -> Option<WindowId> {
+        self.running_apps()
+            .find_map(|app| app.window_id_from_viewport_id(id))
+    }
+
+    fn viewport_id_from_window_id(&self, window_id: WindowId) -> Option<ViewportId> {
+        self.running_apps()
+            .find_map(|app| app.viewport_id_from_window_id(window_id))
+    }
+
+    fn window_to_repaint(
+        &self,
+        app_index: usize,
+        viewport_id: ViewportId,
+        frame_nr: u64,
+    ) -> Option<WindowId> {
+        self.apps
+            .get(app_index)?
+            .as_ref()?
+            .window_to_repaint(app_index, viewport_id, frame_nr)
+    }
+
+    fn save_and_destroy(&mut self) {
+        for app in self.apps.iter_mut().flatten() {
+            app.save_and_destroy();
+        }
+    }
+
+    fn run_ui_and_paint(
+        &mut self,
+        event_loop: &EventLoopWindowTarget<UserEvent>,
+        window_id: WindowId,
+    ) -> EventResult {
+        let Some(app_index) = self.app_of_window(window_id) else {
+            return EventResult::Wait;
+        };
+        let Some(app) = &mut self.apps[app_index] else {
+            return EventResult::Wait;
+        };
+        match app.run_ui_and_paint(event_loop, window_id) {
+            EventResult::Exit => self.exit_app(app_index),
+            event_result => event_result,
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        event_loop: &EventLoopWindowTarget<UserEvent>,
+        event: &winit::event::Event<UserEvent>,
+    ) -> Result<EventResult> {
+        let window_id = match event {
+            winit::event::Event::WindowEvent { window_id, .. } => Some(*window_id),
+            #[cfg(feature = "accesskit")]
+            winit::event::Event::UserEvent(UserEvent::AccessKitActionRequest(
+                egui_winit::accesskit_winit::ActionRequestEvent { window_id, .. },
+            )) => Some(*window_id),
+            _ => None,
+        };
+
+        if let Some(window_id) = window_id {
+            // Events of a window only go to the app that owns it:
+            return Ok(match self.app_of_window(window_id) {
+                Some(app_index) => self.on_app_event(app_index, event_loop, event),
+                None => EventResult::Wait,
+            });
+        }
+
+        // Everything else goes to all apps.
+        // We can only return one result, so we ask for the repaints of the others ourselves.
+        let mut combined_result = EventResult::Wait;
+        for app_index in 0..self.apps.len() {
+            match self.on_app_event(app_index, event_loop, event) {
+                EventResult::Wait => {}
+                EventResult::Exit => combined_result = EventResult::Exit,
+                event_result if combined_result == EventResult::Wait => {
+                    combined_result = event_result;
+                }
+                EventResult::RepaintNow(window_id)
+                | EventResult::RepaintNext(window_id)
+                | EventResult::RepaintAt(window_id, _) => {
+                    if let Some(window) = self.window(window_id) {
+                        window.request_redraw();
+                    }
+                }
+            }
+        }
+        Ok(combined_result)
+    }
+}
@@ -0,0 +1,46 @@
+//! Files the OS asks the app to open, see [`crate::NativeOptions::open_files`].
+//!
+//! * Windows and Linux start the app with the files as command line arguments.
+//!   With [`crate::NativeOptions::single_instance`] they are forwarded to the running instance.
+//! * Mac sends an `OpenDocuments` Apple event to the running app (starting it first if needed),
+//!   e.g. when a document is double-clicked or dropped on the dock icon.
+
+use std::path::{Path, PathBuf};
+
+use winit::event_loop::EventLoop;
+
+use super::winit_integration::UserEvent;
+use crate::epi;
+
+/// If this command line argument is the path of an existing file, return its full path.
+pub fn file_from_arg(arg: &str) -> Option<PathBuf> {
+    let path = Path::new(arg);
+    if path.is_file() {
+        Some(std::fs::canonicalize(path).unwrap_or_else(|_| path.to_owned()))
+    } else {
+        None
+    }
+}
+
+/// Start listening for files to open.
+///
+/// Returns the files in our own command line arguments,
+/// which should be delivered to the app once the root viewport is created.
+pub fn start(
+    native_options: &epi::NativeOptions,
+    event_loop: &EventLoop<UserEvent>,
+) -> Vec<PathBuf> {
+    if !native_options.open_files {
+        return vec![];
+    }
+
+    #[cfg(target_os = "macos")]
+    super::apple_events::handle_open_files(event_loop.create_proxy());
+    #[cfg(not(target_os = "macos"))]
+    let _ = event_loop;
+
+    std::env::args()
+        .skip(1)
+        .filter_map(|arg| file_from_arg(&arg))
+        .collect()
+}
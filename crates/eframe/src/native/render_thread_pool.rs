@@ -0,0 +1,195 @@
+//! Painting deferred viewports on their own threads, see [`crate::NativeOptions::render_thread_per_viewport`].
+//!
+//! The ui of every viewport still runs on the main thread, since [`egui::Context::run`]
+//! runs one frame at a time. Only the painting is moved to the render thread of the viewport,
+//! so that waiting for vsync in one window doesn't stall the event loop and the other windows.
+
+use std::{collections::hash_map::Entry, sync::Arc, thread::JoinHandle};
+
+use parking_lot::{Condvar, Mutex};
+
+use egui::{epaint::ClippedPrimitive, TexturesDelta, ViewportId, ViewportIdMap, ViewportIdSet};
+
+/// A frame of a viewport, ready to be painted.
+pub struct RenderJob {
+    pub pixels_per_point: f32,
+    pub clear_color: [f32; 4],
+    pub clipped_primitives: Vec<ClippedPrimitive>,
+    pub textures_delta: TexturesDelta,
+}
+
+/// One render thread for each deferred viewport, sharing the painter with the main thread.
+///
+/// All use of the painter is serialized by its lock.
+/// New textures are uploaded on the calling thread before a frame is queued,
+/// so that they are ready for whichever viewport is painted next.
+/// Textures are freed by the render thread after painting the frame that freed them.
+pub struct ViewportRenderThreadPool {
+    painter: Arc<Mutex<egui_wgpu::winit::Painter>>,
+    threads: ViewportIdMap<RenderThread>,
+}
+
+impl ViewportRenderThreadPool {
+    pub fn new(painter: Arc<Mutex<egui_wgpu::winit::Painter>>) -> Self {
+        Self {
+            painter,
+            threads: Default::default(),
+        }
+    }
+
+    /// Paint a frame of the viewport on its render thread, starting the thread if needed.
+    ///
+    /// If the previous frame hasn't been painted yet, it is replaced by this one.
+    pub fn paint(&mut self, viewport_id: ViewportId, mut job: RenderJob) {
+        crate::profile_function!();
+
+        if !job.textures_delta.set.is_empty() {
+            let render_state = self.painter.lock().render_state();
+            if let Some(render_state) = render_state {
+                crate::profile_scope!("update_textures");
+                let mut renderer = render_state.renderer.write();
+                for (id, image_delta) in std::mem::take(&mut job.textures_delta.set) {
+                    renderer.update_texture(
+                        &render_state.device,
+                        &render_state.queue,
+                        id,
+                        &image_delta,
+                    );
+                }
+            }
+        }
+
+        let thread = match self.threads.entry(viewport_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => match RenderThread::spawn(viewport_id, self.painter.clone()) {
+                Ok(thread) => entry.insert(thread),
+                Err(err) => {
+                    log::warn!("Failed to spawn render thread for {viewport_id:?}: {err}");
+                    job.paint(viewport_id, &mut self.painter.lock());
+                    return;
+                }
+            },
+        };
+
+        let (slot, condvar) = &*thread.slot;
+        let mut slot = slot.lock();
+        if let Some(skipped) = slot.job.take() {
+            // The textures freed by the skipped frame must still be freed:
+            job.textures_delta.free.extend(skipped.textures_delta.free);
+        }
+        slot.job = Some(job);
+        condvar.notify_all();
+    }
+
+    /// Block until the render thread of the viewport has painted all of its frames.
+    pub fn wait(&self, viewport_id: ViewportId) {
+        if let Some(thread) = self.threads.get(&viewport_id) {
+            thread.wait();
+        }
+    }
+
+    /// Stop the render threads of the viewports that are closed,
+    /// after they have painted their last frame.
+    ///
+    /// Must not be called while holding the painter lock.
+    pub fn gc_viewports(&mut self, active_viewports: &ViewportIdSet) {
+        self.threads.retain(|id, _| active_viewports.contains(id));
+    }
+}
+
+#[derive(Default)]
+struct Slot {
+    /// The next frame to paint.
+    job: Option<RenderJob>,
+
+    /// The render thread is painting a frame.
+    painting: bool,
+
+    /// The render thread should stop once it has painted [`Self::job`].
+    quit: bool,
+}
+
+struct RenderThread {
+    slot: Arc<(Mutex<Slot>, Condvar)>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl RenderThread {
+    fn spawn(
+        viewport_id: ViewportId,
+        painter: Arc<Mutex<egui_wgpu::winit::Painter>>,
+    ) -> std::io::Result<Self> {
+        let slot = Arc::new((Mutex::new(Slot::default()), Condvar::new()));
+        let join_handle = std::thread::Builder::new()
+            .name(format!("eframe_render_{viewport_id:?}"))
+            .spawn({
+                let slot = slot.clone();
+                move || render_loop(viewport_id, &painter, &slot)
+            })?;
+        Ok(Self {
+            slot,
+            join_handle: Some(join_handle),
+        })
+    }
+
+    fn wait(&self) {
+        let (slot, condvar) = &*self.slot;
+        let mut slot = slot.lock();
+        while slot.job.is_some() || slot.painting {
+            condvar.wait(&mut slot);
+        }
+    }
+}
+
+impl Drop for RenderThread {
+    fn drop(&mut self) {
+        {
+            let (slot, condvar) = &*self.slot;
+            slot.lock().quit = true;
+            condvar.notify_all();
+        }
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle.join().ok();
+        }
+    }
+}
+
+fn render_loop(
+    viewport_id: ViewportId,
+    painter: &Mutex<egui_wgpu::winit::Painter>,
+    slot: &(Mutex<Slot>, Condvar),
+) {
+    let (slot, condvar) = slot;
+    loop {
+        let job = {
+            let mut slot = slot.lock();
+            while slot.job.is_none() && !slot.quit {
+                condvar.wait(&mut slot);
+            }
+            let Some(job) = slot.job.take() else {
+                return; // quit
+            };
+            slot.painting = true;
+            job
+        };
+
+        job.paint(viewport_id, &mut painter.lock());
+
+        slot.lock().painting = false;
+        condvar.notify_all();
+    }
+}
+
+impl RenderJob {
+    fn paint(&self, viewport_id: ViewportId, painter: &mut egui_wgpu::winit::Painter) {
+        crate::profile_function!();
+        painter.paint_and_update_textures(
+            viewport_id,
+            self.pixels_per_point,
+            self.clear_color,
+            &self.clipped_primitives,
+            &self.textures_delta,
+            false,
+        );
+    }
+}
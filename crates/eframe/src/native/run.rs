@@ -1,6 +1,9 @@
 use std::{cell::RefCell, time::Instant};
 
-use winit::event_loop::{EventLoop, EventLoopBuilder};
+use winit::{
+    event_loop::{EventLoop, EventLoopBuilder, EventLoopWindowTarget},
+    window::WindowId,
+};
 
 use egui::epaint::ahash::HashMap;
 
@@ -20,6 +23,19 @@ fn create_event_loop_builder(
     crate::profile_function!();
     let mut event_loop_builder = winit::event_loop::EventLoopBuilder::with_user_event();
 
+    #[cfg(target_os = "android")]
+    if let Some(android_app) = &native_options.android_app {
+        use winit::platform::android::EventLoopBuilderExtAndroid as _;
+        event_loop_builder.with_android_app(android_app.clone());
+    }
+
+    #[cfg(target_os = "macos")]
+    if native_options.intercept_quit {
+        // Without the default menu, Cmd-Q is sent to us as a normal key press.
+        use winit::platform::macos::EventLoopBuilderExtMacOS as _;
+        event_loop_builder.with_default_menu(false);
+    }
+
     if let Some(hook) = std::mem::take(&mut native_options.event_loop_builder) {
         hook(&mut event_loop_builder);
     }
@@ -27,7 +43,9 @@ fn create_event_loop_builder(
     event_loop_builder
 }
 
-fn create_event_loop(native_options: &mut epi::NativeOptions) -> Result<EventLoop<UserEvent>> {
+pub(crate) fn create_event_loop(
+    native_options: &mut epi::NativeOptions,
+) -> Result<EventLoop<UserEvent>> {
     crate::profile_function!();
     let mut builder = create_event_loop_builder(native_options);
 
@@ -39,7 +57,7 @@ fn create_event_loop(native_options: &mut epi::NativeOptions) -> Result<EventLoo
 ///
 /// We reuse the event-loop so we can support closing and opening an eframe window
 /// multiple times. This is just a limitation of winit.
-fn with_event_loop<R>(
+pub(crate) fn with_event_loop<R>(
     mut native_options: epi::NativeOptions,
     f: impl FnOnce(&mut EventLoop<UserEvent>, epi::NativeOptions) -> R,
 ) -> Result<R> {
@@ -60,11 +78,11 @@ fn with_event_loop<R>(
 }
 
 #[cfg(not(target_os = "ios"))]
-fn run_and_return(
+pub(crate) fn run_and_return(
     event_loop: &mut EventLoop<UserEvent>,
     mut winit_app: impl WinitApp,
 ) -> Result<()> {
-    use winit::{event_loop::ControlFlow, platform::run_on_demand::EventLoopExtRunOnDemand};
+    use winit::platform::run_on_demand::EventLoopExtRunOnDemand;
 
     log::debug!("Entering the winit event loop (run_on_demand)…");
 
@@ -74,135 +92,13 @@ fn run_and_return(
     let mut returned_result = Ok(());
 
     event_loop.run_on_demand(|event, event_loop_window_target| {
-        crate::profile_scope!("winit_event", short_event_description(&event));
-
-        log::trace!("winit event: {event:?}");
-
-        if matches!(event, winit::event::Event::AboutToWait) {
-            return; // early-out: don't trigger another wait
-        }
-
-        let event_result = match &event {
-            winit::event::Event::LoopExiting => {
-                // On Mac, Cmd-Q we get here and then `run_on_demand` doesn't return (despite its name),
-                // so we need to save state now:
-                log::debug!("Received Event::LoopExiting - saving app state…");
-                winit_app.save_and_destroy();
-                return;
-            }
-
-            winit::event::Event::WindowEvent {
-                event: winit::event::WindowEvent::RedrawRequested,
-                window_id,
-            } => {
-                windows_next_repaint_times.remove(window_id);
-                winit_app.run_ui_and_paint(event_loop_window_target, *window_id)
-            }
-
-            winit::event::Event::UserEvent(UserEvent::RequestRepaint {
-                when,
-                frame_nr,
-                viewport_id,
-            }) => {
-                let current_frame_nr = winit_app.frame_nr(*viewport_id);
-                if current_frame_nr == *frame_nr || current_frame_nr == *frame_nr + 1 {
-                    log::trace!("UserEvent::RequestRepaint scheduling repaint at {when:?}");
-                    if let Some(window_id) = winit_app.window_id_from_viewport_id(*viewport_id) {
-                        EventResult::RepaintAt(window_id, *when)
-                    } else {
-                        EventResult::Wait
-                    }
-                } else {
-                    log::trace!("Got outdated UserEvent::RequestRepaint");
-                    EventResult::Wait // old request - we've already repainted
-                }
-            }
-
-            winit::event::Event::NewEvents(winit::event::StartCause::ResumeTimeReached {
-                ..
-            }) => {
-                log::trace!("Woke up to check next_repaint_time");
-                EventResult::Wait
-            }
-
-            event => match winit_app.on_event(event_loop_window_target, event) {
-                Ok(event_result) => {
-                    log::trace!("event_result: {event_result:?}");
-                    event_result
-                }
-                Err(err) => {
-                    log::error!("Exiting because of error: {err} during event {event:?}");
-                    returned_result = Err(err);
-                    EventResult::Exit
-                }
-            },
-        };
-
-        match event_result {
-            EventResult::Wait => {
-                event_loop_window_target.set_control_flow(ControlFlow::Wait);
-            }
-            EventResult::RepaintNow(window_id) => {
-                log::trace!(
-                    "RepaintNow of {window_id:?} caused by {}",
-                    short_event_description(&event)
-                );
-                if cfg!(target_os = "windows") {
-                    // Fix flickering on Windows, see https://github.com/emilk/egui/pull/2280
-                    windows_next_repaint_times.remove(&window_id);
-
-                    winit_app.run_ui_and_paint(event_loop_window_target, window_id);
-                } else {
-                    // Fix for https://github.com/emilk/egui/issues/2425
-                    windows_next_repaint_times.insert(window_id, Instant::now());
-                }
-            }
-            EventResult::RepaintNext(window_id) => {
-                log::trace!(
-                    "RepaintNext of {window_id:?} caused by {}",
-                    short_event_description(&event)
-                );
-                windows_next_repaint_times.insert(window_id, Instant::now());
-            }
-            EventResult::RepaintAt(window_id, repaint_time) => {
-                windows_next_repaint_times.insert(
-                    window_id,
-                    windows_next_repaint_times
-                        .get(&window_id)
-                        .map_or(repaint_time, |last| (*last).min(repaint_time)),
-                );
-            }
-            EventResult::Exit => {
-                log::debug!("Asking to exit event loop…");
-                winit_app.save_and_destroy();
-                event_loop_window_target.exit();
-                return;
-            }
-        }
-
-        let mut next_repaint_time = windows_next_repaint_times.values().min().copied();
-
-        windows_next_repaint_times.retain(|window_id, repaint_time| {
-            if Instant::now() < *repaint_time {
-                return true; // not yet ready
-            };
-
-            next_repaint_time = None;
-            event_loop_window_target.set_control_flow(ControlFlow::Poll);
-
-            if let Some(window) = winit_app.window(*window_id) {
-                log::trace!("request_redraw for {window_id:?}");
-                window.request_redraw();
-                true
-            } else {
-                log::trace!("No window found for {window_id:?}");
-                false
-            }
-        });
-
-        if let Some(next_repaint_time) = next_repaint_time {
-            event_loop_window_target.set_control_flow(ControlFlow::WaitUntil(next_repaint_time));
-        };
+        on_demand_event(
+            &mut winit_app,
+            &mut windows_next_repaint_times,
+            &mut returned_result,
+            &event,
+            event_loop_window_target,
+        );
     })?;
 
     log::debug!("eframe window closed");
@@ -226,7 +122,159 @@ fn run_and_return(
     returned_result
 }
 
-fn run_and_exit(
+/// What to show for the profiling scope of an event: what it is, and for which viewport.
+#[cfg(feature = "puffin")]
+fn event_scope_data(winit_app: &dyn WinitApp, event: &winit::event::Event<UserEvent>) -> String {
+    let description = short_event_description(event);
+    let window_id = match event {
+        winit::event::Event::WindowEvent { window_id, .. } => Some(*window_id),
+        _ => None,
+    };
+    match window_id.and_then(|window_id| winit_app.viewport_id_from_window_id(window_id)) {
+        Some(viewport_id) => format!("{description} {viewport_id:?}"),
+        None => description.to_owned(),
+    }
+}
+
+/// Handle one event of the event loop, for [`run_and_return`] and [`PumpedApp::pump`].
+#[cfg(not(target_os = "ios"))]
+fn on_demand_event(
+    winit_app: &mut dyn WinitApp,
+    windows_next_repaint_times: &mut HashMap<WindowId, Instant>,
+    returned_result: &mut Result<()>,
+    event: &winit::event::Event<UserEvent>,
+    event_loop_window_target: &EventLoopWindowTarget<UserEvent>,
+) {
+    use winit::event_loop::ControlFlow;
+
+    crate::profile_scope!("winit_event", event_scope_data(&*winit_app, event));
+
+    log::trace!("winit event: {event:?}");
+
+    if matches!(event, winit::event::Event::AboutToWait) {
+        return; // early-out: don't trigger another wait
+    }
+
+    let event_result = match event {
+        winit::event::Event::LoopExiting => {
+            // On Mac, Cmd-Q we get here and then `run_on_demand` doesn't return (despite its name),
+            // so we need to save state now:
+            log::debug!("Received Event::LoopExiting - saving app state…");
+            winit_app.save_and_destroy();
+            return;
+        }
+
+        winit::event::Event::WindowEvent {
+            event: winit::event::WindowEvent::RedrawRequested,
+            window_id,
+        } => {
+            windows_next_repaint_times.remove(window_id);
+            winit_app.run_ui_and_paint(event_loop_window_target, *window_id)
+        }
+
+        winit::event::Event::UserEvent(UserEvent::RequestRepaint {
+            app_index,
+            when,
+            frame_nr,
+            viewport_id,
+        }) => {
+            if let Some(window_id) =
+                winit_app.window_to_repaint(*app_index, *viewport_id, *frame_nr)
+            {
+                log::trace!("UserEvent::RequestRepaint scheduling repaint at {when:?}");
+                EventResult::RepaintAt(window_id, *when)
+            } else {
+                log::trace!("Got outdated UserEvent::RequestRepaint");
+                EventResult::Wait
+            }
+        }
+
+        winit::event::Event::NewEvents(winit::event::StartCause::ResumeTimeReached { .. }) => {
+            log::trace!("Woke up to check next_repaint_time");
+            EventResult::Wait
+        }
+
+        event => match winit_app.on_event(event_loop_window_target, event) {
+            Ok(event_result) => {
+                log::trace!("event_result: {event_result:?}");
+                event_result
+            }
+            Err(err) => {
+                log::error!("Exiting because of error: {err} during event {event:?}");
+                *returned_result = Err(err);
+                EventResult::Exit
+            }
+        },
+    };
+
+    match event_result {
+        EventResult::Wait => {
+            event_loop_window_target.set_control_flow(ControlFlow::Wait);
+        }
+        EventResult::RepaintNow(window_id) => {
+            log::trace!(
+                "RepaintNow of {window_id:?} caused by {}",
+                short_event_description(event)
+            );
+            if cfg!(target_os = "windows") {
+                // Fix flickering on Windows, see https://github.com/emilk/egui/pull/2280
+                windows_next_repaint_times.remove(&window_id);
+
+                winit_app.run_ui_and_paint(event_loop_window_target, window_id);
+            } else {
+                // Fix for https://github.com/emilk/egui/issues/2425
+                windows_next_repaint_times.insert(window_id, Instant::now());
+            }
+        }
+        EventResult::RepaintNext(window_id) => {
+            log::trace!(
+                "RepaintNext of {window_id:?} caused by {}",
+                short_event_description(event)
+            );
+            windows_next_repaint_times.insert(window_id, Instant::now());
+        }
+        EventResult::RepaintAt(window_id, repaint_time) => {
+            windows_next_repaint_times.insert(
+                window_id,
+                windows_next_repaint_times
+                    .get(&window_id)
+                    .map_or(repaint_time, |last| (*last).min(repaint_time)),
+            );
+        }
+        EventResult::Exit => {
+            log::debug!("Asking to exit event loop…");
+            winit_app.save_and_destroy();
+            event_loop_window_target.exit();
+            return;
+        }
+    }
+
+    let mut next_repaint_time = windows_next_repaint_times.values().min().copied();
+
+    windows_next_repaint_times.retain(|window_id, repaint_time| {
+        if Instant::now() < *repaint_time {
+            return true; // not yet ready
+        };
+
+        next_repaint_time = None;
+        event_loop_window_target.set_control_flow(ControlFlow::Poll);
+
+        if let Some(window) = winit_app.window(*window_id) {
+            log::trace!("request_redraw for {window_id:?}");
+            window.request_redraw();
+            true
+        } else {
+            log::trace!("No window found for {window_id:?}");
+            false
+        }
+    });
+
+    if let Some(next_repaint_time) = next_repaint_time {
+        event_loop_window_target.set_control_flow(ControlFlow::WaitUntil(next_repaint_time));
+    };
+}
+
+pub(crate) fn run_and_exit(
     event_loop: EventLoop<UserEvent>,
     mut winit_app: impl WinitApp + 'static,
 ) -> Result<()> {
@@ -237,7 +285,7 @@ fn run_and_exit(
     let mut windows_next_repaint_times = HashMap::default();
 
     event_loop.run(move |event, event_loop_window_target| {
-        crate::profile_scope!("winit_event", short_event_description(&event));
+        crate::profile_scope!("winit_event", event_scope_data(&winit_app, &event));
 
         log::trace!("winit event: {event:?}");
 
@@ -260,20 +308,18 @@ fn run_and_exit(
             }
 
             winit::event::Event::UserEvent(UserEvent::RequestRepaint {
+                app_index,
                 when,
                 frame_nr,
                 viewport_id,
             }) => {
-                let current_frame_nr = winit_app.frame_nr(*viewport_id);
-                if current_frame_nr == *frame_nr || current_frame_nr == *frame_nr + 1 {
-                    if let Some(window_id) = winit_app.window_id_from_viewport_id(*viewport_id) {
-                        EventResult::RepaintAt(window_id, *when)
-                    } else {
-                        EventResult::Wait
-                    }
+                if let Some(window_id) =
+                    winit_app.window_to_repaint(*app_index, *viewport_id, *frame_nr)
+                {
+                    EventResult::RepaintAt(window_id, *when)
                 } else {
                     log::trace!("Got outdated UserEvent::RequestRepaint");
-                    EventResult::Wait // old request - we've already repainted
+                    EventResult::Wait
                 }
             }
 
@@ -386,12 +432,18 @@ pub fn run_glow(
     #[cfg(not(target_os = "ios"))]
     if native_options.run_and_return {
         return with_event_loop(native_options, |event_loop, native_options| {
+            if !super::single_instance::start(&native_options, event_loop) {
+                return Ok(());
+            }
             let glow_eframe = GlowWinitApp::new(event_loop, app_name, native_options, app_creator);
             run_and_return(event_loop, glow_eframe)
         })?;
     }
 
     let event_loop = create_event_loop(&mut native_options)?;
+    if !super::single_instance::start(&native_options, &event_loop) {
+        return Ok(());
+    }
     let glow_eframe = GlowWinitApp::new(&event_loop, app_name, native_options, app_creator);
     run_and_exit(event_loop, glow_eframe)
 }
@@ -409,12 +461,135 @@ pub fn run_wgpu(
     #[cfg(not(target_os = "ios"))]
     if native_options.run_and_return {
         return with_event_loop(native_options, |event_loop, native_options| {
+            if !super::single_instance::start(&native_options, event_loop) {
+                return Ok(());
+            }
             let wgpu_eframe = WgpuWinitApp::new(event_loop, app_name, native_options, app_creator);
             run_and_return(event_loop, wgpu_eframe)
         })?;
     }
 
     let event_loop = create_event_loop(&mut native_options)?;
+    if !super::single_instance::start(&native_options, &event_loop) {
+        return Ok(());
+    }
     let wgpu_eframe = WgpuWinitApp::new(&event_loop, app_name, native_options, app_creator);
     run_and_exit(event_loop, wgpu_eframe)
 }
+
+// ----------------------------------------------------------------------------
+
+/// An eframe app that is driven by your own main loop, instead of taking over the thread.
+///
+/// Create it with [`crate::run_pumped`], then call [`Self::pump`] once per iteration of your loop.
+#[cfg(not(target_os = "ios"))]
+pub struct PumpedApp {
+    event_loop: EventLoop<UserEvent>,
+
+    /// `None` once the app has exited.
+    winit_app: Option<Box<dyn WinitApp>>,
+
+    /// When to repaint what window
+    windows_next_repaint_times: HashMap<WindowId, Instant>,
+}
+
+#[cfg(not(target_os = "ios"))]
+impl PumpedApp {
+    fn new(event_loop: EventLoop<UserEvent>, winit_app: Option<Box<dyn WinitApp>>) -> Self {
+        Self {
+            event_loop,
+            winit_app,
+            windows_next_repaint_times: HashMap::default(),
+        }
+    }
+
+    /// Handle the pending events of the windows, and repaint those that need it.
+    ///
+    /// With a `timeout` of `Some(Duration::ZERO)` this returns as soon as the pending events are handled.
+    /// With a longer `timeout` it waits for new events for at most that long, and with `None`
+    /// until there are new events, or until egui wants to repaint.
+    ///
+    /// Returns [`std::ops::ControlFlow::Break`] once the app has exited, e.g. because its last window was closed.
+    /// The app state has then been saved, and further calls do nothing.
+    pub fn pump(
+        &mut self,
+        timeout: Option<std::time::Duration>,
+    ) -> std::ops::ControlFlow<Result<()>> {
+        use winit::platform::pump_events::{EventLoopExtPumpEvents as _, PumpStatus};
+
+        let Some(winit_app) = &mut self.winit_app else {
+            return std::ops::ControlFlow::Break(Ok(()));
+        };
+
+        let mut returned_result = Ok(());
+        let status = self
+            .event_loop
+            .pump_events(timeout, |event, event_loop_window_target| {
+                on_demand_event(
+                    winit_app.as_mut(),
+                    &mut self.windows_next_repaint_times,
+                    &mut returned_result,
+                    &event,
+                    event_loop_window_target,
+                );
+            });
+
+        match status {
+            PumpStatus::Continue => std::ops::ControlFlow::Continue(()),
+            PumpStatus::Exit(_) => {
+                log::debug!("eframe app exited");
+                self.winit_app = None;
+                std::ops::ControlFlow::Break(returned_result)
+            }
+        }
+    }
+
+    /// Has the app exited, see [`Self::pump`].
+    pub fn has_exited(&self) -> bool {
+        self.winit_app.is_none()
+    }
+}
+
+#[cfg(not(target_os = "ios"))]
+impl Drop for PumpedApp {
+    fn drop(&mut self) {
+        if let Some(winit_app) = &mut self.winit_app {
+            log::debug!("PumpedApp dropped before exiting - saving app state…");
+            winit_app.save_and_destroy();
+        }
+    }
+}
+
+#[cfg(feature = "glow")]
+#[cfg(not(target_os = "ios"))]
+pub fn pumped_glow(
+    app_name: &str,
+    mut native_options: epi::NativeOptions,
+    app_creator: epi::AppCreator,
+) -> Result<PumpedApp> {
+    use super::glow_integration::GlowWinitApp;
+
+    let event_loop = create_event_loop(&mut native_options)?;
+    if !super::single_instance::start(&native_options, &event_loop) {
+        return Ok(PumpedApp::new(event_loop, None));
+    }
+    let glow_eframe = GlowWinitApp::new(&event_loop, app_name, native_options, app_creator);
+    Ok(PumpedApp::new(event_loop, Some(Box::new(glow_eframe))))
+}
+
+#[cfg(feature = "wgpu")]
+#[cfg(not(target_os = "ios"))]
+pub fn pumped_wgpu(
+    app_name: &str,
+    mut native_options: epi::NativeOptions,
+    app_creator: epi::AppCreator,
+) -> Result<PumpedApp> {
+    use super::wgpu_integration::WgpuWinitApp;
+
+    let event_loop = create_event_loop(&mut native_options)?;
+    if !super::single_instance::start(&native_options, &event_loop) {
+        return Ok(PumpedApp::new(event_loop, None));
+    }
+    let wgpu_eframe = WgpuWinitApp::new(&event_loop, app_name, native_options, app_creator);
+    Ok(PumpedApp::new(event_loop, Some(Box::new(wgpu_eframe))))
+}
@@ -0,0 +1,575 @@
+//! Make sure only one instance of an app is running, see [`crate::NativeOptions::single_instance`].
+//!
+//! The first instance listens on a per-user endpoint derived from the app id:
+//! a Unix domain socket in a directory only the user can access, or a named pipe on Windows.
+//! Later instances connect to it and send their command line arguments.
+//! They only exit once the running instance has acknowledged them.
+
+use std::{
+    io::{Read, Write},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use winit::event_loop::{EventLoop, EventLoopProxy};
+
+use super::winit_integration::UserEvent;
+use crate::epi;
+
+/// Sent first in each message, so we ignore other programs that happen to use the same endpoint.
+const MAGIC: &str = "eframe-single-instance";
+
+/// Sent back by the running instance once it has received our arguments.
+const ACK: &str = "eframe-single-instance-ack";
+
+/// The arguments are separated by this, since it can't be part of a command line argument.
+const SEPARATOR: char = '\0';
+
+/// Ignore messages larger than this.
+const MAX_MESSAGE_LEN: usize = 1024 * 1024;
+
+/// Are we already listening for other instances?
+///
+/// An app can call [`crate::run_native`] several times, and shouldn't forward its arguments to itself.
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+/// If [`epi::NativeOptions::single_instance`] is set, make sure we are the only instance of the app.
+///
+/// Returns `false` if another instance is already running.
+/// Our command line arguments have then been sent to it, and we should exit.
+pub fn start(native_options: &epi::NativeOptions, event_loop: &EventLoop<UserEvent>) -> bool {
+    crate::profile_function!();
+
+    let Some(app_id) = &native_options.single_instance else {
+        return true;
+    };
+    if LISTENING.load(Ordering::SeqCst) {
+        return true;
+    }
+
+    match endpoint::bind(app_id) {
+        Ok(Some(listener)) => {
+            log::debug!("Listening for other instances of {app_id:?}");
+            LISTENING.store(true, Ordering::SeqCst);
+            listen(
+                listener,
+                app_id.clone(),
+                native_options.url_schemes.clone(),
+                native_options.open_files,
+                event_loop.create_proxy(),
+            );
+            true
+        }
+        Ok(None) => match forward_args(app_id) {
+            Ok(()) => {
+                log::info!("{app_id:?} is already running - sent it our arguments");
+                false
+            }
+            Err(err) => {
+                log::warn!(
+                    "Failed to send our arguments to the running instance of {app_id:?}: {err}"
+                );
+                true
+            }
+        },
+        Err(err) => {
+            log::warn!("Failed to check for other instances of {app_id:?}: {err}");
+            true
+        }
+    }
+}
+
+/// The name of the endpoint, without any user-specific parts.
+fn endpoint_name(app_id: &str) -> String {
+    format!("eframe-{:016x}", fnv1a(app_id.as_bytes()))
+}
+
+/// A hash that is the same in all processes and versions of the app.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Tell the running instance about our command line arguments, and wait for it to acknowledge them.
+fn forward_args(app_id: &str) -> std::io::Result<()> {
+    let mut stream = endpoint::connect(app_id)?;
+
+    let mut message = format!("{MAGIC}{SEPARATOR}{app_id}");
+    for arg in std::env::args_os().skip(1) {
+        // The other instance has a different working directory, so send full paths:
+        let path = std::path::Path::new(&arg);
+        let arg = if path.is_relative() && path.exists() {
+            std::fs::canonicalize(path).map_or(arg, |path| path.into_os_string())
+        } else {
+            arg
+        };
+        message.push(SEPARATOR);
+        message.push_str(&arg.to_string_lossy());
+    }
+
+    write_message(&mut stream, message.as_bytes())?;
+
+    if read_message(&mut stream)? == ACK.as_bytes() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "unexpected reply from the running instance",
+        ))
+    }
+}
+
+/// Write a length-prefixed message.
+fn write_message(stream: &mut impl Write, message: &[u8]) -> std::io::Result<()> {
+    let len = u32::try_from(message.len())
+        .map_err(|_err| std::io::Error::new(std::io::ErrorKind::InvalidInput, "too long"))?;
+    stream.write_all(&len.to_le_bytes())?;
+    stream.write_all(message)?;
+    stream.flush()
+}
+
+/// Read a message written with [`write_message`].
+fn read_message(stream: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut len = [0; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if MAX_MESSAGE_LEN < len {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "message too long",
+        ));
+    }
+    let mut message = vec![0; len];
+    stream.read_exact(&mut message)?;
+    Ok(message)
+}
+
+fn listen(
+    listener: endpoint::Listener,
+    app_id: String,
+    url_schemes: Vec<String>,
+    open_files: bool,
+    proxy: EventLoopProxy<UserEvent>,
+) {
+    let result = std::thread::Builder::new()
+        .name("eframe_single_instance".to_owned())
+        .spawn(move || {
+            endpoint::serve(&listener, |mut stream| {
+                let Some(args) = read_args(&mut stream, &app_id) else {
+                    return true;
+                };
+                let open_requests: Vec<UserEvent> = args
+                    .iter()
+                    .filter_map(|arg| {
+                        if super::url_scheme::is_deep_link(&url_schemes, arg) {
+                            Some(UserEvent::DeepLink(arg.clone()))
+                        } else if open_files {
+                            super::open_files::file_from_arg(arg).map(UserEvent::OpenFile)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                if proxy.send_event(UserEvent::InstanceArgs(args)).is_err() {
+                    return false; // The event loop is gone, so let the other instance start instead
+                }
+                for event in open_requests {
+                    proxy.send_event(event).ok();
+                }
+                if let Err(err) = write_message(&mut stream, ACK.as_bytes()) {
+                    log::warn!("Failed to reply to another instance: {err}");
+                }
+                true
+            });
+        });
+    if let Err(err) = result {
+        log::warn!("Failed to spawn thread: {err}");
+    }
+}
+
+fn read_args(stream: &mut impl Read, app_id: &str) -> Option<Vec<String>> {
+    let message = match read_message(stream) {
+        Ok(message) => String::from_utf8_lossy(&message).into_owned(),
+        Err(err) => {
+            log::debug!("Failed to read message from another instance: {err}");
+            return None;
+        }
+    };
+
+    let mut parts = message.split(SEPARATOR);
+    if parts.next() != Some(MAGIC) || parts.next() != Some(app_id) {
+        log::debug!("Ignoring unknown connection on the single instance endpoint");
+        return None;
+    }
+    Some(parts.map(ToOwned::to_owned).collect())
+}
+
+// ----------------------------------------------------------------------------
+
+/// A Unix domain socket in a directory only the current user can access.
+///
+/// The instance that holds a lock on a file next to the socket is the one listening on it,
+/// so that only it removes a socket left behind by an instance that crashed.
+#[cfg(unix)]
+#[allow(unsafe_code)]
+mod endpoint {
+    use std::{
+        fs::File,
+        os::unix::{
+            fs::{DirBuilderExt as _, MetadataExt as _},
+            io::AsRawFd as _,
+            net::{UnixListener, UnixStream},
+        },
+        path::PathBuf,
+        time::Duration,
+    };
+
+    pub struct Listener {
+        listener: UnixListener,
+
+        /// Locked for as long as we are listening.
+        _lock: File,
+    }
+
+    /// A directory only the current user can access, in `$XDG_RUNTIME_DIR`, `$TMPDIR` or `/tmp`.
+    pub(super) fn socket_dir() -> std::io::Result<PathBuf> {
+        let base = ["XDG_RUNTIME_DIR", "TMPDIR"]
+            .into_iter()
+            .filter_map(std::env::var_os)
+            .map(PathBuf::from)
+            .find(|dir| dir.is_dir())
+            .unwrap_or_else(|| PathBuf::from("/tmp"));
+        let uid = unsafe { libc::getuid() };
+        let dir = base.join(format!("eframe-{uid}"));
+
+        match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err),
+        }
+
+        // Someone else could have created it first, e.g. in a shared `/tmp`:
+        let metadata = std::fs::symlink_metadata(&dir)?;
+        if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("{dir:?} is not a private directory of the current user"),
+            ));
+        }
+        Ok(dir)
+    }
+
+    fn socket_path(app_id: &str) -> std::io::Result<PathBuf> {
+        Ok(socket_dir()?.join(format!("{}.sock", super::endpoint_name(app_id))))
+    }
+
+    /// Returns `None` if another instance is already listening.
+    pub fn bind(app_id: &str) -> std::io::Result<Option<Listener>> {
+        let path = socket_path(app_id)?;
+
+        let lock = File::create(path.with_extension("lock"))?;
+        if unsafe { libc::flock(lock.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+            let err = std::io::Error::last_os_error();
+            return if err.kind() == std::io::ErrorKind::WouldBlock {
+                Ok(None)
+            } else {
+                Err(err)
+            };
+        }
+
+        // We hold the lock, so a socket that is still there was left behind by an instance that crashed:
+        if path.exists() {
+            log::debug!("Removing stale single instance socket {path:?}");
+            std::fs::remove_file(&path)?;
+        }
+        let listener = UnixListener::bind(&path)?;
+        Ok(Some(Listener {
+            listener,
+            _lock: lock,
+        }))
+    }
+
+    pub fn connect(app_id: &str) -> std::io::Result<UnixStream> {
+        let path = socket_path(app_id)?;
+        let mut attempts = 0;
+        let stream = loop {
+            match UnixStream::connect(&path) {
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        std::io::ErrorKind::NotFound | std::io::ErrorKind::ConnectionRefused
+                    ) && attempts < 20 =>
+                {
+                    // The running instance has the lock, but is not listening yet:
+                    attempts += 1;
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                result => break result?,
+            }
+        };
+        stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+        stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+        Ok(stream)
+    }
+
+    /// Handle connections until `handle` returns `false`.
+    pub fn serve(listener: &Listener, mut handle: impl FnMut(UnixStream) -> bool) {
+        for stream in listener.listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(Duration::from_secs(1))).ok();
+                    if !handle(stream) {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    log::warn!("Failed to accept connection from another instance: {err}");
+                }
+            }
+        }
+    }
+}
+
+/// A named pipe that only local clients can connect to.
+///
+/// The pipe name contains the user name, and the default security descriptor
+/// only gives write access to the user that created it (and administrators).
+#[cfg(windows)]
+#[allow(unsafe_code)]
+mod endpoint {
+    use std::{
+        fs::File,
+        io::{Read, Write},
+        os::windows::{
+            ffi::OsStrExt as _,
+            io::{AsRawHandle as _, FromRawHandle as _},
+        },
+        time::{Duration, Instant},
+    };
+
+    use winapi::{
+        shared::winerror::{ERROR_ACCESS_DENIED, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED},
+        um::{
+            handleapi::{CloseHandle, INVALID_HANDLE_VALUE},
+            namedpipeapi::{ConnectNamedPipe, CreateNamedPipeW, PeekNamedPipe},
+            winbase::{
+                FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX, PIPE_READMODE_BYTE,
+                PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+            },
+            winnt::HANDLE,
+        },
+    };
+
+    pub struct Listener {
+        name: Vec<u16>,
+        pipe: HANDLE,
+    }
+
+    // The handle is only used by the listening thread.
+    unsafe impl Send for Listener {}
+
+    fn pipe_name(app_id: &str) -> String {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        format!(
+            r"\\.\pipe\{}-{:016x}",
+            super::endpoint_name(app_id),
+            super::fnv1a(user.as_bytes())
+        )
+    }
+
+    /// Create a new instance of the pipe.
+    fn create_pipe(name: &[u16], first: bool) -> std::io::Result<HANDLE> {
+        let mut open_mode = PIPE_ACCESS_DUPLEX;
+        if first {
+            // Fail if another instance of the app already created the pipe:
+            open_mode |= FILE_FLAG_FIRST_PIPE_INSTANCE;
+        }
+        let pipe = unsafe {
+            CreateNamedPipeW(
+                name.as_ptr(),
+                open_mode,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                4096,
+                4096,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        if pipe == INVALID_HANDLE_VALUE {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(pipe)
+        }
+    }
+
+    /// Returns `None` if another instance is already listening.
+    pub fn bind(app_id: &str) -> std::io::Result<Option<Listener>> {
+        let name: Vec<u16> = std::ffi::OsStr::new(&pipe_name(app_id))
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        match create_pipe(&name, true) {
+            Ok(pipe) => Ok(Some(Listener { name, pipe })),
+            Err(err) if err.raw_os_error() == Some(ERROR_ACCESS_DENIED as i32) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Our end of the pipe when connecting to the running instance.
+    ///
+    /// Reads time out, like the socket on Unix, in case the running instance hangs.
+    pub struct Client(File);
+
+    impl Read for Client {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            // Synchronous reads of a pipe can't time out, so wait until there is something to read:
+            let deadline = Instant::now() + Duration::from_secs(1);
+            loop {
+                let mut available = 0;
+                let peeked = unsafe {
+                    PeekNamedPipe(
+                        self.0.as_raw_handle().cast(),
+                        std::ptr::null_mut(),
+                        0,
+                        std::ptr::null_mut(),
+                        &mut available,
+                        std::ptr::null_mut(),
+                    )
+                };
+                if peeked == 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                if 0 < available {
+                    return self.0.read(buf);
+                }
+                if deadline <= Instant::now() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "the running instance did not reply",
+                    ));
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+    }
+
+    impl Write for Client {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.0.flush()
+        }
+    }
+
+    pub fn connect(app_id: &str) -> std::io::Result<Client> {
+        let name = pipe_name(app_id);
+        let mut attempts = 0;
+        loop {
+            match std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&name)
+            {
+                Err(err) if err.raw_os_error() == Some(ERROR_PIPE_BUSY as i32) && attempts < 20 => {
+                    // All instances of the pipe are busy with other clients:
+                    attempts += 1;
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                result => return result.map(Client),
+            }
+        }
+    }
+
+    /// Handle connections until `handle` returns `false`.
+    pub fn serve(listener: &Listener, mut handle: impl FnMut(File) -> bool) {
+        let name = &listener.name;
+        let mut pipe = listener.pipe;
+        loop {
+            let connected = if unsafe { ConnectNamedPipe(pipe, std::ptr::null_mut()) } != 0 {
+                Ok(())
+            } else {
+                let err = std::io::Error::last_os_error();
+                if err.raw_os_error() == Some(ERROR_PIPE_CONNECTED as i32) {
+                    Ok(()) // The client connected before we called `ConnectNamedPipe`
+                } else {
+                    Err(err)
+                }
+            };
+
+            // Create the next instance before handling this one,
+            // so there is always a pipe for other instances to find:
+            let next_pipe = match create_pipe(name, false) {
+                Ok(next_pipe) => next_pipe,
+                Err(err) => {
+                    log::warn!("Failed to create single instance pipe: {err}");
+                    unsafe { CloseHandle(pipe) };
+                    return;
+                }
+            };
+
+            match connected {
+                Ok(()) => {
+                    // The `File` closes the handle when dropped.
+                    let stream = unsafe { File::from_raw_handle(pipe.cast()) };
+                    if !handle(stream) {
+                        unsafe { CloseHandle(next_pipe) };
+                        return;
+                    }
+                }
+                Err(err) => {
+                    log::warn!("Failed to accept connection from another instance: {err}");
+                    unsafe { CloseHandle(pipe) };
+                }
+            }
+            pipe = next_pipe;
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unix_endpoint() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let app_id = format!("eframe-test-{}", std::process::id());
+
+    let listener = endpoint::bind(&app_id).unwrap().unwrap();
+    assert!(
+        endpoint::bind(&app_id).unwrap().is_none(),
+        "there can only be one listener"
+    );
+
+    let dir = endpoint::socket_dir().unwrap();
+    let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o700);
+
+    let client = std::thread::Builder::new()
+        .spawn({
+            let app_id = app_id.clone();
+            move || {
+                let mut stream = endpoint::connect(&app_id).unwrap();
+                write_message(&mut stream, b"hello").unwrap();
+                read_message(&mut stream).unwrap()
+            }
+        })
+        .unwrap();
+    endpoint::serve(&listener, |mut stream| {
+        assert_eq!(read_message(&mut stream).unwrap(), b"hello");
+        write_message(&mut stream, ACK.as_bytes()).unwrap();
+        false
+    });
+    assert_eq!(client.join().unwrap(), ACK.as_bytes());
+
+    // Once the listener is gone, its socket is replaced by the next instance:
+    drop(listener);
+    let listener = endpoint::bind(&app_id).unwrap();
+    assert!(listener.is_some());
+    drop(listener);
+
+    let socket = dir.join(format!("{}.sock", endpoint_name(&app_id)));
+    std::fs::remove_file(&socket).ok();
+    std::fs::remove_file(socket.with_extension("lock")).ok();
+}
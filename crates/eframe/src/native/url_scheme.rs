@@ -0,0 +1,169 @@
+//! Deep links using custom URL schemes, see [`crate::NativeOptions::url_schemes`].
+//!
+//! When the user opens e.g. `myapp://settings`, the OS delivers it differently per platform:
+//! * Windows and Linux start the app with the URL as a command line argument.
+//!   With [`crate::NativeOptions::single_instance`] that is forwarded to the running instance.
+//! * Mac sends a `GetURL` Apple event to the running app (starting it first if needed).
+
+use winit::event_loop::EventLoop;
+
+use super::winit_integration::UserEvent;
+use crate::epi;
+
+/// Is this command line argument a URL using one of the given schemes?
+pub fn is_deep_link(url_schemes: &[String], arg: &str) -> bool {
+    arg.split_once(':').map_or(false, |(scheme, _)| {
+        url_schemes
+            .iter()
+            .any(|url_scheme| url_scheme.eq_ignore_ascii_case(scheme))
+    })
+}
+
+/// Start listening for deep links.
+///
+/// Returns the deep links in our own command line arguments,
+/// which should be delivered to the app once the root viewport is created.
+pub fn start(
+    native_options: &epi::NativeOptions,
+    event_loop: &EventLoop<UserEvent>,
+) -> Vec<String> {
+    let url_schemes = &native_options.url_schemes;
+    if url_schemes.is_empty() {
+        return vec![];
+    }
+
+    #[cfg(target_os = "macos")]
+    super::apple_events::handle_urls(event_loop.create_proxy());
+    #[cfg(not(target_os = "macos"))]
+    let _ = event_loop;
+
+    std::env::args()
+        .skip(1)
+        .filter(|arg| is_deep_link(url_schemes, arg))
+        .collect()
+}
+
+/// Register this executable as the handler of a custom URL scheme, e.g. `"myapp"`.
+///
+/// Afterwards, opening a link like `myapp://settings` starts the app (or, with
+/// [`crate::NativeOptions::single_instance`], forwards it to the running instance),
+/// and the app gets it as an [`egui::Event::DeepLink`] if the scheme is in [`crate::NativeOptions::url_schemes`].
+///
+/// This is usually done by the installer of your app, but can also be done on startup.
+///
+/// * On Windows this writes to `HKEY_CURRENT_USER\Software\Classes`.
+/// * On Linux this writes a `.desktop` file to `~/.local/share/applications` and calls `xdg-mime`.
+/// * On Mac the URL schemes can only be registered by listing them under `CFBundleURLTypes`
+///   in the `Info.plist` of your app bundle, so this returns an error.
+pub fn register_url_scheme(scheme: &str) -> std::io::Result<()> {
+    use std::io::{Error, ErrorKind};
+
+    let is_valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !is_valid {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid URL scheme: {scheme:?}"),
+        ));
+    }
+
+    let exe = std::env::current_exe()?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let key = format!(r"HKCU\Software\Classes\{scheme}");
+        let command = format!("\"{}\" \"%1\"", exe.display());
+        reg_add(&key, None, &format!("URL:{scheme} Protocol"))?;
+        reg_add(&key, Some("URL Protocol"), "")?;
+        reg_add(&format!(r"{key}\shell\open\command"), None, &command)
+    }
+
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    {
+        let data_dir = std::env::var_os("XDG_DATA_HOME")
+            .map(std::path::PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .map(|home| std::path::PathBuf::from(home).join(".local/share"))
+            })
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "Can't find the home directory"))?;
+        let applications_dir = data_dir.join("applications");
+        std::fs::create_dir_all(&applications_dir)?;
+
+        let name = exe.file_stem().map_or_else(
+            || scheme.to_owned(),
+            |stem| stem.to_string_lossy().into_owned(),
+        );
+        let file_name = format!("{name}-{scheme}-handler.desktop");
+        let desktop_entry = format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name={name}\n\
+             Exec=\"{}\" %u\n\
+             NoDisplay=true\n\
+             MimeType=x-scheme-handler/{scheme};\n",
+            exe.display()
+        );
+        std::fs::write(applications_dir.join(&file_name), desktop_entry)?;
+
+        let status = std::process::Command::new("xdg-mime")
+            .args(["default", &file_name, &format!("x-scheme-handler/{scheme}")])
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::Other,
+                format!("xdg-mime failed: {status}"),
+            ))
+        }
+    }
+
+    #[cfg(not(any(
+        target_os = "windows",
+        all(
+            unix,
+            not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+        )
+    )))]
+    {
+        let _ = exe;
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "URL schemes must be registered in the Info.plist of the app bundle",
+        ))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn reg_add(key: &str, value: Option<&str>, data: &str) -> std::io::Result<()> {
+    let mut command = std::process::Command::new("reg");
+    command.args(["add", key]);
+    match value {
+        Some(value) => command.args(["/v", value]),
+        None => command.arg("/ve"),
+    };
+    let status = command.args(["/d", data, "/f"]).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("reg add {key:?} failed: {status}"),
+        ))
+    }
+}
+
+#[test]
+fn test_is_deep_link() {
+    let url_schemes = vec!["myapp".to_owned()];
+    assert!(is_deep_link(&url_schemes, "myapp://settings"));
+    assert!(is_deep_link(&url_schemes, "MyApp:open?file=foo"));
+    assert!(!is_deep_link(&url_schemes, "https://example.com"));
+    assert!(!is_deep_link(&url_schemes, "myapp"));
+}
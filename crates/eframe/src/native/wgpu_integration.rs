@@ -23,17 +23,28 @@ use egui::{
 use egui_winit::accesskit_winit;
 
 use crate::{
-    native::{epi_integration::EpiIntegration, winit_integration::EventResult},
+    native::{
+        epi_integration::EpiIntegration,
+        render_thread_pool::{RenderJob, ViewportRenderThreadPool},
+        winit_integration::EventResult,
+    },
     App, AppCreator, CreationContext, NativeOptions, Result, Storage, UserEvent,
 };
 
-use super::{winit_integration::WinitApp, *};
+use super::{
+    winit_integration::{bring_to_front, WinitApp},
+    *,
+};
 
 // ----------------------------------------------------------------------------
 // Types:
 
 pub struct WgpuWinitApp {
     repaint_proxy: Arc<Mutex<EventLoopProxy<UserEvent>>>,
+
+    /// See [`WinitApp::set_app_index`].
+    app_index: usize,
+
     app_name: String,
     native_options: NativeOptions,
 
@@ -42,6 +53,9 @@ pub struct WgpuWinitApp {
 
     /// Set when we are actually up and running.
     running: Option<WgpuWinitRunning>,
+
+    /// Deep links and files to open, waiting for the root viewport to be created.
+    pending_events: Vec<egui::Event>,
 }
 
 /// State that is initialized when the application is first starts running via
@@ -62,10 +76,17 @@ struct WgpuWinitRunning {
 /// This is shared by all viewports.
 ///
 /// Wrapped in an `Rc<RefCell<…>>` so it can be re-entrantly shared via a weak-pointer.
+///
+/// The windows may only be used from the main thread.
+/// The painter is behind a lock, since it is shared with the render threads of the deferred viewports.
 pub struct SharedState {
     egui_ctx: egui::Context,
     viewports: Viewports,
-    painter: egui_wgpu::winit::Painter,
+    painter: Arc<Mutex<egui_wgpu::winit::Painter>>,
+
+    /// Set if [`NativeOptions::render_thread_per_viewport`] is enabled.
+    render_threads: Option<ViewportRenderThreadPool>,
+
     viewport_from_window: HashMap<WindowId, ViewportId>,
     focused_viewport: Option<ViewportId>,
 }
@@ -107,12 +128,41 @@ impl WgpuWinitApp {
             "EFRAME_SCREENSHOT_TO not yet implemented for wgpu backend"
         );
 
+        let pending_events = winit_integration::startup_events(&native_options, event_loop);
+
         Self {
             repaint_proxy: Arc::new(Mutex::new(event_loop.create_proxy())),
+            app_index: 0,
             app_name: app_name.to_owned(),
             native_options,
             running: None,
             app_creator: Some(app_creator),
+            pending_events,
+        }
+    }
+
+    /// Give the pending events to the root viewport, if it has been created.
+    fn deliver_pending_events(&mut self) -> Option<Rc<Window>> {
+        let running = self.running.as_ref()?;
+        let mut shared = running.shared.borrow_mut();
+        let viewport = shared.viewports.get_mut(&ViewportId::ROOT)?;
+        let (Some(window), Some(egui_winit)) = (&viewport.window, &mut viewport.egui_winit) else {
+            return None;
+        };
+        for event in self.pending_events.drain(..) {
+            egui_winit.push_event(event);
+        }
+        Some(window.clone())
+    }
+
+    /// The app was asked to open a URL or file: tell the app and show the root viewport.
+    fn on_open_request(&mut self, event: egui::Event) -> EventResult {
+        self.pending_events.push(event);
+        if let Some(window) = self.deliver_pending_events() {
+            bring_to_front(&window);
+            EventResult::RepaintNext(window.id())
+        } else {
+            EventResult::Wait
         }
     }
 
@@ -134,7 +184,7 @@ impl WgpuWinitApp {
                 event_loop,
                 &running.integration.egui_ctx,
                 viewport_from_window,
-                painter,
+                &mut painter.lock(),
             );
         }
     }
@@ -144,7 +194,11 @@ impl WgpuWinitApp {
         if let Some(running) = &mut self.running {
             let mut shared = running.shared.borrow_mut();
             shared.viewports.remove(&ViewportId::ROOT);
-            pollster::block_on(shared.painter.set_window(ViewportId::ROOT, None))?;
+            if let Some(render_threads) = &mut shared.render_threads {
+                // Stop all render threads before their surfaces are destroyed:
+                render_threads.gc_viewports(&ViewportIdSet::default());
+            }
+            pollster::block_on(shared.painter.lock().set_window(ViewportId::ROOT, None))?;
         }
         Ok(())
     }
@@ -161,7 +215,10 @@ impl WgpuWinitApp {
 
         #[allow(unsafe_code, unused_mut, unused_unsafe)]
         let mut painter = egui_wgpu::winit::Painter::new(
-            self.native_options.wgpu_options.clone(),
+            egui_wgpu::WgpuConfiguration {
+                color_management: self.native_options.color_management,
+                ..self.native_options.wgpu_options.clone()
+            },
             self.native_options.multisampling.max(1) as _,
             egui_wgpu::depth_format_from_bits(
                 self.native_options.depth_buffer,
@@ -178,6 +235,8 @@ impl WgpuWinitApp {
         let wgpu_render_state = painter.render_state();
 
         let system_theme = winit_integration::system_theme(&window, &self.native_options);
+        #[cfg(feature = "webview")]
+        let webview_backend = self.native_options.webview_backend.take();
         let integration = EpiIntegration::new(
             egui_ctx.clone(),
             &window,
@@ -188,10 +247,13 @@ impl WgpuWinitApp {
             #[cfg(feature = "glow")]
             None,
             wgpu_render_state.clone(),
+            #[cfg(feature = "webview")]
+            webview_backend,
         );
 
         {
             let event_loop_proxy = self.repaint_proxy.clone();
+            let app_index = self.app_index;
 
             egui_ctx.set_request_repaint_callback(move |info| {
                 log::trace!("request_repaint_callback: {info:?}");
@@ -201,6 +263,7 @@ impl WgpuWinitApp {
                 event_loop_proxy
                     .lock()
                     .send_event(UserEvent::RequestRepaint {
+                        app_index,
                         when,
                         frame_nr,
                         viewport_id: info.viewport_id,
@@ -218,6 +281,9 @@ impl WgpuWinitApp {
             painter.max_texture_side(),
         );
 
+        #[cfg(target_os = "android")]
+        egui_winit.set_android_app(self.native_options.android_app.clone());
+
         #[cfg(feature = "accesskit")]
         {
             let event_loop_proxy = self.repaint_proxy.lock().clone();
@@ -265,11 +331,18 @@ impl WgpuWinitApp {
             },
         );
 
+        let painter = Arc::new(Mutex::new(painter));
+        let render_threads = self
+            .native_options
+            .render_thread_per_viewport
+            .then(|| ViewportRenderThreadPool::new(painter.clone()));
+
         let shared = Rc::new(RefCell::new(SharedState {
             egui_ctx,
             viewport_from_window,
             viewports,
             painter,
+            render_threads,
             focused_viewport: Some(ViewportId::ROOT),
         }));
 
@@ -303,6 +376,10 @@ impl WgpuWinitApp {
 }
 
 impl WinitApp for WgpuWinitApp {
+    fn set_app_index(&mut self, app_index: usize) {
+        self.app_index = app_index;
+    }
+
     fn frame_nr(&self, viewport_id: ViewportId) -> u64 {
         self.running
             .as_ref()
@@ -350,6 +427,11 @@ impl WinitApp for WgpuWinitApp {
         )
     }
 
+    fn viewport_id_from_window_id(&self, window_id: WindowId) -> Option<ViewportId> {
+        let shared = self.running.as_ref()?.shared.try_borrow().ok()?;
+        shared.viewport_from_window.get(&window_id).copied()
+    }
+
     fn save_and_destroy(&mut self) {
         if let Some(mut running) = self.running.take() {
             running.save_and_destroy();
@@ -403,13 +485,13 @@ impl WinitApp for WgpuWinitApp {
                     self.init_run_state(egui_ctx, event_loop, storage, window, builder)?
                 };
 
-                EventResult::RepaintNow(
-                    running.shared.borrow().viewports[&ViewportId::ROOT]
-                        .window
-                        .as_ref()
-                        .unwrap()
-                        .id(),
-                )
+                let window_id = running.shared.borrow().viewports[&ViewportId::ROOT]
+                    .window
+                    .as_ref()
+                    .unwrap()
+                    .id();
+                self.deliver_pending_events();
+                EventResult::RepaintNow(window_id)
             }
 
             winit::event::Event::Suspended => {
@@ -426,6 +508,54 @@ impl WinitApp for WgpuWinitApp {
                 }
             }
 
+            winit::event::Event::DeviceEvent {
+                event: winit::event::DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                if let Some(running) = &mut self.running {
+                    let mut shared_lock = running.shared.borrow_mut();
+                    let SharedState {
+                        viewports,
+                        focused_viewport,
+                        ..
+                    } = &mut *shared_lock;
+                    // Device events are not tied to a window, so send them to the focused viewport:
+                    if let Some(viewport) = focused_viewport.and_then(|id| viewports.get_mut(&id)) {
+                        if let (Some(window), Some(egui_winit)) =
+                            (&viewport.window, &mut viewport.egui_winit)
+                        {
+                            egui_winit.on_mouse_motion(*delta);
+                            return Ok(EventResult::RepaintNext(window.id()));
+                        }
+                    }
+                }
+                EventResult::Wait
+            }
+
+            winit::event::Event::UserEvent(UserEvent::InstanceArgs(args)) => {
+                if let Some(running) = &self.running {
+                    let mut shared = running.shared.borrow_mut();
+                    if let Some(viewport) = shared.viewports.get_mut(&ViewportId::ROOT) {
+                        if let (Some(window), Some(egui_winit)) =
+                            (&viewport.window, &mut viewport.egui_winit)
+                        {
+                            egui_winit.push_event(egui::Event::InstanceArgs(args.clone()));
+                            bring_to_front(window);
+                            return Ok(EventResult::RepaintNext(window.id()));
+                        }
+                    }
+                }
+                EventResult::Wait
+            }
+
+            winit::event::Event::UserEvent(UserEvent::DeepLink(url)) => {
+                self.on_open_request(egui::Event::DeepLink(url.clone()))
+            }
+
+            winit::event::Event::UserEvent(UserEvent::OpenFile(path)) => {
+                self.on_open_request(egui::Event::OpenFile(path.clone()))
+            }
+
             #[cfg(feature = "accesskit")]
             winit::event::Event::UserEvent(UserEvent::AccessKitActionRequest(
                 accesskit_winit::ActionRequestEvent { request, window_id },
@@ -472,7 +602,9 @@ impl WgpuWinitRunning {
         #[cfg(not(feature = "glow"))]
         self.app.on_exit();
 
-        shared.painter.destroy();
+        // Stop the render threads before destroying the painter:
+        shared.render_threads = None;
+        shared.painter.lock().destroy();
     }
 
     /// This is called both for the root viewport, and all deferred viewports
@@ -492,12 +624,25 @@ impl WgpuWinitRunning {
         #[cfg(feature = "puffin")]
         puffin::GlobalProfiler::lock().new_frame();
 
+        // Each profiler frame is the painting of one viewport:
+        crate::profile_scope!("viewport", format!("{viewport_id:?}"));
+        crate::trace_span!(
+            "frame",
+            viewport = ?viewport_id,
+            frame_nr = self.integration.egui_ctx.frame_nr_for(viewport_id)
+        );
+
         let Self {
             app,
             integration,
             shared,
         } = self;
 
+        let mut timings = egui::FrameTimings::default();
+        let input_start = Instant::now();
+
+        let viewports_before: ViewportIdSet = shared.borrow().viewports.keys().copied().collect();
+
         let (viewport_ui_cb, raw_input) = {
             crate::profile_scope!("Prepare");
             let mut shared_lock = shared.borrow_mut();
@@ -544,7 +689,8 @@ impl WgpuWinitRunning {
 
             {
                 crate::profile_scope!("set_window");
-                if let Err(err) = pollster::block_on(painter.set_window(viewport_id, Some(window)))
+                if let Err(err) =
+                    pollster::block_on(painter.lock().set_window(viewport_id, Some(window)))
                 {
                     log::warn!("Failed to set window: {err}");
                 }
@@ -566,9 +712,13 @@ impl WgpuWinitRunning {
 
         // ------------------------------------------------------------
 
+        timings.input = input_start.elapsed().as_secs_f32();
+
         // Runs the update, which could call immediate viewports,
         // so make sure we hold no locks here!
+        let update_start = Instant::now();
         let full_output = integration.update(app.as_mut(), viewport_ui_cb.as_deref(), raw_input);
+        timings.update = update_start.elapsed().as_secs_f32();
 
         // ------------------------------------------------------------
 
@@ -578,6 +728,7 @@ impl WgpuWinitRunning {
             egui_ctx,
             viewports,
             painter,
+            render_threads,
             viewport_from_window,
             focused_viewport,
         } = &mut *shared;
@@ -587,6 +738,7 @@ impl WgpuWinitRunning {
         };
 
         viewport.info.events.clear(); // they should have been processed
+        let is_deferred = viewport.viewport_ui_cb.is_some();
 
         let Viewport {
             window: Some(window),
@@ -599,6 +751,12 @@ impl WgpuWinitRunning {
 
         integration.post_update();
 
+        for (viewport_id, samples) in integration.frame.msaa_requests.drain(..) {
+            painter
+                .lock()
+                .set_msaa_samples(viewport_id, samples.max(1) as _);
+        }
+
         let FullOutput {
             platform_output,
             textures_delta,
@@ -607,20 +765,61 @@ impl WgpuWinitRunning {
             viewport_output,
         } = full_output;
 
-        egui_winit.handle_platform_output(window, platform_output);
+        #[cfg(feature = "webview")]
+        integration.update_webviews(
+            viewport_id,
+            window,
+            pixels_per_point,
+            &platform_output.webviews,
+        );
+
+        {
+            crate::trace_span!("platform_output");
+            egui_winit.handle_platform_output(window, platform_output);
+        }
 
         {
-            let clipped_primitives = egui_ctx.tessellate(shapes, pixels_per_point);
+            let tessellate_start = Instant::now();
+            let clipped_primitives = {
+                crate::trace_span!("tessellate");
+                egui_ctx.tessellate(shapes, pixels_per_point)
+            };
+            timings.tessellate = tessellate_start.elapsed().as_secs_f32();
 
+            // With wgpu, presenting is part of painting, so `timings.swap` stays zero.
+            // On a render thread, only queueing the frame is measured.
+            let paint_start = Instant::now();
             let screenshot_requested = std::mem::take(&mut viewport.screenshot_requested);
-            let screenshot = painter.paint_and_update_textures(
-                viewport_id,
-                pixels_per_point,
-                app.clear_color(&egui_ctx.style().visuals),
-                &clipped_primitives,
-                &textures_delta,
-                screenshot_requested,
-            );
+            let clear_color = app.clear_color(&egui_ctx.style().visuals);
+            let screenshot = match render_threads {
+                Some(render_threads) if is_deferred && !screenshot_requested => {
+                    render_threads.paint(
+                        viewport_id,
+                        RenderJob {
+                            pixels_per_point,
+                            clear_color,
+                            clipped_primitives,
+                            textures_delta,
+                        },
+                    );
+                    None
+                }
+                _ => {
+                    crate::trace_span!("paint");
+                    if let Some(render_threads) = render_threads {
+                        // Screenshots are taken here, after the queued frames:
+                        render_threads.wait(viewport_id);
+                    }
+                    painter.lock().paint_and_update_textures(
+                        viewport_id,
+                        pixels_per_point,
+                        clear_color,
+                        &clipped_primitives,
+                        &textures_delta,
+                        screenshot_requested,
+                    )
+                }
+            };
             if let Some(screenshot) = screenshot {
                 egui_winit
                     .egui_input_mut()
@@ -630,7 +829,12 @@ impl WgpuWinitRunning {
                         image: screenshot.into(),
                     });
             }
+            egui_winit.on_frame_presented();
+            timings.paint = paint_start.elapsed().as_secs_f32();
         }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(?timings, "Painted frame");
+        egui_ctx.report_frame_timings(viewport_id, timings);
 
         integration.post_rendering(window);
 
@@ -646,7 +850,23 @@ impl WgpuWinitRunning {
         // Prune dead viewports:
         viewports.retain(|id, _| active_viewports_ids.contains(id));
         viewport_from_window.retain(|_, id| active_viewports_ids.contains(id));
-        painter.gc_viewports(&active_viewports_ids);
+        if let Some(render_threads) = render_threads {
+            render_threads.gc_viewports(&active_viewports_ids);
+        }
+        painter.lock().gc_viewports(&active_viewports_ids);
+
+        epi_integration::notify_viewport_changes(
+            app.as_mut(),
+            &viewports_before,
+            viewports
+                .iter()
+                .map(|(id, viewport)| (*id, &viewport.builder)),
+        );
+        integration.record_open_viewports(viewports.iter().filter_map(|(id, viewport)| {
+            Some((*id, &viewport.builder, viewport.window.as_deref()?))
+        }));
+        #[cfg(feature = "webview")]
+        integration.retain_webviews(&viewport_from_window.keys().copied().collect::<Vec<_>>());
 
         let window = viewport_from_window
             .get(&window_id)
@@ -718,7 +938,10 @@ impl WgpuWinitRunning {
                         NonZeroU32::new(physical_size.height),
                     ) {
                         repaint_asap = true;
-                        shared.painter.on_window_resized(viewport_id, width, height);
+                        shared
+                            .painter
+                            .lock()
+                            .on_window_resized(viewport_id, width, height);
                     }
                 }
             }
@@ -830,7 +1053,10 @@ fn create_window(
 ) -> Result<(Window, ViewportBuilder), winit::error::OsError> {
     crate::profile_function!();
 
-    let window_settings = epi_integration::load_window_settings(storage);
+    let window_settings = epi_integration::load_window_settings(
+        storage,
+        egui_winit::monitor_layout_hash(event_loop.available_monitors()),
+    );
     let viewport_builder = epi_integration::viewport_builder(
         egui_ctx.zoom_factor(),
         event_loop,
@@ -858,6 +1084,8 @@ fn render_immediate_viewport(
         viewport_ui_cb,
     } = immediate_viewport;
 
+    crate::trace_span!("frame", viewport = ?ids.this, immediate = true);
+
     let input = {
         let SharedState {
             egui_ctx,
@@ -877,7 +1105,12 @@ fn render_immediate_viewport(
             None,
         );
         if viewport.window.is_none() {
-            viewport.initialize_window(event_loop, egui_ctx, viewport_from_window, painter);
+            viewport.initialize_window(
+                event_loop,
+                egui_ctx,
+                viewport_from_window,
+                &mut painter.lock(),
+            );
         }
 
         let (Some(window), Some(egui_winit)) = (&viewport.window, &mut viewport.egui_winit) else {
@@ -928,6 +1161,7 @@ fn render_immediate_viewport(
         return;
     };
 
+    let mut painter = painter.lock();
     {
         crate::profile_scope!("set_window");
         if let Err(err) = pollster::block_on(painter.set_window(ids.this, Some(window))) {
@@ -947,6 +1181,8 @@ fn render_immediate_viewport(
         &textures_delta,
         false,
     );
+    drop(painter);
+    egui_winit.on_frame_presented();
 
     egui_winit.handle_platform_output(window, platform_output);
 
@@ -1,7 +1,7 @@
 use std::{rc::Rc, time::Instant};
 
 use winit::{
-    event_loop::EventLoopWindowTarget,
+    event_loop::{EventLoop, EventLoopWindowTarget},
     window::{Window, WindowId},
 };
 
@@ -38,6 +38,9 @@ pub fn create_egui_context(storage: Option<&dyn crate::Storage>) -> egui::Contex
 pub enum UserEvent {
     /// A repaint is requested.
     RequestRepaint {
+        /// Which app requested it, when there are several, see [`crate::MultiApp`].
+        app_index: usize,
+
         /// What to repaint.
         viewport_id: ViewportId,
 
@@ -48,6 +51,17 @@ pub enum UserEvent {
         frame_nr: u64,
     },
 
+    /// Another instance of the app was started with these command line arguments,
+    /// see [`crate::NativeOptions::single_instance`].
+    InstanceArgs(Vec<String>),
+
+    /// The app was asked to open a URL using one of its schemes,
+    /// see [`crate::NativeOptions::url_schemes`].
+    DeepLink(String),
+
+    /// The app was asked to open a file, see [`crate::NativeOptions::open_files`].
+    OpenFile(std::path::PathBuf),
+
     /// A request related to [`accesskit`](https://accesskit.dev/).
     #[cfg(feature = "accesskit")]
     AccessKitActionRequest(accesskit_winit::ActionRequestEvent),
@@ -61,6 +75,11 @@ impl From<accesskit_winit::ActionRequestEvent> for UserEvent {
 }
 
 pub trait WinitApp {
+    /// The index of this app among those sharing the event loop, see [`crate::MultiApp`].
+    ///
+    /// This is sent along with its [`UserEvent::RequestRepaint`]s.
+    fn set_app_index(&mut self, app_index: usize);
+
     /// The current frame number, as reported by egui.
     fn frame_nr(&self, viewport_id: ViewportId) -> u64;
 
@@ -72,6 +91,25 @@ pub trait WinitApp {
 
     fn window_id_from_viewport_id(&self, id: ViewportId) -> Option<WindowId>;
 
+    /// `None` if the window is not ours, or if we are busy with it right now.
+    fn viewport_id_from_window_id(&self, window_id: WindowId) -> Option<ViewportId>;
+
+    /// The window to repaint for a [`UserEvent::RequestRepaint`],
+    /// or `None` if we have already repainted since it was requested.
+    fn window_to_repaint(
+        &self,
+        _app_index: usize,
+        viewport_id: ViewportId,
+        frame_nr: u64,
+    ) -> Option<WindowId> {
+        let current_frame_nr = self.frame_nr(viewport_id);
+        if current_frame_nr == frame_nr || current_frame_nr == frame_nr + 1 {
+            self.window_id_from_viewport_id(viewport_id)
+        } else {
+            None // old request - we've already repainted
+        }
+    }
+
     fn save_and_destroy(&mut self);
 
     fn run_ui_and_paint(
@@ -109,6 +147,31 @@ pub enum EventResult {
     Exit,
 }
 
+/// Start listening for deep links, files to open and clicks in the dock menu.
+///
+/// Returns the ones in our own command line arguments,
+/// which should be given to the root viewport once it is created.
+pub fn startup_events(
+    native_options: &crate::NativeOptions,
+    event_loop: &EventLoop<UserEvent>,
+) -> Vec<egui::Event> {
+    super::dock::start(event_loop);
+    let deep_links = super::url_scheme::start(native_options, event_loop);
+    let files = super::open_files::start(native_options, event_loop);
+    deep_links
+        .into_iter()
+        .map(egui::Event::DeepLink)
+        .chain(files.into_iter().map(egui::Event::OpenFile))
+        .collect()
+}
+
+/// Show the window and give it focus, e.g. when another instance of the app was started.
+pub fn bring_to_front(window: &Window) {
+    window.set_visible(true);
+    window.set_minimized(false);
+    window.focus_window();
+}
+
 pub fn system_theme(window: &Window, options: &crate::NativeOptions) -> Option<crate::Theme> {
     if options.follow_system_theme {
         window
@@ -125,6 +188,9 @@ pub fn short_event_description(event: &winit::event::Event<UserEvent>) -> &'stat
     match event {
         winit::event::Event::UserEvent(user_event) => match user_event {
             UserEvent::RequestRepaint { .. } => "UserEvent::RequestRepaint",
+            UserEvent::InstanceArgs(_) => "UserEvent::InstanceArgs",
+            UserEvent::DeepLink(_) => "UserEvent::DeepLink",
+            UserEvent::OpenFile(_) => "UserEvent::OpenFile",
             #[cfg(feature = "accesskit")]
             UserEvent::AccessKitActionRequest(_) => "UserEvent::AccessKitActionRequest",
         },
@@ -0,0 +1,354 @@
+//! Expose the [`accesskit`] tree of egui to screen readers on the web.
+//!
+//! There is no AccessKit adapter for the web, so we mirror the tree into invisible DOM elements
+//! with ARIA roles and attributes, which is what browsers expose to screen readers.
+//! Clicks and focus changes on those elements (e.g. from a screen reader) are sent back to egui
+//! as [`accesskit::ActionRequest`]s, see [`super::events::install_accesskit_events`].
+
+use std::collections::{HashMap, HashSet};
+
+use egui::accesskit::{self, Checked, NodeId, Role};
+use wasm_bindgen::prelude::*;
+
+/// The attribute we store the [`NodeId`] in, so we can map DOM events back to nodes.
+const NODE_ID_ATTRIBUTE: &str = "data-accesskit-id";
+
+/// Mirrors an [`accesskit`] tree into the DOM.
+pub(crate) struct AccessKitDom {
+    /// Visually hidden container for the tree, placed right after the canvas.
+    container: web_sys::HtmlElement,
+
+    root: Option<NodeId>,
+    focus: Option<NodeId>,
+    elements: HashMap<NodeId, web_sys::HtmlElement>,
+    children: HashMap<NodeId, Vec<NodeId>>,
+}
+
+impl AccessKitDom {
+    pub fn new(canvas: &web_sys::HtmlCanvasElement) -> Result<Self, JsValue> {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let container: web_sys::HtmlElement = document.create_element("div")?.dyn_into()?;
+
+        // Hide it visually, but not from screen readers:
+        let style = container.style();
+        style.set_property("position", "absolute")?;
+        style.set_property("width", "1px")?;
+        style.set_property("height", "1px")?;
+        style.set_property("overflow", "hidden")?;
+        style.set_property("clip-path", "inset(50%)")?;
+        style.set_property("white-space", "nowrap")?;
+        style.set_property("pointer-events", "none")?;
+
+        canvas.after_with_node_1(&container)?;
+
+        Ok(Self {
+            container,
+            root: None,
+            focus: None,
+            elements: Default::default(),
+            children: Default::default(),
+        })
+    }
+
+    /// The element containing the whole mirrored tree.
+    pub fn container(&self) -> &web_sys::HtmlElement {
+        &self.container
+    }
+
+    pub fn update(&mut self, update: accesskit::TreeUpdate) {
+        crate::profile_function!();
+
+        let accesskit::TreeUpdate { nodes, tree, focus } = update;
+
+        let mut removed = vec![];
+
+        for (id, node) in &nodes {
+            if node.role() == Role::InlineTextBox {
+                continue; // The contents of text inputs are exposed as a whole instead
+            }
+
+            let element = match self.elements.get(id) {
+                Some(element) => element.clone(),
+                None => match create_element(*id) {
+                    Ok(element) => {
+                        self.elements.insert(*id, element.clone());
+                        element
+                    }
+                    Err(err) => {
+                        log::error!(
+                            "Failed to create accessibility element: {}",
+                            super::string_from_js_value(&err)
+                        );
+                        continue;
+                    }
+                },
+            };
+
+            if let Err(err) = set_attributes(&element, node) {
+                log::error!(
+                    "Failed to update accessibility element: {}",
+                    super::string_from_js_value(&err)
+                );
+            }
+
+            let children = if is_text_leaf(node.role()) {
+                vec![]
+            } else {
+                node.children().to_vec()
+            };
+            if let Some(old_children) = self.children.insert(*id, children) {
+                removed.extend(old_children);
+            }
+        }
+
+        // Now that all elements exist we can put them in the right order.
+        // This also moves children from their old parent, if they were moved.
+        let mut still_used = HashSet::new();
+        for (id, _) in &nodes {
+            if let (Some(element), Some(children)) = (self.elements.get(id), self.children.get(id))
+            {
+                for child in children {
+                    still_used.insert(*child);
+                    if let Some(child_element) = self.elements.get(child) {
+                        element.append_child(child_element).ok();
+                    }
+                }
+            }
+        }
+
+        for id in removed {
+            if !still_used.contains(&id) {
+                self.remove_subtree(id);
+            }
+        }
+
+        if let Some(tree) = tree {
+            if self.root != Some(tree.root) {
+                if let Some(old_root) = self.root.replace(tree.root) {
+                    self.remove_subtree(old_root);
+                }
+                if let Some(root_element) = self.elements.get(&tree.root) {
+                    self.container.append_child(root_element).ok();
+                }
+            }
+        }
+
+        self.set_focus(focus);
+    }
+
+    fn remove_subtree(&mut self, id: NodeId) {
+        if let Some(element) = self.elements.remove(&id) {
+            element.remove();
+        }
+        for child in self.children.remove(&id).unwrap_or_default() {
+            self.remove_subtree(child);
+        }
+    }
+
+    fn set_focus(&mut self, focus: NodeId) {
+        if self.focus == Some(focus) {
+            return;
+        }
+        self.focus = Some(focus);
+
+        if Some(focus) == self.root {
+            return;
+        }
+
+        if let Some(element) = self.elements.get(&focus) {
+            // Text input is handled by the text agent, which needs to keep the focus.
+            let is_text_input = element.get_attribute("role").as_deref() == Some("textbox");
+            if !is_text_input {
+                // Moving the DOM focus is what makes the screen reader announce the widget.
+                element.focus().ok();
+            }
+        }
+    }
+}
+
+impl Drop for AccessKitDom {
+    fn drop(&mut self) {
+        self.container.remove();
+    }
+}
+
+/// The [`NodeId`] of the element the event was targeted at, if any.
+pub(crate) fn node_id_from_event(event: &web_sys::Event) -> Option<NodeId> {
+    let element: web_sys::Element = event.target()?.dyn_into().ok()?;
+    let id = element.get_attribute(NODE_ID_ATTRIBUTE)?.parse().ok()?;
+    Some(NodeId(id))
+}
+
+fn create_element(id: NodeId) -> Result<web_sys::HtmlElement, JsValue> {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let element: web_sys::HtmlElement = document.create_element("div")?.dyn_into()?;
+    element.set_attribute(NODE_ID_ATTRIBUTE, &id.0.to_string())?;
+    element.set_tab_index(-1); // So that we can focus it
+    Ok(element)
+}
+
+/// Leaf nodes whose text we set as the contents of the element.
+fn is_text_leaf(role: Role) -> bool {
+    role == Role::StaticText || is_text_input(role)
+}
+
+fn is_text_input(role: Role) -> bool {
+    matches!(
+        role,
+        Role::TextInput
+            | Role::MultilineTextInput
+            | Role::SearchInput
+            | Role::EmailInput
+            | Role::NumberInput
+            | Role::PasswordInput
+            | Role::PhoneNumberInput
+            | Role::UrlInput
+    )
+}
+
+fn set_attributes(element: &web_sys::HtmlElement, node: &accesskit::Node) -> Result<(), JsValue> {
+    let role = node.role();
+
+    set_or_remove_attribute(element, "role", aria_role(role))?;
+
+    if role == Role::StaticText {
+        set_text_if_changed(element, node.name().unwrap_or_default());
+        set_or_remove_attribute(element, "aria-label", None)?;
+    } else {
+        set_or_remove_attribute(element, "aria-label", node.name())?;
+    }
+
+    if is_text_input(role) {
+        set_text_if_changed(element, node.value().unwrap_or_default());
+        set_or_remove_attribute(
+            element,
+            "aria-multiline",
+            (role == Role::MultilineTextInput).then_some("true"),
+        )?;
+        set_or_remove_attribute(element, "aria-placeholder", node.placeholder())?;
+    }
+
+    set_or_remove_attribute(element, "aria-description", node.description())?;
+
+    if matches!(
+        role,
+        Role::Slider | Role::SpinButton | Role::ProgressIndicator | Role::ScrollBar | Role::Meter
+    ) {
+        let number = |value: Option<f64>| value.map(|value| value.to_string());
+        set_or_remove_attribute(
+            element,
+            "aria-valuenow",
+            number(node.numeric_value()).as_deref(),
+        )?;
+        set_or_remove_attribute(
+            element,
+            "aria-valuemin",
+            number(node.min_numeric_value()).as_deref(),
+        )?;
+        set_or_remove_attribute(
+            element,
+            "aria-valuemax",
+            number(node.max_numeric_value()).as_deref(),
+        )?;
+        set_or_remove_attribute(element, "aria-valuetext", node.value())?;
+    }
+
+    let checked = node.checked().map(|checked| match checked {
+        Checked::False => "false",
+        Checked::True => "true",
+        Checked::Mixed => "mixed",
+    });
+    if role == Role::ToggleButton {
+        set_or_remove_attribute(element, "aria-pressed", checked)?;
+    } else {
+        set_or_remove_attribute(element, "aria-checked", checked)?;
+    }
+
+    let boolean = |value: bool| value.then_some("true");
+    set_or_remove_attribute(element, "aria-disabled", boolean(node.is_disabled()))?;
+    set_or_remove_attribute(element, "aria-hidden", boolean(node.is_hidden()))?;
+    set_or_remove_attribute(
+        element,
+        "aria-expanded",
+        node.is_expanded()
+            .map(|expanded| if expanded { "true" } else { "false" }),
+    )?;
+    set_or_remove_attribute(
+        element,
+        "aria-selected",
+        node.is_selected()
+            .map(|selected| if selected { "true" } else { "false" }),
+    )?;
+
+    Ok(())
+}
+
+/// Only touch the DOM if something changed, so we don't spam the screen reader with updates.
+fn set_or_remove_attribute(
+    element: &web_sys::HtmlElement,
+    name: &str,
+    value: Option<&str>,
+) -> Result<(), JsValue> {
+    let current = element.get_attribute(name);
+    match value {
+        Some(value) if current.as_deref() != Some(value) => element.set_attribute(name, value),
+        None if current.is_some() => element.remove_attribute(name),
+        _ => Ok(()),
+    }
+}
+
+fn set_text_if_changed(element: &web_sys::HtmlElement, text: &str) {
+    if element.text_content().as_deref() != Some(text) {
+        element.set_text_content(Some(text));
+    }
+}
+
+/// The closest [ARIA role](https://www.w3.org/TR/wai-aria/#role_definitions) for an AccessKit role.
+fn aria_role(role: Role) -> Option<&'static str> {
+    Some(match role {
+        Role::Alert => "alert",
+        Role::AlertDialog => "alertdialog",
+        Role::Application => "application",
+        Role::Button | Role::DefaultButton | Role::ToggleButton => "button",
+        Role::Cell => "cell",
+        Role::CheckBox => "checkbox",
+        Role::ColumnHeader => "columnheader",
+        Role::ComboBox | Role::EditableComboBox => "combobox",
+        Role::Dialog => "dialog",
+        Role::Grid => "grid",
+        Role::Group | Role::Window => "group",
+        Role::Heading => "heading",
+        Role::Image => "img",
+        Role::Link => "link",
+        Role::List => "list",
+        Role::ListBox => "listbox",
+        Role::ListBoxOption => "option",
+        Role::ListItem => "listitem",
+        Role::Menu => "menu",
+        Role::MenuBar => "menubar",
+        Role::MenuItem => "menuitem",
+        Role::MenuItemCheckBox => "menuitemcheckbox",
+        Role::MenuItemRadio => "menuitemradio",
+        Role::Meter => "meter",
+        Role::ProgressIndicator => "progressbar",
+        Role::RadioButton => "radio",
+        Role::RadioGroup => "radiogroup",
+        Role::Row => "row",
+        Role::RowHeader => "rowheader",
+        Role::ScrollBar => "scrollbar",
+        Role::Slider => "slider",
+        Role::SpinButton => "spinbutton",
+        Role::Status => "status",
+        Role::Switch => "switch",
+        Role::Tab => "tab",
+        Role::TabList => "tablist",
+        Role::TabPanel => "tabpanel",
+        Role::Table => "table",
+        Role::Toolbar => "toolbar",
+        Role::Tooltip => "tooltip",
+        Role::Tree => "tree",
+        Role::TreeItem => "treeitem",
+        role if is_text_input(role) => "textbox",
+        _ => return None,
+    })
+}
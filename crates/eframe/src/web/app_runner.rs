@@ -16,7 +16,14 @@ pub struct AppRunner {
     pub(crate) ime: Option<egui::output::IMEOutput>,
     pub(crate) mutable_text_under_cursor: bool,
 
+    /// Have we already warned that the app asked for non-embedded viewports?
+    warned_about_native_viewports: bool,
+
+    #[cfg(feature = "accesskit")]
+    pub(crate) accesskit: Option<super::accesskit_dom::AccessKitDom>,
+
     // Output for the last run:
+    frame_timings: egui::FrameTimings,
     textures_delta: TexturesDelta,
     clipped_primitives: Option<Vec<egui::ClippedPrimitive>>,
 }
@@ -82,6 +89,14 @@ impl AppRunner {
             wgpu_render_state: None,
         });
 
+        let needs_repaint: std::sync::Arc<NeedRepaint> = Default::default();
+        {
+            let needs_repaint = needs_repaint.clone();
+            egui_ctx.set_request_repaint_callback(move |info| {
+                needs_repaint.repaint_after(info.delay.as_secs_f64());
+            });
+        }
+
         let frame = epi::Frame {
             info,
             storage: Some(Box::new(storage)),
@@ -93,15 +108,22 @@ impl AppRunner {
             wgpu_render_state: painter.render_state(),
             #[cfg(all(feature = "wgpu", feature = "glow"))]
             wgpu_render_state: None,
+
+            picked_files: Default::default(),
+            needs_repaint: needs_repaint.clone(),
+            install_prompt: None,
         };
 
-        let needs_repaint: std::sync::Arc<NeedRepaint> = Default::default();
-        {
-            let needs_repaint = needs_repaint.clone();
-            egui_ctx.set_request_repaint_callback(move |info| {
-                needs_repaint.repaint_after(info.delay.as_secs_f64());
-            });
-        }
+        #[cfg(feature = "accesskit")]
+        let accesskit = if web_options.accesskit {
+            egui_ctx.enable_accesskit();
+            let canvas = super::canvas_element_or_die(canvas_id);
+            let accesskit = super::accesskit_dom::AccessKitDom::new(&canvas)
+                .map_err(|err| super::string_from_js_value(&err))?;
+            Some(accesskit)
+        } else {
+            None
+        };
 
         let mut runner = Self {
             web_options,
@@ -114,6 +136,10 @@ impl AppRunner {
             last_save_time: now_sec(),
             ime: None,
             mutable_text_under_cursor: false,
+            warned_about_native_viewports: false,
+            #[cfg(feature = "accesskit")]
+            accesskit,
+            frame_timings: Default::default(),
             textures_delta: Default::default(),
             clipped_primitives: None,
         };
@@ -183,11 +209,21 @@ impl AppRunner {
 
         super::resize_canvas_to_screen_size(self.canvas_id(), self.web_options.max_size_points);
         let canvas_size = super::canvas_size_in_points(self.canvas_id());
+        self.input
+            .raw
+            .dropped_files
+            .append(&mut self.frame.picked_files.borrow_mut());
         let raw_input = self.input.new_frame(canvas_size);
 
+        // The web has no native windows, so we always fall back to embedded viewports:
+        self.ensure_embedded_viewports();
+
+        let update_start = now_sec();
+        self.frame_timings.input = (update_start - frame_start) as f32;
         let full_output = self.egui_ctx.run(raw_input, |egui_ctx| {
             self.app.update(egui_ctx, &mut self.frame);
         });
+        self.frame_timings.update = (now_sec() - update_start) as f32;
         let egui::FullOutput {
             platform_output,
             textures_delta,
@@ -196,21 +232,27 @@ impl AppRunner {
             viewport_output,
         } = full_output;
 
-        if viewport_output.len() > 1 {
-            log::warn!("Multiple viewports not yet supported on the web");
-        }
-        for viewport_output in viewport_output.values() {
-            for command in &viewport_output.commands {
-                // TODO(emilk): handle some of the commands
-                log::warn!(
-                    "Unhandled egui viewport command: {command:?} - not implemented in web backend"
-                );
+        // The web page is the root viewport.
+        // All other viewports are embedded in it (see `egui::Context::embed_viewports`),
+        // so they show up as e.g. `egui::Window`s and never produce any output of their own.
+        for (viewport_id, viewport_output) in &viewport_output {
+            if *viewport_id == egui::ViewportId::ROOT {
+                for command in &viewport_output.commands {
+                    self.handle_viewport_command(command);
+                }
+            } else {
+                // The app turned off `embed_viewports` during `update`.
+                // We can't show this viewport now, but it will be embedded next frame.
+                self.ensure_embedded_viewports();
             }
         }
 
         self.handle_platform_output(platform_output);
         self.textures_delta.append(textures_delta);
+
+        let tessellate_start = now_sec();
         self.clipped_primitives = Some(self.egui_ctx.tessellate(shapes, pixels_per_point));
+        self.frame_timings.tessellate = (now_sec() - tessellate_start) as f32;
 
         self.frame.info.cpu_usage = Some((now_sec() - frame_start) as f32);
     }
@@ -221,6 +263,7 @@ impl AppRunner {
         let clipped_primitives = std::mem::take(&mut self.clipped_primitives);
 
         if let Some(clipped_primitives) = clipped_primitives {
+            let paint_start = now_sec();
             if let Err(err) = self.painter.paint_and_update_textures(
                 self.app.clear_color(&self.egui_ctx.style().visuals),
                 &clipped_primitives,
@@ -229,26 +272,48 @@ impl AppRunner {
             ) {
                 log::error!("Failed to paint: {}", super::string_from_js_value(&err));
             }
+            let paint_end = now_sec();
+            self.egui_ctx
+                .on_frame_presented(egui::ViewportId::ROOT, paint_end);
+
+            // The browser presents the frame after we return, so there is no `swap` time.
+            self.frame_timings.paint = (paint_end - paint_start) as f32;
+            self.egui_ctx.report_frame_timings(
+                egui::ViewportId::ROOT,
+                std::mem::take(&mut self.frame_timings),
+            );
         }
     }
 
     fn handle_platform_output(&mut self, platform_output: egui::PlatformOutput) {
         #[cfg(feature = "web_screen_reader")]
         if self.egui_ctx.options(|o| o.screen_reader) {
-            super::screen_reader::speak(&platform_output.events_description());
+            super::screen_reader::speak_all(&platform_output.speech());
         }
 
         let egui::PlatformOutput {
             cursor_icon,
             open_url,
+            open_path: _,              // no file system access on the web
+            reveal_in_file_manager: _, // no file system access on the web
+            play_system_sound: _,      // the browser has no system sounds
+            haptic,
             copied_text,
-            events: _, // already handled
+            events: _,        // already handled
+            announcements: _, // already handled
             mutable_text_under_cursor,
             ime,
+            virtual_keyboard: _, // the text agent shows the keyboard, see `mutable_text_under_cursor`
+            webviews: _,         // native web views are not supported on the web
             #[cfg(feature = "accesskit")]
-                accesskit_update: _, // not currently implemented
+            accesskit_update,
         } = platform_output;
 
+        #[cfg(feature = "accesskit")]
+        if let (Some(accesskit), Some(update)) = (&mut self.accesskit, accesskit_update) {
+            accesskit.update(update);
+        }
+
         super::set_cursor_icon(cursor_icon);
         if let Some(open) = open_url {
             super::open_url(&open.url, open.new_tab);
@@ -262,6 +327,10 @@ impl AppRunner {
         #[cfg(not(web_sys_unstable_apis))]
         let _ = copied_text;
 
+        if let Some(haptic) = haptic {
+            super::vibrate(haptic);
+        }
+
         self.mutable_text_under_cursor = mutable_text_under_cursor;
 
         if self.ime != ime {
@@ -269,6 +338,55 @@ impl AppRunner {
             self.ime = ime;
         }
     }
+
+    /// Turn on [`egui::Context::embed_viewports`] if the app turned it off,
+    /// warning (once) that native viewports are not supported on the web.
+    fn ensure_embedded_viewports(&mut self) {
+        if self.egui_ctx.embed_viewports() {
+            return;
+        }
+        if !self.warned_about_native_viewports {
+            log::warn!(
+                "Native viewports are not supported on the web - falling back to embedded viewports"
+            );
+            self.warned_about_native_viewports = true;
+        }
+        self.egui_ctx.set_embed_viewports(true);
+        self.egui_ctx.request_repaint();
+    }
+
+    fn handle_viewport_command(&self, command: &egui::ViewportCommand) {
+        use egui::ViewportCommand;
+
+        match command {
+            ViewportCommand::Title(title) => {
+                super::set_document_title(title);
+            }
+            ViewportCommand::Fullscreen(fullscreen) => {
+                super::set_fullscreen(self.canvas_id(), *fullscreen);
+            }
+            ViewportCommand::SetFullscreen(mode) => {
+                // The browser only has one kind of fullscreen:
+                super::set_fullscreen(self.canvas_id(), mode.is_some());
+            }
+            ViewportCommand::Focus => {
+                if let Some(canvas) = super::canvas_element(self.canvas_id()) {
+                    canvas.focus().ok();
+                }
+            }
+            ViewportCommand::CursorIcon(_)
+            | ViewportCommand::IMERect(_)
+            | ViewportCommand::IMEAllowed(_)
+            | ViewportCommand::IMEPurpose(_) => {
+                // Already applied to the `PlatformOutput` by egui
+            }
+            _ => {
+                log::warn!(
+                    "Unhandled egui viewport command: {command:?} - not supported on the web"
+                );
+            }
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
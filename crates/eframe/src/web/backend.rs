@@ -28,11 +28,17 @@ impl WebInput {
             time: Some(super::now_sec()),
             ..self.raw.take()
         };
-        raw_input
+        let viewport_info = raw_input
             .viewports
             .entry(egui::ViewportId::ROOT)
-            .or_default()
-            .native_pixels_per_point = Some(super::native_pixels_per_point());
+            .or_default();
+        viewport_info.native_pixels_per_point = Some(super::native_pixels_per_point());
+        viewport_info.title = web_sys::window()
+            .and_then(|window| window.document())
+            .map(|document| document.title());
+        viewport_info.fullscreen = super::is_fullscreen();
+        viewport_info.focused = Some(raw_input.focused);
+        viewport_info.virtual_keyboard_inset = virtual_keyboard_inset();
         raw_input
     }
 
@@ -45,6 +51,22 @@ impl WebInput {
     }
 }
 
+/// How much of the bottom of the page is covered by the on-screen keyboard, in CSS pixels.
+///
+/// The visual viewport shrinks when the keyboard is shown, but the layout viewport does not.
+fn virtual_keyboard_inset() -> Option<f32> {
+    // `web_sys` doesn't expose `window.visualViewport` (yet), so we look it up dynamically:
+    let get = |object: &wasm_bindgen::JsValue, key: &str| {
+        js_sys::Reflect::get(object, &wasm_bindgen::JsValue::from_str(key)).ok()
+    };
+    let window = web_sys::window()?;
+    let visual_viewport = get(&window, "visualViewport")?;
+    let visible_height = get(&visual_viewport, "height")?.as_f64()?;
+    let offset_top = get(&visual_viewport, "offsetTop")?.as_f64()?;
+    let layout_height = window.inner_height().ok()?.as_f64()?;
+    Some((layout_height - offset_top - visible_height).max(0.0) as f32)
+}
+
 // ----------------------------------------------------------------------------
 
 /// Stores when to do the next repaint.
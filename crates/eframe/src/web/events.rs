@@ -16,7 +16,8 @@ fn paint_and_schedule(runner_ref: &WebRunner) -> Result<(), JsValue> {
 }
 
 fn paint_if_needed(runner: &mut AppRunner) {
-    if runner.needs_repaint.needs_repaint() {
+    // Don't waste battery on a hidden tab. We repaint when it becomes visible again.
+    if runner.needs_repaint.needs_repaint() && !is_page_hidden() {
         if runner.has_outstanding_paint_data() {
             // We have already run the logic, e.g. in an on-click event,
             // so let's only present the results:
@@ -53,6 +54,20 @@ pub(crate) fn request_animation_frame(runner_ref: WebRunner) -> Result<(), JsVal
 pub(crate) fn install_document_events(runner_ref: &WebRunner) -> Result<(), JsValue> {
     let document = web_sys::window().unwrap().document().unwrap();
 
+    runner_ref.add_event_listener(
+        &document,
+        "visibilitychange",
+        |_: web_sys::Event, runner| {
+            if is_page_hidden() {
+                // On mobile, this may be the last event we get before the page is killed,
+                // so this is the most reliable place to save.
+                runner.save();
+            } else {
+                runner.needs_repaint.repaint_asap();
+            }
+        },
+    )?;
+
     {
         // Avoid sticky modifier keys on alt-tab:
         for event_name in ["blur", "focus"] {
@@ -87,11 +102,13 @@ pub(crate) fn install_document_events(runner_ref: &WebRunner) -> Result<(), JsVa
 
             let key = event.key();
             let egui_key = translate_key(&key);
+            let physical_key = translate_code(&event.code());
 
             if let Some(key) = egui_key {
                 runner.input.raw.events.push(egui::Event::Key {
                     key,
-                    physical_key: None, // TODO
+                    physical_key,
+                    scancode: None,
                     pressed: true,
                     repeat: false, // egui will fill this in for us!
                     modifiers,
@@ -158,7 +175,8 @@ pub(crate) fn install_document_events(runner_ref: &WebRunner) -> Result<(), JsVa
             if let Some(key) = translate_key(&event.key()) {
                 runner.input.raw.events.push(egui::Event::Key {
                     key,
-                    physical_key: None, // TODO
+                    physical_key: translate_code(&event.code()),
+                    scancode: None,
                     pressed: false,
                     repeat: false,
                     modifiers,
@@ -169,11 +187,16 @@ pub(crate) fn install_document_events(runner_ref: &WebRunner) -> Result<(), JsVa
     )?;
 
     #[cfg(web_sys_unstable_apis)]
-    runner_ref.add_event_listener(
-        &document,
-        "paste",
-        |event: web_sys::ClipboardEvent, runner| {
+    runner_ref.add_event_listener(&document, "paste", {
+        let runner_ref = runner_ref.clone();
+
+        move |event: web_sys::ClipboardEvent, runner| {
             if let Some(data) = event.clipboard_data() {
+                // Pasted images etc. show up as dropped files:
+                if let Some(files) = data.files() {
+                    load_dropped_files(&runner_ref, &files);
+                }
+
                 if let Ok(text) = data.get_data("text") {
                     let text = text.replace("\r\n", "\n");
                     if !text.is_empty() {
@@ -184,8 +207,8 @@ pub(crate) fn install_document_events(runner_ref: &WebRunner) -> Result<(), JsVa
                     event.prevent_default();
                 }
             }
-        },
-    )?;
+        }
+    })?;
 
     #[cfg(web_sys_unstable_apis)]
     runner_ref.add_event_listener(
@@ -232,10 +255,28 @@ pub(crate) fn install_window_events(runner_ref: &WebRunner) -> Result<(), JsValu
     let window = web_sys::window().unwrap();
 
     // Save-on-close
-    runner_ref.add_event_listener(&window, "onbeforeunload", |_: web_sys::Event, runner| {
+    runner_ref.add_event_listener(&window, "beforeunload", |_: web_sys::Event, runner| {
         runner.save();
     })?;
 
+    // Chromium-based browsers fire this when the page can be installed as an app.
+    // We hold on to the event so the app can decide when to show the prompt,
+    // see `epi::Frame::show_install_prompt`.
+    runner_ref.add_event_listener(
+        &window,
+        "beforeinstallprompt",
+        |event: web_sys::Event, runner| {
+            event.prevent_default(); // Don't show the browser's own install banner
+            runner.frame.install_prompt = Some(event);
+            runner.needs_repaint.repaint_asap();
+        },
+    )?;
+
+    runner_ref.add_event_listener(&window, "appinstalled", |_: web_sys::Event, runner| {
+        runner.frame.install_prompt = None;
+        runner.needs_repaint.repaint_asap();
+    })?;
+
     for event_name in &["load", "pagehide", "pageshow", "resize"] {
         runner_ref.add_event_listener(&window, event_name, |_: web_sys::Event, runner| {
             runner.needs_repaint.repaint_asap();
@@ -250,6 +291,88 @@ pub(crate) fn install_window_events(runner_ref: &WebRunner) -> Result<(), JsValu
     Ok(())
 }
 
+/// Repaint whenever `devicePixelRatio` changes, e.g. when the user zooms the page
+/// or moves the browser window to a monitor with a different DPI.
+///
+/// On the next frame the canvas backing store is resized, [`egui::ViewportInfo::native_pixels_per_point`]
+/// is updated, and egui re-rasterizes the fonts for the new scale, so that text stays crisp.
+///
+/// There is no event for this, so we listen to a media query matching the _current_ ratio,
+/// and install a new one each time it stops matching.
+pub(crate) fn install_pixel_ratio_change_event(runner_ref: &WebRunner) -> Result<(), JsValue> {
+    let window = web_sys::window().unwrap();
+    let query = format!("(resolution: {}dppx)", window.device_pixel_ratio());
+    let Some(media_query_list) = window.match_media(&query)? else {
+        return Ok(());
+    };
+
+    let closure = Closure::once_into_js({
+        let runner_ref = runner_ref.clone();
+        move || {
+            let Some(runner) = runner_ref.try_lock() else {
+                return; // We have panicked or been destroyed
+            };
+            log::debug!(
+                "devicePixelRatio changed to {}",
+                web_sys::window().unwrap().device_pixel_ratio()
+            );
+            runner.needs_repaint.repaint_asap();
+            drop(runner);
+
+            if let Err(err) = install_pixel_ratio_change_event(&runner_ref) {
+                log::error!(
+                    "Failed to listen for devicePixelRatio changes: {}",
+                    string_from_js_value(&err)
+                );
+            }
+        }
+    });
+    media_query_list.add_event_listener_with_callback_and_add_event_listener_options(
+        "change",
+        closure.unchecked_ref(),
+        web_sys::AddEventListenerOptions::new().once(true),
+    )?;
+
+    Ok(())
+}
+
+/// Forward clicks and focus changes on the accessibility elements
+/// (e.g. from a screen reader) to egui.
+#[cfg(feature = "accesskit")]
+pub(crate) fn install_accesskit_events(runner_ref: &WebRunner) -> Result<(), JsValue> {
+    use egui::accesskit::{Action, ActionRequest};
+
+    let Some(container) = runner_ref
+        .try_lock()
+        .and_then(|runner| Some(runner.accesskit.as_ref()?.container().clone()))
+    else {
+        return Ok(());
+    };
+
+    for (event_name, action) in [("click", Action::Default), ("focusin", Action::Focus)] {
+        runner_ref.add_event_listener(
+            &container,
+            event_name,
+            move |event: web_sys::Event, runner| {
+                if let Some(target) = super::accesskit_dom::node_id_from_event(&event) {
+                    runner
+                        .input
+                        .raw
+                        .events
+                        .push(egui::Event::AccessKitActionRequest(ActionRequest {
+                            action,
+                            target,
+                            data: None,
+                        }));
+                    runner.needs_repaint.repaint_asap();
+                }
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
 pub(crate) fn install_color_scheme_change_event(runner_ref: &WebRunner) -> Result<(), JsValue> {
     let window = web_sys::window().unwrap();
 
@@ -330,6 +453,21 @@ pub(crate) fn install_canvas_events(runner_ref: &WebRunner) -> Result<(), JsValu
         },
     )?;
 
+    for event_name in ["pointerdown", "pointermove", "pointerup"] {
+        runner_ref.add_event_listener(
+            &canvas,
+            event_name,
+            |event: web_sys::PointerEvent, runner| {
+                // Pens also send the usual mouse events, so we only report the extra pen info here.
+                if event.pointer_type() == "pen" {
+                    let pen = pen_from_pointer_event(runner.canvas_id(), &event);
+                    runner.input.raw.events.push(egui::Event::Pen(pen));
+                    runner.needs_repaint.repaint_asap();
+                }
+            },
+        )?;
+    }
+
     runner_ref.add_event_listener(&canvas, "mouseup", |event: web_sys::MouseEvent, runner| {
         if let Some(button) = button_from_mouse_event(&event) {
             let pos = pos_from_mouse_event(runner.canvas_id(), &event);
@@ -522,45 +660,7 @@ pub(crate) fn install_canvas_events(runner_ref: &WebRunner) -> Result<(), JsValu
                 runner.needs_repaint.repaint_asap();
 
                 if let Some(files) = data_transfer.files() {
-                    for i in 0..files.length() {
-                        if let Some(file) = files.get(i) {
-                            let name = file.name();
-                            let mime = file.type_();
-                            let last_modified = std::time::UNIX_EPOCH
-                                + std::time::Duration::from_millis(file.last_modified() as u64);
-
-                            log::debug!("Loading {:?} ({} bytes)…", name, file.size());
-
-                            let future = wasm_bindgen_futures::JsFuture::from(file.array_buffer());
-
-                            let runner_ref = runner_ref.clone();
-                            let future = async move {
-                                match future.await {
-                                    Ok(array_buffer) => {
-                                        let bytes = js_sys::Uint8Array::new(&array_buffer).to_vec();
-                                        log::debug!("Loaded {:?} ({} bytes).", name, bytes.len());
-
-                                        if let Some(mut runner_lock) = runner_ref.try_lock() {
-                                            runner_lock.input.raw.dropped_files.push(
-                                                egui::DroppedFile {
-                                                    name,
-                                                    mime,
-                                                    last_modified: Some(last_modified),
-                                                    bytes: Some(bytes.into()),
-                                                    ..Default::default()
-                                                },
-                                            );
-                                            runner_lock.needs_repaint.repaint_asap();
-                                        }
-                                    }
-                                    Err(err) => {
-                                        log::error!("Failed to read file: {:?}", err);
-                                    }
-                                }
-                            };
-                            wasm_bindgen_futures::spawn_local(future);
-                        }
-                    }
+                    load_dropped_files(&runner_ref, &files);
                 }
                 event.stop_propagation();
                 event.prevent_default();
@@ -570,3 +670,18 @@ pub(crate) fn install_canvas_events(runner_ref: &WebRunner) -> Result<(), JsValu
 
     Ok(())
 }
+
+/// Load the given files in the background and hand them to egui as [`egui::DroppedFile`]s.
+fn load_dropped_files(runner_ref: &WebRunner, files: &web_sys::FileList) {
+    for i in 0..files.length() {
+        if let Some(file) = files.get(i) {
+            let runner_ref = runner_ref.clone();
+            load_file(&file, move |file| {
+                if let Some(mut runner_lock) = runner_ref.try_lock() {
+                    runner_lock.input.raw.dropped_files.push(file);
+                    runner_lock.needs_repaint.repaint_asap();
+                }
+            });
+        }
+    }
+}
@@ -9,6 +9,24 @@ pub fn pos_from_mouse_event(canvas_id: &str, event: &web_sys::MouseEvent) -> egu
     }
 }
 
+pub fn pen_from_pointer_event(canvas_id: &str, event: &web_sys::PointerEvent) -> egui::PenInput {
+    const ERASER_BUTTON: u16 = 32;
+    egui::PenInput {
+        pos: pos_from_mouse_event(canvas_id, event),
+        pressure: event.pressure(),
+        tilt: Some(egui::vec2(
+            (event.tilt_x() as f32).to_radians(),
+            (event.tilt_y() as f32).to_radians(),
+        )),
+        twist: Some((event.twist() as f32).to_radians()),
+        tool: if event.buttons() & ERASER_BUTTON != 0 {
+            egui::PenTool::Eraser
+        } else {
+            egui::PenTool::Pen
+        },
+    }
+}
+
 pub fn button_from_mouse_event(event: &web_sys::MouseEvent) -> Option<egui::PointerButton> {
     match event.button() {
         0 => Some(egui::PointerButton::Primary),
@@ -115,6 +133,16 @@ pub fn translate_key(key: &str) -> Option<egui::Key> {
     egui::Key::from_name(key)
 }
 
+/// Translate a [`KeyboardEvent.code`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/code),
+/// which names the physical key, e.g. `"KeyW"` or `"Digit1"`.
+pub fn translate_code(code: &str) -> Option<egui::Key> {
+    let name = code
+        .strip_prefix("Key")
+        .or_else(|| code.strip_prefix("Digit"))
+        .unwrap_or(code);
+    egui::Key::from_name(name)
+}
+
 pub fn modifiers_from_event(event: &web_sys::KeyboardEvent) -> egui::Modifiers {
     egui::Modifiers {
         alt: event.alt_key(),
@@ -2,6 +2,8 @@
 
 #![allow(clippy::missing_errors_doc)] // So many `-> Result<_, JsValue>`
 
+#[cfg(feature = "accesskit")]
+mod accesskit_dom;
 mod app_runner;
 mod backend;
 mod events;
@@ -111,6 +113,39 @@ fn canvas_element_or_die(canvas_id: &str) -> web_sys::HtmlCanvasElement {
         .unwrap_or_else(|| panic!("Failed to find canvas with id {canvas_id:?}"))
 }
 
+/// Is the page in a background tab, minimized, or otherwise not visible?
+fn is_page_hidden() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .map_or(false, |document| document.hidden())
+}
+
+fn set_document_title(title: &str) -> Option<()> {
+    web_sys::window()?.document()?.set_title(title);
+    Some(())
+}
+
+fn is_fullscreen() -> Option<bool> {
+    Some(
+        web_sys::window()?
+            .document()?
+            .fullscreen_element()
+            .is_some(),
+    )
+}
+
+fn set_fullscreen(canvas_id: &str, fullscreen: bool) -> Option<()> {
+    if fullscreen {
+        canvas_element(canvas_id)?.request_fullscreen().ok()
+    } else {
+        let document = web_sys::window()?.document()?;
+        if document.fullscreen_element().is_some() {
+            document.exit_fullscreen();
+        }
+        Some(())
+    }
+}
+
 fn canvas_origin(canvas_id: &str) -> egui::Pos2 {
     let rect = canvas_element(canvas_id)
         .unwrap()
@@ -209,6 +244,140 @@ fn set_clipboard_text(s: &str) {
     }
 }
 
+/// Put data of the given MIME type on the clipboard, using the async Clipboard API.
+///
+/// We go through [`js_sys::Reflect`] since `ClipboardItem` is not (yet) part of `web-sys`.
+pub(crate) fn set_clipboard_data(mime: &str, bytes: &[u8]) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("no window")?;
+    let clipboard = js_sys::Reflect::get(&window.navigator(), &"clipboard".into())?;
+    let clipboard_item = js_sys::Reflect::get(&window, &"ClipboardItem".into())?;
+    if clipboard.is_undefined() || clipboard_item.is_undefined() {
+        return Err("the Clipboard API is not available (are you on https?)".into());
+    }
+
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(
+        &js_sys::Array::of1(&js_sys::Uint8Array::from(bytes)),
+        web_sys::BlobPropertyBag::new().type_(mime),
+    )?;
+    let items = js_sys::Object::new();
+    js_sys::Reflect::set(&items, &mime.into(), &blob)?;
+    let item =
+        js_sys::Reflect::construct(clipboard_item.unchecked_ref(), &js_sys::Array::of1(&items))?;
+
+    let write: js_sys::Function = js_sys::Reflect::get(&clipboard, &"write".into())?.dyn_into()?;
+    let promise: js_sys::Promise = write
+        .call1(&clipboard, &js_sys::Array::of1(&item))?
+        .dyn_into()?;
+    let future = wasm_bindgen_futures::JsFuture::from(promise);
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = future.await {
+            log::error!("Copy action failed: {}", string_from_js_value(&err));
+        }
+    });
+    Ok(())
+}
+
+/// Call `prompt()` on a deferred `beforeinstallprompt` event.
+///
+/// `BeforeInstallPromptEvent` is non-standard, so it is not part of `web-sys`.
+pub(crate) fn show_install_prompt(event: &web_sys::Event) -> Result<(), JsValue> {
+    let prompt: js_sys::Function = js_sys::Reflect::get(event, &"prompt".into())?.dyn_into()?;
+    prompt.call0(event)?;
+    Ok(())
+}
+
+/// Let the user download `bytes` as a file called `file_name`.
+pub(crate) fn download(bytes: &[u8], file_name: &str) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("no document")?;
+
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&js_sys::Array::of1(
+        &js_sys::Uint8Array::from(bytes),
+    ))?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let anchor: web_sys::HtmlElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_attribute("href", &url)?;
+    anchor.set_attribute("download", file_name)?;
+    anchor.click();
+
+    // Revoking the url right away can cancel the download in some browsers (e.g. Firefox and Safari):
+    let revoke = Closure::once_into_js(move || {
+        web_sys::Url::revoke_object_url(&url).ok();
+    });
+    web_sys::window()
+        .ok_or("no window")?
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            revoke.unchecked_ref(),
+            DOWNLOAD_URL_LIFETIME_MS,
+        )?;
+    Ok(())
+}
+
+/// How long the object url of a download is kept alive, in milliseconds.
+const DOWNLOAD_URL_LIFETIME_MS: i32 = 60_000;
+
+/// Open the file picker of the browser and call `on_pick` once the picked file has been loaded.
+///
+/// Nothing happens if the user cancels.
+pub(crate) fn pick_file(
+    accept: &str,
+    on_pick: impl FnOnce(egui::DroppedFile) + 'static,
+) -> Option<()> {
+    let document = web_sys::window()?.document()?;
+    let input: web_sys::HtmlInputElement =
+        document.create_element("input").ok()?.dyn_into().ok()?;
+    input.set_type("file");
+    input.set_accept(accept);
+
+    let on_change = Closure::once_into_js({
+        let input = input.clone();
+        move || {
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                load_file(&file, on_pick);
+            }
+        }
+    });
+    input
+        .add_event_listener_with_callback("change", on_change.unchecked_ref())
+        .ok()?;
+    input.click();
+    Some(())
+}
+
+/// Read the contents of a file in the background, then call `on_load` with it.
+fn load_file(file: &web_sys::File, on_load: impl FnOnce(egui::DroppedFile) + 'static) {
+    let name = file.name();
+    let mime = file.type_();
+    let last_modified =
+        std::time::UNIX_EPOCH + std::time::Duration::from_millis(file.last_modified() as u64);
+
+    log::debug!("Loading {:?} ({} bytes)…", name, file.size());
+
+    let future = wasm_bindgen_futures::JsFuture::from(file.array_buffer());
+    let future = async move {
+        match future.await {
+            Ok(array_buffer) => {
+                let bytes = js_sys::Uint8Array::new(&array_buffer).to_vec();
+                log::debug!("Loaded {:?} ({} bytes).", name, bytes.len());
+
+                on_load(egui::DroppedFile {
+                    name,
+                    mime,
+                    last_modified: Some(last_modified),
+                    bytes: Some(bytes.into()),
+                    ..Default::default()
+                });
+            }
+            Err(err) => {
+                log::error!("Failed to read file: {:?}", err);
+            }
+        }
+    };
+    wasm_bindgen_futures::spawn_local(future);
+}
+
 fn cursor_web_name(cursor: egui::CursorIcon) -> &'static str {
     match cursor {
         egui::CursorIcon::Alias => "alias",
@@ -261,6 +430,19 @@ pub fn open_url(url: &str, new_tab: bool) -> Option<()> {
     Some(())
 }
 
+/// Give tactile feedback with the Vibration API, where supported (mostly Android browsers).
+pub fn vibrate(haptic: egui::HapticKind) -> Option<()> {
+    let milliseconds = match haptic {
+        egui::HapticKind::Selection => 5,
+        egui::HapticKind::Alignment => 10,
+        egui::HapticKind::Impact => 20,
+    };
+    web_sys::window()?
+        .navigator()
+        .vibrate_with_duration(milliseconds);
+    Some(())
+}
+
 /// e.g. "#fragment" part of "www.example.com/index.html#fragment",
 ///
 /// Percent decoded
@@ -1,21 +1,95 @@
-/// Speak the given text out loud.
+use std::cell::RefCell;
+
+use egui::{AnnouncePriority, Announcement};
+use wasm_bindgen::{closure::Closure, JsCast as _};
+
+thread_local! {
+    /// The polite announcements that have been queued but not yet said, oldest first.
+    ///
+    /// These are queued again if an assertive announcement interrupts them.
+    static PENDING: RefCell<Pending> = RefCell::default();
+}
+
+#[derive(Default)]
+struct Pending {
+    next_id: u64,
+    texts: Vec<(u64, String)>,
+}
+
+/// Speak the given text out loud, interrupting previous speech.
 pub fn speak(text: &str) {
-    if text.is_empty() {
+    speak_all(&[Announcement::new(text, AnnouncePriority::Assertive)]);
+}
+
+/// Speak these out loud, in order, see [`egui::PlatformOutput::speech`].
+///
+/// [`AnnouncePriority::Assertive`] announcements interrupt previous speech,
+/// [`AnnouncePriority::Polite`] ones are said after it.
+pub fn speak_all(announcements: &[Announcement]) {
+    if announcements.iter().all(|a| a.text.is_empty()) {
         return;
     }
 
-    if let Some(window) = web_sys::window() {
+    let Some(speech_synthesis) = web_sys::window().and_then(|w| w.speech_synthesis().ok()) else {
+        return;
+    };
+
+    for announcement in announcements {
+        let text = announcement.text.as_str();
+        if text.is_empty() {
+            continue;
+        }
         log::debug!("Speaking {text:?}");
 
-        if let Ok(speech_synthesis) = window.speech_synthesis() {
-            speech_synthesis.cancel(); // interrupt previous speech, if any
+        match announcement.priority {
+            AnnouncePriority::Assertive => {
+                speech_synthesis.cancel(); // interrupt previous speech, if any
+                utter(&speech_synthesis, text);
 
-            if let Ok(utterance) = web_sys::SpeechSynthesisUtterance::new_with_text(text) {
-                utterance.set_rate(1.0);
-                utterance.set_pitch(1.0);
-                utterance.set_volume(1.0);
-                speech_synthesis.speak(&utterance);
+                // Don't lose what was interrupted:
+                let interrupted =
+                    PENDING.with(|pending| std::mem::take(&mut pending.borrow_mut().texts));
+                for (_, text) in interrupted {
+                    utter_polite(&speech_synthesis, text);
+                }
+            }
+            AnnouncePriority::Polite => {
+                utter_polite(&speech_synthesis, text.to_owned());
             }
         }
     }
 }
+
+/// Queue the text, and remember it until it has been said.
+fn utter_polite(speech_synthesis: &web_sys::SpeechSynthesis, text: String) {
+    let Some(utterance) = utter(speech_synthesis, &text) else {
+        return;
+    };
+
+    let id = PENDING.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        let id = pending.next_id;
+        pending.next_id += 1;
+        pending.texts.push((id, text));
+        id
+    });
+
+    // Exactly one of these is fired, so the closure is only called once:
+    let on_done = Closure::once_into_js(move || {
+        PENDING.with(|pending| pending.borrow_mut().texts.retain(|(i, _)| *i != id));
+    });
+    utterance.set_onend(Some(on_done.unchecked_ref()));
+    utterance.set_onerror(Some(on_done.unchecked_ref()));
+}
+
+fn utter(
+    speech_synthesis: &web_sys::SpeechSynthesis,
+    text: &str,
+) -> Option<web_sys::SpeechSynthesisUtterance> {
+    let utterance = web_sys::SpeechSynthesisUtterance::new_with_text(text).ok()?;
+    utterance.set_rate(1.0);
+    utterance.set_pitch(1.0);
+    utterance.set_volume(1.0);
+    speech_synthesis.speak(&utterance);
+    Some(utterance)
+}
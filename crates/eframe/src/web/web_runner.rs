@@ -65,8 +65,12 @@ impl WebRunner {
             events::install_canvas_events(self)?;
             events::install_document_events(self)?;
             events::install_window_events(self)?;
+            events::install_pixel_ratio_change_event(self)?;
             super::text_agent::install_text_agent(self)?;
 
+            #[cfg(feature = "accesskit")]
+            events::install_accesskit_events(self)?;
+
             if follow_system_theme {
                 events::install_color_scheme_change_event(self)?;
             }
@@ -159,6 +163,9 @@ impl WebRunner {
                 // Cast the event to the expected event type
                 let event = event.unchecked_into::<E>();
                 closure(event, &mut runner_lock);
+
+                // Remember when the input arrived, see `egui::RawInput::event_times`:
+                runner_lock.input.raw.fill_event_times(super::now_sec());
             }
         }) as Box<dyn FnMut(web_sys::Event)>);
 
@@ -0,0 +1,236 @@
+//! Painting [`epaint::CustomShaderCallback`]s.
+
+use std::{borrow::Cow, num::NonZeroU64};
+
+use epaint::{ahash::HashMap, CustomShaderCallback, ShaderCode, MAX_SHADER_PARAMS};
+
+/// Put before the [`ShaderCode::wgsl`].
+const PRELUDE: &str = r#"
+struct ShaderRect {
+    size: vec2<f32>,
+    pixels_per_point: f32,
+    _padding: f32,
+    params: array<vec4<f32>, 16>,
+};
+@group(0) @binding(0) var<uniform> shader_rect: ShaderRect;
+"#;
+
+/// Put after the [`ShaderCode::wgsl`], calling its `shade` function.
+const ENTRY_POINTS: &str = r#"
+struct ShaderRectVertex {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// A triangle strip covering the viewport, which is set to the rect.
+@vertex
+fn shader_rect_vs(@builtin(vertex_index) index: u32) -> ShaderRectVertex {
+    let uv = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+    var out: ShaderRectVertex;
+    out.position = vec4<f32>(2.0 * uv.x - 1.0, 1.0 - 2.0 * uv.y, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn shader_rect_fs_gamma_framebuffer(in: ShaderRectVertex) -> @location(0) vec4<f32> {
+    return shade(in.uv);
+}
+
+@fragment
+fn shader_rect_fs_linear_framebuffer(in: ShaderRectVertex) -> @location(0) vec4<f32> {
+    let color = shade(in.uv);
+    let cutoff = color.rgb < vec3<f32>(0.04045);
+    let lower = color.rgb / vec3<f32>(12.92);
+    let higher = pow((color.rgb + vec3<f32>(0.055)) / vec3<f32>(1.055), vec3<f32>(2.4));
+    return vec4<f32>(select(higher, lower, cutoff), color.a);
+}
+"#;
+
+/// The uniform buffer of each painted rect.
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct ShaderRectUniforms {
+    size: [f32; 2],
+    pixels_per_point: f32,
+    _padding: f32,
+    params: [[f32; 4]; MAX_SHADER_PARAMS],
+}
+
+/// Compiled [`ShaderCode`]s, and what is needed to paint the [`CustomShaderCallback`]s of a frame.
+pub(crate) struct CustomShaders {
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+
+    /// By [`ShaderCode::id`] and sample count.
+    pipelines: HashMap<(u64, u32), wgpu::RenderPipeline>,
+
+    /// A uniform buffer for each rect, reused between frames.
+    uniforms: Vec<(wgpu::Buffer, wgpu::BindGroup)>,
+
+    /// The pipeline of each rect of the current frame, in paint order.
+    draws: Vec<(u64, u32)>,
+}
+
+impl CustomShaders {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("egui_custom_shader_bind_group_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    has_dynamic_offset: false,
+                    min_binding_size: NonZeroU64::new(
+                        std::mem::size_of::<ShaderRectUniforms>() as _
+                    ),
+                    ty: wgpu::BufferBindingType::Uniform,
+                },
+                count: None,
+            }],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("egui_custom_shader_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        Self {
+            bind_group_layout,
+            pipeline_layout,
+            pipelines: Default::default(),
+            uniforms: Vec::new(),
+            draws: Vec::new(),
+        }
+    }
+
+    /// Compile the shaders and upload the uniforms of the `callbacks` of this frame, in paint order.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        callbacks: &[(&CustomShaderCallback, epaint::Rect)],
+        pixels_per_point: f32,
+        output_color_format: wgpu::TextureFormat,
+        output_depth_format: Option<wgpu::TextureFormat>,
+        msaa_samples: u32,
+    ) {
+        crate::profile_function!();
+
+        self.draws.clear();
+        for (index, (callback, rect)) in callbacks.iter().enumerate() {
+            let key = (callback.code.id(), msaa_samples);
+            self.pipelines.entry(key).or_insert_with(|| {
+                create_pipeline(
+                    device,
+                    &self.pipeline_layout,
+                    &callback.code,
+                    output_color_format,
+                    output_depth_format,
+                    msaa_samples,
+                )
+            });
+            self.draws.push(key);
+
+            if self.uniforms.len() <= index {
+                let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("egui_custom_shader_uniforms"),
+                    size: std::mem::size_of::<ShaderRectUniforms>() as _,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("egui_custom_shader_bind_group"),
+                    layout: &self.bind_group_layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                });
+                self.uniforms.push((buffer, bind_group));
+            }
+            let uniforms = ShaderRectUniforms {
+                size: rect.size().into(),
+                pixels_per_point,
+                _padding: 0.0,
+                params: callback.padded_params(),
+            };
+            queue.write_buffer(
+                &self.uniforms[index].0,
+                0,
+                bytemuck::cast_slice(&[uniforms]),
+            );
+        }
+    }
+
+    /// Paint the rect with the given index in [`Self::prepare`], into the viewport of the render pass.
+    pub fn paint<'rp>(&'rp self, index: usize, render_pass: &mut wgpu::RenderPass<'rp>) {
+        let (Some(key), Some((_, bind_group))) = (self.draws.get(index), self.uniforms.get(index))
+        else {
+            return;
+        };
+        render_pass.set_pipeline(&self.pipelines[key]);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..4, 0..1);
+    }
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    pipeline_layout: &wgpu::PipelineLayout,
+    code: &ShaderCode,
+    output_color_format: wgpu::TextureFormat,
+    output_depth_format: Option<wgpu::TextureFormat>,
+    msaa_samples: u32,
+) -> wgpu::RenderPipeline {
+    crate::profile_function!();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("egui_custom_shader"),
+        source: wgpu::ShaderSource::Wgsl(Cow::Owned(format!(
+            "{PRELUDE}\n{}\n{ENTRY_POINTS}",
+            code.wgsl()
+        ))),
+    });
+    let fs_entry_point = if output_color_format.is_srgb() {
+        "shader_rect_fs_linear_framebuffer"
+    } else {
+        "shader_rect_fs_gamma_framebuffer"
+    };
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("egui_custom_shader_pipeline"),
+        layout: Some(pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &module,
+            entry_point: "shader_rect_vs",
+            buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleStrip,
+            ..Default::default()
+        },
+        depth_stencil: output_depth_format.map(|format| wgpu::DepthStencilState {
+            format,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: msaa_samples,
+            ..Default::default()
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: fs_entry_point,
+            targets: &[Some(wgpu::ColorTargetState {
+                format: output_color_format,
+                // Premultiplied alpha, like the rest of egui:
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        multiview: None,
+    })
+}
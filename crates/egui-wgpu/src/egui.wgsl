@@ -10,7 +10,8 @@ struct Locals {
     screen_size: vec2<f32>,
     // Uniform buffers need to be at least 16 bytes in WebGL.
     // See https://github.com/gfx-rs/wgpu/issues/2072
-    _padding: vec2<u32>,
+    gamma: f32,
+    brightness: f32,
 };
 @group(0) @binding(0) var<uniform> r_locals: Locals;
 
@@ -35,6 +36,16 @@ fn gamma_from_linear_rgba(linear_rgba: vec4<f32>) -> vec4<f32> {
     return vec4<f32>(gamma_from_linear_rgb(linear_rgba.rgb), linear_rgba.a);
 }
 
+// Apply `epaint::ColorManagement::gamma` and `brightness` to a premultiplied color in gamma space.
+fn adjust_color(color_gamma: vec4<f32>) -> vec4<f32> {
+    if color_gamma.a <= 0.0 {
+        return color_gamma;
+    }
+    let unmultiplied = color_gamma.rgb / color_gamma.a;
+    let adjusted = r_locals.brightness * pow(unmultiplied, vec3<f32>(r_locals.gamma));
+    return vec4<f32>(adjusted * color_gamma.a, color_gamma.a);
+}
+
 // [u8; 4] SRGB as u32 -> [r, g, b, a] in 0.-1
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
@@ -67,6 +78,26 @@ fn vs_main(
     return out;
 }
 
+// Like `vs_main`, but for an `epaint::InstancedMesh`, with one `epaint::InstanceTransform` per instance.
+@vertex
+fn vs_main_instanced(
+    @location(0) a_pos: vec2<f32>,
+    @location(1) a_tex_coord: vec2<f32>,
+    @location(2) a_color: u32,
+    @location(3) i_rotation: vec2<f32>, // (sin, cos), scaled
+    @location(4) i_translation: vec2<f32>,
+) -> VertexOutput {
+    let s = i_rotation.x;
+    let c = i_rotation.y;
+    let pos = vec2<f32>(c * a_pos.x - s * a_pos.y, s * a_pos.x + c * a_pos.y) + i_translation;
+
+    var out: VertexOutput;
+    out.tex_coord = a_tex_coord;
+    out.color = unpack_color(a_color);
+    out.position = position_from_screen(pos);
+    return out;
+}
+
 // Fragment shader bindings
 
 @group(1) @binding(0) var r_tex_color: texture_2d<f32>;
@@ -77,7 +108,7 @@ fn fs_main_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
     // We always have an sRGB aware texture at the moment.
     let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
     let tex_gamma = gamma_from_linear_rgba(tex_linear);
-    let out_color_gamma = in.color * tex_gamma;
+    let out_color_gamma = adjust_color(in.color * tex_gamma);
     return vec4<f32>(linear_from_gamma_rgb(out_color_gamma.rgb), out_color_gamma.a);
 }
 
@@ -86,6 +117,27 @@ fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
     // We always have an sRGB aware texture at the moment.
     let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
     let tex_gamma = gamma_from_linear_rgba(tex_linear);
-    let out_color_gamma = in.color * tex_gamma;
+    let out_color_gamma = adjust_color(in.color * tex_gamma);
     return out_color_gamma;
 }
+
+// Turn the alpha of a signed distance field into coverage, see `epaint::FontImage::distance_field`.
+fn distance_field_coverage(tex_linear: vec4<f32>) -> f32 {
+    // 0.5 is the edge. Dividing by the screen-space rate of change gives the distance in pixels:
+    let distance = tex_linear.a;
+    let width = max(fwidth(distance), 1e-4);
+    return clamp((distance - 0.5) / width + 0.5, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main_linear_framebuffer_distance_field(in: VertexOutput) -> @location(0) vec4<f32> {
+    let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
+    let out_color_gamma = adjust_color(in.color * distance_field_coverage(tex_linear));
+    return vec4<f32>(linear_from_gamma_rgb(out_color_gamma.rgb), out_color_gamma.a);
+}
+
+@fragment
+fn fs_main_gamma_framebuffer_distance_field(in: VertexOutput) -> @location(0) vec4<f32> {
+    let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
+    return adjust_color(in.color * distance_field_coverage(tex_linear));
+}
@@ -13,6 +13,8 @@ pub mod renderer;
 pub use renderer::Renderer;
 pub use renderer::{Callback, CallbackResources, CallbackTrait};
 
+mod custom_shader;
+
 /// Module for painting [`egui`](https://github.com/emilk/egui) with [`wgpu`] on [`winit`].
 #[cfg(feature = "winit")]
 pub mod winit;
@@ -130,7 +132,10 @@ impl RenderState {
             crate::profile_scope!("get_capabilities");
             surface.get_capabilities(&adapter).formats
         };
-        let target_format = crate::preferred_framebuffer_format(&capabilities)?;
+        let target_format = crate::framebuffer_format_for_blending(
+            &capabilities,
+            config.color_management.blending,
+        )?;
 
         let (device, queue) = {
             crate::profile_scope!("request_device");
@@ -139,7 +144,8 @@ impl RenderState {
                 .await?
         };
 
-        let renderer = Renderer::new(&device, target_format, depth_format, msaa_samples);
+        let mut renderer = Renderer::new(&device, target_format, depth_format, msaa_samples);
+        renderer.set_color_management(config.color_management);
 
         Ok(Self {
             adapter: Arc::new(adapter),
@@ -189,7 +195,8 @@ pub struct WgpuConfiguration {
     pub supported_backends: wgpu::Backends,
 
     /// Configuration passed on device request, given an adapter
-    pub device_descriptor: Arc<dyn Fn(&wgpu::Adapter) -> wgpu::DeviceDescriptor<'static>>,
+    pub device_descriptor:
+        Arc<dyn Fn(&wgpu::Adapter) -> wgpu::DeviceDescriptor<'static> + Send + Sync>,
 
     /// Present mode used for the primary surface.
     pub present_mode: wgpu::PresentMode,
@@ -198,7 +205,13 @@ pub struct WgpuConfiguration {
     pub power_preference: wgpu::PowerPreference,
 
     /// Callback for surface errors.
-    pub on_surface_error: Arc<dyn Fn(wgpu::SurfaceError) -> SurfaceErrorAction>,
+    pub on_surface_error: Arc<dyn Fn(wgpu::SurfaceError) -> SurfaceErrorAction + Send + Sync>,
+
+    /// How egui's colors are turned into pixels.
+    ///
+    /// The [`epaint::Blending`] decides the surface format, so it can't be changed later.
+    /// The rest can be changed with [`Renderer::set_color_management`].
+    pub color_management: epaint::ColorManagement,
 }
 
 impl std::fmt::Debug for WgpuConfiguration {
@@ -207,6 +220,7 @@ impl std::fmt::Debug for WgpuConfiguration {
             .field("supported_backends", &self.supported_backends)
             .field("present_mode", &self.present_mode)
             .field("power_preference", &self.power_preference)
+            .field("color_management", &self.color_management)
             .finish_non_exhaustive()
     }
 }
@@ -253,6 +267,8 @@ impl Default for WgpuConfiguration {
                 }
                 SurfaceErrorAction::SkipFrame
             }),
+
+            color_management: Default::default(),
         }
     }
 }
@@ -264,11 +280,32 @@ impl Default for WgpuConfiguration {
 pub fn preferred_framebuffer_format(
     formats: &[wgpu::TextureFormat],
 ) -> Result<wgpu::TextureFormat, WgpuError> {
+    framebuffer_format_for_blending(formats, epaint::Blending::Gamma)
+}
+
+/// Find the framebuffer format that gives the wanted [`epaint::Blending`].
+///
+/// Blending happens in linear space for `sRGB` formats, and in gamma space for the others.
+/// If there is no such format, the first one is returned.
+///
+/// # Errors
+/// Returns [`WgpuError::NoSurfaceFormatsAvailable`] if the given list of formats is empty.
+pub fn framebuffer_format_for_blending(
+    formats: &[wgpu::TextureFormat],
+    blending: epaint::Blending,
+) -> Result<wgpu::TextureFormat, WgpuError> {
+    let wanted: &[wgpu::TextureFormat] = match blending {
+        epaint::Blending::Gamma => &[
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::TextureFormat::Bgra8Unorm,
+        ],
+        epaint::Blending::Linear => &[
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            wgpu::TextureFormat::Bgra8UnormSrgb,
+        ],
+    };
     for &format in formats {
-        if matches!(
-            format,
-            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Bgra8Unorm
-        ) {
+        if wanted.contains(&format) {
             return Ok(format);
         }
     }
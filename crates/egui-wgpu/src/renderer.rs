@@ -2,7 +2,13 @@
 
 use std::{borrow::Cow, num::NonZeroU64, ops::Range};
 
-use epaint::{ahash::HashMap, emath::NumExt, PaintCallbackInfo, Primitive, Vertex};
+use epaint::{
+    ahash::{HashMap, HashSet},
+    emath::NumExt,
+    CustomShaderCallback, InstanceTransform, Mesh, PaintCallbackInfo, Primitive, Vertex,
+};
+
+use crate::custom_shader::CustomShaders;
 
 use wgpu;
 use wgpu::util::DeviceExt as _;
@@ -128,12 +134,24 @@ struct UniformBuffer {
     screen_size_in_points: [f32; 2],
     // Uniform buffers need to be at least 16 bytes in WebGL.
     // See https://github.com/gfx-rs/wgpu/issues/2072
-    _padding: [u32; 2],
+    gamma: f32,
+    brightness: f32,
+}
+
+impl UniformBuffer {
+    /// The initial contents of the uniform buffer.
+    const INITIAL: Self = Self {
+        screen_size_in_points: [0.0, 0.0],
+        gamma: 1.0,
+        brightness: 1.0,
+    };
 }
 
 impl PartialEq for UniformBuffer {
     fn eq(&self, other: &Self) -> bool {
         self.screen_size_in_points == other.screen_size_in_points
+            && self.gamma == other.gamma
+            && self.brightness == other.brightness
     }
 }
 
@@ -143,15 +161,174 @@ struct SlicedBuffer {
     capacity: wgpu::BufferAddress,
 }
 
+/// The render pipelines for one sample count.
+struct Pipelines {
+    regular: wgpu::RenderPipeline,
+
+    /// Used for the textures in [`Renderer::distance_field_textures`].
+    distance_field: wgpu::RenderPipeline,
+
+    /// Used for [`Primitive::Instanced`].
+    instanced: wgpu::RenderPipeline,
+    instanced_distance_field: wgpu::RenderPipeline,
+}
+
+impl Pipelines {
+    fn new(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+        pipeline_layout: &wgpu::PipelineLayout,
+        output_color_format: wgpu::TextureFormat,
+        output_depth_format: Option<wgpu::TextureFormat>,
+        msaa_samples: u32,
+    ) -> Self {
+        crate::profile_function!();
+
+        let depth_stencil = output_depth_format.map(|format| wgpu::DepthStencilState {
+            format,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        });
+
+        let (fs_entry_point, fs_distance_field_entry_point) = if output_color_format.is_srgb() {
+            log::debug!("Detected a linear (sRGBA aware) framebuffer {:?}, so blending will be in linear space", output_color_format);
+            (
+                "fs_main_linear_framebuffer",
+                "fs_main_linear_framebuffer_distance_field",
+            )
+        } else {
+            // this is what we prefer:
+            (
+                "fs_main_gamma_framebuffer",
+                "fs_main_gamma_framebuffer_distance_field",
+            )
+        };
+
+        let vertex_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: 5 * 4,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            // 0: vec2 position
+            // 1: vec2 texture coordinates
+            // 2: uint color
+            attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
+        };
+        let instance_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<InstanceTransform>() as _,
+            step_mode: wgpu::VertexStepMode::Instance,
+            // 3: vec2 rotation (sin, cos)
+            // 4: vec2 translation
+            attributes: &wgpu::vertex_attr_array![3 => Float32x2, 4 => Float32x2],
+        };
+
+        let create_pipeline = |label, instanced: bool, fs_entry_point| {
+            crate::profile_scope!("create_render_pipeline");
+            let (vs_entry_point, buffers) = if instanced {
+                (
+                    "vs_main_instanced",
+                    vec![vertex_buffer_layout.clone(), instance_buffer_layout.clone()],
+                )
+            } else {
+                ("vs_main", vec![vertex_buffer_layout.clone()])
+            };
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(pipeline_layout),
+                vertex: wgpu::VertexState {
+                    entry_point: vs_entry_point,
+                    module,
+                    buffers: &buffers,
+                },
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    unclipped_depth: false,
+                    conservative: false,
+                    cull_mode: None,
+                    front_face: wgpu::FrontFace::default(),
+                    polygon_mode: wgpu::PolygonMode::default(),
+                    strip_index_format: None,
+                },
+                depth_stencil: depth_stencil.clone(),
+                multisample: wgpu::MultisampleState {
+                    alpha_to_coverage_enabled: false,
+                    count: msaa_samples,
+                    mask: !0,
+                },
+
+                fragment: Some(wgpu::FragmentState {
+                    module,
+                    entry_point: fs_entry_point,
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: output_color_format,
+                        blend: Some(wgpu::BlendState {
+                            color: wgpu::BlendComponent {
+                                src_factor: wgpu::BlendFactor::One,
+                                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                                operation: wgpu::BlendOperation::Add,
+                            },
+                            alpha: wgpu::BlendComponent {
+                                src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
+                                dst_factor: wgpu::BlendFactor::One,
+                                operation: wgpu::BlendOperation::Add,
+                            },
+                        }),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                multiview: None,
+            })
+        };
+        Self {
+            regular: create_pipeline("egui_pipeline", false, fs_entry_point),
+            distance_field: create_pipeline(
+                "egui_distance_field_pipeline",
+                false,
+                fs_distance_field_entry_point,
+            ),
+            instanced: create_pipeline("egui_instanced_pipeline", true, fs_entry_point),
+            instanced_distance_field: create_pipeline(
+                "egui_instanced_distance_field_pipeline",
+                true,
+                fs_distance_field_entry_point,
+            ),
+        }
+    }
+
+    /// The pipeline for meshes that are `instanced` and/or use a `distance_field` texture.
+    fn get(&self, (instanced, distance_field): (bool, bool)) -> &wgpu::RenderPipeline {
+        match (instanced, distance_field) {
+            (false, false) => &self.regular,
+            (false, true) => &self.distance_field,
+            (true, false) => &self.instanced,
+            (true, true) => &self.instanced_distance_field,
+        }
+    }
+}
+
 /// Renderer for a egui based GUI.
 pub struct Renderer {
-    pipeline: wgpu::RenderPipeline,
+    module: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    output_color_format: wgpu::TextureFormat,
+    output_depth_format: Option<wgpu::TextureFormat>,
+
+    /// The pipelines for [`Self::msaa_samples`].
+    pipelines: Pipelines,
+    msaa_samples: u32,
+
+    /// Pipelines for other sample counts that were used before, see [`Self::set_msaa_samples`].
+    inactive_pipelines: HashMap<u32, Pipelines>,
 
     index_buffer: SlicedBuffer,
     vertex_buffer: SlicedBuffer,
 
+    /// The [`InstanceTransform`]s of each [`Primitive::Instanced`].
+    instance_buffer: SlicedBuffer,
+
     uniform_buffer: wgpu::Buffer,
     previous_uniform_buffer_content: UniformBuffer,
+    color_management: epaint::ColorManagement,
     uniform_bind_group: wgpu::BindGroup,
     texture_bind_group_layout: wgpu::BindGroupLayout,
 
@@ -162,6 +339,12 @@ pub struct Renderer {
     next_user_texture_id: u64,
     samplers: HashMap<epaint::textures::TextureOptions, wgpu::Sampler>,
 
+    /// Font textures storing signed distance fields, see [`epaint::FontImage::distance_field`].
+    distance_field_textures: HashSet<epaint::TextureId>,
+
+    /// For painting [`epaint::CustomShaderCallback`]s.
+    custom_shaders: CustomShaders,
+
     /// Storage for resources shared with all invocations of [`CallbackTrait`]'s methods.
     ///
     /// See also [`CallbackTrait`].
@@ -192,10 +375,7 @@ impl Renderer {
 
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("egui_uniform_buffer"),
-            contents: bytemuck::cast_slice(&[UniformBuffer {
-                screen_size_in_points: [0.0, 0.0],
-                _padding: Default::default(),
-            }]),
+            contents: bytemuck::cast_slice(&[UniformBuffer::INITIAL]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
@@ -205,7 +385,7 @@ impl Renderer {
                 label: Some("egui_uniform_bind_group_layout"),
                 entries: &[wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         has_dynamic_offset: false,
                         min_binding_size: NonZeroU64::new(std::mem::size_of::<UniformBuffer>() as _),
@@ -263,84 +443,30 @@ impl Renderer {
             push_constant_ranges: &[],
         });
 
-        let depth_stencil = output_depth_format.map(|format| wgpu::DepthStencilState {
-            format,
-            depth_write_enabled: false,
-            depth_compare: wgpu::CompareFunction::Always,
-            stencil: wgpu::StencilState::default(),
-            bias: wgpu::DepthBiasState::default(),
-        });
-
-        let pipeline = {
-            crate::profile_scope!("create_render_pipeline");
-            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("egui_pipeline"),
-                layout: Some(&pipeline_layout),
-                vertex: wgpu::VertexState {
-                    entry_point: "vs_main",
-                    module: &module,
-                    buffers: &[wgpu::VertexBufferLayout {
-                        array_stride: 5 * 4,
-                        step_mode: wgpu::VertexStepMode::Vertex,
-                        // 0: vec2 position
-                        // 1: vec2 texture coordinates
-                        // 2: uint color
-                        attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
-                    }],
-                },
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    unclipped_depth: false,
-                    conservative: false,
-                    cull_mode: None,
-                    front_face: wgpu::FrontFace::default(),
-                    polygon_mode: wgpu::PolygonMode::default(),
-                    strip_index_format: None,
-                },
-                depth_stencil,
-                multisample: wgpu::MultisampleState {
-                    alpha_to_coverage_enabled: false,
-                    count: msaa_samples,
-                    mask: !0,
-                },
-
-                fragment: Some(wgpu::FragmentState {
-                    module: &module,
-                    entry_point: if output_color_format.is_srgb() {
-                        log::warn!("Detected a linear (sRGBA aware) framebuffer {:?}. egui prefers Rgba8Unorm or Bgra8Unorm", output_color_format);
-                        "fs_main_linear_framebuffer"
-                    } else {
-                        "fs_main_gamma_framebuffer" // this is what we prefer
-                    },
-                    targets: &[Some(wgpu::ColorTargetState {
-                        format: output_color_format,
-                        blend: Some(wgpu::BlendState {
-                            color: wgpu::BlendComponent {
-                                src_factor: wgpu::BlendFactor::One,
-                                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                                operation: wgpu::BlendOperation::Add,
-                            },
-                            alpha: wgpu::BlendComponent {
-                                src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
-                                dst_factor: wgpu::BlendFactor::One,
-                                operation: wgpu::BlendOperation::Add,
-                            },
-                        }),
-                        write_mask: wgpu::ColorWrites::ALL,
-                    })],
-                }),
-                multiview: None,
-            }
-        )
-        };
+        let pipelines = Pipelines::new(
+            device,
+            &module,
+            &pipeline_layout,
+            output_color_format,
+            output_depth_format,
+            msaa_samples,
+        );
 
         const VERTEX_BUFFER_START_CAPACITY: wgpu::BufferAddress =
             (std::mem::size_of::<Vertex>() * 1024) as _;
         const INDEX_BUFFER_START_CAPACITY: wgpu::BufferAddress =
             (std::mem::size_of::<u32>() * 1024 * 3) as _;
+        const INSTANCE_BUFFER_START_CAPACITY: wgpu::BufferAddress =
+            (std::mem::size_of::<InstanceTransform>() * 1024) as _;
 
         Self {
-            pipeline,
+            module,
+            pipeline_layout,
+            output_color_format,
+            output_depth_format,
+            pipelines,
+            msaa_samples,
+            inactive_pipelines: HashMap::default(),
             vertex_buffer: SlicedBuffer {
                 buffer: create_vertex_buffer(device, VERTEX_BUFFER_START_CAPACITY),
                 slices: Vec::with_capacity(64),
@@ -351,21 +477,78 @@ impl Renderer {
                 slices: Vec::with_capacity(64),
                 capacity: INDEX_BUFFER_START_CAPACITY,
             },
-            uniform_buffer,
-            // Buffers on wgpu are zero initialized, so this is indeed its current state!
-            previous_uniform_buffer_content: UniformBuffer {
-                screen_size_in_points: [0.0, 0.0],
-                _padding: [0, 0],
+            instance_buffer: SlicedBuffer {
+                buffer: create_instance_buffer(device, INSTANCE_BUFFER_START_CAPACITY),
+                slices: Vec::with_capacity(16),
+                capacity: INSTANCE_BUFFER_START_CAPACITY,
             },
+            uniform_buffer,
+            previous_uniform_buffer_content: UniformBuffer::INITIAL,
+            color_management: Default::default(),
             uniform_bind_group,
             texture_bind_group_layout,
             textures: HashMap::default(),
             next_user_texture_id: 0,
             samplers: HashMap::default(),
+            distance_field_textures: HashSet::default(),
+            custom_shaders: CustomShaders::new(device),
             callback_resources: CallbackResources::default(),
         }
     }
 
+    /// How egui's colors are turned into pixels.
+    pub fn color_management(&self) -> epaint::ColorManagement {
+        self.color_management
+    }
+
+    /// Change how egui's colors are turned into pixels. Takes effect from the next [`Self::update_buffers`].
+    ///
+    /// The [`epaint::Blending`] is decided by the output color format the renderer was created with:
+    /// an `sRGB` format blends in linear space, other formats in gamma space.
+    /// Use [`crate::framebuffer_format_for_blending`] to pick the format.
+    pub fn set_color_management(&mut self, color_management: epaint::ColorManagement) {
+        let blending = blending_of_format(self.output_color_format);
+        if color_management.blending != blending {
+            log::warn!(
+                "{:?} blending was requested, but the output format {:?} uses {blending:?} blending",
+                color_management.blending,
+                self.output_color_format
+            );
+        }
+        self.color_management = color_management;
+    }
+
+    /// The multisample count of the render passes [`Self::render`] draws into.
+    pub fn msaa_samples(&self) -> u32 {
+        self.msaa_samples
+    }
+
+    /// Change the multisample count of the render passes [`Self::render`] draws into.
+    ///
+    /// The pipelines of each sample count are created the first time it is used, and kept around,
+    /// so it is cheap to switch back and forth, e.g. when viewports use different sample counts.
+    pub fn set_msaa_samples(&mut self, device: &wgpu::Device, msaa_samples: u32) {
+        if msaa_samples == self.msaa_samples {
+            return;
+        }
+        let pipelines = self
+            .inactive_pipelines
+            .remove(&msaa_samples)
+            .unwrap_or_else(|| {
+                Pipelines::new(
+                    device,
+                    &self.module,
+                    &self.pipeline_layout,
+                    self.output_color_format,
+                    self.output_depth_format,
+                    msaa_samples,
+                )
+            });
+        let previous = std::mem::replace(&mut self.pipelines, pipelines);
+        self.inactive_pipelines.insert(self.msaa_samples, previous);
+        self.msaa_samples = msaa_samples;
+    }
+
     /// Executes the egui renderer onto an existing wgpu renderpass.
     pub fn render<'rp>(
         &'rp self,
@@ -382,8 +565,13 @@ impl Renderer {
         // run.
         let mut needs_reset = true;
 
+        // (instanced, distance field) of the pipeline that is set, see `Self::pipeline`.
+        let mut current_pipeline = (false, false);
+
+        let mut custom_shader_index = 0;
         let mut index_buffer_slices = self.index_buffer.slices.iter();
         let mut vertex_buffer_slices = self.vertex_buffer.slices.iter();
+        let mut instance_buffer_slices = self.instance_buffer.slices.iter();
 
         for epaint::ClippedPrimitive {
             clip_rect,
@@ -399,8 +587,9 @@ impl Renderer {
                     0.0,
                     1.0,
                 );
-                render_pass.set_pipeline(&self.pipeline);
+                render_pass.set_pipeline(self.pipelines.get((false, false)));
                 render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                current_pipeline = (false, false);
                 needs_reset = false;
             }
 
@@ -409,10 +598,22 @@ impl Renderer {
 
                 if rect.width == 0 || rect.height == 0 {
                     // Skip rendering zero-sized clip areas.
-                    if let Primitive::Mesh(_) = primitive {
-                        // If this is a mesh, we need to advance the index and vertex buffer iterators:
-                        index_buffer_slices.next().unwrap();
-                        vertex_buffer_slices.next().unwrap();
+                    match primitive {
+                        Primitive::Mesh(_) => {
+                            // If this is a mesh, we need to advance the index and vertex buffer iterators:
+                            index_buffer_slices.next().unwrap();
+                            vertex_buffer_slices.next().unwrap();
+                        }
+                        Primitive::Instanced(_) => {
+                            index_buffer_slices.next().unwrap();
+                            vertex_buffer_slices.next().unwrap();
+                            instance_buffer_slices.next().unwrap();
+                        }
+                        Primitive::Callback(callback) => {
+                            if callback.callback.is::<CustomShaderCallback>() {
+                                custom_shader_index += 1;
+                            }
+                        }
                     }
                     continue;
                 }
@@ -426,6 +627,14 @@ impl Renderer {
                     let vertex_buffer_slice = vertex_buffer_slices.next().unwrap();
 
                     if let Some((_texture, bind_group)) = self.textures.get(&mesh.texture_id) {
+                        let wanted_pipeline = (
+                            false,
+                            self.distance_field_textures.contains(&mesh.texture_id),
+                        );
+                        if wanted_pipeline != current_pipeline {
+                            render_pass.set_pipeline(self.pipelines.get(wanted_pipeline));
+                            current_pipeline = wanted_pipeline;
+                        }
                         render_pass.set_bind_group(1, bind_group, &[]);
                         render_pass.set_index_buffer(
                             self.index_buffer.buffer.slice(
@@ -444,7 +653,75 @@ impl Renderer {
                         log::warn!("Missing texture: {:?}", mesh.texture_id);
                     }
                 }
+                Primitive::Instanced(instanced) => {
+                    let mesh = &instanced.mesh;
+                    let index_buffer_slice = index_buffer_slices.next().unwrap();
+                    let vertex_buffer_slice = vertex_buffer_slices.next().unwrap();
+                    let instance_buffer_slice = instance_buffer_slices.next().unwrap();
+
+                    if let Some((_texture, bind_group)) = self.textures.get(&mesh.texture_id) {
+                        let wanted_pipeline = (
+                            true,
+                            self.distance_field_textures.contains(&mesh.texture_id),
+                        );
+                        if wanted_pipeline != current_pipeline {
+                            render_pass.set_pipeline(self.pipelines.get(wanted_pipeline));
+                            current_pipeline = wanted_pipeline;
+                        }
+                        render_pass.set_bind_group(1, bind_group, &[]);
+                        render_pass.set_index_buffer(
+                            self.index_buffer.buffer.slice(
+                                index_buffer_slice.start as u64..index_buffer_slice.end as u64,
+                            ),
+                            wgpu::IndexFormat::Uint32,
+                        );
+                        render_pass.set_vertex_buffer(
+                            0,
+                            self.vertex_buffer.buffer.slice(
+                                vertex_buffer_slice.start as u64..vertex_buffer_slice.end as u64,
+                            ),
+                        );
+                        render_pass.set_vertex_buffer(
+                            1,
+                            self.instance_buffer.buffer.slice(
+                                instance_buffer_slice.start as u64
+                                    ..instance_buffer_slice.end as u64,
+                            ),
+                        );
+                        render_pass.draw_indexed(
+                            0..mesh.indices.len() as u32,
+                            0,
+                            0..instanced.transforms.len() as u32,
+                        );
+                    } else {
+                        log::warn!("Missing texture: {:?}", mesh.texture_id);
+                    }
+                }
                 Primitive::Callback(callback) => {
+                    if callback.callback.is::<CustomShaderCallback>() {
+                        let viewport_px = PaintCallbackInfo {
+                            viewport: callback.rect,
+                            clip_rect: *clip_rect,
+                            pixels_per_point,
+                            screen_size_px: size_in_pixels,
+                        }
+                        .viewport_in_pixels();
+                        if viewport_px.width_px > 0 && viewport_px.height_px > 0 {
+                            needs_reset = true;
+                            render_pass.set_viewport(
+                                viewport_px.left_px as f32,
+                                viewport_px.top_px as f32,
+                                viewport_px.width_px as f32,
+                                viewport_px.height_px as f32,
+                                0.0,
+                                1.0,
+                            );
+                            self.custom_shaders.paint(custom_shader_index, render_pass);
+                        }
+                        custom_shader_index += 1;
+                        continue;
+                    }
+
                     let Some(cbfn) = callback.callback.downcast_ref::<Callback>() else {
                         // We already warned in the `prepare` callback
                         continue;
@@ -604,11 +881,19 @@ impl Renderer {
             let origin = wgpu::Origin3d::ZERO;
             queue_write_data_to_texture(&texture, origin);
             self.textures.insert(id, (Some(texture), bind_group));
+
+            if matches!(&image_delta.image, epaint::ImageData::Font(image) if image.distance_field)
+            {
+                self.distance_field_textures.insert(id);
+            } else {
+                self.distance_field_textures.remove(&id);
+            }
         };
     }
 
     pub fn free_texture(&mut self, id: &epaint::TextureId) {
         self.textures.remove(id);
+        self.distance_field_textures.remove(id);
     }
 
     /// Get the WGPU texture and bind group associated to a texture that has been allocated by egui.
@@ -774,7 +1059,8 @@ impl Renderer {
 
         let uniform_buffer_content = UniformBuffer {
             screen_size_in_points,
-            _padding: Default::default(),
+            gamma: self.color_management.gamma,
+            brightness: self.color_management.brightness,
         };
         if uniform_buffer_content != self.previous_uniform_buffer_content {
             crate::profile_scope!("update uniforms");
@@ -788,23 +1074,40 @@ impl Renderer {
 
         // Determine how many vertices & indices need to be rendered, and gather prepare callbacks
         let mut callbacks = Vec::new();
-        let (vertex_count, index_count) = {
+        let mut custom_shader_callbacks = Vec::new();
+        let (vertex_count, index_count, instance_count) = {
             crate::profile_scope!("count_vertices_indices");
-            paint_jobs.iter().fold((0, 0), |acc, clipped_primitive| {
-                match &clipped_primitive.primitive {
-                    Primitive::Mesh(mesh) => {
-                        (acc.0 + mesh.vertices.len(), acc.1 + mesh.indices.len())
-                    }
-                    Primitive::Callback(callback) => {
-                        if let Some(c) = callback.callback.downcast_ref::<Callback>() {
-                            callbacks.push(c.0.as_ref());
-                        } else {
-                            log::warn!("Unknown paint callback: expected `egui_wgpu::Callback`");
-                        };
-                        acc
-                    }
-                }
-            })
+            paint_jobs
+                .iter()
+                .fold(
+                    (0, 0, 0),
+                    |acc, clipped_primitive| match &clipped_primitive.primitive {
+                        Primitive::Mesh(mesh) => (
+                            acc.0 + mesh.vertices.len(),
+                            acc.1 + mesh.indices.len(),
+                            acc.2,
+                        ),
+                        Primitive::Instanced(instanced) => (
+                            acc.0 + instanced.mesh.vertices.len(),
+                            acc.1 + instanced.mesh.indices.len(),
+                            acc.2 + instanced.transforms.len(),
+                        ),
+                        Primitive::Callback(callback) => {
+                            if let Some(c) = callback.callback.downcast_ref::<Callback>() {
+                                callbacks.push(c.0.as_ref());
+                            } else if let Some(c) =
+                                callback.callback.downcast_ref::<CustomShaderCallback>()
+                            {
+                                custom_shader_callbacks.push((c, callback.rect));
+                            } else {
+                                log::warn!(
+                                    "Unknown paint callback: expected `egui_wgpu::Callback`"
+                                );
+                            };
+                            acc
+                        }
+                    },
+                )
         };
 
         if index_count > 0 {
@@ -827,18 +1130,13 @@ impl Renderer {
                 )
                 .expect("Failed to create staging buffer for index data");
             let mut index_offset = 0;
-            for epaint::ClippedPrimitive { primitive, .. } in paint_jobs {
-                match primitive {
-                    Primitive::Mesh(mesh) => {
-                        let size = mesh.indices.len() * std::mem::size_of::<u32>();
-                        let slice = index_offset..(size + index_offset);
-                        index_buffer_staging[slice.clone()]
-                            .copy_from_slice(bytemuck::cast_slice(&mesh.indices));
-                        self.index_buffer.slices.push(slice);
-                        index_offset += size;
-                    }
-                    Primitive::Callback(_) => {}
-                }
+            for mesh in paint_jobs.iter().filter_map(primitive_mesh) {
+                let size = mesh.indices.len() * std::mem::size_of::<u32>();
+                let slice = index_offset..(size + index_offset);
+                index_buffer_staging[slice.clone()]
+                    .copy_from_slice(bytemuck::cast_slice(&mesh.indices));
+                self.index_buffer.slices.push(slice);
+                index_offset += size;
             }
         }
         if vertex_count > 0 {
@@ -862,21 +1160,60 @@ impl Renderer {
                 )
                 .expect("Failed to create staging buffer for vertex data");
             let mut vertex_offset = 0;
+            for mesh in paint_jobs.iter().filter_map(primitive_mesh) {
+                let size = mesh.vertices.len() * std::mem::size_of::<Vertex>();
+                let slice = vertex_offset..(size + vertex_offset);
+                vertex_buffer_staging[slice.clone()]
+                    .copy_from_slice(bytemuck::cast_slice(&mesh.vertices));
+                self.vertex_buffer.slices.push(slice);
+                vertex_offset += size;
+            }
+        }
+        if instance_count > 0 {
+            crate::profile_scope!("instances");
+
+            self.instance_buffer.slices.clear();
+            let required_instance_buffer_size =
+                (std::mem::size_of::<InstanceTransform>() * instance_count) as u64;
+            if self.instance_buffer.capacity < required_instance_buffer_size {
+                // Resize instance buffer if needed.
+                self.instance_buffer.capacity =
+                    (self.instance_buffer.capacity * 2).at_least(required_instance_buffer_size);
+                self.instance_buffer.buffer =
+                    create_instance_buffer(device, self.instance_buffer.capacity);
+            }
+
+            let mut instance_buffer_staging = queue
+                .write_buffer_with(
+                    &self.instance_buffer.buffer,
+                    0,
+                    NonZeroU64::new(required_instance_buffer_size).unwrap(),
+                )
+                .expect("Failed to create staging buffer for instance data");
+            let mut instance_offset = 0;
             for epaint::ClippedPrimitive { primitive, .. } in paint_jobs {
-                match primitive {
-                    Primitive::Mesh(mesh) => {
-                        let size = mesh.vertices.len() * std::mem::size_of::<Vertex>();
-                        let slice = vertex_offset..(size + vertex_offset);
-                        vertex_buffer_staging[slice.clone()]
-                            .copy_from_slice(bytemuck::cast_slice(&mesh.vertices));
-                        self.vertex_buffer.slices.push(slice);
-                        vertex_offset += size;
-                    }
-                    Primitive::Callback(_) => {}
+                if let Primitive::Instanced(instanced) = primitive {
+                    let size =
+                        instanced.transforms.len() * std::mem::size_of::<InstanceTransform>();
+                    let slice = instance_offset..(size + instance_offset);
+                    instance_buffer_staging[slice.clone()]
+                        .copy_from_slice(bytemuck::cast_slice(&instanced.transforms));
+                    self.instance_buffer.slices.push(slice);
+                    instance_offset += size;
                 }
             }
         }
 
+        self.custom_shaders.prepare(
+            device,
+            queue,
+            &custom_shader_callbacks,
+            screen_descriptor.pixels_per_point,
+            self.output_color_format,
+            self.output_depth_format,
+            self.msaa_samples,
+        );
+
         let mut user_cmd_bufs = Vec::new();
         {
             crate::profile_scope!("prepare callbacks");
@@ -905,6 +1242,24 @@ impl Renderer {
     }
 }
 
+/// In which color space the hardware blends when rendering to the given format.
+fn blending_of_format(format: wgpu::TextureFormat) -> epaint::Blending {
+    if format.is_srgb() {
+        epaint::Blending::Linear
+    } else {
+        epaint::Blending::Gamma
+    }
+}
+
+/// The mesh whose indices and vertices go into the index and vertex buffers, if any.
+fn primitive_mesh(clipped_primitive: &epaint::ClippedPrimitive) -> Option<&Mesh> {
+    match &clipped_primitive.primitive {
+        Primitive::Mesh(mesh) => Some(mesh),
+        Primitive::Instanced(instanced) => Some(&instanced.mesh),
+        Primitive::Callback(_) => None,
+    }
+}
+
 fn create_sampler(
     options: epaint::textures::TextureOptions,
     device: &wgpu::Device,
@@ -937,6 +1292,16 @@ fn create_vertex_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
     })
 }
 
+fn create_instance_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
+    crate::profile_function!();
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("egui_instance_buffer"),
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        size,
+        mapped_at_creation: false,
+    })
+}
+
 fn create_index_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
     crate::profile_function!();
     device.create_buffer(&wgpu::BufferDescriptor {
@@ -71,6 +71,49 @@ impl BufferPadding {
     }
 }
 
+/// Where in the frame a [`RenderHook`] runs. See [`Painter::add_render_hook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RenderStage {
+    /// After the frame has been cleared, before egui is painted, e.g. to render a 3D scene under the UI.
+    BeforeUi,
+
+    /// After egui has been painted, e.g. for post-processing or overlays.
+    AfterUi,
+}
+
+/// Everything a [`RenderHook`] needs to render into a viewport.
+pub struct RenderHookInfo<'a> {
+    pub device: &'a wgpu::Device,
+    pub queue: &'a wgpu::Queue,
+
+    /// The encoder egui records its own render pass into.
+    ///
+    /// Commands recorded here are ordered with the egui render pass according to the [`RenderStage`].
+    pub encoder: &'a mut wgpu::CommandEncoder,
+
+    /// The color attachment egui renders to.
+    ///
+    /// If multisampling is enabled, this is the multisampled texture, and `resolve_target` is the frame.
+    pub view: &'a wgpu::TextureView,
+
+    /// Where to resolve `view` to when multisampling, as in [`wgpu::RenderPassColorAttachment`].
+    pub resolve_target: Option<&'a wgpu::TextureView>,
+
+    /// The depth attachment, if the painter was created with a depth format.
+    pub depth_view: Option<&'a wgpu::TextureView>,
+
+    pub screen_descriptor: &'a renderer::ScreenDescriptor,
+
+    /// The same resources that are given to [`crate::CallbackTrait`].
+    pub callback_resources: &'a mut crate::CallbackResources,
+}
+
+/// Custom rendering into a viewport, registered with [`Painter::add_render_hook`].
+///
+/// Render passes that load the color attachment should use [`wgpu::LoadOp::Load`],
+/// since the frame has already been cleared (and, for [`RenderStage::AfterUi`], painted) when the hook runs.
+pub type RenderHook = Box<dyn Fn(&mut RenderHookInfo<'_>) + Send + Sync>;
+
 /// Everything you need to paint egui with [`wgpu`] on [`winit`].
 ///
 /// Alternatively you can use [`crate::renderer`] directly.
@@ -89,7 +132,11 @@ pub struct Painter {
     // Per viewport/window:
     depth_texture_view: ViewportIdMap<wgpu::TextureView>,
     msaa_texture_view: ViewportIdMap<wgpu::TextureView>,
+
+    /// Overrides of [`Self::msaa_samples`], see [`Self::set_msaa_samples`].
+    viewport_msaa_samples: ViewportIdMap<u32>,
     surfaces: ViewportIdMap<SurfaceState>,
+    render_hooks: ViewportIdMap<Vec<(RenderStage, RenderHook)>>,
 }
 
 impl Painter {
@@ -129,7 +176,68 @@ impl Painter {
             depth_texture_view: Default::default(),
             surfaces: Default::default(),
             msaa_texture_view: Default::default(),
+            viewport_msaa_samples: Default::default(),
+            render_hooks: Default::default(),
+        }
+    }
+
+    /// The multisample count used for the given viewport.
+    pub fn msaa_samples(&self, viewport_id: ViewportId) -> u32 {
+        self.viewport_msaa_samples
+            .get(&viewport_id)
+            .copied()
+            .unwrap_or(self.msaa_samples)
+    }
+
+    /// Change the multisample count of the given viewport, e.g. from a quality setting.
+    ///
+    /// The multisampled color and depth targets of the viewport are recreated,
+    /// and the next frame is painted with the new sample count.
+    /// A count of 1 turns multisampling off.
+    ///
+    /// The render pipelines of any [`crate::CallbackTrait`] or [`RenderHook`] painting into the viewport
+    /// must use the same sample count.
+    pub fn set_msaa_samples(&mut self, viewport_id: ViewportId, msaa_samples: u32) {
+        if self.msaa_samples(viewport_id) == msaa_samples {
+            return;
         }
+        self.viewport_msaa_samples.insert(viewport_id, msaa_samples);
+
+        if let Some(surface_state) = self.surfaces.get(&viewport_id) {
+            if let (Some(width), Some(height)) = (
+                NonZeroU32::new(surface_state.width),
+                NonZeroU32::new(surface_state.height),
+            ) {
+                self.resize_and_generate_depth_texture_view_and_msaa_view(
+                    viewport_id,
+                    width,
+                    height,
+                );
+            }
+        }
+    }
+
+    /// Run `hook` every time the given viewport is painted, at the given [`RenderStage`].
+    ///
+    /// This lets you render under or over the UI of a whole viewport without managing the surface yourself.
+    /// To render within a region of the UI, use a [`crate::Callback`] instead.
+    ///
+    /// Hooks of the same stage run in the order they were added.
+    pub fn add_render_hook(
+        &mut self,
+        viewport_id: ViewportId,
+        stage: RenderStage,
+        hook: impl Fn(&mut RenderHookInfo<'_>) + Send + Sync + 'static,
+    ) {
+        self.render_hooks
+            .entry(viewport_id)
+            .or_default()
+            .push((stage, Box::new(hook)));
+    }
+
+    /// Remove all render hooks of the given viewport.
+    pub fn clear_render_hooks(&mut self, viewport_id: ViewportId) {
+        self.render_hooks.remove(&viewport_id);
     }
 
     /// Get the [`RenderState`].
@@ -191,7 +299,7 @@ impl Painter {
         viewport_id: ViewportId,
         window: Option<&winit::window::Window>,
     ) -> Result<(), crate::WgpuError> {
-        crate::profile_scope!("Painter::set_window"); // profle_function gives bad names for async functions
+        crate::profile_scope!("Painter::set_window", format!("{viewport_id:?}")); // profle_function gives bad names for async functions
 
         if let Some(window) = window {
             let size = window.inner_size();
@@ -287,11 +395,12 @@ impl Painter {
         width_in_pixels: NonZeroU32,
         height_in_pixels: NonZeroU32,
     ) {
-        crate::profile_function!();
+        crate::profile_function!(format!("{viewport_id:?}"));
 
         let width = width_in_pixels.get();
         let height = height_in_pixels.get();
 
+        let msaa_samples = self.msaa_samples(viewport_id);
         let render_state = self.render_state.as_ref().unwrap();
         let surface_state = self.surfaces.get_mut(&viewport_id).unwrap();
 
@@ -313,7 +422,7 @@ impl Painter {
                             depth_or_array_layers: 1,
                         },
                         mip_level_count: 1,
-                        sample_count: self.msaa_samples,
+                        sample_count: msaa_samples,
                         dimension: wgpu::TextureDimension::D2,
                         format: depth_format,
                         usage: wgpu::TextureUsages::RENDER_ATTACHMENT
@@ -324,7 +433,7 @@ impl Painter {
             );
         }
 
-        if let Some(render_state) = (self.msaa_samples > 1)
+        if let Some(render_state) = (msaa_samples > 1)
             .then_some(self.render_state.as_ref())
             .flatten()
         {
@@ -341,7 +450,7 @@ impl Painter {
                             depth_or_array_layers: 1,
                         },
                         mip_level_count: 1,
-                        sample_count: self.msaa_samples,
+                        sample_count: msaa_samples,
                         dimension: wgpu::TextureDimension::D2,
                         format: texture_format,
                         usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -349,7 +458,9 @@ impl Painter {
                     })
                     .create_view(&wgpu::TextureViewDescriptor::default()),
             );
-        };
+        } else {
+            self.msaa_texture_view.remove(&viewport_id);
+        }
     }
 
     pub fn on_window_resized(
@@ -358,7 +469,7 @@ impl Painter {
         width_in_pixels: NonZeroU32,
         height_in_pixels: NonZeroU32,
     ) {
-        crate::profile_function!();
+        crate::profile_function!(format!("{viewport_id:?}"));
 
         if self.surfaces.contains_key(&viewport_id) {
             self.resize_and_generate_depth_texture_view_and_msaa_view(
@@ -480,8 +591,9 @@ impl Painter {
         textures_delta: &epaint::textures::TexturesDelta,
         capture: bool,
     ) -> Option<epaint::ColorImage> {
-        crate::profile_function!();
+        crate::profile_function!(format!("{viewport_id:?}"));
 
+        let msaa_samples = self.msaa_samples(viewport_id);
         let render_state = self.render_state.as_mut()?;
         let surface_state = self.surfaces.get(&viewport_id)?;
 
@@ -523,6 +635,7 @@ impl Painter {
 
         let user_cmd_bufs = {
             let mut renderer = render_state.renderer.write();
+            renderer.set_msaa_samples(&render_state.device, msaa_samples);
             for (id, image_delta) in &textures_delta.set {
                 renderer.update_texture(
                     &render_state.device,
@@ -551,7 +664,6 @@ impl Painter {
         };
 
         {
-            let renderer = render_state.renderer.read();
             let frame_view = if capture {
                 Self::update_capture_state(
                     &mut self.screen_capture_state,
@@ -568,29 +680,101 @@ impl Painter {
                     .create_view(&wgpu::TextureViewDescriptor::default())
             };
 
-            let (view, resolve_target) = (self.msaa_samples > 1)
-                .then_some(self.msaa_texture_view.get(&viewport_id))
-                .flatten()
+            let (view, resolve_target) = self
+                .msaa_texture_view
+                .get(&viewport_id)
                 .map_or((&frame_view, None), |texture_view| {
                     (texture_view, Some(&frame_view))
                 });
+            let depth_view = self.depth_texture_view.get(&viewport_id);
+            let clear_color = {
+                // The clear color is in gamma space, but is written as is only to non-sRGB targets:
+                let [r, g, b, a] = clear_color;
+                let [r, g, b] = if render_state.target_format.is_srgb() {
+                    [r, g, b].map(epaint::ecolor::linear_from_gamma)
+                } else {
+                    [r, g, b]
+                };
+                wgpu::Color {
+                    r: r as f64,
+                    g: g as f64,
+                    b: b as f64,
+                    a: a as f64,
+                }
+            };
+
+            let hooks = self
+                .render_hooks
+                .get(&viewport_id)
+                .map_or(&[][..], Vec::as_slice);
+            let run_hooks = |stage: RenderStage, encoder: &mut wgpu::CommandEncoder| {
+                let mut hooks = hooks.iter().filter(|(s, _)| *s == stage).peekable();
+                if hooks.peek().is_none() {
+                    return false;
+                }
+                crate::profile_scope!("render_hooks");
+                let mut renderer = render_state.renderer.write();
+                let mut info = RenderHookInfo {
+                    device: &render_state.device,
+                    queue: &render_state.queue,
+                    encoder,
+                    view,
+                    resolve_target,
+                    depth_view,
+                    screen_descriptor: &screen_descriptor,
+                    callback_resources: &mut renderer.callback_resources,
+                };
+                for (_, hook) in hooks {
+                    hook(&mut info);
+                }
+                true
+            };
+
+            let has_pre_ui_hooks = hooks.iter().any(|(s, _)| *s == RenderStage::BeforeUi);
+            if has_pre_ui_hooks {
+                // Clear the frame for the hooks, and let egui paint on top of what they render.
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("egui_clear"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(clear_color),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: depth_view.map(|view| {
+                        wgpu::RenderPassDepthStencilAttachment {
+                            view,
+                            depth_ops: Some(wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(1.0),
+                                store: wgpu::StoreOp::Store,
+                            }),
+                            stencil_ops: None,
+                        }
+                    }),
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                run_hooks(RenderStage::BeforeUi, &mut encoder);
+            }
 
+            let renderer = render_state.renderer.read();
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("egui_render"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                     view,
                     resolve_target,
                     ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: clear_color[0] as f64,
-                            g: clear_color[1] as f64,
-                            b: clear_color[2] as f64,
-                            a: clear_color[3] as f64,
-                        }),
+                        load: if has_pre_ui_hooks {
+                            wgpu::LoadOp::Load
+                        } else {
+                            wgpu::LoadOp::Clear(clear_color)
+                        },
                         store: wgpu::StoreOp::Store,
                     },
                 })],
-                depth_stencil_attachment: self.depth_texture_view.get(&viewport_id).map(|view| {
+                depth_stencil_attachment: depth_view.map(|view| {
                     wgpu::RenderPassDepthStencilAttachment {
                         view,
                         depth_ops: Some(wgpu::Operations {
@@ -607,6 +791,10 @@ impl Painter {
             });
 
             renderer.render(&mut render_pass, clipped_primitives, &screen_descriptor);
+            drop(render_pass);
+            drop(renderer);
+
+            run_hooks(RenderStage::AfterUi, &mut encoder);
         }
 
         {
@@ -649,6 +837,10 @@ impl Painter {
             .retain(|id, _| active_viewports.contains(id));
         self.msaa_texture_view
             .retain(|id, _| active_viewports.contains(id));
+        self.viewport_msaa_samples
+            .retain(|id, _| active_viewports.contains(id));
+        self.render_hooks
+            .retain(|id, _| active_viewports.contains(id));
     }
 
     #[allow(clippy::unused_self)]
@@ -0,0 +1,152 @@
+//! Applying an [`egui::Backdrop`] to a window.
+//!
+//! winit can only make a window transparent and (on some platforms) blur what is behind it,
+//! so we use the compositor materials directly where we can:
+//! * On Windows 11 we set `DWMWA_SYSTEMBACKDROP_TYPE` to Mica or Acrylic.
+//! * On Mac we put an `NSVisualEffectView` behind the contents of the window.
+//!
+//! Elsewhere (including Windows 10) the blurred backdrops fall back to [`Window::set_blur`].
+
+use egui::Backdrop;
+use winit::window::Window;
+
+/// The window must have been created with [`winit::window::WindowBuilder::with_transparent`].
+pub fn set_backdrop(window: &Window, backdrop: Backdrop) {
+    #[cfg(target_os = "windows")]
+    let is_native = windows::set_backdrop(window, backdrop);
+
+    #[cfg(target_os = "macos")]
+    let is_native = mac::set_backdrop(window, backdrop);
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let is_native = false;
+
+    window.set_blur(!is_native && backdrop.is_blurred());
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use raw_window_handle::{HasRawWindowHandle as _, RawWindowHandle};
+    use winapi::um::{
+        dwmapi::{DwmExtendFrameIntoClientArea, DwmSetWindowAttribute},
+        uxtheme::MARGINS,
+    };
+
+    use super::{Backdrop, Window};
+
+    // Added in Windows 11 (build 22621), so not part of `winapi`:
+    const DWMWA_SYSTEMBACKDROP_TYPE: u32 = 38;
+    const DWMSBT_NONE: u32 = 1;
+    const DWMSBT_MAINWINDOW: u32 = 2; // Mica
+    const DWMSBT_TRANSIENTWINDOW: u32 = 3; // Acrylic
+
+    /// Returns `false` if the system backdrops are not supported, e.g. on Windows 10.
+    pub fn set_backdrop(window: &Window, backdrop: Backdrop) -> bool {
+        let RawWindowHandle::Win32(handle) = window.raw_window_handle() else {
+            return false;
+        };
+        let hwnd = handle.hwnd.cast();
+
+        let backdrop_type = match backdrop {
+            Backdrop::Transparent => DWMSBT_NONE,
+            Backdrop::Mica => DWMSBT_MAINWINDOW,
+            Backdrop::Acrylic | Backdrop::Vibrancy => DWMSBT_TRANSIENTWINDOW,
+        };
+
+        // The backdrop is part of the window frame, so extend the frame over the whole window:
+        let margin = if backdrop == Backdrop::Transparent {
+            0
+        } else {
+            -1
+        };
+        let margins = MARGINS {
+            cxLeftWidth: margin,
+            cxRightWidth: margin,
+            cyTopHeight: margin,
+            cyBottomHeight: margin,
+        };
+
+        unsafe {
+            DwmExtendFrameIntoClientArea(hwnd, &margins);
+            let result = DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_SYSTEMBACKDROP_TYPE,
+                (&backdrop_type as *const u32).cast(),
+                std::mem::size_of::<u32>() as u32,
+            );
+            0 <= result
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod mac {
+    use cocoa::{
+        appkit::{
+            NSView, NSViewHeightSizable, NSViewWidthSizable, NSVisualEffectBlendingMode,
+            NSVisualEffectMaterial, NSVisualEffectState, NSVisualEffectView, NSWindowOrderingMode,
+        },
+        base::{id, nil, BOOL, YES},
+        foundation::NSArray,
+    };
+    use objc::{class, msg_send, sel, sel_impl};
+    use raw_window_handle::{HasRawWindowHandle as _, RawWindowHandle};
+
+    use super::{Backdrop, Window};
+
+    /// Always succeeds on Mac, so returns `true` unless the window isn't an `AppKit` window.
+    pub fn set_backdrop(window: &Window, backdrop: Backdrop) -> bool {
+        let RawWindowHandle::AppKit(handle) = window.raw_window_handle() else {
+            return false;
+        };
+        let ns_view: id = handle.ns_view.cast();
+
+        unsafe {
+            remove_effect_views(ns_view);
+
+            let material = match backdrop {
+                Backdrop::Transparent => return true,
+                Backdrop::Acrylic => NSVisualEffectMaterial::HudWindow,
+                Backdrop::Mica | Backdrop::Vibrancy => {
+                    NSVisualEffectMaterial::UnderWindowBackground
+                }
+            };
+
+            let effect_view = NSVisualEffectView::alloc(nil);
+            let effect_view =
+                NSVisualEffectView::initWithFrame_(effect_view, NSView::bounds(ns_view));
+            effect_view.setMaterial_(material);
+            effect_view.setBlendingMode_(NSVisualEffectBlendingMode::BehindWindow);
+            effect_view.setState_(NSVisualEffectState::Active);
+            NSView::setAutoresizingMask_(effect_view, NSViewWidthSizable | NSViewHeightSizable);
+
+            let () = msg_send![ns_view,
+                addSubview: effect_view
+                positioned: NSWindowOrderingMode::NSWindowBelow.bits()
+                relativeTo: nil];
+
+            // The superview keeps it alive:
+            let () = msg_send![effect_view, release];
+        }
+
+        true
+    }
+
+    /// Remove the effect views we added before.
+    unsafe fn remove_effect_views(ns_view: id) {
+        unsafe {
+            let subviews: id = msg_send![ns_view, subviews];
+            // Iterate over a copy, since we remove from the original:
+            let subviews: id = msg_send![subviews, copy];
+            for i in 0..subviews.count() {
+                let subview = subviews.objectAtIndex(i);
+                let is_effect_view: BOOL =
+                    msg_send![subview, isKindOfClass: class!(NSVisualEffectView)];
+                if is_effect_view == YES {
+                    NSView::removeFromSuperview(subview);
+                }
+            }
+            let () = msg_send![subviews, release];
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Gamepad (game controller) input, using [`gilrs`].
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use egui::{mutex::Mutex, GamepadButton};
+use gilrs::{Axis, Button, EventType, GamepadId};
+
+/// How far an analog stick needs to be pushed to count as a d-pad press.
+const STICK_PRESS_THRESHOLD: f32 = 0.6;
+
+/// How far back an analog stick needs to go to count as a d-pad release.
+const STICK_RELEASE_THRESHOLD: f32 = 0.3;
+
+/// Reads gamepad input on a background thread and turns it into [`egui::Event::GamepadButton`].
+///
+/// The left analog stick is translated into d-pad presses,
+/// so that both can be used to move the keyboard focus around.
+///
+/// Gamepads are not tied to a window, so call [`Self::take_events`] once per frame of
+/// the root viewport and append the result to its [`egui::RawInput::events`].
+pub struct Gamepads {
+    events: Arc<Mutex<Vec<egui::Event>>>,
+}
+
+impl Gamepads {
+    /// Start listening to gamepads.
+    ///
+    /// The root viewport of `egui_ctx` is repainted whenever there is new input.
+    pub fn new(egui_ctx: egui::Context) -> Self {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let weak_events = Arc::downgrade(&events);
+        if let Err(err) = std::thread::Builder::new()
+            .name("egui_gamepads".to_owned())
+            .spawn(move || run(&egui_ctx, &weak_events))
+        {
+            log::warn!("Failed to spawn gamepad thread: {err}");
+        }
+        Self { events }
+    }
+
+    /// All gamepad events since the last call.
+    pub fn take_events(&self) -> Vec<egui::Event> {
+        std::mem::take(&mut *self.events.lock())
+    }
+}
+
+fn run(egui_ctx: &egui::Context, events: &Weak<Mutex<Vec<egui::Event>>>) {
+    let mut gilrs = match gilrs::Gilrs::new() {
+        Ok(gilrs) => gilrs,
+        Err(err) => {
+            log::warn!("Failed to initialize gamepad support: {err}");
+            return;
+        }
+    };
+
+    // Which d-pad button each stick axis is currently "pressing".
+    let mut stick_buttons: HashMap<(GamepadId, Axis), GamepadButton> = Default::default();
+
+    loop {
+        // Wake up now and then to check if we should shut down.
+        let event = gilrs.next_event_blocking(Some(Duration::from_millis(500)));
+        let Some(events) = events.upgrade() else {
+            return; // `Gamepads` was dropped
+        };
+        let Some(gilrs::Event { id, event, .. }) = event else {
+            continue;
+        };
+
+        let mut new_events = vec![];
+        match event {
+            EventType::ButtonPressed(button, _) | EventType::ButtonReleased(button, _) => {
+                if let Some(button) = translate_button(button) {
+                    new_events.push(egui::Event::GamepadButton {
+                        button,
+                        pressed: matches!(event, EventType::ButtonPressed(..)),
+                    });
+                }
+            }
+            EventType::AxisChanged(axis @ (Axis::LeftStickX | Axis::LeftStickY), value, _) => {
+                let current = stick_buttons.get(&(id, axis)).copied();
+                let new = if value.abs() >= STICK_PRESS_THRESHOLD {
+                    Some(match (axis, value > 0.0) {
+                        (Axis::LeftStickX, true) => GamepadButton::DPadRight,
+                        (Axis::LeftStickX, false) => GamepadButton::DPadLeft,
+                        (_, true) => GamepadButton::DPadUp, // gilrs has positive y going up
+                        (_, false) => GamepadButton::DPadDown,
+                    })
+                } else if value.abs() <= STICK_RELEASE_THRESHOLD {
+                    None
+                } else {
+                    current // In the dead zone between press and release: no change
+                };
+
+                if new != current {
+                    if let Some(button) = current {
+                        new_events.push(egui::Event::GamepadButton {
+                            button,
+                            pressed: false,
+                        });
+                    }
+                    if let Some(button) = new {
+                        new_events.push(egui::Event::GamepadButton {
+                            button,
+                            pressed: true,
+                        });
+                        stick_buttons.insert((id, axis), button);
+                    } else {
+                        stick_buttons.remove(&(id, axis));
+                    }
+                }
+            }
+            EventType::Disconnected => {
+                stick_buttons.retain(|(gamepad_id, _), _| *gamepad_id != id);
+            }
+            _ => {}
+        }
+
+        if !new_events.is_empty() {
+            events.lock().extend(new_events);
+            egui_ctx.request_repaint_of(egui::ViewportId::ROOT);
+        }
+    }
+}
+
+fn translate_button(button: Button) -> Option<GamepadButton> {
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::North => GamepadButton::North,
+        Button::West => GamepadButton::West,
+        Button::LeftTrigger => GamepadButton::LeftBumper,
+        Button::RightTrigger => GamepadButton::RightBumper,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
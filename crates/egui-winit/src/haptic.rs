@@ -0,0 +1,181 @@
+//! Tactile feedback, see [`egui::PlatformOutput::haptic`].
+//!
+//! * On Mac we use `NSHapticFeedbackManager`, which is only felt on a Force Touch trackpad
+//!   while it is being touched.
+//! * On iOS we use `UISelectionFeedbackGenerator` and `UIImpactFeedbackGenerator`.
+//! * On Android we call `View.performHapticFeedback` on the view of the activity,
+//!   which respects the haptic feedback setting of the user.
+
+use egui::HapticKind;
+
+#[cfg(target_os = "android")]
+pub fn perform(android_app: &winit::platform::android::activity::AndroidApp, haptic: HapticKind) {
+    if let Err(err) = android::perform(android_app, haptic) {
+        log::debug!("Failed to perform haptic feedback: {err}");
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+pub fn perform(haptic: HapticKind) {
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    apple::perform(haptic);
+
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    let _ = haptic;
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+#[allow(unsafe_code)]
+mod apple {
+    use std::ffi::{c_char, c_void};
+
+    use super::HapticKind;
+
+    type Id = *mut c_void;
+    type Sel = *mut c_void;
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> Id;
+        fn sel_registerName(name: *const c_char) -> Sel;
+        fn objc_msgSend();
+    }
+
+    /// SAFETY: `name` must be nul-terminated.
+    unsafe fn class(name: &str) -> Id {
+        objc_getClass(name.as_ptr().cast())
+    }
+
+    /// SAFETY: `name` must be nul-terminated.
+    unsafe fn sel(name: &str) -> Sel {
+        sel_registerName(name.as_ptr().cast())
+    }
+
+    /// Send a message without arguments.
+    ///
+    /// SAFETY: `selector` must take no arguments and return an object (or nothing).
+    unsafe fn send(receiver: Id, selector: &str) -> Id {
+        let send: unsafe extern "C" fn(Id, Sel) -> Id =
+            std::mem::transmute(objc_msgSend as *const ());
+        send(receiver, sel(selector))
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn perform(haptic: HapticKind) {
+        const NS_HAPTIC_FEEDBACK_PATTERN_GENERIC: isize = 0;
+        const NS_HAPTIC_FEEDBACK_PATTERN_ALIGNMENT: isize = 1;
+        const NS_HAPTIC_FEEDBACK_PATTERN_LEVEL_CHANGE: isize = 2;
+        const NS_HAPTIC_FEEDBACK_PERFORMANCE_TIME_NOW: usize = 1;
+
+        let pattern = match haptic {
+            HapticKind::Selection => NS_HAPTIC_FEEDBACK_PATTERN_LEVEL_CHANGE,
+            HapticKind::Alignment => NS_HAPTIC_FEEDBACK_PATTERN_ALIGNMENT,
+            HapticKind::Impact => NS_HAPTIC_FEEDBACK_PATTERN_GENERIC,
+        };
+
+        // SAFETY: the selectors exist on macOS 10.11 and later, and we pass the arguments they take.
+        unsafe {
+            let manager = class("NSHapticFeedbackManager\0");
+            if manager.is_null() {
+                return;
+            }
+            let performer = send(manager, "defaultPerformer\0");
+            if performer.is_null() {
+                return;
+            }
+            let perform: unsafe extern "C" fn(Id, Sel, isize, usize) =
+                std::mem::transmute(objc_msgSend as *const ());
+            perform(
+                performer,
+                sel("performFeedbackPattern:performanceTime:\0"),
+                pattern,
+                NS_HAPTIC_FEEDBACK_PERFORMANCE_TIME_NOW,
+            );
+        }
+    }
+
+    #[cfg(target_os = "ios")]
+    pub fn perform(haptic: HapticKind) {
+        const UI_IMPACT_FEEDBACK_STYLE_LIGHT: isize = 0;
+        const UI_IMPACT_FEEDBACK_STYLE_MEDIUM: isize = 1;
+
+        // SAFETY: the selectors exist on iOS 10 and later, and we pass the arguments they take.
+        // We release the generators we create.
+        unsafe {
+            let generator = match haptic {
+                HapticKind::Selection => {
+                    let generator = send(class("UISelectionFeedbackGenerator\0"), "new\0");
+                    if !generator.is_null() {
+                        send(generator, "selectionChanged\0");
+                    }
+                    generator
+                }
+                HapticKind::Alignment | HapticKind::Impact => {
+                    let style = if haptic == HapticKind::Alignment {
+                        UI_IMPACT_FEEDBACK_STYLE_LIGHT
+                    } else {
+                        UI_IMPACT_FEEDBACK_STYLE_MEDIUM
+                    };
+                    let generator = send(class("UIImpactFeedbackGenerator\0"), "alloc\0");
+                    let init: unsafe extern "C" fn(Id, Sel, isize) -> Id =
+                        std::mem::transmute(objc_msgSend as *const ());
+                    let generator = init(generator, sel("initWithStyle:\0"), style);
+                    if !generator.is_null() {
+                        send(generator, "impactOccurred\0");
+                    }
+                    generator
+                }
+            };
+            if !generator.is_null() {
+                send(generator, "release\0");
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+#[allow(unsafe_code)]
+mod android {
+    use jni::{
+        objects::{JObject, JValue},
+        JavaVM,
+    };
+    use winit::platform::android::activity::AndroidApp;
+
+    use super::HapticKind;
+
+    // From `android.view.HapticFeedbackConstants`:
+    const VIRTUAL_KEY: i32 = 1;
+    const KEYBOARD_TAP: i32 = 3;
+    const CLOCK_TICK: i32 = 4;
+
+    pub fn perform(android_app: &AndroidApp, haptic: HapticKind) -> jni::errors::Result<()> {
+        let constant = match haptic {
+            HapticKind::Selection => CLOCK_TICK,
+            HapticKind::Alignment => KEYBOARD_TAP,
+            HapticKind::Impact => VIRTUAL_KEY,
+        };
+
+        // SAFETY: the VM and the activity outlive the app.
+        let vm = unsafe { JavaVM::from_raw(android_app.vm_as_ptr().cast()) }?;
+        let activity = unsafe { JObject::from_raw(android_app.activity_as_ptr().cast()) };
+
+        let mut env = vm.attach_current_thread()?;
+        // Our thread stays attached, so free the local references when done:
+        env.with_local_frame(4, |env| {
+            let window = env
+                .call_method(&activity, "getWindow", "()Landroid/view/Window;", &[])?
+                .l()?;
+            let view = env
+                .call_method(&window, "getDecorView", "()Landroid/view/View;", &[])?
+                .l()?;
+            env.call_method(
+                &view,
+                "performHapticFeedback",
+                "(I)Z",
+                &[JValue::Int(constant)],
+            )?;
+            Ok(())
+        })
+    }
+}
@@ -17,10 +17,17 @@ use egui::accesskit;
 use egui::{Pos2, Rect, Vec2, ViewportBuilder, ViewportCommand, ViewportId, ViewportInfo};
 pub use winit;
 
+mod backdrop;
 pub mod clipboard;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+mod haptic;
+mod sound;
+mod taskbar;
 mod window_settings;
 
-pub use window_settings::WindowSettings;
+pub use taskbar::set_taskbar_badge_count;
+pub use window_settings::{monitor_layout_hash, WindowSettings};
 
 use raw_window_handle::HasRawDisplayHandle;
 
@@ -45,6 +52,16 @@ pub fn pixels_per_point(egui_ctx: &egui::Context, window: &Window) -> f32 {
     egui_zoom_factor * native_pixels_per_point
 }
 
+/// A size in egui points as a winit logical size, given the current egui zoom factor.
+///
+/// Unlike a [`PhysicalSize`], winit converts this to pixels using the scale factor
+/// of the monitor the window is actually on, also after it moves to another monitor.
+#[allow(clippy::disallowed_types)] // We take the zoom factor into account here
+fn logical_size(egui_ctx: &egui::Context, size: Vec2) -> winit::dpi::LogicalSize<f32> {
+    let zoom_factor = egui_ctx.zoom_factor();
+    winit::dpi::LogicalSize::new(zoom_factor * size.x, zoom_factor * size.y)
+}
+
 // ----------------------------------------------------------------------------
 
 #[must_use]
@@ -99,6 +116,13 @@ pub struct State {
     accesskit: Option<accesskit_winit::Adapter>,
 
     allow_ime: bool,
+
+    /// Is the on-screen keyboard currently shown?
+    virtual_keyboard_shown: bool,
+
+    /// Needed to show and hide the on-screen keyboard.
+    #[cfg(target_os = "android")]
+    android_app: Option<winit::platform::android::activity::AndroidApp>,
 }
 
 impl State {
@@ -137,11 +161,16 @@ impl State {
             accesskit: None,
 
             allow_ime: false,
+
+            virtual_keyboard_shown: false,
+
+            #[cfg(target_os = "android")]
+            android_app: None,
         };
 
         slf.egui_input
             .viewports
-            .entry(ViewportId::ROOT)
+            .entry(viewport_id)
             .or_default()
             .native_pixels_per_point = native_pixels_per_point;
 
@@ -151,6 +180,17 @@ impl State {
         slf
     }
 
+    /// Needed to show the on-screen keyboard when [`egui::PlatformOutput::virtual_keyboard`] is set.
+    ///
+    /// This is the `AndroidApp` passed to your `android_main`.
+    #[cfg(target_os = "android")]
+    pub fn set_android_app(
+        &mut self,
+        android_app: Option<winit::platform::android::activity::AndroidApp>,
+    ) {
+        self.android_app = android_app;
+    }
+
     #[cfg(feature = "accesskit")]
     pub fn init_accesskit<T: From<accesskit_winit::ActionRequestEvent> + Send>(
         &mut self,
@@ -177,6 +217,26 @@ impl State {
         &self.egui_ctx
     }
 
+    /// The viewport this state handles the input for.
+    #[inline]
+    pub fn viewport_id(&self) -> ViewportId {
+        self.viewport_id
+    }
+
+    /// The `pixels_per_point` of the window of this viewport,
+    /// i.e. the current egui zoom factor times the native scale factor of the monitor the window is on.
+    ///
+    /// This is kept up-to-date by [`Self::on_window_event`] and [`Self::take_egui_input`].
+    pub fn pixels_per_point(&self) -> f32 {
+        let native_pixels_per_point = self
+            .egui_input
+            .viewports
+            .get(&self.viewport_id)
+            .and_then(|info| info.native_pixels_per_point)
+            .unwrap_or(1.0);
+        self.egui_ctx.zoom_factor() * native_pixels_per_point
+    }
+
     /// The current input state.
     /// This is changed by [`Self::on_window_event`] and cleared by [`Self::take_egui_input`].
     #[inline]
@@ -191,6 +251,25 @@ impl State {
         &mut self.egui_input
     }
 
+    /// Add an event that didn't come from `winit`, e.g. from a game engine or a test.
+    ///
+    /// The event is handled by egui the next frame, together with the events from [`Self::on_window_event`].
+    pub fn push_event(&mut self, event: egui::Event) {
+        match &event {
+            egui::Event::PointerMoved(pos) => {
+                self.pointer_pos_in_points = Some(*pos);
+            }
+            egui::Event::PointerButton { pressed, .. } => {
+                self.any_pointer_button_down = *pressed;
+            }
+            egui::Event::PointerGone => {
+                self.pointer_pos_in_points = None;
+            }
+            _ => {}
+        }
+        self.egui_input.events.push(event);
+    }
+
     /// Prepare for a new frame by extracting the accumulated input,
     ///
     /// as well as setting [the time](egui::RawInput::time) and [screen rectangle](egui::RawInput::screen_rect).
@@ -241,6 +320,19 @@ impl State {
             accesskit.process_event(window, event);
         }
 
+        let num_events_before = self.egui_input.events.len();
+        let response = self.process_window_event(window, event);
+        if num_events_before < self.egui_input.events.len() {
+            self.note_event_time();
+        }
+        response
+    }
+
+    fn process_window_event(
+        &mut self,
+        window: &Window,
+        event: &winit::event::WindowEvent,
+    ) -> EventResponse {
         use winit::event::WindowEvent;
         match event {
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
@@ -315,25 +407,11 @@ impl State {
                 //
                 // We use input_method_editor_started to manually insert CompositionStart
                 // between Commits.
-                match ime {
-                    winit::event::Ime::Enabled | winit::event::Ime::Disabled => (),
-                    winit::event::Ime::Commit(text) => {
-                        self.input_method_editor_started = false;
-                        self.egui_input
-                            .events
-                            .push(egui::Event::CompositionEnd(text.clone()));
-                    }
-                    winit::event::Ime::Preedit(text, Some(_)) => {
-                        if !self.input_method_editor_started {
-                            self.input_method_editor_started = true;
-                            self.egui_input.events.push(egui::Event::CompositionStart);
-                        }
-                        self.egui_input
-                            .events
-                            .push(egui::Event::CompositionUpdate(text.clone()));
-                    }
-                    winit::event::Ime::Preedit(_, None) => {}
-                };
+                on_ime(
+                    &mut self.input_method_editor_started,
+                    ime,
+                    &mut self.egui_input.events,
+                );
 
                 EventResponse {
                     repaint: true,
@@ -460,6 +538,35 @@ impl State {
         self.egui_input
             .events
             .push(egui::Event::AccessKitActionRequest(request));
+        self.note_event_time();
+    }
+
+    /// Call this with the raw mouse motion from [`winit::event::DeviceEvent::MouseMotion`].
+    ///
+    /// Device events are not tied to a window, so only call this for the focused viewport.
+    /// The result can be found in [`Self::egui_input`] and be extracted with [`Self::take_egui_input`].
+    pub fn on_mouse_motion(&mut self, delta: (f64, f64)) {
+        self.egui_input
+            .events
+            .push(egui::Event::MouseMoved(egui::Vec2::new(
+                delta.0 as f32,
+                delta.1 as f32,
+            )));
+        self.note_event_time();
+    }
+
+    /// Remember when we received the new events, see [`egui::RawInput::event_times`].
+    fn note_event_time(&mut self) {
+        let now = self.start_time.elapsed().as_secs_f64();
+        self.egui_input.fill_event_times(now);
+    }
+
+    /// Call this right after presenting a frame of this viewport on screen.
+    ///
+    /// This is used to measure the input latency, see [`egui::Context::latency_stats`].
+    pub fn on_frame_presented(&self) {
+        let now = self.start_time.elapsed().as_secs_f64();
+        self.egui_ctx.on_frame_presented(self.viewport_id, now);
     }
 
     fn on_mouse_button_input(
@@ -568,6 +675,29 @@ impl State {
                 None => None,
             },
         });
+
+        if let Some(winit::event::Force::Calibrated {
+            force,
+            max_possible_force,
+            altitude_angle: Some(_),
+        }) = touch.force
+        {
+            // Only pens report an altitude angle (e.g. the Apple Pencil on iOS).
+            // winit does not tell us the azimuth, so we can't compute the tilt.
+            self.egui_input
+                .events
+                .push(egui::Event::Pen(egui::PenInput {
+                    pos: egui::pos2(
+                        touch.location.x as f32 / pixels_per_point,
+                        touch.location.y as f32 / pixels_per_point,
+                    ),
+                    pressure: (force / max_possible_force) as f32,
+                    tilt: None,
+                    twist: None,
+                    tool: egui::PenTool::Pen,
+                }));
+        }
+
         // If we're not yet translating a touch or we're translating this very
         // touch …
         if self.pointer_touch_id.is_none() || self.pointer_touch_id.unwrap() == touch.id {
@@ -681,6 +811,8 @@ impl State {
 
         let pressed = *state == winit::event::ElementState::Pressed;
 
+        let scancode = scancode_from_physical_key(*physical_key);
+
         let physical_key = if let winit::keyboard::PhysicalKey::Code(keycode) = *physical_key {
             key_from_key_code(keycode)
         } else {
@@ -698,15 +830,38 @@ impl State {
             physical_key
         );
 
+        // Dead keys (e.g. `´` or `¨` on many European layouts) produce no text by themselves.
+        // The OS combines them with the next key press, which then has the combined text (e.g. `é`),
+        // or reports the whole sequence as an IME composition (see `on_ime`).
+
+        // Make sure there is text, and that it is not control characters
+        // (e.g. delete is sent as "\u{f728}" on macOS).
+        // Also make sure the key is pressed (not released). On Linux, text might
+        // contain some data even when the key is released.
+        let text = text.as_ref().filter(|text| {
+            pressed && !text.is_empty() && text.chars().all(egui::is_printable_char)
+        });
+
+        let modifiers = self.egui_input.modifiers;
+
+        let is_text_input =
+            is_alt_gr_text_input(egui::os::OperatingSystem::from_target_os(), modifiers, text);
+
+        if pressed && self.input_method_editor_started {
+            // The key is part of an IME composition (or a compose sequence on Linux),
+            // so it is not a command, and its text arrives with `Ime::Commit`.
+            return false;
+        }
+
         if let Some(logical_key) = logical_key {
-            if pressed {
-                if is_cut_command(self.egui_input.modifiers, logical_key) {
+            if pressed && !is_text_input {
+                if is_cut_command(modifiers, logical_key) {
                     self.egui_input.events.push(egui::Event::Cut);
                     return true;
-                } else if is_copy_command(self.egui_input.modifiers, logical_key) {
+                } else if is_copy_command(modifiers, logical_key) {
                     self.egui_input.events.push(egui::Event::Copy);
                     return true;
-                } else if is_paste_command(self.egui_input.modifiers, logical_key) {
+                } else if is_paste_command(modifiers, logical_key) {
                     if let Some(contents) = self.clipboard.get() {
                         let contents = contents.replace("\r\n", "\n");
                         if !contents.is_empty() {
@@ -720,28 +875,21 @@ impl State {
             self.egui_input.events.push(egui::Event::Key {
                 key: logical_key,
                 physical_key,
+                scancode,
                 pressed,
                 repeat: false, // egui will fill this in for us!
-                modifiers: self.egui_input.modifiers,
+                modifiers,
             });
         }
 
-        if let Some(text) = &text {
-            // Make sure there is text, and that it is not control characters
-            // (e.g. delete is sent as "\u{f728}" on macOS).
-            if !text.is_empty() && text.chars().all(is_printable_char) {
-                // On some platforms we get here when the user presses Cmd-C (copy), ctrl-W, etc.
-                // We need to ignore these characters that are side-effects of commands.
-                // Also make sure the key is pressed (not released). On Linux, text might
-                // contain some data even when the key is released.
-                let is_cmd = self.egui_input.modifiers.ctrl
-                    || self.egui_input.modifiers.command
-                    || self.egui_input.modifiers.mac_cmd;
-                if pressed && !is_cmd {
-                    self.egui_input
-                        .events
-                        .push(egui::Event::Text(text.to_string()));
-                }
+        if let Some(text) = text {
+            // On some platforms we get here when the user presses Cmd-C (copy), ctrl-W, etc.
+            // We need to ignore these characters that are side-effects of commands.
+            let is_cmd = modifiers.ctrl || modifiers.command || modifiers.mac_cmd;
+            if is_text_input || !is_cmd {
+                self.egui_input
+                    .events
+                    .push(egui::Event::Text(text.to_string()));
             }
         }
 
@@ -753,7 +901,8 @@ impl State {
     /// This will, if needed:
     /// * update the cursor
     /// * copy text to the clipboard
-    /// * open any clicked urls
+    /// * open any clicked urls and local files
+    /// * play system sounds and haptic feedback
     /// * update the IME
     /// *
     pub fn handle_platform_output(
@@ -766,10 +915,17 @@ impl State {
         let egui::PlatformOutput {
             cursor_icon,
             open_url,
+            open_path,
+            reveal_in_file_manager,
+            play_system_sound,
+            haptic,
             copied_text,
             events: _,                    // handled elsewhere
+            announcements: _,             // only used by the screen reader of eframe web
             mutable_text_under_cursor: _, // only used in eframe web
             ime,
+            virtual_keyboard,
+            webviews: _, // shown by eframe
             #[cfg(feature = "accesskit")]
             accesskit_update,
         } = platform_output;
@@ -777,7 +933,27 @@ impl State {
         self.set_cursor_icon(window, cursor_icon);
 
         if let Some(open_url) = open_url {
-            open_url_in_browser(&open_url.url);
+            if let Some(path) = path_from_file_url(&open_url.url) {
+                open_path_in_default_app(&path);
+            } else {
+                open_url_in_browser(&open_url.url);
+            }
+        }
+
+        if let Some(path) = open_path {
+            open_path_in_default_app(&path);
+        }
+
+        if let Some(path) = reveal_in_file_manager {
+            reveal_path_in_file_manager(&path);
+        }
+
+        if let Some(sound) = play_system_sound {
+            sound::play(sound);
+        }
+
+        if let Some(haptic) = haptic {
+            self.perform_haptic(haptic);
         }
 
         if !copied_text.is_empty() {
@@ -790,6 +966,11 @@ impl State {
             window.set_ime_allowed(allow_ime);
         }
 
+        if self.virtual_keyboard_shown != virtual_keyboard {
+            self.virtual_keyboard_shown = virtual_keyboard;
+            self.set_virtual_keyboard_visible(virtual_keyboard);
+        }
+
         if let Some(ime) = ime {
             let rect = ime.rect;
             let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
@@ -813,6 +994,33 @@ impl State {
         }
     }
 
+    #[allow(clippy::unused_self)]
+    fn perform_haptic(&self, haptic: egui::HapticKind) {
+        #[cfg(target_os = "android")]
+        if let Some(android_app) = &self.android_app {
+            haptic::perform(android_app, haptic);
+        }
+
+        #[cfg(not(target_os = "android"))]
+        haptic::perform(haptic);
+    }
+
+    #[allow(clippy::unused_self)]
+    fn set_virtual_keyboard_visible(&self, visible: bool) {
+        #[cfg(target_os = "android")]
+        if let Some(android_app) = &self.android_app {
+            if visible {
+                android_app.show_soft_input(false);
+            } else {
+                android_app.hide_soft_input(false);
+            }
+        }
+
+        // Desktops have physical keyboards, and winit can't show the keyboard on iOS yet.
+        #[cfg(not(target_os = "android"))]
+        let _ = visible;
+    }
+
     fn set_cursor_icon(&mut self, window: &Window, cursor_icon: egui::CursorIcon) {
         if self.current_cursor_icon == Some(cursor_icon) {
             // Prevent flickering near frame boundary when Windows OS tries to control cursor icon for window resizing.
@@ -901,24 +1109,52 @@ pub fn update_viewport_info(
     let inner_rect = inner_rect_px.map(|r| r / pixels_per_point);
     let outer_rect = outer_rect_px.map(|r| r / pixels_per_point);
 
-    let monitor_size = {
+    let (monitor_size, monitor_name, monitor_position) = {
         crate::profile_scope!("monitor_size");
         if let Some(monitor) = window.current_monitor() {
             let size = monitor.size().to_logical::<f32>(pixels_per_point.into());
-            Some(egui::vec2(size.width, size.height))
+            let position = monitor.position();
+            (
+                Some(egui::vec2(size.width, size.height)),
+                monitor.name(),
+                Some(egui::pos2(position.x as f32, position.y as f32) / pixels_per_point),
+            )
         } else {
-            None
+            (None, None, None)
         }
     };
 
     viewport_info.focused = Some(window.has_focus());
-    viewport_info.fullscreen = Some(window.fullscreen().is_some());
+    let fullscreen = window.fullscreen();
+    viewport_info.fullscreen = Some(fullscreen.is_some());
+    viewport_info.fullscreen_mode =
+        fullscreen.map(|fullscreen| from_winit_fullscreen(window, fullscreen));
     viewport_info.inner_rect = inner_rect;
     viewport_info.monitor_size = monitor_size;
+    viewport_info.monitor_name = monitor_name;
+    viewport_info.monitor_position = monitor_position;
     viewport_info.native_pixels_per_point = Some(window.scale_factor() as f32);
     viewport_info.outer_rect = outer_rect;
     viewport_info.title = Some(window.title());
 
+    #[cfg(target_os = "android")]
+    {
+        // The content rect excludes the on-screen keyboard, display cutouts and system bars.
+        // We attribute the bottom to the keyboard, and the other sides to the safe area.
+        use winit::platform::android::WindowExtAndroid as _;
+        let content_rect = window.content_rect();
+        let window_size_px = window.inner_size();
+        let inset = |px: i32| (px as f32 / pixels_per_point).max(0.0);
+        viewport_info.virtual_keyboard_inset =
+            Some(inset(window_size_px.height as i32 - content_rect.bottom));
+        viewport_info.safe_area_insets = Some(egui::Margin {
+            left: inset(content_rect.left),
+            right: inset(window_size_px.width as i32 - content_rect.right),
+            top: inset(content_rect.top),
+            bottom: 0.0,
+        });
+    }
+
     if false {
         // It's tempting to do this, but it leads to a deadlock on Mac when running
         // `cargo run -p custom_window_frame`.
@@ -928,6 +1164,108 @@ pub fn update_viewport_info(
     }
 }
 
+fn monitor_from_selection(
+    window: &Window,
+    selection: egui::viewport::MonitorSelection,
+) -> Option<winit::monitor::MonitorHandle> {
+    use egui::viewport::MonitorSelection;
+    match selection {
+        MonitorSelection::Current => window.current_monitor(),
+        MonitorSelection::Primary => window.primary_monitor(),
+        MonitorSelection::Index(index) => window.available_monitors().nth(index),
+    }
+}
+
+fn selection_from_monitor(
+    window: &Window,
+    monitor: Option<winit::monitor::MonitorHandle>,
+) -> egui::viewport::MonitorSelection {
+    use egui::viewport::MonitorSelection;
+    monitor
+        .and_then(|monitor| window.available_monitors().position(|m| m == monitor))
+        .map_or(MonitorSelection::Current, MonitorSelection::Index)
+}
+
+fn to_winit_fullscreen(
+    window: &Window,
+    mode: egui::viewport::FullscreenMode,
+) -> winit::window::Fullscreen {
+    use egui::viewport::FullscreenMode;
+    use winit::window::Fullscreen;
+
+    let monitor = monitor_from_selection(window, mode.monitor());
+    if matches!(mode, FullscreenMode::Exclusive(_)) {
+        let video_mode = monitor.as_ref().and_then(|monitor| {
+            monitor.video_modes().max_by_key(|video_mode| {
+                let size = video_mode.size();
+                (
+                    size.width * size.height,
+                    video_mode.refresh_rate_millihertz(),
+                )
+            })
+        });
+        if let Some(video_mode) = video_mode {
+            return Fullscreen::Exclusive(video_mode);
+        }
+        log::debug!("No video mode found for exclusive fullscreen; falling back to borderless");
+    }
+    Fullscreen::Borderless(monitor)
+}
+
+fn from_winit_fullscreen(
+    window: &Window,
+    fullscreen: winit::window::Fullscreen,
+) -> egui::viewport::FullscreenMode {
+    use egui::viewport::FullscreenMode;
+    use winit::window::Fullscreen;
+
+    match fullscreen {
+        Fullscreen::Borderless(monitor) => {
+            FullscreenMode::Borderless(selection_from_monitor(window, monitor))
+        }
+        Fullscreen::Exclusive(video_mode) => {
+            FullscreenMode::Exclusive(selection_from_monitor(window, Some(video_mode.monitor())))
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    all(
+        any(feature = "x11", feature = "wayland"),
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    )
+))]
+fn scancode_from_physical_key(physical_key: winit::keyboard::PhysicalKey) -> Option<u32> {
+    use winit::platform::scancode::PhysicalKeyExtScancode as _;
+    physical_key.to_scancode()
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    all(
+        any(feature = "x11", feature = "wayland"),
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    )
+)))]
+fn scancode_from_physical_key(_physical_key: winit::keyboard::PhysicalKey) -> Option<u32> {
+    None
+}
+
 fn open_url_in_browser(_url: &str) {
     #[cfg(feature = "webbrowser")]
     if let Err(err) = webbrowser::open(_url) {
@@ -940,16 +1278,190 @@ fn open_url_in_browser(_url: &str) {
     }
 }
 
-/// Winit sends special keys (backspace, delete, F1, …) as characters.
-/// Ignore those.
-/// We also ignore '\r', '\n', '\t'.
-/// Newlines are handled by the `Key::Enter` event.
-fn is_printable_char(chr: char) -> bool {
-    let is_in_private_use_area = '\u{e000}' <= chr && chr <= '\u{f8ff}'
-        || '\u{f0000}' <= chr && chr <= '\u{ffffd}'
-        || '\u{100000}' <= chr && chr <= '\u{10fffd}';
+/// `file:///home/user/notes.txt` -> `/home/user/notes.txt`
+fn path_from_file_url(url: &str) -> Option<std::path::PathBuf> {
+    let path = url.strip_prefix("file://")?;
+    // Allow `file://localhost/…`:
+    let path = path.strip_prefix("localhost").unwrap_or(path);
+
+    // Percent-decode:
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let decoded = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(decoded) = decoded {
+            bytes.push(decoded);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    let path = String::from_utf8(bytes).ok()?;
 
-    !is_in_private_use_area && !chr.is_ascii_control()
+    // `/C:/Users/…` -> `C:/Users/…`
+    let path = if cfg!(windows) && path.as_bytes().get(2) == Some(&b':') {
+        &path[1..]
+    } else {
+        &path[..]
+    };
+    Some(path.into())
+}
+
+fn open_path_in_default_app(_path: &std::path::Path) {
+    #[cfg(feature = "links")]
+    {
+        #[cfg(target_os = "windows")]
+        spawn_command("explorer", &[_path.as_os_str()]);
+
+        #[cfg(target_os = "macos")]
+        spawn_command("open", &[_path.as_os_str()]);
+
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        spawn_command("xdg-open", &[_path.as_os_str()]);
+    }
+
+    #[cfg(not(feature = "links"))]
+    {
+        log::warn!("Cannot open path - feature \"links\" not enabled.");
+    }
+}
+
+fn reveal_path_in_file_manager(_path: &std::path::Path) {
+    #[cfg(feature = "links")]
+    {
+        #[cfg(target_os = "windows")]
+        {
+            let mut select = std::ffi::OsString::from("/select,");
+            select.push(_path);
+            spawn_command("explorer", &[select.as_os_str()]);
+        }
+
+        #[cfg(target_os = "macos")]
+        spawn_command("open", &[std::ffi::OsStr::new("-R"), _path.as_os_str()]);
+
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        {
+            // Ask the file manager to select the file, falling back to opening its directory.
+            // This can block for a while if there is no file manager, so do it in the background:
+            let path = std::fs::canonicalize(_path).unwrap_or_else(|_| _path.to_owned());
+            run_in_background(move || {
+                let uri = format!("file://{}", path.display());
+                let shown = std::process::Command::new("dbus-send")
+                    .args([
+                        "--session",
+                        "--dest=org.freedesktop.FileManager1",
+                        "--type=method_call",
+                        "/org/freedesktop/FileManager1",
+                        "org.freedesktop.FileManager1.ShowItems",
+                        &format!("array:string:{uri}"),
+                        "string:",
+                    ])
+                    .status()
+                    .map_or(false, |status| status.success());
+                if !shown {
+                    let dir = path.parent().unwrap_or(&path);
+                    if let Err(err) = std::process::Command::new("xdg-open").arg(dir).status() {
+                        log::warn!("Failed to run \"xdg-open\": {err}");
+                    }
+                }
+            });
+        }
+    }
+
+    #[cfg(not(feature = "links"))]
+    {
+        log::warn!("Cannot reveal path - feature \"links\" not enabled.");
+    }
+}
+
+/// Start a program without waiting for it to finish.
+#[cfg(feature = "links")]
+fn spawn_command(program: &str, args: &[&std::ffi::OsStr]) {
+    match std::process::Command::new(program).args(args).spawn() {
+        Ok(mut child) => {
+            // Reap the process when it exits, so it doesn't linger as a zombie:
+            run_in_background(move || {
+                child.wait().ok();
+            });
+        }
+        Err(err) => {
+            log::warn!("Failed to run {program:?}: {err}");
+        }
+    }
+}
+
+#[cfg(feature = "links")]
+fn run_in_background(f: impl FnOnce() + Send + 'static) {
+    if let Err(err) = std::thread::Builder::new()
+        .name("egui_winit_open".to_owned())
+        .spawn(f)
+    {
+        log::warn!("Failed to spawn thread: {err}");
+    }
+}
+
+/// On Windows, `AltGr` is reported as Ctrl+Alt. Many keyboard layouts use it to type
+/// characters like `@`, `€` and `{`, so such key presses are text input, not commands.
+///
+/// Other platforms report `AltGr` as a modifier of its own,
+/// so there Ctrl+Alt is always a shortcut.
+fn is_alt_gr_text_input(
+    os: egui::os::OperatingSystem,
+    modifiers: egui::Modifiers,
+    text: Option<&winit::keyboard::SmolStr>,
+) -> bool {
+    os == egui::os::OperatingSystem::Windows && modifiers.ctrl && modifiers.alt && text.is_some()
+}
+
+/// Translate an IME event into egui composition events.
+///
+/// Compose sequences and dead keys on Linux go through the IME too,
+/// as a preedit of the pending characters followed by a commit of the result.
+fn on_ime(
+    input_method_editor_started: &mut bool,
+    ime: &winit::event::Ime,
+    events: &mut Vec<egui::Event>,
+) {
+    // on Mac even Cmd-C is pressed during ime, a `c` is pushed to Preedit.
+    // So no need to check is_mac_cmd.
+    //
+    // How winit produce `Ime::Enabled` and `Ime::Disabled` differs in MacOS
+    // and Windows.
+    //
+    // - On Windows, before and after each Commit will produce an Enable/Disabled
+    // event.
+    // - On MacOS, only when user explicit enable/disable ime. No Disabled
+    // after Commit.
+    //
+    // We use input_method_editor_started to manually insert CompositionStart
+    // between Commits.
+    match ime {
+        winit::event::Ime::Enabled => (),
+        winit::event::Ime::Commit(text) => {
+            *input_method_editor_started = false;
+            events.push(egui::Event::CompositionEnd(text.clone()));
+        }
+        winit::event::Ime::Preedit(text, Some(_)) if !text.is_empty() => {
+            if !*input_method_editor_started {
+                *input_method_editor_started = true;
+                events.push(egui::Event::CompositionStart);
+            }
+            events.push(egui::Event::CompositionUpdate(text.clone()));
+        }
+        winit::event::Ime::Preedit(_, _) | winit::event::Ime::Disabled => {
+            // The preedit was cleared, e.g. because a compose sequence was cancelled,
+            // or right before a commit. Remove the marked text and end the composition.
+            if *input_method_editor_started {
+                *input_method_editor_started = false;
+                events.push(egui::Event::CompositionEnd(String::new()));
+            }
+        }
+    };
 }
 
 fn is_cut_command(modifiers: egui::Modifiers, keycode: egui::Key) -> bool {
@@ -1248,10 +1760,8 @@ fn process_viewport_command(
             }
         }
         ViewportCommand::InnerSize(size) => {
-            let width_px = pixels_per_point * size.x.max(1.0);
-            let height_px = pixels_per_point * size.y.max(1.0);
             if window
-                .request_inner_size(PhysicalSize::new(width_px, height_px))
+                .request_inner_size(logical_size(egui_ctx, size.max(Vec2::splat(1.0))))
                 .is_some()
             {
                 log::debug!("ViewportCommand::InnerSize ignored by winit");
@@ -1283,14 +1793,31 @@ fn process_viewport_command(
             ));
         }
         ViewportCommand::MinInnerSize(s) => {
-            window.set_min_inner_size((s.is_finite() && s != Vec2::ZERO).then_some(
-                PhysicalSize::new(pixels_per_point * s.x, pixels_per_point * s.y),
-            ));
+            window.set_min_inner_size(
+                (s.is_finite() && s != Vec2::ZERO).then(|| logical_size(egui_ctx, s)),
+            );
         }
         ViewportCommand::MaxInnerSize(s) => {
-            window.set_max_inner_size((s.is_finite() && s != Vec2::INFINITY).then_some(
-                PhysicalSize::new(pixels_per_point * s.x, pixels_per_point * s.y),
-            ));
+            window.set_max_inner_size(
+                (s.is_finite() && s != Vec2::INFINITY).then(|| logical_size(egui_ctx, s)),
+            );
+        }
+        ViewportCommand::SetSizeConstraints {
+            min_inner_size,
+            max_inner_size,
+        } => {
+            let min_inner_size = min_inner_size.filter(|s| s.is_finite() && *s != Vec2::ZERO);
+            let max_inner_size = max_inner_size.filter(|s| s.is_finite() && *s != Vec2::INFINITY);
+            if let (Some(min), Some(max)) = (min_inner_size, max_inner_size) {
+                if min.x > max.x || min.y > max.y {
+                    log::warn!("{command:?}: the minimum size is larger than the maximum size");
+                }
+            }
+
+            // Remove the old constraints first, so they never conflict with the new ones:
+            window.set_min_inner_size(None::<PhysicalSize<u32>>);
+            window.set_max_inner_size(max_inner_size.map(|s| logical_size(egui_ctx, s)));
+            window.set_min_inner_size(min_inner_size.map(|s| logical_size(egui_ctx, s)));
         }
         ViewportCommand::ResizeIncrements(s) => {
             window.set_resize_increments(
@@ -1328,12 +1855,22 @@ fn process_viewport_command(
         ViewportCommand::Fullscreen(v) => {
             window.set_fullscreen(v.then_some(winit::window::Fullscreen::Borderless(None)));
         }
+        ViewportCommand::SetFullscreen(mode) => {
+            window.set_fullscreen(mode.map(|mode| to_winit_fullscreen(window, mode)));
+        }
         ViewportCommand::Decorations(v) => window.set_decorations(v),
         ViewportCommand::WindowLevel(l) => window.set_window_level(match l {
             egui::viewport::WindowLevel::AlwaysOnBottom => WindowLevel::AlwaysOnBottom,
             egui::viewport::WindowLevel::AlwaysOnTop => WindowLevel::AlwaysOnTop,
             egui::viewport::WindowLevel::Normal => WindowLevel::Normal,
         }),
+        ViewportCommand::SkipTaskbar(_skip_taskbar) => {
+            #[cfg(target_os = "windows")]
+            {
+                use winit::platform::windows::WindowExtWindows as _;
+                window.set_skip_taskbar(_skip_taskbar);
+            }
+        }
         ViewportCommand::Icon(icon) => {
             window.set_window_icon(icon.map(|icon| {
                 winit::window::Icon::from_rgba(icon.rgba.clone(), icon.width, icon.height)
@@ -1386,20 +1923,45 @@ fn process_viewport_command(
             }
         }
         ViewportCommand::CursorGrab(o) => {
-            if let Err(err) = window.set_cursor_grab(match o {
+            let mut result = window.set_cursor_grab(match o {
                 egui::viewport::CursorGrab::None => CursorGrabMode::None,
                 egui::viewport::CursorGrab::Confined => CursorGrabMode::Confined,
                 egui::viewport::CursorGrab::Locked => CursorGrabMode::Locked,
-            }) {
+            });
+            if result.is_err() && o == egui::viewport::CursorGrab::Locked {
+                // Locking is not supported on Windows and X11, but confining is close enough
+                // since we still get the raw mouse motion.
+                result = window.set_cursor_grab(CursorGrabMode::Confined);
+            }
+            if let Err(err) = result {
                 log::warn!("{command:?}: {err}");
             }
         }
         ViewportCommand::CursorVisible(v) => window.set_cursor_visible(v),
+        ViewportCommand::CursorIcon(cursor_icon) => {
+            // `egui::Context` keeps applying the override to `PlatformOutput::cursor_icon`,
+            // but set it right away so it takes effect before the next frame.
+            if let Some(cursor_icon) = cursor_icon {
+                if let Some(winit_cursor_icon) = translate_cursor(cursor_icon) {
+                    window.set_cursor_visible(true);
+                    window.set_cursor_icon(winit_cursor_icon);
+                } else {
+                    window.set_cursor_visible(false);
+                }
+            }
+        }
         ViewportCommand::MousePassthrough(passthrough) => {
             if let Err(err) = window.set_cursor_hittest(!passthrough) {
                 log::warn!("{command:?}: {err}");
             }
         }
+        ViewportCommand::Backdrop(backdrop) => {
+            window.set_transparent(true);
+            backdrop::set_backdrop(window, backdrop);
+        }
+        ViewportCommand::TaskbarProgress(progress) => {
+            taskbar::set_taskbar_progress(window, progress);
+        }
         ViewportCommand::Screenshot => {
             *screenshot_requested = true;
         }
@@ -1477,16 +2039,19 @@ pub fn create_winit_window_builder<T>(
 
         // Windows:
         drag_and_drop: _drag_and_drop,
+        skip_taskbar: _skip_taskbar,
 
         // wayland:
         app_id: _app_id,
 
         mouse_passthrough: _, // handled in `apply_viewport_builder_to_window`
+        backdrop,             // also applied in `apply_viewport_builder_to_window`
     } = viewport_builder;
 
     let mut window_builder = winit::window::WindowBuilder::new()
         .with_title(title.unwrap_or_else(|| "egui window".to_owned()))
-        .with_transparent(transparent.unwrap_or(false))
+        // The backdrop itself is applied in `apply_viewport_builder_to_window`:
+        .with_transparent(transparent.unwrap_or(false) || backdrop.is_some())
         .with_decorations(decorations.unwrap_or(true))
         .with_resizable(resizable.unwrap_or(true))
         .with_visible(visible.unwrap_or(true))
@@ -1514,25 +2079,18 @@ pub fn create_winit_window_builder<T>(
         })
         .with_active(active.unwrap_or(true));
 
+    // The sizes are logical, so that winit converts them with the scale factor
+    // of the monitor the window ends up on:
     if let Some(size) = inner_size {
-        window_builder = window_builder.with_inner_size(PhysicalSize::new(
-            pixels_per_point * size.x,
-            pixels_per_point * size.y,
-        ));
+        window_builder = window_builder.with_inner_size(logical_size(egui_ctx, size));
     }
 
     if let Some(size) = min_inner_size {
-        window_builder = window_builder.with_min_inner_size(PhysicalSize::new(
-            pixels_per_point * size.x,
-            pixels_per_point * size.y,
-        ));
+        window_builder = window_builder.with_min_inner_size(logical_size(egui_ctx, size));
     }
 
     if let Some(size) = max_inner_size {
-        window_builder = window_builder.with_max_inner_size(PhysicalSize::new(
-            pixels_per_point * size.x,
-            pixels_per_point * size.y,
-        ));
+        window_builder = window_builder.with_max_inner_size(logical_size(egui_ctx, size));
     }
 
     if let Some(pos) = position {
@@ -1549,6 +2107,10 @@ pub fn create_winit_window_builder<T>(
         ));
     }
 
+    if let Some(app_id) = &_app_id {
+        taskbar::set_app_id(app_id);
+    }
+
     #[cfg(all(feature = "wayland", target_os = "linux"))]
     if let Some(app_id) = _app_id {
         use winit::platform::wayland::WindowBuilderExtWayland as _;
@@ -1561,6 +2123,12 @@ pub fn create_winit_window_builder<T>(
         window_builder = window_builder.with_drag_and_drop(enable);
     }
 
+    #[cfg(target_os = "windows")]
+    if let Some(skip_taskbar) = _skip_taskbar {
+        use winit::platform::windows::WindowBuilderExtWindows as _;
+        window_builder = window_builder.with_skip_taskbar(skip_taskbar);
+    }
+
     #[cfg(target_os = "macos")]
     {
         use winit::platform::macos::WindowBuilderExtMacOS as _;
@@ -1586,6 +2154,10 @@ pub fn apply_viewport_builder_to_window(
         }
     }
 
+    if let Some(backdrop) = builder.backdrop {
+        backdrop::set_backdrop(window, backdrop);
+    }
+
     {
         // In `create_winit_window_builder` we didn't know
         // on what monitor the window would appear, so we didn't know
@@ -1596,26 +2168,17 @@ pub fn apply_viewport_builder_to_window(
 
         if let Some(size) = builder.inner_size {
             if window
-                .request_inner_size(PhysicalSize::new(
-                    pixels_per_point * size.x,
-                    pixels_per_point * size.y,
-                ))
+                .request_inner_size(logical_size(egui_ctx, size))
                 .is_some()
             {
                 log::debug!("Failed to set window size");
             }
         }
         if let Some(size) = builder.min_inner_size {
-            window.set_min_inner_size(Some(PhysicalSize::new(
-                pixels_per_point * size.x,
-                pixels_per_point * size.y,
-            )));
+            window.set_min_inner_size(Some(logical_size(egui_ctx, size)));
         }
         if let Some(size) = builder.max_inner_size {
-            window.set_max_inner_size(Some(PhysicalSize::new(
-                pixels_per_point * size.x,
-                pixels_per_point * size.y,
-            )));
+            window.set_max_inner_size(Some(logical_size(egui_ctx, size)));
         }
         if let Some(pos) = builder.position {
             let pos = PhysicalPosition::new(pixels_per_point * pos.x, pixels_per_point * pos.y);
@@ -1719,3 +2282,100 @@ mod profiling_scopes {
     }
     pub(crate) use profile_scope;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::os::OperatingSystem;
+    use winit::event::Ime;
+
+    #[test]
+    fn alt_gr_is_only_text_input_on_windows() {
+        let ctrl_alt = egui::Modifiers::CTRL | egui::Modifiers::ALT;
+        let at = winit::keyboard::SmolStr::new("@");
+
+        assert!(is_alt_gr_text_input(
+            OperatingSystem::Windows,
+            ctrl_alt,
+            Some(&at)
+        ));
+        assert!(!is_alt_gr_text_input(
+            OperatingSystem::Windows,
+            ctrl_alt,
+            None
+        ));
+        assert!(!is_alt_gr_text_input(
+            OperatingSystem::Windows,
+            egui::Modifiers::CTRL,
+            Some(&at)
+        ));
+
+        // Ctrl+Alt+C is a copy shortcut on Linux, even if the layout produces text for it.
+        let c = winit::keyboard::SmolStr::new("c");
+        assert!(!is_alt_gr_text_input(
+            OperatingSystem::Nix,
+            ctrl_alt,
+            Some(&c)
+        ));
+        assert!(!is_alt_gr_text_input(
+            OperatingSystem::Mac,
+            ctrl_alt,
+            Some(&c)
+        ));
+    }
+
+    #[test]
+    fn ime_compose_sequence() {
+        let mut started = false;
+        let mut events = vec![];
+        for ime in [
+            Ime::Enabled,
+            Ime::Preedit("´".to_owned(), Some((0, 2))),
+            Ime::Preedit(String::new(), None),
+            Ime::Commit("é".to_owned()),
+            Ime::Disabled,
+        ] {
+            on_ime(&mut started, &ime, &mut events);
+        }
+        assert!(!started);
+        assert_eq!(
+            events,
+            vec![
+                egui::Event::CompositionStart,
+                egui::Event::CompositionUpdate("´".to_owned()),
+                egui::Event::CompositionEnd(String::new()),
+                egui::Event::CompositionEnd("é".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ime_cancelled_compose_sequence_ends_composition() {
+        let mut started = false;
+        let mut events = vec![];
+        for ime in [
+            Ime::Preedit("¨".to_owned(), Some((0, 2))),
+            Ime::Preedit(String::new(), None),
+        ] {
+            on_ime(&mut started, &ime, &mut events);
+        }
+        assert!(!started);
+        assert_eq!(
+            events,
+            vec![
+                egui::Event::CompositionStart,
+                egui::Event::CompositionUpdate("¨".to_owned()),
+                egui::Event::CompositionEnd(String::new()),
+            ]
+        );
+
+        // A stray empty preedit outside a composition does nothing:
+        events.clear();
+        on_ime(
+            &mut started,
+            &Ime::Preedit(String::new(), None),
+            &mut events,
+        );
+        assert!(events.is_empty());
+    }
+}
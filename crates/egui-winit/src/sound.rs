@@ -0,0 +1,121 @@
+//! Playing the standard feedback sounds of the OS, see [`egui::PlatformOutput::play_system_sound`].
+//!
+//! * On Windows we call `MessageBeep`, which plays the sound the user picked for each kind.
+//! * On Mac we call `NSBeep`, which plays the alert sound for every kind.
+//! * On Linux we run `canberra-gtk-play` with the matching sound of the freedesktop sound theme.
+
+use egui::SoundKind;
+
+pub fn play(sound: SoundKind) {
+    #[cfg(target_os = "windows")]
+    windows::play(sound);
+
+    #[cfg(target_os = "macos")]
+    mac::play(sound);
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    freedesktop::play(sound);
+
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    let _ = sound;
+}
+
+#[cfg(target_os = "windows")]
+#[allow(unsafe_code)]
+mod windows {
+    use super::SoundKind;
+
+    const MB_OK: u32 = 0x00;
+    const MB_ICONHAND: u32 = 0x10;
+    const MB_ICONEXCLAMATION: u32 = 0x30;
+    const MB_ICONASTERISK: u32 = 0x40;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn MessageBeep(kind: u32) -> i32;
+    }
+
+    pub fn play(sound: SoundKind) {
+        let kind = match sound {
+            SoundKind::Beep => MB_OK,
+            SoundKind::Alert => MB_ICONEXCLAMATION,
+            SoundKind::Error => MB_ICONHAND,
+            SoundKind::Notification => MB_ICONASTERISK,
+        };
+        // SAFETY: `MessageBeep` plays the sound asynchronously, and has no other effects.
+        unsafe {
+            MessageBeep(kind);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[allow(unsafe_code)]
+mod mac {
+    use super::SoundKind;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {
+        fn NSBeep();
+    }
+
+    pub fn play(_sound: SoundKind) {
+        // SAFETY: `NSBeep` plays the alert sound asynchronously, and has no other effects.
+        unsafe {
+            NSBeep();
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod freedesktop {
+    use super::SoundKind;
+
+    pub fn play(sound: SoundKind) {
+        // See the freedesktop.org sound naming specification:
+        let id = match sound {
+            SoundKind::Beep => "bell",
+            SoundKind::Alert => "dialog-warning",
+            SoundKind::Error => "dialog-error",
+            SoundKind::Notification => "message",
+        };
+
+        match std::process::Command::new("canberra-gtk-play")
+            .args(["--id", id])
+            .spawn()
+        {
+            Ok(mut child) => {
+                // Reap the process when it exits, so it doesn't linger as a zombie:
+                let result = std::thread::Builder::new()
+                    .name("egui_winit_sound".to_owned())
+                    .spawn(move || {
+                        child.wait().ok();
+                    });
+                if let Err(err) = result {
+                    log::debug!("Failed to spawn thread: {err}");
+                }
+            }
+            Err(err) => log::debug!("Failed to run \"canberra-gtk-play\": {err}"),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Showing [`egui::TaskbarProgress`] (and a badge count) on the taskbar entry of a window.
+//!
+//! winit has no API for this, so we talk to the platform directly:
+//! * On Windows we use the `ITaskbarList3` COM interface.
+//! * On Linux we emit the `com.canonical.Unity.LauncherEntry.Update` D-Bus signal
+//!   (understood by KDE, Dock-to-Dash, Plank, …) using `gdbus`.
+//!   This identifies the app by its `.desktop` file, see [`egui::ViewportBuilder::with_app_id`].
+
+use egui::TaskbarProgress;
+use winit::window::Window;
+
+pub fn set_taskbar_progress(_window: &Window, _progress: TaskbarProgress) {
+    #[cfg(target_os = "windows")]
+    windows::set_progress(_window, _progress);
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    unity::set_progress(_progress);
+}
+
+/// Show a number on the launcher icon of the app, or hide it with `None`.
+///
+/// Only supported on Linux, using the same D-Bus signal as [`set_taskbar_progress`].
+pub fn set_taskbar_badge_count(_count: Option<i64>) {
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    unity::set_count(_count);
+}
+
+/// Remember the app id of the first window, used to identify the app on Linux.
+pub fn set_app_id(_app_id: &str) {
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    unity::APP_ID.get_or_init(|| _app_id.to_owned());
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::ptr::null_mut;
+
+    use raw_window_handle::{HasRawWindowHandle as _, RawWindowHandle};
+    use winapi::{
+        shared::{winerror::SUCCEEDED, wtypesbase::CLSCTX_INPROC_SERVER},
+        um::{
+            combaseapi::{CoCreateInstance, CoInitializeEx},
+            objbase::COINIT_APARTMENTTHREADED,
+            shobjidl_core::{
+                CLSID_TaskbarList, ITaskbarList3, TBPF_INDETERMINATE, TBPF_NOPROGRESS, TBPF_NORMAL,
+            },
+        },
+        Interface as _,
+    };
+
+    use super::{TaskbarProgress, Window};
+
+    pub fn set_progress(window: &Window, progress: TaskbarProgress) {
+        let RawWindowHandle::Win32(handle) = window.raw_window_handle() else {
+            return;
+        };
+        let hwnd = handle.hwnd.cast();
+
+        // SAFETY: we follow the COM rules: the object is only used on this thread,
+        // and released before returning.
+        unsafe {
+            // winit already initialized COM on this thread, so this is usually a no-op:
+            CoInitializeEx(null_mut(), COINIT_APARTMENTTHREADED);
+
+            let mut object = null_mut();
+            let hr = CoCreateInstance(
+                &CLSID_TaskbarList,
+                null_mut(),
+                CLSCTX_INPROC_SERVER,
+                &ITaskbarList3::uuidof(),
+                &mut object,
+            );
+            if !SUCCEEDED(hr) || object.is_null() {
+                log::debug!("Failed to create ITaskbarList3: {hr:#x}");
+                return;
+            }
+
+            let taskbar = &*object.cast::<ITaskbarList3>();
+            if SUCCEEDED(taskbar.HrInit()) {
+                match progress {
+                    TaskbarProgress::None => {
+                        taskbar.SetProgressState(hwnd, TBPF_NOPROGRESS);
+                    }
+                    TaskbarProgress::Indeterminate => {
+                        taskbar.SetProgressState(hwnd, TBPF_INDETERMINATE);
+                    }
+                    TaskbarProgress::Fraction(fraction) => {
+                        const TOTAL: u64 = 10_000;
+                        let completed = (fraction.clamp(0.0, 1.0) * TOTAL as f32).round() as u64;
+                        taskbar.SetProgressState(hwnd, TBPF_NORMAL);
+                        taskbar.SetProgressValue(hwnd, completed, TOTAL);
+                    }
+                }
+            }
+            taskbar.Release();
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod unity {
+    use std::process::{Child, Command};
+    use std::sync::OnceLock;
+
+    use egui::mutex::Mutex;
+
+    use super::TaskbarProgress;
+
+    pub static APP_ID: OnceLock<String> = OnceLock::new();
+
+    /// `gdbus` processes that may not have exited yet.
+    static RUNNING: OnceLock<Mutex<Vec<Child>>> = OnceLock::new();
+
+    pub fn set_progress(progress: TaskbarProgress) {
+        let properties = match progress {
+            TaskbarProgress::None => "{'progress-visible': <false>}".to_owned(),
+            // The launcher API has no indeterminate state, so show an empty bar:
+            TaskbarProgress::Indeterminate => {
+                "{'progress': <0.0>, 'progress-visible': <true>}".to_owned()
+            }
+            TaskbarProgress::Fraction(fraction) => {
+                format!("{{'progress': <{fraction:.3}>, 'progress-visible': <true>}}")
+            }
+        };
+        emit_update(&properties);
+    }
+
+    pub fn set_count(count: Option<i64>) {
+        let properties = match count {
+            Some(count) => format!("{{'count': <int64 {count}>, 'count-visible': <true>}}"),
+            None => "{'count-visible': <false>}".to_owned(),
+        };
+        emit_update(&properties);
+    }
+
+    /// Emit `com.canonical.Unity.LauncherEntry.Update` with these properties (a `GVariant` `a{sv}`).
+    fn emit_update(properties: &str) {
+        let desktop_id = match APP_ID.get() {
+            Some(app_id) => app_id.clone(),
+            None => match std::env::current_exe() {
+                Ok(exe) => exe
+                    .file_stem()
+                    .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned()),
+                Err(_) => return,
+            },
+        };
+        if desktop_id.is_empty() {
+            return;
+        }
+
+        let mut running = RUNNING.get_or_init(|| Mutex::new(Vec::new())).lock();
+        // Reap the processes that have exited, so they don't linger as zombies:
+        running.retain_mut(|child| matches!(child.try_wait(), Ok(None)));
+
+        let result = Command::new("gdbus")
+            .args([
+                "emit",
+                "--session",
+                "--object-path",
+                &format!("/com/canonical/unity/launcherentry/{}", std::process::id()),
+                "--signal",
+                "com.canonical.Unity.LauncherEntry.Update",
+                &format!("application://{desktop_id}.desktop"),
+                properties,
+            ])
+            .spawn();
+        match result {
+            Ok(child) => running.push(child),
+            Err(err) => log::debug!("Failed to run gdbus: {err}"),
+        }
+    }
+}
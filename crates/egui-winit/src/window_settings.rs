@@ -15,6 +15,35 @@ pub struct WindowSettings {
 
     /// Inner size of window in logical pixels
     inner_size_points: Option<egui::Vec2>,
+
+    /// The [`monitor_layout_hash`] of the monitors that were connected when this was saved.
+    monitor_layout: Option<u64>,
+}
+
+/// Identifies the current layout of the monitors: their positions, sizes and scale factors.
+///
+/// Use it to store window settings per monitor setup, e.g. one for a laptop on its own
+/// and another for the same laptop connected to a second monitor.
+///
+/// The monitors can come from [`winit::window::Window::available_monitors`]
+/// or [`winit::event_loop::EventLoopWindowTarget::available_monitors`].
+pub fn monitor_layout_hash(monitors: impl Iterator<Item = winit::monitor::MonitorHandle>) -> u64 {
+    crate::profile_function!();
+
+    let mut monitors: Vec<_> = monitors
+        .map(|monitor| {
+            let position = monitor.position();
+            let size = monitor.size();
+            (
+                (position.x, position.y),
+                (size.width, size.height),
+                monitor.scale_factor().to_bits(),
+            )
+        })
+        .collect();
+    // The order of the monitors is not guaranteed:
+    monitors.sort_unstable();
+    egui::util::hash(monitors)
 }
 
 impl WindowSettings {
@@ -43,6 +72,8 @@ impl WindowSettings {
                 inner_size_points.width,
                 inner_size_points.height,
             )),
+
+            monitor_layout: Some(monitor_layout_hash(window.available_monitors())),
         }
     }
 
@@ -50,6 +81,12 @@ impl WindowSettings {
         self.inner_size_points
     }
 
+    /// The [`monitor_layout_hash`] of when these settings were saved,
+    /// or `None` if they were saved by an older version.
+    pub fn monitor_layout(&self) -> Option<u64> {
+        self.monitor_layout
+    }
+
     pub fn initialize_viewport_builder(
         &self,
         mut viewport_builder: ViewportBuilder,
@@ -100,6 +137,10 @@ impl WindowSettings {
         }
     }
 
+    /// Make sure the window ends up on one of the available monitors.
+    ///
+    /// This always clamps on Windows, and on other platforms only if the monitors
+    /// have changed since the settings were saved, see [`Self::monitor_layout`].
     pub fn clamp_position_to_monitors<E>(
         &mut self,
         egui_zoom_factor: f32,
@@ -109,11 +150,25 @@ impl WindowSettings {
         // the given position is invalid.
         // If this happens on Mac, the window is clamped into valid area.
         // If this happens on Windows, the window becomes invisible to the user 🤦‍♂️
-        // So on Windows we clamp the position to the monitor it is on.
-        if !cfg!(target_os = "windows") {
+        // So on Windows we always clamp the position to the monitor it is on.
+        // Elsewhere we only do so if the monitors are not the same as when the settings were saved.
+        let monitor_layout_changed = self.monitor_layout.map_or(true, |saved| {
+            saved != monitor_layout_hash(event_loop.available_monitors())
+        });
+        if !cfg!(target_os = "windows") && !monitor_layout_changed {
             return;
         }
 
+        self.clamp_position_to_available_monitors(egui_zoom_factor, event_loop);
+    }
+
+    /// Move the window so that it is within one of the available monitors,
+    /// preferably the one it is already on, or else the primary one.
+    pub fn clamp_position_to_available_monitors<E>(
+        &mut self,
+        egui_zoom_factor: f32,
+        event_loop: &winit::event_loop::EventLoopWindowTarget<E>,
+    ) {
         let Some(inner_size_points) = self.inner_size_points else {
             return;
         };
@@ -0,0 +1,302 @@
+//! Headless benchmarking of egui code, e.g. widgets, layout and tessellation.
+//!
+//! A [`Harness`] runs your ui for a number of frames without any window or GPU,
+//! and measures how long each phase of the frame took.
+//! It works on its own, and can also drive a [`criterion`](https://docs.rs/criterion) benchmark:
+//!
+//! ```
+//! let mut harness = egui::bench::Harness::new(|ctx| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         egui::ScrollArea::vertical().show(ui, |ui| {
+//!             for i in 0..100 {
+//!                 ui.label(format!("Row {i}"));
+//!             }
+//!         });
+//!     });
+//! });
+//!
+//! let report = harness.run(10);
+//! println!("{report}");
+//!
+//! // With criterion:
+//! // c.bench_function("scroll_area", |b| b.iter(|| harness.run_frame()));
+//! // c.bench_function("scroll_area tessellate", |b| {
+//! //     b.iter_custom(|iters| harness.time(iters, egui::bench::Phase::Tessellate))
+//! // });
+//! ```
+//!
+//! `egui` can't count allocations by itself,
+//! but you can hook up a counting global allocator with [`Harness::with_allocation_counter`].
+
+use std::time::{Duration, Instant};
+
+use crate::*;
+
+/// A part of a frame, see [`Harness::time`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// [`Context::begin_frame`].
+    Input,
+
+    /// Running the ui closure and [`Context::end_frame`].
+    Update,
+
+    /// [`Context::tessellate`].
+    Tessellate,
+
+    /// The whole frame.
+    Total,
+}
+
+/// Measurements of a single frame run by a [`Harness`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameStats {
+    /// How long the phases of the frame took.
+    ///
+    /// `input` is [`Context::begin_frame`], `update` is the ui closure plus [`Context::end_frame`],
+    /// and `tessellate` is [`Context::tessellate`]. Nothing is painted, so `paint` and `swap` are zero.
+    pub timings: FrameTimings,
+
+    /// Number of heap allocations during the frame,
+    /// if an allocation counter was set with [`Harness::with_allocation_counter`].
+    pub allocations: Option<u64>,
+
+    /// Number of [`epaint::ClippedShape`]s output by the frame.
+    pub num_shapes: usize,
+
+    /// Number of vertices after tessellation.
+    pub num_vertices: usize,
+
+    /// Number of indices after tessellation.
+    pub num_indices: usize,
+}
+
+impl FrameStats {
+    fn phase(&self, phase: Phase) -> f32 {
+        match phase {
+            Phase::Input => self.timings.input,
+            Phase::Update => self.timings.update,
+            Phase::Tessellate => self.timings.tessellate,
+            Phase::Total => self.timings.total(),
+        }
+    }
+}
+
+/// The result of [`Harness::run`].
+#[derive(Clone, Debug, Default)]
+pub struct BenchReport {
+    /// One entry per frame, in order.
+    pub frames: Vec<FrameStats>,
+}
+
+impl BenchReport {
+    /// Mean time of the given phase, in seconds.
+    pub fn mean(&self, phase: Phase) -> f32 {
+        let sum: f32 = self.frames.iter().map(|frame| frame.phase(phase)).sum();
+        sum / self.frames.len().max(1) as f32
+    }
+
+    /// Median time of the given phase, in seconds.
+    pub fn median(&self, phase: Phase) -> f32 {
+        let mut times: Vec<f32> = self.frames.iter().map(|frame| frame.phase(phase)).collect();
+        times.sort_by(|a, b| a.total_cmp(b));
+        times.get(times.len() / 2).copied().unwrap_or_default()
+    }
+
+    /// Mean number of allocations per frame, if they were counted.
+    pub fn mean_allocations(&self) -> Option<f64> {
+        let total = self
+            .frames
+            .iter()
+            .map(|frame| frame.allocations)
+            .sum::<Option<u64>>()?;
+        Some(total as f64 / self.frames.len().max(1) as f64)
+    }
+}
+
+impl std::fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} frames, median / mean:", self.frames.len())?;
+        for (name, phase) in [
+            ("input", Phase::Input),
+            ("update", Phase::Update),
+            ("tessellate", Phase::Tessellate),
+            ("total", Phase::Total),
+        ] {
+            writeln!(
+                f,
+                "  {name:<10} {:8.3} ms / {:8.3} ms",
+                1e3 * self.median(phase),
+                1e3 * self.mean(phase)
+            )?;
+        }
+        if let Some(allocations) = self.mean_allocations() {
+            writeln!(f, "  {allocations:.1} allocations per frame")?;
+        }
+        if let Some(last) = self.frames.last() {
+            writeln!(
+                f,
+                "  {} shapes, {} vertices, {} indices",
+                last.num_shapes, last.num_vertices, last.num_indices
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs a ui headlessly, frame after frame, and measures it.
+///
+/// See the [module-level docs](crate::bench).
+pub struct Harness {
+    ctx: Context,
+    run_ui: Box<dyn FnMut(&Context)>,
+    raw_input: RawInput,
+    time: f64,
+    frame_duration: f64,
+    allocation_counter: Option<Box<dyn Fn() -> u64>>,
+}
+
+impl Harness {
+    /// Benchmark the given ui, in a new [`Context`] with an 800x600 screen.
+    pub fn new(run_ui: impl FnMut(&Context) + 'static) -> Self {
+        Self {
+            ctx: Context::default(),
+            run_ui: Box::new(run_ui),
+            raw_input: RawInput {
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+                ..Default::default()
+            },
+            time: 0.0,
+            frame_duration: 1.0 / 60.0,
+            allocation_counter: None,
+        }
+    }
+
+    /// The size of the simulated screen, in points.
+    #[inline]
+    pub fn with_screen_size(mut self, size: Vec2) -> Self {
+        self.raw_input.screen_rect = Some(Rect::from_min_size(Pos2::ZERO, size));
+        self
+    }
+
+    /// The input given to every frame.
+    ///
+    /// The [`RawInput::time`] is advanced by 1/60 of a second each frame,
+    /// so animations progress as in a real app.
+    #[inline]
+    pub fn with_raw_input(mut self, raw_input: RawInput) -> Self {
+        self.raw_input = raw_input;
+        self
+    }
+
+    /// Count allocations, using a function that returns the total number of allocations so far.
+    ///
+    /// Usually this reads a counter incremented by a [`std::alloc::GlobalAlloc`] wrapper
+    /// installed with `#[global_allocator]` in your benchmark.
+    #[inline]
+    pub fn with_allocation_counter(mut self, counter: impl Fn() -> u64 + 'static) -> Self {
+        self.allocation_counter = Some(Box::new(counter));
+        self
+    }
+
+    /// The [`Context`] the ui runs in, e.g. to change the [`Style`] before running.
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Run one full frame, including tessellation.
+    pub fn run_frame(&mut self) -> FrameStats {
+        let allocations_before = self.allocation_counter.as_ref().map(|count| count());
+
+        let mut raw_input = self.raw_input.clone();
+        raw_input.time = Some(self.time);
+        raw_input.predicted_dt = self.frame_duration as f32;
+        self.time += self.frame_duration;
+
+        let start = Instant::now();
+        self.ctx.begin_frame(raw_input);
+        let input_done = Instant::now();
+        (self.run_ui)(&self.ctx);
+        let full_output = self.ctx.end_frame();
+        let update_done = Instant::now();
+        let num_shapes = full_output.shapes.len();
+        let primitives = self
+            .ctx
+            .tessellate(full_output.shapes, full_output.pixels_per_point);
+        let tessellate_done = Instant::now();
+
+        let allocations = self
+            .allocation_counter
+            .as_ref()
+            .zip(allocations_before)
+            .map(|(count, before)| count().saturating_sub(before));
+
+        let (mut num_vertices, mut num_indices) = (0, 0);
+        for primitive in &primitives {
+            if let epaint::Primitive::Mesh(mesh) = &primitive.primitive {
+                num_vertices += mesh.vertices.len();
+                num_indices += mesh.indices.len();
+            }
+        }
+
+        FrameStats {
+            timings: FrameTimings {
+                input: (input_done - start).as_secs_f32(),
+                update: (update_done - input_done).as_secs_f32(),
+                tessellate: (tessellate_done - update_done).as_secs_f32(),
+                paint: 0.0,
+                swap: 0.0,
+            },
+            allocations,
+            num_shapes,
+            num_vertices,
+            num_indices,
+        }
+    }
+
+    /// Run the given number of frames and collect their stats.
+    pub fn run(&mut self, num_frames: usize) -> BenchReport {
+        BenchReport {
+            frames: (0..num_frames).map(|_| self.run_frame()).collect(),
+        }
+    }
+
+    /// Run `num_frames` frames and return the total time spent in the given phase.
+    ///
+    /// This matches the signature of criterion's `Bencher::iter_custom`,
+    /// so you can benchmark a single phase, e.g. only the tessellation.
+    pub fn time(&mut self, num_frames: u64, phase: Phase) -> Duration {
+        (0..num_frames)
+            .map(|_| Duration::from_secs_f32(self.run_frame().phase(phase)))
+            .sum()
+    }
+}
+
+#[test]
+fn test_harness() {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    let counter = Arc::new(AtomicU64::new(0));
+    let mut harness = Harness::new({
+        let counter = counter.clone();
+        move |ctx| {
+            counter.fetch_add(3, Ordering::Relaxed); // pretend we allocate
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label("Hello");
+            });
+        }
+    })
+    .with_allocation_counter(move || counter.load(Ordering::Relaxed));
+
+    let report = harness.run(3);
+    assert_eq!(report.frames.len(), 3);
+    assert_eq!(report.mean_allocations(), Some(3.0));
+    let last = report.frames.last().unwrap();
+    assert!(last.num_shapes > 0);
+    assert!(last.num_vertices > 0);
+    assert!(report.mean(Phase::Total) > 0.0);
+    assert!(report.to_string().contains("3 frames"));
+}
@@ -74,6 +74,7 @@ pub struct Area {
     default_pos: Option<Pos2>,
     pivot: Align2,
     anchor: Option<(Align2, Vec2)>,
+    anchored_to: Option<(Rect, RectAlign)>,
     new_pos: Option<Pos2>,
     edges_padded_for_resize: bool,
 }
@@ -92,6 +93,7 @@ impl Area {
             new_pos: None,
             pivot: Align2::LEFT_TOP,
             anchor: None,
+            anchored_to: None,
             edges_padded_for_resize: false,
         }
     }
@@ -216,6 +218,33 @@ impl Area {
         self.movable(false)
     }
 
+    /// Position the area next to `parent_rect`, usually the [`Response::rect`] of a widget.
+    ///
+    /// For instance, [`RectAlign::BOTTOM_START`] puts the area below `parent_rect`,
+    /// with their left sides aligned.
+    ///
+    /// If the area doesn't fit on the screen (or in [`Self::constrain_to`]),
+    /// it is mirrored to the other side of `parent_rect`, see [`RectAlign::symmetries`].
+    ///
+    /// This makes the area immovable.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let response = ui.button("Options");
+    /// egui::Area::new("options_popup")
+    ///     .order(egui::Order::Foreground)
+    ///     .anchored_to(response.rect, egui::RectAlign::BOTTOM_START)
+    ///     .show(ui.ctx(), |ui| {
+    ///         ui.label("Below the button, or above it if there is no room below");
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn anchored_to(mut self, parent_rect: Rect, align: RectAlign) -> Self {
+        self.anchored_to = Some((parent_rect, align));
+        self.movable(false)
+    }
+
     pub(crate) fn get_pivot(&self) -> Align2 {
         if let Some((pivot, _)) = self.anchor {
             pivot
@@ -273,6 +302,7 @@ impl Area {
             new_pos,
             pivot,
             anchor,
+            anchored_to,
             constrain,
             constrain_rect,
             edges_padded_for_resize,
@@ -309,6 +339,15 @@ impl Area {
             );
         }
 
+        if let Some((parent_rect, align)) = anchored_to {
+            let bounds = constrain_rect.unwrap_or_else(|| ctx.screen_rect());
+            let alternatives = std::iter::once(align).chain(align.symmetries());
+            let align =
+                RectAlign::find_best_align(alternatives, bounds, parent_rect, 0.0, state.size)
+                    .unwrap_or(align);
+            state.set_left_top_pos(align.align_rect(parent_rect, state.size, 0.0).min);
+        }
+
         // interact right away to prevent frame-delay
         let mut move_response = {
             let interact_id = layer_id.id.with("move");
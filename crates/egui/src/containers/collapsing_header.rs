@@ -13,6 +13,20 @@ pub(crate) struct InnerState {
     open_height: Option<f32>,
 }
 
+/// A request to open or close all collapsing regions in a scope, see [`CollapsingState::open_all`].
+#[derive(Clone, Copy, Debug)]
+struct OpenAllRequest {
+    open: bool,
+
+    /// The frame of the request, so each region only follows it once.
+    frame_nr: u64,
+}
+
+/// The scopes of [`CollapsingState::scope`] we are currently inside of, innermost last.
+fn scopes_id() -> Id {
+    Id::new("collapsing_scopes")
+}
+
 /// This is a a building block for building collapsing regions.
 ///
 /// It is used by [`CollapsingHeader`] and [`Window`], but can also be used on its own.
@@ -22,13 +36,17 @@ pub(crate) struct InnerState {
 pub struct CollapsingState {
     id: Id,
     state: InnerState,
+    animation_time: Option<f32>,
 }
 
 impl CollapsingState {
     pub fn load(ctx: &Context, id: Id) -> Option<Self> {
         ctx.data_mut(|d| {
-            d.get_persisted::<InnerState>(id)
-                .map(|state| Self { id, state })
+            d.get_persisted::<InnerState>(id).map(|state| Self {
+                id,
+                state,
+                animation_time: None,
+            })
         })
     }
 
@@ -44,14 +62,112 @@ impl CollapsingState {
         self.id
     }
 
+    /// Load the state, or create it if it isn't stored yet.
+    ///
+    /// This also applies any [`Self::open_all`] or [`Self::close_all`]
+    /// of the [`Self::scope`]s we are inside of.
     pub fn load_with_default_open(ctx: &Context, id: Id, default_open: bool) -> Self {
-        Self::load(ctx, id).unwrap_or(Self {
+        let mut state = Self::load(ctx, id).unwrap_or(Self {
             id,
             state: InnerState {
                 open: default_open,
                 open_height: None,
             },
-        })
+            animation_time: None,
+        });
+        state.follow_open_all(ctx);
+        state
+    }
+
+    /// Open all collapsing regions shown inside [`Self::scope`] with this `scope_id`,
+    /// including nested ones as they are shown.
+    ///
+    /// The user can still close them afterwards.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::collapsing_header::CollapsingState;
+    ///
+    /// let scope_id = ui.make_persistent_id("tree");
+    /// ui.horizontal(|ui| {
+    ///     if ui.button("Expand all").clicked() {
+    ///         CollapsingState::open_all(ui.ctx(), scope_id);
+    ///     }
+    ///     if ui.button("Collapse all").clicked() {
+    ///         CollapsingState::close_all(ui.ctx(), scope_id);
+    ///     }
+    /// });
+    /// CollapsingState::scope(ui, scope_id, |ui| {
+    ///     ui.collapsing("Fruit", |ui| {
+    ///         ui.collapsing("Apples", |ui| ui.label("Granny Smith"));
+    ///     });
+    /// });
+    /// # });
+    /// ```
+    pub fn open_all(ctx: &Context, scope_id: Id) {
+        Self::set_all_open(ctx, scope_id, true);
+    }
+
+    /// Close all collapsing regions shown inside [`Self::scope`] with this `scope_id`,
+    /// including nested ones.
+    ///
+    /// See [`Self::open_all`].
+    pub fn close_all(ctx: &Context, scope_id: Id) {
+        Self::set_all_open(ctx, scope_id, false);
+    }
+
+    fn set_all_open(ctx: &Context, scope_id: Id, open: bool) {
+        let request = OpenAllRequest {
+            open,
+            frame_nr: ctx.frame_nr(),
+        };
+        ctx.data_mut(|d| d.insert_temp(scope_id.with("open_all"), request));
+        ctx.request_repaint();
+    }
+
+    /// The collapsing regions shown by `add_contents` are affected by
+    /// [`Self::open_all`] and [`Self::close_all`] with this `scope_id`.
+    ///
+    /// Scopes can be nested.
+    pub fn scope<R>(ui: &mut Ui, scope_id: Id, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+        ui.data_mut(|d| {
+            d.get_temp_mut_or_default::<Vec<Id>>(scopes_id())
+                .push(scope_id);
+        });
+        let ret = add_contents(ui);
+        ui.data_mut(|d| {
+            d.get_temp_mut_or_default::<Vec<Id>>(scopes_id()).pop();
+        });
+        ret
+    }
+
+    /// Follow the latest [`OpenAllRequest`] of the scopes we are in, unless we already have.
+    fn follow_open_all(&mut self, ctx: &Context) {
+        let followed_id = self.id.with("open_all_followed");
+        let request = ctx.data(|d| {
+            let scopes = d.get_temp::<Vec<Id>>(scopes_id()).unwrap_or_default();
+            scopes
+                .iter()
+                .filter_map(|scope_id| d.get_temp::<OpenAllRequest>(scope_id.with("open_all")))
+                .max_by_key(|request| request.frame_nr)
+                .filter(|request| {
+                    d.get_temp::<u64>(followed_id)
+                        .map_or(true, |followed| followed < request.frame_nr)
+                })
+        });
+        if let Some(request) = request {
+            self.state.open = request.open;
+            ctx.data_mut(|d| d.insert_temp(followed_id, request.frame_nr));
+        }
+    }
+
+    /// How long it takes to open or close, in seconds.
+    ///
+    /// Default: [`Style::animation_time`].
+    #[inline]
+    pub fn animation_time(mut self, animation_time: f32) -> Self {
+        self.animation_time = Some(animation_time);
+        self
     }
 
     pub fn is_open(&self) -> bool {
@@ -72,7 +188,10 @@ impl CollapsingState {
         if ctx.memory(|mem| mem.everything_is_visible()) {
             1.0
         } else {
-            ctx.animate_bool(self.id, self.state.open)
+            let animation_time = self
+                .animation_time
+                .unwrap_or_else(|| ctx.style().animation_time);
+            ctx.animate_bool_with_time(self.id, self.state.open, animation_time)
         }
     }
 
@@ -363,6 +482,7 @@ pub struct CollapsingHeader {
     selected: bool,
     show_background: bool,
     icon: Option<IconPainter>,
+    animation_time: Option<f32>,
 }
 
 impl CollapsingHeader {
@@ -385,6 +505,7 @@ impl CollapsingHeader {
             selected: false,
             show_background: false,
             icon: None,
+            animation_time: None,
         }
     }
 
@@ -460,16 +581,26 @@ impl CollapsingHeader {
         self.icon = Some(Box::new(icon_fn));
         self
     }
+
+    /// How long it takes to open or close, in seconds.
+    ///
+    /// Default: [`Style::animation_time`].
+    #[inline]
+    pub fn animation_time(mut self, animation_time: f32) -> Self {
+        self.animation_time = Some(animation_time);
+        self
+    }
 }
 
-struct Prepared {
+struct Prepared<T> {
     header_response: Response,
+    trailing_returned: Option<T>,
     state: CollapsingState,
     openness: f32,
 }
 
 impl CollapsingHeader {
-    fn begin(self, ui: &mut Ui) -> Prepared {
+    fn begin<T>(self, ui: &mut Ui, add_trailing: Option<impl FnOnce(&mut Ui) -> T>) -> Prepared<T> {
         assert!(
             ui.layout().main_dir().is_vertical(),
             "Horizontal collapsing is unimplemented"
@@ -484,6 +615,7 @@ impl CollapsingHeader {
             selectable,
             selected,
             show_background,
+            animation_time,
         } = self;
 
         // TODO(emilk): horizontal layout, with icon and text as labels. Insert background behind using Frame.
@@ -499,7 +631,7 @@ impl CollapsingHeader {
         let text_max_x = text_pos.x + galley.size().x;
 
         let mut desired_width = text_max_x + button_padding.x - available.left();
-        if ui.visuals().collapsing_header_frame {
+        if ui.visuals().collapsing_header_frame || add_trailing.is_some() {
             desired_width = desired_width.max(available.width()); // fill full width
         }
 
@@ -514,6 +646,9 @@ impl CollapsingHeader {
         );
 
         let mut state = CollapsingState::load_with_default_open(ui.ctx(), id, default_open);
+        if let Some(animation_time) = animation_time {
+            state = state.animation_time(animation_time);
+        }
         if let Some(open) = open {
             if open != state.is_open() {
                 state.toggle(ui);
@@ -529,6 +664,26 @@ impl CollapsingHeader {
 
         let openness = state.openness(ui.ctx());
 
+        // Added after the header, so they are on top of it and get the clicks:
+        let mut text_clip_rect = ui.clip_rect();
+        let trailing_returned = add_trailing.map(|add_trailing| {
+            let trailing_rect = rect.shrink2(vec2(button_padding.x, 0.0));
+            let mut trailing_ui = ui.child_ui_with_id_source(
+                trailing_rect,
+                Layout::right_to_left(Align::Center),
+                (id, "trailing"),
+            );
+            let ret = add_trailing(&mut trailing_ui);
+            let trailing_left = trailing_ui.min_rect().left();
+            if trailing_left < trailing_rect.right() {
+                text_clip_rect.max.x = text_clip_rect
+                    .max
+                    .x
+                    .min(trailing_left - ui.spacing().item_spacing.x);
+            }
+            ret
+        });
+
         if ui.is_rect_visible(rect) {
             let visuals = ui.style().interact_selectable(&header_response, selected);
 
@@ -563,11 +718,16 @@ impl CollapsingHeader {
                 }
             }
 
-            ui.painter().galley(text_pos, galley, visuals.text_color());
+            ui.painter().with_clip_rect(text_clip_rect).galley(
+                text_pos,
+                galley,
+                visuals.text_color(),
+            );
         }
 
         Prepared {
             header_response,
+            trailing_returned,
             state,
             openness,
         }
@@ -579,7 +739,47 @@ impl CollapsingHeader {
         ui: &mut Ui,
         add_body: impl FnOnce(&mut Ui) -> R,
     ) -> CollapsingResponse<R> {
-        self.show_dyn(ui, Box::new(add_body), true)
+        self.show_dyn(ui, None::<fn(&mut Ui)>, Box::new(add_body), true)
+            .0
+    }
+
+    /// Like [`Self::show`], but with widgets at the right end of the header, e.g. buttons or a badge.
+    ///
+    /// The widgets are laid out right-to-left, and the header fills the available width.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut items = vec!["Apple", "Banana"];
+    /// let (_, clear) = egui::CollapsingHeader::new("Items").show_with_trailing(
+    ///     ui,
+    ///     |ui| {
+    ///         let clear = ui.small_button("🗑").on_hover_text("Remove all").clicked();
+    ///         ui.weak(items.len().to_string());
+    ///         clear
+    ///     },
+    ///     |ui| {
+    ///         for item in &items {
+    ///             ui.label(*item);
+    ///         }
+    ///     },
+    /// );
+    /// if clear {
+    ///     items.clear();
+    /// }
+    /// # });
+    /// ```
+    pub fn show_with_trailing<T, R>(
+        self,
+        ui: &mut Ui,
+        add_trailing: impl FnOnce(&mut Ui) -> T,
+        add_body: impl FnOnce(&mut Ui) -> R,
+    ) -> (CollapsingResponse<R>, T) {
+        let (response, trailing_returned) =
+            self.show_dyn(ui, Some(add_trailing), Box::new(add_body), true);
+        (
+            response,
+            trailing_returned.expect("the trailing widgets are always shown"),
+        )
     }
 
     #[inline]
@@ -588,15 +788,17 @@ impl CollapsingHeader {
         ui: &mut Ui,
         add_body: impl FnOnce(&mut Ui) -> R,
     ) -> CollapsingResponse<R> {
-        self.show_dyn(ui, Box::new(add_body), false)
+        self.show_dyn(ui, None::<fn(&mut Ui)>, Box::new(add_body), false)
+            .0
     }
 
-    fn show_dyn<'c, R>(
+    fn show_dyn<'c, T, R>(
         self,
         ui: &mut Ui,
+        add_trailing: Option<impl FnOnce(&mut Ui) -> T>,
         add_body: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
         indented: bool,
-    ) -> CollapsingResponse<R> {
+    ) -> (CollapsingResponse<R>, Option<T>) {
         // Make sure body is bellow header,
         // and make sure it is one unit (necessary for putting a [`CollapsingHeader`] in a grid).
         ui.vertical(|ui| {
@@ -604,9 +806,10 @@ impl CollapsingHeader {
 
             let Prepared {
                 header_response,
+                trailing_returned,
                 mut state,
                 openness,
-            } = self.begin(ui); // show the header
+            } = self.begin(ui, add_trailing); // show the header
 
             let ret_response = if indented {
                 state.show_body_indented(&header_response, ui, add_body)
@@ -614,7 +817,7 @@ impl CollapsingHeader {
                 state.show_body_unindented(ui, add_body)
             };
 
-            if let Some(ret_response) = ret_response {
+            let response = if let Some(ret_response) = ret_response {
                 CollapsingResponse {
                     header_response,
                     body_response: Some(ret_response.response),
@@ -628,7 +831,8 @@ impl CollapsingHeader {
                     body_returned: None,
                     openness,
                 }
-            }
+            };
+            (response, trailing_returned)
         })
         .inner
     }
@@ -660,3 +864,38 @@ impl<R> CollapsingResponse<R> {
         self.openness >= 1.0
     }
 }
+
+#[test]
+fn open_all_opens_nested() {
+    let ctx = Context::default();
+    let scope_id = Id::new("scope");
+    let run = |request: Option<bool>| {
+        let mut open = (false, false);
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                match request {
+                    Some(true) => CollapsingState::open_all(ui.ctx(), scope_id),
+                    Some(false) => CollapsingState::close_all(ui.ctx(), scope_id),
+                    None => {}
+                }
+                CollapsingState::scope(ui, scope_id, |ui| {
+                    let outer = CollapsingHeader::new("outer")
+                        .animation_time(0.0)
+                        .show(ui, |ui| {
+                            CollapsingHeader::new("inner")
+                                .animation_time(0.0)
+                                .show(ui, |_| {})
+                                .fully_open()
+                        });
+                    open = (outer.fully_open(), outer.body_returned.unwrap_or(false));
+                });
+            });
+        });
+        open
+    };
+
+    assert_eq!(run(None), (false, false));
+    assert_eq!(run(Some(true)), (true, true));
+    assert_eq!(run(None), (true, true));
+    assert_eq!(run(Some(false)), (false, false));
+}
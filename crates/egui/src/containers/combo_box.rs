@@ -12,6 +12,76 @@ pub enum AboveOrBelow {
 /// A function that paints the [`ComboBox`] icon
 pub type IconPainter = Box<dyn FnOnce(&Ui, Rect, &WidgetVisuals, bool, AboveOrBelow)>;
 
+/// Typed characters this many seconds apart start a new typeahead search.
+const TYPEAHEAD_TIMEOUT: f64 = 1.0;
+
+/// An option of [`ComboBox::show_options`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComboBoxOption {
+    /// Shown in the list, and used for filtering and typeahead.
+    pub text: String,
+
+    /// Consecutive options with the same group are shown under a header with the group name.
+    pub group: Option<String>,
+
+    /// Disabled options are shown, but can't be selected.
+    pub enabled: bool,
+}
+
+impl ComboBoxOption {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            group: None,
+            enabled: true,
+        }
+    }
+
+    /// Show the option under a header with this name.
+    #[inline]
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Can the option be selected? Default: true.
+    #[inline]
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+impl From<&str> for ComboBoxOption {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for ComboBoxOption {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// The state of an open [`ComboBox::show_options`] list.
+#[derive(Clone, Debug, Default)]
+struct OptionsState {
+    is_open: bool,
+    filter: String,
+    typeahead: String,
+    last_typed_time: f64,
+
+    /// Scroll to this option, and focus it if the bool is set.
+    scroll_to: Option<(usize, bool)>,
+}
+
+/// A row of the [`ComboBox::show_options`] list.
+enum OptionsRow<'a> {
+    Header(&'a str),
+    Option(usize),
+}
+
 /// A drop-down selection menu with a descriptive label.
 ///
 /// ```
@@ -37,6 +107,8 @@ pub struct ComboBox {
     width: Option<f32>,
     icon: Option<IconPainter>,
     wrap_enabled: bool,
+    searchable: bool,
+    close_on_click_inside: bool,
 }
 
 impl ComboBox {
@@ -49,6 +121,8 @@ impl ComboBox {
             width: None,
             icon: None,
             wrap_enabled: false,
+            searchable: false,
+            close_on_click_inside: true,
         }
     }
 
@@ -62,6 +136,8 @@ impl ComboBox {
             width: None,
             icon: None,
             wrap_enabled: false,
+            searchable: false,
+            close_on_click_inside: true,
         }
     }
 
@@ -74,6 +150,8 @@ impl ComboBox {
             width: None,
             icon: None,
             wrap_enabled: false,
+            searchable: false,
+            close_on_click_inside: true,
         }
     }
 
@@ -137,6 +215,15 @@ impl ComboBox {
         self
     }
 
+    /// Show a text field for filtering the options of [`Self::show_options`]. Default is OFF.
+    ///
+    /// This has no effect on [`Self::show_ui`] and [`Self::show_index`].
+    #[inline]
+    pub fn searchable(mut self, searchable: bool) -> Self {
+        self.searchable = searchable;
+        self
+    }
+
     /// Show the combo box, with the given ui code for the menu contents.
     ///
     /// Returns `InnerResponse { inner: None }` if the combo box is closed.
@@ -160,6 +247,8 @@ impl ComboBox {
             width,
             icon,
             wrap_enabled,
+            searchable: _,
+            close_on_click_inside,
         } = self;
 
         let button_id = ui.make_persistent_id(id_source);
@@ -172,6 +261,7 @@ impl ComboBox {
                 menu_contents,
                 icon,
                 wrap_enabled,
+                close_on_click_inside,
                 width,
             );
             if let Some(label) = label {
@@ -232,8 +322,214 @@ impl ComboBox {
         }
         response
     }
+
+    /// Show a list of options with the given selected index.
+    ///
+    /// Unlike [`Self::show_index`], this supports option groups, disabled options,
+    /// a filter field (see [`Self::searchable`]) and typing the start of an option to jump to it.
+    /// Only the visible options are shown, so this works well with thousands of options.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::ComboBoxOption;
+    ///
+    /// let options = [
+    ///     ComboBoxOption::new("Apple").group("Fruit"),
+    ///     ComboBoxOption::new("Banana").group("Fruit"),
+    ///     ComboBoxOption::new("Durian").group("Fruit").enabled(false),
+    ///     ComboBoxOption::new("Carrot").group("Vegetables"),
+    /// ];
+    /// let mut selected = 0;
+    /// egui::ComboBox::from_label("Food")
+    ///     .searchable(true)
+    ///     .show_options(ui, &mut selected, &options);
+    /// # });
+    /// ```
+    pub fn show_options(
+        mut self,
+        ui: &mut Ui,
+        selected: &mut usize,
+        options: &[ComboBoxOption],
+    ) -> Response {
+        if self.selected_text.is_empty() {
+            if let Some(option) = options.get(*selected) {
+                self.selected_text = option.text.clone().into();
+            }
+        }
+        // We close the popup ourselves when an option is selected.
+        self.close_on_click_inside = false;
+        let searchable = self.searchable;
+        let popup_id = ui.make_persistent_id(self.id_source).with("popup");
+        let mut state: OptionsState = ui.data(|d| d.get_temp(popup_id)).unwrap_or_default();
+
+        let mut changed = false;
+        let mut response = self
+            .show_ui(ui, |ui| {
+                let just_opened = !state.is_open;
+                if just_opened {
+                    state.is_open = true;
+                    state.filter.clear();
+                    state.scroll_to = Some((*selected, false));
+                }
+
+                if searchable {
+                    let filter_response = ui.add(
+                        TextEdit::singleline(&mut state.filter)
+                            .hint_text("Search…")
+                            .desired_width(f32::INFINITY),
+                    );
+                    if just_opened {
+                        filter_response.request_focus();
+                    }
+                    if filter_response.changed() {
+                        state.scroll_to = Some((0, false));
+                    }
+                    if filter_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                        let filter = state.filter.to_lowercase();
+                        if let Some(i) = (0..options.len()).find(|&i| {
+                            options[i].enabled && options[i].text.to_lowercase().contains(&filter)
+                        }) {
+                            *selected = i;
+                            changed = true;
+                            ui.memory_mut(|m| m.close_popup());
+                        }
+                    }
+                } else if let Some(i) = typeahead(ui, &mut state, options, *selected) {
+                    state.scroll_to = Some((i, true));
+                }
+
+                let rows = options_rows(options, &state.filter);
+                let row_height = ui.spacing().interact_size.y;
+                // The filter field stays put above the list:
+                let max_height = ui.spacing().combo_height
+                    - ui.min_rect().height()
+                    - ui.spacing().item_spacing.y;
+                let mut scroll_area =
+                    ScrollArea::vertical().max_height(max_height.at_least(row_height));
+                let scroll_to = state.scroll_to.take();
+                if let Some((option, _)) = scroll_to {
+                    let row = rows
+                        .iter()
+                        .position(|row| matches!(row, OptionsRow::Option(i) if *i == option))
+                        .unwrap_or(0);
+                    let offset = row as f32 * (row_height + ui.spacing().item_spacing.y);
+                    scroll_area = scroll_area.vertical_scroll_offset(offset);
+                }
+                scroll_area.show_rows(ui, row_height, rows.len(), |ui, row_range| {
+                    for row in &rows[row_range] {
+                        match *row {
+                            OptionsRow::Header(group) => {
+                                ui.allocate_ui_with_layout(
+                                    vec2(ui.available_width(), row_height),
+                                    Layout::left_to_right(Align::Center),
+                                    |ui| ui.label(RichText::new(group).strong()),
+                                );
+                            }
+                            OptionsRow::Option(i) => {
+                                let option = &options[i];
+                                let option_response = ui.add_enabled(
+                                    option.enabled,
+                                    SelectableLabel::new(i == *selected, option.text.as_str()),
+                                );
+                                if scroll_to == Some((i, true)) {
+                                    option_response.request_focus();
+                                }
+                                if option_response.clicked() {
+                                    *selected = i;
+                                    changed = true;
+                                    ui.memory_mut(|m| m.close_popup());
+                                }
+                            }
+                        }
+                    }
+                });
+            })
+            .response;
+
+        state.is_open = ui.memory(|m| m.is_popup_open(popup_id));
+        if !state.is_open && response.has_focus() {
+            // Typing while the closed combo box is focused selects the option directly:
+            if let Some(i) = typeahead(ui, &mut state, options, *selected) {
+                *selected = i;
+                changed = true;
+            }
+        }
+        ui.data_mut(|d| d.insert_temp(popup_id, state));
+
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
 }
 
+/// The rows of the options that match the filter, with a header before each group.
+fn options_rows<'a>(options: &'a [ComboBoxOption], filter: &str) -> Vec<OptionsRow<'a>> {
+    let filter = filter.to_lowercase();
+    let mut rows = vec![];
+    let mut current_group = None;
+    for (i, option) in options.iter().enumerate() {
+        if !filter.is_empty() && !option.text.to_lowercase().contains(&filter) {
+            continue;
+        }
+        let group = option.group.as_deref();
+        if group != current_group {
+            if let Some(group) = group {
+                rows.push(OptionsRow::Header(group));
+            }
+            current_group = group;
+        }
+        rows.push(OptionsRow::Option(i));
+    }
+    rows
+}
+
+/// Find the next enabled option starting with what the user has typed.
+///
+/// Typing the same letter repeatedly cycles through the options starting with it.
+fn typeahead(
+    ui: &Ui,
+    state: &mut OptionsState,
+    options: &[ComboBoxOption],
+    current: usize,
+) -> Option<usize> {
+    let typed: String = ui.input(|i| {
+        i.events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    });
+    if typed.is_empty() || options.is_empty() {
+        return None;
+    }
+
+    let now = ui.input(|i| i.time);
+    if TYPEAHEAD_TIMEOUT < now - state.last_typed_time {
+        state.typeahead.clear();
+    }
+    state.last_typed_time = now;
+    state.typeahead.push_str(&typed.to_lowercase());
+
+    let is_repeated_letter = {
+        let mut chars = state.typeahead.chars();
+        let first = chars.next();
+        chars.all(|c| Some(c) == first)
+    };
+    let (prefix, start) = if is_repeated_letter {
+        let first_len = state.typeahead.chars().next().map_or(0, char::len_utf8);
+        (&state.typeahead[..first_len], current + 1)
+    } else {
+        (state.typeahead.as_str(), current)
+    };
+    (0..options.len())
+        .map(|i| (start + i) % options.len())
+        .find(|&i| options[i].enabled && options[i].text.to_lowercase().starts_with(prefix))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn combo_box_dyn<'c, R>(
     ui: &mut Ui,
     button_id: Id,
@@ -241,6 +537,7 @@ fn combo_box_dyn<'c, R>(
     menu_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
     icon: Option<IconPainter>,
     wrap_enabled: bool,
+    close_on_click_inside: bool,
     width: Option<f32>,
 ) -> InnerResponse<Option<R>> {
     let popup_id = button_id.with("popup");
@@ -335,11 +632,12 @@ fn combo_box_dyn<'c, R>(
     if button_response.clicked() {
         ui.memory_mut(|mem| mem.toggle_popup(popup_id));
     }
-    let inner = crate::popup::popup_above_or_below_widget(
+    let inner = crate::popup::popup_above_or_below_widget_impl(
         ui,
         popup_id,
         &button_response,
         above_or_below,
+        close_on_click_inside,
         |ui| {
             ScrollArea::vertical()
                 .max_height(ui.spacing().combo_height)
@@ -431,3 +729,85 @@ fn paint_default_icon(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::run_in_panel;
+
+    fn options() -> Vec<ComboBoxOption> {
+        vec![
+            ComboBoxOption::new("Apple").group("Fruit"),
+            ComboBoxOption::new("Banana").group("Fruit"),
+            ComboBoxOption::new("Durian").group("Fruit").enabled(false),
+            ComboBoxOption::new("Date").group("Fruit"),
+            ComboBoxOption::new("Carrot").group("Vegetables"),
+            ComboBoxOption::new("Chard").group("Vegetables"),
+        ]
+    }
+
+    fn rows(options: &[ComboBoxOption], filter: &str) -> Vec<String> {
+        options_rows(options, filter)
+            .into_iter()
+            .map(|row| match row {
+                OptionsRow::Header(group) => format!("# {group}"),
+                OptionsRow::Option(i) => options[i].text.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_options_rows_filter() {
+        let options = options();
+        assert_eq!(
+            rows(&options, ""),
+            [
+                "# Fruit",
+                "Apple",
+                "Banana",
+                "Durian",
+                "Date",
+                "# Vegetables",
+                "Carrot",
+                "Chard"
+            ]
+        );
+
+        // Case-insensitive substrings, and only the headers of groups with matches:
+        assert_eq!(rows(&options, "AN"), ["# Fruit", "Banana", "Durian"]);
+        assert_eq!(rows(&options, "ar"), ["# Vegetables", "Carrot", "Chard"]);
+        assert_eq!(rows(&options, "xyz"), Vec::<String>::new());
+    }
+
+    /// Type `text` at `time` into a focused, closed combo box.
+    fn type_text(ctx: &Context, selected: &mut usize, text: &str, time: f64) {
+        let options = options();
+        let input = RawInputBuilder::new().time(time).text(text);
+        run_in_panel(ctx, input, |ui| {
+            ComboBox::from_id_source("food")
+                .show_options(ui, selected, &options)
+                .request_focus();
+        });
+    }
+
+    #[test]
+    fn test_typeahead_skips_disabled_options() {
+        let ctx = Context::default();
+        let mut selected = 0;
+        type_text(&ctx, &mut selected, "", 0.0);
+
+        // "Durian" is disabled:
+        type_text(&ctx, &mut selected, "d", 1.0);
+        assert_eq!(options()[selected].text, "Date");
+
+        // Typing the same letter again cycles, but only through the enabled options:
+        type_text(&ctx, &mut selected, "d", 1.5);
+        assert_eq!(options()[selected].text, "Date");
+
+        // After a pause a new search starts, and longer prefixes narrow it down:
+        type_text(&ctx, &mut selected, "c", 3.0);
+        assert_eq!(options()[selected].text, "Carrot");
+        type_text(&ctx, &mut selected, "h", 3.5);
+        assert_eq!(options()[selected].text, "Chard");
+    }
+}
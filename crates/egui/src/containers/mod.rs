@@ -8,8 +8,12 @@ mod combo_box;
 pub(crate) mod frame;
 pub mod panel;
 pub mod popup;
+pub(crate) mod reorderable_list;
 pub(crate) mod resize;
 pub mod scroll_area;
+pub mod splitter;
+pub mod steps;
+pub mod viewport_frame;
 pub(crate) mod window;
 
 pub use {
@@ -19,7 +23,11 @@ pub use {
     frame::Frame,
     panel::{CentralPanel, SidePanel, TopBottomPanel},
     popup::*,
+    reorderable_list::ReorderableListResponse,
     resize::Resize,
     scroll_area::ScrollArea,
-    window::Window,
+    splitter::{Splitter, SplitterState},
+    steps::{Steps, StepsResponse, StepsState},
+    viewport_frame::ViewportFrame,
+    window::{Window, WindowMode},
 };
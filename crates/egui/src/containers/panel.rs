@@ -39,6 +39,49 @@ impl PanelState {
     }
 }
 
+fn is_collapsed(ctx: &Context, panel_id: Id) -> bool {
+    ctx.data_mut(|d| d.get_persisted(panel_id.with("__collapsed")))
+        .unwrap_or(false)
+}
+
+fn set_collapsed(ctx: &Context, panel_id: Id, collapsed: bool) {
+    ctx.data_mut(|d| d.insert_persisted(panel_id.with("__collapsed"), collapsed));
+}
+
+/// The button for collapsing or expanding a panel.
+///
+/// It is put in the given corner of the [`Ui`], on top of its contents,
+/// with an arrow pointing in the direction the panel will move.
+fn collapse_button(ui: &Ui, panel_id: Id, corner: Align2, arrow: Vec2) {
+    let size = Vec2::splat(ui.spacing().interact_size.y);
+    let rect = corner.align_size_within_rect(size, ui.max_rect());
+    let response = ui.interact(rect, panel_id.with("__collapse_button"), Sense::click());
+    let visuals = ui.style().interact(&response);
+
+    if response.hovered() || response.has_focus() {
+        ui.painter().rect(
+            rect.expand(visuals.expansion),
+            visuals.rounding,
+            visuals.weak_bg_fill,
+            visuals.bg_stroke,
+        );
+    }
+
+    let center = rect.center();
+    let tip = center + 0.25 * size.x * arrow;
+    let back = center - 0.25 * size.x * arrow;
+    let side = 0.3 * size.x * arrow.rot90();
+    ui.painter().add(Shape::convex_polygon(
+        vec![tip, back + side, back - side],
+        visuals.fg_stroke.color,
+        Stroke::NONE,
+    ));
+
+    if response.clicked() {
+        set_collapsed(ui.ctx(), panel_id, !is_collapsed(ui.ctx(), panel_id));
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// [`Left`](Side::Left) or [`Right`](Side::Right)
@@ -69,6 +112,22 @@ impl Side {
             Self::Right => rect.right(),
         }
     }
+
+    /// The top corner at this side.
+    fn top_corner(self) -> Align2 {
+        match self {
+            Self::Left => Align2::LEFT_TOP,
+            Self::Right => Align2::RIGHT_TOP,
+        }
+    }
+
+    /// Points towards this side.
+    fn direction(self) -> Vec2 {
+        match self {
+            Self::Left => -Vec2::X,
+            Self::Right => Vec2::X,
+        }
+    }
 }
 
 /// A panel that covers the entire left or right side of a [`Ui`] or screen.
@@ -98,6 +157,7 @@ pub struct SidePanel {
     show_separator_line: bool,
     default_width: f32,
     width_range: Rangef,
+    overlay: bool,
 }
 
 impl SidePanel {
@@ -121,11 +181,14 @@ impl SidePanel {
             show_separator_line: true,
             default_width: 200.0,
             width_range: Rangef::new(96.0, f32::INFINITY),
+            overlay: false,
         }
     }
 
     /// Can panel be resized by dragging the edge of it?
     ///
+    /// Double-clicking the edge resets the width to [`Self::default_width`].
+    ///
     /// Default is `true`.
     ///
     /// If you want your panel to be resizable you also need a widget in it that
@@ -192,6 +255,20 @@ impl SidePanel {
         self
     }
 
+    /// Float the panel over the content behind it, instead of taking up space.
+    ///
+    /// The [`CentralPanel`] (and any panels added after this one) then keep their full size,
+    /// and the panel is shown above them, like a drawer.
+    ///
+    /// This only affects top-level panels, i.e. [`Self::show`] and friends.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn overlay(mut self, overlay: bool) -> Self {
+        self.overlay = overlay;
+        self
+    }
+
     /// Change the background color, margins, etc.
     #[inline]
     pub fn frame(mut self, frame: Frame) -> Self {
@@ -224,6 +301,7 @@ impl SidePanel {
             show_separator_line,
             default_width,
             width_range,
+            overlay: _,
         } = self;
 
         let available_rect = ui.available_rect_before_wrap();
@@ -266,6 +344,14 @@ impl SidePanel {
                     side.set_rect_width(&mut panel_rect, width);
                 }
 
+                if mouse_over_resize_line
+                    && ui.input(|i| i.pointer.button_double_clicked(PointerButton::Primary))
+                {
+                    let width =
+                        clamp_to_range(default_width, width_range).at_most(available_rect.width());
+                    side.set_rect_width(&mut panel_rect, width);
+                }
+
                 let dragging_something_else =
                     ui.input(|i| i.pointer.any_down() || i.pointer.any_pressed());
                 resize_hover = mouse_over_resize_line && !dragging_something_else;
@@ -343,6 +429,23 @@ impl SidePanel {
         let layer_id = LayerId::background();
         let side = self.side;
         let available_rect = ctx.available_rect();
+
+        if self.overlay {
+            let pivot = side.top_corner();
+            return Area::new(self.id.with("__overlay"))
+                .order(Order::Middle)
+                .fixed_pos(pivot.pos_in_rect(&available_rect))
+                .pivot(pivot)
+                .constrain_to(available_rect)
+                .show(ctx, |ui| {
+                    let mut panel_ui = ui.child_ui(available_rect, Layout::top_down(Align::Min));
+                    let inner_response = self.show_inside_dyn(&mut panel_ui, add_contents);
+                    ui.expand_to_include_rect(inner_response.response.rect);
+                    inner_response
+                })
+                .inner;
+        }
+
         let clip_rect = ctx.screen_rect();
         let mut panel_ui = Ui::new(ctx.clone(), layer_id, self.id, available_rect, clip_rect);
 
@@ -428,6 +531,113 @@ impl SidePanel {
         }
     }
 
+    /// Has the user collapsed the panel with the given id, using the button of [`Self::show_collapsible`]?
+    pub fn is_collapsed(ctx: &Context, id: impl Into<Id>) -> bool {
+        is_collapsed(ctx, id.into())
+    }
+
+    /// Collapse or expand a panel shown with [`Self::show_collapsible`].
+    pub fn set_collapsed(ctx: &Context, id: impl Into<Id>, collapsed: bool) {
+        set_collapsed(ctx, id.into(), collapsed);
+    }
+
+    fn collapsed_width(&self, style: &Style) -> f32 {
+        let frame = self.frame.unwrap_or_else(|| Frame::side_top_panel(style));
+        frame.total_margin().sum().x + style.spacing.interact_size.y
+    }
+
+    /// Show the panel with a button in its top corner that collapses it into a narrow strip,
+    /// which in turn has a button to expand it again.
+    ///
+    /// Collapsing and expanding is animated.
+    /// The inner value is `None` while the panel is collapsed or animating.
+    ///
+    /// See also [`Self::is_collapsed`] and [`Self::set_collapsed`].
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// egui::SidePanel::left("tools").show_collapsible(ctx, |ui| {
+    ///    ui.label("Hello World!");
+    /// });
+    /// # });
+    /// ```
+    pub fn show_collapsible<R>(
+        self,
+        ctx: &Context,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<Option<R>> {
+        let id = self.id;
+        let side = self.side;
+        let is_expanded = !is_collapsed(ctx, id);
+        let how_expanded = ctx.animate_bool(id.with("animation"), is_expanded);
+
+        if how_expanded < 1.0 {
+            let collapsed_width = self.collapsed_width(&ctx.style());
+            let expanded_width =
+                PanelState::load(ctx, id).map_or(self.default_width, |state| state.rect.width());
+            let width = lerp(collapsed_width..=expanded_width, how_expanded);
+            let response = Self {
+                id: id.with("collapsed_panel"),
+                ..self
+            }
+            .resizable(false)
+            .exact_width(width)
+            .show(ctx, |ui| {
+                if how_expanded == 0.0 {
+                    collapse_button(ui, id, Align2::CENTER_TOP, -side.direction());
+                }
+            })
+            .response;
+            InnerResponse::new(None, response)
+        } else {
+            let InnerResponse { inner, response } = self.show(ctx, |ui| {
+                let inner = add_contents(ui);
+                collapse_button(ui, id, side.opposite().top_corner(), side.direction());
+                inner
+            });
+            InnerResponse::new(Some(inner), response)
+        }
+    }
+
+    /// Like [`Self::show_collapsible`], but inside a [`Ui`].
+    pub fn show_collapsible_inside<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<Option<R>> {
+        let id = self.id;
+        let side = self.side;
+        let is_expanded = !is_collapsed(ui.ctx(), id);
+        let how_expanded = ui.ctx().animate_bool(id.with("animation"), is_expanded);
+
+        if how_expanded < 1.0 {
+            let collapsed_width = self.collapsed_width(ui.style());
+            let expanded_width = PanelState::load(ui.ctx(), id)
+                .map_or(self.default_width, |state| state.rect.width());
+            let width = lerp(collapsed_width..=expanded_width, how_expanded);
+            let response = Self {
+                id: id.with("collapsed_panel"),
+                ..self
+            }
+            .resizable(false)
+            .exact_width(width)
+            .show_inside(ui, |ui| {
+                if how_expanded == 0.0 {
+                    collapse_button(ui, id, Align2::CENTER_TOP, -side.direction());
+                }
+            })
+            .response;
+            InnerResponse::new(None, response)
+        } else {
+            let InnerResponse { inner, response } = self.show_inside(ui, |ui| {
+                let inner = add_contents(ui);
+                collapse_button(ui, id, side.opposite().top_corner(), side.direction());
+                inner
+            });
+            InnerResponse::new(Some(inner), response)
+        }
+    }
+
     /// Show either a collapsed or a expanded panel, with a nice animation between.
     pub fn show_animated_between<R>(
         ctx: &Context,
@@ -524,6 +734,30 @@ impl TopBottomSide {
             Self::Bottom => rect.bottom(),
         }
     }
+
+    /// The left corner at this side.
+    fn left_corner(self) -> Align2 {
+        match self {
+            Self::Top => Align2::LEFT_TOP,
+            Self::Bottom => Align2::LEFT_BOTTOM,
+        }
+    }
+
+    /// The right corner at this side.
+    fn right_corner(self) -> Align2 {
+        match self {
+            Self::Top => Align2::RIGHT_TOP,
+            Self::Bottom => Align2::RIGHT_BOTTOM,
+        }
+    }
+
+    /// Points towards this side.
+    fn direction(self) -> Vec2 {
+        match self {
+            Self::Top => -Vec2::Y,
+            Self::Bottom => Vec2::Y,
+        }
+    }
 }
 
 /// A panel that covers the entire top or bottom of a [`Ui`] or screen.
@@ -553,6 +787,7 @@ pub struct TopBottomPanel {
     show_separator_line: bool,
     default_height: Option<f32>,
     height_range: Rangef,
+    overlay: bool,
 }
 
 impl TopBottomPanel {
@@ -576,11 +811,15 @@ impl TopBottomPanel {
             show_separator_line: true,
             default_height: None,
             height_range: Rangef::new(20.0, f32::INFINITY),
+            overlay: false,
         }
     }
 
     /// Can panel be resized by dragging the edge of it?
     ///
+    /// Double-clicking the edge resets the height to [`Self::default_height`],
+    /// or if there is none, to the height the panel got from its contents when first shown.
+    ///
     /// Default is `false`.
     ///
     /// If you want your panel to be resizable you also need a widget in it that
@@ -650,6 +889,20 @@ impl TopBottomPanel {
         self
     }
 
+    /// Float the panel over the content behind it, instead of taking up space.
+    ///
+    /// The [`CentralPanel`] (and any panels added after this one) then keep their full size,
+    /// and the panel is shown above them, like a drawer.
+    ///
+    /// This only affects top-level panels, i.e. [`Self::show`] and friends.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn overlay(mut self, overlay: bool) -> Self {
+        self.overlay = overlay;
+        self
+    }
+
     /// Change the background color, margins, etc.
     #[inline]
     pub fn frame(mut self, frame: Frame) -> Self {
@@ -682,12 +935,15 @@ impl TopBottomPanel {
             show_separator_line,
             default_height,
             height_range,
+            overlay: _,
         } = self;
 
         let available_rect = ui.available_rect_before_wrap();
         let mut panel_rect = available_rect;
+        let state = PanelState::load(ui.ctx(), id);
+        let auto_height_id = id.with("__auto_height");
         {
-            let mut height = if let Some(state) = PanelState::load(ui.ctx(), id) {
+            let mut height = if let Some(state) = state {
                 state.rect.height()
             } else {
                 default_height.unwrap_or_else(|| ui.style().spacing.interact_size.y)
@@ -728,6 +984,17 @@ impl TopBottomPanel {
                     side.set_rect_height(&mut panel_rect, height);
                 }
 
+                if mouse_over_resize_line
+                    && ui.input(|i| i.pointer.button_double_clicked(PointerButton::Primary))
+                {
+                    let height = default_height
+                        .or_else(|| ui.ctx().data_mut(|d| d.get_persisted(auto_height_id)))
+                        .unwrap_or_else(|| ui.spacing().interact_size.y);
+                    let height =
+                        clamp_to_range(height, height_range).at_most(available_rect.height());
+                    side.set_rect_height(&mut panel_rect, height);
+                }
+
                 let dragging_something_else =
                     ui.input(|i| i.pointer.any_down() || i.pointer.any_pressed());
                 resize_hover = mouse_over_resize_line && !dragging_something_else;
@@ -763,6 +1030,11 @@ impl TopBottomPanel {
         }
         ui.expand_to_include_rect(rect);
 
+        if resizable && state.is_none() && default_height.is_none() {
+            // Remember the height the contents gave the panel, to reset to on double-click:
+            ui.ctx()
+                .data_mut(|d| d.insert_persisted(auto_height_id, rect.height()));
+        }
         PanelState { rect }.store(ui.ctx(), id);
 
         {
@@ -806,6 +1078,22 @@ impl TopBottomPanel {
         let available_rect = ctx.available_rect();
         let side = self.side;
 
+        if self.overlay {
+            let pivot = side.left_corner();
+            return Area::new(self.id.with("__overlay"))
+                .order(Order::Middle)
+                .fixed_pos(pivot.pos_in_rect(&available_rect))
+                .pivot(pivot)
+                .constrain_to(available_rect)
+                .show(ctx, |ui| {
+                    let mut panel_ui = ui.child_ui(available_rect, Layout::top_down(Align::Min));
+                    let inner_response = self.show_inside_dyn(&mut panel_ui, add_contents);
+                    ui.expand_to_include_rect(inner_response.response.rect);
+                    inner_response
+                })
+                .inner;
+        }
+
         let clip_rect = ctx.screen_rect();
         let mut panel_ui = Ui::new(ctx.clone(), layer_id, self.id, available_rect, clip_rect);
 
@@ -900,6 +1188,110 @@ impl TopBottomPanel {
         }
     }
 
+    /// Has the user collapsed the panel with the given id, using the button of [`Self::show_collapsible`]?
+    pub fn is_collapsed(ctx: &Context, id: impl Into<Id>) -> bool {
+        is_collapsed(ctx, id.into())
+    }
+
+    /// Collapse or expand a panel shown with [`Self::show_collapsible`].
+    pub fn set_collapsed(ctx: &Context, id: impl Into<Id>, collapsed: bool) {
+        set_collapsed(ctx, id.into(), collapsed);
+    }
+
+    fn collapsed_height(&self, style: &Style) -> f32 {
+        let frame = self.frame.unwrap_or_else(|| Frame::side_top_panel(style));
+        frame.total_margin().sum().y + style.spacing.interact_size.y
+    }
+
+    fn expanded_height(&self, ctx: &Context) -> f32 {
+        PanelState::load(ctx, self.id)
+            .map(|state| state.rect.height())
+            .or(self.default_height)
+            .unwrap_or_else(|| ctx.style().spacing.interact_size.y)
+    }
+
+    /// Show the panel with a button in its corner that collapses it into a thin strip,
+    /// which in turn has a button to expand it again.
+    ///
+    /// Collapsing and expanding is animated.
+    /// The inner value is `None` while the panel is collapsed or animating.
+    ///
+    /// See also [`Self::is_collapsed`] and [`Self::set_collapsed`].
+    pub fn show_collapsible<R>(
+        self,
+        ctx: &Context,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<Option<R>> {
+        let id = self.id;
+        let side = self.side;
+        let is_expanded = !is_collapsed(ctx, id);
+        let how_expanded = ctx.animate_bool(id.with("animation"), is_expanded);
+
+        if how_expanded < 1.0 {
+            let collapsed_height = self.collapsed_height(&ctx.style());
+            let expanded_height = self.expanded_height(ctx);
+            let height = lerp(collapsed_height..=expanded_height, how_expanded);
+            let response = Self {
+                id: id.with("collapsed_panel"),
+                ..self
+            }
+            .resizable(false)
+            .exact_height(height)
+            .show(ctx, |ui| {
+                if how_expanded == 0.0 {
+                    collapse_button(ui, id, Align2::RIGHT_CENTER, -side.direction());
+                }
+            })
+            .response;
+            InnerResponse::new(None, response)
+        } else {
+            let InnerResponse { inner, response } = self.show(ctx, |ui| {
+                let inner = add_contents(ui);
+                collapse_button(ui, id, side.opposite().right_corner(), side.direction());
+                inner
+            });
+            InnerResponse::new(Some(inner), response)
+        }
+    }
+
+    /// Like [`Self::show_collapsible`], but inside a [`Ui`].
+    pub fn show_collapsible_inside<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<Option<R>> {
+        let id = self.id;
+        let side = self.side;
+        let is_expanded = !is_collapsed(ui.ctx(), id);
+        let how_expanded = ui.ctx().animate_bool(id.with("animation"), is_expanded);
+
+        if how_expanded < 1.0 {
+            let collapsed_height = self.collapsed_height(ui.style());
+            let expanded_height = self.expanded_height(ui.ctx());
+            let height = lerp(collapsed_height..=expanded_height, how_expanded);
+            let response = Self {
+                id: id.with("collapsed_panel"),
+                ..self
+            }
+            .resizable(false)
+            .exact_height(height)
+            .show_inside(ui, |ui| {
+                if how_expanded == 0.0 {
+                    collapse_button(ui, id, Align2::RIGHT_CENTER, -side.direction());
+                }
+            })
+            .response;
+            InnerResponse::new(None, response)
+        } else {
+            let InnerResponse { inner, response } = self.show_inside(ui, |ui| {
+                let inner = add_contents(ui);
+                collapse_button(ui, id, side.opposite().right_corner(), side.direction());
+                inner
+            });
+            InnerResponse::new(Some(inner), response)
+        }
+    }
+
     /// Show either a collapsed or a expanded panel, with a nice animation between.
     pub fn show_animated_between<R>(
         ctx: &Context,
@@ -1006,6 +1398,7 @@ impl TopBottomPanel {
 #[derive(Default)]
 pub struct CentralPanel {
     frame: Option<Frame>,
+    respect_safe_area: bool,
 }
 
 impl CentralPanel {
@@ -1015,6 +1408,19 @@ impl CentralPanel {
         self.frame = Some(frame);
         self
     }
+
+    /// Keep the contents away from display notches, rounded screen corners and system bars
+    /// (see [`crate::InputState::safe_area_insets`]).
+    ///
+    /// The background still fills the whole panel.
+    /// Only has an effect when using [`Self::show`].
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn respect_safe_area(mut self, respect_safe_area: bool) -> Self {
+        self.respect_safe_area = respect_safe_area;
+        self
+    }
 }
 
 impl CentralPanel {
@@ -1033,7 +1439,10 @@ impl CentralPanel {
         ui: &mut Ui,
         add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
     ) -> InnerResponse<R> {
-        let Self { frame } = self;
+        let Self {
+            frame,
+            respect_safe_area: _, // handled in `show_dyn`
+        } = self;
 
         let panel_rect = ui.available_rect_before_wrap();
         let mut panel_ui = ui.child_ui(panel_rect, Layout::top_down(Align::Min));
@@ -1056,7 +1465,7 @@ impl CentralPanel {
 
     /// Show the panel at the top level.
     fn show_dyn<'c, R>(
-        self,
+        mut self,
         ctx: &Context,
         add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
     ) -> InnerResponse<R> {
@@ -1064,6 +1473,23 @@ impl CentralPanel {
         let layer_id = LayerId::background();
         let id = Id::new((ctx.viewport_id(), "central_panel"));
 
+        if self.respect_safe_area {
+            let safe_rect = ctx.input(|i| i.safe_area_insets().shrink_rect(i.screen_rect()));
+
+            // Only pad the sides of the panel that reach into the unsafe area:
+            let padding = Margin {
+                left: (safe_rect.left() - available_rect.left()).at_least(0.0),
+                right: (available_rect.right() - safe_rect.right()).at_least(0.0),
+                top: (safe_rect.top() - available_rect.top()).at_least(0.0),
+                bottom: (available_rect.bottom() - safe_rect.bottom()).at_least(0.0),
+            };
+            let mut frame = self
+                .frame
+                .unwrap_or_else(|| Frame::central_panel(&ctx.style()));
+            frame.inner_margin = frame.inner_margin + padding;
+            self.frame = Some(frame);
+        }
+
         let clip_rect = ctx.screen_rect();
         let mut panel_ui = Ui::new(ctx.clone(), layer_id, id, available_rect, clip_rect);
 
@@ -1080,3 +1506,109 @@ fn clamp_to_range(x: f32, range: Rangef) -> f32 {
     let range = range.as_positive();
     x.clamp(range.min, range.max)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{click, press};
+
+    #[test]
+    fn top_bottom_panel_double_click_resets_height() {
+        let ctx = Context::default();
+        let run = |input: RawInputBuilder| {
+            let mut panel_rect = Rect::NOTHING;
+            let _ = ctx.run(input.screen_size(vec2(400.0, 400.0)).build(), |ctx| {
+                panel_rect = TopBottomPanel::top("top")
+                    .resizable(true)
+                    .show(ctx, |ui| {
+                        ui.label("One");
+                        ui.label("Two");
+                        ui.label("Three");
+                        // Fill the panel, so that it keeps the height it is resized to:
+                        ui.allocate_space(vec2(0.0, ui.available_height().at_least(0.0)));
+                    })
+                    .response
+                    .rect;
+            });
+            panel_rect
+        };
+
+        let auto_height = run(RawInputBuilder::new()).height();
+        assert!(ctx.style().spacing.interact_size.y < auto_height);
+
+        // Drag the edge down:
+        press(pos2(200.0, auto_height), |input| {
+            run(input);
+        });
+        run(RawInputBuilder::new().pointer_moved(pos2(200.0, 250.0)));
+        run(RawInputBuilder::new().pointer_button(
+            pos2(200.0, 250.0),
+            PointerButton::Primary,
+            false,
+        ));
+        let resized_rect = run(RawInputBuilder::new());
+        assert_eq!(resized_rect.height(), 250.0);
+
+        // Double-click the edge:
+        for _ in 0..2 {
+            click(pos2(200.0, 250.0), |input| {
+                run(input);
+            });
+        }
+        let reset_rect = run(RawInputBuilder::new().pointer_gone());
+        assert_eq!(reset_rect.height(), auto_height);
+    }
+
+    #[test]
+    fn overlay_panels_take_no_space() {
+        let run = |overlay: bool| {
+            let ctx = Context::default();
+            let mut rects = [Rect::NOTHING; 3];
+            for _ in 0..2 {
+                let input = RawInputBuilder::new().screen_size(vec2(400.0, 400.0));
+                let _ = ctx.run(input.build(), |ctx| {
+                    rects[0] = SidePanel::left("left")
+                        .overlay(overlay)
+                        .show(ctx, |ui| ui.label("Left"))
+                        .response
+                        .rect;
+                    rects[1] = TopBottomPanel::top("top")
+                        .overlay(overlay)
+                        .show(ctx, |ui| ui.label("Top"))
+                        .response
+                        .rect;
+                    rects[2] = CentralPanel::default()
+                        .show(ctx, |ui| ui.label("Central"))
+                        .response
+                        .rect;
+                });
+            }
+            (ctx, rects)
+        };
+
+        let (_, [left, top, central]) = run(false);
+        assert_eq!(top.left(), left.right());
+        assert_eq!(central.min, pos2(left.right(), top.bottom()));
+
+        let (ctx, [left, top, central]) = run(true);
+        assert_eq!(left.min, Pos2::ZERO);
+        assert_eq!(top.min, Pos2::ZERO);
+        assert_eq!(central.min, Pos2::ZERO);
+
+        // The panels are shown above the central panel:
+        assert_eq!(
+            ctx.layer_id_at(left.center()),
+            Some(LayerId::new(
+                Order::Middle,
+                Id::new("left").with("__overlay")
+            ))
+        );
+        assert_eq!(
+            ctx.layer_id_at(pos2(top.right() - 1.0, top.center().y)),
+            Some(LayerId::new(
+                Order::Middle,
+                Id::new("top").with("__overlay")
+            ))
+        );
+    }
+}
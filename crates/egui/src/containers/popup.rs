@@ -309,6 +309,9 @@ pub fn popup_below_widget<R>(
 
 /// Shows a popup above or below another widget.
 ///
+/// If there is no room for the popup on the requested side, it is shown on the other side.
+/// See [`Area::anchored_to`] for more ways to position a popup next to a widget.
+///
 /// Useful for drop-down menus (combo boxes) or suggestion menus under text fields.
 ///
 /// The opened popup will have the same width as the parent.
@@ -338,18 +341,38 @@ pub fn popup_above_or_below_widget<R>(
     widget_response: &Response,
     above_or_below: AboveOrBelow,
     add_contents: impl FnOnce(&mut Ui) -> R,
+) -> Option<R> {
+    let close_on_click_inside = true;
+    popup_above_or_below_widget_impl(
+        ui,
+        popup_id,
+        widget_response,
+        above_or_below,
+        close_on_click_inside,
+        add_contents,
+    )
+}
+
+/// Like [`popup_above_or_below_widget`], but optionally stays open when clicking inside the popup,
+/// e.g. for popups with text fields.
+pub(crate) fn popup_above_or_below_widget_impl<R>(
+    ui: &Ui,
+    popup_id: Id,
+    widget_response: &Response,
+    above_or_below: AboveOrBelow,
+    close_on_click_inside: bool,
+    add_contents: impl FnOnce(&mut Ui) -> R,
 ) -> Option<R> {
     if ui.memory(|mem| mem.is_popup_open(popup_id)) {
-        let (pos, pivot) = match above_or_below {
-            AboveOrBelow::Above => (widget_response.rect.left_top(), Align2::LEFT_BOTTOM),
-            AboveOrBelow::Below => (widget_response.rect.left_bottom(), Align2::LEFT_TOP),
+        let align = match above_or_below {
+            AboveOrBelow::Above => RectAlign::TOP_START,
+            AboveOrBelow::Below => RectAlign::BOTTOM_START,
         };
 
-        let inner = Area::new(popup_id)
+        let area_response = Area::new(popup_id)
             .order(Order::Foreground)
             .constrain(true)
-            .fixed_pos(pos)
-            .pivot(pivot)
+            .anchored_to(widget_response.rect, align)
             .show(ui.ctx(), |ui| {
                 // Note: we use a separate clip-rect for this area, so the popup can be outside the parent.
                 // See https://github.com/emilk/egui/issues/825
@@ -364,10 +387,19 @@ pub fn popup_above_or_below_widget<R>(
                         .inner
                     })
                     .inner
-            })
-            .inner;
-
-        if ui.input(|i| i.key_pressed(Key::Escape)) || widget_response.clicked_elsewhere() {
+            });
+        let inner = area_response.inner;
+
+        let clicked_inside = !close_on_click_inside
+            && ui.input(|i| {
+                i.pointer.any_click()
+                    && i.pointer
+                        .interact_pos()
+                        .map_or(false, |pos| area_response.response.rect.contains(pos))
+            });
+        if ui.input(|i| i.key_pressed(Key::Escape))
+            || (widget_response.clicked_elsewhere() && !clicked_inside)
+        {
             ui.memory_mut(|mem| mem.close_popup());
         }
         Some(inner)
@@ -0,0 +1,239 @@
+//! A list of items that can be reordered by dragging them, see [`Ui::reorderable_list`].
+
+use crate::*;
+
+/// How close to the edge of the visible area the pointer must be to scroll, in multiples of [`style::Spacing::interact_size`].
+const AUTO_SCROLL_MARGIN: f32 = 2.0;
+
+/// How many points per frame to scroll per point the pointer is within the scroll margin.
+const AUTO_SCROLL_SPEED: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug)]
+struct DragState {
+    /// The index of the dragged item.
+    dragged: usize,
+
+    /// Where the dragged item would be dropped, among the other items.
+    target: usize,
+
+    /// The pointer relative to the top left of the dragged item.
+    grab_offset: Vec2,
+
+    /// The size of the dragged row.
+    size: Vec2,
+
+    /// When the drag started, so each drag gets new gap animations.
+    start_frame: u64,
+}
+
+/// What [`Ui::reorderable_list`] returns.
+pub struct ReorderableListResponse {
+    /// The response of the whole list.
+    ///
+    /// [`Response::changed`] is set when the items were reordered.
+    pub response: Response,
+
+    /// Set when the items were reordered this frame.
+    ///
+    /// `new_order[i]` is the old index of the item that is now at index `i`.
+    pub new_order: Option<Vec<usize>>,
+}
+
+pub(crate) fn reorderable_list<T>(
+    ui: &mut Ui,
+    id_source: Id,
+    items: &mut [T],
+    mut add_item: impl FnMut(&mut Ui, &mut T),
+) -> ReorderableListResponse {
+    let id = ui.make_persistent_id(id_source);
+    let handle_id = |index: usize| id.with(("handle", index));
+
+    let mut drag: Option<DragState> = ui.data(|d| d.get_temp(id));
+    if let Some(state) = drag {
+        // The drag can be canceled, or the items changed from under us:
+        let is_dragging = ui.memory(|m| m.is_being_dragged(handle_id(state.dragged)));
+        if !is_dragging || items.len() <= state.dragged {
+            drag = None;
+        }
+    }
+    let pointer = ui.input(|i| i.pointer.interact_pos());
+    let row_spacing = ui.spacing().item_spacing.y;
+    let animation_time = ui.style().animation_time;
+
+    let mut drag_started = None;
+    let mut other_row_centers = vec![];
+
+    let mut response = ui
+        .vertical(|ui| {
+            // The dragged row is left out, and the other rows make room for it where it would be dropped.
+            let add_gap = |ui: &mut Ui, drag: &DragState, slot: usize| {
+                let gap_id = id.with(("gap", drag.start_frame, slot));
+                let target_height = if drag.target == slot {
+                    drag.size.y + row_spacing
+                } else {
+                    0.0
+                };
+                let height =
+                    ui.ctx()
+                        .animate_value_with_time(gap_id, target_height, animation_time);
+                ui.add_space(height);
+            };
+
+            let mut slot = 0;
+            for (index, item) in items.iter_mut().enumerate() {
+                if let Some(drag) = &drag {
+                    if index == drag.dragged {
+                        continue;
+                    }
+                    add_gap(ui, drag, slot);
+                }
+
+                let InnerResponse {
+                    inner: handle_response,
+                    response: row_response,
+                } = ui.push_id(index, |ui| {
+                    ui.horizontal(|ui| {
+                        let handle_response = drag_handle(ui, Some(handle_id(index)));
+                        add_item(ui, item);
+                        handle_response
+                    })
+                    .inner
+                });
+
+                if let (Some(handle_response), Some(pointer)) = (handle_response, pointer) {
+                    if handle_response.drag_started() {
+                        drag_started = Some(DragState {
+                            dragged: index,
+                            target: index,
+                            grab_offset: pointer - row_response.rect.min,
+                            size: row_response.rect.size(),
+                            start_frame: ui.ctx().frame_nr(),
+                        });
+                    }
+                }
+                other_row_centers.push(row_response.rect.center().y);
+                slot += 1;
+            }
+            if let Some(drag) = &drag {
+                add_gap(ui, drag, slot);
+            }
+        })
+        .response;
+
+    let mut new_order = None;
+
+    if let (Some(state), Some(pointer)) = (&mut drag, pointer) {
+        // Show the dragged item under the pointer:
+        let dragged = state.dragged;
+        let width = state.size.x;
+        Area::new(id.with("dragged_item"))
+            .order(Order::Tooltip)
+            .interactable(false)
+            .fixed_pos(pointer - state.grab_offset)
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).inner_margin(0.0).show(ui, |ui| {
+                    ui.set_width(width);
+                    ui.push_id(dragged, |ui| {
+                        ui.horizontal(|ui| {
+                            drag_handle(ui, None);
+                            add_item(ui, &mut items[dragged]);
+                        });
+                    });
+                });
+            });
+        ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+
+        state.target = other_row_centers
+            .iter()
+            .filter(|&&center| center < pointer.y)
+            .count();
+
+        // Scroll when dragging close to the edge of the visible area:
+        let clip_rect = ui.clip_rect();
+        let margin = AUTO_SCROLL_MARGIN * ui.spacing().interact_size.y;
+        let scroll = if pointer.y < clip_rect.top() + margin {
+            clip_rect.top() + margin - pointer.y
+        } else if clip_rect.bottom() - margin < pointer.y {
+            clip_rect.bottom() - margin - pointer.y
+        } else {
+            0.0
+        };
+        if scroll != 0.0 {
+            ui.scroll_with_delta(vec2(0.0, AUTO_SCROLL_SPEED * scroll.clamp(-margin, margin)));
+            ui.ctx().request_repaint();
+        }
+
+        if ui.input(|i| i.pointer.any_released()) {
+            let (from, to) = (state.dragged, state.target);
+            if from != to {
+                let mut order: Vec<usize> = (0..items.len()).collect();
+                move_item(&mut order, from, to);
+                move_item(items, from, to);
+                new_order = Some(order);
+            }
+            drag = None;
+        }
+    }
+
+    let drag = drag.or(drag_started);
+    ui.data_mut(|d| match drag {
+        Some(drag) => d.insert_temp(id, drag),
+        None => d.remove::<DragState>(id),
+    });
+
+    if new_order.is_some() {
+        response.mark_changed();
+    }
+    ReorderableListResponse {
+        response,
+        new_order,
+    }
+}
+
+/// Move the item at `from` to `to`, shifting the ones in between.
+fn move_item<T>(items: &mut [T], from: usize, to: usize) {
+    if from < to {
+        items[from..=to].rotate_left(1);
+    } else {
+        items[to..=from].rotate_right(1);
+    }
+}
+
+/// The grip to drag a row by. Only interactive if it has an id.
+fn drag_handle(ui: &mut Ui, id: Option<Id>) -> Option<Response> {
+    let size = vec2(ui.spacing().icon_width, ui.spacing().interact_size.y);
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    let response = id.map(|id| {
+        ui.interact(rect, id, Sense::drag())
+            .on_hover_cursor(CursorIcon::Grab)
+    });
+
+    if ui.is_rect_visible(rect) {
+        let visuals = match &response {
+            Some(response) => ui.style().interact(response),
+            None => &ui.visuals().widgets.active,
+        };
+        let stroke = Stroke::new(1.0, visuals.fg_stroke.color);
+        let half_width = 0.35 * rect.width();
+        for dy in [-3.0, 0.0, 3.0] {
+            let y = rect.center().y + dy;
+            ui.painter().line_segment(
+                [
+                    pos2(rect.center().x - half_width, y),
+                    pos2(rect.center().x + half_width, y),
+                ],
+                stroke,
+            );
+        }
+    }
+    response
+}
+
+#[test]
+fn test_move_item() {
+    let mut items = [0, 1, 2, 3, 4];
+    move_item(&mut items, 1, 3);
+    assert_eq!(items, [0, 2, 3, 1, 4]);
+    move_item(&mut items, 4, 0);
+    assert_eq!(items, [4, 0, 2, 3, 1]);
+}
@@ -0,0 +1,299 @@
+//! A [`Splitter`] divides the available space into several regions separated by draggable lines.
+//!
+//! Unlike [`crate::SidePanel`] and [`crate::TopBottomPanel`], a [`Splitter`] does not
+//! attach to the edges of the screen, so it can be used anywhere, e.g. inside a [`crate::CentralPanel`],
+//! and can be nested to create more complex layouts.
+//!
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! egui::Splitter::horizontal("workspace").show(ui, 3, |ui, index| {
+//!     ui.label(format!("Pane {index}"));
+//! });
+//! # });
+//! ```
+
+use crate::*;
+
+/// The persisted state of a [`Splitter`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SplitterState {
+    /// The fraction of the space given to each pane. Sums to one.
+    pub ratios: Vec<f32>,
+}
+
+impl SplitterState {
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_persisted(id))
+    }
+
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
+}
+
+/// Splits the available space into several resizable panes.
+///
+/// The panes are separated by lines that the user can drag to resize the panes.
+/// The separators can also be focused with tab and moved with the arrow keys.
+/// Double-clicking a separator resets the panes to their default sizes.
+///
+/// The sizes are persisted as ratios, so the panes keep their relative sizes when the
+/// available space changes.
+///
+/// Splitters can be nested by showing a [`Splitter`] inside of a pane:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// egui::Splitter::horizontal("outer").show(ui, 2, |ui, index| {
+///     if index == 0 {
+///         ui.label("Sidebar");
+///     } else {
+///         egui::Splitter::vertical("inner").show(ui, 2, |ui, index| {
+///             ui.label(format!("Editor {index}"));
+///         });
+///     }
+/// });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Splitter {
+    id_source: Id,
+    vertical: bool,
+    default_ratios: Option<Vec<f32>>,
+    min_size: f32,
+    resizable: bool,
+}
+
+impl Splitter {
+    /// The panes are laid out next to each other, left to right,
+    /// separated by vertical lines.
+    pub fn horizontal(id_source: impl std::hash::Hash) -> Self {
+        Self::new(id_source, false)
+    }
+
+    /// The panes are laid out on top of each other, top to bottom,
+    /// separated by horizontal lines.
+    pub fn vertical(id_source: impl std::hash::Hash) -> Self {
+        Self::new(id_source, true)
+    }
+
+    fn new(id_source: impl std::hash::Hash, vertical: bool) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            vertical,
+            default_ratios: None,
+            min_size: 32.0,
+            resizable: true,
+        }
+    }
+
+    /// The initial relative sizes of the panes, e.g. `[1.0, 3.0]`.
+    ///
+    /// These are normalized so they sum to one.
+    /// By default all panes get the same size.
+    #[inline]
+    pub fn default_ratios(mut self, ratios: impl Into<Vec<f32>>) -> Self {
+        self.default_ratios = Some(ratios.into());
+        self
+    }
+
+    /// The user can't drag a separator so that a pane becomes smaller than this.
+    ///
+    /// Default: `32.0`.
+    #[inline]
+    pub fn min_size(mut self, min_size: f32) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Can the user resize the panes?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    fn default_state(&self, num_panes: usize) -> SplitterState {
+        let mut ratios = match &self.default_ratios {
+            Some(ratios) if ratios.len() == num_panes => ratios.clone(),
+            _ => vec![1.0; num_panes],
+        };
+        let sum: f32 = ratios.iter().sum();
+        if 0.0 < sum {
+            for ratio in &mut ratios {
+                *ratio /= sum;
+            }
+        }
+        SplitterState { ratios }
+    }
+
+    /// Show `num_panes` panes, calling `add_pane` with the index of each one.
+    ///
+    /// The splitter fills all the available space of the parent [`Ui`].
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        num_panes: usize,
+        mut add_pane: impl FnMut(&mut Ui, usize) -> R,
+    ) -> InnerResponse<Vec<R>> {
+        let id = ui.make_persistent_id(self.id_source);
+        let vertical = self.vertical;
+        let rect = ui.available_rect_before_wrap();
+
+        let mut state = SplitterState::load(ui.ctx(), id)
+            .filter(|state| state.ratios.len() == num_panes)
+            .unwrap_or_else(|| self.default_state(num_panes));
+
+        let axis = usize::from(vertical);
+        let separator_width = ui.spacing().item_spacing[axis];
+        let num_separators = num_panes.saturating_sub(1);
+        let total = (rect.size()[axis] - separator_width * num_separators as f32).at_least(0.0);
+        let min_ratio = if 0.0 < total {
+            (self.min_size / total).min(1.0 / num_panes as f32)
+        } else {
+            0.0
+        };
+
+        // Handle the separators first, so that the panes use the new sizes this frame.
+        let mut separators = Vec::with_capacity(num_separators);
+        let mut pane_start = rect.min[axis];
+        for i in 0..num_separators {
+            let line_pos = pane_start + state.ratios[i] * total + 0.5 * separator_width;
+            pane_start += state.ratios[i] * total + separator_width;
+
+            let grab_radius = ui.style().interaction.resize_grab_radius_side;
+            let interact_rect = if vertical {
+                Rect::from_x_y_ranges(
+                    rect.x_range(),
+                    line_pos - grab_radius..=line_pos + grab_radius,
+                )
+            } else {
+                Rect::from_x_y_ranges(
+                    line_pos - grab_radius..=line_pos + grab_radius,
+                    rect.y_range(),
+                )
+            };
+
+            let sense = if self.resizable {
+                Sense::click_and_drag()
+            } else {
+                Sense::hover()
+            };
+            let response = ui.interact(interact_rect, id.with(("separator", i)), sense);
+
+            let mut delta = 0.0;
+            if response.dragged() {
+                delta += response.drag_delta()[axis];
+            }
+            if response.has_focus() {
+                ui.memory_mut(|mem| {
+                    mem.set_focus_lock_filter(
+                        response.id,
+                        EventFilter {
+                            horizontal_arrows: !vertical,
+                            vertical_arrows: vertical,
+                            ..Default::default()
+                        },
+                    );
+                });
+                let (dec_key, inc_key) = if vertical {
+                    (Key::ArrowUp, Key::ArrowDown)
+                } else {
+                    (Key::ArrowLeft, Key::ArrowRight)
+                };
+                let steps =
+                    ui.input(|i| i.num_presses(inc_key) as f32 - i.num_presses(dec_key) as f32);
+                delta += steps * ui.spacing().interact_size.y;
+            }
+
+            if response.double_clicked() {
+                state = self.default_state(num_panes);
+            } else if delta != 0.0 && 0.0 < total {
+                let pair = state.ratios[i] + state.ratios[i + 1];
+                let left = (state.ratios[i] + delta / total)
+                    .clamp(min_ratio, (pair - min_ratio).at_least(min_ratio));
+                state.ratios[i] = left;
+                state.ratios[i + 1] = pair - left;
+            }
+
+            if response.hovered() || response.dragged() {
+                ui.ctx().set_cursor_icon(if vertical {
+                    CursorIcon::ResizeRow
+                } else {
+                    CursorIcon::ResizeColumn
+                });
+            }
+
+            separators.push(response);
+        }
+
+        let mut inner = Vec::with_capacity(num_panes);
+        let mut pane_start = rect.min[axis];
+        let mut line_positions = Vec::with_capacity(num_separators);
+        for (i, ratio) in state.ratios.iter().enumerate() {
+            let pane_size = ratio * total;
+            let pane_rect = if vertical {
+                Rect::from_x_y_ranges(rect.x_range(), pane_start..=pane_start + pane_size)
+            } else {
+                Rect::from_x_y_ranges(pane_start..=pane_start + pane_size, rect.y_range())
+            };
+            let mut child_ui = ui.child_ui_with_id_source(pane_rect, *ui.layout(), (id, i));
+            child_ui.set_clip_rect(ui.clip_rect().intersect(pane_rect));
+            inner.push(add_pane(&mut child_ui, i));
+
+            pane_start += pane_size;
+            if i + 1 < num_panes {
+                line_positions.push(pane_start + 0.5 * separator_width);
+                pane_start += separator_width;
+            }
+        }
+
+        for (line_pos, response) in line_positions.into_iter().zip(&separators) {
+            let stroke = if response.dragged() || response.has_focus() {
+                ui.visuals().widgets.active.fg_stroke
+            } else if response.hovered() {
+                ui.visuals().widgets.hovered.fg_stroke
+            } else {
+                ui.visuals().widgets.noninteractive.bg_stroke
+            };
+            let line_pos = ui.painter().round_to_pixel(line_pos);
+            if vertical {
+                ui.painter().hline(rect.x_range(), line_pos, stroke);
+            } else {
+                ui.painter().vline(line_pos, rect.y_range(), stroke);
+            }
+        }
+
+        state.store(ui.ctx(), id);
+
+        let response = ui.allocate_rect(rect, Sense::hover());
+        InnerResponse::new(inner, response)
+    }
+}
+
+#[test]
+fn splitter_keeps_ratios() {
+    let ctx = Context::default();
+    let run = |ratios: [f32; 2]| {
+        let mut rects = vec![];
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                rects = Splitter::horizontal("splitter")
+                    .default_ratios(ratios)
+                    .show(ui, 2, |ui, _| ui.max_rect())
+                    .inner;
+            });
+        });
+        rects[0].width() / (rects[0].width() + rects[1].width())
+    };
+
+    let ratio = run([1.0, 3.0]);
+    assert!((ratio - 0.25).abs() < 1e-3, "{ratio}");
+
+    // The persisted ratios win over the defaults:
+    let ratio = run([1.0, 1.0]);
+    assert!((ratio - 0.25).abs() < 1e-3, "{ratio}");
+}
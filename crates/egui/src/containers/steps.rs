@@ -0,0 +1,323 @@
+//! [`Steps`] walks the user through a form or setup in several steps, like a wizard.
+//!
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! # let mut name = String::new();
+//! let response = egui::Steps::new("signup", ["Name", "Confirm"]).show(ui, |ui, step| {
+//!     if step == 0 {
+//!         ui.text_edit_singleline(&mut name);
+//!         if name.is_empty() {
+//!             return Err("Enter a name".to_owned());
+//!         }
+//!     } else {
+//!         ui.label(format!("Sign up as {name}?"));
+//!     }
+//!     Ok(())
+//! });
+//! if response.finished {
+//!     // Sign up…
+//! }
+//! # });
+//! ```
+
+use crate::*;
+
+/// The persisted state of [`Steps`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct StepsState {
+    /// The index of the step being shown.
+    pub current: usize,
+}
+
+impl StepsState {
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_persisted(id))
+    }
+
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
+}
+
+/// A change of step that is being animated.
+#[derive(Clone, Copy, Debug)]
+struct Transition {
+    start_time: f64,
+    forward: bool,
+}
+
+/// What [`Steps::show`] returns.
+pub struct StepsResponse {
+    /// The response of the whole container.
+    ///
+    /// [`Response::changed`] is set when the step changed.
+    pub response: Response,
+
+    /// The id the [`StepsState`] is stored under, e.g. for going back to the first step.
+    pub id: Id,
+
+    /// The index of the step being shown, after this frame.
+    pub current: usize,
+
+    /// The user clicked the finish button on the last step.
+    pub finished: bool,
+}
+
+/// Shows one step of several at a time, with a header showing the progress
+/// and buttons for going to the previous and next step.
+///
+/// The current step is stored in [`Memory`], see [`StepsState`].
+///
+/// The contents of each step decide whether the user can go on to the next step,
+/// by returning an error message if anything is missing or wrong.
+/// Then the next button is disabled, and shows the message when hovered.
+/// The user can always go back, also by clicking an earlier step in the header.
+#[must_use = "You should call .show()"]
+pub struct Steps {
+    id_source: Id,
+    titles: Vec<WidgetText>,
+    finish_text: WidgetText,
+    animated: bool,
+}
+
+impl Steps {
+    /// One step is shown for each title.
+    pub fn new(
+        id_source: impl std::hash::Hash,
+        titles: impl IntoIterator<Item = impl Into<WidgetText>>,
+    ) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            titles: titles.into_iter().map(Into::into).collect(),
+            finish_text: "Finish".into(),
+            animated: true,
+        }
+    }
+
+    /// The text of the next button on the last step. Default: "Finish".
+    #[inline]
+    pub fn finish_text(mut self, finish_text: impl Into<WidgetText>) -> Self {
+        self.finish_text = finish_text.into();
+        self
+    }
+
+    /// Slide the contents in when the step changes. Default: `true`.
+    #[inline]
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animated = animated;
+        self
+    }
+
+    /// Show the current step, calling `add_step` with its index.
+    ///
+    /// `add_step` returns `Err` with a message if the user can't go on to the next step yet.
+    pub fn show(
+        self,
+        ui: &mut Ui,
+        add_step: impl FnOnce(&mut Ui, usize) -> Result<(), String>,
+    ) -> StepsResponse {
+        let Self {
+            id_source,
+            titles,
+            finish_text,
+            animated,
+        } = self;
+
+        let id = ui.make_persistent_id(id_source);
+        let transition_id = id.with("transition");
+        let last = titles.len().saturating_sub(1);
+        let mut state = StepsState::load(ui.ctx(), id).unwrap_or_default();
+        state.current = state.current.min(last);
+        let old_step = state.current;
+        let mut finished = false;
+
+        let mut response = ui
+            .vertical(|ui| {
+                if let Some(step) = progress_header(ui, &titles, state.current) {
+                    state.current = step;
+                }
+                ui.separator();
+
+                let validation = step_contents(ui, transition_id, old_step, add_step);
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    let back = ui.add_enabled(0 < old_step, Button::new("⏴ Back"));
+                    if back.clicked() {
+                        state.current = old_step - 1;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        let next_text = if old_step == last {
+                            finish_text
+                        } else {
+                            "Next ⏵".into()
+                        };
+                        let mut next = ui.add_enabled(validation.is_ok(), Button::new(next_text));
+                        if let Err(message) = validation {
+                            next = next.on_disabled_hover_text(message);
+                        }
+                        if next.clicked() {
+                            if old_step == last {
+                                finished = true;
+                            } else {
+                                state.current = old_step + 1;
+                            }
+                        }
+                        ui.weak(format!("Step {} of {}", old_step + 1, titles.len()));
+                    });
+                });
+            })
+            .response;
+
+        if state.current != old_step {
+            response.mark_changed();
+            if animated {
+                let transition = Transition {
+                    start_time: ui.input(|i| i.time),
+                    forward: old_step < state.current,
+                };
+                ui.data_mut(|d| d.insert_temp(transition_id, transition));
+            }
+        }
+        state.store(ui.ctx(), id);
+
+        StepsResponse {
+            response,
+            id,
+            current: state.current,
+            finished,
+        }
+    }
+}
+
+/// The titles of all steps, with the earlier ones marked as done.
+///
+/// Returns the earlier step that was clicked, if any.
+fn progress_header(ui: &mut Ui, titles: &[WidgetText], current: usize) -> Option<usize> {
+    let mut clicked = None;
+    ui.horizontal_wrapped(|ui| {
+        let size = Vec2::splat(ui.spacing().interact_size.y);
+        for (index, title) in titles.iter().enumerate() {
+            if 0 < index {
+                let (rect, _) = ui.allocate_exact_size(
+                    vec2(ui.spacing().interact_size.y, size.y),
+                    Sense::hover(),
+                );
+                let stroke = if index <= current {
+                    ui.visuals().selection.stroke
+                } else {
+                    ui.visuals().widgets.noninteractive.bg_stroke
+                };
+                ui.painter().hline(rect.x_range(), rect.center().y, stroke);
+            }
+
+            let done = index < current;
+            let sense = if done { Sense::click() } else { Sense::hover() };
+            let (rect, response) = ui.allocate_exact_size(size, sense);
+            response.widget_info(|| {
+                WidgetInfo::selected(WidgetType::SelectableLabel, index == current, title.text())
+            });
+            if response.clicked() {
+                clicked = Some(index);
+            }
+
+            if ui.is_rect_visible(rect) {
+                let visuals = ui.style().interact_selectable(&response, index == current);
+                let radius = 0.5 * rect.height() - visuals.expansion.max(0.0);
+                let (fill, text_color) = if index <= current {
+                    (
+                        ui.visuals().selection.bg_fill,
+                        ui.visuals().strong_text_color(),
+                    )
+                } else {
+                    (visuals.bg_fill, visuals.text_color())
+                };
+                ui.painter()
+                    .circle(rect.center(), radius, fill, visuals.fg_stroke);
+                let mark = if done {
+                    "✔".to_owned()
+                } else {
+                    (index + 1).to_string()
+                };
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    mark,
+                    TextStyle::Button.resolve(ui.style()),
+                    text_color,
+                );
+            }
+
+            let title = if index == current {
+                title.clone().strong()
+            } else {
+                title.clone()
+            };
+            let label = ui.add(Label::new(title).sense(sense));
+            if label.clicked() {
+                clicked = Some(index);
+            }
+            if done {
+                (response | label).on_hover_cursor(CursorIcon::PointingHand);
+            }
+        }
+    });
+    clicked
+}
+
+/// Show the contents of the current step, sliding in if the step just changed.
+fn step_contents(
+    ui: &mut Ui,
+    transition_id: Id,
+    step: usize,
+    add_step: impl FnOnce(&mut Ui, usize) -> Result<(), String>,
+) -> Result<(), String> {
+    let rect = ui.available_rect_before_wrap();
+
+    let mut offset = 0.0;
+    if let Some(transition) = ui.data(|d| d.get_temp::<Transition>(transition_id)) {
+        let animation_time = ui.style().animation_time;
+        let t = ((ui.input(|i| i.time) - transition.start_time) as f32 / animation_time)
+            .clamp(0.0, 1.0);
+        if t < 1.0 {
+            let eased = 1.0 - (1.0 - t).powi(3);
+            let direction = if transition.forward { 1.0 } else { -1.0 };
+            offset = direction * (1.0 - eased) * 0.25 * rect.width();
+            ui.ctx().request_repaint();
+        } else {
+            ui.data_mut(|d| d.remove::<Transition>(transition_id));
+        }
+    }
+
+    let mut child_ui = ui.child_ui_with_id_source(
+        rect.translate(vec2(offset, 0.0)),
+        *ui.layout(),
+        (transition_id, step),
+    );
+    child_ui.set_clip_rect(ui.clip_rect().intersect(rect));
+    let result = add_step(&mut child_ui, step);
+    let size = child_ui.min_rect().size();
+    ui.allocate_rect(Rect::from_min_size(rect.min, size), Sense::hover());
+    result
+}
+
+#[test]
+fn steps_clamp_current() {
+    let ctx = Context::default();
+    let mut shown = vec![];
+    let _ = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let id = ui.make_persistent_id(Id::new("steps"));
+            StepsState { current: 5 }.store(ui.ctx(), id);
+            let response = Steps::new("steps", ["One", "Two"]).show(ui, |_, step| {
+                shown.push(step);
+                Ok(())
+            });
+            assert_eq!(response.current, 1);
+            assert!(!response.finished);
+        });
+    });
+    assert_eq!(shown, [1]);
+}
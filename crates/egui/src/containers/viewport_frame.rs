@@ -0,0 +1,365 @@
+//! A custom window frame for native viewports without OS decorations.
+
+use crate::*;
+
+use super::window::{close_button, maximize_button, minimize_button};
+
+/// Draws a title bar and frame around the contents of an undecorated native viewport
+/// (one created with [`ViewportBuilder::with_decorations`] set to `false`).
+///
+/// It implements the behaviors users expect from a native window:
+/// * dragging the title bar moves the window
+/// * double-clicking the title bar maximizes or restores the window
+/// * minimize, maximize and close buttons in the title bar
+/// * dragging the edges and corners of the window resizes it
+///
+/// All of these are implemented by sending [`ViewportCommand`]s to the current viewport.
+///
+/// The frame fills the whole viewport, like a [`CentralPanel`].
+/// To get rounded corners, the viewport needs to be transparent
+/// (see [`ViewportBuilder::with_transparent`]).
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// egui::ViewportFrame::new("My app").show(ctx, |ui| {
+///     ui.label("Hello World!");
+/// });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct ViewportFrame {
+    title: WidgetText,
+    frame: Option<Frame>,
+    title_bar_height: f32,
+    minimizable: bool,
+    maximizable: bool,
+    closable: bool,
+    resizable: bool,
+    resize_border: f32,
+}
+
+impl ViewportFrame {
+    pub fn new(title: impl Into<WidgetText>) -> Self {
+        Self {
+            title: title.into().fallback_text_style(TextStyle::Heading),
+            frame: None,
+            title_bar_height: 32.0,
+            minimizable: true,
+            maximizable: true,
+            closable: true,
+            resizable: true,
+            resize_border: 4.0,
+        }
+    }
+
+    /// Change the background color, margins, rounding, etc.
+    ///
+    /// By default this looks like a [`Window`].
+    #[inline]
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    /// Height of the title bar.
+    ///
+    /// Default: `32.0`.
+    #[inline]
+    pub fn title_bar_height(mut self, title_bar_height: f32) -> Self {
+        self.title_bar_height = title_bar_height;
+        self
+    }
+
+    /// Show a button that minimizes the viewport?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn minimizable(mut self, minimizable: bool) -> Self {
+        self.minimizable = minimizable;
+        self
+    }
+
+    /// Show a button that maximizes and restores the viewport,
+    /// and do the same when the title bar is double-clicked?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn maximizable(mut self, maximizable: bool) -> Self {
+        self.maximizable = maximizable;
+        self
+    }
+
+    /// Show a button that closes the viewport?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Can the user resize the viewport by dragging its edges and corners?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// How far in from the edges of the viewport the user can grab to resize it.
+    ///
+    /// Default: `4.0`.
+    #[inline]
+    pub fn resize_border(mut self, resize_border: f32) -> Self {
+        self.resize_border = resize_border;
+        self
+    }
+
+    pub fn show<R>(
+        self,
+        ctx: &Context,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.show_dyn(ctx, Box::new(add_contents))
+    }
+
+    fn show_dyn<'c, R>(
+        self,
+        ctx: &Context,
+        add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
+    ) -> InnerResponse<R> {
+        let (is_maximized, is_fullscreen) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (
+                viewport.maximized.unwrap_or(false),
+                viewport.fullscreen.unwrap_or(false),
+            )
+        });
+
+        let frame = self.frame.unwrap_or_else(|| {
+            let style = ctx.style();
+            Frame {
+                fill: style.visuals.window_fill(),
+                stroke: style.visuals.window_stroke(),
+                rounding: style.visuals.window_rounding,
+                outer_margin: (0.5 * style.visuals.window_stroke().width).into(), // so the stroke is within the bounds
+                ..Default::default()
+            }
+        });
+        let frame = if is_maximized || is_fullscreen {
+            // Corners of maximized windows are not rounded:
+            Frame {
+                rounding: Rounding::ZERO,
+                ..frame
+            }
+        } else {
+            frame
+        };
+
+        CentralPanel::default().frame(frame).show(ctx, |ui| {
+            let app_rect = ui.max_rect();
+
+            let title_bar_rect = {
+                let mut rect = app_rect;
+                rect.max.y = rect.min.y + self.title_bar_height;
+                rect
+            };
+            self.title_bar_ui(ui, title_bar_rect, is_maximized);
+
+            let content_rect = {
+                let mut rect = app_rect;
+                rect.min.y = title_bar_rect.max.y;
+                rect
+            }
+            .shrink(ui.spacing().window_margin.left);
+            let mut content_ui = ui.child_ui(content_rect, *ui.layout());
+            let inner = add_contents(&mut content_ui);
+            ui.expand_to_include_rect(content_ui.min_rect());
+
+            // The widget added last gets the input, so add the resize handles on top of everything:
+            if self.resizable && !is_maximized && !is_fullscreen {
+                resize_handles(ui, ctx.screen_rect(), self.resize_border);
+            }
+
+            inner
+        })
+    }
+
+    fn title_bar_ui(&self, ui: &mut Ui, title_bar_rect: Rect, is_maximized: bool) {
+        let title_bar_response = ui.interact(
+            title_bar_rect,
+            Id::new("viewport_title_bar"),
+            Sense::click_and_drag(),
+        );
+
+        let title_galley = self.title.clone().into_galley(
+            ui,
+            Some(false),
+            title_bar_rect.width(),
+            TextStyle::Heading,
+        );
+        let text_pos = Align2::CENTER_CENTER
+            .align_size_within_rect(title_galley.size(), title_bar_rect)
+            .min;
+        ui.painter()
+            .galley(text_pos, title_galley, ui.visuals().text_color());
+
+        ui.painter().hline(
+            title_bar_rect.x_range().shrink(1.0),
+            title_bar_rect.bottom(),
+            ui.visuals().widgets.noninteractive.bg_stroke,
+        );
+
+        if title_bar_response.double_clicked() {
+            if self.maximizable {
+                ui.ctx()
+                    .send_viewport_cmd(ViewportCommand::Maximized(!is_maximized));
+            }
+        } else if title_bar_response.drag_started_by(PointerButton::Primary) {
+            ui.ctx().send_viewport_cmd(ViewportCommand::StartDrag);
+        }
+
+        // Buttons, right to left:
+        let button_size = Vec2::splat(ui.spacing().icon_width);
+        let pad = 0.5 * (title_bar_rect.height() - button_size.y);
+        let mut button_rect = Rect::from_min_size(
+            pos2(
+                title_bar_rect.right() - pad - button_size.x,
+                title_bar_rect.center().y - 0.5 * button_size.y,
+            ),
+            button_size,
+        );
+        let button_step = vec2(button_size.x + ui.spacing().item_spacing.x, 0.0);
+
+        if self.closable {
+            if close_button(ui, button_rect)
+                .on_hover_text("Close the window")
+                .clicked()
+            {
+                ui.ctx().send_viewport_cmd(ViewportCommand::Close);
+            }
+            button_rect = button_rect.translate(-button_step);
+        }
+
+        if self.maximizable {
+            let hover_text = if is_maximized {
+                "Restore the window"
+            } else {
+                "Maximize the window"
+            };
+            if maximize_button(ui, button_rect, is_maximized)
+                .on_hover_text(hover_text)
+                .clicked()
+            {
+                ui.ctx()
+                    .send_viewport_cmd(ViewportCommand::Maximized(!is_maximized));
+            }
+            button_rect = button_rect.translate(-button_step);
+        }
+
+        if self.minimizable
+            && minimize_button(ui, button_rect, false)
+                .on_hover_text("Minimize the window")
+                .clicked()
+        {
+            ui.ctx().send_viewport_cmd(ViewportCommand::Minimized(true));
+        }
+    }
+}
+
+/// Invisible handles along the edges and corners of the viewport
+/// that start resizing it when dragged.
+fn resize_handles(ui: &mut Ui, rect: Rect, border: f32) {
+    use ResizeDirection::{East, North, NorthEast, NorthWest, South, SouthEast, SouthWest, West};
+
+    // Corners last, so they win over the edges:
+    let corner = 2.0 * border;
+    let handles = [
+        (
+            North,
+            Rect::from_min_max(rect.left_top(), pos2(rect.right(), rect.top() + border)),
+            CursorIcon::ResizeNorth,
+        ),
+        (
+            South,
+            Rect::from_min_max(
+                pos2(rect.left(), rect.bottom() - border),
+                rect.right_bottom(),
+            ),
+            CursorIcon::ResizeSouth,
+        ),
+        (
+            West,
+            Rect::from_min_max(rect.left_top(), pos2(rect.left() + border, rect.bottom())),
+            CursorIcon::ResizeWest,
+        ),
+        (
+            East,
+            Rect::from_min_max(pos2(rect.right() - border, rect.top()), rect.right_bottom()),
+            CursorIcon::ResizeEast,
+        ),
+        (
+            NorthWest,
+            Rect::from_min_size(rect.left_top(), Vec2::splat(corner)),
+            CursorIcon::ResizeNorthWest,
+        ),
+        (
+            NorthEast,
+            Rect::from_min_size(rect.right_top() - vec2(corner, 0.0), Vec2::splat(corner)),
+            CursorIcon::ResizeNorthEast,
+        ),
+        (
+            SouthWest,
+            Rect::from_min_size(rect.left_bottom() - vec2(0.0, corner), Vec2::splat(corner)),
+            CursorIcon::ResizeSouthWest,
+        ),
+        (
+            SouthEast,
+            Rect::from_min_size(
+                rect.right_bottom() - Vec2::splat(corner),
+                Vec2::splat(corner),
+            ),
+            CursorIcon::ResizeSouthEast,
+        ),
+    ];
+
+    for (direction, handle_rect, cursor_icon) in handles {
+        let id = Id::new("viewport_resize_handle").with(direction as u8);
+        let response = ui.interact(handle_rect, id, Sense::drag());
+        if response.hovered() || response.dragged() {
+            ui.ctx().set_cursor_icon(cursor_icon);
+        }
+        if response.drag_started_by(PointerButton::Primary) {
+            ui.ctx()
+                .send_viewport_cmd(ViewportCommand::BeginResize(direction));
+        }
+    }
+}
+
+#[test]
+fn viewport_frame_resize_handles() {
+    let ctx = Context::default();
+    let screen_rect = Rect::from_min_size(Pos2::ZERO, vec2(400.0, 300.0));
+    let run = |events: Vec<Event>| {
+        let input = RawInput {
+            screen_rect: Some(screen_rect),
+            events,
+            ..Default::default()
+        };
+        let output = ctx.run(input, |ctx| {
+            ViewportFrame::new("Title").show(ctx, |ui| ui.label("Contents"));
+        });
+        output.viewport_output[&ViewportId::ROOT].commands.clone()
+    };
+
+    let corner = screen_rect.right_bottom() - Vec2::splat(1.0);
+    run(vec![Event::PointerMoved(corner)]);
+    let commands = run(vec![Event::PointerButton {
+        pos: corner,
+        button: PointerButton::Primary,
+        pressed: true,
+        modifiers: Default::default(),
+    }]);
+    assert!(commands.contains(&ViewportCommand::BeginResize(ResizeDirection::SouthEast)));
+}
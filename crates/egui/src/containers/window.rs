@@ -16,6 +16,7 @@ use super::*;
 /// * if the window has a scroll area (off by default)
 /// * if the window can be collapsed (minimized) to just the title bar (yes, by default)
 /// * if there should be a close button (none by default)
+/// * if the window can be minimized to the bottom of the screen or maximized (no, by default)
 ///
 /// ```
 /// # egui::__run_test_ctx(|ctx| {
@@ -38,6 +39,8 @@ pub struct Window<'open> {
     resize: Resize,
     scroll: ScrollArea,
     collapsible: bool,
+    minimizable: bool,
+    maximizable: bool,
     default_open: bool,
     with_title_bar: bool,
 }
@@ -62,6 +65,8 @@ impl<'open> Window<'open> {
                 .default_size([340.0, 420.0]), // Default inner size of a window
             scroll: ScrollArea::neither(),
             collapsible: true,
+            minimizable: false,
+            maximizable: false,
             default_open: true,
             with_title_bar: true,
         }
@@ -311,6 +316,32 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// Show a button in the title bar that minimizes the window to a title strip
+    /// docked to the bottom of the screen.
+    ///
+    /// Clicking the button again (or double-clicking the title) restores the window.
+    /// The state is persisted, see [`Self::mode`].
+    ///
+    /// Default is `false`.
+    #[inline]
+    pub fn minimizable(mut self, minimizable: bool) -> Self {
+        self.minimizable = minimizable;
+        self
+    }
+
+    /// Show a button in the title bar that maximizes the window to fill
+    /// [`Context::available_rect`], i.e. the space not taken up by panels.
+    ///
+    /// Clicking the button again restores the previous position and size.
+    /// The state is persisted, see [`Self::mode`].
+    ///
+    /// Default is `false`.
+    #[inline]
+    pub fn maximizable(mut self, maximizable: bool) -> Self {
+        self.maximizable = maximizable;
+        self
+    }
+
     /// Show title bar on top of the window?
     /// If `false`, the window will not be collapsible nor have a close-button.
     #[inline]
@@ -362,6 +393,27 @@ impl<'open> Window<'open> {
 }
 
 impl<'open> Window<'open> {
+    /// Is the window with the given [`Id`] minimized, maximized or neither?
+    ///
+    /// The id is the one given to [`Self::id`], or by default `Id::new(title)`.
+    ///
+    /// This can be used to e.g. open a native viewport with the same size and state
+    /// as an embedded window.
+    pub fn mode(ctx: &Context, id: Id) -> WindowMode {
+        ModeState::load(ctx, ctx.viewport_local_id(id)).mode
+    }
+
+    /// Minimize, maximize or restore the window with the given [`Id`].
+    ///
+    /// This works even if the window is not [`Self::minimizable`] or [`Self::maximizable`].
+    pub fn set_mode(ctx: &Context, id: Id, mode: WindowMode) {
+        let state_id = ctx.viewport_local_id(id);
+        let mut mode_state = ModeState::load(ctx, state_id);
+        let outer_rect = ctx.memory(|mem| mem.area_rect(id));
+        mode_state.set_mode(ctx, state_id, mode, outer_rect);
+        mode_state.store(ctx, state_id);
+    }
+
     /// Returns `None` if the window is not open (if [`Window::open`] was called with `&mut false`).
     /// Returns `Some(InnerResponse { inner: None })` if the window is collapsed.
     #[inline]
@@ -386,6 +438,8 @@ impl<'open> Window<'open> {
             resize,
             scroll,
             collapsible,
+            minimizable,
+            maximizable,
             default_open,
             with_title_bar,
         } = self;
@@ -402,11 +456,21 @@ impl<'open> Window<'open> {
 
         let area_id = area.id;
         let area_layer_id = area.layer();
-        let resize_id = area_id.with("resize");
+        let state_id = ctx.viewport_local_id(area_id);
+        let resize_id = state_id.with("resize");
         let mut collapsing =
-            CollapsingState::load_with_default_open(ctx, area_id.with("collapsing"), default_open);
+            CollapsingState::load_with_default_open(ctx, state_id.with("collapsing"), default_open);
+
+        let mut mode_state = ModeState::load(ctx, state_id);
+        let mode = mode_state.mode;
+        let (area, resize) = if mode == WindowMode::Normal {
+            (area, resize)
+        } else {
+            (area.movable(false), resize.resizable(false))
+        };
 
-        let is_collapsed = with_title_bar && !collapsing.is_open();
+        let is_minimized = with_title_bar && mode == WindowMode::Minimized;
+        let is_collapsed = with_title_bar && (!collapsing.is_open() || is_minimized);
         let possible = PossibleInteractions::new(&area, &resize, is_collapsed);
 
         let area = area.movable(false); // We move it manually, or the area will move the window when we want to resize it
@@ -426,6 +490,33 @@ impl<'open> Window<'open> {
             0.0
         };
 
+        match mode {
+            WindowMode::Normal => {
+                if let Some(restore) = mode_state.restore.take() {
+                    area.state_mut().set_left_top_pos(restore.rect.min);
+                    if let Some(mut resize_state) = resize::State::load(ctx, resize_id) {
+                        resize_state.desired_size = restore.desired_size;
+                        resize_state.store(ctx, resize_id);
+                    }
+                    mode_state.store(ctx, state_id);
+                }
+            }
+            WindowMode::Minimized => {
+                let size = area.state().size;
+                let x = minimized_dock_x(ctx, size.x);
+                let bottom = ctx.screen_rect().bottom();
+                area.state_mut().set_left_top_pos(pos2(x, bottom - size.y));
+            }
+            WindowMode::Maximized => {
+                let available_rect = ctx.available_rect();
+                area.state_mut().set_left_top_pos(available_rect.min);
+                let margins = frame.outer_margin.sum()
+                    + frame.inner_margin.sum()
+                    + vec2(0.0, title_bar_height);
+                resize = resize.fixed_size((available_rect.size() - margins).max(Vec2::ZERO));
+            }
+        }
+
         // First interact (move etc) to avoid frame delay:
         let last_frame_outer_rect = area.state().rect();
         let interaction = if possible.movable || possible.resizable() {
@@ -470,9 +561,13 @@ impl<'open> Window<'open> {
                 let title_bar = show_title_bar(
                     &mut frame.content_ui,
                     title,
-                    show_close_button,
                     &mut collapsing,
-                    collapsible,
+                    TitleBarButtons {
+                        close: show_close_button,
+                        collapse: collapsible && !is_minimized,
+                        minimize: minimizable,
+                        maximize: maximizable,
+                    },
                 );
                 resize.min_size.x = resize.min_size.x.at_least(title_bar.rect.width()); // Prevent making window smaller than title bar width
                 Some(title_bar)
@@ -480,21 +575,29 @@ impl<'open> Window<'open> {
                 None
             };
 
-            let (content_inner, content_response) = collapsing
-                .show_body_unindented(&mut frame.content_ui, |ui| {
-                    resize.show(ui, |ui| {
-                        if title_bar.is_some() {
-                            ui.add_space(title_content_spacing);
-                        }
-
-                        if scroll.is_any_scroll_enabled() {
-                            scroll.show(ui, add_contents).inner
-                        } else {
-                            add_contents(ui)
-                        }
+            let (content_inner, content_response) = if is_minimized {
+                (None, None)
+            } else {
+                collapsing
+                    .show_body_unindented(&mut frame.content_ui, |ui| {
+                        resize.show(ui, |ui| {
+                            if mode == WindowMode::Maximized {
+                                // Fill the whole window, even if the contents are small:
+                                ui.set_min_size(ui.max_rect().size());
+                            }
+                            if title_bar.is_some() {
+                                ui.add_space(title_content_spacing);
+                            }
+
+                            if scroll.is_any_scroll_enabled() {
+                                scroll.show(ui, add_contents).inner
+                            } else {
+                                add_contents(ui)
+                            }
+                        })
                     })
-                })
-                .map_or((None, None), |ir| (Some(ir.inner), Some(ir.response)));
+                    .map_or((None, None), |ir| (Some(ir.inner), Some(ir.response)))
+            };
 
             let outer_rect = frame.end(&mut area_content_ui).rect;
             paint_resize_corner(&area_content_ui, &possible, outer_rect, frame_stroke);
@@ -523,14 +626,19 @@ impl<'open> Window<'open> {
                     );
                 };
 
+                let mut new_mode = mode;
                 title_bar.ui(
                     &mut area_content_ui,
                     outer_rect,
                     &content_response,
                     open,
                     &mut collapsing,
-                    collapsible,
+                    &mut new_mode,
                 );
+                if new_mode != mode {
+                    mode_state.set_mode(ctx, state_id, new_mode, Some(outer_rect));
+                    mode_state.store(ctx, state_id);
+                }
             }
 
             collapsing.store(ctx);
@@ -565,6 +673,90 @@ impl<'open> Window<'open> {
     }
 }
 
+/// Whether an embedded [`Window`] is shown as usual, minimized or maximized.
+///
+/// See [`Window::minimizable`], [`Window::maximizable`] and [`Window::mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum WindowMode {
+    /// The window has its usual position and size.
+    #[default]
+    Normal,
+
+    /// Only the title bar is shown, docked to the bottom of the screen.
+    Minimized,
+
+    /// The window fills [`Context::available_rect`].
+    Maximized,
+}
+
+/// Where to put a window back when it is restored.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct Restore {
+    /// The outer rect of the window before it was minimized or maximized.
+    rect: Rect,
+
+    /// The [`Resize`] size of the window before it was minimized or maximized.
+    desired_size: Vec2,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct ModeState {
+    mode: WindowMode,
+
+    /// Set when leaving [`WindowMode::Normal`], and applied when going back to it.
+    restore: Option<Restore>,
+}
+
+impl ModeState {
+    fn load(ctx: &Context, state_id: Id) -> Self {
+        ctx.data_mut(|d| d.get_persisted(state_id.with("mode")))
+            .unwrap_or_default()
+    }
+
+    fn store(self, ctx: &Context, state_id: Id) {
+        ctx.data_mut(|d| d.insert_persisted(state_id.with("mode"), self));
+    }
+
+    fn set_mode(
+        &mut self,
+        ctx: &Context,
+        state_id: Id,
+        mode: WindowMode,
+        outer_rect: Option<Rect>,
+    ) {
+        if self.mode == WindowMode::Normal && mode != WindowMode::Normal {
+            self.restore = outer_rect.map(|rect| Restore {
+                rect,
+                desired_size: resize::State::load(ctx, state_id.with("resize"))
+                    .map_or(rect.size(), |state| state.desired_size),
+            });
+        }
+        self.mode = mode;
+    }
+}
+
+/// Minimized windows are laid out left-to-right along the bottom of the screen,
+/// in the order they are shown.
+fn minimized_dock_x(ctx: &Context, width: f32) -> f32 {
+    let frame_nr = ctx.frame_nr();
+    let left = ctx.screen_rect().left();
+    let spacing = ctx.style().spacing.item_spacing.x;
+    let dock_id = ctx.viewport_local_id(Id::new("__minimized_windows"));
+    ctx.data_mut(|d| {
+        let (dock_frame_nr, next_x) = d.get_temp_mut_or_insert_with(dock_id, || (frame_nr, left));
+        if *dock_frame_nr != frame_nr {
+            *dock_frame_nr = frame_nr;
+            *next_x = left;
+        }
+        let x = *next_x;
+        *next_x += width + spacing;
+        x
+    })
+}
+
 fn paint_resize_corner(
     ui: &Ui,
     possible: &PossibleInteractions,
@@ -931,14 +1123,31 @@ struct TitleBar {
     /// Size of the title bar in an expanded state. This size become known only
     /// after expanding window and painting its content
     rect: Rect,
+
+    buttons: TitleBarButtons,
+}
+
+/// Which buttons to show in the title bar of a [`Window`].
+#[derive(Clone, Copy, Debug)]
+struct TitleBarButtons {
+    /// The "Close" button on the right.
+    close: bool,
+
+    /// The collapsing button on the left.
+    collapse: bool,
+
+    /// The "Minimize" button on the right.
+    minimize: bool,
+
+    /// The "Maximize" button on the right.
+    maximize: bool,
 }
 
 fn show_title_bar(
     ui: &mut Ui,
     title: WidgetText,
-    show_close_button: bool,
     collapsing: &mut CollapsingState,
-    collapsible: bool,
+    buttons: TitleBarButtons,
 ) -> TitleBar {
     let inner_response = ui.horizontal(|ui| {
         let height = ui
@@ -951,16 +1160,21 @@ fn show_title_bar(
 
         let pad = (height - button_size.y) / 2.0; // calculated so that the icon is on the diagonal (if window padding is symmetrical)
 
-        if collapsible {
+        if buttons.collapse {
             ui.add_space(pad);
             collapsing.show_default_button_with_size(ui, button_size);
         }
 
         let title_galley = title.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Heading);
 
-        let minimum_width = if collapsible || show_close_button {
-            // If at least one button is shown we make room for both buttons (since title is centered):
-            2.0 * (pad + button_size.x + item_spacing.x) + title_galley.size().x
+        let num_right_buttons = usize::from(buttons.close)
+            + usize::from(buttons.minimize)
+            + usize::from(buttons.maximize);
+        let num_side_buttons = num_right_buttons.max(usize::from(buttons.collapse));
+        let minimum_width = if 0 < num_side_buttons {
+            // If at least one button is shown we make room for buttons on both sides (since title is centered):
+            2.0 * (pad + num_side_buttons as f32 * (button_size.x + item_spacing.x))
+                + title_galley.size().x
         } else {
             pad + title_galley.size().x + pad
         };
@@ -972,6 +1186,7 @@ fn show_title_bar(
             title_galley,
             min_rect,
             rect: Rect::NAN, // Will be filled in later
+            buttons,
         }
     });
 
@@ -993,9 +1208,9 @@ impl TitleBar {
     /// - `open`: if `None`, no "Close" button will be rendered, otherwise renders and processes
     ///   the "Close" button and writes a `false` if window was closed
     /// - `collapsing`: holds the current expanding state. Can be changed by double click on the
-    ///   title if `collapsible` is `true`
-    /// - `collapsible`: if `true`, double click on the title bar will be handled for a change
-    ///   of `collapsing` state
+    ///   title if the window is collapsible
+    /// - `mode`: the current [`WindowMode`]. Changed by the minimize and maximize buttons,
+    ///   and restored by double click on the title of a minimized window
     fn ui(
         mut self,
         ui: &mut Ui,
@@ -1003,18 +1218,42 @@ impl TitleBar {
         content_response: &Option<Response>,
         open: Option<&mut bool>,
         collapsing: &mut CollapsingState,
-        collapsible: bool,
+        mode: &mut WindowMode,
     ) {
         if let Some(content_response) = &content_response {
             // Now we know how large we got to be:
             self.rect.max.x = self.rect.max.x.max(content_response.rect.max.x);
         }
 
+        // Add buttons now that we know our full width, starting from the right:
+        let mut slot = 0;
         if let Some(open) = open {
-            // Add close button now that we know our full width:
-            if self.close_button_ui(ui).clicked() {
+            if close_button(ui, self.button_rect(ui, slot)).clicked() {
                 *open = false;
             }
+            slot += 1;
+        }
+        if self.buttons.maximize {
+            let is_maximized = *mode == WindowMode::Maximized;
+            if maximize_button(ui, self.button_rect(ui, slot), is_maximized).clicked() {
+                *mode = if is_maximized {
+                    WindowMode::Normal
+                } else {
+                    WindowMode::Maximized
+                };
+            }
+            slot += 1;
+        }
+        if self.buttons.minimize {
+            let is_minimized = *mode == WindowMode::Minimized;
+            if minimize_button(ui, self.button_rect(ui, slot), is_minimized).clicked() {
+                *mode = if is_minimized {
+                    WindowMode::Normal
+                } else {
+                    WindowMode::Minimized
+                };
+            }
+            slot += 1;
         }
 
         let full_top_rect = Rect::from_x_y_ranges(self.rect.x_range(), self.min_rect.y_range());
@@ -1036,35 +1275,39 @@ impl TitleBar {
             ui.painter().hline(outer_rect.x_range(), y, stroke);
         }
 
-        // Don't cover the close- and collapse buttons:
-        let double_click_rect = self.rect.shrink2(vec2(32.0, 0.0));
+        // Don't cover the buttons:
+        let button_width = ui.spacing().icon_width + ui.spacing().item_spacing.x;
+        let side_width = (slot.max(1) as f32 * button_width).at_least(32.0);
+        let double_click_rect = self.rect.shrink2(vec2(side_width, 0.0));
 
         if ui
             .interact(double_click_rect, self.id, Sense::click())
             .double_clicked()
-            && collapsible
         {
-            collapsing.toggle(ui);
+            if *mode == WindowMode::Minimized {
+                *mode = WindowMode::Normal;
+            } else if self.buttons.collapse {
+                collapsing.toggle(ui);
+            }
         }
     }
 
-    /// Paints the "Close" button at the right side of the title bar
-    /// and processes clicks on it.
+    /// The rect of a button at the right side of the title bar.
     ///
+    /// `slot` zero is the right-most button.
     /// The button is square and its size is determined by the
     /// [`crate::style::Spacing::icon_width`] setting.
-    fn close_button_ui(&self, ui: &mut Ui) -> Response {
+    fn button_rect(&self, ui: &Ui, slot: usize) -> Rect {
         let button_size = Vec2::splat(ui.spacing().icon_width);
         let pad = (self.rect.height() - button_size.y) / 2.0; // calculated so that the icon is on the diagonal (if window padding is symmetrical)
-        let button_rect = Rect::from_min_size(
+        let offset = slot as f32 * (button_size.x + ui.spacing().item_spacing.x);
+        Rect::from_min_size(
             pos2(
-                self.rect.right() - pad - button_size.x,
+                self.rect.right() - pad - button_size.x - offset,
                 self.rect.center().y - 0.5 * button_size.y,
             ),
             button_size,
-        );
-
-        close_button(ui, button_rect)
+        )
     }
 }
 
@@ -1078,7 +1321,7 @@ impl TitleBar {
 /// - `rect`: The rectangular area to fit the button in
 ///
 /// Returns the result of a click on a button if it was pressed
-fn close_button(ui: &mut Ui, rect: Rect) -> Response {
+pub(crate) fn close_button(ui: &mut Ui, rect: Rect) -> Response {
     let close_id = ui.auto_id_with("window_close_button");
     let response = ui.interact(rect, close_id, Sense::click());
     ui.expand_to_include_rect(response.rect);
@@ -1092,3 +1335,189 @@ fn close_button(ui: &mut Ui, rect: Rect) -> Response {
         .line_segment([rect.right_top(), rect.left_bottom()], stroke);
     response
 }
+
+/// Paints the "Minimize" button of the window and processes clicks on it.
+///
+/// The button is a line at the bottom of `rect`,
+/// or a "restore" symbol if the window is already minimized.
+pub(crate) fn minimize_button(ui: &mut Ui, rect: Rect, is_minimized: bool) -> Response {
+    let minimize_id = ui.auto_id_with("window_minimize_button");
+    let response = ui.interact(rect, minimize_id, Sense::click());
+    ui.expand_to_include_rect(response.rect);
+
+    let visuals = ui.style().interact(&response);
+    let rect = rect.shrink(2.0).expand(visuals.expansion);
+    if is_minimized {
+        paint_restore_icon(ui, rect, visuals.fg_stroke);
+    } else {
+        ui.painter()
+            .hline(rect.x_range(), rect.bottom(), visuals.fg_stroke);
+    }
+    response
+}
+
+/// Paints the "Maximize" button of the window and processes clicks on it.
+///
+/// The button is a square outline,
+/// or a "restore" symbol if the window is already maximized.
+pub(crate) fn maximize_button(ui: &mut Ui, rect: Rect, is_maximized: bool) -> Response {
+    let maximize_id = ui.auto_id_with("window_maximize_button");
+    let response = ui.interact(rect, maximize_id, Sense::click());
+    ui.expand_to_include_rect(response.rect);
+
+    let visuals = ui.style().interact(&response);
+    let rect = rect.shrink(2.0).expand(visuals.expansion);
+    if is_maximized {
+        paint_restore_icon(ui, rect, visuals.fg_stroke);
+    } else {
+        ui.painter().rect_stroke(rect, 0.0, visuals.fg_stroke);
+    }
+    response
+}
+
+/// Two overlapping squares.
+fn paint_restore_icon(ui: &Ui, rect: Rect, stroke: Stroke) {
+    let offset = 0.25 * rect.width();
+    let back = Rect::from_min_max(rect.min + vec2(offset, 0.0), rect.max - vec2(0.0, offset));
+    let front = back.translate(vec2(-offset, offset));
+    ui.painter().rect_stroke(front, 0.0, stroke);
+    ui.painter()
+        .line_segment([back.left_top(), back.right_top()], stroke);
+    ui.painter()
+        .line_segment([back.right_top(), back.right_bottom()], stroke);
+}
+
+#[test]
+fn window_minimize_maximize_restore() {
+    let ctx = Context::default();
+    let id = Id::new("Window");
+    let run = |ctx: &Context| {
+        for _ in 0..3 {
+            let _ = ctx.run(Default::default(), |ctx| {
+                TopBottomPanel::top("top").show(ctx, |ui| ui.label("Top"));
+                Window::new("Window")
+                    .minimizable(true)
+                    .maximizable(true)
+                    .default_pos([100.0, 100.0])
+                    .show(ctx, |ui| ui.label("Contents"));
+            });
+        }
+        ctx.memory(|mem| mem.area_rect(id)).unwrap()
+    };
+
+    let normal_rect = run(&ctx);
+
+    Window::set_mode(&ctx, id, WindowMode::Maximized);
+    let maximized_rect = run(&ctx);
+    assert_eq!(Window::mode(&ctx, id), WindowMode::Maximized);
+    let available_rect = ctx.available_rect();
+    assert_eq!(maximized_rect.min, available_rect.min);
+    assert!((maximized_rect.width() - available_rect.width()).abs() < 1.0);
+
+    Window::set_mode(&ctx, id, WindowMode::Minimized);
+    let minimized_rect = run(&ctx);
+    assert!(minimized_rect.height() < normal_rect.height());
+    assert_eq!(minimized_rect.bottom(), ctx.screen_rect().bottom());
+
+    Window::set_mode(&ctx, id, WindowMode::Normal);
+    let restored_rect = run(&ctx);
+    assert_eq!(restored_rect, normal_rect);
+}
+
+#[test]
+fn window_title_bar_buttons() {
+    use crate::RawInputBuilder;
+
+    let ctx = Context::default();
+    let id = Id::new("Window");
+    let run = |input: RawInputBuilder| {
+        let _ = ctx.run(input.build(), |ctx| {
+            Window::new("Window")
+                .minimizable(true)
+                .maximizable(true)
+                .default_pos([100.0, 100.0])
+                .show(ctx, |ui| ui.label("Contents"));
+        });
+        ctx.memory(|mem| mem.area_rect(id)).unwrap()
+    };
+
+    // The center of the title bar button in the given slot, counting from the right:
+    let button_center = |window_rect: Rect, slot: usize| {
+        let style = ctx.style();
+        let margin = style.spacing.window_margin;
+        let button_size = style.spacing.icon_width;
+        let height = ctx
+            .fonts(|fonts| fonts.row_height(&TextStyle::Heading.resolve(&style)))
+            .max(style.spacing.interact_size.y);
+        let pad = (height - button_size) / 2.0;
+        let x = window_rect.right()
+            - margin.right
+            - pad
+            - 0.5 * button_size
+            - slot as f32 * (button_size + style.spacing.item_spacing.x);
+        pos2(x, window_rect.top() + margin.top + 0.5 * height)
+    };
+    let click = |pos: Pos2| {
+        crate::test_util::click(pos, |input| {
+            run(input);
+        });
+        run(RawInputBuilder::new())
+    };
+
+    let normal_rect = run(RawInputBuilder::new());
+    run(RawInputBuilder::new());
+    assert_eq!(Window::mode(&ctx, id), WindowMode::Normal);
+
+    // Without a close button, the maximize button is the right-most one:
+    let maximized_rect = click(button_center(normal_rect, 0));
+    assert_eq!(Window::mode(&ctx, id), WindowMode::Maximized);
+    assert!(normal_rect.width() < maximized_rect.width());
+
+    // Clicking it again restores the window:
+    click(button_center(maximized_rect, 0));
+    assert_eq!(Window::mode(&ctx, id), WindowMode::Normal);
+    assert_eq!(run(RawInputBuilder::new()), normal_rect);
+
+    click(button_center(normal_rect, 1));
+    assert_eq!(Window::mode(&ctx, id), WindowMode::Minimized);
+    let minimized_rect = run(RawInputBuilder::new()); // The title strip needs a frame to shrink
+    assert!(minimized_rect.height() < normal_rect.height());
+
+    // The minimize button turns into a restore button:
+    click(button_center(minimized_rect, 1));
+    assert_eq!(Window::mode(&ctx, id), WindowMode::Normal);
+    assert_eq!(run(RawInputBuilder::new()), normal_rect);
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn window_mode_is_persisted() {
+    let id = Id::new("Window");
+    let run = |ctx: &Context| {
+        for _ in 0..3 {
+            let _ = ctx.run(Default::default(), |ctx| {
+                Window::new("Window")
+                    .maximizable(true)
+                    .default_pos([100.0, 100.0])
+                    .show(ctx, |ui| ui.label("Contents"));
+            });
+        }
+        ctx.memory(|mem| mem.area_rect(id)).unwrap()
+    };
+
+    let ctx = Context::default();
+    let normal_rect = run(&ctx);
+    Window::set_mode(&ctx, id, WindowMode::Maximized);
+    let maximized_rect = run(&ctx);
+
+    let serialized = ctx.memory(|mem| ron::to_string(mem)).unwrap();
+    let ctx = Context::default();
+    ctx.memory_mut(|mem| *mem = ron::from_str(&serialized).unwrap());
+
+    assert_eq!(Window::mode(&ctx, id), WindowMode::Maximized);
+    assert_eq!(run(&ctx), maximized_rect);
+
+    // Where to restore the window to is persisted too:
+    Window::set_mode(&ctx, id, WindowMode::Normal);
+    assert_eq!(run(&ctx), normal_rect);
+}
@@ -78,6 +78,7 @@ impl ContextImpl {
         // Each request results in two repaints, just to give some things time to settle.
         // This solves some corner-cases of missing repaints on frame-delayed responses.
         viewport.repaint.outstanding = 1;
+        viewport.repaint.num_requests += 1;
 
         if let Some(callback) = &self.request_repaint_callback {
             // We save some CPU time by only calling the callback if we need to.
@@ -146,6 +147,21 @@ struct ViewportState {
     /// State related to repaint scheduling.
     repaint: ViewportRepaintInfo,
 
+    /// Unhandled key events from other viewports, delivered at the start of the next frame.
+    ///
+    /// See [`Context::route_unhandled_keys_to`].
+    /// Each event is paired with the time it was received.
+    routed_events: Vec<(Event, f64)>,
+
+    /// Set with [`ViewportCommand::CursorIcon`].
+    cursor_icon_override: Option<CursorIcon>,
+
+    /// Input-to-present latency, see [`Context::latency_stats`].
+    latency: ViewportLatency,
+
+    /// Timings of the latest frames, oldest first. See [`Context::report_frame_timings`].
+    frame_timings: std::collections::VecDeque<crate::FrameTimings>,
+
     // ----------------------
     // The output of a frame:
     graphics: GraphicLayers,
@@ -172,6 +188,56 @@ struct ViewportRepaintInfo {
 
     /// Did we?
     requested_last_frame: bool,
+
+    /// Monotonically increasing counter of calls to [`Context::request_repaint_after`] and friends.
+    num_requests: u64,
+}
+
+/// Per-viewport input latency measurements.
+struct ViewportLatency {
+    /// [`RawInput::oldest_event_time`] of the input that has been processed, but not yet presented.
+    unpresented_input_time: Option<f64>,
+
+    /// Seconds from input to present.
+    history: crate::util::History<f32>,
+}
+
+impl Default for ViewportLatency {
+    fn default() -> Self {
+        Self {
+            unpresented_input_time: None,
+            history: crate::util::History::new(0..300, 1.0),
+        }
+    }
+}
+
+impl ViewportLatency {
+    fn stats(&self) -> LatencyStats {
+        LatencyStats {
+            latest: self.history.latest(),
+            average: self.history.average(),
+            max: self.history.values().reduce(f32::max),
+            num_samples: self.history.len(),
+        }
+    }
+}
+
+/// How long it takes from receiving input until the result is presented on screen.
+///
+/// See [`Context::latency_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LatencyStats {
+    /// The latency of the latest presented frame that had input, in seconds.
+    pub latest: Option<f32>,
+
+    /// Average latency over the last second, in seconds.
+    pub average: Option<f32>,
+
+    /// Worst latency over the last second, in seconds.
+    pub max: Option<f32>,
+
+    /// Number of frames the stats are based on.
+    pub num_samples: usize,
 }
 
 impl Default for ViewportRepaintInfo {
@@ -186,12 +252,20 @@ impl Default for ViewportRepaintInfo {
             outstanding: 1,
 
             requested_last_frame: false,
+
+            num_requests: 0,
         }
     }
 }
 
 // ----------------------------------------------------------------------------
 
+/// See [`Context::set_open_url_handler`].
+type OpenUrlHandler = Arc<dyn Fn(&Context, &crate::OpenUrl) -> bool + Send + Sync>;
+
+/// See [`Context::set_system_sound_handler`].
+type SystemSoundHandler = Arc<dyn Fn(&Context, crate::SoundKind) -> bool + Send + Sync>;
+
 #[derive(Default)]
 struct ContextImpl {
     /// Since we could have multiple viewport across multiple monitors with
@@ -228,11 +302,43 @@ struct ContextImpl {
 
     request_repaint_callback: Option<Box<dyn Fn(RequestRepaintInfo) + Send + Sync>>,
 
+    /// See [`Context::set_open_url_handler`].
+    open_url_handler: Option<OpenUrlHandler>,
+
+    /// See [`Context::set_system_sound_handler`].
+    system_sound_handler: Option<SystemSoundHandler>,
+
+    /// See [`Context::set_webview_hook`].
+    webview_hook: Option<crate::link_viewports::WebviewHook>,
+
     viewport_parents: ViewportIdMap<ViewportId>,
     viewports: ViewportIdMap<ViewportState>,
 
     embed_viewports: bool,
 
+    /// See [`Context::set_profiler_overlay`].
+    profiler_overlay: bool,
+
+    /// See [`Context::set_tessellation_options_for_layer`].
+    layer_tessellation_options: HashMap<LayerId, TessellationOptions>,
+
+    /// See [`Context::set_inspector`].
+    inspector: bool,
+
+    /// See [`Context::progress`].
+    progress: crate::progress::ProgressRegistry,
+
+    /// See [`Context::record_input`].
+    #[cfg(feature = "persistence")]
+    input_recorder: Option<crate::input_recording::InputRecorder>,
+
+    /// See [`Context::replay_input`].
+    #[cfg(feature = "persistence")]
+    input_replay: Option<crate::input_recording::InputReplay>,
+
+    /// See [`Context::route_unhandled_keys_to`].
+    unhandled_keys_target: Option<ViewportId>,
+
     #[cfg(feature = "accesskit")]
     is_accesskit_enabled: bool,
     #[cfg(feature = "accesskit")]
@@ -243,6 +349,9 @@ struct ContextImpl {
 
 impl ContextImpl {
     fn begin_frame_mut(&mut self, mut new_raw_input: RawInput) {
+        #[cfg(feature = "persistence")]
+        self.replay_and_record_input(&mut new_raw_input);
+
         let viewport_id = new_raw_input.viewport_id;
         let parent_id = new_raw_input
             .viewports
@@ -255,6 +364,22 @@ impl ContextImpl {
         self.viewport_stack.push(ids);
         let viewport = self.viewports.entry(viewport_id).or_default();
 
+        if let Some(time) = new_raw_input.oldest_event_time() {
+            // Keep the oldest, in case the previous frame was never presented:
+            viewport.latency.unpresented_input_time.get_or_insert(time);
+        }
+
+        if !viewport.routed_events.is_empty() {
+            let (mut events, mut event_times): (Vec<_>, Vec<_>) =
+                std::mem::take(&mut viewport.routed_events)
+                    .into_iter()
+                    .unzip();
+            events.append(&mut new_raw_input.events);
+            event_times.append(&mut new_raw_input.event_times);
+            new_raw_input.events = events;
+            new_raw_input.event_times = event_times;
+        }
+
         if viewport.repaint.outstanding == 0 {
             // We are repainting now, so we can wait a while for the next repaint.
             viewport.repaint.repaint_delay = Duration::MAX;
@@ -308,6 +433,23 @@ impl ContextImpl {
 
         viewport.frame_state.begin_frame(&viewport.input);
 
+        let input = &mut viewport.input;
+        if self
+            .memory
+            .options
+            .style
+            .interaction
+            .touch_mode
+            .is_active(input)
+            && input
+                .gestures()
+                .iter()
+                .any(|gesture| matches!(gesture, crate::Gesture::LongPress { .. }))
+        {
+            // The long-press is a secondary click, so releasing the finger shouldn't also be a primary click:
+            input.pointer.has_moved_too_much_for_a_click = true;
+        }
+
         // Ensure we register the background area so panels and background ui can catch clicks:
         let screen_rect = viewport.input.screen_rect();
         self.memory.areas_mut().set_state(
@@ -338,6 +480,14 @@ impl ContextImpl {
         }
 
         self.update_fonts_mut();
+
+        let max_texture_side = self.viewport().input.max_texture_side;
+        self.tex_manager.0.write().set_max_texture_side(
+            self.memory
+                .options
+                .tile_large_textures
+                .then_some(max_texture_side),
+        );
     }
 
     /// Load fonts unless already loaded.
@@ -374,6 +524,8 @@ impl ContextImpl {
                 )
             });
 
+        fonts.set_distance_field(self.memory.options.distance_field_text);
+
         {
             crate::profile_scope!("Fonts::begin_frame");
             fonts.begin_frame(pixels_per_point, max_texture_side);
@@ -429,6 +581,41 @@ impl ContextImpl {
             .parent
     }
 
+    /// Forward the key events nobody consumed this frame to the [`Context::route_unhandled_keys_to`] target.
+    fn route_unhandled_keys(&mut self, from: ViewportId) {
+        let Some(target) = self.unhandled_keys_target else {
+            return;
+        };
+        if target == from || !self.all_viewport_ids().contains(&target) {
+            return;
+        }
+        if self.memory.focus().is_some() {
+            return; // A widget (e.g. a `TextEdit`) has keyboard focus, so the keys are handled.
+        }
+
+        let Some(viewport) = self.viewports.get(&from) else {
+            return;
+        };
+        // We no longer know exactly when each key event arrived, so use the time of the frame:
+        let time = viewport.input.time;
+        let key_events: Vec<(Event, f64)> = viewport
+            .input
+            .events
+            .iter()
+            .filter(|event| matches!(event, Event::Key { .. }))
+            .map(|event| (event.clone(), time))
+            .collect();
+
+        if !key_events.is_empty() {
+            self.viewports
+                .entry(target)
+                .or_default()
+                .routed_events
+                .extend(key_events);
+            self.request_repaint(target);
+        }
+    }
+
     fn all_viewport_ids(&self) -> ViewportIdSet {
         self.viewports
             .keys()
@@ -445,6 +632,47 @@ impl ContextImpl {
     fn viewport_for(&mut self, viewport_id: ViewportId) -> &mut ViewportState {
         self.viewports.entry(viewport_id).or_default()
     }
+
+    /// See [`Context::replay_input`] and [`Context::record_input`].
+    #[cfg(feature = "persistence")]
+    fn replay_and_record_input(&mut self, raw_input: &mut RawInput) {
+        if let Some(replay) = &mut self.input_replay {
+            let viewport_id = raw_input.viewport_id;
+            if let Some(recorded) = replay.next_frame(viewport_id) {
+                // Make the real window the same size as during the recording:
+                let live_size = raw_input.screen_rect.map(|rect| rect.size());
+                if let Some(size) = recorded.screen_rect.map(|rect| rect.size()) {
+                    if live_size.map_or(true, |live_size| (live_size - size).length() > 0.5) {
+                        self.viewport_for(viewport_id)
+                            .commands
+                            .push(ViewportCommand::InnerSize(size));
+                    }
+                }
+                *raw_input = recorded;
+            } else {
+                // Don't let the user interfere with the replay:
+                raw_input.events.clear();
+            }
+
+            if let Some(next_viewport_id) =
+                self.input_replay.as_ref().and_then(|r| r.next_viewport())
+            {
+                self.request_repaint(next_viewport_id);
+            } else {
+                #[cfg(feature = "log")]
+                log::info!("egui: Finished replaying input");
+                self.input_replay = None;
+            }
+        }
+
+        if let Some(recorder) = &mut self.input_recorder {
+            if let Err(_err) = recorder.record(raw_input) {
+                #[cfg(feature = "log")]
+                log::warn!("egui: Failed to record input, stopping the recording: {_err}");
+                self.input_recorder = None;
+            }
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -476,6 +704,19 @@ impl ContextImpl {
 ///
 /// [`Ui`] has many of the same accessor functions, and the same applies there.
 ///
+/// ## Threading
+/// [`Context`] is `Send + Sync`, so it is safe to clone it and send it to other threads,
+/// e.g. to call [`Self::request_repaint`] or to load textures from a background thread.
+/// Painting with a [`Painter`](crate::Painter) only appends shapes to the context under the same lock,
+/// so it is thread safe too, but shapes from different threads will be interleaved in an unspecified order.
+///
+/// [`Self::run`] must not be called concurrently for the same [`Context`].
+/// Deferred viewport callbacks ([`crate::DeferredViewportUiCallback`]) are `Send + Sync`,
+/// but integrations like `eframe` call them from the same thread that calls [`Self::run`],
+/// since the native windows are not thread safe.
+/// The tessellated output of a frame can be painted on another thread,
+/// which `eframe` does for deferred viewports with `NativeOptions::render_thread_per_viewport`.
+///
 /// ## Example:
 ///
 /// ``` no_run
@@ -735,6 +976,46 @@ impl Context {
         self.write(move |ctx| writer(&mut ctx.memory.options.tessellation_options))
     }
 
+    /// The [`TessellationOptions`] used for everything painted in the given layer.
+    ///
+    /// These are the global [`TessellationOptions`],
+    /// unless they were overridden with [`Self::set_tessellation_options_for_layer`].
+    pub fn tessellation_options_for_layer(&self, layer_id: LayerId) -> TessellationOptions {
+        self.read(|ctx| {
+            ctx.layer_tessellation_options
+                .get(&layer_id)
+                .copied()
+                .unwrap_or(ctx.memory.options.tessellation_options)
+        })
+    }
+
+    /// Use other [`TessellationOptions`] for everything painted in the given layer,
+    /// or go back to the global ones with `None`.
+    ///
+    /// For instance, you can turn off feathering for a layer with pixel art,
+    /// while the rest of the UI stays anti-aliased:
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// # let layer_id = egui::LayerId::background();
+    /// let mut options = ctx.tessellation_options(|options| *options);
+    /// options.feathering = false;
+    /// ctx.set_tessellation_options_for_layer(layer_id, Some(options));
+    /// ```
+    pub fn set_tessellation_options_for_layer(
+        &self,
+        layer_id: LayerId,
+        options: Option<TessellationOptions>,
+    ) {
+        self.write(|ctx| {
+            if let Some(options) = options {
+                ctx.layer_tessellation_options.insert(layer_id, options);
+            } else {
+                ctx.layer_tessellation_options.remove(&layer_id);
+            }
+        });
+    }
+
     /// If the given [`Id`] has been used previously the same frame at at different position,
     /// then an error will be printed on screen.
     ///
@@ -745,7 +1026,12 @@ impl Context {
     /// The most important thing is that [`Rect::min`] is approximately correct,
     /// because that's where the warning will be painted. If you don't know what size to pick, just pick [`Vec2::ZERO`].
     pub fn check_for_id_clash(&self, id: Id, new_rect: Rect, what: &str) {
-        let prev_rect = self.frame_state_mut(move |state| state.used_ids.insert(id, new_rect));
+        let prev_rect = self.frame_state_mut(move |state| {
+            if let Some(log) = &mut state.used_ids_log {
+                log.push((id, new_rect));
+            }
+            state.used_ids.insert(id, new_rect)
+        });
 
         if !self.options(|opt| opt.warn_on_id_clash) {
             return;
@@ -819,6 +1105,7 @@ impl Context {
 
     /// Use `ui.interact` instead
     #[allow(clippy::too_many_arguments)]
+    #[track_caller]
     pub(crate) fn interact(
         &self,
         clip_rect: Rect,
@@ -829,6 +1116,21 @@ impl Context {
         sense: Sense,
         enabled: bool,
     ) -> Response {
+        let location = std::panic::Location::caller();
+        if self.inspector() {
+            self.write(|ctx| {
+                let widget = crate::inspector::WidgetRecord {
+                    id,
+                    layer_id,
+                    rect,
+                    sense,
+                    enabled,
+                    location,
+                };
+                ctx.viewport().frame_state.inspector_widgets.push(widget);
+            });
+        }
+
         let gap = 0.1; // Just to make sure we don't accidentally hover two things at once (a small eps should be sufficient).
 
         // Make it easier to click things:
@@ -838,6 +1140,14 @@ impl Context {
                 .at_most(Vec2::splat(5.0)),
         );
 
+        // Make small widgets big enough to hit with a finger:
+        let interact_rect = if sense.interactive() && self.is_touch_mode() {
+            let min_size = Vec2::splat(self.style().interaction.touch_target_size);
+            interact_rect.expand2((0.5 * (min_size - interact_rect.size())).at_least(Vec2::ZERO))
+        } else {
+            interact_rect
+        };
+
         // Respect clip rectangle when interacting
         let interact_rect = clip_rect.intersect(interact_rect);
         let mut hovered = self.rect_contains_pointer(layer_id, interact_rect);
@@ -910,15 +1220,17 @@ impl Context {
             }
         }
 
-        self.interact_with_hovered(layer_id, id, rect, sense, enabled, hovered)
+        self.interact_with_hovered(layer_id, id, rect, clip_rect, sense, enabled, hovered)
     }
 
     /// You specify if a thing is hovered, and the function gives a [`Response`].
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn interact_with_hovered(
         &self,
         layer_id: LayerId,
         id: Id,
         rect: Rect,
+        clip_rect: Rect,
         sense: Sense,
         enabled: bool,
         hovered: bool,
@@ -932,6 +1244,7 @@ impl Context {
             layer_id,
             id,
             rect,
+            clip_rect,
             sense,
             enabled,
             hovered,
@@ -973,9 +1286,11 @@ impl Context {
 
             if sense.click
                 && memory.has_focus(response.id)
-                && (input.key_pressed(Key::Space) || input.key_pressed(Key::Enter))
+                && (input.key_pressed(Key::Space)
+                    || input.key_pressed(Key::Enter)
+                    || input.gamepad_button_pressed(GamepadButton::South))
             {
-                // Space/enter works like a primary click for e.g. selected buttons
+                // Space/enter/gamepad-south works like a primary click for e.g. selected buttons
                 response.clicked[PointerButton::Primary as usize] = true;
             }
 
@@ -1045,6 +1360,18 @@ impl Context {
                         }
                     }
                 }
+
+                if sense.click
+                    && response.is_pointer_button_down_on
+                    && memory.options.style.interaction.touch_mode.is_active(input)
+                    && input
+                        .gestures()
+                        .iter()
+                        .any(|gesture| matches!(gesture, crate::Gesture::LongPress { .. }))
+                {
+                    // Press-and-hold works like a secondary click on touch screens, e.g. to open a context menu
+                    response.clicked[PointerButton::Secondary as usize] = true;
+                }
             }
 
             if response.is_pointer_button_down_on {
@@ -1111,16 +1438,173 @@ impl Context {
 
     /// Open an URL in a browser.
     ///
-    /// Equivalent to:
+    /// Unless it is handled by the handler set with [`Self::set_open_url_handler`], this is equivalent to:
     /// ```
     /// # let ctx = egui::Context::default();
     /// # let open_url = egui::OpenUrl::same_tab("http://www.example.com");
     /// ctx.output_mut(|o| o.open_url = Some(open_url));
     /// ```
     pub fn open_url(&self, open_url: crate::OpenUrl) {
+        let handler = self.read(|ctx| ctx.open_url_handler.clone());
+        if let Some(handler) = handler {
+            if handler(self, &open_url) {
+                return;
+            }
+        }
         self.output_mut(|o| o.open_url = Some(open_url));
     }
 
+    /// Decide what happens when a URL is opened with [`Self::open_url`],
+    /// e.g. when a [`crate::Hyperlink`] is clicked.
+    ///
+    /// If the handler returns `true`, the URL has been handled and is not passed on to the backend.
+    /// Use this to e.g. open links in new tabs of your app when [`crate::OpenUrl::new_tab`] is set,
+    /// which it is when a hyperlink is middle-clicked or clicked with a modifier key held down.
+    ///
+    /// Note that only one handler can be set. Any new call overrides the previous handler.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.set_open_url_handler(|ctx, open_url| {
+    ///     if open_url.new_tab {
+    ///         ctx.open_url_in_viewport(&open_url.url);
+    ///         true
+    ///     } else {
+    ///         false
+    ///     }
+    /// });
+    /// ```
+    pub fn set_open_url_handler(
+        &self,
+        handler: impl Fn(&Self, &crate::OpenUrl) -> bool + Send + Sync + 'static,
+    ) {
+        self.write(|ctx| ctx.open_url_handler = Some(Arc::new(handler)));
+    }
+
+    /// Show the contents of URLs inside the app with this hook, e.g. using an embedded web view.
+    ///
+    /// This enables [`Self::open_url_in_viewport`] and [`crate::Hyperlink::open_in_viewport`].
+    /// The hook is called each frame with the [`Ui`] of the viewport showing the URL.
+    ///
+    /// Note that only one hook can be set. Any new call overrides the previous hook.
+    ///
+    /// egui can't render web pages itself, so the hook would usually show a [`crate::WebView`],
+    /// which the integration shows as a native web view:
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.set_webview_hook(|ui, url| {
+    ///     ui.add(egui::WebView::new(url));
+    /// });
+    /// ```
+    pub fn set_webview_hook(&self, hook: impl Fn(&mut Ui, &str) + Send + Sync + 'static) {
+        self.write(|ctx| ctx.webview_hook = Some(Arc::new(hook)));
+    }
+
+    /// Open an URL in a new viewport inside the app, shown with the hook set with [`Self::set_webview_hook`].
+    ///
+    /// The viewport stays open until the user closes it.
+    /// If no hook has been set, the URL is opened in a new browser tab instead.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn open_url_in_viewport(&self, url: impl ToString) {
+        if self.read(|ctx| ctx.webview_hook.is_some()) {
+            crate::link_viewports::open(self, url.to_string());
+        } else {
+            self.open_url(crate::OpenUrl::new_tab(url));
+        }
+    }
+
+    /// Open a local file or directory with its default application.
+    ///
+    /// Not supported on the web.
+    ///
+    /// Equivalent to:
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.output_mut(|o| o.open_path = Some("notes.txt".into()));
+    /// ```
+    pub fn open_path(&self, path: impl Into<std::path::PathBuf>) {
+        self.output_mut(|o| o.open_path = Some(path.into()));
+    }
+
+    /// Show a local file or directory in the file manager (Finder, Explorer, …).
+    ///
+    /// Where supported, the file is selected in its parent directory.
+    /// Not supported on the web.
+    ///
+    /// Equivalent to:
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.output_mut(|o| o.reveal_in_file_manager = Some("notes.txt".into()));
+    /// ```
+    pub fn reveal_in_file_manager(&self, path: impl Into<std::path::PathBuf>) {
+        self.output_mut(|o| o.reveal_in_file_manager = Some(path.into()));
+    }
+
+    /// Play a standard feedback sound of the OS, e.g. when input is rejected or an alert is shown.
+    ///
+    /// Unless it is handled by the handler set with [`Self::set_system_sound_handler`], this is equivalent to:
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.output_mut(|o| o.play_system_sound = Some(egui::SoundKind::Beep));
+    /// ```
+    pub fn play_system_sound(&self, sound: crate::SoundKind) {
+        let handler = self.read(|ctx| ctx.system_sound_handler.clone());
+        if let Some(handler) = handler {
+            if handler(self, sound) {
+                return;
+            }
+        }
+        self.output_mut(|o| o.play_system_sound = Some(sound));
+    }
+
+    /// Decide what happens when a sound is requested with [`Self::play_system_sound`].
+    ///
+    /// If the handler returns `true`, the sound has been handled and is not passed on to the backend.
+    /// Use this to play your own sounds, or to mute some (or all) of them.
+    ///
+    /// Note that only one handler can be set. Any new call overrides the previous handler.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// # let sounds_enabled = true;
+    /// ctx.set_system_sound_handler(move |_ctx, _sound| !sounds_enabled);
+    /// ```
+    pub fn set_system_sound_handler(
+        &self,
+        handler: impl Fn(&Self, crate::SoundKind) -> bool + Send + Sync + 'static,
+    ) {
+        self.write(|ctx| ctx.system_sound_handler = Some(Arc::new(handler)));
+    }
+
+    /// Have a screen reader read out this text, e.g. the result of a search that finished in the background.
+    ///
+    /// This is for the built-in text-to-speech fallback, see [`crate::Options::screen_reader`],
+    /// which `eframe` supports on the web (with its `web_screen_reader` feature).
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// # let results = vec![1, 2, 3];
+    /// ctx.announce(format!("{} search results", results.len()), egui::AnnouncePriority::Polite);
+    /// ```
+    pub fn announce(&self, text: impl Into<String>, priority: crate::AnnouncePriority) {
+        let announcement = crate::Announcement::new(text, priority);
+        self.output_mut(|o| o.announcements.push(announcement));
+    }
+
+    /// Give tactile feedback, e.g. when a value moves to the next step, or a dragged item is dropped.
+    ///
+    /// Does nothing on devices without haptics.
+    ///
+    /// Equivalent to:
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.output_mut(|o| o.haptic = Some(egui::HapticKind::Selection));
+    /// ```
+    pub fn haptic(&self, haptic: crate::HapticKind) {
+        self.output_mut(|o| o.haptic = Some(haptic));
+    }
+
     /// Copy the given text to the system clipboard.
     ///
     /// Empty strings are ignored.
@@ -1387,14 +1871,37 @@ impl Context {
         self.options_mut(|opt| std::sync::Arc::make_mut(&mut opt.style).visuals = visuals);
     }
 
+    /// Should interaction be adapted to touch screens right now?
+    ///
+    /// See [`crate::style::TouchMode`].
+    pub fn is_touch_mode(&self) -> bool {
+        let touch_mode = self.options(|opt| opt.style.interaction.touch_mode);
+        self.input(|i| touch_mode.is_active(i))
+    }
+
     /// The number of physical pixels for each logical point.
     ///
     /// This is calculated as [`Self::zoom_factor`] * [`Self::native_pixels_per_point`]
+    ///
+    /// Each viewport can be on a different monitor, and so can have a different `pixels_per_point`.
+    /// This returns the value of the current viewport.
+    /// Use [`Self::pixels_per_point_for`] to query another viewport.
     #[inline(always)]
     pub fn pixels_per_point(&self) -> f32 {
         self.input(|i| i.pixels_per_point)
     }
 
+    /// The number of physical pixels for each logical point in the given viewport.
+    ///
+    /// Returns `None` if the viewport has not been shown yet.
+    pub fn pixels_per_point_for(&self, viewport_id: ViewportId) -> Option<f32> {
+        self.read(|ctx| {
+            ctx.viewports
+                .get(&viewport_id)
+                .map(|viewport| viewport.input.pixels_per_point)
+        })
+    }
+
     /// Set the number of physical pixels for each logical point.
     /// Will become active at the start of the next frame.
     ///
@@ -1405,7 +1912,7 @@ impl Context {
         }
     }
 
-    /// The number of physical pixels for each logical point on this monitor.
+    /// The number of physical pixels for each logical point on the monitor of the current viewport.
     ///
     /// This is given as input to egui via [`ViewportInfo::native_pixels_per_point`]
     /// and cannot be changed.
@@ -1527,18 +2034,69 @@ impl Context {
     ) -> TextureHandle {
         let name = name.into();
         let image = image.into();
+        self.prepare_texture_upload(&name, image.size());
+        let tex_mngr = self.tex_manager();
+        let tex_id = tex_mngr.write().alloc(name, image, options);
+        TextureHandle::new(tex_mngr, tex_id)
+    }
+
+    /// Allocate a texture that is meant to be changed every frame, e.g. for video or live plots.
+    ///
+    /// Write to it with [`DynamicTexture::set_partial`],
+    /// and call [`DynamicTexture::id`] once per frame to upload the changes and get the texture to paint.
+    ///
+    /// ```
+    /// struct Scope {
+    ///     texture: Option<egui::DynamicTexture>,
+    ///     x: usize,
+    /// }
+    ///
+    /// impl Scope {
+    ///     fn ui(&mut self, ui: &mut egui::Ui) {
+    ///         let texture = self.texture.get_or_insert_with(|| {
+    ///             ui.ctx().load_dynamic_texture(
+    ///                 "scope",
+    ///                 egui::ColorImage::new([256, 64], egui::Color32::BLACK),
+    ///                 Default::default(),
+    ///             )
+    ///         });
+    ///
+    ///         let column = egui::ColorImage::new([1, 64], egui::Color32::GREEN);
+    ///         texture.set_partial([self.x, 0], &column);
+    ///         self.x = (self.x + 1) % 256;
+    ///
+    ///         ui.image((texture.id(), texture.size_vec2()));
+    ///     }
+    /// }
+    /// ```
+    pub fn load_dynamic_texture(
+        &self,
+        name: &str,
+        image: ColorImage,
+        options: TextureOptions,
+    ) -> DynamicTexture {
+        self.prepare_texture_upload(name, image.size);
+        DynamicTexture::new(&self.tex_manager(), name, image, options)
+    }
+
+    /// Large textures are split into tiles if [`crate::Options::tile_large_textures`] is set,
+    /// and otherwise must fit in [`crate::InputState::max_texture_side`].
+    fn prepare_texture_upload(&self, name: &str, [width, height]: [usize; 2]) {
         let max_texture_side = self.input(|i| i.max_texture_side);
+        let tile_large_textures = self.options(|o| o.tile_large_textures);
         crate::egui_assert!(
-            image.width() <= max_texture_side && image.height() <= max_texture_side,
+            tile_large_textures || (width <= max_texture_side && height <= max_texture_side),
             "Texture {:?} has size {}x{}, but the maximum texture side is {}",
             name,
-            image.width(),
-            image.height(),
+            width,
+            height,
             max_texture_side
         );
-        let tex_mngr = self.tex_manager();
-        let tex_id = tex_mngr.write().alloc(name, image, options);
-        TextureHandle::new(tex_mngr, tex_id)
+
+        // The app may load textures before the first frame:
+        self.tex_manager()
+            .write()
+            .set_max_texture_side(tile_large_textures.then_some(max_texture_side));
     }
 
     /// Low-level texture manager.
@@ -1597,6 +2155,36 @@ impl Context {
             crate::gui_zoom::zoom_with_keyboard(self);
         }
 
+        if self.profiler_overlay() {
+            let frame_timings = self.write(|ctx| {
+                let viewport = ctx.viewport();
+                viewport.frame_timings.make_contiguous().to_vec()
+            });
+            crate::profiler_overlay::paint(self, &frame_timings);
+        }
+
+        if self.options(|o| o.inspector_shortcut)
+            && self.input_mut(|i| i.consume_shortcut(&crate::inspector::SHORTCUT))
+        {
+            self.set_inspector(!self.inspector());
+        }
+
+        if self.inspector() {
+            // Take the widgets first, so the inspector doesn't list its own widgets.
+            let widgets = self.frame_state_mut(|fs| std::mem::take(&mut fs.inspector_widgets));
+            crate::inspector::show(self, &widgets);
+        }
+
+        if self.options(|o| o.taskbar_progress) {
+            self.update_taskbar_progress();
+        }
+
+        if self.viewport_id() == ViewportId::ROOT {
+            if let Some(hook) = self.read(|ctx| ctx.webview_hook.clone()) {
+                crate::link_viewports::show(self, &hook);
+            }
+        }
+
         self.write(|ctx| ctx.end_frame())
     }
 }
@@ -1604,6 +2192,9 @@ impl Context {
 impl ContextImpl {
     fn end_frame(&mut self) -> FullOutput {
         let ended_viewport_id = self.viewport_id();
+
+        self.route_unhandled_keys(ended_viewport_id);
+
         let viewport = self.viewports.entry(ended_viewport_id).or_default();
         let pixels_per_point = viewport.input.pixels_per_point;
 
@@ -1640,9 +2231,12 @@ impl ContextImpl {
         // Inform the backend of all textures that have been updated (including font atlas).
         let textures_delta = self.tex_manager.0.write().take_delta();
 
-        #[cfg_attr(not(feature = "accesskit"), allow(unused_mut))]
         let mut platform_output: PlatformOutput = std::mem::take(&mut viewport.output);
 
+        if let Some(cursor_icon) = viewport.cursor_icon_override {
+            platform_output.cursor_icon = cursor_icon;
+        }
+
         #[cfg(feature = "accesskit")]
         {
             crate::profile_scope!("accesskit");
@@ -1670,7 +2264,10 @@ impl ContextImpl {
             }
         }
 
-        let shapes = viewport.graphics.drain(self.memory.areas().order());
+        let shapes = viewport.graphics.drain(
+            self.memory.areas().order(),
+            &self.layer_tessellation_options,
+        );
 
         if viewport.input.wants_repaint() {
             self.request_repaint(ended_viewport_id);
@@ -1818,7 +2415,7 @@ impl Context {
             };
 
             let paint_stats = PaintStats::from_shapes(&shapes);
-            let clipped_primitives = {
+            let mut clipped_primitives = {
                 crate::profile_scope!("tessellator::tessellate_shapes");
                 tessellator::tessellate_shapes(
                     pixels_per_point,
@@ -1828,6 +2425,13 @@ impl Context {
                     shapes,
                 )
             };
+            {
+                crate::profile_scope!("resolve_tiles");
+                ctx.tex_manager
+                    .0
+                    .read()
+                    .resolve_tiles(&mut clipped_primitives);
+            }
             ctx.paint_stats = paint_stats.with_clipped_primitives(&clipped_primitives);
             clipped_primitives
         })
@@ -1869,17 +2473,27 @@ impl Context {
     // ---------------------------------------------------------------------
 
     /// Is the pointer (mouse/touch) over any egui area?
+    ///
+    /// Returns `false` if the pointer was claimed with [`InputState::consume_pointer`].
     pub fn is_pointer_over_area(&self) -> bool {
-        let pointer_pos = self.input(|i| i.pointer.interact_pos());
+        let (pointer_pos, pointer_consumed) =
+            self.input(|i| (i.pointer.interact_pos(), i.is_pointer_consumed()));
         if let Some(pointer_pos) = pointer_pos {
-            if let Some(layer) = self.layer_id_at(pointer_pos) {
-                if layer.order == Order::Background {
-                    !self.frame_state(|state| state.unused_rect.contains(pointer_pos))
-                } else {
-                    true
-                }
-            } else {
-                false
+            !pointer_consumed && self.is_over_area(pointer_pos)
+        } else {
+            false
+        }
+    }
+
+    /// Is the given position over any egui area (e.g. a [`Window`] or a panel) in the current viewport?
+    ///
+    /// Useful when egui is embedded in e.g. a game engine that needs to know which parts of the screen egui covers.
+    pub fn is_over_area(&self, pos: Pos2) -> bool {
+        if let Some(layer) = self.layer_id_at(pos) {
+            if layer.order == Order::Background {
+                !self.frame_state(|state| state.unused_rect.contains(pos))
+            } else {
+                true
             }
         } else {
             false
@@ -1897,6 +2511,12 @@ impl Context {
             || (self.is_pointer_over_area() && !self.input(|i| i.pointer.any_down()))
     }
 
+    /// Like [`Self::wants_pointer_input`], but for a pointer at the given position,
+    /// e.g. to decide where to send a mouse click that happened in-between frames.
+    pub fn wants_pointer_input_at(&self, pos: Pos2) -> bool {
+        self.is_using_pointer() || (self.is_over_area(pos) && !self.input(|i| i.pointer.any_down()))
+    }
+
     /// Is egui currently using the pointer position (e.g. dragging a slider)?
     ///
     /// NOTE: this will return `false` if the pointer is just hovering over an egui area.
@@ -1990,7 +2610,13 @@ impl Context {
 
     pub(crate) fn rect_contains_pointer(&self, layer_id: LayerId, rect: Rect) -> bool {
         rect.is_positive() && {
-            let pointer_pos = self.input(|i| i.pointer.interact_pos());
+            let pointer_pos = self.input(|i| {
+                if i.is_pointer_consumed() {
+                    None
+                } else {
+                    i.pointer.interact_pos()
+                }
+            });
             if let Some(pointer_pos) = pointer_pos {
                 rect.contains(pointer_pos) && self.layer_id_at(pointer_pos) == Some(layer_id)
             } else {
@@ -2012,6 +2638,120 @@ impl Context {
     pub fn set_debug_on_hover(&self, debug_on_hover: bool) {
         self.style_mut(|style| style.debug.debug_on_hover = debug_on_hover);
     }
+
+    /// Open or close the widget inspector.
+    ///
+    /// The inspector highlights the widget under the pointer and shows its [`Id`], rectangle,
+    /// [`Sense`], layer and the source location that allocated it.
+    /// It also shows a window listing all layers of the current viewport and the widgets in them.
+    ///
+    /// The source location is that of the code calling [`Ui::add`] or one of its shortcuts,
+    /// like [`Ui::button`]. Widgets that are not added that way, like the parts of a composite widget,
+    /// show where they called [`Ui::interact`] or one of the `Ui::allocate_*` functions.
+    ///
+    /// In debug builds the inspector can also be toggled with [`crate::inspector::SHORTCUT`],
+    /// see [`crate::Options::inspector_shortcut`].
+    pub fn set_inspector(&self, open: bool) {
+        self.write(|ctx| ctx.inspector = open);
+    }
+
+    /// Is the inspector from [`Self::set_inspector`] open?
+    pub fn inspector(&self) -> bool {
+        self.read(|ctx| ctx.inspector)
+    }
+
+    /// Record the code that added the widget with this id, for the inspector.
+    ///
+    /// Called by [`Ui::add`], since [`Self::interact`] is usually called from inside the widget.
+    pub(crate) fn set_inspector_location(
+        &self,
+        id: Id,
+        location: &'static std::panic::Location<'static>,
+    ) {
+        if self.inspector() {
+            self.write(|ctx| {
+                let widgets = &mut ctx.viewport().frame_state.inspector_widgets;
+                if let Some(widget) = widgets.iter_mut().rev().find(|widget| widget.id == id) {
+                    widget.location = location;
+                }
+            });
+        }
+    }
+}
+
+/// ## Progress
+impl Context {
+    /// Report the progress of a long-running task, e.g. a background job.
+    ///
+    /// The task is shown by [`crate::ProgressStatus`] in the current viewport,
+    /// and on the taskbar entry of its window (see [`crate::Options::taskbar_progress`]),
+    /// until [`crate::Progress::finish`] is called.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// let progress = ctx.progress("compress");
+    /// progress.set(0.7, "Compressing…");
+    /// // …
+    /// progress.finish();
+    /// ```
+    ///
+    /// The returned [`crate::Progress`] can be sent to the thread doing the work.
+    /// Updating it requests a repaint of the viewport.
+    pub fn progress(&self, task_id: impl std::hash::Hash) -> crate::Progress {
+        crate::Progress::new(self.clone(), Id::new(task_id), self.viewport_id())
+    }
+
+    /// All tasks reported with [`Self::progress`] that haven't finished yet, oldest first.
+    pub fn progress_tasks(&self) -> Vec<crate::TaskProgress> {
+        self.read(|ctx| ctx.progress.tasks.clone())
+    }
+
+    /// The combined progress of all unfinished tasks of the given viewport,
+    /// or `None` if there are none.
+    pub fn aggregated_progress(
+        &self,
+        viewport_id: ViewportId,
+    ) -> Option<crate::AggregatedProgress> {
+        self.read(|ctx| ctx.progress.aggregated(viewport_id))
+    }
+
+    /// Returns `true` if anything changed.
+    pub(crate) fn set_task_progress(&self, task: crate::TaskProgress) -> bool {
+        self.write(|ctx| ctx.progress.set(task))
+    }
+
+    pub(crate) fn remove_task_progress(&self, id: Id) {
+        self.write(|ctx| ctx.progress.remove(id));
+    }
+
+    /// Send [`ViewportCommand::TaskbarProgress`] to the current viewport if its progress changed.
+    fn update_taskbar_progress(&self) {
+        let viewport_id = self.viewport_id();
+        let command = self.write(|ctx| {
+            let taskbar_progress = ctx
+                .progress
+                .aggregated(viewport_id)
+                .map_or(crate::TaskbarProgress::None, |p| p.taskbar_progress());
+            let shown = ctx
+                .progress
+                .taskbar
+                .get(&viewport_id)
+                .copied()
+                .unwrap_or_default();
+            if taskbar_progress == shown {
+                return None;
+            }
+            if taskbar_progress == crate::TaskbarProgress::None {
+                ctx.progress.taskbar.remove(&viewport_id);
+            } else {
+                ctx.progress.taskbar.insert(viewport_id, taskbar_progress);
+            }
+            Some(ViewportCommand::TaskbarProgress(taskbar_progress))
+        });
+        if let Some(command) = command {
+            self.send_viewport_cmd_to(viewport_id, command);
+        }
+    }
 }
 
 /// ## Animation
@@ -2095,6 +2835,17 @@ impl Context {
                 if tessellation_options != prev_tessellation_options {
                     self.tessellation_options_mut(move |o| *o = tessellation_options);
                 }
+
+                let mut distance_field_text = self.options(|o| o.distance_field_text);
+                if ui
+                    .checkbox(&mut distance_field_text, "Distance field text")
+                    .on_hover_text(
+                        "Keeps text crisp when it is scaled up. Requires backend support.",
+                    )
+                    .changed()
+                {
+                    self.options_mut(|o| o.distance_field_text = distance_field_text);
+                }
             });
     }
 
@@ -2133,6 +2884,18 @@ impl Context {
             .map_or_else(String::new, |layer| layer.short_debug_format());
         ui.label(format!("Top layer under mouse: {top_layer}"));
 
+        let mut inspector = self.inspector();
+        if ui
+            .checkbox(&mut inspector, "Widget inspector")
+            .on_hover_text(format!(
+                "Inspect the widget under the pointer ({})",
+                self.format_shortcut(&crate::inspector::SHORTCUT)
+            ))
+            .changed()
+        {
+            self.set_inspector(inspector);
+        }
+
         ui.add_space(16.0);
 
         ui.label(format!(
@@ -2165,8 +2928,17 @@ impl Context {
         CollapsingHeader::new("🔠 Font texture")
             .default_open(false)
             .show(ui, |ui| {
-                let font_image_size = self.fonts(|f| f.font_image_size());
-                crate::introspection::font_texture_ui(ui, font_image_size);
+                let stats = self.fonts(|f| f.font_atlas_stats());
+                ui.label(format!(
+                    "{:.0}% full (height {} of at most {} texels), {} glyphs in {} font sizes, {} evictions",
+                    100.0 * stats.atlas.fill_ratio,
+                    stats.atlas.size[1],
+                    stats.atlas.max_height,
+                    stats.num_glyphs,
+                    stats.num_font_sizes,
+                    stats.num_evictions,
+                ));
+                crate::introspection::font_texture_ui(ui, stats.atlas.size);
             });
     }
 
@@ -2238,9 +3010,11 @@ impl Context {
             self.memory_mut(|mem| *mem = Default::default());
         }
 
-        let (num_state, num_serialized) = self.data(|d| (d.len(), d.count_serialized()));
+        let stats = self.memory(|mem| mem.stats());
+        let num_serialized = self.data(|d| d.count_serialized());
         ui.label(format!(
-            "{num_state} widget states stored (of which {num_serialized} are serialized)."
+            "{} widget states stored (of which {num_serialized} are serialized).",
+            stats.data
         ));
 
         ui.horizontal(|ui| {
@@ -2337,6 +3111,316 @@ impl Context {
     }
 }
 
+/// Where a [`Context::begin_recording`] started.
+pub(crate) struct RecordingStart {
+    layer_id: LayerId,
+    num_shapes: usize,
+    num_overlay_shapes: usize,
+    num_layer_rects: usize,
+    num_used_ids: usize,
+    owns_used_ids_log: bool,
+    num_focus_interest: usize,
+    owns_focus_interest_log: bool,
+    num_used_data: usize,
+    owns_usage_log: bool,
+    num_repaint_requests: u64,
+}
+
+/// Everything a [`crate::Ui::retained`] block added to the frame.
+#[derive(Clone)]
+pub(crate) struct Recording {
+    pub layer_id: LayerId,
+
+    /// The shapes painted to [`Self::layer_id`].
+    pub shapes: crate::layers::PaintList,
+
+    /// The shapes painted on top of [`Self::layer_id`], e.g. by [`crate::Response::badge`].
+    pub overlay_shapes: crate::layers::PaintList,
+
+    /// The interactive rectangles, used for hit-testing next frame.
+    pub layer_rects: Vec<(Id, Rect)>,
+
+    /// All ids passed to [`Context::check_for_id_clash`].
+    pub used_ids: Vec<(Id, Rect)>,
+
+    /// The widgets that are interested in keyboard focus, in order, so that Tab can move into the block.
+    pub focus_interest: Vec<Id>,
+
+    /// The values in [`crate::Memory::data`] that were read or written,
+    /// so that they aren't garbage collected while the block is replayed.
+    pub used_data: Vec<u64>,
+
+    /// Did the contents request a repaint (e.g. because of an animation)?
+    pub requested_repaint: bool,
+}
+
+/// ## Retained mode
+impl Context {
+    /// Start recording what is added to the given layer.
+    ///
+    /// Recordings can be nested.
+    pub(crate) fn begin_recording(&self, layer_id: LayerId) -> RecordingStart {
+        self.write(|ctx| {
+            let (num_used_data, owns_usage_log) = ctx.memory.data.begin_usage_log();
+
+            let focus = &mut ctx.memory.interaction_mut().focus;
+            let owns_focus_interest_log = focus.interest_log.is_none();
+            let num_focus_interest = focus.interest_log.get_or_insert_with(Vec::new).len();
+
+            let viewport = ctx.viewport();
+            let num_shapes = viewport.graphics.list(layer_id).len();
+            let num_overlay_shapes = viewport.graphics.overlay_list(layer_id).len();
+            let num_layer_rects = viewport
+                .layer_rects_this_frame
+                .get(&layer_id)
+                .map_or(0, |rects| rects.len());
+            let owns_used_ids_log = viewport.frame_state.used_ids_log.is_none();
+            let log = viewport
+                .frame_state
+                .used_ids_log
+                .get_or_insert_with(Vec::new);
+            RecordingStart {
+                layer_id,
+                num_shapes,
+                num_overlay_shapes,
+                num_layer_rects,
+                num_used_ids: log.len(),
+                owns_used_ids_log,
+                num_focus_interest,
+                owns_focus_interest_log,
+                num_used_data,
+                owns_usage_log,
+                num_repaint_requests: viewport.repaint.num_requests,
+            }
+        })
+    }
+
+    /// Finish a recording started with [`Self::begin_recording`].
+    pub(crate) fn end_recording(&self, start: &RecordingStart) -> Recording {
+        let RecordingStart {
+            layer_id,
+            num_shapes,
+            num_overlay_shapes,
+            num_layer_rects,
+            num_used_ids,
+            owns_used_ids_log,
+            num_focus_interest,
+            owns_focus_interest_log,
+            num_used_data,
+            owns_usage_log,
+            num_repaint_requests,
+        } = *start;
+
+        self.write(|ctx| {
+            let used_data = ctx.memory.data.end_usage_log(num_used_data, owns_usage_log);
+
+            let focus = &mut ctx.memory.interaction_mut().focus;
+            let focus_interest = if owns_focus_interest_log {
+                focus.interest_log.take().unwrap_or_default()
+            } else {
+                focus
+                    .interest_log
+                    .as_ref()
+                    .and_then(|log| log.get(num_focus_interest..))
+                    .unwrap_or_default()
+                    .to_vec()
+            };
+
+            let viewport = ctx.viewport();
+            let shapes = viewport.graphics.list(layer_id).tail(num_shapes);
+            let overlay_shapes = viewport
+                .graphics
+                .overlay_list(layer_id)
+                .tail(num_overlay_shapes);
+            let layer_rects = viewport
+                .layer_rects_this_frame
+                .get(&layer_id)
+                .and_then(|rects| rects.get(num_layer_rects..))
+                .unwrap_or_default()
+                .to_vec();
+            let used_ids = if owns_used_ids_log {
+                viewport.frame_state.used_ids_log.take().unwrap_or_default()
+            } else {
+                viewport
+                    .frame_state
+                    .used_ids_log
+                    .as_ref()
+                    .and_then(|log| log.get(num_used_ids..))
+                    .unwrap_or_default()
+                    .to_vec()
+            };
+            Recording {
+                layer_id,
+                shapes,
+                overlay_shapes,
+                layer_rects,
+                used_ids,
+                focus_interest,
+                used_data,
+                requested_repaint: viewport.repaint.num_requests != num_repaint_requests,
+            }
+        })
+    }
+
+    /// Undo everything added to the frame since [`Self::begin_recording`].
+    ///
+    /// Used for sizing passes, see [`crate::Ui::measure_then_place`].
+    pub(crate) fn discard_recording(&self, start: &RecordingStart) {
+        let RecordingStart {
+            layer_id,
+            num_shapes,
+            num_overlay_shapes,
+            num_layer_rects,
+            num_used_ids,
+            owns_used_ids_log,
+            num_focus_interest,
+            owns_focus_interest_log,
+            num_used_data,
+            owns_usage_log,
+            num_repaint_requests: _,
+        } = *start;
+
+        self.write(|ctx| {
+            // The values were still used, so an enclosing recording should keep them:
+            ctx.memory.data.end_usage_log(num_used_data, owns_usage_log);
+
+            let focus = &mut ctx.memory.interaction_mut().focus;
+            if owns_focus_interest_log {
+                focus.interest_log = None;
+            } else if let Some(log) = &mut focus.interest_log {
+                log.truncate(num_focus_interest);
+            }
+
+            let viewport = ctx.viewport();
+            viewport.graphics.list(layer_id).truncate(num_shapes);
+            viewport
+                .graphics
+                .overlay_list(layer_id)
+                .truncate(num_overlay_shapes);
+            if let Some(rects) = viewport.layer_rects_this_frame.get_mut(&layer_id) {
+                rects.truncate(num_layer_rects);
+            }
+
+            let frame_state = &mut viewport.frame_state;
+            let Some(log) = &mut frame_state.used_ids_log else {
+                return;
+            };
+            for (id, _) in log.drain(num_used_ids..) {
+                frame_state.used_ids.remove(&id);
+            }
+            if owns_used_ids_log {
+                frame_state.used_ids_log = None;
+            }
+        });
+    }
+
+    /// Add everything in the recording to this frame again, translated by `delta`.
+    pub(crate) fn replay_recording(&self, recording: &Recording, delta: Vec2) {
+        self.write(|ctx| {
+            ctx.memory.data.mark_logged_used(&recording.used_data);
+            for &id in &recording.focus_interest {
+                ctx.memory.interested_in_focus(id);
+            }
+
+            let viewport = ctx.viewport();
+
+            let mut shapes = recording.shapes.clone();
+            shapes.translate(delta);
+            viewport
+                .graphics
+                .list(recording.layer_id)
+                .append(&mut shapes);
+
+            let mut overlay_shapes = recording.overlay_shapes.clone();
+            overlay_shapes.translate(delta);
+            viewport
+                .graphics
+                .overlay_list(recording.layer_id)
+                .append(&mut overlay_shapes);
+
+            viewport
+                .layer_rects_this_frame
+                .entry(recording.layer_id)
+                .or_default()
+                .extend(
+                    recording
+                        .layer_rects
+                        .iter()
+                        .map(|&(id, rect)| (id, rect.translate(delta))),
+                );
+
+            let frame_state = &mut viewport.frame_state;
+            for &(id, rect) in &recording.used_ids {
+                let rect = rect.translate(delta);
+                frame_state.used_ids.insert(id, rect);
+                if let Some(log) = &mut frame_state.used_ids_log {
+                    log.push((id, rect));
+                }
+            }
+        });
+    }
+
+    /// Is the user interacting with anything in the recording,
+    /// or could they start to this frame?
+    ///
+    /// If so, the recording is out of date, or soon will be.
+    ///
+    /// Keyboard input goes to the widget with keyboard focus, so that is covered
+    /// by checking if a widget in the recording has (or just lost) the focus.
+    pub(crate) fn is_recording_active(&self, recording: &Recording, delta: Vec2) -> bool {
+        if recording.layer_rects.is_empty() && recording.used_ids.is_empty() {
+            return false;
+        }
+
+        self.write(|ctx| {
+            #[cfg(feature = "accesskit")]
+            if ctx.is_accesskit_enabled {
+                return true; // The accesskit nodes are not recorded
+            }
+
+            if ctx.inspector {
+                return true;
+            }
+
+            let memory = &ctx.memory;
+            let interaction = memory.interaction();
+            let is_active_id = |id: Id| {
+                memory.has_focus(id)
+                    || memory.had_focus_last_frame(id)
+                    || interaction.drag_id == Some(id)
+                    || interaction.click_id == Some(id)
+            };
+            if recording.used_ids.iter().any(|&(id, _)| is_active_id(id))
+                || recording
+                    .layer_rects
+                    .iter()
+                    .any(|&(id, _)| is_active_id(id))
+            {
+                return true;
+            }
+
+            let is_over_recording = |pos: Pos2| {
+                recording
+                    .layer_rects
+                    .iter()
+                    .any(|&(_, rect)| rect.translate(delta).contains(pos))
+            };
+            let input = &ctx.viewport().input;
+            input
+                .pointer
+                .interact_pos()
+                .map_or(false, is_over_recording)
+                || input.events.iter().any(|event| match event {
+                    Event::PointerMoved(pos)
+                    | Event::PointerButton { pos, .. }
+                    | Event::Touch { pos, .. }
+                    | Event::Pen(PenInput { pos, .. }) => is_over_recording(*pos),
+                    _ => false,
+                })
+        })
+    }
+}
+
 /// ## Accessibility
 impl Context {
     /// Call the provided function with the given ID pushed on the stack of
@@ -2519,6 +3603,27 @@ impl Context {
         }
     }
 
+    /// Upload egui's own textures again, e.g. after the graphics context was lost.
+    ///
+    /// This re-uploads the font atlas, and re-creates the textures of images
+    /// loaded with [`Ui::image`] or [`Image`] (from their cached image data).
+    ///
+    /// egui can't restore textures you created yourself with [`Self::load_texture`],
+    /// so you need to re-create those.
+    pub fn reupload_textures(&self) {
+        crate::profile_function!();
+
+        self.read(|ctx| {
+            for fonts in ctx.fonts.values() {
+                fonts.texture_atlas().lock().mark_all_dirty();
+            }
+        });
+
+        for loader in self.loaders().texture.lock().iter() {
+            loader.forget_all();
+        }
+    }
+
     /// Try loading the bytes from the given uri using any available bytes loaders.
     ///
     /// Loaders are expected to cache results, so that this call is immediate-mode safe.
@@ -2652,6 +3757,50 @@ impl Context {
         self.read(|ctx| ctx.viewport_id())
     }
 
+    /// Namespace the given [`Id`] by the current viewport.
+    ///
+    /// This is used for the [`Id`] of every top-level [`Ui`], so that the same ui shown in
+    /// two different viewports doesn't share state (collapsing headers, scroll offsets, …).
+    ///
+    /// The [`Id`] is returned unchanged in the root viewport,
+    /// and when [`crate::Options::namespace_ids_by_viewport`] is `false`.
+    pub fn viewport_local_id(&self, id: Id) -> Id {
+        let viewport_id = self.viewport_id();
+        if viewport_id == ViewportId::ROOT || !self.options(|o| o.namespace_ids_by_viewport) {
+            id
+        } else {
+            id.with(viewport_id)
+        }
+    }
+
+    /// Forward key events that no viewport handled to the given viewport.
+    ///
+    /// Keyboard events only go to the viewport whose native window has OS focus.
+    /// This lets you implement app-wide shortcuts (e.g. `Ctrl-S`) in one place:
+    /// any [`Event::Key`] that is still in [`InputState::events`] at the end of the frame of
+    /// another viewport (i.e. not removed with [`InputState::consume_key`] and not received
+    /// while a widget had keyboard focus) is delivered to `viewport_id` at the start of its next frame.
+    ///
+    /// Set to `None` (the default) to turn this off.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.route_unhandled_keys_to(Some(egui::ViewportId::ROOT));
+    ///
+    /// // In the root viewport:
+    /// if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::S)) {
+    ///     // save, no matter which window had focus
+    /// }
+    /// ```
+    pub fn route_unhandled_keys_to(&self, viewport_id: Option<ViewportId>) {
+        self.write(|ctx| ctx.unhandled_keys_target = viewport_id);
+    }
+
+    /// See [`Self::route_unhandled_keys_to`].
+    pub fn unhandled_keys_target(&self) -> Option<ViewportId> {
+        self.read(|ctx| ctx.unhandled_keys_target)
+    }
+
     /// Return the `ViewportId` of his parent.
     ///
     /// If this is the root viewport, this will return [`ViewportId::ROOT`].
@@ -2661,6 +3810,133 @@ impl Context {
         self.read(|ctx| ctx.parent_viewport_id())
     }
 
+    /// For integrations: call this right after presenting a frame of the given viewport on screen.
+    ///
+    /// `time` must use the same clock as [`RawInput::time`].
+    /// Together with [`RawInput::event_times`] this is used to calculate the [`Self::latency_stats`].
+    pub fn on_frame_presented(&self, viewport_id: ViewportId, time: f64) {
+        self.write(|ctx| {
+            if let Some(viewport) = ctx.viewports.get_mut(&viewport_id) {
+                let latency = &mut viewport.latency;
+                if let Some(input_time) = latency.unpresented_input_time.take() {
+                    latency.history.add(time, (time - input_time) as f32);
+                }
+                latency.history.flush(time);
+            }
+        });
+    }
+
+    /// Show a small always-on-top graph of how long the latest frames took,
+    /// split into the parts given by [`FrameTimings`].
+    ///
+    /// The timings must be provided by the integration (e.g. `eframe`),
+    /// using [`Self::report_frame_timings`].
+    /// Unlike the `puffin` profiler, this requires no extra dependencies or setup.
+    pub fn set_profiler_overlay(&self, show: bool) {
+        self.write(|ctx| ctx.profiler_overlay = show);
+    }
+
+    /// Is the overlay from [`Self::set_profiler_overlay`] shown?
+    pub fn profiler_overlay(&self) -> bool {
+        self.read(|ctx| ctx.profiler_overlay)
+    }
+
+    /// For integrations: call this once per frame of the given viewport,
+    /// with how long the different parts of the frame took.
+    ///
+    /// See [`Self::set_profiler_overlay`].
+    pub fn report_frame_timings(&self, viewport_id: ViewportId, timings: FrameTimings) {
+        self.write(|ctx| {
+            if let Some(viewport) = ctx.viewports.get_mut(&viewport_id) {
+                let frame_timings = &mut viewport.frame_timings;
+                if frame_timings.len() == crate::profiler_overlay::MAX_FRAMES {
+                    frame_timings.pop_front();
+                }
+                frame_timings.push_back(timings);
+            }
+        });
+    }
+
+    /// How long it takes from receiving input until the result is presented on screen, per viewport.
+    ///
+    /// This is useful to diagnose slow frames.
+    /// Only viewports whose integration calls [`Self::on_frame_presented`]
+    /// and sets [`RawInput::event_times`] will have any samples.
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// for (viewport_id, stats) in ctx.latency_stats() {
+    ///     if let Some(average) = stats.average {
+    ///         println!("{viewport_id:?}: {:.1} ms", 1e3 * average);
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub fn latency_stats(&self) -> ViewportIdMap<LatencyStats> {
+        self.read(|ctx| {
+            ctx.viewports
+                .iter()
+                .map(|(id, viewport)| (*id, viewport.latency.stats()))
+                .collect()
+        })
+    }
+
+    /// Start writing the [`RawInput`] of every frame, for all viewports, to the given file.
+    ///
+    /// The file can later be played back with [`Self::replay_input`],
+    /// e.g. to reproduce a bug or to benchmark a specific interaction.
+    /// Any previous recording is stopped.
+    ///
+    /// With `eframe` you can also set the `EFRAME_RECORD_INPUT` environment variable to a path.
+    #[cfg(feature = "persistence")]
+    pub fn record_input(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let recorder = crate::input_recording::InputRecorder::create(path.as_ref())?;
+        self.write(|ctx| ctx.input_recorder = Some(recorder));
+        Ok(())
+    }
+
+    /// Stop the recording started with [`Self::record_input`], if any.
+    #[cfg(feature = "persistence")]
+    pub fn stop_recording_input(&self) {
+        self.write(|ctx| ctx.input_recorder = None);
+    }
+
+    /// Is [`Self::record_input`] recording?
+    #[cfg(feature = "persistence")]
+    pub fn is_recording_input(&self) -> bool {
+        self.read(|ctx| ctx.input_recorder.is_some())
+    }
+
+    /// Replay a file written by [`Self::record_input`].
+    ///
+    /// From the next frame on, the input given to [`Self::run`] is replaced by the recorded input,
+    /// frame by frame, until the recording runs out.
+    /// This includes the recorded time, so animations play out the same way.
+    /// egui requests repaints so that the integration keeps running frames during the replay,
+    /// and asks the integration to resize the window to the recorded size.
+    ///
+    /// Live events are ignored during the replay.
+    /// For the replay to be deterministic the app must start from the same state as the recording.
+    ///
+    /// With `eframe` you can also set the `EFRAME_REPLAY_INPUT` environment variable to a path.
+    #[cfg(feature = "persistence")]
+    pub fn replay_input(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let replay = crate::input_recording::InputReplay::load(path.as_ref())?;
+        self.write(|ctx| {
+            if let Some(viewport_id) = replay.next_viewport() {
+                ctx.request_repaint(viewport_id);
+            }
+            ctx.input_replay = (!replay.is_done()).then_some(replay);
+        });
+        Ok(())
+    }
+
+    /// Is a recording from [`Self::replay_input`] being played back?
+    #[cfg(feature = "persistence")]
+    pub fn is_replaying_input(&self) -> bool {
+        self.read(|ctx| ctx.input_replay.is_some())
+    }
+
     /// For integrations: Set this to render a sync viewport.
     ///
     /// This will only be set the callback for the current thread,
@@ -2706,6 +3982,26 @@ impl Context {
         self.send_viewport_cmd_to(self.viewport_id(), command);
     }
 
+    /// Lock (or unlock) the mouse pointer to the given viewport.
+    ///
+    /// While locked, the cursor is hidden and grabbed by the window,
+    /// and you should read the unbounded mouse motion from [`crate::PointerState::motion`].
+    /// This is useful for e.g. an orbit or fly camera in a 3D view.
+    ///
+    /// This is a shortcut for sending [`ViewportCommand::CursorGrab`] and [`ViewportCommand::CursorIcon`].
+    pub fn set_pointer_lock(&self, viewport_id: ViewportId, locked: bool) {
+        if locked {
+            self.send_viewport_cmd_to(viewport_id, ViewportCommand::CursorGrab(CursorGrab::Locked));
+            self.send_viewport_cmd_to(
+                viewport_id,
+                ViewportCommand::CursorIcon(Some(CursorIcon::None)),
+            );
+        } else {
+            self.send_viewport_cmd_to(viewport_id, ViewportCommand::CursorGrab(CursorGrab::None));
+            self.send_viewport_cmd_to(viewport_id, ViewportCommand::CursorIcon(None));
+        }
+    }
+
     /// Send a command to a speicfic viewport.
     ///
     /// This lets you affect another viewport, e.g. resizing its window.
@@ -2716,7 +4012,13 @@ impl Context {
             self.request_repaint_of(self.parent_viewport_id());
         }
 
-        self.write(|ctx| ctx.viewport_for(id).commands.push(command));
+        self.write(|ctx| {
+            let viewport = ctx.viewport_for(id);
+            if let ViewportCommand::CursorIcon(cursor_icon) = command {
+                viewport.cursor_icon_override = cursor_icon;
+            }
+            viewport.commands.push(command);
+        });
     }
 
     /// Show a deferred viewport, creating a new native window, if possible.
@@ -2859,4 +4161,342 @@ impl Context {
 fn context_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Context>();
+    assert_send_sync::<Arc<DeferredViewportUiCallback>>();
+    assert_send_sync::<crate::Painter>();
+}
+
+#[test]
+fn test_route_unhandled_keys() {
+    let ctx = Context::default();
+    ctx.route_unhandled_keys_to(Some(ViewportId::ROOT));
+
+    let child = ViewportId::from_hash_of("child");
+    let key_s = Event::Key {
+        key: Key::S,
+        physical_key: None,
+        scancode: None,
+        pressed: true,
+        repeat: false,
+        modifiers: Modifiers::COMMAND,
+    };
+
+    let mut raw_input = RawInput {
+        viewport_id: child,
+        events: vec![key_s],
+        ..Default::default()
+    };
+    raw_input.viewports.insert(child, Default::default());
+    let _ = ctx.run(raw_input, |_ctx| {});
+
+    let mut saved = false;
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        saved = ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::S));
+    });
+    assert!(
+        saved,
+        "The key should have been routed to the root viewport"
+    );
+
+    let mut saved_again = false;
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        saved_again = ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::S));
+    });
+    assert!(!saved_again, "Routed keys should only be delivered once");
+}
+
+#[test]
+fn test_touch_mode() {
+    let ctx = Context::default();
+    let pos = pos2(10.0, 10.0);
+    let touch = Event::Touch {
+        device_id: TouchDeviceId(0),
+        id: TouchId(0),
+        phase: TouchPhase::Start,
+        pos,
+        force: None,
+    };
+
+    let mut touch_mode = None;
+    let mut run = |events: Vec<Event>| {
+        let raw_input = RawInput {
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(raw_input, |ctx| touch_mode = Some(ctx.is_touch_mode()));
+        touch_mode.unwrap()
+    };
+
+    assert!(!run(vec![]));
+    assert!(run(vec![touch.clone(), Event::PointerMoved(pos)]));
+    assert!(
+        run(vec![]),
+        "Touch mode should stick after the finger is lifted"
+    );
+    assert!(
+        !run(vec![Event::PointerMoved(pos)]),
+        "Mouse turns off touch mode"
+    );
+
+    ctx.style_mut(|style| style.interaction.touch_mode = crate::style::TouchMode::Off);
+    assert!(!run(vec![touch]));
+}
+
+#[test]
+fn test_orientation_changed() {
+    let ctx = Context::default();
+    let run = |size: Vec2| {
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, size)),
+            ..Default::default()
+        };
+        let mut changed = None;
+        let _ = ctx.run(raw_input, |ctx| {
+            changed = Some(ctx.input(|i| (i.orientation(), i.orientation_changed())));
+        });
+        changed.unwrap()
+    };
+
+    let portrait = vec2(400.0, 800.0);
+    let landscape = vec2(800.0, 400.0);
+    assert_eq!(run(portrait), (crate::Orientation::Portrait, false));
+    assert_eq!(run(portrait), (crate::Orientation::Portrait, false));
+    assert_eq!(run(landscape), (crate::Orientation::Landscape, true));
+    assert_eq!(run(landscape), (crate::Orientation::Landscape, false));
+}
+
+#[test]
+fn test_latency_stats() {
+    let ctx = Context::default();
+    let run = |time: f64, event_times: Vec<f64>| {
+        let raw_input = RawInput {
+            time: Some(time),
+            events: vec![Event::PointerGone, Event::PointerGone],
+            event_times,
+            ..Default::default()
+        };
+        let _ = ctx.run(raw_input, |ctx| {
+            // Events without a known time get the frame time:
+            let times: Vec<Option<f64>> =
+                ctx.input(|i| i.raw.events_with_times().map(|(_, t)| t).collect());
+            assert_eq!(times.len(), 2);
+            assert!(times.iter().all(Option::is_some));
+        });
+    };
+
+    run(1.0, vec![]);
+    ctx.on_frame_presented(ViewportId::ROOT, 1.01);
+    assert_eq!(ctx.latency_stats()[&ViewportId::ROOT].num_samples, 0);
+
+    run(2.0, vec![1.99]);
+    ctx.on_frame_presented(ViewportId::ROOT, 2.02);
+    let stats = ctx.latency_stats()[&ViewportId::ROOT];
+    assert_eq!(stats.num_samples, 1);
+    assert!((stats.latest.unwrap() - 0.03).abs() < 1e-4);
+}
+
+#[test]
+fn test_raw_input_append_keeps_event_times() {
+    let mut raw = RawInput {
+        time: Some(1.0),
+        events: vec![Event::PointerGone],
+        ..Default::default()
+    };
+    raw.append(RawInput {
+        time: Some(2.0),
+        events: vec![Event::PointerGone],
+        event_times: vec![1.5],
+        ..Default::default()
+    });
+    assert_eq!(raw.event_times, vec![1.0, 1.5]);
+    assert_eq!(raw.oldest_event_time(), Some(1.0));
+}
+
+#[test]
+fn test_profiler_overlay() {
+    let ctx = Context::default();
+    ctx.set_profiler_overlay(true);
+
+    for _ in 0..3 {
+        let _ = ctx.run(Default::default(), |_ctx| {});
+        ctx.report_frame_timings(
+            ViewportId::ROOT,
+            FrameTimings {
+                update: 0.005,
+                paint: 0.002,
+                ..Default::default()
+            },
+        );
+    }
+
+    let output = ctx.run(Default::default(), |_ctx| {});
+    assert!(!output.shapes.is_empty(), "The overlay should be painted");
+}
+
+#[test]
+fn test_inspector() {
+    let ctx = Context::default();
+    ctx.options_mut(|o| o.inspector_shortcut = true);
+
+    let press_shortcut = || RawInput {
+        modifiers: Modifiers::COMMAND.plus(Modifiers::SHIFT),
+        events: vec![Event::Key {
+            key: Key::I,
+            physical_key: None,
+            scancode: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::COMMAND.plus(Modifiers::SHIFT),
+        }],
+        ..Default::default()
+    };
+
+    let _ = ctx.run(press_shortcut(), |_ctx| {});
+    assert!(ctx.inspector());
+
+    let mut num_widgets = 0;
+    let mut button_line = 0;
+    let mut recorded_line = None;
+    let _ = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            button_line = line!() + 1;
+            let button = ui.button("Inspect me");
+            recorded_line = ctx.frame_state(|fs| {
+                fs.inspector_widgets
+                    .iter()
+                    .find(|widget| widget.id == button.id)
+                    .map(|widget| (widget.location.file(), widget.location.line()))
+            });
+        });
+        num_widgets = ctx.frame_state(|fs| fs.inspector_widgets.len());
+    });
+    assert!(
+        num_widgets > 0,
+        "Widgets should be recorded while inspecting"
+    );
+    assert_eq!(
+        recorded_line,
+        Some((file!(), button_line)),
+        "The location should be the code adding the widget"
+    );
+
+    let _ = ctx.run(press_shortcut(), |_ctx| {});
+    assert!(!ctx.inspector());
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn test_record_and_replay_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("input_recording.ron");
+    let frame = |time: f64| RawInput {
+        time: Some(time),
+        events: vec![Event::Text(format!("frame {time}"))],
+        ..Default::default()
+    };
+
+    let ctx = Context::default();
+    ctx.record_input(&path).unwrap();
+    for i in 0..3 {
+        let _ = ctx.run(frame(i as f64), |_ctx| {});
+    }
+    ctx.stop_recording_input();
+
+    let ctx = Context::default();
+    ctx.replay_input(&path).unwrap();
+    assert!(ctx.is_replaying_input());
+    let mut events = vec![];
+    for _ in 0..3 {
+        // Live input is ignored during the replay:
+        let _ = ctx.run(frame(100.0), |ctx| {
+            ctx.input(|i| events.extend(i.events.iter().cloned()));
+        });
+    }
+    assert!(!ctx.is_replaying_input());
+
+    assert_eq!(
+        events,
+        vec![
+            Event::Text("frame 0".to_owned()),
+            Event::Text("frame 1".to_owned()),
+            Event::Text("frame 2".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn test_consume_pointer() {
+    let ctx = Context::default();
+    let pos = pos2(50.0, 50.0);
+    let game_rect = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+
+    let run = |consume: bool, events: Vec<Event>| {
+        let mut hovered = false;
+        let _ = ctx.run(
+            RawInput {
+                events,
+                ..Default::default()
+            },
+            |ctx| {
+                if consume {
+                    assert!(ctx.input_mut(|i| i.consume_pointer(game_rect)));
+                }
+                CentralPanel::default().show(ctx, |ui| {
+                    hovered = ui
+                        .interact(game_rect, Id::new("button"), Sense::click())
+                        .hovered();
+                });
+                assert_eq!(ctx.wants_pointer_input(), !consume);
+            },
+        );
+        hovered
+    };
+
+    assert!(run(false, vec![Event::PointerMoved(pos)]));
+    assert!(!run(true, vec![]));
+    assert!(ctx.wants_pointer_input_at(pos));
+    assert!(ctx.is_over_area(pos));
+}
+
+#[test]
+fn test_viewport_local_id() {
+    let child = ViewportId::from_hash_of("child");
+
+    // Count the frames each viewport showed the same ui in,
+    // and return the ids of the ui and of a window in it.
+    let run = |ctx: &Context, viewport_id: ViewportId| {
+        let mut ids = None;
+        let input = crate::RawInputBuilder::new()
+            .viewport_id(viewport_id)
+            .build();
+        let _ = ctx.run(input, |ctx| {
+            TopBottomPanel::top("top").show(ctx, |ui| {
+                ui.data_mut(|d| *d.get_temp_mut_or_default::<u32>(ui.id()) += 1);
+                ids = Some((ui.id(), ctx.viewport_local_id(Id::new("window"))));
+            });
+        });
+        ids.unwrap()
+    };
+    let num_frames = |ctx: &Context, id: Id| ctx.data_mut(|d| d.get_temp::<u32>(id));
+
+    // By default the same ui in two viewports has separate state:
+    let ctx = Context::default();
+    let (root_ui, root_window) = run(&ctx, ViewportId::ROOT);
+    run(&ctx, ViewportId::ROOT);
+    let (child_ui, child_window) = run(&ctx, child);
+    assert_ne!(root_ui, child_ui);
+    assert_ne!(root_window, child_window);
+    assert_eq!(root_window, Id::new("window"));
+    assert_eq!(num_frames(&ctx, root_ui), Some(2));
+    assert_eq!(num_frames(&ctx, child_ui), Some(1));
+
+    // Without the namespacing, all viewports use the ids of the root viewport:
+    let ctx = Context::default();
+    ctx.options_mut(|o| o.namespace_ids_by_viewport = false);
+    let (root_ui, root_window) = run(&ctx, ViewportId::ROOT);
+    run(&ctx, ViewportId::ROOT);
+    let (child_ui, child_window) = run(&ctx, child);
+    assert_eq!(root_ui, child_ui);
+    assert_eq!(root_window, child_window);
+    assert_eq!(child_window, Id::new("window"));
+    assert_eq!(num_frames(&ctx, root_ui), Some(3));
 }
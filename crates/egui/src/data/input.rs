@@ -60,6 +60,16 @@ pub struct RawInput {
     /// and/or the pointer (mouse/touch) with [`crate::Context::is_using_pointer`].
     pub events: Vec<Event>,
 
+    /// When each of the [`Self::events`] was received, using the same clock as [`Self::time`].
+    ///
+    /// `event_times[i]` is the time of `events[i]`.
+    /// This may be shorter than [`Self::events`] (e.g. empty) if the integration doesn't know
+    /// when some events were received, in which case egui uses [`Self::time`] for them.
+    ///
+    /// This is also used to measure the latency from input to presenting the result on screen,
+    /// see [`crate::Context::latency_stats`].
+    pub event_times: Vec<f64>,
+
     /// Dragged files hovering over egui.
     pub hovered_files: Vec<HoveredFile>,
 
@@ -86,6 +96,7 @@ impl Default for RawInput {
             predicted_dt: 1.0 / 60.0,
             modifiers: Modifiers::default(),
             events: vec![],
+            event_times: vec![],
             hovered_files: Default::default(),
             dropped_files: Default::default(),
             focused: true, // integrations opt into global focus tracking
@@ -114,6 +125,7 @@ impl RawInput {
             predicted_dt: self.predicted_dt,
             modifiers: self.modifiers,
             events: std::mem::take(&mut self.events),
+            event_times: std::mem::take(&mut self.event_times),
             hovered_files: self.hovered_files.clone(),
             dropped_files: std::mem::take(&mut self.dropped_files),
             focused: self.focused,
@@ -131,11 +143,20 @@ impl RawInput {
             predicted_dt,
             modifiers,
             mut events,
+            mut event_times,
             mut hovered_files,
             mut dropped_files,
             focused,
         } = newer;
 
+        // Keep `event_times` parallel to `events`:
+        if let Some(fallback_time) = self.time {
+            self.fill_event_times(fallback_time);
+        }
+        if self.event_times.len() == self.events.len() {
+            self.event_times.append(&mut event_times);
+        }
+
         self.viewport_id = viewport_ids;
         self.viewports = viewports;
         self.screen_rect = screen_rect.or(self.screen_rect);
@@ -148,6 +169,226 @@ impl RawInput {
         self.dropped_files.append(&mut dropped_files);
         self.focused = focused;
     }
+
+    /// The [`Self::events`] together with when they were received, if known.
+    ///
+    /// See [`Self::event_times`].
+    pub fn events_with_times(&self) -> impl Iterator<Item = (&Event, Option<f64>)> + '_ {
+        self.events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| (event, self.event_times.get(i).copied()))
+    }
+
+    /// When the oldest of the [`Self::events`] was received, if known.
+    pub fn oldest_event_time(&self) -> Option<f64> {
+        self.event_times.iter().copied().reduce(f64::min)
+    }
+
+    /// Use `time` for all [`Self::events`] that have no [`Self::event_times`] yet.
+    ///
+    /// Integrations can call this after pushing new events, with the time they were received.
+    pub fn fill_event_times(&mut self, time: f64) {
+        self.event_times.truncate(self.events.len());
+        self.event_times.resize(self.events.len(), time);
+    }
+}
+
+/// Helper for constructing a [`RawInput`] in integrations that don't use `winit`,
+/// e.g. on top of SDL, Qt or a game engine.
+///
+/// Events are added in order, and key and pointer events use the latest [`Self::modifiers`].
+///
+/// ```
+/// use egui::{pos2, vec2, Key, PointerButton, RawInputBuilder};
+///
+/// let raw_input = RawInputBuilder::new()
+///     .screen_size(vec2(800.0, 600.0))
+///     .pixels_per_point(2.0)
+///     .time(1.5)
+///     .pointer_moved(pos2(10.0, 20.0))
+///     .pointer_button(pos2(10.0, 20.0), PointerButton::Primary, true)
+///     .key(Key::A, true)
+///     .text("a")
+///     .build();
+///
+/// assert_eq!(raw_input.events.len(), 4);
+/// assert_eq!(raw_input.viewport().native_pixels_per_point, Some(2.0));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[must_use = "You should call .build()"]
+pub struct RawInputBuilder {
+    raw: RawInput,
+    native_pixels_per_point: Option<f32>,
+}
+
+impl RawInputBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Which viewport the input is for. Default: [`ViewportId::ROOT`].
+    #[inline]
+    pub fn viewport_id(mut self, viewport_id: ViewportId) -> Self {
+        self.raw.viewport_id = viewport_id;
+        self
+    }
+
+    /// The size of the window, in points.
+    #[inline]
+    pub fn screen_size(self, size: Vec2) -> Self {
+        self.screen_rect(Rect::from_min_size(Pos2::ZERO, size))
+    }
+
+    /// See [`RawInput::screen_rect`].
+    #[inline]
+    pub fn screen_rect(mut self, screen_rect: Rect) -> Self {
+        self.raw.screen_rect = Some(screen_rect);
+        self
+    }
+
+    /// The number of physical pixels per point of the monitor the window is on.
+    ///
+    /// See [`ViewportInfo::native_pixels_per_point`].
+    #[inline]
+    pub fn pixels_per_point(mut self, native_pixels_per_point: f32) -> Self {
+        self.native_pixels_per_point = Some(native_pixels_per_point);
+        self
+    }
+
+    /// See [`RawInput::max_texture_side`].
+    #[inline]
+    pub fn max_texture_side(mut self, max_texture_side: usize) -> Self {
+        self.raw.max_texture_side = Some(max_texture_side);
+        self
+    }
+
+    /// See [`RawInput::time`].
+    #[inline]
+    pub fn time(mut self, time: f64) -> Self {
+        self.raw.time = Some(time);
+        self
+    }
+
+    /// See [`RawInput::predicted_dt`].
+    #[inline]
+    pub fn predicted_dt(mut self, predicted_dt: f32) -> Self {
+        self.raw.predicted_dt = predicted_dt;
+        self
+    }
+
+    /// Does the window have keyboard focus? Default: `true`.
+    #[inline]
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.raw.focused = focused;
+        self
+    }
+
+    /// The modifier keys currently held down.
+    ///
+    /// This is used for all key and pointer events added after this call.
+    #[inline]
+    pub fn modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.raw.modifiers = modifiers;
+        self
+    }
+
+    /// Add any event.
+    #[inline]
+    pub fn event(mut self, event: Event) -> Self {
+        self.raw.events.push(event);
+        self
+    }
+
+    /// A key was pressed or released.
+    ///
+    /// Use [`Self::event`] with [`Event::Key`] if you also know the physical key.
+    pub fn key(self, key: Key, pressed: bool) -> Self {
+        let modifiers = self.raw.modifiers;
+        self.event(Event::Key {
+            key,
+            physical_key: None,
+            scancode: None,
+            pressed,
+            repeat: false,
+            modifiers,
+        })
+    }
+
+    /// Text was typed.
+    ///
+    /// Control characters and characters in the private use areas (used by some platforms for special keys)
+    /// are ignored, see [`is_printable_char`].
+    pub fn text(self, text: impl Into<String>) -> Self {
+        let mut text: String = text.into();
+        text.retain(is_printable_char);
+        if text.is_empty() {
+            self
+        } else {
+            self.event(Event::Text(text))
+        }
+    }
+
+    /// The mouse or touch moved to a new place, in points.
+    #[inline]
+    pub fn pointer_moved(self, pos: Pos2) -> Self {
+        self.event(Event::PointerMoved(pos))
+    }
+
+    /// A mouse button was pressed or released.
+    pub fn pointer_button(self, pos: Pos2, button: PointerButton, pressed: bool) -> Self {
+        let modifiers = self.raw.modifiers;
+        self.event(Event::PointerButton {
+            pos,
+            button,
+            pressed,
+            modifiers,
+        })
+    }
+
+    /// The mouse left the window.
+    #[inline]
+    pub fn pointer_gone(self) -> Self {
+        self.event(Event::PointerGone)
+    }
+
+    /// The user scrolled, in points. See [`Event::Scroll`].
+    #[inline]
+    pub fn scroll(self, delta: Vec2) -> Self {
+        self.event(Event::Scroll(delta))
+    }
+
+    /// The user zoomed, e.g. with a pinch gesture. See [`Event::Zoom`].
+    #[inline]
+    pub fn zoom(self, factor: f32) -> Self {
+        self.event(Event::Zoom(factor))
+    }
+
+    pub fn build(self) -> RawInput {
+        let Self {
+            mut raw,
+            native_pixels_per_point,
+        } = self;
+        let viewport = raw.viewports.entry(raw.viewport_id).or_default();
+        if native_pixels_per_point.is_some() {
+            viewport.native_pixels_per_point = native_pixels_per_point;
+        }
+        raw
+    }
+}
+
+/// Is this a character that should be inserted when typed?
+///
+/// Some platforms send special keys (backspace, delete, F1, …) as characters
+/// in the private use areas of unicode. Those are ignored,
+/// as are control characters like `'\r'`, `'\n'` and `'\t'`.
+/// Newlines are handled by the [`Key::Enter`] event.
+pub fn is_printable_char(chr: char) -> bool {
+    let is_in_private_use_area = '\u{e000}' <= chr && chr <= '\u{f8ff}'
+        || '\u{f0000}' <= chr && chr <= '\u{ffffd}'
+        || '\u{100000}' <= chr && chr <= '\u{10fffd}';
+
+    !is_in_private_use_area && !chr.is_ascii_control()
 }
 
 /// An input event from the backend into egui, about a specific [viewport](crate::viewport).
@@ -165,6 +406,26 @@ pub enum ViewportEvent {
     ///
     /// This even will wake up both the child and parent viewport.
     Close,
+
+    /// The window moved. See [`ViewportInfo::outer_rect`] for the new position.
+    Moved,
+
+    /// The window changed size. See [`ViewportInfo::inner_rect`] for the new size.
+    Resized,
+
+    /// The window was minimized (`true`) or restored from being minimized (`false`).
+    Minimized(bool),
+
+    /// The window was maximized (`true`) or restored from being maximized (`false`).
+    Maximized(bool),
+
+    /// The window entered (`true`) or left (`false`) fullscreen mode.
+    Fullscreen(bool),
+
+    /// The window moved to a different monitor.
+    ///
+    /// See [`ViewportInfo::monitor_name`], [`ViewportInfo::monitor_position`] and [`ViewportInfo::monitor_size`].
+    MonitorChanged,
 }
 
 /// Information about the current viewport, given as input each frame.
@@ -195,6 +456,15 @@ pub struct ViewportInfo {
     /// Current monitor size in egui points.
     pub monitor_size: Option<Vec2>,
 
+    /// The name of the current monitor, if the platform provides one.
+    ///
+    /// Together with [`Self::monitor_position`] this can be used to identify a monitor,
+    /// e.g. to remember the window position for each monitor.
+    pub monitor_name: Option<String>,
+
+    /// The top-left corner of the current monitor, in the same space as [`Self::outer_rect`].
+    pub monitor_position: Option<Pos2>,
+
     /// The inner rectangle of the native window, in monitor space and ui points scale.
     ///
     /// This is the content rectangle of the viewport.
@@ -214,10 +484,26 @@ pub struct ViewportInfo {
     /// Are we in fullscreen mode?
     pub fullscreen: Option<bool>,
 
+    /// How are we fullscreen?
+    ///
+    /// `None` if we are not fullscreen, or if it is unknown.
+    pub fullscreen_mode: Option<crate::viewport::FullscreenMode>,
+
     /// Is the window focused and able to receive input?
     ///
     /// This should be the same as [`RawInput::focused`].
     pub focused: Option<bool>,
+
+    /// How many points at the bottom of the viewport are covered by an on-screen (virtual) keyboard.
+    ///
+    /// See [`crate::InputState::virtual_keyboard_inset`].
+    pub virtual_keyboard_inset: Option<f32>,
+
+    /// The parts of the viewport that are covered by e.g. a display notch,
+    /// rounded screen corners or system bars, in points.
+    ///
+    /// See [`crate::InputState::safe_area_insets`].
+    pub safe_area_insets: Option<crate::Margin>,
 }
 
 impl ViewportInfo {
@@ -235,6 +521,49 @@ impl ViewportInfo {
             .any(|&event| event == ViewportEvent::Close)
     }
 
+    /// Add events for everything that changed since the `previous` info,
+    /// e.g. [`ViewportEvent::Moved`] if the window moved.
+    ///
+    /// Nothing is added for values that were unknown in either of them.
+    pub(crate) fn add_change_events(&mut self, previous: &Self) {
+        fn changed<T: PartialEq>(previous: &Option<T>, current: &Option<T>) -> bool {
+            previous.is_some() && current.is_some() && previous != current
+        }
+
+        let min = |rect: &Option<Rect>| rect.map(|rect| rect.min);
+        let size = |rect: &Option<Rect>| rect.map(|rect| rect.size());
+
+        if changed(&min(&previous.outer_rect), &min(&self.outer_rect)) {
+            self.events.push(ViewportEvent::Moved);
+        }
+        if changed(&size(&previous.inner_rect), &size(&self.inner_rect)) {
+            self.events.push(ViewportEvent::Resized);
+        }
+        if let (true, Some(minimized)) = (
+            changed(&previous.minimized, &self.minimized),
+            self.minimized,
+        ) {
+            self.events.push(ViewportEvent::Minimized(minimized));
+        }
+        if let (true, Some(maximized)) = (
+            changed(&previous.maximized, &self.maximized),
+            self.maximized,
+        ) {
+            self.events.push(ViewportEvent::Maximized(maximized));
+        }
+        if let (true, Some(fullscreen)) = (
+            changed(&previous.fullscreen, &self.fullscreen),
+            self.fullscreen,
+        ) {
+            self.events.push(ViewportEvent::Fullscreen(fullscreen));
+        }
+        if changed(&previous.monitor_name, &self.monitor_name)
+            || changed(&previous.monitor_position, &self.monitor_position)
+        {
+            self.events.push(ViewportEvent::MonitorChanged);
+        }
+    }
+
     pub fn ui(&self, ui: &mut crate::Ui) {
         let Self {
             parent,
@@ -242,12 +571,17 @@ impl ViewportInfo {
             events,
             native_pixels_per_point,
             monitor_size,
+            monitor_name,
+            monitor_position,
             inner_rect,
             outer_rect,
             minimized,
             maximized,
             fullscreen,
+            fullscreen_mode,
             focused,
+            virtual_keyboard_inset,
+            safe_area_insets,
         } = self;
 
         crate::Grid::new("viewport_info").show(ui, |ui| {
@@ -271,6 +605,14 @@ impl ViewportInfo {
             ui.label(opt_as_str(monitor_size));
             ui.end_row();
 
+            ui.label("Monitor name:");
+            ui.label(opt_as_str(monitor_name));
+            ui.end_row();
+
+            ui.label("Monitor position:");
+            ui.label(opt_as_str(monitor_position));
+            ui.end_row();
+
             ui.label("Inner rect:");
             ui.label(opt_rect_as_string(inner_rect));
             ui.end_row();
@@ -291,10 +633,22 @@ impl ViewportInfo {
             ui.label(opt_as_str(fullscreen));
             ui.end_row();
 
+            ui.label("Fullscreen mode:");
+            ui.label(opt_as_str(fullscreen_mode));
+            ui.end_row();
+
             ui.label("Focused:");
             ui.label(opt_as_str(focused));
             ui.end_row();
 
+            ui.label("Virtual keyboard inset:");
+            ui.label(opt_as_str(virtual_keyboard_inset));
+            ui.end_row();
+
+            ui.label("Safe area insets:");
+            ui.label(opt_as_str(safe_area_insets));
+            ui.end_row();
+
             fn opt_rect_as_string(v: &Option<Rect>) -> String {
                 v.as_ref().map_or(String::new(), |r| {
                     format!("Pos: {:?}, size: {:?}", r.min, r.size())
@@ -308,6 +662,39 @@ impl ViewportInfo {
     }
 }
 
+#[test]
+fn viewport_info_change_events() {
+    let previous = ViewportInfo {
+        outer_rect: Some(Rect::from_min_size(pos2(10.0, 10.0), vec2(100.0, 100.0))),
+        inner_rect: Some(Rect::from_min_size(pos2(10.0, 30.0), vec2(100.0, 80.0))),
+        maximized: Some(false),
+        monitor_name: Some("A".to_owned()),
+        ..Default::default()
+    };
+
+    let mut current = previous.clone();
+    current.add_change_events(&previous);
+    assert!(current.events.is_empty());
+
+    let mut current = ViewportInfo {
+        outer_rect: Some(Rect::from_min_size(pos2(20.0, 10.0), vec2(100.0, 100.0))),
+        inner_rect: Some(Rect::from_min_size(pos2(20.0, 30.0), vec2(100.0, 80.0))),
+        maximized: Some(true),
+        minimized: Some(false), // was unknown
+        monitor_name: Some("B".to_owned()),
+        ..Default::default()
+    };
+    current.add_change_events(&previous);
+    assert_eq!(
+        current.events,
+        vec![
+            ViewportEvent::Moved,
+            ViewportEvent::Maximized(true),
+            ViewportEvent::MonitorChanged
+        ]
+    );
+}
+
 /// A file about to be dropped into egui.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -374,9 +761,16 @@ pub enum Event {
         /// where e.g. the physical location of WSAD on QWERTY should always map to movement,
         /// even if the user is using Dvorak or AZERTY.
         ///
-        /// `eframe` does not (yet) implement this on web.
+        /// See also [`KeyboardShortcut::new_physical`] and [`crate::InputState::physical_key_pressed`].
         physical_key: Option<Key>,
 
+        /// The raw platform-specific scancode of the physical key, if known.
+        ///
+        /// Unlike [`Self::Key::physical_key`] this is also set for keys that egui has no [`Key`] for,
+        /// which is useful for letting the user bind arbitrary keys.
+        /// The values differ between platforms, so don't store them across machines.
+        scancode: Option<u32>,
+
         /// Was it pressed or released?
         pressed: bool,
 
@@ -396,6 +790,13 @@ pub enum Event {
     /// The mouse or touch moved to a new place.
     PointerMoved(Pos2),
 
+    /// The raw mouse motion reported by the OS, before any acceleration is applied.
+    ///
+    /// Unlike [`Self::PointerMoved`] this is not limited by the edges of the screen,
+    /// and keeps coming while the pointer is locked (see [`crate::Context::set_pointer_lock`]).
+    /// The units are unspecified, and usually not points.
+    MouseMoved(Vec2),
+
     /// A mouse button was pressed or released (or a touch started or stopped).
     PointerButton {
         /// Where is the pointer?
@@ -471,6 +872,13 @@ pub enum Event {
         force: Option<f32>,
     },
 
+    /// Extra information from a pen/stylus, for e.g. pressure-sensitive brushes.
+    ///
+    /// Reported *in addition to* [`Self::PointerMoved`], [`Self::PointerButton`] and [`Self::Touch`],
+    /// so pens also work as a normal pointer.
+    /// The latest state can be read with [`crate::PointerState::pen`].
+    Pen(PenInput),
+
     /// A raw mouse wheel event as sent by the backend (minus the z coordinate),
     /// for implementing alternative custom controls.
     /// Note that the same event can also trigger [`Self::Zoom`] and [`Self::Scroll`],
@@ -487,6 +895,18 @@ pub enum Event {
         modifiers: Modifiers,
     },
 
+    /// A button on a gamepad (game controller) was pressed or released.
+    ///
+    /// The d-pad moves the keyboard focus like the arrow keys,
+    /// [`GamepadButton::South`] clicks the focused widget,
+    /// and [`GamepadButton::East`] surrenders focus like escape.
+    GamepadButton {
+        button: GamepadButton,
+
+        /// Was it pressed or released?
+        pressed: bool,
+    },
+
     /// The native window gained or lost focused (e.g. the user clicked alt-tab).
     WindowFocused(bool),
 
@@ -499,6 +919,67 @@ pub enum Event {
         viewport_id: crate::ViewportId,
         image: std::sync::Arc<ColorImage>,
     },
+
+    /// Another instance of the app was started, with these command line arguments
+    /// (not including the name of the executable).
+    ///
+    /// The other instance exits right away, so this is where you should e.g. open the files it was given.
+    ///
+    /// Sent by `eframe` when `NativeOptions::single_instance` is set,
+    /// and when an item of the dock menu set with `Frame::set_dock_menu` is picked on Mac.
+    InstanceArgs(Vec<String>),
+
+    /// The app was asked to open a URL using one of its custom URL schemes, e.g. `myapp://settings`.
+    ///
+    /// Sent by `eframe` for the schemes in `NativeOptions::url_schemes`,
+    /// both for the URL the app was started with and for URLs opened while it is running.
+    DeepLink(String),
+
+    /// The app was asked to open a file, e.g. because the user double-clicked a document
+    /// associated with the app, or dropped a file on its dock icon.
+    ///
+    /// Sent by `eframe` when `NativeOptions::open_files` is set,
+    /// both for the files the app was started with and for files opened while it is running.
+    OpenFile(std::path::PathBuf),
+}
+
+/// Which end of a pen/stylus is in use.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PenTool {
+    /// The normal tip of the pen.
+    #[default]
+    Pen,
+
+    /// The eraser at the back of the pen (or a pen button that switches to eraser mode).
+    Eraser,
+}
+
+/// The state of a pen/stylus, see [`Event::Pen`].
+///
+/// Which fields are available depends on the hardware and the integration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PenInput {
+    /// Position of the pen tip, in points.
+    pub pos: Pos2,
+
+    /// How hard the pen is pressed, from 0.0 (hovering) to 1.0 (maximum pressure).
+    pub pressure: f32,
+
+    /// The tilt of the pen in radians, along the x and y axes of the screen.
+    ///
+    /// Zero means the pen is perpendicular to the screen.
+    /// `None` if not supported.
+    pub tilt: Option<Vec2>,
+
+    /// Clockwise rotation of the pen around its own axis, in radians.
+    ///
+    /// `None` if not supported.
+    pub twist: Option<f32>,
+
+    /// Which end of the pen is in use.
+    pub tool: PenTool,
 }
 
 /// Mouse button (or similar for touch input)
@@ -525,6 +1006,43 @@ pub enum PointerButton {
 /// Number of pointer buttons supported by egui, i.e. the number of possible states of [`PointerButton`].
 pub const NUM_POINTER_BUTTONS: usize = 5;
 
+/// A button on a gamepad (game controller).
+///
+/// The face buttons are named after their position, since the labels differ between controllers
+/// (e.g. [`Self::South`] is `A` on an Xbox controller and `✕` on a `PlayStation` controller).
+///
+/// Analog sticks are reported as d-pad presses by the integrations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GamepadButton {
+    /// The bottom face button. Works like a primary click on the focused widget.
+    South,
+
+    /// The right face button. Surrenders keyboard focus, like escape.
+    East,
+
+    /// The top face button.
+    North,
+
+    /// The left face button.
+    West,
+
+    /// Moves focus to the previous widget, like shift-tab.
+    LeftBumper,
+
+    /// Moves focus to the next widget, like tab.
+    RightBumper,
+
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
 /// State of the modifier keys. These must be fed to egui.
 ///
 /// The best way to compare [`Modifiers`] is by using [`Modifiers::matches`].
@@ -1434,6 +1952,13 @@ pub struct KeyboardShortcut {
     pub modifiers: Modifiers,
 
     pub logical_key: Key,
+
+    /// If `true`, [`Self::logical_key`] is matched against the physical position of the key
+    /// (as on a US QWERTY keyboard) instead of what the keyboard layout maps it to.
+    ///
+    /// See [`Self::new_physical`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub physical: bool,
 }
 
 impl KeyboardShortcut {
@@ -1441,6 +1966,22 @@ impl KeyboardShortcut {
         Self {
             modifiers,
             logical_key,
+            physical: false,
+        }
+    }
+
+    /// A shortcut that matches the physical position of the key, regardless of keyboard layout.
+    ///
+    /// For instance, `KeyboardShortcut::new_physical(Modifiers::NONE, Key::W)` matches the key
+    /// labeled `Z` on an AZERTY keyboard, which is useful for game-style WASD controls.
+    ///
+    /// For most shortcuts you should prefer [`Self::new`],
+    /// so that e.g. `Ctrl+Z` matches the key the user sees as `Z`.
+    pub const fn new_physical(modifiers: Modifiers, physical_key: Key) -> Self {
+        Self {
+            modifiers,
+            logical_key: physical_key,
+            physical: true,
         }
     }
 
@@ -1473,6 +2014,36 @@ fn format_kb_shortcut() {
     assert_eq!(cmd_shift_f.format(&ModifierNames::SYMBOLS, true), "⇧⌘F");
 }
 
+#[test]
+fn physical_kb_shortcut() {
+    // The key labeled `Z` on an AZERTY keyboard is where `W` is on QWERTY:
+    let azerty_z = Event::Key {
+        key: Key::Z,
+        physical_key: Some(Key::W),
+        scancode: None,
+        pressed: true,
+        repeat: false,
+        modifiers: Modifiers::NONE,
+    };
+    let input = RawInput {
+        events: vec![azerty_z],
+        ..Default::default()
+    };
+    let input = crate::InputState::default()
+        .begin_frame(Default::default(), false, 1.0)
+        .begin_frame(input, false, 1.0);
+    assert!(input.key_pressed(Key::Z));
+    assert!(input.physical_key_pressed(Key::W));
+    assert!(input.physical_key_down(Key::W));
+
+    let mut input_clone = input.clone();
+    assert!(!input_clone.consume_shortcut(&KeyboardShortcut::new(Modifiers::NONE, Key::W)));
+    assert!(input_clone.consume_shortcut(&KeyboardShortcut::new_physical(Modifiers::NONE, Key::W)));
+
+    let mut input_clone = input;
+    assert!(input_clone.consume_shortcut(&KeyboardShortcut::new(Modifiers::NONE, Key::Z)));
+}
+
 // ----------------------------------------------------------------------------
 
 impl RawInput {
@@ -1486,6 +2057,7 @@ impl RawInput {
             predicted_dt,
             modifiers,
             events,
+            event_times,
             hovered_files,
             dropped_files,
             focused,
@@ -1509,6 +2081,7 @@ impl RawInput {
             ui.label("time: None");
         }
         ui.label(format!("predicted_dt: {:.1} ms", 1e3 * predicted_dt));
+        ui.label(format!("event_times: {event_times:?}"));
         ui.label(format!("modifiers: {modifiers:#?}"));
         ui.label(format!("hovered_files: {}", hovered_files.len()));
         ui.label(format!("dropped_files: {}", dropped_files.len()));
@@ -1636,16 +2209,22 @@ impl Default for EventFilter {
 
 impl EventFilter {
     pub fn matches(&self, event: &Event) -> bool {
-        if let Event::Key { key, .. } = event {
-            match key {
+        match event {
+            Event::Key { key, .. } => match key {
                 crate::Key::Tab => self.tab,
                 crate::Key::ArrowUp | crate::Key::ArrowDown => self.vertical_arrows,
                 crate::Key::ArrowRight | crate::Key::ArrowLeft => self.horizontal_arrows,
                 crate::Key::Escape => self.escape,
                 _ => true,
-            }
-        } else {
-            true
+            },
+            Event::GamepadButton { button, .. } => match button {
+                GamepadButton::LeftBumper | GamepadButton::RightBumper => self.tab,
+                GamepadButton::DPadUp | GamepadButton::DPadDown => self.vertical_arrows,
+                GamepadButton::DPadLeft | GamepadButton::DPadRight => self.horizontal_arrows,
+                GamepadButton::East => self.escape,
+                _ => true,
+            },
+            _ => true,
         }
     }
 }
@@ -79,6 +79,21 @@ pub struct IMEOutput {
     pub cursor_rect: crate::Rect,
 }
 
+/// A native web view that the integration should show over a part of the viewport,
+/// see [`crate::WebView`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct WebViewOutput {
+    /// Identifies the web view from one frame to the next.
+    pub id: crate::Id,
+
+    /// The page to show. The web view navigates to it when it changes.
+    pub url: String,
+
+    /// Where to show the web view, in points.
+    pub rect: crate::Rect,
+}
+
 /// The non-rendering part of what egui emits each frame.
 ///
 /// You can access (and modify) this with [`crate::Context::output`].
@@ -93,6 +108,28 @@ pub struct PlatformOutput {
     /// If set, open this url.
     pub open_url: Option<OpenUrl>,
 
+    /// If set, open this local file or directory with its default application.
+    ///
+    /// Not supported on the web.
+    pub open_path: Option<std::path::PathBuf>,
+
+    /// If set, show this local file or directory in the file manager (Finder, Explorer, …).
+    ///
+    /// Not supported on the web.
+    pub reveal_in_file_manager: Option<std::path::PathBuf>,
+
+    /// If set, play this standard sound of the OS, e.g. to signal invalid input.
+    ///
+    /// Use [`crate::Context::play_system_sound`] to set this.
+    /// Not supported on the web.
+    pub play_system_sound: Option<SoundKind>,
+
+    /// If set, give this tactile feedback, e.g. when a stepped slider moves to the next step.
+    ///
+    /// Use [`crate::Context::haptic`] to set this.
+    /// Supported on Android, iOS, Mac (with a Force Touch trackpad) and some mobile browsers.
+    pub haptic: Option<HapticKind>,
+
     /// If set, put this text in the system clipboard. Ignore if empty.
     ///
     /// This is often a response to [`crate::Event::Copy`] or [`crate::Event::Cut`].
@@ -109,6 +146,9 @@ pub struct PlatformOutput {
     /// Events that may be useful to e.g. a screen reader.
     pub events: Vec<OutputEvent>,
 
+    /// Texts for a screen reader to read out, added with [`crate::Context::announce`].
+    pub announcements: Vec<Announcement>,
+
     /// Is there a mutable [`TextEdit`](crate::TextEdit) under the cursor?
     /// Use by `eframe` web to show/hide mobile keyboard and IME agent.
     pub mutable_text_under_cursor: bool,
@@ -118,6 +158,18 @@ pub struct PlatformOutput {
     /// Useful for IME.
     pub ime: Option<IMEOutput>,
 
+    /// Should the on-screen (virtual) keyboard be shown?
+    ///
+    /// Set by a focused [`TextEdit`](crate::TextEdit) in touch mode (see [`crate::style::TouchMode`]).
+    /// Used by `eframe` to show the keyboard on Android.
+    pub virtual_keyboard: bool,
+
+    /// The native web views to show in this viewport, added by [`crate::WebView`].
+    ///
+    /// Web views that were shown in the previous frame but aren't in this list should be closed.
+    /// Used by `eframe` when it has a `WebViewBackend`, e.g. from the `egui_webview` crate.
+    pub webviews: Vec<WebViewOutput>,
+
     /// The difference in the widget tree since last frame.
     ///
     /// NOTE: this needs to be per-viewport.
@@ -144,15 +196,46 @@ impl PlatformOutput {
         Default::default()
     }
 
+    /// What a text-to-speech screen reader should say this frame, in order.
+    ///
+    /// The [`Self::events_description`] and the [`AnnouncePriority::Assertive`] announcements
+    /// come first, and interrupt whatever is being said.
+    /// They are followed by the [`AnnouncePriority::Polite`] announcements, which should be queued
+    /// (and not be lost when something interrupts them later).
+    pub fn speech(&self) -> Vec<Announcement> {
+        let events_description = self.events_description();
+        let interrupting = (!events_description.is_empty())
+            .then(|| Announcement::new(events_description, AnnouncePriority::Assertive));
+        let announcements = self
+            .announcements
+            .iter()
+            .filter(|a| a.priority == AnnouncePriority::Assertive)
+            .chain(
+                self.announcements
+                    .iter()
+                    .filter(|a| a.priority == AnnouncePriority::Polite),
+            )
+            .filter(|a| !a.text.is_empty())
+            .cloned();
+        interrupting.into_iter().chain(announcements).collect()
+    }
+
     /// Add on new output.
     pub fn append(&mut self, newer: Self) {
         let Self {
             cursor_icon,
             open_url,
+            open_path,
+            reveal_in_file_manager,
+            play_system_sound,
+            haptic,
             copied_text,
             mut events,
+            mut announcements,
             mutable_text_under_cursor,
             ime,
+            virtual_keyboard,
+            webviews,
             #[cfg(feature = "accesskit")]
             accesskit_update,
         } = newer;
@@ -161,12 +244,27 @@ impl PlatformOutput {
         if open_url.is_some() {
             self.open_url = open_url;
         }
+        if open_path.is_some() {
+            self.open_path = open_path;
+        }
+        if reveal_in_file_manager.is_some() {
+            self.reveal_in_file_manager = reveal_in_file_manager;
+        }
+        if play_system_sound.is_some() {
+            self.play_system_sound = play_system_sound;
+        }
+        if haptic.is_some() {
+            self.haptic = haptic;
+        }
         if !copied_text.is_empty() {
             self.copied_text = copied_text;
         }
         self.events.append(&mut events);
+        self.announcements.append(&mut announcements);
         self.mutable_text_under_cursor = mutable_text_under_cursor;
         self.ime = ime.or(self.ime);
+        self.virtual_keyboard = virtual_keyboard;
+        self.webviews = webviews; // Only show the web views of the latest frame
 
         #[cfg(feature = "accesskit")]
         {
@@ -216,6 +314,68 @@ impl OpenUrl {
     }
 }
 
+/// A standard feedback sound of the OS, see [`crate::Context::play_system_sound`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SoundKind {
+    /// The default beep, e.g. when input is rejected.
+    Beep,
+
+    /// Something needs the attention of the user, e.g. when an alert is shown.
+    Alert,
+
+    /// Something went wrong.
+    Error,
+
+    /// Something finished, or there is news.
+    Notification,
+}
+
+/// Tactile feedback, see [`crate::Context::haptic`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum HapticKind {
+    /// A light tick, e.g. when a value moves to the next step (detent).
+    Selection,
+
+    /// Something snapped into place, e.g. a dragged item lined up with a new slot.
+    Alignment,
+
+    /// A heavier bump, e.g. when a dragged item is dropped.
+    Impact,
+}
+
+/// How urgent an [`Announcement`] is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum AnnouncePriority {
+    /// Said after whatever is being said (or queued) has been said, e.g. "3 search results".
+    #[default]
+    Polite,
+
+    /// Said right away, interrupting whatever is being said, e.g. "Connection lost".
+    ///
+    /// Interrupted [`Self::Polite`] announcements are said afterwards.
+    Assertive,
+}
+
+/// A text for a screen reader to read out, see [`crate::Context::announce`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Announcement {
+    pub text: String,
+    pub priority: AnnouncePriority,
+}
+
+impl Announcement {
+    pub fn new(text: impl Into<String>, priority: AnnouncePriority) -> Self {
+        Self {
+            text: text.into(),
+            priority,
+        }
+    }
+}
+
 /// Types of attention to request from a user when a native window is not in focus.
 ///
 /// See [winit's documentation][user_attention_type] for platform-specific meaning of the attention types.
@@ -675,3 +835,25 @@ impl WidgetInfo {
         description.trim().to_owned()
     }
 }
+
+#[test]
+fn test_speech_order() {
+    let output = PlatformOutput {
+        events: vec![OutputEvent::Clicked(WidgetInfo::labeled(
+            WidgetType::Button,
+            "Search",
+        ))],
+        announcements: vec![
+            Announcement::new("3 search results", AnnouncePriority::Polite),
+            Announcement::new("Connection lost", AnnouncePriority::Assertive),
+            Announcement::new("", AnnouncePriority::Polite),
+        ],
+        ..Default::default()
+    };
+
+    let texts: Vec<String> = output.speech().into_iter().map(|a| a.text).collect();
+    assert_eq!(
+        texts,
+        ["Search: button", "Connection lost", "3 search results"]
+    );
+}
@@ -21,6 +21,11 @@ pub(crate) struct FrameState {
     /// All [`Id`]s that were used this frame.
     pub(crate) used_ids: IdMap<Rect>,
 
+    /// If set, every [`Id`] added to [`Self::used_ids`] is also logged here.
+    ///
+    /// Used by [`crate::Ui::retained`] to record which ids a block used.
+    pub(crate) used_ids_log: Option<Vec<(Id, Rect)>>,
+
     /// Starts off as the screen_rect, shrinks as panels are added.
     /// The [`CentralPanel`] does not change this.
     /// This is the area available to Window's.
@@ -55,6 +60,10 @@ pub(crate) struct FrameState {
     /// Highlight these widgets the next frame. Write to this.
     pub(crate) highlight_next_frame: IdSet,
 
+    /// All widgets interacted with this frame, if the inspector is open.
+    /// See [`crate::Context::set_inspector`].
+    pub(crate) inspector_widgets: Vec<crate::inspector::WidgetRecord>,
+
     #[cfg(debug_assertions)]
     pub(crate) has_debug_viewed_this_frame: bool,
 }
@@ -63,6 +72,7 @@ impl Default for FrameState {
     fn default() -> Self {
         Self {
             used_ids: Default::default(),
+            used_ids_log: None,
             available_rect: Rect::NAN,
             unused_rect: Rect::NAN,
             used_by_panels: Rect::NAN,
@@ -73,6 +83,7 @@ impl Default for FrameState {
             accesskit_state: None,
             highlight_this_frame: Default::default(),
             highlight_next_frame: Default::default(),
+            inspector_widgets: Default::default(),
 
             #[cfg(debug_assertions)]
             has_debug_viewed_this_frame: false,
@@ -85,6 +96,7 @@ impl FrameState {
         crate::profile_function!();
         let Self {
             used_ids,
+            used_ids_log,
             available_rect,
             unused_rect,
             used_by_panels,
@@ -95,18 +107,21 @@ impl FrameState {
             accesskit_state,
             highlight_this_frame,
             highlight_next_frame,
+            inspector_widgets,
 
             #[cfg(debug_assertions)]
             has_debug_viewed_this_frame,
         } = self;
 
         used_ids.clear();
+        *used_ids_log = None;
         *available_rect = input.screen_rect();
         *unused_rect = input.screen_rect();
         *used_by_panels = Rect::NOTHING;
         *tooltip_state = None;
         *scroll_delta = input.scroll_delta;
         *scroll_target = [None, None];
+        inspector_widgets.clear();
 
         #[cfg(debug_assertions)]
         {
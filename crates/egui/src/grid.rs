@@ -37,11 +37,6 @@ impl State {
     fn row_height(&self, row: usize) -> Option<f32> {
         self.row_heights.get(row).copied()
     }
-
-    fn full_width(&self, x_spacing: f32) -> f32 {
-        self.col_widths.iter().sum::<f32>()
-            + (self.col_widths.len().at_least(1) - 1) as f32 * x_spacing
-    }
 }
 
 // ----------------------------------------------------------------------------
@@ -49,6 +44,14 @@ impl State {
 // type alias for boxed function to determine row color during grid generation
 type ColorPickerFn = Box<dyn Send + Sync + Fn(usize, &Style) -> Option<Color32>>;
 
+/// A cell covering more than one column or row.
+#[derive(Clone, Debug)]
+struct SpannedCell {
+    cols: std::ops::Range<usize>,
+    rows: std::ops::Range<usize>,
+    size: Vec2,
+}
+
 pub(crate) struct GridLayout {
     ctx: Context,
     style: std::sync::Arc<Style>,
@@ -63,6 +66,10 @@ pub(crate) struct GridLayout {
 
     /// State accumulated during the current frame.
     curr_state: State,
+
+    /// Also store the state here, see [`Grid::size_hash`].
+    size_id: Option<Id>,
+
     initial_available: Rect,
 
     // Options:
@@ -71,6 +78,26 @@ pub(crate) struct GridLayout {
     min_cell_size: Vec2,
     max_cell_size: Vec2,
     color_picker: Option<ColorPickerFn>,
+    columns: Vec<GridColumn>,
+    frozen_header_rows: usize,
+
+    /// The width of each column this frame, based on [`Self::prev_state`] and [`Self::columns`].
+    col_widths: Vec<f32>,
+
+    /// Set by [`Ui::set_grid_cell_span`], reset after each cell.
+    next_cell_span: (usize, usize),
+
+    /// Cells added this frame that cover more than one column or row.
+    spanned_cells: Vec<SpannedCell>,
+
+    /// Where the header rows were painted, see [`Grid::freeze_header_rows`].
+    header: Option<(LayerId, std::ops::Range<usize>, Rect)>,
+    first_header_shape: usize,
+    clip_rect: Rect,
+    first_row: usize,
+
+    /// The bottom of the lowest cell so far.
+    bottom: f32,
 
     // Cursor:
     col: usize,
@@ -99,6 +126,7 @@ impl GridLayout {
             is_first_frame,
             prev_state,
             curr_state: State::default(),
+            size_id: None,
             initial_available,
 
             num_columns: None,
@@ -106,17 +134,78 @@ impl GridLayout {
             min_cell_size: ui.spacing().interact_size,
             max_cell_size: Vec2::INFINITY,
             color_picker: None,
+            columns: Vec::new(),
+            frozen_header_rows: 0,
+
+            col_widths: Vec::new(),
+            next_cell_span: (1, 1),
+            spanned_cells: Vec::new(),
+            header: None,
+            first_header_shape: 0,
+            clip_rect: ui.clip_rect(),
+            first_row: 0,
+            bottom: initial_available.top(),
 
             col: 0,
             row: 0,
         }
     }
-}
 
-impl GridLayout {
-    fn prev_col_width(&self, col: usize) -> f32 {
-        self.prev_state
-            .col_width(col)
+    /// Decide the width of each column, based on the previous frame.
+    ///
+    /// Must be called once the options have been set.
+    fn layout_columns(&mut self, ui: &Ui) {
+        let num_columns = self
+            .num_columns
+            .unwrap_or_default()
+            .max(self.prev_state.col_widths.len());
+
+        let mut widths: Vec<f32> = (0..num_columns)
+            .map(|col| {
+                let column = self.column(col);
+                self.prev_state
+                    .col_width(col)
+                    .unwrap_or(self.min_cell_size.x)
+                    .at_least(column.min_width)
+                    .at_most(column.max_width)
+            })
+            .collect();
+
+        let total_weight: f32 = (0..num_columns).map(|col| self.column(col).weight).sum();
+        if 0.0 < total_weight && !self.is_first_frame {
+            let fixed_width: f32 = (0..num_columns)
+                .filter(|&col| self.column(col).weight <= 0.0)
+                .map(|col| widths[col])
+                .sum();
+            let spacing = num_columns.saturating_sub(1) as f32 * self.spacing.x;
+            let free_width = self.initial_available.width() - fixed_width - spacing;
+
+            for (col, width) in widths.iter_mut().enumerate() {
+                let column = self.column(col);
+                if 0.0 < column.weight {
+                    *width = width
+                        .max(free_width * column.weight / total_weight)
+                        .at_most(column.max_width);
+                }
+            }
+        }
+
+        self.col_widths = widths;
+        self.first_row = self.row;
+
+        if 0 < self.frozen_header_rows {
+            self.first_header_shape = ui.ctx().graphics_mut(|g| g.list(ui.layer_id()).len());
+        }
+    }
+
+    fn column(&self, col: usize) -> GridColumn {
+        self.columns.get(col).copied().unwrap_or_default()
+    }
+
+    fn col_width(&self, col: usize) -> f32 {
+        self.col_widths
+            .get(col)
+            .copied()
             .unwrap_or(self.min_cell_size.x)
     }
 
@@ -126,14 +215,40 @@ impl GridLayout {
             .unwrap_or(self.min_cell_size.y)
     }
 
+    /// The width of the cell at the cursor, including any column span.
+    fn cell_width(&self) -> f32 {
+        let (colspan, _) = self.next_cell_span;
+        let cols = self.col..self.col + colspan;
+        cols.clone().map(|col| self.col_width(col)).sum::<f32>()
+            + (cols.len() - 1) as f32 * self.spacing.x
+    }
+
+    /// The height of the cell at the cursor, including any row span.
+    fn cell_height(&self) -> f32 {
+        let (_, rowspan) = self.next_cell_span;
+        let rows = self.row..self.row + rowspan;
+        rows.clone()
+            .map(|row| self.prev_row_height(row))
+            .sum::<f32>()
+            + (rows.len() - 1) as f32 * self.spacing.y
+    }
+
+    pub(crate) fn set_next_cell_span(&mut self, colspan: usize, rowspan: usize) {
+        self.next_cell_span = (colspan.at_least(1), rowspan.at_least(1));
+    }
+
     pub(crate) fn wrap_text(&self) -> bool {
-        self.max_cell_size.x.is_finite()
+        self.max_cell_size.x.is_finite() || self.column(self.col).max_width.is_finite()
     }
 
     pub(crate) fn available_rect(&self, region: &Region) -> Rect {
-        let is_last_column = Some(self.col + 1) == self.num_columns;
+        let (colspan, _) = self.next_cell_span;
+        let column = self.column(self.col);
+        let is_last_column = Some(self.col + colspan) == self.num_columns;
 
-        let width = if is_last_column {
+        let width = if 0.0 < column.weight && !self.is_first_frame {
+            self.cell_width()
+        } else if is_last_column {
             // The first frame we don't really know the widths of the previous columns,
             // so returning a big available width here can cause trouble.
             if self.is_first_frame {
@@ -150,14 +265,22 @@ impl GridLayout {
         } else {
             // If we want to allow width-filling widgets like [`Separator`] in one of the first cells
             // then we need to make sure they don't spill out of the first cell:
-            self.prev_state
-                .col_width(self.col)
-                .or_else(|| self.curr_state.col_width(self.col))
-                .unwrap_or(self.min_cell_size.x)
+            if colspan == 1 && self.prev_state.col_width(self.col).is_none() {
+                self.curr_state
+                    .col_width(self.col)
+                    .unwrap_or(self.min_cell_size.x)
+            } else {
+                self.cell_width()
+            }
         };
 
         // If something above was wider, we can be wider:
         let width = width.max(self.curr_state.col_width(self.col).unwrap_or(0.0));
+        let width = if colspan == 1 {
+            width.at_most(column.max_width)
+        } else {
+            width
+        };
 
         let available = region.max_rect.intersect(region.cursor);
 
@@ -170,16 +293,20 @@ impl GridLayout {
     }
 
     pub(crate) fn next_cell(&self, cursor: Rect, child_size: Vec2) -> Rect {
-        let width = self.prev_state.col_width(self.col).unwrap_or(0.0);
-        let height = self.prev_row_height(self.row);
+        let width = if self.prev_state.col_width(self.col).is_some() {
+            self.cell_width()
+        } else {
+            0.0
+        };
+        let height = self.cell_height();
         let size = child_size.max(vec2(width, height));
         Rect::from_min_size(cursor.min, size)
     }
 
-    #[allow(clippy::unused_self)]
     pub(crate) fn align_size_within_rect(&self, size: Vec2, frame: Rect) -> Rect {
-        // TODO(emilk): allow this alignment to be customized
-        Align2::LEFT_CENTER.align_size_within_rect(size, frame)
+        self.column(self.col)
+            .align
+            .align_size_within_rect(size, frame)
     }
 
     pub(crate) fn justify_and_align(&self, frame: Rect, size: Vec2) -> Rect {
@@ -193,8 +320,8 @@ impl GridLayout {
             let debug_expand_height = self.style.debug.show_expand_height;
             if debug_expand_width || debug_expand_height {
                 let rect = widget_rect;
-                let too_wide = rect.width() > self.prev_col_width(self.col);
-                let too_high = rect.height() > self.prev_row_height(self.row);
+                let too_wide = rect.width() > self.cell_width();
+                let too_high = rect.height() > self.cell_height();
 
                 if (debug_expand_width && too_wide) || (debug_expand_height && too_high) {
                     let painter = self.ctx.debug_painter();
@@ -212,13 +339,46 @@ impl GridLayout {
             }
         }
 
-        self.curr_state
-            .set_min_col_width(self.col, widget_rect.width().max(self.min_cell_size.x));
-        self.curr_state
-            .set_min_row_height(self.row, widget_rect.height().max(self.min_cell_size.y));
+        let (colspan, rowspan) = self.next_cell_span;
+        let cell_width = self.cell_width();
+
+        if colspan == 1 {
+            self.curr_state
+                .set_min_col_width(self.col, widget_rect.width().max(self.min_cell_size.x));
+        }
+        if rowspan == 1 {
+            self.curr_state
+                .set_min_row_height(self.row, widget_rect.height().max(self.min_cell_size.y));
+        }
+        if colspan > 1 || rowspan > 1 {
+            self.spanned_cells.push(SpannedCell {
+                cols: self.col..self.col + colspan,
+                rows: self.row..self.row + rowspan,
+                size: widget_rect.size().max(self.min_cell_size),
+            });
+        }
+        self.bottom = self.bottom.max(widget_rect.bottom());
+
+        cursor.min.x += cell_width + self.spacing.x;
+        self.col += colspan;
+        self.next_cell_span = (1, 1);
+        self.skip_occupied_cells(cursor);
+    }
 
-        cursor.min.x += self.prev_col_width(self.col) + self.spacing.x;
-        self.col += 1;
+    /// Move the cursor past any cells covered by a row span from a previous row.
+    fn skip_occupied_cells(&mut self, cursor: &mut Rect) {
+        while let Some(cell) = self
+            .spanned_cells
+            .iter()
+            .find(|cell| cell.rows.start < self.row && cell.rows.contains(&self.row))
+            .filter(|cell| cell.cols.contains(&self.col))
+        {
+            let cols = self.col..cell.cols.end;
+            cursor.min.x += cols
+                .map(|col| self.col_width(col) + self.spacing.x)
+                .sum::<f32>();
+            self.col = cell.cols.end;
+        }
     }
 
     fn paint_row(&mut self, cursor: &Rect, painter: &Painter) {
@@ -233,7 +393,9 @@ impl GridLayout {
             return;
         };
         // Paint background for coming row:
-        let size = Vec2::new(self.prev_state.full_width(self.spacing.x), height);
+        let full_width = self.col_widths.iter().sum::<f32>()
+            + (self.col_widths.len().at_least(1) - 1) as f32 * self.spacing.x;
+        let size = Vec2::new(full_width, height);
         let rect = Rect::from_min_size(cursor.min, size);
         let rect = rect.expand2(0.5 * self.spacing.y * Vec2::Y);
         let rect = rect.expand2(2.0 * Vec2::X); // HACK: just looks better with some spacing on the sides
@@ -251,16 +413,149 @@ impl GridLayout {
 
         self.col = 0;
         self.row += 1;
+        self.next_cell_span = (1, 1);
+
+        if 0 < self.frozen_header_rows && self.row - self.first_row == self.frozen_header_rows {
+            let layer_id = painter.layer_id();
+            let num_shapes = self.ctx.graphics_mut(|g| g.list(layer_id).len());
+            let rect = Rect::from_x_y_ranges(
+                self.initial_available.x_range(),
+                self.initial_available.top()..=cursor.min.y - self.spacing.y,
+            );
+            self.header = Some((layer_id, self.first_header_shape..num_shapes, rect));
+        }
 
         self.paint_row(cursor, painter);
+        self.skip_occupied_cells(cursor);
     }
 
-    pub(crate) fn save(&self) {
+    pub(crate) fn save(&mut self) {
+        // Make sure the rows and columns are big enough for the cells spanning them:
+        for cell in std::mem::take(&mut self.spanned_cells) {
+            let width = cell
+                .cols
+                .clone()
+                .map(|col| self.curr_state.col_width(col).unwrap_or(0.0))
+                .sum::<f32>()
+                + (cell.cols.len() - 1) as f32 * self.spacing.x;
+            if let Some(last_col) = cell.cols.clone().last() {
+                let missing = cell.size.x - width;
+                if 0.0 < missing {
+                    let col_width = self.curr_state.col_width(last_col).unwrap_or(0.0);
+                    self.curr_state
+                        .set_min_col_width(last_col, col_width + missing);
+                }
+            }
+
+            let height = cell
+                .rows
+                .clone()
+                .map(|row| self.curr_state.row_height(row).unwrap_or(0.0))
+                .sum::<f32>()
+                + (cell.rows.len() - 1) as f32 * self.spacing.y;
+            if let Some(last_row) = cell.rows.clone().last() {
+                let missing = cell.size.y - height;
+                if 0.0 < missing {
+                    let row_height = self.curr_state.row_height(last_row).unwrap_or(0.0);
+                    self.curr_state
+                        .set_min_row_height(last_row, row_height + missing);
+                }
+            }
+        }
+
+        if let Some((layer_id, shapes, rect)) = self.header.take() {
+            self.freeze_header(layer_id, shapes, rect);
+        }
+
         if self.curr_state != self.prev_state {
+            if let Some(size_id) = self.size_id {
+                self.curr_state.clone().store(&self.ctx, size_id);
+            }
             self.curr_state.clone().store(&self.ctx, self.id);
             self.ctx.request_repaint();
         }
     }
+
+    /// If the header rows have been scrolled out of view, paint them again at the top.
+    fn freeze_header(&self, layer_id: LayerId, shapes: std::ops::Range<usize>, rect: Rect) {
+        let clip_rect = self.clip_rect;
+        let delta = (clip_rect.top() - rect.top()).at_most(self.bottom - rect.bottom());
+        if delta <= 0.0 {
+            return;
+        }
+        let delta = vec2(0.0, delta);
+
+        let background = Shape::rect_filled(
+            rect.translate(delta)
+                .expand2(0.5 * self.spacing.y * Vec2::Y),
+            0.0,
+            self.style.visuals.window_fill(),
+        );
+        self.ctx.graphics_mut(|g| {
+            let list = g.list(layer_id);
+            let header = list.copy_range(shapes, delta);
+            list.add(clip_rect, background);
+            list.extend_clipped(header);
+        });
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Options for one column of a [`Grid`], see [`Grid::column`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridColumn {
+    weight: f32,
+    min_width: f32,
+    max_width: f32,
+    align: Align2,
+}
+
+impl Default for GridColumn {
+    fn default() -> Self {
+        Self {
+            weight: 0.0,
+            min_width: 0.0,
+            max_width: f32::INFINITY,
+            align: Align2::LEFT_CENTER,
+        }
+    }
+}
+
+impl GridColumn {
+    /// Share the width left over by the other columns with the other weighted columns,
+    /// proportionally to `weight`.
+    ///
+    /// A column with weight `2.0` gets twice the extra width of a column with weight `1.0`.
+    /// Default: `0.0` (only as wide as the contents).
+    #[inline]
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Never make the column narrower than this.
+    #[inline]
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Wrap text and shrink widgets to make the column at most this wide.
+    #[inline]
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// How to align the contents of the cells in this column.
+    ///
+    /// Default: [`Align2::LEFT_CENTER`].
+    #[inline]
+    pub fn align(mut self, align: Align2) -> Self {
+        self.align = align;
+        self
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -268,7 +563,10 @@ impl GridLayout {
 /// A simple grid layout.
 ///
 /// The cells are always laid out left to right, top-down.
-/// The contents of each cell will be aligned to the left and center.
+/// The contents of each cell will be aligned to the left and center,
+/// unless configured otherwise with [`Grid::column`].
+///
+/// A cell can span several columns and rows, see [`Ui::set_grid_cell_span`].
 ///
 /// If you want to add multiple widgets to a cell you need to group them with
 /// [`Ui::horizontal`], [`Ui::vertical`] etc.
@@ -301,6 +599,9 @@ pub struct Grid {
     spacing: Option<Vec2>,
     start_row: usize,
     color_picker: Option<ColorPickerFn>,
+    size_id: Option<Id>,
+    columns: Vec<GridColumn>,
+    frozen_header_rows: usize,
 }
 
 impl Grid {
@@ -315,6 +616,9 @@ impl Grid {
             spacing: None,
             start_row: 0,
             color_picker: None,
+            size_id: None,
+            columns: Vec::new(),
+            frozen_header_rows: 0,
         }
     }
 
@@ -388,6 +692,54 @@ impl Grid {
         self.start_row = start_row;
         self
     }
+
+    /// Set the options of the next column.
+    ///
+    /// Call this once for each column you want to configure, starting with the first one.
+    /// Columns that are not configured use [`GridColumn::default`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::{Align2, Grid, GridColumn};
+    /// Grid::new("settings")
+    ///     .num_columns(2)
+    ///     .column(GridColumn::default().align(Align2::RIGHT_CENTER))
+    ///     .column(GridColumn::default().weight(1.0).min_width(100.0))
+    ///     .show(ui, |ui| {
+    ///         ui.label("Name:");
+    ///         ui.label("Alice");
+    ///         ui.end_row();
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn column(mut self, column: GridColumn) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Keep the first `num_rows` rows visible at the top when the grid is scrolled
+    /// (e.g. inside a [`ScrollArea`]).
+    ///
+    /// The frozen rows are painted on top of the rows below them,
+    /// but are only interactive where they would normally be.
+    #[inline]
+    pub fn freeze_header_rows(mut self, num_rows: usize) -> Self {
+        self.frozen_header_rows = num_rows;
+        self
+    }
+
+    /// A hash of everything that affects the sizes of the cells, i.e. the contents and the style.
+    ///
+    /// A [`Grid`] normally needs one frame to learn the sizes of its columns and rows,
+    /// and is invisible during that first frame.
+    /// If a grid with the same size hash has been shown before (e.g. in a popup that was closed),
+    /// its sizes are used right away instead.
+    #[inline]
+    pub fn size_hash(mut self, size_hash: impl std::hash::Hash) -> Self {
+        self.size_id = Some(Id::new(("grid_size", size_hash)));
+        self
+    }
 }
 
 impl Grid {
@@ -409,6 +761,9 @@ impl Grid {
             spacing,
             start_row,
             mut color_picker,
+            size_id,
+            columns,
+            frozen_header_rows,
         } = self;
         let min_col_width = min_col_width.unwrap_or_else(|| ui.spacing().interact_size.x);
         let min_row_height = min_row_height.unwrap_or_else(|| ui.spacing().interact_size.y);
@@ -418,7 +773,8 @@ impl Grid {
         }
 
         let id = ui.make_persistent_id(id_source);
-        let prev_state = State::load(ui.ctx(), id);
+        let prev_state = State::load(ui.ctx(), id)
+            .or_else(|| size_id.and_then(|size_id| State::load(ui.ctx(), size_id)));
 
         // Each grid cell is aligned LEFT_CENTER.
         // If somebody wants to wrap more things inside a cell,
@@ -436,8 +792,12 @@ impl Grid {
                     max_cell_size,
                     spacing,
                     row: start_row,
+                    size_id,
+                    columns,
+                    frozen_header_rows,
                     ..GridLayout::new(ui, id, prev_state)
                 };
+                grid.layout_columns(ui);
 
                 // paint first incoming row
                 if is_color {
@@ -462,3 +822,78 @@ fn striped_row_color(row: usize, style: &Style) -> Option<Color32> {
     }
     None
 }
+
+#[test]
+fn grid_size_hash_skips_invisible_first_frame() {
+    let ctx = Context::default();
+    let mut is_visible = vec![];
+    for grid_id in ["first", "second"] {
+        for _ in 0..2 {
+            let _ = ctx.run(Default::default(), |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    Grid::new(grid_id).size_hash("contents").show(ui, |ui| {
+                        is_visible.push(ui.is_visible());
+                        ui.label("Hello");
+                        ui.label("World");
+                        ui.end_row();
+                    });
+                });
+            });
+        }
+    }
+    assert_eq!(is_visible, [false, true, true, true]);
+}
+
+#[test]
+fn grid_spans_and_weights() {
+    let ctx = Context::default();
+    let mut rects = vec![];
+    let mut weighted_width = 0.0;
+    for _ in 0..3 {
+        rects.clear();
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                Grid::new("grid").show(ui, |ui| {
+                    // A cell covering two rows, and a wide cell covering two columns:
+                    ui.set_grid_cell_span(1, 2);
+                    rects.push(ui.allocate_space(vec2(50.0, 50.0)).1);
+                    ui.set_grid_cell_span(2, 1);
+                    rects.push(ui.allocate_space(vec2(200.0, 20.0)).1);
+                    ui.end_row();
+
+                    // The first column is covered by the row span:
+                    rects.push(ui.allocate_space(vec2(50.0, 20.0)).1);
+                    rects.push(ui.allocate_space(vec2(50.0, 20.0)).1);
+                    ui.end_row();
+                });
+
+                Grid::new("weights")
+                    .column(GridColumn::default())
+                    .column(GridColumn::default().weight(1.0))
+                    .column(GridColumn::default())
+                    .show(ui, |ui| {
+                        ui.allocate_space(vec2(50.0, 20.0));
+                        weighted_width = ui.available_width();
+                        ui.allocate_space(vec2(50.0, 20.0));
+                        ui.allocate_space(vec2(50.0, 20.0));
+                        ui.end_row();
+                    });
+            });
+        });
+    }
+
+    let spacing = ctx.style().spacing.item_spacing;
+    assert_eq!(
+        rects[2].left(),
+        rects[1].left(),
+        "Skipped the row-spanned cell"
+    );
+    assert_eq!(
+        rects[3].left() - rects[2].left(),
+        50.0 + spacing.x,
+        "The column span should only widen the last column"
+    );
+
+    let available_width = ctx.screen_rect().shrink(8.0).width();
+    assert_eq!(weighted_width, available_width - 100.0 - 2.0 * spacing.x);
+}
@@ -0,0 +1,79 @@
+//! Recording and replaying of [`RawInput`], see [`crate::Context::record_input`].
+//!
+//! A recording is a text file with one [`RawInput`] per line, encoded as RON,
+//! in the order the frames were run (interleaved for all viewports).
+
+use std::{
+    collections::VecDeque,
+    io::{BufRead as _, Write as _},
+    path::Path,
+};
+
+use crate::{RawInput, ViewportId};
+
+fn ron_error(err: impl std::error::Error + Send + Sync + 'static) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+}
+
+/// Writes the input of each frame to a file.
+pub(crate) struct InputRecorder {
+    writer: std::io::BufWriter<std::fs::File>,
+}
+
+impl InputRecorder {
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        let file = std::fs::File::create(path)?;
+        Ok(Self {
+            writer: std::io::BufWriter::new(file),
+        })
+    }
+
+    /// Append one frame.
+    ///
+    /// We flush after every frame, so that the recording is complete even if the app crashes.
+    pub fn record(&mut self, raw_input: &RawInput) -> std::io::Result<()> {
+        let line = ron::to_string(raw_input).map_err(ron_error)?;
+        writeln!(self.writer, "{line}")?;
+        self.writer.flush()
+    }
+}
+
+/// The remaining frames of a recording that is being replayed.
+pub(crate) struct InputReplay {
+    frames: VecDeque<RawInput>,
+}
+
+impl InputReplay {
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        let mut frames = VecDeque::new();
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                frames.push_back(ron::from_str(&line).map_err(ron_error)?);
+            }
+        }
+        Ok(Self { frames })
+    }
+
+    /// The next recorded frame for the given viewport, if any.
+    ///
+    /// Frames of other viewports that were recorded before it are kept,
+    /// so each viewport replays its own frames in order.
+    pub fn next_frame(&mut self, viewport_id: ViewportId) -> Option<RawInput> {
+        let index = self
+            .frames
+            .iter()
+            .position(|frame| frame.viewport_id == viewport_id)?;
+        self.frames.remove(index)
+    }
+
+    /// The viewport of the next recorded frame, which should be repainted next.
+    pub fn next_viewport(&self) -> Option<ViewportId> {
+        self.frames.front().map(|frame| frame.viewport_id)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
@@ -1,12 +1,12 @@
 mod touch_state;
 
 use crate::data::input::*;
-use crate::{emath::*, util::History};
+use crate::{emath::*, util::History, Margin};
 use std::collections::{BTreeMap, HashSet};
 
 pub use crate::data::input::Key;
-pub use touch_state::MultiTouchInfo;
 use touch_state::TouchState;
+pub use touch_state::{Gesture, MultiTouchInfo};
 
 /// If the pointer moves more than this, it won't become a click (but it is still a drag)
 const MAX_CLICK_DIST: f32 = 6.0; // TODO(emilk): move to settings
@@ -33,6 +33,18 @@ pub struct InputState {
     /// (We keep a separate [`TouchState`] for each encountered touch device.)
     touch_states: BTreeMap<TouchDeviceId, TouchState>,
 
+    /// Touch gestures recognized this frame that haven't been consumed yet.
+    gestures: Vec<Gesture>,
+
+    /// Did the latest pointer event come from a touch screen (rather than e.g. a mouse)?
+    last_pointer_was_touch: bool,
+
+    /// The orientation of the screen this frame, once we know it.
+    orientation: Option<Orientation>,
+
+    /// Did the orientation change since last frame?
+    orientation_changed: bool,
+
     /// How many points the user scrolled.
     ///
     /// The delta dictates how the _content_ should move.
@@ -51,6 +63,9 @@ pub struct InputState {
     /// * `zoom > 1`: pinch spread
     zoom_factor_delta: f32,
 
+    /// Has the app claimed the pointer this frame with [`Self::consume_pointer`]?
+    pointer_consumed: bool,
+
     /// Position and size of the egui area.
     pub screen_rect: Rect,
 
@@ -115,6 +130,10 @@ pub struct InputState {
     // The keys that are currently being held down.
     pub keys_down: HashSet<Key>,
 
+    /// The physical keys that are currently being held down,
+    /// see [`crate::Event::Key::physical_key`].
+    pub physical_keys_down: HashSet<Key>,
+
     /// In-order events received this frame
     pub events: Vec<Event>,
 }
@@ -125,8 +144,13 @@ impl Default for InputState {
             raw: Default::default(),
             pointer: Default::default(),
             touch_states: Default::default(),
+            gestures: Default::default(),
+            last_pointer_was_touch: false,
+            orientation: None,
+            orientation_changed: false,
             scroll_delta: Vec2::ZERO,
             zoom_factor_delta: 1.0,
+            pointer_consumed: false,
             screen_rect: Rect::from_min_size(Default::default(), vec2(10_000.0, 10_000.0)),
             pixels_per_point: 1.0,
             max_texture_side: 2048,
@@ -137,6 +161,7 @@ impl Default for InputState {
             focused: false,
             modifiers: Default::default(),
             keys_down: Default::default(),
+            physical_keys_down: Default::default(),
             events: Default::default(),
         }
     }
@@ -152,7 +177,15 @@ impl InputState {
     ) -> Self {
         crate::profile_function!();
 
+        if let (Some(previous), Some(current)) = (
+            self.raw.viewports.get(&new.viewport_id),
+            new.viewports.get_mut(&new.viewport_id),
+        ) {
+            current.add_change_events(previous);
+        }
+
         let time = new.time.unwrap_or(self.time + new.predicted_dt as f64);
+        new.fill_event_times(time);
         let unstable_dt = (time - self.time) as f32;
 
         let stable_dt = if requested_repaint_last_frame {
@@ -164,19 +197,44 @@ impl InputState {
         };
 
         let screen_rect = new.screen_rect.unwrap_or(self.screen_rect);
+        let orientation = new
+            .screen_rect
+            .map(|rect| Orientation::from_size(rect.size()))
+            .or(self.orientation);
+        let orientation_changed = self.orientation.is_some() && orientation != self.orientation;
         self.create_touch_states_for_new_devices(&new.events);
+        let mut gestures = vec![];
         for touch_state in self.touch_states.values_mut() {
             touch_state.begin_frame(time, &new, self.pointer.interact_pos);
+            gestures.extend_from_slice(touch_state.gestures());
         }
         let pointer = self.pointer.begin_frame(time, &new);
 
+        // Backends usually emit pointer events alongside touch events,
+        // so any touch this frame means the pointer is a finger.
+        let last_pointer_was_touch = if new.events.iter().any(|e| matches!(e, Event::Touch { .. }))
+        {
+            true
+        } else if new.events.iter().any(|e| {
+            matches!(
+                e,
+                Event::PointerMoved(_) | Event::PointerButton { .. } | Event::Pen(_)
+            )
+        }) {
+            false
+        } else {
+            self.last_pointer_was_touch
+        };
+
         let mut keys_down = self.keys_down;
+        let mut physical_keys_down = self.physical_keys_down;
         let mut scroll_delta = Vec2::ZERO;
         let mut zoom_factor_delta = 1.0;
         for event in &mut new.events {
             match event {
                 Event::Key {
                     key,
+                    physical_key,
                     pressed,
                     repeat,
                     ..
@@ -184,8 +242,12 @@ impl InputState {
                     if *pressed {
                         let first_press = keys_down.insert(*key);
                         *repeat = !first_press;
+                        physical_keys_down.extend(*physical_key);
                     } else {
                         keys_down.remove(key);
+                        if let Some(physical_key) = physical_key {
+                            physical_keys_down.remove(physical_key);
+                        }
                     }
                 }
                 Event::Scroll(delta) => {
@@ -210,13 +272,19 @@ impl InputState {
             // Therefore we clear all the modifiers and down keys here to avoid that.
             modifiers = Default::default();
             keys_down = Default::default();
+            physical_keys_down = Default::default();
         }
 
         Self {
             pointer,
             touch_states: self.touch_states,
+            gestures,
+            last_pointer_was_touch,
+            orientation,
+            orientation_changed,
             scroll_delta,
             zoom_factor_delta,
+            pointer_consumed: false,
             screen_rect,
             pixels_per_point,
             max_texture_side: new.max_texture_side.unwrap_or(self.max_texture_side),
@@ -227,6 +295,7 @@ impl InputState {
             focused: new.focused,
             modifiers,
             keys_down,
+            physical_keys_down,
             events: new.events.clone(), // TODO(emilk): remove clone() and use raw.events
             raw: new,
         }
@@ -243,6 +312,52 @@ impl InputState {
         self.screen_rect
     }
 
+    /// How many points at the bottom of the screen are covered by an on-screen (virtual) keyboard.
+    ///
+    /// Use this to keep the focused text field visible while the keyboard is shown:
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut text = String::new();
+    /// let response = ui.text_edit_singleline(&mut text);
+    /// let visible_rect = ui.input(|i| {
+    ///     let mut rect = i.screen_rect();
+    ///     rect.max.y -= i.virtual_keyboard_inset();
+    ///     rect
+    /// });
+    /// if response.has_focus() && !visible_rect.contains_rect(response.rect) {
+    ///     ui.scroll_to_rect(response.rect, Some(egui::Align::Center));
+    /// }
+    /// # });
+    /// ```
+    ///
+    /// This is zero if there is no keyboard, or if the backend doesn't know.
+    pub fn virtual_keyboard_inset(&self) -> f32 {
+        self.viewport().virtual_keyboard_inset.unwrap_or(0.0)
+    }
+
+    /// The parts of the screen covered by e.g. a display notch, rounded corners or system bars.
+    ///
+    /// Keep important content inside `i.safe_area_insets().shrink_rect(i.screen_rect())`,
+    /// or use [`crate::CentralPanel::respect_safe_area`].
+    ///
+    /// This is zero on desktop, or if the backend doesn't know.
+    pub fn safe_area_insets(&self) -> Margin {
+        self.viewport().safe_area_insets.unwrap_or_default()
+    }
+
+    /// Is the screen taller than it is wide?
+    #[inline]
+    pub fn orientation(&self) -> Orientation {
+        Orientation::from_size(self.screen_rect.size())
+    }
+
+    /// Did the [`Self::orientation`] change since last frame, e.g. because a phone was rotated?
+    #[inline(always)]
+    pub fn orientation_changed(&self) -> bool {
+        self.orientation_changed
+    }
+
     /// Zoom scale factor this frame (e.g. from ctrl-scroll or pinch gesture).
     /// * `zoom = 1`: no change
     /// * `zoom < 1`: pinch together
@@ -282,8 +397,57 @@ impl InputState {
         )
     }
 
+    /// Claim the pointer for your own use (e.g. to control a game) if it is within `area`.
+    ///
+    /// If the pointer is within `area`, all pointer events this frame (clicks, scrolling, zooming, touches, …)
+    /// are consumed, no egui widget will be hovered or clicked, and `true` is returned.
+    /// [`crate::Context::wants_pointer_input`] will then return `false`,
+    /// unless egui was already using the pointer (e.g. the user is dragging a slider).
+    ///
+    /// Call this before adding any widgets:
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// # let viewport_3d = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(100.0, 100.0));
+    /// if ctx.input_mut(|i| i.consume_pointer(viewport_3d)) {
+    ///     // Handle the pointer in the 3D view
+    /// }
+    /// # });
+    /// ```
+    pub fn consume_pointer(&mut self, area: Rect) -> bool {
+        let inside = self
+            .pointer
+            .hover_pos()
+            .map_or(false, |pos| area.contains(pos));
+        if inside {
+            self.pointer_consumed = true;
+            self.scroll_delta = Vec2::ZERO;
+            self.zoom_factor_delta = 1.0;
+            self.events.retain(|event| {
+                !matches!(
+                    event,
+                    Event::PointerButton { .. }
+                        | Event::Scroll(_)
+                        | Event::Zoom(_)
+                        | Event::Touch { .. }
+                        | Event::MouseWheel { .. }
+                )
+            });
+        }
+        inside
+    }
+
+    /// Has the pointer been claimed by [`Self::consume_pointer`] this frame?
+    #[inline(always)]
+    pub fn is_pointer_consumed(&self) -> bool {
+        self.pointer_consumed
+    }
+
     pub fn wants_repaint(&self) -> bool {
-        self.pointer.wants_repaint() || self.scroll_delta != Vec2::ZERO || !self.events.is_empty()
+        self.pointer.wants_repaint()
+            || self.scroll_delta != Vec2::ZERO
+            || !self.events.is_empty()
+            || self.touch_states.values().any(|t| t.wants_repaint())
     }
 
     /// Count presses of a key. If non-zero, the presses are consumed, so that this will only return non-zero once.
@@ -296,18 +460,41 @@ impl InputState {
     /// i.e. check for `Cmd-Shift-S` ("Save as…") before `Cmd-S` ("Save"),
     /// so that a user pressing `Cmd-Shift-S` won't trigger the wrong command!
     pub fn count_and_consume_key(&mut self, modifiers: Modifiers, logical_key: Key) -> usize {
+        self.count_and_consume_key_impl(modifiers, logical_key, false)
+    }
+
+    /// Like [`Self::count_and_consume_key`], but matches the physical position of the key,
+    /// regardless of keyboard layout. See [`crate::Event::Key::physical_key`].
+    pub fn count_and_consume_physical_key(
+        &mut self,
+        modifiers: Modifiers,
+        physical_key: Key,
+    ) -> usize {
+        self.count_and_consume_key_impl(modifiers, physical_key, true)
+    }
+
+    fn count_and_consume_key_impl(
+        &mut self,
+        modifiers: Modifiers,
+        desired_key: Key,
+        physical: bool,
+    ) -> usize {
         let mut count = 0usize;
 
         self.events.retain(|event| {
-            let is_match = matches!(
-                event,
+            let is_match = match event {
                 Event::Key {
-                    key: ev_key,
+                    key,
+                    physical_key,
                     modifiers: ev_mods,
                     pressed: true,
                     ..
-                } if *ev_key == logical_key && ev_mods.matches_logically(modifiers)
-            );
+                } => {
+                    let key = if physical { *physical_key } else { Some(*key) };
+                    key == Some(desired_key) && ev_mods.matches_logically(modifiers)
+                }
+                _ => false,
+            };
 
             count += is_match as usize;
 
@@ -330,6 +517,29 @@ impl InputState {
         self.count_and_consume_key(modifiers, logical_key) > 0
     }
 
+    /// Like [`Self::consume_key`], but the modifiers must match exactly
+    /// (using [`Modifiers::matches_exact`]).
+    ///
+    /// This means `Cmd-S` will not consume a `Cmd-Shift-S` key press,
+    /// so the order you check your shortcuts in doesn't matter.
+    pub fn consume_key_exact(&mut self, modifiers: Modifiers, logical_key: Key) -> bool {
+        let mut consumed = false;
+        self.events.retain(|event| {
+            let is_match = matches!(
+                event,
+                Event::Key {
+                    key,
+                    modifiers: ev_mods,
+                    pressed: true,
+                    ..
+                } if *key == logical_key && ev_mods.matches_exact(modifiers)
+            );
+            consumed |= is_match;
+            !is_match
+        });
+        consumed
+    }
+
     /// Check if the given shortcut has been pressed.
     ///
     /// If so, `true` is returned and the key pressed is consumed, so that this will only return `true` once.
@@ -343,8 +553,9 @@ impl InputState {
         let KeyboardShortcut {
             modifiers,
             logical_key,
+            physical,
         } = *shortcut;
-        self.consume_key(modifiers, logical_key)
+        self.count_and_consume_key_impl(modifiers, logical_key, physical) > 0
     }
 
     /// Was the given key pressed this frame?
@@ -375,6 +586,27 @@ impl InputState {
         self.keys_down.contains(&desired_key)
     }
 
+    /// Was the key at the given physical position pressed this frame?
+    ///
+    /// This ignores the keyboard layout, see [`crate::Event::Key::physical_key`].
+    /// Includes key-repeat events.
+    pub fn physical_key_pressed(&self, desired_key: Key) -> bool {
+        self.events.iter().any(|event| {
+            matches!(
+                event,
+                Event::Key { physical_key: Some(key), pressed: true, .. }
+                if *key == desired_key
+            )
+        })
+    }
+
+    /// Is the key at the given physical position currently held down?
+    ///
+    /// This ignores the keyboard layout, see [`crate::Event::Key::physical_key`].
+    pub fn physical_key_down(&self, desired_key: Key) -> bool {
+        self.physical_keys_down.contains(&desired_key)
+    }
+
     /// Was the given key released this frame?
     pub fn key_released(&self, desired_key: Key) -> bool {
         self.events.iter().any(|event| {
@@ -389,6 +621,19 @@ impl InputState {
         })
     }
 
+    /// Was the given gamepad button pressed this frame?
+    pub fn gamepad_button_pressed(&self, desired_button: GamepadButton) -> bool {
+        self.events.iter().any(|event| {
+            matches!(
+                event,
+                Event::GamepadButton {
+                    button,
+                    pressed: true,
+                } if *button == desired_button
+            )
+        })
+    }
+
     /// Also known as device pixel ratio, > 1 for high resolution screens.
     #[inline(always)]
     pub fn pixels_per_point(&self) -> f32 {
@@ -445,6 +690,39 @@ impl InputState {
         }
     }
 
+    /// The touch gestures (pinch, rotate, swipe, long-press) recognized this frame,
+    /// that haven't been consumed yet.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// for gesture in ui.input(|i| i.gestures().to_vec()) {
+    ///     if let egui::Gesture::Swipe { num_touches: 3, delta, .. } = gesture {
+    ///         // e.g. switch tabs
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
+
+    /// Remove and return the first gesture matching the filter, so no one else will see it.
+    ///
+    /// See also [`crate::Ui::consume_gesture`].
+    pub fn consume_gesture(&mut self, filter: impl Fn(&Gesture) -> bool) -> Option<Gesture> {
+        let index = self.gestures.iter().position(filter)?;
+        Some(self.gestures.remove(index))
+    }
+
+    /// Did the latest pointer event come from a touch screen (rather than e.g. a mouse)?
+    ///
+    /// This stays `true` after the finger is lifted, until a mouse is used.
+    /// Used by [`crate::style::TouchMode::Auto`].
+    #[inline(always)]
+    pub fn last_pointer_was_touch(&self) -> bool {
+        self.last_pointer_was_touch
+    }
+
     /// True if there currently are any fingers touching egui.
     pub fn any_touches(&self) -> bool {
         !self.touch_states.is_empty()
@@ -550,6 +828,29 @@ impl PointerEvent {
     }
 }
 
+/// Is the screen taller than it is wide, or the other way around?
+///
+/// See [`InputState::orientation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Orientation {
+    /// Taller than wide.
+    Portrait,
+
+    /// Wider than tall (or square).
+    Landscape,
+}
+
+impl Orientation {
+    pub fn from_size(size: Vec2) -> Self {
+        if size.y > size.x {
+            Self::Portrait
+        } else {
+            Self::Landscape
+        }
+    }
+}
+
 /// Mouse or touch state.
 #[derive(Clone, Debug)]
 pub struct PointerState {
@@ -577,6 +878,13 @@ pub struct PointerState {
     /// Current velocity of pointer.
     velocity: Vec2,
 
+    /// Sum of all [`Event::MouseMoved`] this frame,
+    /// or `None` if there were none.
+    motion: Option<Vec2>,
+
+    /// Latest [`Event::Pen`], until the pointer is gone.
+    pen: Option<PenInput>,
+
     /// Recent movement of the pointer.
     /// Used for calculating velocity of pointer.
     pos_history: History<Pos2>,
@@ -619,6 +927,8 @@ impl Default for PointerState {
             interact_pos: None,
             delta: Vec2::ZERO,
             velocity: Vec2::ZERO,
+            motion: None,
+            pen: None,
             pos_history: History::new(0..1000, 0.1),
             down: Default::default(),
             press_origin: None,
@@ -641,6 +951,7 @@ impl PointerState {
 
         let old_pos = self.latest_pos;
         self.interact_pos = self.latest_pos;
+        self.motion = None;
 
         for event in &new.events {
             match event {
@@ -724,8 +1035,15 @@ impl PointerState {
                 }
                 Event::PointerGone => {
                     self.latest_pos = None;
+                    self.pen = None;
                     // NOTE: we do NOT clear `self.interact_pos` here. It will be cleared next frame.
                 }
+                Event::MouseMoved(delta) => {
+                    *self.motion.get_or_insert(Vec2::ZERO) += *delta;
+                }
+                Event::Pen(pen) => {
+                    self.pen = Some(*pen);
+                }
                 _ => {}
             }
         }
@@ -759,7 +1077,7 @@ impl PointerState {
     }
 
     fn wants_repaint(&self) -> bool {
-        !self.pointer_events.is_empty() || self.delta != Vec2::ZERO
+        !self.pointer_events.is_empty() || self.delta != Vec2::ZERO || self.motion.is_some()
     }
 
     /// How much the pointer moved compared to last frame, in points.
@@ -774,6 +1092,28 @@ impl PointerState {
         self.velocity
     }
 
+    /// The raw, unaccelerated mouse motion this frame, as reported by the OS.
+    ///
+    /// Unlike [`Self::delta`] this keeps working when the pointer hits the edge of the screen,
+    /// or is locked with [`crate::Context::set_pointer_lock`],
+    /// which makes it a good fit for controlling a 3D camera.
+    ///
+    /// `None` if there was no motion, or if the integration does not support it.
+    /// See [`Event::MouseMoved`].
+    #[inline(always)]
+    pub fn motion(&self) -> Option<Vec2> {
+        self.motion
+    }
+
+    /// The latest pen/stylus state (pressure, tilt, …), if the pointer is a pen.
+    ///
+    /// This only holds the last sample. For smooth pressure-sensitive strokes,
+    /// go through all the [`Event::Pen`] in [`InputState::events`] instead.
+    #[inline(always)]
+    pub fn pen(&self) -> Option<&PenInput> {
+        self.pen.as_ref()
+    }
+
     /// Where did the current click/drag originate?
     /// `None` if no mouse button is down.
     #[inline(always)]
@@ -1006,8 +1346,13 @@ impl InputState {
             raw,
             pointer,
             touch_states,
+            gestures,
+            last_pointer_was_touch,
+            orientation,
+            orientation_changed,
             scroll_delta,
             zoom_factor_delta,
+            pointer_consumed,
             screen_rect,
             pixels_per_point,
             max_texture_side,
@@ -1018,6 +1363,7 @@ impl InputState {
             focused,
             modifiers,
             keys_down,
+            physical_keys_down,
             events,
         } = self;
 
@@ -1041,8 +1387,13 @@ impl InputState {
             });
         }
 
+        ui.label(format!("gestures: {gestures:?}"));
+        ui.label(format!("last_pointer_was_touch: {last_pointer_was_touch}"));
+        ui.label(format!("orientation: {orientation:?}"));
+        ui.label(format!("orientation_changed: {orientation_changed}"));
         ui.label(format!("scroll_delta: {scroll_delta:?} points"));
         ui.label(format!("zoom_factor_delta: {zoom_factor_delta:4.2}x"));
+        ui.label(format!("pointer_consumed: {pointer_consumed}"));
         ui.label(format!("screen_rect: {screen_rect:?} points"));
         ui.label(format!(
             "{pixels_per_point} physical pixels for each logical point"
@@ -1060,6 +1411,7 @@ impl InputState {
         ui.label(format!("focused:   {focused}"));
         ui.label(format!("modifiers: {modifiers:#?}"));
         ui.label(format!("keys_down: {keys_down:?}"));
+        ui.label(format!("physical_keys_down: {physical_keys_down:?}"));
         ui.scope(|ui| {
             ui.set_min_height(150.0);
             ui.label(format!("events: {events:#?}"))
@@ -1076,6 +1428,8 @@ impl PointerState {
             interact_pos,
             delta,
             velocity,
+            motion,
+            pen,
             pos_history: _,
             down,
             press_origin,
@@ -1094,6 +1448,8 @@ impl PointerState {
             "velocity: [{:3.0} {:3.0}] points/sec",
             velocity.x, velocity.y
         ));
+        ui.label(format!("motion: {motion:?}"));
+        ui.label(format!("pen: {pen:?}"));
         ui.label(format!("down: {down:#?}"));
         ui.label(format!("press_origin: {press_origin:?}"));
         ui.label(format!("press_start_time: {press_start_time:?} s"));
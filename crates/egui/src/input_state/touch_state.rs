@@ -6,6 +6,74 @@ use crate::{
     Event, RawInput, TouchId, TouchPhase,
 };
 
+/// How long a single finger must rest before it is a [`Gesture::LongPress`].
+const LONG_PRESS_DURATION: f64 = 0.5;
+
+/// How far a finger may wander and still be considered resting, for [`Gesture::LongPress`].
+const LONG_PRESS_MAX_DISTANCE: f32 = 10.0;
+
+/// A [`Gesture::Swipe`] must be at least this long…
+const SWIPE_MIN_DISTANCE: f32 = 50.0;
+
+/// …and take no longer than this.
+const SWIPE_MAX_DURATION: f64 = 0.5;
+
+/// A high-level touch gesture, recognized from the raw [`Event::Touch`] events.
+///
+/// Read them with [`crate::InputState::gestures`], or claim them for a part of the ui
+/// with [`crate::Ui::consume_gesture`].
+///
+/// Positions are in touch device coordinates, which for touch screens are the same as screen points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// Two or more fingers are pinching, reported every frame the distance between them changes.
+    Pinch {
+        /// The center of the fingers.
+        center: Pos2,
+
+        /// Zoom factor compared to the previous frame.
+        /// `< 1` when pinching together, `> 1` when spreading.
+        zoom_delta: f32,
+    },
+
+    /// Two or more fingers are rotating around each other, reported every frame they rotate.
+    Rotate {
+        /// The center of the fingers.
+        center: Pos2,
+
+        /// Rotation in radians compared to the previous frame.
+        rotation_delta: f32,
+    },
+
+    /// Two or more fingers moved quickly in the same direction and were lifted.
+    Swipe {
+        /// Where the swipe started.
+        start: Pos2,
+
+        /// How many fingers were used.
+        num_touches: usize,
+
+        /// The total movement of the fingers.
+        delta: Vec2,
+    },
+
+    /// A single finger rested in the same place for a while.
+    ///
+    /// Reported once, while the finger is still down.
+    LongPress { pos: Pos2 },
+}
+
+impl Gesture {
+    /// Where the gesture took place.
+    pub fn pos(&self) -> Pos2 {
+        match *self {
+            Self::Pinch { center, .. } | Self::Rotate { center, .. } => center,
+            Self::Swipe { start, .. } => start,
+            Self::LongPress { pos } => pos,
+        }
+    }
+}
+
 /// All you probably need to know about a multi-touch gesture.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct MultiTouchInfo {
@@ -80,6 +148,36 @@ pub(crate) struct TouchState {
     /// If a gesture has been recognized (i.e. when exactly two fingers touch the surface), this
     /// holds state information
     gesture_state: Option<GestureState>,
+
+    /// Tracks a potential [`Gesture::Swipe`].
+    swipe: Option<SwipeTracker>,
+
+    /// Tracks a potential [`Gesture::LongPress`].
+    long_press: Option<LongPressTracker>,
+
+    /// The gestures recognized this frame.
+    gestures: Vec<Gesture>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SwipeTracker {
+    start_time: f64,
+    start_pos: Pos2,
+    last_pos: Pos2,
+    num_touches: usize,
+
+    /// Some fingers were lifted, so the swipe has been evaluated.
+    /// Wait for the rest of the fingers to be lifted before tracking a new one.
+    done: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LongPressTracker {
+    start_time: f64,
+    start_pos: Pos2,
+
+    /// Either reported, or cancelled because the finger moved.
+    done: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -127,6 +225,9 @@ impl TouchState {
             device_id,
             active_touches: Default::default(),
             gesture_state: None,
+            swipe: None,
+            long_press: None,
+            gestures: Vec::new(),
         }
     }
 
@@ -170,12 +271,109 @@ impl TouchState {
                 state.previous = None;
             }
         }
+
+        self.recognize_gestures(time);
     }
 
     pub fn is_active(&self) -> bool {
         self.gesture_state.is_some()
     }
 
+    /// The gestures recognized this frame.
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
+
+    /// Are we waiting for time to pass before we can recognize a gesture?
+    pub fn wants_repaint(&self) -> bool {
+        self.long_press.is_some_and(|long_press| !long_press.done)
+    }
+
+    fn recognize_gestures(&mut self, time: f64) {
+        self.gestures.clear();
+
+        let num_touches = self.active_touches.len();
+
+        // Pinch and rotate:
+        if let Some(state) = &self.gesture_state {
+            if state.previous.is_some() {
+                let center = state.current.avg_pos;
+                if let Some(info) = self.info() {
+                    if info.zoom_delta != 1.0 {
+                        self.gestures.push(Gesture::Pinch {
+                            center,
+                            zoom_delta: info.zoom_delta,
+                        });
+                    }
+                    if info.rotation_delta != 0.0 {
+                        self.gestures.push(Gesture::Rotate {
+                            center,
+                            rotation_delta: info.rotation_delta,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Swipe:
+        let avg_pos = self
+            .gesture_state
+            .as_ref()
+            .map(|state| state.current.avg_pos);
+        match (&mut self.swipe, avg_pos) {
+            (None, Some(avg_pos)) => {
+                self.swipe = Some(SwipeTracker {
+                    start_time: time,
+                    start_pos: avg_pos,
+                    last_pos: avg_pos,
+                    num_touches,
+                    done: false,
+                });
+            }
+            (Some(swipe), Some(avg_pos)) if !swipe.done => {
+                if swipe.num_touches <= num_touches {
+                    swipe.num_touches = num_touches;
+                    swipe.last_pos = avg_pos;
+                } else {
+                    // A finger was lifted: evaluate using the position from before that,
+                    // since the average position jumps when the number of fingers changes.
+                    swipe.done = true;
+                    self.gestures.extend(swipe.gesture(time));
+                }
+            }
+            (Some(swipe), None) => {
+                if !swipe.done {
+                    self.gestures.extend(swipe.gesture(time));
+                }
+                self.swipe = None;
+            }
+            _ => {}
+        }
+
+        // Long press:
+        if num_touches == 1 {
+            let pos = self.active_touches.values().next().unwrap().pos;
+            if let Some(long_press) = &mut self.long_press {
+                if !long_press.done {
+                    if long_press.start_pos.distance(pos) > LONG_PRESS_MAX_DISTANCE {
+                        long_press.done = true;
+                    } else if LONG_PRESS_DURATION <= time - long_press.start_time {
+                        long_press.done = true;
+                        self.gestures.push(Gesture::LongPress { pos });
+                    }
+                }
+            } else {
+                self.long_press = Some(LongPressTracker {
+                    start_time: time,
+                    start_pos: pos,
+                    done: false,
+                });
+            }
+        } else {
+            self.long_press = None;
+        }
+    }
+
     pub fn info(&self) -> Option<MultiTouchInfo> {
         self.gesture_state.as_ref().map(|state| {
             // state.previous can be `None` when the number of simultaneous touches has just
@@ -284,6 +482,18 @@ impl TouchState {
     }
 }
 
+impl SwipeTracker {
+    fn gesture(&self, time: f64) -> Option<Gesture> {
+        let delta = self.last_pos - self.start_pos;
+        (time - self.start_time <= SWIPE_MAX_DURATION && SWIPE_MIN_DISTANCE <= delta.length())
+            .then_some(Gesture::Swipe {
+                start: self.start_pos,
+                num_touches: self.num_touches,
+                delta,
+            })
+    }
+}
+
 impl TouchState {
     pub fn ui(&self, ui: &mut crate::Ui) {
         ui.label(format!("{self:?}"));
@@ -337,3 +547,77 @@ impl PinchType {
         }
     }
 }
+
+#[test]
+fn test_gestures() {
+    fn touch(id: u64, phase: TouchPhase, pos: Pos2) -> Event {
+        Event::Touch {
+            device_id: TouchDeviceId(0),
+            id: TouchId(id),
+            phase,
+            pos,
+            force: None,
+        }
+    }
+    fn frame(state: &mut TouchState, time: f64, events: Vec<Event>) -> Vec<Gesture> {
+        let raw_input = RawInput {
+            events,
+            ..Default::default()
+        };
+        state.begin_frame(time, &raw_input, Some(Pos2::ZERO));
+        state.gestures().to_vec()
+    }
+
+    // Long press:
+    let mut state = TouchState::new(TouchDeviceId(0));
+    let pos = Pos2::new(10.0, 10.0);
+    assert!(frame(&mut state, 0.0, vec![touch(0, TouchPhase::Start, pos)]).is_empty());
+    assert!(state.wants_repaint());
+    assert!(frame(&mut state, 0.2, vec![]).is_empty());
+    assert_eq!(
+        frame(&mut state, 0.6, vec![]),
+        vec![Gesture::LongPress { pos }]
+    );
+    assert!(
+        frame(&mut state, 1.0, vec![]).is_empty(),
+        "only reported once"
+    );
+    assert!(!state.wants_repaint());
+
+    // Two-finger swipe to the right:
+    let mut state = TouchState::new(TouchDeviceId(0));
+    let (a, b) = (Pos2::new(0.0, 0.0), Pos2::new(0.0, 20.0));
+    frame(
+        &mut state,
+        0.0,
+        vec![
+            touch(0, TouchPhase::Start, a),
+            touch(1, TouchPhase::Start, b),
+        ],
+    );
+    let right = Vec2::new(100.0, 0.0);
+    frame(
+        &mut state,
+        0.1,
+        vec![
+            touch(0, TouchPhase::Move, a + right),
+            touch(1, TouchPhase::Move, b + right),
+        ],
+    );
+    let gestures = frame(
+        &mut state,
+        0.2,
+        vec![
+            touch(0, TouchPhase::End, a + right),
+            touch(1, TouchPhase::End, b + right),
+        ],
+    );
+    assert_eq!(
+        gestures,
+        vec![Gesture::Swipe {
+            start: Pos2::new(0.0, 10.0),
+            num_touches: 2,
+            delta: right,
+        }]
+    );
+}
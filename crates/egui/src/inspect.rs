@@ -0,0 +1,277 @@
+//! Editable property grids, see [`EguiInspect`].
+
+use std::ops::RangeInclusive;
+
+use crate::{emath::Numeric, *};
+
+/// How to show a field, set by the `#[inspect(…)]` attributes of `#[derive(EguiInspect)]`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InspectOptions {
+    /// Show numbers with a [`Slider`] in this range, instead of a [`DragValue`].
+    pub range: Option<RangeInclusive<f64>>,
+
+    /// How fast a [`DragValue`] changes when dragged.
+    pub speed: Option<f64>,
+
+    /// Edit strings with a multiline [`TextEdit`].
+    pub multiline: bool,
+
+    /// Edit `[f32; 3]`, `[f32; 4]`, `[u8; 3]` and `[u8; 4]` as colors.
+    pub color: bool,
+}
+
+/// Something that can be shown and edited in a [`Ui`], e.g. as a row in a property grid.
+///
+/// Implemented for numbers, `bool`, `String`, colors, [`Vec2`], [`Pos2`], [`Option`] and [`Vec`].
+///
+/// With the `derive` feature you can `#[derive(EguiInspect)]` for your own structs and enums,
+/// and show them with [`Ui::inspect`]:
+///
+/// ```ignore
+/// #[derive(egui::EguiInspect)]
+/// struct Light {
+///     name: String,
+///
+///     #[inspect(range = 0.0..=10.0)]
+///     intensity: f32,
+///
+///     #[inspect(color)]
+///     color: [f32; 3],
+///
+///     #[inspect(read_only)]
+///     id: u64,
+///
+///     #[inspect(skip)]
+///     cache: Vec<u8>,
+/// }
+///
+/// # egui::__run_test_ui(|ui| {
+/// if ui.inspect(&mut light).changed() {
+///     // …
+/// }
+/// # });
+/// ```
+///
+/// The derive macro shows structs as a [`Grid`] with a row per field,
+/// and enums as a [`ComboBox`] for picking the variant followed by its fields.
+/// Fields can have these attributes:
+/// * `#[inspect(skip)]`: don't show the field.
+/// * `#[inspect(read_only)]`: show the field, but don't allow editing it.
+/// * `#[inspect(name = "Label")]`: the label of the field (also works on enum variants).
+/// * `#[inspect(range = 0.0..=1.0)]`: show a number with a slider in this range.
+/// * `#[inspect(speed = 0.1)]`: how fast a number changes when dragged.
+/// * `#[inspect(multiline)]`: edit a `String` with a multiline text edit.
+/// * `#[inspect(color)]`: edit a `[f32; 3]`, `[f32; 4]`, `[u8; 3]` or `[u8; 4]` as a color.
+///
+/// Switching the variant of an enum resets its fields to their [`Default`].
+/// If you use egui through `eframe`, add `#[inspect(crate = "eframe::egui")]` to the type.
+pub trait EguiInspect {
+    /// Show an editor for the value.
+    ///
+    /// The response is [`Response::changed`] if the value was changed.
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response;
+}
+
+impl<T: EguiInspect + ?Sized> EguiInspect for &mut T {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        (**self).inspect_ui(ui, options)
+    }
+}
+
+impl<T: EguiInspect + ?Sized> EguiInspect for Box<T> {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        (**self).inspect_ui(ui, options)
+    }
+}
+
+fn number_ui<Num: Numeric>(ui: &mut Ui, value: &mut Num, options: &InspectOptions) -> Response {
+    if let Some(range) = &options.range {
+        let range = Num::from_f64(*range.start())..=Num::from_f64(*range.end());
+        ui.add(Slider::new(value, range))
+    } else {
+        let mut drag_value = DragValue::new(value);
+        if let Some(speed) = options.speed {
+            drag_value = drag_value.speed(speed);
+        }
+        ui.add(drag_value)
+    }
+}
+
+macro_rules! impl_numbers {
+    ($($t: ident)*) => {
+        $(
+            impl EguiInspect for $t {
+                fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+                    number_ui(ui, self, options)
+                }
+            }
+        )*
+    };
+}
+
+impl_numbers!(i8 u8 i16 u16 i32 u32 i64 u64 isize usize f32 f64);
+
+impl EguiInspect for bool {
+    fn inspect_ui(&mut self, ui: &mut Ui, _options: &InspectOptions) -> Response {
+        ui.checkbox(self, "")
+    }
+}
+
+impl EguiInspect for String {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        if options.multiline {
+            ui.text_edit_multiline(self)
+        } else {
+            ui.text_edit_singleline(self)
+        }
+    }
+}
+
+impl EguiInspect for Color32 {
+    fn inspect_ui(&mut self, ui: &mut Ui, _options: &InspectOptions) -> Response {
+        ui.color_edit_button_srgba(self)
+    }
+}
+
+impl EguiInspect for Rgba {
+    fn inspect_ui(&mut self, ui: &mut Ui, _options: &InspectOptions) -> Response {
+        let mut rgba = self.to_array();
+        let response = ui.color_edit_button_rgba_premultiplied(&mut rgba);
+        *self = Self::from_rgba_premultiplied(rgba[0], rgba[1], rgba[2], rgba[3]);
+        response
+    }
+}
+
+/// Show the elements of an array side by side.
+fn array_ui<T: EguiInspect>(ui: &mut Ui, array: &mut [T], options: &InspectOptions) -> Response {
+    ui.horizontal(|ui| {
+        let mut response = ui.allocate_response(Vec2::ZERO, Sense::hover());
+        for value in array {
+            response |= value.inspect_ui(ui, options);
+        }
+        response
+    })
+    .inner
+}
+
+impl EguiInspect for [f32; 3] {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        if options.color {
+            ui.color_edit_button_rgb(self)
+        } else {
+            array_ui(ui, self, options)
+        }
+    }
+}
+
+impl EguiInspect for [f32; 4] {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        if options.color {
+            ui.color_edit_button_rgba_unmultiplied(self)
+        } else {
+            array_ui(ui, self, options)
+        }
+    }
+}
+
+impl EguiInspect for [u8; 3] {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        if options.color {
+            ui.color_edit_button_srgb(self)
+        } else {
+            array_ui(ui, self, options)
+        }
+    }
+}
+
+impl EguiInspect for [u8; 4] {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        if options.color {
+            ui.color_edit_button_srgba_unmultiplied(self)
+        } else {
+            array_ui(ui, self, options)
+        }
+    }
+}
+
+impl EguiInspect for Vec2 {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        array_ui(ui, &mut [&mut self.x, &mut self.y], options)
+    }
+}
+
+impl EguiInspect for Pos2 {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        array_ui(ui, &mut [&mut self.x, &mut self.y], options)
+    }
+}
+
+/// A checkbox for whether the value is `Some`, followed by the value.
+///
+/// Checking the box sets the value to its [`Default`].
+impl<T: EguiInspect + Default> EguiInspect for Option<T> {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        ui.horizontal(|ui| {
+            let mut is_some = self.is_some();
+            let mut response = ui.checkbox(&mut is_some, "");
+            if response.changed() {
+                *self = is_some.then(T::default);
+            }
+            if let Some(value) = self {
+                response |= value.inspect_ui(ui, options);
+            }
+            response
+        })
+        .inner
+    }
+}
+
+/// A collapsing header with a row per element.
+impl<T: EguiInspect> EguiInspect for Vec<T> {
+    fn inspect_ui(&mut self, ui: &mut Ui, options: &InspectOptions) -> Response {
+        let id = ui.next_auto_id();
+        let collapsing = CollapsingHeader::new(format!("{} items", self.len()))
+            .id_source(id)
+            .show(ui, |ui| {
+                Grid::new(id)
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        let mut response = ui.allocate_response(Vec2::ZERO, Sense::hover());
+                        for (i, value) in self.iter_mut().enumerate() {
+                            ui.label(i.to_string());
+                            response |= value.inspect_ui(ui, options);
+                            ui.end_row();
+                        }
+                        response
+                    })
+                    .inner
+            });
+        match collapsing.body_returned {
+            Some(body) => collapsing.header_response | body,
+            None => collapsing.header_response,
+        }
+    }
+}
+
+#[test]
+fn test_inspect() {
+    let mut value = 1.0_f32;
+    let mut name = String::from("egui");
+    let mut color = Some([1.0_f32, 0.5, 0.0]);
+    let mut points = vec![Pos2::ZERO, pos2(1.0, 2.0)];
+
+    crate::__run_test_ui(|ui| {
+        let options = InspectOptions {
+            range: Some(0.0..=10.0),
+            ..Default::default()
+        };
+        assert!(!value.inspect_ui(ui, &options).changed());
+        assert!(!ui.inspect(&mut name).changed());
+        let options = InspectOptions {
+            color: true,
+            ..Default::default()
+        };
+        color.inspect_ui(ui, &options);
+        ui.inspect(&mut points);
+    });
+}
@@ -0,0 +1,221 @@
+//! An interactive inspector for the widgets and layers of a viewport,
+//! similar to the DOM inspector of a web browser.
+//!
+//! See [`crate::Context::set_inspector`].
+
+use std::panic::Location;
+
+use crate::*;
+
+/// The keyboard shortcut that toggles the inspector (`Cmd` + `Shift` + `I`).
+///
+/// Controlled by [`crate::Options::inspector_shortcut`].
+/// Note that most web browsers use the same shortcut for their developer tools.
+pub const SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::I);
+
+/// A widget that was interacted with this frame, recorded while the inspector is open.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct WidgetRecord {
+    pub id: Id,
+    pub layer_id: LayerId,
+    pub rect: Rect,
+    pub sense: Sense,
+    pub enabled: bool,
+
+    /// The code that added the widget with [`Ui::add`], or else the code that allocated it.
+    pub location: &'static Location<'static>,
+}
+
+/// Highlight the widget under the pointer, and show the hierarchy window.
+pub(crate) fn show(ctx: &Context, widgets: &[WidgetRecord]) {
+    let hovered = ctx
+        .pointer_hover_pos()
+        .and_then(|pos| widget_at(ctx, widgets, pos));
+    if let Some(widget) = hovered {
+        highlight(ctx, widget);
+        info_area(ctx, widget);
+    }
+
+    let mut open = true;
+    Window::new("🔍 Inspector")
+        .id(Id::new("egui_inspector"))
+        .open(&mut open)
+        .default_width(360.0)
+        .default_height(400.0)
+        .show(ctx, |ui| hierarchy_ui(ui, widgets, hovered));
+    if !open {
+        ctx.set_inspector(false);
+    }
+}
+
+/// The smallest widget containing `pos` on the top-most layer.
+fn widget_at<'a>(
+    ctx: &Context,
+    widgets: &'a [WidgetRecord],
+    pos: Pos2,
+) -> Option<&'a WidgetRecord> {
+    let top_layer = ctx.layer_id_at(pos).unwrap_or_else(LayerId::background);
+    widgets
+        .iter()
+        .filter(|widget| widget.layer_id == top_layer && widget.rect.contains(pos))
+        .min_by(|a, b| a.rect.area().total_cmp(&b.rect.area()))
+}
+
+fn highlight(ctx: &Context, widget: &WidgetRecord) {
+    let painter = ctx.debug_painter();
+    painter.rect(
+        widget.rect,
+        0.0,
+        Color32::LIGHT_BLUE.gamma_multiply(0.2),
+        (1.0, Color32::LIGHT_BLUE),
+    );
+}
+
+/// Show the details of the widget next to it.
+fn info_area(ctx: &Context, widget: &WidgetRecord) {
+    let below = widget.rect.left_bottom() + vec2(0.0, 4.0);
+    Area::new(Id::new("egui_inspector_info"))
+        .order(Order::Debug)
+        .fixed_pos(below)
+        .constrain(true)
+        .interactable(false)
+        .show(ctx, |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| widget_details_ui(ui, widget));
+        });
+}
+
+fn widget_details_ui(ui: &mut Ui, widget: &WidgetRecord) {
+    let WidgetRecord {
+        id,
+        layer_id,
+        rect,
+        sense,
+        enabled,
+        location,
+    } = *widget;
+
+    ui.style_mut().override_text_style = Some(TextStyle::Monospace);
+    Grid::new("egui_inspector_details")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Id");
+            ui.label(id.short_debug_format());
+            ui.end_row();
+
+            ui.label("Rect");
+            ui.label(format!(
+                "{:.1} {:.1} - {:.1} {:.1} ({:.1} x {:.1})",
+                rect.min.x,
+                rect.min.y,
+                rect.max.x,
+                rect.max.y,
+                rect.width(),
+                rect.height()
+            ));
+            ui.end_row();
+
+            ui.label("Sense");
+            ui.label(sense_description(sense));
+            ui.end_row();
+
+            ui.label("Enabled");
+            ui.label(enabled.to_string());
+            ui.end_row();
+
+            ui.label("Layer");
+            ui.label(layer_id.short_debug_format());
+            ui.end_row();
+
+            ui.label("Location");
+            ui.label(format!("{}:{}", location.file(), location.line()));
+            ui.end_row();
+        });
+}
+
+fn sense_description(sense: Sense) -> String {
+    let Sense {
+        click,
+        drag,
+        focusable,
+    } = sense;
+    let parts: Vec<&str> = [(click, "click"), (drag, "drag"), (focusable, "focusable")]
+        .into_iter()
+        .filter_map(|(on, name)| on.then_some(name))
+        .collect();
+    if parts.is_empty() {
+        "hover".to_owned()
+    } else {
+        parts.join(" + ")
+    }
+}
+
+/// All layers of the current viewport with the widgets in them, back-to-front.
+fn hierarchy_ui(ui: &mut Ui, widgets: &[WidgetRecord], hovered: Option<&WidgetRecord>) {
+    let ctx = ui.ctx().clone();
+
+    ui.label(format!("Viewport: {:?}", ctx.viewport_id()));
+    ui.label(format!(
+        "Hover a widget to inspect it. Press {} to close.",
+        ctx.format_shortcut(&SHORTCUT)
+    ));
+    ui.separator();
+
+    if let Some(widget) = hovered {
+        widget_details_ui(ui, widget);
+        ui.separator();
+    }
+
+    let layer_order = ctx.memory(|mem| mem.layer_ids().collect::<Vec<_>>());
+    let mut layers: ahash::HashSet<LayerId> = ctx.memory(|mem| mem.areas().visible_layer_ids());
+    layers.extend(widgets.iter().map(|widget| widget.layer_id));
+    let mut layers: Vec<LayerId> = layers.into_iter().collect();
+    layers.sort_by_key(|layer| {
+        let index = layer_order.iter().position(|l| l == layer);
+        (layer.order, index, layer.id.value())
+    });
+
+    ScrollArea::vertical()
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            for layer_id in layers {
+                let layer_widgets: Vec<&WidgetRecord> = widgets
+                    .iter()
+                    .filter(|widget| widget.layer_id == layer_id)
+                    .collect();
+                let area_rect =
+                    ctx.memory(|mem| mem.areas().get(layer_id.id).map(|state| state.rect()));
+
+                let header = CollapsingHeader::new(format!(
+                    "{} ({} widgets)",
+                    layer_id.short_debug_format(),
+                    layer_widgets.len()
+                ))
+                .id_source(layer_id)
+                .show(ui, |ui| {
+                    for widget in layer_widgets {
+                        let text = format!(
+                            "{} {}:{}",
+                            widget.id.short_debug_format(),
+                            widget.location.file(),
+                            widget.location.line()
+                        );
+                        let response = ui.selectable_label(
+                            hovered.is_some_and(|hovered| hovered.id == widget.id),
+                            RichText::new(text).monospace(),
+                        );
+                        if response.hovered() {
+                            highlight(&ctx, widget);
+                        }
+                    }
+                });
+
+                if header.header_response.hovered() {
+                    if let Some(area_rect) = area_rect {
+                        ctx.debug_painter()
+                            .debug_rect(area_rect, Color32::LIGHT_BLUE, "");
+                    }
+                }
+            }
+        });
+}
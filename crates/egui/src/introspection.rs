@@ -141,6 +141,7 @@ impl Widget for &mut epaint::TessellationOptions {
                 coarse_tessellation_culling,
                 prerasterized_discs,
                 round_text_to_pixels,
+                crisp_lines,
                 debug_paint_clip_rects,
                 debug_paint_text_rects,
                 debug_ignore_clip_rects,
@@ -158,6 +159,9 @@ impl Widget for &mut epaint::TessellationOptions {
 
             ui.checkbox(prerasterized_discs, "Speed up filled circles with pre-rasterization");
 
+            ui.checkbox(crisp_lines, "Crisp lines")
+                .on_hover_text("Snap horizontal and vertical lines and rectangle outlines to the pixel grid.");
+
             ui.add(
                 crate::widgets::Slider::new(bezier_tolerance, 0.0001..=10.0)
                     .logarithmic(true)
@@ -2,7 +2,7 @@
 //! are sometimes painted behind or in front of other things.
 
 use crate::{Id, *};
-use epaint::{ClippedShape, Shape};
+use epaint::{ClippedShape, Shape, TessellationOptions};
 
 /// Different layer categories
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -123,11 +123,52 @@ impl PaintList {
         self.0.is_empty()
     }
 
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// A copy of all shapes from index `start` and onwards.
+    pub(crate) fn tail(&self, start: usize) -> Self {
+        Self(self.0.get(start..).unwrap_or_default().to_vec())
+    }
+
+    /// Remove all shapes from index `len` and onwards.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
+    /// Copies of the shapes in `range`, translated by `delta`.
+    ///
+    /// The clip rectangles and clip shapes are not translated.
+    pub(crate) fn copy_range(
+        &self,
+        range: std::ops::Range<usize>,
+        delta: Vec2,
+    ) -> Vec<ClippedShape> {
+        let mut shapes = self.0.get(range).unwrap_or_default().to_vec();
+        for clipped_shape in &mut shapes {
+            clipped_shape.shape.translate(delta);
+        }
+        shapes
+    }
+
+    /// Move all shapes of `other` to the end of `self`.
+    pub(crate) fn append(&mut self, other: &mut Self) {
+        self.0.append(&mut other.0);
+    }
+
     /// Returns the index of the new [`Shape`] that can be used with `PaintList::set`.
     #[inline(always)]
     pub fn add(&mut self, clip_rect: Rect, shape: Shape) -> ShapeIdx {
+        self.add_clipped(ClippedShape::new(clip_rect, shape))
+    }
+
+    /// Like [`Self::add`], but can also carry a [`epaint::ClipShape`].
+    #[inline(always)]
+    pub fn add_clipped(&mut self, clipped_shape: ClippedShape) -> ShapeIdx {
         let idx = ShapeIdx(self.0.len());
-        self.0.push(ClippedShape { clip_rect, shape });
+        self.0.push(clipped_shape);
         idx
     }
 
@@ -135,10 +176,15 @@ impl PaintList {
         self.0.extend(
             shapes
                 .into_iter()
-                .map(|shape| ClippedShape { clip_rect, shape }),
+                .map(|shape| ClippedShape::new(clip_rect, shape)),
         );
     }
 
+    /// Like [`Self::extend`], but can also carry a [`epaint::ClipShape`].
+    pub fn extend_clipped<I: IntoIterator<Item = ClippedShape>>(&mut self, clipped_shapes: I) {
+        self.0.extend(clipped_shapes);
+    }
+
     /// Modify an existing [`Shape`].
     ///
     /// Sometimes you want to paint a frame behind some contents, but don't know how large the frame needs to be
@@ -148,56 +194,157 @@ impl PaintList {
     /// and then later setting it using `paint_list.set(idx, cr, frame);`.
     #[inline(always)]
     pub fn set(&mut self, idx: ShapeIdx, clip_rect: Rect, shape: Shape) {
-        self.0[idx.0] = ClippedShape { clip_rect, shape };
+        self.set_clipped(idx, ClippedShape::new(clip_rect, shape));
+    }
+
+    /// Like [`Self::set`], but can also carry a [`epaint::ClipShape`].
+    #[inline(always)]
+    pub fn set_clipped(&mut self, idx: ShapeIdx, clipped_shape: ClippedShape) {
+        self.0[idx.0] = clipped_shape;
     }
 
     /// Translate each [`Shape`] and clip rectangle by this much, in-place
     pub fn translate(&mut self, delta: Vec2) {
-        for ClippedShape { clip_rect, shape } in &mut self.0 {
+        for ClippedShape {
+            clip_rect,
+            clip_shape,
+            tessellation_options: _,
+            shape,
+        } in &mut self.0
+        {
             *clip_rect = clip_rect.translate(delta);
+            if let Some(clip_shape) = clip_shape {
+                clip_shape.translate(delta);
+            }
             shape.translate(delta);
         }
     }
 }
 
 #[derive(Clone, Default)]
-pub(crate) struct GraphicLayers([IdMap<PaintList>; Order::COUNT]);
+pub(crate) struct GraphicLayers {
+    layers: [IdMap<PaintList>; Order::COUNT],
+
+    /// Painted right after the layer with the same [`LayerId`], see [`Self::overlay_list`].
+    overlays: [IdMap<PaintList>; Order::COUNT],
+}
 
 impl GraphicLayers {
     pub fn list(&mut self, layer_id: LayerId) -> &mut PaintList {
-        self.0[layer_id.order as usize]
+        self.layers[layer_id.order as usize]
             .entry(layer_id.id)
             .or_default()
     }
 
-    pub fn drain(&mut self, area_order: &[LayerId]) -> Vec<ClippedShape> {
+    /// Shapes that are painted on top of everything in the layer,
+    /// e.g. badges that should not be covered by widgets added later.
+    pub fn overlay_list(&mut self, layer_id: LayerId) -> &mut PaintList {
+        self.overlays[layer_id.order as usize]
+            .entry(layer_id.id)
+            .or_default()
+    }
+
+    /// All shapes, in paint order.
+    ///
+    /// The shapes of the layers in `tessellation_options` are tagged with those options.
+    pub fn drain(
+        &mut self,
+        area_order: &[LayerId],
+        tessellation_options: &ahash::HashMap<LayerId, TessellationOptions>,
+    ) -> Vec<ClippedShape> {
         crate::profile_function!();
 
         let mut all_shapes: Vec<_> = Default::default();
 
         for &order in &Order::ALL {
-            let order_map = &mut self.0[order as usize];
+            let order_map = &mut self.layers[order as usize];
+            let overlay_map = &mut self.overlays[order as usize];
 
             // If a layer is empty at the start of the frame
             // then nobody has added to it, and it is old and defunct.
             // Free it to save memory:
             order_map.retain(|_, list| !list.is_empty());
+            overlay_map.retain(|_, list| !list.is_empty());
+
+            // Layers already drained through `area_order` are empty by then:
+            let ids: Vec<Id> = order_map
+                .keys()
+                .chain(overlay_map.keys())
+                .copied()
+                .collect();
+
+            let mut append_layer = |id: &Id, all_shapes: &mut Vec<ClippedShape>| {
+                let start = all_shapes.len();
+                if let Some(list) = order_map.get_mut(id) {
+                    all_shapes.append(&mut list.0);
+                }
+                if let Some(overlay) = overlay_map.get_mut(id) {
+                    all_shapes.append(&mut overlay.0);
+                }
+                if let Some(options) = tessellation_options.get(&LayerId::new(order, *id)) {
+                    for clipped_shape in &mut all_shapes[start..] {
+                        clipped_shape.tessellation_options = Some(*options);
+                    }
+                }
+            };
 
             // First do the layers part of area_order:
             for layer_id in area_order {
                 if layer_id.order == order {
-                    if let Some(list) = order_map.get_mut(&layer_id.id) {
-                        all_shapes.append(&mut list.0);
-                    }
+                    append_layer(&layer_id.id, &mut all_shapes);
                 }
             }
 
             // Also draw areas that are missing in `area_order`:
-            for shapes in order_map.values_mut() {
-                all_shapes.append(&mut shapes.0);
+            for id in &ids {
+                append_layer(id, &mut all_shapes);
             }
         }
 
         all_shapes
     }
 }
+
+#[test]
+fn test_overlays_are_painted_after_their_layer() {
+    let background = LayerId::background();
+    let window = LayerId::new(Order::Middle, Id::new("window"));
+    let shape = |x: f32| Shape::circle_filled(Pos2::new(x, 0.0), 1.0, Color32::WHITE);
+
+    let mut layers = GraphicLayers::default();
+    layers
+        .overlay_list(background)
+        .add(Rect::EVERYTHING, shape(1.0));
+    layers.list(window).add(Rect::EVERYTHING, shape(2.0));
+    layers.list(background).add(Rect::EVERYTHING, shape(0.0));
+
+    let xs: Vec<f32> = layers
+        .drain(&[background, window], &Default::default())
+        .iter()
+        .map(|clipped| clipped.shape.visual_bounding_rect().center().x)
+        .collect();
+    assert_eq!(xs, vec![0.0, 1.0, 2.0]);
+}
+
+#[test]
+fn test_tessellation_options_for_layer() {
+    let background = LayerId::background();
+    let window = LayerId::new(Order::Middle, Id::new("window"));
+    let shape = Shape::circle_filled(Pos2::ZERO, 1.0, Color32::WHITE);
+
+    let mut layers = GraphicLayers::default();
+    layers.list(background).add(Rect::EVERYTHING, shape.clone());
+    layers.overlay_list(window).add(Rect::EVERYTHING, shape);
+
+    let pixel_art = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+    let tessellation_options = std::iter::once((window, pixel_art)).collect();
+    let options: Vec<_> = layers
+        .drain(&[background, window], &tessellation_options)
+        .into_iter()
+        .map(|clipped| clipped.tessellation_options)
+        .collect();
+    assert_eq!(options, vec![None, Some(pixel_art)]);
+}
@@ -344,6 +344,7 @@
 #![cfg_attr(not(feature = "puffin"), forbid(unsafe_code))]
 
 mod animation_manager;
+pub mod bench;
 pub mod containers;
 mod context;
 mod data;
@@ -351,18 +352,26 @@ mod frame_state;
 pub(crate) mod grid;
 pub mod gui_zoom;
 mod id;
+#[cfg(feature = "persistence")]
+mod input_recording;
 mod input_state;
+mod inspect;
+pub mod inspector;
 pub mod introspection;
 pub mod layers;
 mod layout;
+mod link_viewports;
 pub mod load;
 mod memory;
 pub mod menu;
 pub mod os;
 mod painter;
 pub(crate) mod placer;
+mod profiler_overlay;
+mod progress;
 mod response;
 mod sense;
+pub mod snapshot;
 pub mod style;
 mod ui;
 pub mod util;
@@ -374,6 +383,9 @@ pub mod widgets;
 #[cfg(debug_assertions)]
 mod callstack;
 
+#[cfg(test)]
+mod test_util;
+
 #[cfg(feature = "accesskit")]
 pub use accesskit;
 
@@ -386,46 +398,53 @@ pub use epaint::emath;
 #[cfg(feature = "color-hex")]
 pub use ecolor::hex_color;
 pub use ecolor::{Color32, Rgba};
+#[cfg(feature = "derive")]
+pub use egui_inspect_derive::EguiInspect;
 pub use emath::{
-    lerp, pos2, remap, remap_clamp, vec2, Align, Align2, NumExt, Pos2, Rangef, Rect, Vec2, Vec2b,
+    lerp, pos2, remap, remap_clamp, vec2, Align, Align2, NumExt, Pos2, Rangef, Rect, RectAlign,
+    Vec2, Vec2b,
 };
 pub use epaint::{
     mutex,
     text::{FontData, FontDefinitions, FontFamily, FontId, FontTweak},
     textures::{TextureFilter, TextureOptions, TexturesDelta},
-    ClippedPrimitive, ColorImage, FontImage, ImageData, Mesh, PaintCallback, PaintCallbackInfo,
-    Rounding, Shape, Stroke, TextureHandle, TextureId,
+    ClippedPrimitive, ColorImage, DynamicTexture, FontImage, ImageData, Mesh, PaintCallback,
+    PaintCallbackInfo, Rounding, Shape, Stroke, TextureHandle, TextureId,
 };
 
 pub mod text {
     pub use crate::text_edit::CCursorRange;
     pub use epaint::text::{
         cursor::CCursor, FontData, FontDefinitions, FontFamily, Fonts, Galley, LayoutJob,
-        LayoutSection, TextFormat, TextWrapping, TAB_SIZE,
+        LayoutSection, TextFormat, TextMetrics, TextShadow, TextWrapping, TruncateMode, TAB_SIZE,
     };
 }
 
 pub use {
     containers::*,
-    context::{Context, RequestRepaintInfo},
+    context::{Context, LatencyStats, RequestRepaintInfo},
     data::{
         input::*,
         output::{
-            self, CursorIcon, FullOutput, OpenUrl, PlatformOutput, UserAttentionType, WidgetInfo,
+            self, AnnouncePriority, Announcement, CursorIcon, FullOutput, HapticKind, OpenUrl,
+            PlatformOutput, SoundKind, UserAttentionType, WebViewOutput, WidgetInfo,
         },
     },
-    grid::Grid,
+    grid::{Grid, GridColumn},
     id::{Id, IdMap},
-    input_state::{InputState, MultiTouchInfo, PointerState},
+    input_state::{Gesture, InputState, MultiTouchInfo, Orientation, PointerState},
+    inspect::{EguiInspect, InspectOptions},
     layers::{LayerId, Order},
     layout::*,
     load::SizeHint,
-    memory::{Memory, Options},
+    memory::{Memory, MemoryStats, Options, StateStats},
     painter::Painter,
-    response::{InnerResponse, Response},
+    profiler_overlay::FrameTimings,
+    progress::{AggregatedProgress, Progress, TaskProgress},
+    response::{Badge, InnerResponse, Response},
     sense::Sense,
-    style::{FontSelection, Margin, Style, TextStyle, Visuals},
-    text::{Galley, TextFormat},
+    style::{FontSelection, Margin, NumberFormatter, Style, TextStyle, TouchMode, Visuals},
+    text::{Galley, TextFormat, TruncateMode},
     ui::Ui,
     viewport::*,
     widget_text::{RichText, WidgetText},
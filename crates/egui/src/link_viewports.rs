@@ -0,0 +1,87 @@
+//! Hyperlinks opened in their own viewports inside the app,
+//! see [`crate::Context::open_url_in_viewport`].
+
+use std::sync::Arc;
+
+use crate::*;
+
+/// Shows the contents of a URL in a [`Ui`], e.g. with an embedded web view.
+///
+/// See [`crate::Context::set_webview_hook`].
+pub(crate) type WebviewHook = Arc<dyn Fn(&mut Ui, &str) + Send + Sync>;
+
+fn urls_id() -> Id {
+    Id::new("egui_link_viewports")
+}
+
+fn viewport_id(url: &str) -> ViewportId {
+    ViewportId::from_hash_of(("egui_link_viewport", url))
+}
+
+/// Start showing this URL in its own viewport.
+pub(crate) fn open(ctx: &Context, url: String) {
+    ctx.data_mut(|data| {
+        let urls: &mut Vec<String> = data.get_temp_mut_or_default(urls_id());
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    });
+    ctx.request_repaint_of(ViewportId::ROOT);
+}
+
+fn close(ctx: &Context, url: &str) {
+    ctx.data_mut(|data| {
+        let urls: &mut Vec<String> = data.get_temp_mut_or_default(urls_id());
+        urls.retain(|open_url| open_url != url);
+    });
+    ctx.request_repaint_of(ViewportId::ROOT);
+}
+
+/// Show the viewports of all open links. Called by the root viewport each frame.
+pub(crate) fn show(ctx: &Context, hook: &WebviewHook) {
+    let urls: Vec<String> = ctx.data(|data| data.get_temp(urls_id()).unwrap_or_default());
+
+    for url in urls {
+        let hook = hook.clone();
+        let builder = ViewportBuilder::default()
+            .with_title(url.clone())
+            .with_inner_size([800.0, 600.0]);
+        ctx.show_viewport_deferred(viewport_id(&url), builder, move |ctx, class| {
+            if class == ViewportClass::Embedded {
+                let mut open = true;
+                Window::new(url.as_str())
+                    .id(Id::new(("egui_link_viewport", &url)))
+                    .open(&mut open)
+                    .default_size([800.0, 600.0])
+                    .show(ctx, |ui| hook(ui, &url));
+                if !open {
+                    close(ctx, &url);
+                }
+            } else {
+                CentralPanel::default().show(ctx, |ui| hook(ui, &url));
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    close(ctx, &url);
+                }
+            }
+        });
+    }
+}
+
+#[test]
+fn open_url_in_viewport() {
+    let url = "https://github.com/emilk/egui";
+    let ctx = Context::default();
+
+    // Without a hook, the url is opened in a new browser tab:
+    let output = ctx.run(Default::default(), |ctx| ctx.open_url_in_viewport(url));
+    assert!(output.platform_output.open_url == Some(OpenUrl::new_tab(url)));
+
+    ctx.set_embed_viewports(false);
+    ctx.set_webview_hook(|ui, url| {
+        ui.label(url);
+    });
+    let _ = ctx.run(Default::default(), |ctx| ctx.open_url_in_viewport(url));
+    let output = ctx.run(Default::default(), |_ctx| {});
+    assert!(output.platform_output.open_url.is_none());
+    assert!(output.viewport_output.contains_key(&viewport_id(url)));
+}
@@ -181,6 +181,13 @@ pub struct Options {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub zoom_with_keyboard: bool,
 
+    /// If `true`, pressing [`crate::inspector::SHORTCUT`] (Cmd+Shift+I)
+    /// toggles the widget inspector ([`crate::Context::set_inspector`]).
+    ///
+    /// By default this is `true` in debug builds.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub inspector_shortcut: bool,
+
     /// Controls the tessellator.
     pub tessellation_options: epaint::TessellationOptions,
 
@@ -203,10 +210,61 @@ pub struct Options {
     /// if you are changing [`Style::text_styles`], of have a lot of text styles.
     pub preload_font_glyphs: bool,
 
+    /// If true, text is painted using signed distance fields (see [`epaint::Fonts::set_distance_field`]).
+    ///
+    /// This keeps text crisp when it is scaled up after tessellation, e.g. by a zoomable canvas,
+    /// without having to rasterize the glyphs again for every zoom level.
+    /// Color emojis are painted using only their outlines in this mode.
+    ///
+    /// This requires support from the rendering backend. `egui-wgpu` and `egui_glow` support it.
+    ///
+    /// This is `false` by default.
+    pub distance_field_text: bool,
+
+    /// If true, images loaded with [`crate::Context::load_texture`] that are larger than
+    /// [`crate::InputState::max_texture_side`] are split into several textures,
+    /// which are painted in place of the image (see [`epaint::textures::TextureTiling`]).
+    ///
+    /// If false, such images are passed on to the backend as is, which will fail to paint them.
+    ///
+    /// This is `true` by default.
+    pub tile_large_textures: bool,
+
     /// Check reusing of [`Id`]s, and show a visual warning on screen when one is found.
     ///
     /// By default this is `true` in debug builds.
     pub warn_on_id_clash: bool,
+
+    /// If `true`, the [`Id`]s of all widgets shown in a viewport other than the root viewport
+    /// are namespaced by the [`ViewportId`] of that viewport.
+    ///
+    /// This means that showing the same [`crate::Window`] (or any other ui) in two viewports
+    /// gives each copy its own state: collapsing headers, scroll offsets, text cursors, focus, etc.
+    ///
+    /// Set this to `false` if you intentionally want to share such state between viewports.
+    ///
+    /// See also [`crate::Context::viewport_local_id`].
+    ///
+    /// This is `true` by default.
+    pub namespace_ids_by_viewport: bool,
+
+    /// If set, state in [`Memory::data`] that hasn't been read or written
+    /// for this many frames is removed at the end of the frame.
+    ///
+    /// This stops memory from growing forever in UIs that keep creating widgets with new [`Id`]s.
+    /// Note that this also forgets the state of widgets that are merely hidden for a while,
+    /// e.g. the scroll offset of a tab that isn't shown.
+    ///
+    /// Frames are counted on the root viewport. See [`crate::util::IdTypeMap::remove_unused`].
+    ///
+    /// This is `None` (off) by default.
+    pub gc_unused_data_after_frames: Option<u64>,
+
+    /// If `true`, the combined progress of the tasks reported with [`crate::Context::progress`]
+    /// is shown on the taskbar entry of their window, using [`crate::ViewportCommand::TaskbarProgress`].
+    ///
+    /// This is `true` by default.
+    pub taskbar_progress: bool,
 }
 
 impl Default for Options {
@@ -215,10 +273,16 @@ impl Default for Options {
             style: Default::default(),
             zoom_factor: 1.0,
             zoom_with_keyboard: true,
+            inspector_shortcut: cfg!(debug_assertions),
             tessellation_options: Default::default(),
             screen_reader: false,
             preload_font_glyphs: true,
+            distance_field_text: false,
+            tile_large_textures: true,
             warn_on_id_clash: cfg!(debug_assertions),
+            namespace_ids_by_viewport: true,
+            gc_unused_data_after_frames: None,
+            taskbar_progress: true,
         }
     }
 }
@@ -285,6 +349,11 @@ pub(crate) struct Focus {
 
     /// A cache of widget ids that are interested in focus with their corresponding rectangles.
     focus_widgets_cache: IdMap<Rect>,
+
+    /// If set, every [`Id`] passed to [`Self::interested_in_focus`] is also logged here.
+    ///
+    /// Used by [`crate::Ui::retained`] to record which widgets of a block want focus.
+    pub interest_log: Option<Vec<Id>>,
 }
 
 /// The widget with focus.
@@ -339,6 +408,7 @@ impl Focus {
 
     fn begin_frame(&mut self, new_input: &crate::data::input::RawInput) {
         self.id_previous_frame = self.focused();
+        self.interest_log = None;
         if let Some(id) = self.id_next_frame.take() {
             self.focused_widget = Some(FocusWidget::new(id));
         }
@@ -382,6 +452,28 @@ impl Focus {
                         self.focus_direction = cardinality;
                     }
                 }
+
+                if let crate::Event::GamepadButton {
+                    button,
+                    pressed: true,
+                } = event
+                {
+                    if let Some(cardinality) = match button {
+                        crate::GamepadButton::DPadUp => Some(FocusDirection::Up),
+                        crate::GamepadButton::DPadRight => Some(FocusDirection::Right),
+                        crate::GamepadButton::DPadDown => Some(FocusDirection::Down),
+                        crate::GamepadButton::DPadLeft => Some(FocusDirection::Left),
+                        crate::GamepadButton::LeftBumper => Some(FocusDirection::Previous),
+                        crate::GamepadButton::RightBumper => Some(FocusDirection::Next),
+                        crate::GamepadButton::East => {
+                            self.focused_widget = None;
+                            Some(FocusDirection::None)
+                        }
+                        _ => None,
+                    } {
+                        self.focus_direction = cardinality;
+                    }
+                }
             }
 
             #[cfg(feature = "accesskit")]
@@ -465,6 +557,10 @@ impl Focus {
         }
 
         self.last_interested = Some(id);
+
+        if let Some(log) = &mut self.interest_log {
+            log.push(id);
+        }
     }
 
     fn reset_focus(&mut self) {
@@ -582,12 +678,47 @@ impl Memory {
         self.caches.update();
         self.areas_mut().end_frame();
         self.interaction_mut().focus.end_frame(used_ids);
+
+        if self.viewport_id == ViewportId::ROOT {
+            let gc_unused_data_after_frames = self.options.gc_unused_data_after_frames;
+            self.data
+                .set_track_usage(gc_unused_data_after_frames.is_some());
+            if let Some(max_age_in_frames) = gc_unused_data_after_frames {
+                self.data.remove_unused(max_age_in_frames);
+            }
+            self.data.end_frame();
+        }
     }
 
     pub(crate) fn set_viewport_id(&mut self, viewport_id: ViewportId) {
         self.viewport_id = viewport_id;
     }
 
+    /// How much state is stored, and roughly how many bytes it uses.
+    pub fn stats(&self) -> MemoryStats {
+        use crate::{containers::collapsing_header::InnerState, text_edit::TextEditState};
+
+        let num_areas: usize = self.areas.values().map(Areas::count).sum();
+        MemoryStats {
+            areas: StateStats {
+                count: num_areas,
+                num_bytes: num_areas * std::mem::size_of::<area::State>(),
+            },
+            collapsing_headers: StateStats {
+                count: self.data.count::<InnerState>(),
+                num_bytes: self.data.num_bytes_of::<InnerState>(),
+            },
+            text_edits: StateStats {
+                count: self.data.count::<TextEditState>(),
+                num_bytes: self.data.num_bytes_of::<TextEditState>(),
+            },
+            data: StateStats {
+                count: self.data.len(),
+                num_bytes: self.data.num_bytes(),
+            },
+        }
+    }
+
     /// Access memory of the [`Area`](crate::containers::area::Area)s, such as `Window`s.
     pub fn areas(&self) -> &Areas {
         self.areas
@@ -808,6 +939,43 @@ impl Memory {
 
 // ----------------------------------------------------------------------------
 
+/// The number of stored states of some kind, and roughly how many bytes they use.
+///
+/// The byte counts do not include heap allocations owned by the states,
+/// e.g. the undo history of a [`crate::TextEdit`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateStats {
+    /// Number of stored states.
+    pub count: usize,
+
+    /// Approximate number of bytes used.
+    pub num_bytes: usize,
+}
+
+impl std::fmt::Display for StateStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:.1} kB)", self.count, self.num_bytes as f64 * 1e-3)
+    }
+}
+
+/// See [`Memory::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// State of [`Area`](crate::containers::area::Area)s (windows, panels, popups, …), in all viewports.
+    pub areas: StateStats,
+
+    /// State of [`crate::CollapsingHeader`]s. Also included in [`Self::data`].
+    pub collapsing_headers: StateStats,
+
+    /// State of [`crate::TextEdit`]s. Also included in [`Self::data`].
+    pub text_edits: StateStats,
+
+    /// Everything in [`Memory::data`].
+    pub data: StateStats,
+}
+
+// ----------------------------------------------------------------------------
+
 /// Keeps track of [`Area`](crate::containers::area::Area)s, which are free-floating [`Ui`](crate::Ui)s.
 /// These [`Area`](crate::containers::area::Area)s can be in any [`Order`](crate::Order).
 #[derive(Clone, Debug, Default)]
@@ -938,3 +1106,32 @@ fn memory_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Memory>();
 }
+
+#[test]
+fn memory_stats_and_gc() {
+    let mut memory = Memory::default();
+    memory.options.gc_unused_data_after_frames = Some(2);
+
+    let kept = Id::new("kept");
+    let forgotten = Id::new("forgotten");
+    memory.data.insert_temp(kept, 1_u32);
+    memory.data.insert_temp(forgotten, 2_u32);
+    memory
+        .data
+        .insert_persisted(kept, crate::text_edit::TextEditState::default());
+
+    let stats = memory.stats();
+    assert_eq!(stats.data.count, 3);
+    assert_eq!(stats.text_edits.count, 1);
+    assert!(stats.text_edits.num_bytes > 0);
+    assert!(stats.data.num_bytes > stats.text_edits.num_bytes);
+
+    for _ in 0..5 {
+        assert_eq!(memory.data.get_temp::<u32>(kept), Some(1));
+        memory.end_frame(&Default::default());
+    }
+
+    assert_eq!(memory.data.get_temp::<u32>(kept), Some(1));
+    assert_eq!(memory.data.get_temp::<u32>(forgotten), None);
+    assert_eq!(memory.stats().data.count, 1);
+}
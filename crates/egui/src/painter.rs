@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
 use crate::{
-    emath::{Align2, Pos2, Rangef, Rect, Vec2},
+    emath::{vec2, Align2, Pos2, Rangef, Rect, Rot2, Vec2},
     layers::{LayerId, PaintList, ShapeIdx},
     Color32, Context, FontId,
 };
 use epaint::{
-    text::{Fonts, Galley},
-    CircleShape, RectShape, Rounding, Shape, Stroke,
+    text::{Fonts, Galley, LayoutJob, TextFormat},
+    CircleShape, ClipShape, ClippedShape, RectShape, Rounding, Shape, Stroke, TextShape,
 };
 
 /// Helper to paint shapes and text to a specific region on a specific layer.
@@ -25,6 +25,9 @@ pub struct Painter {
     /// This means nothing outside of this rectangle will be visible on screen.
     clip_rect: Rect,
 
+    /// If set, everything painted in this [`Painter`] will also be clipped against this.
+    clip_shape: Option<ClipShape>,
+
     /// If set, all shapes will have their colors modified to be closer to this.
     /// This is used to implement grayed out interfaces.
     fade_to_color: Option<Color32>,
@@ -37,6 +40,7 @@ impl Painter {
             ctx,
             layer_id,
             clip_rect,
+            clip_shape: None,
             fade_to_color: None,
         }
     }
@@ -48,6 +52,7 @@ impl Painter {
             ctx: self.ctx,
             layer_id,
             clip_rect: self.clip_rect,
+            clip_shape: self.clip_shape,
             fade_to_color: None,
         }
     }
@@ -61,6 +66,41 @@ impl Painter {
             ctx: self.ctx.clone(),
             layer_id: self.layer_id,
             clip_rect: rect.intersect(self.clip_rect),
+            clip_shape: self.clip_shape.clone(),
+            fade_to_color: self.fade_to_color,
+        }
+    }
+
+    /// Create a painter that clips everything to the filled area of the given shape,
+    /// e.g. a rounded rectangle or a circle.
+    ///
+    /// See [`ClipShape::from_shape`] for which shapes are supported.
+    ///
+    /// The clip shape is combined with the clip shape and clip rectangle of the parent [`Painter`].
+    ///
+    /// Paint callbacks can only be clipped to rectangles,
+    /// so a callback that crosses the edge of the clip shape is not painted.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let rect = egui::Rect::from_min_size(Default::default(), egui::Vec2::splat(100.0));
+    /// let avatar_painter = ui
+    ///     .painter()
+    ///     .with_clip_shape(egui::epaint::CircleShape::filled(rect.center(), 50.0, egui::Color32::WHITE));
+    /// avatar_painter.rect_filled(rect, 0.0, egui::Color32::RED);
+    /// # });
+    /// ```
+    pub fn with_clip_shape(&self, shape: impl Into<Shape>) -> Self {
+        let clip_shape = ClipShape::from_shape(&shape.into());
+        let clip_shape = match &self.clip_shape {
+            Some(parent) => parent.intersect(&clip_shape),
+            None => clip_shape,
+        };
+        Self {
+            ctx: self.ctx.clone(),
+            layer_id: self.layer_id,
+            clip_rect: clip_shape.bounding_rect().intersect(self.clip_rect),
+            clip_shape: Some(clip_shape),
             fade_to_color: self.fade_to_color,
         }
     }
@@ -121,6 +161,18 @@ impl Painter {
         self.clip_rect = clip_rect;
     }
 
+    /// If set, everything painted in this [`Painter`] will also be clipped against this.
+    #[inline]
+    pub fn clip_shape(&self) -> Option<&ClipShape> {
+        self.clip_shape.as_ref()
+    }
+
+    /// If set, everything painted in this [`Painter`] will also be clipped against this.
+    #[inline]
+    pub fn set_clip_shape(&mut self, clip_shape: Option<ClipShape>) {
+        self.clip_shape = clip_shape;
+    }
+
     /// Useful for pixel-perfect rendering.
     #[inline]
     pub fn round_to_pixel(&self, point: f32) -> f32 {
@@ -153,6 +205,15 @@ impl Painter {
         }
     }
 
+    fn clipped_shape(&self, shape: Shape) -> ClippedShape {
+        ClippedShape {
+            clip_rect: self.clip_rect,
+            clip_shape: self.clip_shape.clone(),
+            tessellation_options: None,
+            shape,
+        }
+    }
+
     /// It is up to the caller to make sure there is room for this.
     /// Can be used for free painting.
     /// NOTE: all coordinates are screen coordinates!
@@ -162,7 +223,8 @@ impl Painter {
         } else {
             let mut shape = shape.into();
             self.transform_shape(&mut shape);
-            self.paint_list(|l| l.add(self.clip_rect, shape))
+            let clipped_shape = self.clipped_shape(shape);
+            self.paint_list(|l| l.add_clipped(clipped_shape))
         }
     }
 
@@ -173,12 +235,12 @@ impl Painter {
         if self.fade_to_color == Some(Color32::TRANSPARENT) {
             return;
         }
-        if self.fade_to_color.is_some() {
+        if self.fade_to_color.is_some() || self.clip_shape.is_some() {
             let shapes = shapes.into_iter().map(|mut shape| {
                 self.transform_shape(&mut shape);
-                shape
+                self.clipped_shape(shape)
             });
-            self.paint_list(|l| l.extend(self.clip_rect, shapes));
+            self.paint_list(|l| l.extend_clipped(shapes));
         } else {
             self.paint_list(|l| l.extend(self.clip_rect, shapes));
         };
@@ -191,7 +253,8 @@ impl Painter {
         }
         let mut shape = shape.into();
         self.transform_shape(&mut shape);
-        self.paint_list(|l| l.set(idx, self.clip_rect, shape));
+        let clipped_shape = self.clipped_shape(shape);
+        self.paint_list(|l| l.set_clipped(idx, clipped_shape));
     }
 }
 
@@ -383,6 +446,69 @@ impl Painter {
         rect
     }
 
+    /// Lay out and paint some text along a path, e.g. for circular labels, gauges or street names.
+    ///
+    /// The baseline of the text follows the `path`, starting at its first point.
+    /// Each glyph is rotated to follow the direction of the path at the center of the glyph.
+    /// Glyphs that don't fit on the path are not painted, and `\n` is not treated as a line break.
+    ///
+    /// To follow a Bézier curve, first flatten it with
+    /// [`epaint::CubicBezierShape::flatten`] or [`epaint::QuadraticBezierShape::flatten`].
+    ///
+    /// Returns the bounding rectangle of the painted glyphs.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn text_on_path(&self, path: &[Pos2], text: impl ToString, format: TextFormat) -> Rect {
+        let single_line_job = |text: String| {
+            let mut job = LayoutJob::single_section(text, format.clone());
+            job.break_on_newline = false;
+            job
+        };
+
+        let fallback_color = format.color;
+        let mut walker = PathWalker::new(path);
+        let mut bounding_rect = Rect::NOTHING;
+
+        let shapes: Vec<Shape> = self.fonts(|fonts| {
+            let galley = fonts.layout_job(single_line_job(text.to_string()));
+            let Some(row) = galley.rows.first() else {
+                return vec![];
+            };
+
+            let mut shapes = vec![];
+            for glyph in &row.glyphs {
+                let Some((point, direction)) = walker.advance_to(glyph.pos.x + 0.5 * glyph.size.x)
+                else {
+                    break; // The rest of the text doesn't fit
+                };
+                let rot = Rot2::from_angle(direction.angle());
+
+                // Center the glyph horizontally on the point, with the baseline on the path:
+                let pos = point - rot * vec2(0.5 * glyph.size.x, glyph.pos.y);
+                let glyph_galley = fonts.layout_job(single_line_job(glyph.chr.to_string()));
+
+                let glyph_rect = Rect::from_min_size(Pos2::ZERO, glyph_galley.size());
+                for corner in [
+                    glyph_rect.left_top(),
+                    glyph_rect.right_top(),
+                    glyph_rect.left_bottom(),
+                    glyph_rect.right_bottom(),
+                ] {
+                    bounding_rect.extend_with(pos + rot * corner.to_vec2());
+                }
+
+                shapes.push(
+                    TextShape::new(pos, glyph_galley, fallback_color)
+                        .with_angle(rot.angle())
+                        .into(),
+                );
+            }
+            shapes
+        });
+
+        self.extend(shapes);
+        bounding_rect
+    }
+
     /// Will wrap text at the given width and line break at `\n`.
     ///
     /// Paint the results with [`Self::galley`].
@@ -456,6 +582,46 @@ impl Painter {
     }
 }
 
+/// Walks along a polyline, for [`Painter::text_on_path`].
+struct PathWalker<'a> {
+    path: &'a [Pos2],
+
+    /// Index of the current segment, from `path[segment]` to `path[segment + 1]`.
+    segment: usize,
+
+    /// Distance along the path to the start of the current segment.
+    segment_start: f32,
+}
+
+impl<'a> PathWalker<'a> {
+    fn new(path: &'a [Pos2]) -> Self {
+        Self {
+            path,
+            segment: 0,
+            segment_start: 0.0,
+        }
+    }
+
+    /// The point at the given distance along the path, and the direction of the path there.
+    ///
+    /// The distance must not be smaller than in the previous call.
+    /// Returns `None` past the end of the path.
+    fn advance_to(&mut self, distance: f32) -> Option<(Pos2, Vec2)> {
+        while self.segment + 1 < self.path.len() {
+            let (a, b) = (self.path[self.segment], self.path[self.segment + 1]);
+            let length = a.distance(b);
+            let t = distance - self.segment_start;
+            if 0.0 < length && t <= length {
+                let direction = (b - a) / length;
+                return Some((a + t.max(0.0) * direction, direction));
+            }
+            self.segment += 1;
+            self.segment_start += length;
+        }
+        None
+    }
+}
+
 fn tint_shape_towards(shape: &mut Shape, target: Color32) {
     epaint::shape_transform::adjust_colors(shape, &|color| {
         if *color != Color32::PLACEHOLDER {
@@ -33,6 +33,11 @@ impl Placer {
         self.grid.as_ref()
     }
 
+    #[inline(always)]
+    pub(crate) fn grid_mut(&mut self) -> Option<&mut grid::GridLayout> {
+        self.grid.as_mut()
+    }
+
     #[inline(always)]
     pub(crate) fn is_grid(&self) -> bool {
         self.grid.is_some()
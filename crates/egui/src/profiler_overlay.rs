@@ -0,0 +1,152 @@
+//! A small always-on-top graph of how long the different parts of recent frames took.
+//!
+//! See [`crate::Context::set_profiler_overlay`].
+
+use crate::*;
+
+/// How long the different parts of a frame took, in seconds.
+///
+/// Reported by the integration with [`Context::report_frame_timings`],
+/// and shown by [`Context::set_profiler_overlay`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameTimings {
+    /// Gathering the input for the frame.
+    pub input: f32,
+
+    /// Running egui and the app code, i.e. [`Context::run`].
+    pub update: f32,
+
+    /// [`Context::tessellate`].
+    pub tessellate: f32,
+
+    /// Uploading textures and issuing draw calls.
+    pub paint: f32,
+
+    /// Presenting the frame, e.g. swapping buffers (which may include waiting for vsync).
+    pub swap: f32,
+}
+
+impl FrameTimings {
+    /// The sum of all the parts.
+    pub fn total(&self) -> f32 {
+        self.parts().iter().map(|(_, seconds)| seconds).sum()
+    }
+
+    fn parts(&self) -> [(&'static str, f32); 5] {
+        let Self {
+            input,
+            update,
+            tessellate,
+            paint,
+            swap,
+        } = *self;
+        [
+            ("input", input),
+            ("update", update),
+            ("tessellate", tessellate),
+            ("paint", paint),
+            ("swap", swap),
+        ]
+    }
+}
+
+/// How many frames we remember and show.
+pub(crate) const MAX_FRAMES: usize = 120;
+
+/// A frame that takes this long fills the whole height of the graph.
+const MAX_SECONDS: f32 = 1.0 / 30.0;
+
+const COLORS: [Color32; 5] = [
+    Color32::from_rgb(120, 120, 255), // input
+    Color32::from_rgb(100, 200, 100), // update
+    Color32::from_rgb(230, 180, 60),  // tessellate
+    Color32::from_rgb(230, 100, 100), // paint
+    Color32::from_rgb(160, 160, 160), // swap
+];
+
+/// Paint the graph in the top right corner of the screen.
+pub(crate) fn paint(ctx: &Context, frames: &[FrameTimings]) {
+    let layer_id = LayerId::new(Order::Debug, Id::new("profiler_overlay"));
+    let painter = ctx.layer_painter(layer_id);
+    let visuals = ctx.style().visuals.clone();
+    let font_id = FontId::monospace(10.0);
+
+    let size = vec2(240.0, 60.0);
+    let margin = 8.0;
+    let screen_rect = ctx.screen_rect();
+    let graph_rect = Rect::from_min_size(
+        pos2(
+            screen_rect.right() - size.x - margin,
+            screen_rect.top() + margin,
+        ),
+        size,
+    );
+    let legend_height = 2.0 * ctx.fonts(|f| f.row_height(&font_id));
+    let frame_rect = graph_rect
+        .expand(4.0)
+        .with_max_y(graph_rect.bottom() + legend_height + 4.0);
+
+    painter.rect(
+        frame_rect,
+        visuals.window_rounding,
+        visuals.extreme_bg_color.gamma_multiply(0.9),
+        visuals.window_stroke(),
+    );
+
+    // The 60 Hz budget:
+    let y_60hz = graph_rect.bottom() - graph_rect.height() * (1.0 / 60.0) / MAX_SECONDS;
+    painter.hline(
+        graph_rect.x_range(),
+        y_60hz,
+        Stroke::new(1.0, visuals.weak_text_color()),
+    );
+
+    let bar_width = graph_rect.width() / MAX_FRAMES as f32;
+    let first_x = graph_rect.right() - bar_width * frames.len() as f32;
+    for (i, frame) in frames.iter().enumerate() {
+        let x = first_x + bar_width * i as f32;
+        let mut y = graph_rect.bottom();
+        for ((_, seconds), color) in frame.parts().into_iter().zip(COLORS) {
+            let height = graph_rect.height() * seconds / MAX_SECONDS;
+            let top = (y - height).max(graph_rect.top());
+            if top < y {
+                painter.rect_filled(
+                    Rect::from_x_y_ranges(x..=x + bar_width, top..=y),
+                    0.0,
+                    color,
+                );
+            }
+            y = top;
+        }
+    }
+
+    // Legend, with the average of each part:
+    let num_frames = frames.len().max(1) as f32;
+    let mut job = text::LayoutJob::default();
+    for (i, ((name, _), color)) in FrameTimings::default()
+        .parts()
+        .into_iter()
+        .zip(COLORS)
+        .enumerate()
+    {
+        let average = frames.iter().map(|f| f.parts()[i].1).sum::<f32>() / num_frames;
+        job.append(
+            &format!("{name} {:.1} ", 1e3 * average),
+            0.0,
+            TextFormat::simple(font_id.clone(), color),
+        );
+    }
+    let total = frames.iter().map(|f| f.total()).sum::<f32>() / num_frames;
+    job.append(
+        &format!("total {:.1} ms", 1e3 * total),
+        0.0,
+        TextFormat::simple(font_id, visuals.text_color()),
+    );
+    job.wrap.max_width = graph_rect.width();
+    let galley = ctx.fonts(|f| f.layout_job(job));
+    painter.galley(
+        pos2(graph_rect.left(), graph_rect.bottom() + 2.0),
+        galley,
+        visuals.text_color(),
+    );
+}
@@ -0,0 +1,180 @@
+//! Reporting the progress of long-running tasks, see [`Context::progress`].
+
+use crate::{Context, Id, TaskbarProgress, ViewportId, ViewportIdMap};
+
+/// The latest state of a task reported with [`Context::progress`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskProgress {
+    pub id: Id,
+
+    /// The viewport whose [`crate::ProgressStatus`] and taskbar entry show the task.
+    pub viewport_id: ViewportId,
+
+    /// How far along the task is, in `0..=1`, or `None` if that is unknown (indeterminate).
+    pub fraction: Option<f32>,
+
+    /// What the task is doing, e.g. "Compressing…".
+    pub text: String,
+}
+
+/// Updates a task in the progress registry of a [`Context`].
+///
+/// Get one with [`Context::progress`].
+/// It is cheap to clone, and can be sent to the background thread doing the work.
+///
+/// The task is shown from the first call to [`Self::set`] or [`Self::set_indeterminate`]
+/// until [`Self::finish`] is called.
+#[derive(Clone)]
+pub struct Progress {
+    ctx: Context,
+    id: Id,
+    viewport_id: ViewportId,
+}
+
+impl Progress {
+    pub(crate) fn new(ctx: Context, id: Id, viewport_id: ViewportId) -> Self {
+        Self {
+            ctx,
+            id,
+            viewport_id,
+        }
+    }
+
+    /// Show the task in this viewport.
+    ///
+    /// Default: the viewport that called [`Context::progress`].
+    #[inline]
+    pub fn viewport(mut self, viewport_id: ViewportId) -> Self {
+        self.viewport_id = viewport_id;
+        self
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Report how far along the task is, in `0..=1`.
+    pub fn set(&self, fraction: f32, text: impl Into<String>) {
+        self.update(Some(fraction.clamp(0.0, 1.0)), text.into());
+    }
+
+    /// Report that the task is busy, without knowing how far along it is.
+    pub fn set_indeterminate(&self, text: impl Into<String>) {
+        self.update(None, text.into());
+    }
+
+    /// The task is done (or cancelled), so stop showing it.
+    pub fn finish(&self) {
+        self.ctx.remove_task_progress(self.id);
+        self.ctx.request_repaint_of(self.viewport_id);
+    }
+
+    fn update(&self, fraction: Option<f32>, text: String) {
+        let task = TaskProgress {
+            id: self.id,
+            viewport_id: self.viewport_id,
+            fraction,
+            text,
+        };
+        if self.ctx.set_task_progress(task) {
+            self.ctx.request_repaint_of(self.viewport_id);
+        }
+    }
+}
+
+/// The combined progress of all tasks of a viewport, see [`Context::aggregated_progress`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregatedProgress {
+    /// The average progress of the tasks, or `None` if any of them is indeterminate.
+    pub fraction: Option<f32>,
+
+    /// The text of the oldest task.
+    pub text: String,
+
+    /// How many tasks there are. Always at least one.
+    pub num_tasks: usize,
+}
+
+impl AggregatedProgress {
+    /// What to show on the taskbar entry of the window, rounded to whole percents.
+    pub fn taskbar_progress(&self) -> TaskbarProgress {
+        match self.fraction {
+            Some(fraction) => TaskbarProgress::Fraction((100.0 * fraction).round() / 100.0),
+            None => TaskbarProgress::Indeterminate,
+        }
+    }
+}
+
+/// All tasks reported with [`Context::progress`].
+#[derive(Default)]
+pub(crate) struct ProgressRegistry {
+    /// Oldest first.
+    pub tasks: Vec<TaskProgress>,
+
+    /// What was last sent with [`crate::ViewportCommand::TaskbarProgress`], per viewport.
+    pub taskbar: ViewportIdMap<TaskbarProgress>,
+}
+
+impl ProgressRegistry {
+    /// Returns `true` if anything changed.
+    pub fn set(&mut self, task: TaskProgress) -> bool {
+        if let Some(existing) = self.tasks.iter_mut().find(|t| t.id == task.id) {
+            let changed = *existing != task;
+            *existing = task;
+            changed
+        } else {
+            self.tasks.push(task);
+            true
+        }
+    }
+
+    pub fn remove(&mut self, id: Id) {
+        self.tasks.retain(|task| task.id != id);
+    }
+
+    pub fn aggregated(&self, viewport_id: ViewportId) -> Option<AggregatedProgress> {
+        let mut tasks = self
+            .tasks
+            .iter()
+            .filter(|task| task.viewport_id == viewport_id);
+        let first = tasks.next()?;
+
+        let mut num_tasks = 1;
+        let mut sum = first.fraction;
+        for task in tasks {
+            num_tasks += 1;
+            sum = sum.zip(task.fraction).map(|(sum, fraction)| sum + fraction);
+        }
+
+        Some(AggregatedProgress {
+            fraction: sum.map(|sum| sum / num_tasks as f32),
+            text: first.text.clone(),
+            num_tasks,
+        })
+    }
+}
+
+#[test]
+fn test_aggregated_progress() {
+    let ctx = Context::default();
+    let viewport_id = ViewportId::ROOT;
+    assert_eq!(ctx.aggregated_progress(viewport_id), None);
+
+    let compress = ctx.progress("compress");
+    let upload = ctx.progress("upload");
+    compress.set(0.5, "Compressing…");
+    upload.set(0.25, "Uploading…");
+    let progress = ctx.aggregated_progress(viewport_id).unwrap();
+    assert_eq!(progress.fraction, Some(0.375));
+    assert_eq!(progress.text, "Compressing…");
+    assert_eq!(progress.num_tasks, 2);
+    assert_eq!(progress.taskbar_progress(), TaskbarProgress::Fraction(0.38));
+
+    upload.set_indeterminate("Connecting…");
+    let progress = ctx.aggregated_progress(viewport_id).unwrap();
+    assert_eq!(progress.fraction, None);
+
+    compress.finish();
+    upload.finish();
+    assert_eq!(ctx.aggregated_progress(viewport_id), None);
+}
@@ -1,7 +1,8 @@
 use crate::{
-    emath::{Align, Pos2, Rect, Vec2},
-    menu, Context, CursorIcon, Id, LayerId, PointerButton, Sense, Ui, WidgetText,
-    NUM_POINTER_BUTTONS,
+    emath::{Align, Align2, Pos2, Rect, Vec2},
+    epaint::{Color32, RectShape, Shape, Stroke},
+    menu, Context, CursorIcon, FontSelection, Id, LayerId, PointerButton, Sense, TextStyle, Ui,
+    WidgetText, NUM_POINTER_BUTTONS,
 };
 
 // ----------------------------------------------------------------------------
@@ -30,6 +31,11 @@ pub struct Response {
     /// The area of the screen we are talking about.
     pub rect: Rect,
 
+    /// The clip rectangle of the [`Ui`] the widget was added to.
+    ///
+    /// Decorations painted outside of [`Self::rect`], like [`Self::badge`], are clipped to this.
+    pub clip_rect: Rect,
+
     /// The senses (click and/or drag) that the widget was interested in (if any).
     pub sense: Sense,
 
@@ -93,6 +99,7 @@ impl std::fmt::Debug for Response {
             layer_id,
             id,
             rect,
+            clip_rect,
             sense,
             enabled,
             hovered,
@@ -110,6 +117,7 @@ impl std::fmt::Debug for Response {
             .field("layer_id", layer_id)
             .field("id", id)
             .field("rect", rect)
+            .field("clip_rect", clip_rect)
             .field("sense", sense)
             .field("enabled", enabled)
             .field("hovered", hovered)
@@ -489,6 +497,104 @@ impl Response {
         self
     }
 
+    /// Paint a notification badge centered on a corner of the widget, e.g. `Align2::RIGHT_TOP`.
+    ///
+    /// The badge is painted on top of everything else in the layer, and does not affect the layout.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let num_unread: usize = 3;
+    /// ui.button("Inbox").badge(num_unread, egui::Align2::RIGHT_TOP);
+    /// ui.button("Settings").badge(egui::Badge::dot(), egui::Align2::RIGHT_TOP);
+    /// # });
+    /// ```
+    pub fn badge(self, badge: impl Into<Badge>, corner: Align2) -> Self {
+        let badge = badge.into();
+        let style = self.ctx.style();
+        let fill = badge.fill.unwrap_or(style.visuals.error_fg_color);
+        let center = corner.pos_in_rect(&self.rect);
+
+        let shapes = match &badge.text {
+            None => {
+                let radius = 0.25 * style.spacing.icon_width;
+                vec![Shape::circle_filled(center, radius, fill)]
+            }
+            Some(text) => {
+                let job = text.clone().into_layout_job(
+                    &style,
+                    FontSelection::Style(TextStyle::Small),
+                    Align::Center,
+                );
+                let galley = self.ctx.fonts(|f| f.layout_job(job));
+                let height = galley.size().y + 2.0;
+                let size = Vec2::new((galley.size().x + 0.5 * height).max(height), height);
+                let rect = Rect::from_center_size(center, size);
+                let text_pos = rect.center() - 0.5 * galley.size();
+                vec![
+                    RectShape::new(rect, 0.5 * height, fill, Stroke::NONE).into(),
+                    Shape::galley(text_pos, galley, badge.text_color),
+                ]
+            }
+        };
+        self.paint_decoration(shapes);
+
+        #[cfg(feature = "accesskit")]
+        if let Some(text) = &badge.text {
+            self.ctx.accesskit_node_builder(self.id, |builder| {
+                builder.set_description(text.text());
+            });
+        }
+
+        self
+    }
+
+    /// Paint a small icon inside a corner of the widget,
+    /// e.g. an error marker at `Align2::RIGHT_CENTER` of a [`crate::TextEdit`].
+    ///
+    /// Like [`Self::badge`], the icon is painted on top of the widget and does not affect the layout.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut email = String::new();
+    /// let mut response = ui.text_edit_singleline(&mut email);
+    /// if !email.contains('@') {
+    ///     let icon = egui::RichText::new("⚠").color(ui.visuals().error_fg_color);
+    ///     response = response
+    ///         .corner_icon(icon, egui::Align2::RIGHT_CENTER)
+    ///         .on_hover_text("Not a valid email address");
+    /// }
+    /// # });
+    /// ```
+    pub fn corner_icon(self, icon: impl Into<WidgetText>, corner: Align2) -> Self {
+        let style = self.ctx.style();
+        let job = icon.into().into_layout_job(
+            &style,
+            FontSelection::Style(TextStyle::Body),
+            Align::Center,
+        );
+        let galley = self.ctx.fonts(|f| f.layout_job(job));
+        let rect = corner.align_size_within_rect(
+            galley.size(),
+            self.rect.shrink2(style.spacing.button_padding),
+        );
+        self.paint_decoration(vec![Shape::galley(
+            rect.min,
+            galley,
+            style.visuals.text_color(),
+        )]);
+        self
+    }
+
+    /// Paint on top of everything else in the layer of the widget, clipped to [`Self::clip_rect`].
+    fn paint_decoration(&self, shapes: Vec<Shape>) {
+        if !self.clip_rect.is_positive() {
+            return;
+        }
+        self.ctx.graphics_mut(|g| {
+            g.overlay_list(self.layer_id).extend(self.clip_rect, shapes);
+        });
+    }
+
     /// Show this text when hovering if the widget is disabled.
     pub fn on_disabled_hover_text(self, text: impl Into<WidgetText>) -> Self {
         self.on_disabled_hover_ui(|ui| {
@@ -531,6 +637,7 @@ impl Response {
             self.layer_id,
             self.id,
             self.rect,
+            self.clip_rect,
             sense,
             self.enabled,
             self.hovered,
@@ -724,6 +831,7 @@ impl Response {
             layer_id: self.layer_id,
             id: self.id,
             rect: self.rect.union(other.rect),
+            clip_rect: self.clip_rect.union(other.clip_rect),
             sense: self.sense.union(other.sense),
             enabled: self.enabled || other.enabled,
             hovered: self.hovered || other.hovered,
@@ -766,6 +874,86 @@ impl Response {
     }
 }
 
+/// A notification badge, see [`Response::badge`].
+///
+/// Can be created from a count, a `&str` or a `String`.
+#[derive(Clone)]
+pub struct Badge {
+    text: Option<WidgetText>,
+    fill: Option<Color32>,
+    text_color: Color32,
+}
+
+impl std::fmt::Debug for Badge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Badge")
+            .field("text", &self.text.as_ref().map(WidgetText::text))
+            .field("fill", &self.fill)
+            .field("text_color", &self.text_color)
+            .finish()
+    }
+}
+
+impl Badge {
+    /// A small dot without text.
+    pub fn dot() -> Self {
+        Self {
+            text: None,
+            fill: None,
+            text_color: Color32::WHITE,
+        }
+    }
+
+    pub fn new(text: impl Into<WidgetText>) -> Self {
+        Self {
+            text: Some(text.into()),
+            fill: None,
+            text_color: Color32::WHITE,
+        }
+    }
+
+    /// Show a count, or "99+" for more than 99.
+    pub fn count(count: usize) -> Self {
+        if count > 99 {
+            Self::new("99+")
+        } else {
+            Self::new(count.to_string())
+        }
+    }
+
+    /// Default: [`crate::Visuals::error_fg_color`].
+    #[inline]
+    pub fn fill(mut self, fill: Color32) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+
+    /// The color of text that has no color of its own. Default: white.
+    #[inline]
+    pub fn text_color(mut self, text_color: Color32) -> Self {
+        self.text_color = text_color;
+        self
+    }
+}
+
+impl From<usize> for Badge {
+    fn from(count: usize) -> Self {
+        Self::count(count)
+    }
+}
+
+impl From<&str> for Badge {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for Badge {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
 /// To summarize the response from many widgets you can use this pattern:
 ///
 /// ```
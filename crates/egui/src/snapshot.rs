@@ -0,0 +1,504 @@
+//! Snapshot testing of what egui paints.
+//!
+//! [`to_snapshot`] turns a [`FullOutput`] into a stable, human readable text,
+//! and [`crate::assert_snapshot`] compares it against a file checked in next to your tests.
+//! This lets widget authors write regression tests that fail when layout or painting changes.
+//!
+//! The snapshot is normalized so that it only changes when what ends up on screen changes:
+//! * shapes are listed in paint order, with nested [`Shape::Vec`]s flattened and [`Shape::Noop`]s removed
+//! * numbers are rounded to two decimals
+//! * text is written as its string plus a hash of the glyph positions
+//! * meshes are written as their size, bounds and a hash of their vertices
+//!
+//! ```
+//! let ctx = egui::Context::default();
+//! let output = ctx.run(Default::default(), |ctx| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         ui.label("Hello snapshots!");
+//!     });
+//! });
+//! let snapshot = egui::snapshot::to_snapshot(&output);
+//! assert!(snapshot.contains("\"Hello snapshots!\""));
+//! ```
+
+use std::{fmt::Write as _, path::Path};
+
+use epaint::{
+    CircleShape, ClippedShape, CubicBezierShape, Galley, Mesh, PathShape, QuadraticBezierShape,
+    RectShape, Shape, TextShape,
+};
+
+use crate::*;
+
+/// Set this environment variable to overwrite snapshot files instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// Compare the painted output against the snapshot file with the given name.
+///
+/// The file is `tests/snapshots/<name>.txt` in the crate calling the macro.
+/// If the `UPDATE_SNAPSHOTS` environment variable is set, the file is (re)written instead.
+/// A missing file is an error, so that a test can't pass without comparing anything,
+/// e.g. on CI.
+///
+/// On a mismatch or a missing file the new snapshot is written to `tests/snapshots/<name>.new.txt`
+/// so you can diff the two.
+///
+/// ```no_run
+/// let ctx = egui::Context::default();
+/// let output = ctx.run(Default::default(), |ctx| {
+///     egui::CentralPanel::default().show(ctx, |ui| {
+///         let _ = ui.button("Click me");
+///     });
+/// });
+/// egui::assert_snapshot!("button", output);
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $output:expr) => {
+        $crate::snapshot::assert_snapshot_file(
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.txt", $name)),
+            &$crate::snapshot::to_snapshot(&$output),
+        )
+    };
+}
+
+/// Serialize the painted output of a frame to a stable text format.
+///
+/// See the [module-level docs](crate::snapshot).
+pub fn to_snapshot(output: &FullOutput) -> String {
+    to_snapshot_of_shapes(&output.shapes, output.pixels_per_point)
+}
+
+/// Like [`to_snapshot`], but for any list of shapes.
+pub fn to_snapshot_of_shapes(shapes: &[ClippedShape], pixels_per_point: f32) -> String {
+    let mut out = String::new();
+    writeln!(out, "pixels_per_point {}", num(pixels_per_point)).ok();
+
+    let mut current_clip = None;
+    for clipped in shapes {
+        let clip = (clipped.clip_rect, clipped.clip_shape.as_ref());
+        if current_clip != Some(clip) {
+            write!(out, "clip {}", rect(clipped.clip_rect)).ok();
+            if let Some(clip_shape) = &clipped.clip_shape {
+                write!(
+                    out,
+                    " shape {} points {}",
+                    clip_shape.points().len(),
+                    rect(clip_shape.bounding_rect())
+                )
+                .ok();
+            }
+            out.push('\n');
+            current_clip = Some(clip);
+        }
+        write_shape(&mut out, &clipped.shape);
+    }
+    out
+}
+
+/// Compare `snapshot` against the contents of the file at `path`.
+///
+/// Used by [`crate::assert_snapshot`].
+///
+/// # Panics
+/// If the snapshot doesn't match the file.
+pub fn assert_snapshot_file(path: &Path, snapshot: &str) {
+    if let Err(err) = compare_snapshot_file(path, snapshot) {
+        panic!("{err}");
+    }
+}
+
+/// Like [`assert_snapshot_file`], but returns an error message instead of panicking.
+pub fn compare_snapshot_file(path: &Path, snapshot: &str) -> Result<(), String> {
+    let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV_VAR).is_some();
+    compare_or_update_snapshot_file(path, snapshot, update)
+}
+
+/// Compare `snapshot` against the file at `path`, or overwrite the file if `update` is set.
+fn compare_or_update_snapshot_file(
+    path: &Path,
+    snapshot: &str,
+    update: bool,
+) -> Result<(), String> {
+    if update {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).ok();
+        }
+        return std::fs::write(path, snapshot)
+            .map_err(|err| format!("Failed to write snapshot {path:?}: {err}"));
+    }
+
+    let new_path = path.with_extension("new.txt");
+    match std::fs::read_to_string(path) {
+        Ok(expected) => {
+            if expected == snapshot {
+                return Ok(());
+            }
+            std::fs::write(&new_path, snapshot).ok();
+
+            let (line_nr, expected_line, actual_line) = expected
+                .lines()
+                .chain(std::iter::repeat(""))
+                .zip(snapshot.lines().chain(std::iter::repeat("")))
+                .enumerate()
+                .find(|(_, (expected, actual))| expected != actual)
+                .map(|(i, (expected, actual))| (i + 1, expected, actual))
+                .unwrap_or_default();
+            Err(format!(
+                "Snapshot {path:?} changed at line {line_nr}:\n  expected: {expected_line}\n  actual:   {actual_line}\n\
+                 The new snapshot was written to {new_path:?}. \
+                 Run with {UPDATE_SNAPSHOTS_ENV_VAR}=1 to accept it."
+            ))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).ok();
+            }
+            std::fs::write(&new_path, snapshot).ok();
+            Err(format!(
+                "Missing snapshot {path:?}. \
+                 The new snapshot was written to {new_path:?}. \
+                 Run with {UPDATE_SNAPSHOTS_ENV_VAR}=1 to accept it."
+            ))
+        }
+        Err(err) => Err(format!("Failed to read snapshot {path:?}: {err}")),
+    }
+}
+
+fn write_shape(out: &mut String, shape: &Shape) {
+    match shape {
+        Shape::Noop => {}
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                write_shape(out, shape);
+            }
+        }
+        Shape::Circle(CircleShape {
+            center,
+            radius,
+            fill,
+            stroke,
+        }) => {
+            writeln!(
+                out,
+                "  circle {} r {} fill {} stroke {}",
+                pos(*center),
+                num(*radius),
+                color(*fill),
+                stroke_str(*stroke)
+            )
+            .ok();
+        }
+        Shape::LineSegment { points, stroke } => {
+            writeln!(
+                out,
+                "  line {} {} stroke {}",
+                pos(points[0]),
+                pos(points[1]),
+                stroke_str(*stroke)
+            )
+            .ok();
+        }
+        Shape::Path(PathShape {
+            points,
+            closed,
+            fill,
+            stroke,
+        }) => {
+            writeln!(
+                out,
+                "  path {} points {} closed {closed} fill {} stroke {} hash {:016x}",
+                points.len(),
+                rect(Rect::from_points(points)),
+                color(*fill),
+                stroke_str(*stroke),
+                hash_positions(points.iter().copied())
+            )
+            .ok();
+        }
+        Shape::Rect(RectShape {
+            rect: r,
+            rounding,
+            fill,
+            stroke,
+            fill_texture_id,
+            uv,
+        }) => {
+            write!(
+                out,
+                "  rect {} rounding {} {} {} {} fill {} stroke {}",
+                rect(*r),
+                num(rounding.nw),
+                num(rounding.ne),
+                num(rounding.sw),
+                num(rounding.se),
+                color(*fill),
+                stroke_str(*stroke)
+            )
+            .ok();
+            if *fill_texture_id != TextureId::default() {
+                write!(out, " texture {fill_texture_id:?} uv {}", rect(*uv)).ok();
+            }
+            out.push('\n');
+        }
+        Shape::Text(TextShape {
+            pos: p,
+            galley,
+            underline,
+            fallback_color,
+            override_text_color,
+            angle,
+        }) => {
+            write!(
+                out,
+                "  text {} {:?} size {} glyphs {:016x} color {}",
+                pos(*p),
+                galley.text(),
+                vec(galley.size()),
+                hash_glyphs(galley),
+                color(override_text_color.unwrap_or(*fallback_color))
+            )
+            .ok();
+            if *underline != Stroke::NONE {
+                write!(out, " underline {}", stroke_str(*underline)).ok();
+            }
+            if *angle != 0.0 {
+                write!(out, " angle {}", num(*angle)).ok();
+            }
+            out.push('\n');
+        }
+        Shape::Mesh(mesh) => {
+            writeln!(
+                out,
+                "  mesh {:?} vertices {} indices {} bounds {} hash {:016x}",
+                mesh.texture_id,
+                mesh.vertices.len(),
+                mesh.indices.len(),
+                rect(mesh.calc_bounds()),
+                hash_mesh(mesh)
+            )
+            .ok();
+        }
+        Shape::Instanced { mesh, transforms } => {
+            writeln!(
+                out,
+                "  instanced {:?} vertices {} indices {} instances {} bounds {} hash {:016x}",
+                mesh.texture_id,
+                mesh.vertices.len(),
+                mesh.indices.len(),
+                transforms.len(),
+                rect(shape.visual_bounding_rect()),
+                hash_mesh(mesh)
+            )
+            .ok();
+        }
+        Shape::QuadraticBezier(QuadraticBezierShape {
+            points,
+            closed,
+            fill,
+            stroke,
+        }) => {
+            writeln!(
+                out,
+                "  quadratic_bezier {} {} {} closed {closed} fill {} stroke {}",
+                pos(points[0]),
+                pos(points[1]),
+                pos(points[2]),
+                color(*fill),
+                stroke_str(*stroke)
+            )
+            .ok();
+        }
+        Shape::CubicBezier(CubicBezierShape {
+            points,
+            closed,
+            fill,
+            stroke,
+        }) => {
+            writeln!(
+                out,
+                "  cubic_bezier {} {} {} {} closed {closed} fill {} stroke {}",
+                pos(points[0]),
+                pos(points[1]),
+                pos(points[2]),
+                pos(points[3]),
+                color(*fill),
+                stroke_str(*stroke)
+            )
+            .ok();
+        }
+        Shape::Callback(callback) => {
+            writeln!(out, "  callback {}", rect(callback.rect)).ok();
+        }
+    }
+}
+
+/// Round to two decimals, so that tiny floating point differences don't change the snapshot.
+fn round(value: f32) -> f32 {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded == 0.0 {
+        0.0 // no negative zero
+    } else {
+        rounded
+    }
+}
+
+fn num(value: f32) -> String {
+    round(value).to_string()
+}
+
+fn pos(p: Pos2) -> String {
+    format!("({} {})", num(p.x), num(p.y))
+}
+
+fn vec(v: Vec2) -> String {
+    format!("{}x{}", num(v.x), num(v.y))
+}
+
+fn rect(r: Rect) -> String {
+    format!(
+        "[{} {} - {} {}]",
+        num(r.min.x),
+        num(r.min.y),
+        num(r.max.x),
+        num(r.max.y)
+    )
+}
+
+fn color(c: Color32) -> String {
+    let [r, g, b, a] = c.to_array();
+    format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+}
+
+fn stroke_str(stroke: Stroke) -> String {
+    if stroke.is_empty() {
+        "none".to_owned()
+    } else {
+        format!("{} {}", num(stroke.width), color(stroke.color))
+    }
+}
+
+/// A hash that is the same on all platforms and versions of Rust (unlike [`std::hash::Hash`]).
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325) // FNV-1a offset basis
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_f32(&mut self, value: f32) {
+        self.write(&round(value).to_le_bytes());
+    }
+
+    fn write_pos(&mut self, p: Pos2) {
+        self.write_f32(p.x);
+        self.write_f32(p.y);
+    }
+}
+
+fn hash_positions(positions: impl Iterator<Item = Pos2>) -> u64 {
+    let mut hasher = StableHasher::new();
+    for p in positions {
+        hasher.write_pos(p);
+    }
+    hasher.0
+}
+
+fn hash_glyphs(galley: &Galley) -> u64 {
+    let mut hasher = StableHasher::new();
+    for row in &galley.rows {
+        for glyph in &row.glyphs {
+            hasher.write(&(glyph.chr as u32).to_le_bytes());
+            hasher.write_pos(glyph.pos);
+            hasher.write_f32(glyph.size.x);
+            hasher.write_f32(glyph.size.y);
+        }
+    }
+    hasher.0
+}
+
+fn hash_mesh(mesh: &Mesh) -> u64 {
+    let mut hasher = StableHasher::new();
+    for &index in &mesh.indices {
+        hasher.write(&index.to_le_bytes());
+    }
+    for vertex in &mesh.vertices {
+        hasher.write_pos(vertex.pos);
+        hasher.write_pos(vertex.uv);
+        hasher.write(&vertex.color.to_array());
+    }
+    hasher.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str) -> String {
+        let ctx = Context::default();
+        let mut output = None;
+        // Run twice, so that the layout has settled:
+        for _ in 0..2 {
+            output = Some(ctx.run(Default::default(), |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    ui.label(text);
+                    let _ = ui.button("Button");
+                });
+            }));
+        }
+        to_snapshot(&output.unwrap())
+    }
+
+    #[test]
+    fn snapshot_is_deterministic() {
+        let snapshot = run("Hello");
+        assert_eq!(snapshot, run("Hello"));
+        assert!(snapshot.contains("\"Hello\""));
+        assert!(snapshot.contains("\"Button\""));
+        assert_ne!(snapshot, run("Hello!"));
+    }
+
+    #[test]
+    fn snapshot_file() {
+        // Don't let `UPDATE_SNAPSHOTS` in the environment of the test run change the result:
+        let compare =
+            |path: &Path, snapshot: &str| compare_or_update_snapshot_file(path, snapshot, false);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("label.txt");
+
+        let result = compare(&path, "a\nb\n");
+        assert!(result.is_err(), "A missing snapshot should fail");
+        std::fs::rename(path.with_extension("new.txt"), &path).unwrap();
+
+        assert_eq!(compare(&path, "a\nb\n"), Ok(()));
+        let result = compare(&path, "a\nc\n");
+        let new_snapshot = std::fs::read_to_string(path.with_extension("new.txt"));
+
+        assert!(result.is_err(), "A changed snapshot should fail");
+        assert_eq!(new_snapshot.unwrap(), "a\nc\n");
+    }
+
+    #[test]
+    fn update_snapshot_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshots").join("label.txt");
+
+        assert_eq!(compare_or_update_snapshot_file(&path, "a\n", true), Ok(()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\n");
+
+        assert_eq!(compare_or_update_snapshot_file(&path, "b\n", true), Ok(()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "b\n");
+        assert!(!path.with_extension("new.txt").exists());
+    }
+}
@@ -215,6 +215,9 @@ pub struct Style {
 
     /// If true and scrolling is enabled for only one direction, allow horizontal scrolling without pressing shift
     pub always_scroll_the_only_direction: bool,
+
+    /// How [`DragValue`], [`crate::Slider`] and plot axes show numbers, and parse typed ones.
+    pub number_formatter: NumberFormatter,
 }
 
 impl Style {
@@ -717,6 +720,45 @@ pub struct Interaction {
 
     /// Delay in seconds before showing tooltips after the mouse stops moving
     pub tooltip_delay: f64,
+
+    /// Adapt interaction to touch screens. See [`TouchMode`].
+    pub touch_mode: TouchMode,
+
+    /// In touch mode, interactive widgets smaller than this get a larger hit area.
+    ///
+    /// This only affects where you can touch a widget, not how it looks.
+    pub touch_target_size: f32,
+}
+
+/// When to adapt interaction to fingers rather than a mouse.
+///
+/// In touch mode:
+/// * small widgets get a hit area of at least [`Interaction::touch_target_size`]
+/// * press-and-hold counts as a secondary click (e.g. opening a context menu)
+/// * [`crate::TextEdit`] shows draggable handles at both ends of the selection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TouchMode {
+    /// Never use touch mode.
+    Off,
+
+    /// Always use touch mode.
+    On,
+
+    /// Use touch mode when the last pointer event came from a touch screen.
+    #[default]
+    Auto,
+}
+
+impl TouchMode {
+    /// Should touch mode be used, given this input?
+    pub fn is_active(self, input: &crate::InputState) -> bool {
+        match self {
+            Self::Off => false,
+            Self::On => true,
+            Self::Auto => input.last_pointer_was_touch(),
+        }
+    }
 }
 
 /// Controls the visual style (colors etc) of egui.
@@ -1080,6 +1122,7 @@ impl Default for Style {
             debug: Default::default(),
             explanation_tooltips: false,
             always_scroll_the_only_direction: false,
+            number_formatter: NumberFormatter::default(),
         }
     }
 }
@@ -1114,6 +1157,8 @@ impl Default for Interaction {
             resize_grab_radius_corner: 10.0,
             show_tooltips_only_when_still: true,
             tooltip_delay: 0.0,
+            touch_mode: TouchMode::Auto,
+            touch_target_size: 40.0,
         }
     }
 }
@@ -1189,6 +1234,26 @@ impl Visuals {
             ..Self::dark()
         }
     }
+
+    /// Make the background of panels and windows translucent,
+    /// so that a compositor backdrop shows through.
+    ///
+    /// Use this together with [`crate::ViewportBuilder::with_backdrop`]:
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.set_visuals(egui::Visuals::dark().translucent());
+    /// ```
+    pub fn translucent(self) -> Self {
+        Self {
+            panel_fill: self.panel_fill.gamma_multiply(0.5),
+            window_fill: self.window_fill.gamma_multiply(0.8),
+            faint_bg_color: self.faint_bg_color.gamma_multiply(0.5),
+            extreme_bg_color: self.extreme_bg_color.gamma_multiply(0.7),
+            code_bg_color: self.code_bg_color.gamma_multiply(0.7),
+            ..self
+        }
+    }
 }
 
 impl Default for Visuals {
@@ -1337,6 +1402,7 @@ impl Style {
             debug,
             explanation_tooltips,
             always_scroll_the_only_direction,
+            number_formatter,
         } = self;
 
         visuals.light_dark_radio_buttons(ui);
@@ -1397,6 +1463,7 @@ impl Style {
         ui.collapsing("📏 Spacing", |ui| spacing.ui(ui));
         ui.collapsing("☝ Interaction", |ui| interaction.ui(ui));
         ui.collapsing("🎨 Visuals", |ui| visuals.ui(ui));
+        ui.collapsing("🔢 Number formatting", |ui| number_formatter.ui(ui));
 
         #[cfg(debug_assertions)]
         ui.collapsing("🐛 Debug", |ui| debug.ui(ui));
@@ -1573,6 +1640,8 @@ impl Interaction {
             resize_grab_radius_corner,
             show_tooltips_only_when_still,
             tooltip_delay,
+            touch_mode,
+            touch_target_size,
         } = self;
         ui.add(Slider::new(resize_grab_radius_side, 0.0..=20.0).text("resize_grab_radius_side"));
         ui.add(
@@ -1584,6 +1653,14 @@ impl Interaction {
         );
         ui.add(Slider::new(tooltip_delay, 0.0..=1.0).text("tooltip_delay"));
 
+        ui.horizontal(|ui| {
+            ui.label("Touch mode:");
+            ui.radio_value(touch_mode, TouchMode::Off, "Off");
+            ui.radio_value(touch_mode, TouchMode::On, "On");
+            ui.radio_value(touch_mode, TouchMode::Auto, "Auto");
+        });
+        ui.add(Slider::new(touch_target_size, 0.0..=80.0).text("touch_target_size"));
+
         ui.vertical_centered(|ui| reset_button(ui, self));
     }
 }
@@ -1947,6 +2024,150 @@ impl HandleShape {
     }
 }
 
+// ----------------------------------------------------------------------------
+
+/// How numbers are shown, e.g. `1,234.5` in English, `1.234,5` in German or `1.2345e3` in engineering notation.
+///
+/// Used by [`DragValue`], [`crate::Slider`] and plot axes, unless they have a custom formatter.
+/// [`Self::parse`] accepts what [`Self::format`] produces, so typed values round-trip.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// // German:
+/// ui.style_mut().number_formatter = egui::NumberFormatter {
+///     decimal_separator: ',',
+///     thousands_separator: Some('.'),
+///     engineering_notation: false,
+/// };
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NumberFormatter {
+    /// Separates the integer part from the fraction, e.g. `'.'` or `','`.
+    pub decimal_separator: char,
+
+    /// Separates groups of three digits in the integer part, e.g. `Some(',')`.
+    ///
+    /// When parsing, any whitespace is skipped if this is whitespace.
+    pub thousands_separator: Option<char>,
+
+    /// Show numbers with an exponent that is a multiple of three, e.g. `12.5e3` and `470e-9`.
+    pub engineering_notation: bool,
+}
+
+impl Default for NumberFormatter {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: None,
+            engineering_notation: false,
+        }
+    }
+}
+
+impl NumberFormatter {
+    /// Format a number with enough decimals to show it accurately, within the given range.
+    ///
+    /// See [`crate::emath::format_with_decimals_in_range`].
+    pub fn format(&self, value: f64, decimals: std::ops::RangeInclusive<usize>) -> String {
+        if self.engineering_notation && value.is_finite() && value != 0.0 {
+            let exponent = 3 * (value.abs().log10() / 3.0).floor() as i32;
+            if exponent != 0 {
+                let mantissa = value / 10_f64.powi(exponent);
+                // Allow as many decimals as needed to keep the same precision:
+                let max_decimals = (*decimals.end() as i32 + exponent).clamp(0, 16) as usize;
+                let min_decimals = (*decimals.start()).min(max_decimals);
+                let mantissa = crate::emath::format_with_decimals_in_range(
+                    mantissa,
+                    min_decimals..=max_decimals,
+                );
+                return format!("{}e{exponent}", self.localize(&mantissa));
+            }
+        }
+        self.localize(&crate::emath::format_with_decimals_in_range(
+            value, decimals,
+        ))
+    }
+
+    /// Parse a number written the way [`Self::format`] writes it.
+    ///
+    /// Exponents (`12.5e3`) are always accepted.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let skip_whitespace = self.thousands_separator.map_or(false, char::is_whitespace);
+        let mut normalized = String::with_capacity(text.len());
+        for c in text.trim().chars() {
+            if Some(c) == self.thousands_separator || (skip_whitespace && c.is_whitespace()) {
+                continue;
+            }
+            normalized.push(if c == self.decimal_separator { '.' } else { c });
+        }
+        normalized.parse().ok()
+    }
+
+    /// Apply the separators to a number formatted by Rust, e.g. `-1234.5`.
+    fn localize(&self, text: &str) -> String {
+        let (sign, unsigned) = match text.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", text),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (unsigned, None),
+        };
+        if !integer.bytes().all(|b| b.is_ascii_digit()) {
+            return text.to_owned(); // `inf` or `NaN`
+        }
+
+        let mut localized = String::with_capacity(text.len() + integer.len() / 3);
+        localized.push_str(sign);
+        for (i, digit) in integer.chars().enumerate() {
+            if let Some(separator) = self.thousands_separator {
+                if i != 0 && (integer.len() - i) % 3 == 0 {
+                    localized.push(separator);
+                }
+            }
+            localized.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            localized.push(self.decimal_separator);
+            localized.push_str(fraction);
+        }
+        localized
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        let Self {
+            decimal_separator,
+            thousands_separator,
+            engineering_notation,
+        } = self;
+
+        crate::Grid::new("number_formatter").show(ui, |ui| {
+            ui.label("Decimal separator:");
+            ui.horizontal(|ui| {
+                ui.radio_value(decimal_separator, '.', "Point");
+                ui.radio_value(decimal_separator, ',', "Comma");
+            });
+            ui.end_row();
+
+            ui.label("Thousands separator:");
+            ui.horizontal(|ui| {
+                ui.radio_value(thousands_separator, None, "None");
+                ui.radio_value(thousands_separator, Some(','), "Comma");
+                ui.radio_value(thousands_separator, Some('.'), "Point");
+                ui.radio_value(thousands_separator, Some('\u{202F}'), "Space");
+                ui.radio_value(thousands_separator, Some('\''), "Apostrophe");
+            });
+            ui.end_row();
+        });
+
+        ui.checkbox(engineering_notation, "Engineering notation");
+        ui.label(format!("Example: {}", self.format(-1_234_567.891, 0..=3)));
+    }
+}
+
 /// How to display numeric color values.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -1988,3 +2209,32 @@ impl std::fmt::Display for NumericColorSpace {
         }
     }
 }
+
+#[test]
+fn test_number_formatter() {
+    let german = NumberFormatter {
+        decimal_separator: ',',
+        thousands_separator: Some('.'),
+        engineering_notation: false,
+    };
+    assert_eq!(german.format(-12_345.5, 0..=3), "-12.345,5");
+    assert_eq!(german.format(123.0, 0..=3), "123");
+    assert_eq!(german.parse("-1.234.567,5"), Some(-1_234_567.5));
+
+    let french = NumberFormatter {
+        thousands_separator: Some('\u{202F}'),
+        ..german
+    };
+    assert_eq!(french.parse("1 234,5"), Some(1_234.5));
+
+    let engineering = NumberFormatter {
+        engineering_notation: true,
+        ..Default::default()
+    };
+    assert_eq!(engineering.format(12_500.0, 0..=3), "12.5e3");
+    assert_eq!(engineering.format(0.000_47, 0..=6), "470e-6");
+    assert_eq!(engineering.format(1.5, 0..=3), "1.5");
+    assert_eq!(engineering.parse("12.5e3"), Some(12_500.0));
+
+    assert_eq!(NumberFormatter::default().format(1234.5, 0..=3), "1234.5");
+}
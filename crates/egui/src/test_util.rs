@@ -0,0 +1,33 @@
+//! Helpers for running widgets in unit tests.
+
+use crate::{vec2, CentralPanel, Context, PointerButton, Pos2, RawInputBuilder, Ui};
+
+/// Run one frame on a 400x400 screen with `add_contents` in a [`CentralPanel`],
+/// and return what `add_contents` returned.
+pub fn run_in_panel<R>(
+    ctx: &Context,
+    input: RawInputBuilder,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> R {
+    let mut result = None;
+    let _ = ctx.run(input.screen_size(vec2(400.0, 400.0)).build(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| result = Some(add_contents(ui)));
+    });
+    result.expect("CentralPanel should always show its contents")
+}
+
+/// Move the pointer to `pos` and press the primary button there, one frame each.
+///
+/// `run` is called with the input of each frame.
+pub fn press(pos: Pos2, mut run: impl FnMut(RawInputBuilder)) {
+    run(RawInputBuilder::new().pointer_moved(pos));
+    run(RawInputBuilder::new().pointer_button(pos, PointerButton::Primary, true));
+}
+
+/// Click at `pos` with the primary button: move there, press and release, one frame each.
+///
+/// `run` is called with the input of each frame.
+pub fn click(pos: Pos2, mut run: impl FnMut(RawInputBuilder)) {
+    press(pos, &mut run);
+    run(RawInputBuilder::new().pointer_button(pos, PointerButton::Primary, false));
+}
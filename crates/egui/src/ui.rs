@@ -62,6 +62,9 @@ pub struct Ui {
 
     /// Indicates whether this Ui belongs to a Menu.
     menu_state: Option<Arc<RwLock<MenuState>>>,
+
+    /// See [`Self::is_sizing_pass`].
+    sizing_pass: bool,
 }
 
 impl Ui {
@@ -72,7 +75,10 @@ impl Ui {
     ///
     /// Normally you would not use this directly, but instead use
     /// [`SidePanel`], [`TopBottomPanel`], [`CentralPanel`], [`Window`] or [`Area`].
+    ///
+    /// The given `id` is namespaced by the current viewport using [`Context::viewport_local_id`].
     pub fn new(ctx: Context, layer_id: LayerId, id: Id, max_rect: Rect, clip_rect: Rect) -> Self {
+        let id = ctx.viewport_local_id(id);
         let style = ctx.style();
         Ui {
             id,
@@ -82,6 +88,7 @@ impl Ui {
             placer: Placer::new(max_rect, Layout::default()),
             enabled: true,
             menu_state: None,
+            sizing_pass: false,
         }
     }
 
@@ -109,6 +116,7 @@ impl Ui {
             placer: Placer::new(max_rect, layout),
             enabled: self.enabled,
             menu_state,
+            sizing_pass: self.sizing_pass,
         }
     }
 
@@ -250,6 +258,15 @@ impl Ui {
         self.painter.is_visible()
     }
 
+    /// Is this the invisible first run of [`Self::measure_then_place`]?
+    ///
+    /// Nothing added during a sizing pass is shown or interactive,
+    /// so you can use this to skip expensive work that doesn't affect the size.
+    #[inline]
+    pub fn is_sizing_pass(&self) -> bool {
+        self.sizing_pass
+    }
+
     /// Calling `set_visible(false)` will cause all further widgets to be invisible,
     /// yet still allocate space.
     ///
@@ -625,8 +642,9 @@ impl Ui {
 /// # Interaction
 impl Ui {
     /// Check for clicks, drags and/or hover on a specific region of this [`Ui`].
+    #[track_caller]
     pub fn interact(&self, rect: Rect, id: Id, sense: Sense) -> Response {
-        self.ctx().interact(
+        let response = self.ctx().interact(
             self.clip_rect(),
             self.spacing().item_spacing,
             self.layer_id(),
@@ -634,7 +652,8 @@ impl Ui {
             rect,
             sense,
             self.enabled,
-        )
+        );
+        self.hide_decorations_if_invisible(response)
     }
 
     /// Check for clicks, and drags on a specific region that is hovered.
@@ -649,8 +668,24 @@ impl Ui {
         id: Id,
         sense: Sense,
     ) -> Response {
-        self.ctx()
-            .interact_with_hovered(self.layer_id(), id, rect, sense, self.enabled, hovered)
+        let response = self.ctx().interact_with_hovered(
+            self.layer_id(),
+            id,
+            rect,
+            self.clip_rect(),
+            sense,
+            self.enabled,
+            hovered,
+        );
+        self.hide_decorations_if_invisible(response)
+    }
+
+    /// Don't paint [`Response::badge`] and the like for invisible widgets.
+    fn hide_decorations_if_invisible(&self, mut response: Response) -> Response {
+        if !self.is_visible() {
+            response.clip_rect = Rect::NOTHING;
+        }
+        response
     }
 
     /// Is the pointer (mouse/touch) above this rectangle in this [`Ui`]?
@@ -667,6 +702,43 @@ impl Ui {
     pub fn ui_contains_pointer(&self) -> bool {
         self.rect_contains_pointer(self.min_rect())
     }
+
+    /// Consume the first touch gesture matching the filter that took place over this [`Ui`].
+    ///
+    /// Consumed gestures are not seen by any other [`Ui`],
+    /// so nested zoomable views can each claim their own pinches.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let mut zoom = 1.0;
+    /// if let Some(egui::Gesture::Pinch { zoom_delta, .. }) =
+    ///     ui.consume_gesture(|g| matches!(g, egui::Gesture::Pinch { .. }))
+    /// {
+    ///     zoom *= zoom_delta;
+    /// }
+    /// # });
+    /// ```
+    pub fn consume_gesture(
+        &self,
+        filter: impl Fn(&crate::Gesture) -> bool,
+    ) -> Option<crate::Gesture> {
+        let rect = self.clip_rect().intersect(self.max_rect());
+        let layer_id = self.layer_id();
+        let gesture = self
+            .ctx()
+            .input(|i| i.gestures().to_vec())
+            .into_iter()
+            .find(|g| {
+                filter(g)
+                    && rect.contains(g.pos())
+                    && self
+                        .ctx()
+                        .layer_id_at(g.pos())
+                        .map_or(true, |l| l == layer_id)
+            })?;
+        self.ctx()
+            .input_mut(|i| i.consume_gesture(|g| *g == gesture))
+    }
 }
 
 /// # Allocating space: where do I put my widgets?
@@ -691,6 +763,7 @@ impl Ui {
     /// ui.painter().rect_stroke(response.rect, 0.0, (1.0, egui::Color32::WHITE));
     /// # });
     /// ```
+    #[track_caller]
     pub fn allocate_response(&mut self, desired_size: Vec2, sense: Sense) -> Response {
         let (id, rect) = self.allocate_space(desired_size);
         self.interact(rect, id, sense)
@@ -701,6 +774,7 @@ impl Ui {
     /// The response rect will be larger if this is part of a justified layout or similar.
     /// This means that if this is a narrow widget in a wide justified layout, then
     /// the widget will react to interactions outside the returned [`Rect`].
+    #[track_caller]
     pub fn allocate_exact_size(&mut self, desired_size: Vec2, sense: Sense) -> (Rect, Response) {
         let response = self.allocate_response(desired_size, sense);
         let rect = self
@@ -712,6 +786,7 @@ impl Ui {
     /// Allocate at least as much space as needed, and interact with that rect.
     ///
     /// The returned [`Rect`] will be the same size as `Response::rect`.
+    #[track_caller]
     pub fn allocate_at_least(&mut self, desired_size: Vec2, sense: Sense) -> (Rect, Response) {
         let response = self.allocate_response(desired_size, sense);
         (response.rect, response)
@@ -803,6 +878,7 @@ impl Ui {
     ///
     /// Ignore the layout of the [`Ui`]: just put my widget here!
     /// The layout cursor will advance to past this `rect`.
+    #[track_caller]
     pub fn allocate_rect(&mut self, rect: Rect, sense: Sense) -> Response {
         register_rect(self, rect);
         let id = self.advance_cursor_after_rect(rect);
@@ -941,6 +1017,7 @@ impl Ui {
     /// painter.line_segment([c, c + r * Vec2::angled(TAU * 3.0 / 8.0)], stroke);
     /// # });
     /// ```
+    #[track_caller]
     pub fn allocate_painter(&mut self, desired_size: Vec2, sense: Sense) -> (Response, Painter) {
         let response = self.allocate_response(desired_size, sense);
         let clip_rect = self.clip_rect().intersect(response.rect); // Make sure we don't paint out of bounds
@@ -1054,8 +1131,20 @@ impl Ui {
     /// # });
     /// ```
     #[inline]
+    #[track_caller]
     pub fn add(&mut self, widget: impl Widget) -> Response {
-        widget.ui(self)
+        self.add_at_location(widget, std::panic::Location::caller())
+    }
+
+    /// Add the widget, and tell the inspector that it was added by the code at `location`.
+    fn add_at_location(
+        &mut self,
+        widget: impl Widget,
+        location: &'static std::panic::Location<'static>,
+    ) -> Response {
+        let response = widget.ui(self);
+        self.ctx().set_inspector_location(response.id, location);
+        response
     }
 
     /// Add a [`Widget`] to this [`Ui`] with a given size.
@@ -1071,23 +1160,30 @@ impl Ui {
     /// ui.add_sized([40.0, 20.0], egui::DragValue::new(&mut my_value));
     /// # });
     /// ```
+    #[track_caller]
     pub fn add_sized(&mut self, max_size: impl Into<Vec2>, widget: impl Widget) -> Response {
+        let location = std::panic::Location::caller();
         // TODO(emilk): configure to overflow to main_dir instead of centered overflow
         // to handle the bug mentioned at https://github.com/emilk/egui/discussions/318#discussioncomment-627578
         // and fixed in https://github.com/emilk/egui/commit/035166276322b3f2324bd8b97ffcedc63fa8419f
         //
         // Make sure we keep the same main direction since it changes e.g. how text is wrapped:
         let layout = Layout::centered_and_justified(self.layout().main_dir());
-        self.allocate_ui_with_layout(max_size.into(), layout, |ui| ui.add(widget))
-            .inner
+        self.allocate_ui_with_layout(max_size.into(), layout, |ui| {
+            ui.add_at_location(widget, location)
+        })
+        .inner
     }
 
     /// Add a [`Widget`] to this [`Ui`] at a specific location (manual layout).
     ///
     /// See also [`Self::add`] and [`Self::add_sized`].
+    #[track_caller]
     pub fn put(&mut self, max_rect: Rect, widget: impl Widget) -> Response {
+        let location = std::panic::Location::caller();
         self.allocate_ui_at_rect(max_rect, |ui| {
-            ui.centered_and_justified(|ui| ui.add(widget)).inner
+            ui.centered_and_justified(|ui| ui.add_at_location(widget, location))
+                .inner
         })
         .inner
     }
@@ -1104,19 +1200,46 @@ impl Ui {
     /// ui.add_enabled(false, egui::Button::new("Can't click this"));
     /// # });
     /// ```
+    #[track_caller]
     pub fn add_enabled(&mut self, enabled: bool, widget: impl Widget) -> Response {
+        let location = std::panic::Location::caller();
         if self.is_enabled() && !enabled {
             let old_painter = self.painter.clone();
             self.set_enabled(false);
-            let response = self.add(widget);
+            let response = self.add_at_location(widget, location);
             self.enabled = true;
             self.painter = old_painter;
             response
         } else {
-            self.add(widget)
+            self.add_at_location(widget, location)
         }
     }
 
+    /// Show an editor for a value, e.g. a property grid for a struct with `#[derive(EguiInspect)]`.
+    ///
+    /// The response is [`Response::changed`] if the value was changed.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut speed = 1.0_f32;
+    /// # let mut offset = egui::Vec2::ZERO;
+    /// egui::Grid::new("settings").show(ui, |ui| {
+    ///     ui.label("Speed");
+    ///     ui.inspect(&mut speed);
+    ///     ui.end_row();
+    ///
+    ///     ui.label("Offset");
+    ///     ui.inspect(&mut offset);
+    ///     ui.end_row();
+    /// });
+    /// # });
+    /// ```
+    ///
+    /// See [`EguiInspect`] for more.
+    pub fn inspect(&mut self, value: &mut (impl EguiInspect + ?Sized)) -> Response {
+        value.inspect_ui(self, &InspectOptions::default())
+    }
+
     /// Add a section that is possibly disabled, i.e. greyed out and non-interactive.
     ///
     /// If you call `add_enabled_ui` from within an already disabled [`Ui`],
@@ -1161,7 +1284,9 @@ impl Ui {
     /// ui.add_visible(false, egui::Label::new("You won't see me!"));
     /// # });
     /// ```
+    #[track_caller]
     pub fn add_visible(&mut self, visible: bool, widget: impl Widget) -> Response {
+        let location = std::panic::Location::caller();
         if self.is_visible() && !visible {
             // temporary make us invisible:
             let old_painter = self.painter.clone();
@@ -1169,13 +1294,13 @@ impl Ui {
 
             self.set_visible(false);
 
-            let response = self.add(widget);
+            let response = self.add_at_location(widget, location);
 
             self.painter = old_painter;
             self.enabled = old_enabled;
             response
         } else {
-            self.add(widget)
+            self.add_at_location(widget, location)
         }
     }
 
@@ -1236,61 +1361,69 @@ impl Ui {
     /// # });
     /// ```
     #[inline]
+    #[track_caller]
     pub fn label(&mut self, text: impl Into<WidgetText>) -> Response {
-        Label::new(text).ui(self)
+        self.add(Label::new(text))
     }
 
     /// Show colored text.
     ///
     /// Shortcut for `ui.label(RichText::new(text).color(color))`
+    #[track_caller]
     pub fn colored_label(
         &mut self,
         color: impl Into<Color32>,
         text: impl Into<RichText>,
     ) -> Response {
-        Label::new(text.into().color(color)).ui(self)
+        self.add(Label::new(text.into().color(color)))
     }
 
     /// Show large text.
     ///
     /// Shortcut for `ui.label(RichText::new(text).heading())`
+    #[track_caller]
     pub fn heading(&mut self, text: impl Into<RichText>) -> Response {
-        Label::new(text.into().heading()).ui(self)
+        self.add(Label::new(text.into().heading()))
     }
 
     /// Show monospace (fixed width) text.
     ///
     /// Shortcut for `ui.label(RichText::new(text).monospace())`
+    #[track_caller]
     pub fn monospace(&mut self, text: impl Into<RichText>) -> Response {
-        Label::new(text.into().monospace()).ui(self)
+        self.add(Label::new(text.into().monospace()))
     }
 
     /// Show text as monospace with a gray background.
     ///
     /// Shortcut for `ui.label(RichText::new(text).code())`
+    #[track_caller]
     pub fn code(&mut self, text: impl Into<RichText>) -> Response {
-        Label::new(text.into().code()).ui(self)
+        self.add(Label::new(text.into().code()))
     }
 
     /// Show small text.
     ///
     /// Shortcut for `ui.label(RichText::new(text).small())`
+    #[track_caller]
     pub fn small(&mut self, text: impl Into<RichText>) -> Response {
-        Label::new(text.into().small()).ui(self)
+        self.add(Label::new(text.into().small()))
     }
 
     /// Show text that stand out a bit (e.g. slightly brighter).
     ///
     /// Shortcut for `ui.label(RichText::new(text).strong())`
+    #[track_caller]
     pub fn strong(&mut self, text: impl Into<RichText>) -> Response {
-        Label::new(text.into().strong()).ui(self)
+        self.add(Label::new(text.into().strong()))
     }
 
     /// Show text that is weaker (fainter color).
     ///
     /// Shortcut for `ui.label(RichText::new(text).weak())`
+    #[track_caller]
     pub fn weak(&mut self, text: impl Into<RichText>) -> Response {
-        Label::new(text.into().weak()).ui(self)
+        self.add(Label::new(text.into().weak()))
     }
 
     /// Looks like a hyperlink.
@@ -1307,8 +1440,9 @@ impl Ui {
     ///
     /// See also [`Link`].
     #[must_use = "You should check if the user clicked this with `if ui.link(…).clicked() { … } "]
+    #[track_caller]
     pub fn link(&mut self, text: impl Into<WidgetText>) -> Response {
-        Link::new(text).ui(self)
+        self.add(Link::new(text))
     }
 
     /// Link to a web page.
@@ -1322,8 +1456,9 @@ impl Ui {
     /// ```
     ///
     /// See also [`Hyperlink`].
+    #[track_caller]
     pub fn hyperlink(&mut self, url: impl ToString) -> Response {
-        Hyperlink::new(url).ui(self)
+        self.add(Hyperlink::new(url))
     }
 
     /// Shortcut for `add(Hyperlink::from_label_and_url(label, url))`.
@@ -1335,28 +1470,31 @@ impl Ui {
     /// ```
     ///
     /// See also [`Hyperlink`].
+    #[track_caller]
     pub fn hyperlink_to(&mut self, label: impl Into<WidgetText>, url: impl ToString) -> Response {
-        Hyperlink::from_label_and_url(label, url).ui(self)
+        self.add(Hyperlink::from_label_and_url(label, url))
     }
 
     /// No newlines (`\n`) allowed. Pressing enter key will result in the [`TextEdit`] losing focus (`response.lost_focus`).
     ///
     /// See also [`TextEdit`].
+    #[track_caller]
     pub fn text_edit_singleline<S: widgets::text_edit::TextBuffer>(
         &mut self,
         text: &mut S,
     ) -> Response {
-        TextEdit::singleline(text).ui(self)
+        self.add(TextEdit::singleline(text))
     }
 
     /// A [`TextEdit`] for multiple lines. Pressing enter key will create a new line.
     ///
     /// See also [`TextEdit`].
+    #[track_caller]
     pub fn text_edit_multiline<S: widgets::text_edit::TextBuffer>(
         &mut self,
         text: &mut S,
     ) -> Response {
-        TextEdit::multiline(text).ui(self)
+        self.add(TextEdit::multiline(text))
     }
 
     /// A [`TextEdit`] for code editing.
@@ -1364,6 +1502,7 @@ impl Ui {
     /// This will be multiline, monospace, and will insert tabs instead of moving focus.
     ///
     /// See also [`TextEdit::code_editor`].
+    #[track_caller]
     pub fn code_editor<S: widgets::text_edit::TextBuffer>(&mut self, text: &mut S) -> Response {
         self.add(TextEdit::multiline(text).code_editor())
     }
@@ -1388,8 +1527,9 @@ impl Ui {
     /// ```
     #[must_use = "You should check if the user clicked this with `if ui.button(…).clicked() { … } "]
     #[inline]
+    #[track_caller]
     pub fn button(&mut self, text: impl Into<WidgetText>) -> Response {
-        Button::new(text).ui(self)
+        self.add(Button::new(text))
     }
 
     /// A button as small as normal body text.
@@ -1398,16 +1538,27 @@ impl Ui {
     ///
     /// Shortcut for `add(Button::new(text).small())`
     #[must_use = "You should check if the user clicked this with `if ui.small_button(…).clicked() { … } "]
+    #[track_caller]
     pub fn small_button(&mut self, text: impl Into<WidgetText>) -> Response {
-        Button::new(text).small().ui(self)
+        self.add(Button::new(text).small())
     }
 
     /// Show a checkbox.
     ///
     /// See also [`Self::toggle_value`].
     #[inline]
+    #[track_caller]
     pub fn checkbox(&mut self, checked: &mut bool, text: impl Into<WidgetText>) -> Response {
-        Checkbox::new(checked, text).ui(self)
+        self.add(Checkbox::new(checked, text))
+    }
+
+    /// Show a [`ToggleSwitch`], an on/off switch that acts like a checkbox.
+    ///
+    /// See also [`Self::checkbox`].
+    #[inline]
+    #[track_caller]
+    pub fn toggle_switch(&mut self, on: &mut bool, text: impl Into<WidgetText>) -> Response {
+        self.add(ToggleSwitch::new(on, text))
     }
 
     /// Acts like a checkbox, but looks like a [`SelectableLabel`].
@@ -1428,8 +1579,9 @@ impl Ui {
     /// Often you want to use [`Self::radio_value`] instead.
     #[must_use = "You should check if the user clicked this with `if ui.radio(…).clicked() { … } "]
     #[inline]
+    #[track_caller]
     pub fn radio(&mut self, selected: bool, text: impl Into<WidgetText>) -> Response {
-        RadioButton::new(selected, text).ui(self)
+        self.add(RadioButton::new(selected, text))
     }
 
     /// Show a [`RadioButton`]. It is selected if `*current_value == selected_value`.
@@ -1469,8 +1621,9 @@ impl Ui {
     ///
     /// See also [`SelectableLabel`] and [`Self::toggle_value`].
     #[must_use = "You should check if the user clicked this with `if ui.selectable_label(…).clicked() { … } "]
+    #[track_caller]
     pub fn selectable_label(&mut self, checked: bool, text: impl Into<WidgetText>) -> Response {
-        SelectableLabel::new(checked, text).ui(self)
+        self.add(SelectableLabel::new(checked, text))
     }
 
     /// Show selectable text. It is selected if `*current_value == selected_value`.
@@ -1497,20 +1650,23 @@ impl Ui {
     ///
     /// See also [`Separator`].
     #[inline]
+    #[track_caller]
     pub fn separator(&mut self) -> Response {
-        Separator::default().ui(self)
+        self.add(Separator::default())
     }
 
     /// Shortcut for `add(Spinner::new())`
     ///
     /// See also [`Spinner`].
     #[inline]
+    #[track_caller]
     pub fn spinner(&mut self) -> Response {
-        Spinner::new().ui(self)
+        self.add(Spinner::new())
     }
 
     /// Modify an angle. The given angle should be in radians, but is shown to the user in degrees.
     /// The angle is NOT wrapped, so the user may select, for instance 720° = 2𝞃 = 4π
+    #[track_caller]
     pub fn drag_angle(&mut self, radians: &mut f32) -> Response {
         let mut degrees = radians.to_degrees();
         let mut response = self.add(DragValue::new(&mut degrees).speed(1.0).suffix("°"));
@@ -1527,6 +1683,7 @@ impl Ui {
     /// Modify an angle. The given angle should be in radians,
     /// but is shown to the user in fractions of one Tau (i.e. fractions of one turn).
     /// The angle is NOT wrapped, so the user may select, for instance 2𝞃 (720°)
+    #[track_caller]
     pub fn drag_angle_tau(&mut self, radians: &mut f32) -> Response {
         use std::f32::consts::TAU;
 
@@ -1574,8 +1731,9 @@ impl Ui {
     ///
     /// See also [`crate::Image`], [`crate::ImageSource`].
     #[inline]
+    #[track_caller]
     pub fn image<'a>(&mut self, source: impl Into<ImageSource<'a>>) -> Response {
-        Image::new(source).ui(self)
+        self.add(Image::new(source))
     }
 }
 
@@ -1734,6 +1892,62 @@ impl Ui {
         InnerResponse::new(ret, response)
     }
 
+    /// Run `add_contents` twice: first invisibly to measure the size of the contents,
+    /// then for real in a [`Ui`] of exactly that size.
+    ///
+    /// Normally egui only learns the size of some contents after laying them out,
+    /// so contents that depend on their own size (e.g. centered or right-aligned widgets
+    /// in a container that should shrink to fit) are misplaced for a frame.
+    /// This avoids that, at the cost of running `add_contents` twice.
+    ///
+    /// `size_hash` should cover everything that affects the size of the contents.
+    /// The measured size is remembered for that hash (and the available width),
+    /// so on later frames only one pass is needed.
+    ///
+    /// During the first pass [`Self::is_sizing_pass`] is `true`.
+    /// Nothing painted then is shown, and no widget is interactive,
+    /// but widgets may still read and write their state in [`Memory`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let names = ["Alice", "Bob", "Charlie"];
+    /// ui.measure_then_place(&names, |ui| {
+    ///     ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
+    ///         for name in &names {
+    ///             ui.label(*name);
+    ///         }
+    ///     });
+    /// });
+    /// # });
+    /// ```
+    pub fn measure_then_place<R>(
+        &mut self,
+        size_hash: impl Hash,
+        mut add_contents: impl FnMut(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let available = self.available_rect_before_wrap();
+        let size_id = Id::new(("measure_then_place", available.width().to_bits())).with(size_hash);
+
+        let size = if let Some(size) = self.ctx().data(|d| d.get_temp::<Vec2>(size_id)) {
+            size
+        } else {
+            let start = self.ctx().begin_recording(self.layer_id());
+            let next_auto_id_source = self.next_auto_id_source;
+            let mut sizing_ui = self.child_ui(available, *self.layout());
+            self.next_auto_id_source = next_auto_id_source; // So both passes get the same ids.
+            sizing_ui.sizing_pass = true;
+            sizing_ui.set_visible(false);
+            add_contents(&mut sizing_ui);
+            self.ctx().discard_recording(&start);
+
+            let size = sizing_ui.min_rect().size();
+            self.ctx().data_mut(|d| d.insert_temp(size_id, size));
+            size
+        };
+
+        self.allocate_ui_with_layout(size, *self.layout(), add_contents)
+    }
+
     /// Redirect shapes to another paint layer.
     pub fn with_layer_id<R>(
         &mut self,
@@ -1746,6 +1960,93 @@ impl Ui {
         })
     }
 
+    /// Only re-run `add_contents` when `version` changes.
+    ///
+    /// The first time, `add_contents` is run as usual, and everything it paints and
+    /// every interactive widget it adds is recorded.
+    /// On later frames, if `version` (e.g. a hash of the data you are showing) is unchanged,
+    /// the recording is replayed instead, and `None` is returned.
+    /// This can save a lot of CPU for big, static parts of a UI.
+    ///
+    /// The contents are re-run anyway if the available size or clip rectangle changes,
+    /// if the user hovers or interacts with a widget in the block,
+    /// if a widget in the block has keyboard focus,
+    /// or if the contents requested a repaint (e.g. for an animation).
+    /// On replayed frames the widgets of the block can still get keyboard focus with Tab,
+    /// and their state in [`crate::Memory::data`] counts as used.
+    ///
+    /// `version` must cover everything that affects the contents, including the [`Style`].
+    /// Only shapes painted to the layer of this [`Ui`] are replayed,
+    /// so popups and tooltips opened by the contents won't show up on replayed frames.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let lines = vec!["a"; 1000];
+    /// let version = lines.len() as u64;
+    /// ui.retained("lines", version, |ui| {
+    ///     for line in &lines {
+    ///         ui.label(*line);
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn retained<R>(
+        &mut self,
+        id_source: impl Hash,
+        version: u64,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<Option<R>> {
+        let id = self.make_persistent_id(id_source);
+        let child_rect = self.available_rect_before_wrap();
+        let next_auto_id_source = self.next_auto_id_source;
+        let mut child_ui = self.child_ui_with_id_source(child_rect, *self.layout(), id);
+        self.next_auto_id_source = next_auto_id_source; // Same as in `scope`.
+
+        let layer_id = child_ui.layer_id();
+        let clip_rect = child_ui.clip_rect().translate(-child_rect.min.to_vec2());
+        let pixels_per_point = self.ctx().pixels_per_point();
+
+        let previous = self.ctx().data(|d| d.get_temp::<RetainedState>(id));
+        let replay = previous.filter(|previous| {
+            let delta = child_rect.min - previous.origin;
+            previous.version == version
+                && previous.recording.layer_id == layer_id
+                && previous.max_size == child_rect.size()
+                && previous.clip_rect == clip_rect
+                && previous.pixels_per_point == pixels_per_point
+                && !previous.was_active
+                && !previous.recording.requested_repaint
+                && !self.ctx().is_recording_active(&previous.recording, delta)
+        });
+
+        let inner = if let Some(previous) = replay {
+            let delta = child_rect.min - previous.origin;
+            self.ctx().replay_recording(&previous.recording, delta);
+            child_ui.expand_to_include_rect(previous.min_rect.translate(delta));
+            None
+        } else {
+            let start = self.ctx().begin_recording(layer_id);
+            let inner = add_contents(&mut child_ui);
+            let recording = self.ctx().end_recording(&start);
+            let was_active = self.ctx().is_recording_active(&recording, Vec2::ZERO);
+            let state = RetainedState {
+                version,
+                origin: child_rect.min,
+                max_size: child_rect.size(),
+                clip_rect,
+                pixels_per_point,
+                min_rect: child_ui.min_rect(),
+                was_active,
+                recording: Arc::new(recording),
+            };
+            self.ctx().data_mut(|d| d.insert_temp(id, state));
+            Some(inner)
+        };
+
+        let response = self.allocate_rect(child_ui.min_rect(), Sense::hover());
+        InnerResponse::new(inner, response)
+    }
+
     /// A [`CollapsingHeader`] that starts out collapsed.
     pub fn collapsing<R>(
         &mut self,
@@ -1755,6 +2056,37 @@ impl Ui {
         CollapsingHeader::new(heading).show(self, add_contents)
     }
 
+    /// A list of items that the user can reorder by dragging the handles next to them.
+    ///
+    /// While dragging, the other items make room where the item would be dropped,
+    /// and an enclosing [`ScrollArea`] scrolls when dragging close to its edges.
+    /// The `items` are reordered when the item is dropped.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut tasks = vec!["Wake up".to_owned(), "Brush teeth".to_owned()];
+    /// let response = ui.reorderable_list("tasks", &mut tasks, |ui, task| {
+    ///     ui.label(task.as_str());
+    /// });
+    /// if let Some(new_order) = response.new_order {
+    ///     // `new_order[i]` is the old index of what is now `tasks[i]`.
+    /// }
+    /// # });
+    /// ```
+    pub fn reorderable_list<T>(
+        &mut self,
+        id_source: impl Hash,
+        items: &mut [T],
+        add_item: impl FnMut(&mut Ui, &mut T),
+    ) -> ReorderableListResponse {
+        crate::containers::reorderable_list::reorderable_list(
+            self,
+            Id::new(id_source),
+            items,
+            add_item,
+        )
+    }
+
     /// Create a child ui which is indented to the right.
     ///
     /// The `id_source` here be anything at all.
@@ -2046,6 +2378,30 @@ impl Ui {
             .end_row(self.spacing().item_spacing, &self.painter().clone());
     }
 
+    /// Make the next cell in a [`Grid`] cover `colspan` columns and `rowspan` rows.
+    ///
+    /// The cells covered by a row span are skipped in the following rows.
+    /// Outside of a [`Grid`] this does nothing.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// egui::Grid::new("spans").num_columns(2).show(ui, |ui| {
+    ///     ui.set_grid_cell_span(2, 1);
+    ///     ui.heading("A heading spanning both columns");
+    ///     ui.end_row();
+    ///
+    ///     ui.label("Name:");
+    ///     ui.label("Alice");
+    ///     ui.end_row();
+    /// });
+    /// # });
+    /// ```
+    pub fn set_grid_cell_span(&mut self, colspan: usize, rowspan: usize) {
+        if let Some(grid) = self.placer.grid_mut() {
+            grid.set_next_cell_span(colspan, rowspan);
+        }
+    }
+
     /// Set row height in horizontal wrapping layout.
     pub fn set_row_height(&mut self, height: f32) {
         self.placer.set_row_height(height);
@@ -2312,8 +2668,155 @@ fn register_rect(ui: &Ui, rect: Rect) {
 #[cfg(not(debug_assertions))]
 fn register_rect(_ui: &Ui, _rect: Rect) {}
 
+/// What [`Ui::retained`] remembers between frames.
+#[derive(Clone)]
+struct RetainedState {
+    version: u64,
+
+    /// Top left corner of the available rectangle when recording.
+    origin: Pos2,
+
+    max_size: Vec2,
+
+    /// Relative to [`Self::origin`].
+    clip_rect: Rect,
+
+    pixels_per_point: f32,
+
+    min_rect: Rect,
+
+    /// Was the user interacting with the contents while recording?
+    /// If so, we need to record again next frame.
+    was_active: bool,
+
+    recording: Arc<crate::context::Recording>,
+}
+
 #[test]
 fn ui_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Ui>();
 }
+
+#[test]
+fn retained_replays_unchanged_contents() {
+    let ctx = Context::default();
+    let mut num_runs = 0;
+    let mut num_shapes = vec![];
+    for _ in 0..3 {
+        let output = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                ui.retained("retained", 1, |ui| {
+                    num_runs += 1;
+                    ui.label("Hello");
+                    ui.label("World");
+                });
+            });
+        });
+        num_shapes.push(output.shapes.len());
+    }
+    assert_eq!(num_runs, 1, "The contents should only be run once");
+    assert!(num_shapes.iter().all(|&n| n == num_shapes[0]));
+
+    let _ = ctx.run(Default::default(), |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            ui.retained("retained", 2, |ui| {
+                num_runs += 1;
+                ui.label("Hello");
+            });
+        });
+    });
+    assert_eq!(num_runs, 2, "A new version should re-run the contents");
+}
+
+#[test]
+fn retained_tab_moves_focus_into_replayed_block() {
+    fn run(ctx: &Context, input: RawInput, num_runs: &mut usize) -> Option<[Id; 2]> {
+        let mut ids = None;
+        let _ = ctx.run(input, |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let before = ui.button("Before").id;
+                ui.retained("retained", 1, |ui| {
+                    *num_runs += 1;
+                    ids = Some([before, ui.button("Inside").id]);
+                });
+            });
+        });
+        ids
+    }
+    let tab = |pressed| RawInputBuilder::new().key(Key::Tab, pressed).build();
+
+    let ctx = Context::default();
+    let mut num_runs = 0;
+    let [before, inside] = run(&ctx, Default::default(), &mut num_runs).unwrap();
+
+    ctx.memory_mut(|mem| mem.request_focus(before));
+    run(&ctx, Default::default(), &mut num_runs);
+    run(&ctx, Default::default(), &mut num_runs);
+    assert_eq!(num_runs, 1, "Focus outside the block shouldn't re-run it");
+
+    run(&ctx, tab(true), &mut num_runs);
+    assert_eq!(num_runs, 1, "The block should be replayed");
+    assert_eq!(ctx.memory(|mem| mem.focus()), Some(inside));
+
+    run(&ctx, tab(false), &mut num_runs);
+    assert_eq!(num_runs, 2, "The focused widget should be shown");
+
+    run(
+        &ctx,
+        RawInputBuilder::new().key(Key::Escape, true).build(),
+        &mut num_runs,
+    );
+    assert_eq!(ctx.memory(|mem| mem.focus()), None);
+    assert_eq!(num_runs, 3, "The widget that lost focus should be shown");
+}
+
+#[test]
+fn retained_block_keeps_its_data() {
+    let ctx = Context::default();
+    ctx.options_mut(|options| options.gc_unused_data_after_frames = Some(1));
+    let id = Id::new("state");
+
+    let mut num_runs = 0;
+    for _ in 0..5 {
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                ui.retained("retained", 1, |ui| {
+                    num_runs += 1;
+                    ui.data_mut(|data| *data.get_temp_mut_or_default::<u32>(id) += 1);
+                    ui.label("Hello");
+                });
+            });
+        });
+    }
+    assert_eq!(num_runs, 1);
+    assert_eq!(
+        ctx.data(|data| data.get_temp::<u32>(id)),
+        Some(1),
+        "The data of a replayed block should not be garbage collected"
+    );
+}
+
+#[test]
+fn measure_then_place_sizing_pass() {
+    let ctx = Context::default();
+    let mut passes = vec![];
+    for _ in 0..2 {
+        let _ = ctx.run(Default::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                let response = ui.measure_then_place("contents", |ui| {
+                    passes.push(ui.is_sizing_pass());
+                    ui.allocate_space(vec2(100.0, 50.0));
+                    ui.is_sizing_pass()
+                });
+                assert!(!response.inner);
+                assert_eq!(response.response.rect.size(), vec2(100.0, 50.0));
+            });
+        });
+    }
+    assert_eq!(
+        passes,
+        [true, false, false],
+        "The size should be remembered after the first frame"
+    );
+}
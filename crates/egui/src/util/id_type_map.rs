@@ -3,7 +3,13 @@
 // For non-serializable types, these simply return `None`.
 // This will also allow users to pick their own serialization format per type.
 
-use std::{any::Any, sync::Arc};
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 // -----------------------------------------------------------------------------------------------
 
@@ -284,6 +290,58 @@ impl Element {
             Self::Serialized(element) => Some(element.clone()),
         }
     }
+
+    /// Approximately how many bytes this element uses,
+    /// not counting any heap allocations owned by the value.
+    fn num_bytes(&self) -> usize {
+        let payload = match self {
+            Self::Value { value, .. } => std::mem::size_of_val(&**value),
+            Self::Serialized(SerializedElement { ron, .. }) => ron.len(),
+        };
+        std::mem::size_of::<Self>() + payload
+    }
+}
+
+/// The frame in which a value was last read or written.
+///
+/// Atomic so that we can update it from [`IdTypeMap::get_temp`], which only takes `&self`.
+#[derive(Debug)]
+struct LastUsed(AtomicU64);
+
+impl LastUsed {
+    fn new(frame_nr: u64) -> Self {
+        Self(AtomicU64::new(frame_nr))
+    }
+
+    fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Clone for LastUsed {
+    fn clone(&self) -> Self {
+        Self::new(self.get())
+    }
+}
+
+/// The values read or written while a [`crate::Ui::retained`] block is recorded.
+///
+/// Behind a lock so that we can log from [`IdTypeMap::get_temp`], which only takes `&self`.
+#[derive(Default)]
+struct UsageLog(crate::mutex::Mutex<Vec<u64>>);
+
+impl Clone for UsageLog {
+    fn clone(&self) -> Self {
+        Self(crate::mutex::Mutex::new(self.0.lock().clone()))
+    }
+}
+
+impl std::fmt::Debug for UsageLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("UsageLog")
+            .field(&self.0.lock().len())
+            .finish()
+    }
 }
 
 #[cfg(feature = "persistence")]
@@ -352,6 +410,23 @@ pub struct IdTypeMap {
     map: nohash_hasher::IntMap<u64, Element>,
 
     max_bytes_per_type: usize,
+
+    /// When each value in [`Self::map`] was last read or written, for [`Self::remove_unused`].
+    ///
+    /// Values that were loaded from disk, but not yet read, have no entry here.
+    /// Empty unless [`Self::track_usage`] is set.
+    last_used: nohash_hasher::IntMap<u64, LastUsed>,
+
+    /// See [`Self::set_track_usage`].
+    track_usage: bool,
+
+    /// If set, every value that is read or written is also logged here.
+    ///
+    /// Used by [`crate::Ui::retained`] to record which values a block used.
+    usage_log: Option<UsageLog>,
+
+    /// Incremented by [`Self::end_frame`].
+    frame_nr: u64,
 }
 
 impl Default for IdTypeMap {
@@ -359,6 +434,10 @@ impl Default for IdTypeMap {
         Self {
             map: Default::default(),
             max_bytes_per_type: 256 * 1024,
+            last_used: Default::default(),
+            track_usage: false,
+            usage_log: None,
+            frame_nr: 0,
         }
     }
 }
@@ -368,6 +447,7 @@ impl IdTypeMap {
     #[inline]
     pub fn insert_temp<T: 'static + Any + Clone + Send + Sync>(&mut self, id: Id, value: T) {
         let hash = hash(TypeId::of::<T>(), id);
+        self.mark_used_mut(hash);
         self.map.insert(hash, Element::new_temp(value));
     }
 
@@ -375,6 +455,7 @@ impl IdTypeMap {
     #[inline]
     pub fn insert_persisted<T: SerializableAny>(&mut self, id: Id, value: T) {
         let hash = hash(TypeId::of::<T>(), id);
+        self.mark_used_mut(hash);
         self.map.insert(hash, Element::new_persisted(value));
     }
 
@@ -384,6 +465,7 @@ impl IdTypeMap {
     #[inline]
     pub fn get_temp<T: 'static + Clone>(&self, id: Id) -> Option<T> {
         let hash = hash(TypeId::of::<T>(), id);
+        self.mark_used(hash);
         self.map.get(&hash).and_then(|x| x.get_temp()).cloned()
     }
 
@@ -396,6 +478,9 @@ impl IdTypeMap {
     #[inline]
     pub fn get_persisted<T: SerializableAny>(&mut self, id: Id) -> Option<T> {
         let hash = hash(TypeId::of::<T>(), id);
+        if self.map.contains_key(&hash) {
+            self.mark_used_mut(hash);
+        }
         self.map
             .get_mut(&hash)
             .and_then(|x| x.get_mut_persisted())
@@ -435,6 +520,7 @@ impl IdTypeMap {
         insert_with: impl FnOnce() -> T,
     ) -> &mut T {
         let hash = hash(TypeId::of::<T>(), id);
+        self.mark_used_mut(hash);
         use std::collections::hash_map::Entry;
         match self.map.entry(hash) {
             Entry::Vacant(vacant) => vacant
@@ -453,6 +539,7 @@ impl IdTypeMap {
         insert_with: impl FnOnce() -> T,
     ) -> &mut T {
         let hash = hash(TypeId::of::<T>(), id);
+        self.mark_used_mut(hash);
         use std::collections::hash_map::Entry;
         match self.map.entry(hash) {
             Entry::Vacant(vacant) => vacant
@@ -481,12 +568,14 @@ impl IdTypeMap {
     pub fn remove<T: 'static>(&mut self, id: Id) {
         let hash = hash(TypeId::of::<T>(), id);
         self.map.remove(&hash);
+        self.last_used.remove(&hash);
     }
 
     /// Remove and fetch the state of this type and id.
     #[inline]
     pub fn remove_temp<T: 'static + Clone>(&mut self, id: Id) -> Option<T> {
         let hash = hash(TypeId::of::<T>(), id);
+        self.last_used.remove(&hash);
         self.map
             .remove(&hash)
             .and_then(|element| element.get_temp().cloned())
@@ -499,11 +588,13 @@ impl IdTypeMap {
             let e: &Element = e;
             e.type_id() != key
         });
+        self.forget_removed();
     }
 
     #[inline]
     pub fn clear(&mut self) {
         self.map.clear();
+        self.last_used.clear();
     }
 
     #[inline]
@@ -537,6 +628,158 @@ impl IdTypeMap {
             .count()
     }
 
+    /// Approximately how many bytes all stored values use.
+    ///
+    /// Heap allocations owned by the values (e.g. the contents of a `String`) are not counted.
+    pub fn num_bytes(&self) -> usize {
+        self.map.values().map(Element::num_bytes).sum()
+    }
+
+    /// Like [`Self::num_bytes`], but only for values of the given type.
+    pub fn num_bytes_of<T: 'static>(&self) -> usize {
+        let key = TypeId::of::<T>();
+        self.map
+            .values()
+            .filter(|e| {
+                let e: &Element = e;
+                e.type_id() == key
+            })
+            .map(Element::num_bytes)
+            .sum()
+    }
+
+    /// Remove all values that haven't been read or written during the last `max_age_in_frames` frames.
+    ///
+    /// Values that were loaded from disk but not yet read are kept,
+    /// since those are already limited by [`Self::max_bytes_per_type`].
+    /// Nothing is removed unless [`Self::set_track_usage`] is turned on.
+    ///
+    /// egui calls this at the end of each frame if [`crate::Options::gc_unused_data_after_frames`] is set.
+    pub fn remove_unused(&mut self, max_age_in_frames: u64) {
+        crate::profile_function!();
+        let Self {
+            map,
+            last_used,
+            frame_nr,
+            ..
+        } = self;
+        map.retain(|hash, element| {
+            if matches!(element, Element::Serialized(_)) {
+                return true;
+            }
+            last_used.get(hash).map_or(true, |last_used| {
+                *frame_nr - last_used.get() <= max_age_in_frames
+            })
+        });
+        self.forget_removed();
+    }
+
+    /// Advance the frame counter used by [`Self::remove_unused`].
+    pub(crate) fn end_frame(&mut self) {
+        self.frame_nr += 1;
+    }
+
+    /// Keep track of when each value was last used, so that [`Self::remove_unused`] can remove the old ones.
+    ///
+    /// Off by default, so that reading and writing values costs nothing extra.
+    /// egui turns this on if [`crate::Options::gc_unused_data_after_frames`] is set.
+    ///
+    /// When turned on, all current values count as used this frame.
+    pub fn set_track_usage(&mut self, track_usage: bool) {
+        if track_usage == self.track_usage {
+            return;
+        }
+        self.track_usage = track_usage;
+
+        let Self {
+            map,
+            last_used,
+            frame_nr,
+            ..
+        } = self;
+        last_used.clear();
+        if track_usage {
+            last_used.extend(
+                map.iter()
+                    .filter(|(_, element)| !matches!(element, Element::Serialized(_)))
+                    .map(|(hash, _)| (*hash, LastUsed::new(*frame_nr))),
+            );
+        }
+    }
+
+    /// Start logging which values are used, for [`crate::Ui::retained`].
+    ///
+    /// Returns the length of the log so far, and whether this call started it.
+    /// Logs can be nested, like the recordings.
+    pub(crate) fn begin_usage_log(&mut self) -> (usize, bool) {
+        match &self.usage_log {
+            Some(log) => (log.0.lock().len(), false),
+            None => {
+                self.usage_log = Some(UsageLog::default());
+                (0, true)
+            }
+        }
+    }
+
+    /// The values used since [`Self::begin_usage_log`] returned `(start, owns_log)`.
+    pub(crate) fn end_usage_log(&mut self, start: usize, owns_log: bool) -> Vec<u64> {
+        if owns_log {
+            self.usage_log
+                .take()
+                .map(|log| std::mem::take(&mut *log.0.lock()))
+                .unwrap_or_default()
+        } else {
+            self.usage_log
+                .as_ref()
+                .and_then(|log| log.0.lock().get(start..).map(<[u64]>::to_vec))
+                .unwrap_or_default()
+        }
+    }
+
+    /// Mark values returned by [`Self::end_usage_log`] as used again,
+    /// for a replayed [`crate::Ui::retained`] block.
+    pub(crate) fn mark_logged_used(&self, hashes: &[u64]) {
+        if let Some(log) = &self.usage_log {
+            log.0.lock().extend_from_slice(hashes);
+        }
+        if self.track_usage {
+            for hash in hashes {
+                if let Some(last_used) = self.last_used.get(hash) {
+                    last_used.0.store(self.frame_nr, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn mark_used(&self, hash: u64) {
+        if let Some(log) = &self.usage_log {
+            log.0.lock().push(hash);
+        }
+        if !self.track_usage {
+            return;
+        }
+        if let Some(last_used) = self.last_used.get(&hash) {
+            last_used.0.store(self.frame_nr, Ordering::Relaxed);
+        }
+    }
+
+    #[inline]
+    fn mark_used_mut(&mut self, hash: u64) {
+        if let Some(log) = &self.usage_log {
+            log.0.lock().push(hash);
+        }
+        if self.track_usage {
+            self.last_used.insert(hash, LastUsed::new(self.frame_nr));
+        }
+    }
+
+    /// Drop the [`LastUsed`] of values that are no longer in the map.
+    fn forget_removed(&mut self) {
+        let Self { map, last_used, .. } = self;
+        last_used.retain(|hash, _| map.contains_key(hash));
+    }
+
     /// The maximum number of bytes that will be used to
     /// store the persisted state of a single widget type.
     ///
@@ -985,3 +1228,36 @@ fn test_serialize_gc() {
         Some(B(2_000_000))
     );
 }
+
+#[test]
+fn test_remove_unused() {
+    let a = Id::new("a");
+    let b = Id::new("b");
+
+    let mut map: IdTypeMap = Default::default();
+    map.insert_temp(a, 1_i32);
+    map.insert_temp(b, 2_i32);
+    map.end_frame();
+    map.end_frame();
+
+    // Usage is not tracked by default, so nothing is removed:
+    map.remove_unused(0);
+    assert_eq!(map.len(), 2);
+    assert!(map.last_used.is_empty());
+
+    // Turning on the tracking counts everything as used this frame:
+    map.set_track_usage(true);
+    map.remove_unused(0);
+    assert_eq!(map.len(), 2);
+
+    for _ in 0..3 {
+        map.end_frame();
+        assert_eq!(map.get_temp::<i32>(a), Some(1));
+    }
+    map.remove_unused(2);
+    assert_eq!(map.get_temp::<i32>(a), Some(1));
+    assert_eq!(map.get_temp::<i32>(b), None);
+
+    map.set_track_usage(false);
+    assert!(map.last_used.is_empty());
+}
@@ -55,6 +55,28 @@
 //! });
 //! ```
 //!
+//! ## Embedded viewports
+//! Not all backends support multiple native windows. For instance, `eframe` on the web
+//! renders everything into a single canvas. In that case new viewports are _embedded_ in their parent
+//! (see [`Context::embed_viewports`]), and the callback is given [`ViewportClass::Embedded`].
+//! `eframe` on the web always embeds viewports: if you turn off [`Context::embed_viewports`],
+//! it logs a warning and turns it back on.
+//! This lets the same code work everywhere, as long as you show embedded contents in e.g. a [`crate::Window`]:
+//!
+//! ```
+//! # egui::__run_test_ctx(|ctx| {
+//! let id = egui::ViewportId::from_hash_of("settings");
+//! let builder = egui::ViewportBuilder::default().with_title("Settings");
+//! ctx.show_viewport_immediate(id, builder, |ctx, class| {
+//!     if class == egui::ViewportClass::Embedded {
+//!         egui::Window::new("Settings").show(ctx, |ui| ui.label("Hello"));
+//!     } else {
+//!         egui::CentralPanel::default().show(ctx, |ui| ui.label("Hello"));
+//!     }
+//! });
+//! # });
+//! ```
+//!
 //! ## For integrations
 //! * There is a [`crate::InputState::viewport`] with information about the current viewport.
 //! * There is a [`crate::RawInput::viewports`] with information about all viewports.
@@ -264,8 +286,14 @@ pub struct ViewportBuilder {
 
     /// The desired outer position of the window.
     pub position: Option<Pos2>,
+
+    /// The desired size of the window, in egui points. See [`Self::with_inner_size`].
     pub inner_size: Option<Vec2>,
+
+    /// In egui points. See [`Self::with_min_inner_size`].
     pub min_inner_size: Option<Vec2>,
+
+    /// In egui points. See [`Self::with_max_inner_size`].
     pub max_inner_size: Option<Vec2>,
 
     pub fullscreen: Option<bool>,
@@ -277,6 +305,7 @@ pub struct ViewportBuilder {
     pub active: Option<bool>,
     pub visible: Option<bool>,
     pub drag_and_drop: Option<bool>,
+    pub skip_taskbar: Option<bool>,
 
     // macOS:
     pub fullsize_content_view: Option<bool>,
@@ -291,6 +320,8 @@ pub struct ViewportBuilder {
     pub window_level: Option<WindowLevel>,
 
     pub mouse_passthrough: Option<bool>,
+
+    pub backdrop: Option<Backdrop>,
 }
 
 impl ViewportBuilder {
@@ -439,6 +470,9 @@ impl ViewportBuilder {
     ///
     /// If this is not set, some platform-specific dimensions will be used.
     ///
+    /// The size is in egui points, so the window has the same size in points
+    /// on every monitor, whatever its scale factor.
+    ///
     /// Should be bigger than 0
     /// Look at winit for more details
     #[inline]
@@ -452,6 +486,8 @@ impl ViewportBuilder {
     /// If this is not set, the window will have no minimum dimensions (aside
     /// from reserved).
     ///
+    /// The size is in egui points, and is kept when the window moves to a monitor with another scale factor.
+    ///
     /// Should be bigger than 0
     /// Look at winit for more details
     #[inline]
@@ -465,6 +501,8 @@ impl ViewportBuilder {
     /// If this is not set, the window will have no maximum or will be set to
     /// the primary monitor's dimensions by the platform.
     ///
+    /// The size is in egui points, and is kept when the window moves to a monitor with another scale factor.
+    ///
     /// Should be bigger than 0
     /// Look at winit for more details
     #[inline]
@@ -507,6 +545,17 @@ impl ViewportBuilder {
         self
     }
 
+    /// On Windows: hide the window from the taskbar, e.g. for overlays and tool windows.
+    ///
+    /// The default is `false`.
+    ///
+    /// This can be changed at runtime with [`ViewportCommand::SkipTaskbar`].
+    #[inline]
+    pub fn with_skip_taskbar(mut self, skip_taskbar: bool) -> Self {
+        self.skip_taskbar = Some(skip_taskbar);
+        self
+    }
+
     /// The initial "outer" position of the window,
     /// i.e. where the top-left corner of the frame/chrome should be.
     #[inline]
@@ -544,6 +593,8 @@ impl ViewportBuilder {
     }
 
     /// Control if window i always-on-top, always-on-bottom, or neither.
+    ///
+    /// This can be changed at runtime with [`ViewportCommand::WindowLevel`].
     #[inline]
     pub fn with_window_level(mut self, level: WindowLevel) -> Self {
         self.window_level = Some(level);
@@ -560,12 +611,30 @@ impl ViewportBuilder {
     ///
     /// Generally you would use this in conjunction with [`Self::with_transparent`]
     /// and [`Self::with_always_on_top`].
+    ///
+    /// This can be changed at runtime with [`ViewportCommand::MousePassthrough`].
     #[inline]
     pub fn with_mouse_passthrough(mut self, value: bool) -> Self {
         self.mouse_passthrough = Some(value);
         self
     }
 
+    /// Let the platform compositor draw a backdrop behind the window, e.g. a blurred desktop.
+    ///
+    /// This also makes the window transparent (see [`Self::with_transparent`]),
+    /// so make sure your panels are translucent, e.g. with [`crate::Visuals::translucent`],
+    /// and in `eframe` return a transparent color from `eframe::App::clear_color()`.
+    ///
+    /// `egui-winit` uses the system backdrops on Windows 11 and an `NSVisualEffectView` on Mac.
+    /// Elsewhere the blurred backdrops blur what is behind the window where the compositor supports it,
+    /// and otherwise fall back to [`Backdrop::Transparent`].
+    #[inline]
+    pub fn with_backdrop(mut self, backdrop: Backdrop) -> Self {
+        self.backdrop = Some(backdrop);
+        self.transparent = Some(true);
+        self
+    }
+
     /// Update this `ViewportBuilder` with a delta,
     /// returning a list of commands and a bool intdicating if the window needs to be recreated.
     #[must_use]
@@ -586,6 +655,7 @@ impl ViewportBuilder {
             active: new_active,
             visible: new_visible,
             drag_and_drop: new_drag_and_drop,
+            skip_taskbar: new_skip_taskbar,
             fullsize_content_view: new_fullsize_content_view,
             title_shown: new_title_shown,
             titlebar_buttons_shown: new_titlebar_buttons_shown,
@@ -595,6 +665,7 @@ impl ViewportBuilder {
             maximize_button: new_maximize_button,
             window_level: new_window_level,
             mouse_passthrough: new_mouse_passthrough,
+            backdrop: new_backdrop,
         } = new_vp_builder;
 
         let mut commands = Vec::new();
@@ -620,17 +691,26 @@ impl ViewportBuilder {
             }
         }
 
-        if let Some(new_min_inner_size) = new_min_inner_size {
-            if Some(new_min_inner_size) != self.min_inner_size {
-                self.min_inner_size = Some(new_min_inner_size);
-                commands.push(ViewportCommand::MinInnerSize(new_min_inner_size));
+        {
+            // Send both constraints at once, so the window never has a minimum larger than its maximum:
+            let mut constraints_changed = false;
+            if let Some(new_min_inner_size) = new_min_inner_size {
+                if Some(new_min_inner_size) != self.min_inner_size {
+                    self.min_inner_size = Some(new_min_inner_size);
+                    constraints_changed = true;
+                }
             }
-        }
-
-        if let Some(new_max_inner_size) = new_max_inner_size {
-            if Some(new_max_inner_size) != self.max_inner_size {
-                self.max_inner_size = Some(new_max_inner_size);
-                commands.push(ViewportCommand::MaxInnerSize(new_max_inner_size));
+            if let Some(new_max_inner_size) = new_max_inner_size {
+                if Some(new_max_inner_size) != self.max_inner_size {
+                    self.max_inner_size = Some(new_max_inner_size);
+                    constraints_changed = true;
+                }
+            }
+            if constraints_changed {
+                commands.push(ViewportCommand::SetSizeConstraints {
+                    min_inner_size: self.min_inner_size,
+                    max_inner_size: self.max_inner_size,
+                });
             }
         }
 
@@ -695,6 +775,20 @@ impl ViewportBuilder {
             }
         }
 
+        if let Some(new_skip_taskbar) = new_skip_taskbar {
+            if Some(new_skip_taskbar) != self.skip_taskbar {
+                self.skip_taskbar = Some(new_skip_taskbar);
+                commands.push(ViewportCommand::SkipTaskbar(new_skip_taskbar));
+            }
+        }
+
+        if let Some(new_backdrop) = new_backdrop {
+            if Some(new_backdrop) != self.backdrop {
+                self.backdrop = Some(new_backdrop);
+                commands.push(ViewportCommand::Backdrop(new_backdrop));
+            }
+        }
+
         if let Some(new_window_level) = new_window_level {
             if Some(new_window_level) != self.window_level {
                 self.window_level = Some(new_window_level);
@@ -776,6 +870,105 @@ pub enum WindowLevel {
     AlwaysOnTop,
 }
 
+/// The progress shown on the taskbar entry of a window, see [`ViewportCommand::TaskbarProgress`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TaskbarProgress {
+    /// Don't show any progress.
+    #[default]
+    None,
+
+    /// Busy, without knowing how far along.
+    Indeterminate,
+
+    /// How far along, in `0..=1`.
+    Fraction(f32),
+}
+
+// Like `Pos2`, we don't expect NaN here, so that `ViewportCommand` can be `Eq`.
+impl Eq for TaskbarProgress {}
+
+/// A material drawn by the platform compositor behind a transparent window.
+///
+/// See [`ViewportBuilder::with_backdrop`].
+///
+/// Support depends on the platform and on what the windowing backend exposes.
+/// Unsupported backdrops fall back to [`Self::Transparent`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Backdrop {
+    /// A fully transparent window, showing whatever is behind it.
+    #[default]
+    Transparent,
+
+    /// The Windows 11 "Mica" material: a blurred and tinted desktop wallpaper.
+    ///
+    /// Where the platform has no such material, a blurred background is used if available.
+    Mica,
+
+    /// The Windows "Acrylic" material: a blurred and tinted view of what is behind the window.
+    ///
+    /// Where the platform has no such material, a blurred background is used if available.
+    Acrylic,
+
+    /// The macOS "vibrancy" effect: a blurred view of what is behind the window.
+    ///
+    /// Where the platform has no such effect, a blurred background is used if available.
+    Vibrancy,
+}
+
+impl Backdrop {
+    /// Should what is behind the window be blurred?
+    pub fn is_blurred(self) -> bool {
+        match self {
+            Self::Transparent => false,
+            Self::Mica | Self::Acrylic | Self::Vibrancy => true,
+        }
+    }
+}
+
+/// Which monitor to use, e.g. for [`FullscreenMode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MonitorSelection {
+    /// The monitor the window is currently on.
+    #[default]
+    Current,
+
+    /// The primary monitor of the system, if the platform has such a concept.
+    Primary,
+
+    /// The monitor with this index in the list of available monitors, as enumerated by the backend.
+    Index(usize),
+}
+
+/// How to make a viewport fullscreen.
+///
+/// See [`ViewportCommand::SetFullscreen`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FullscreenMode {
+    /// Cover the monitor with a borderless window, keeping the desktop video mode.
+    ///
+    /// This is fast to switch in and out of, and plays nicely with other windows.
+    Borderless(MonitorSelection),
+
+    /// Take exclusive control of the monitor,
+    /// using its highest resolution and refresh rate.
+    ///
+    /// Falls back to [`Self::Borderless`] where exclusive fullscreen is not supported.
+    Exclusive(MonitorSelection),
+}
+
+impl FullscreenMode {
+    /// The monitor to go fullscreen on.
+    pub fn monitor(&self) -> MonitorSelection {
+        match self {
+            Self::Borderless(monitor) | Self::Exclusive(monitor) => *monitor,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum IMEPurpose {
@@ -855,15 +1048,24 @@ pub enum ViewportCommand {
     /// Set the outer position of the viewport, i.e. moves the window.
     OuterPosition(Pos2),
 
-    /// Should be bigger than 0
+    /// In egui points. Should be bigger than 0
     InnerSize(Vec2),
 
-    /// Should be bigger than 0
+    /// In egui points. Should be bigger than 0
     MinInnerSize(Vec2),
 
-    /// Should be bigger than 0
+    /// In egui points. Should be bigger than 0
     MaxInnerSize(Vec2),
 
+    /// Set both the minimum and maximum inner size at once, in egui points.
+    ///
+    /// `None` removes that constraint.
+    /// The constraints are kept in points when the window moves to a monitor with another scale factor.
+    SetSizeConstraints {
+        min_inner_size: Option<Vec2>,
+        max_inner_size: Option<Vec2>,
+    },
+
     /// Should be bigger than 0
     ResizeIncrements(Option<Vec2>),
 
@@ -888,8 +1090,16 @@ pub enum ViewportCommand {
     Maximized(bool),
 
     /// Turn borderless fullscreen on/off.
+    ///
+    /// This is the same as [`Self::SetFullscreen`] with [`FullscreenMode::Borderless`]
+    /// on the current monitor.
     Fullscreen(bool),
 
+    /// Enter fullscreen using the given mode and monitor, or leave fullscreen with `None`.
+    ///
+    /// The current mode is available in [`crate::ViewportInfo::fullscreen_mode`].
+    SetFullscreen(Option<FullscreenMode>),
+
     /// Show window decorations, i.e. the chrome around the content
     /// with the title bar, close buttons, resize handles, etc.
     Decorations(bool),
@@ -897,6 +1107,11 @@ pub enum ViewportCommand {
     /// Set window to be always-on-top, always-on-bottom, or neither.
     WindowLevel(WindowLevel),
 
+    /// On Windows: hide the window from the taskbar (`true`), or show it again (`false`).
+    ///
+    /// See [`ViewportBuilder::with_skip_taskbar`].
+    SkipTaskbar(bool),
+
     /// The the window icon.
     Icon(Option<Arc<IconData>>),
 
@@ -935,9 +1150,28 @@ pub enum ViewportCommand {
 
     CursorVisible(bool),
 
+    /// Override the cursor icon of this viewport, regardless of what the widgets ask for.
+    ///
+    /// Use `Some(CursorIcon::None)` together with [`Self::CursorGrab`] to hide and capture
+    /// the mouse in one window, e.g. for a first-person camera.
+    /// `None` goes back to the cursor requested via [`crate::PlatformOutput::cursor_icon`].
+    CursorIcon(Option<crate::CursorIcon>),
+
     /// Enable mouse pass-through: mouse clicks pass through the window, used for non-interactable overlays.
     MousePassthrough(bool),
 
+    /// Change the compositor backdrop behind the window.
+    ///
+    /// The window must have been created transparent, see [`ViewportBuilder::with_backdrop`].
+    Backdrop(Backdrop),
+
+    /// Show progress on the taskbar entry of the window.
+    ///
+    /// Supported on Windows, and on Linux desktops implementing the Unity launcher API (e.g. KDE).
+    /// egui sends this automatically for the tasks reported with [`crate::Context::progress`],
+    /// see [`crate::Options::taskbar_progress`].
+    TaskbarProgress(TaskbarProgress),
+
     /// Take a screenshot.
     ///
     /// The results are returned in `crate::Event::Screenshot`.
@@ -37,6 +37,8 @@ pub struct RichText {
     underline: bool,
     italics: bool,
     raised: bool,
+    outline: crate::Stroke,
+    shadow: crate::text::TextShadow,
 }
 
 impl From<&str> for RichText {
@@ -246,6 +248,22 @@ impl RichText {
         self
     }
 
+    /// Paint an outline around each glyph, to keep the text readable on top of images.
+    ///
+    /// Keep the width to a few points.
+    #[inline]
+    pub fn outline(mut self, outline: impl Into<crate::Stroke>) -> Self {
+        self.outline = outline.into();
+        self
+    }
+
+    /// Paint a drop shadow behind the text, `offset` points away from it.
+    #[inline]
+    pub fn shadow(mut self, offset: impl Into<crate::Vec2>, color: impl Into<Color32>) -> Self {
+        self.shadow = crate::text::TextShadow::new(offset.into(), color.into());
+        self
+    }
+
     /// Override text color.
     ///
     /// If not set, [`Color32::PLACEHOLDER`] will be used,
@@ -347,6 +365,8 @@ impl RichText {
             underline,
             italics,
             raised,
+            outline,
+            shadow,
         } = self;
 
         let line_color = text_color.unwrap_or_else(|| style.visuals.text_color());
@@ -401,6 +421,8 @@ impl RichText {
                 underline,
                 strikethrough,
                 valign,
+                outline,
+                shadow,
             },
         )
     }
@@ -436,6 +458,16 @@ impl RichText {
 pub enum WidgetText {
     RichText(RichText),
 
+    /// A `'static` string, shown with the default style, just like `RichText::new(text)`.
+    ///
+    /// Unlike [`Self::RichText`], this doesn't allocate a [`String`] every frame,
+    /// and the laid out [`Galley`] is looked up without building a [`LayoutJob`] first
+    /// (see [`epaint::Fonts::layout_interned`]).
+    /// This makes it the cheapest way to show constant text, e.g. `ui.label(WidgetText::Static("Name:"))`.
+    ///
+    /// Calling any of the styling methods (e.g. [`Self::color`]) turns this into a [`Self::RichText`].
+    Static(&'static str),
+
     /// Use this [`LayoutJob`] when laying out the text.
     ///
     /// Only [`LayoutJob::text`] and [`LayoutJob::sections`] are guaranteed to be respected.
@@ -468,6 +500,7 @@ impl WidgetText {
     pub fn is_empty(&self) -> bool {
         match self {
             Self::RichText(text) => text.is_empty(),
+            Self::Static(text) => text.is_empty(),
             Self::LayoutJob(job) => job.is_empty(),
             Self::Galley(galley) => galley.is_empty(),
         }
@@ -477,6 +510,7 @@ impl WidgetText {
     pub fn text(&self) -> &str {
         match self {
             Self::RichText(text) => text.text(),
+            Self::Static(text) => text,
             Self::LayoutJob(job) => &job.text,
             Self::Galley(galley) => galley.text(),
         }
@@ -489,6 +523,7 @@ impl WidgetText {
     pub fn text_style(self, text_style: TextStyle) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.text_style(text_style)),
+            Self::Static(text) => Self::RichText(RichText::new(text).text_style(text_style)),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -500,6 +535,9 @@ impl WidgetText {
     pub fn fallback_text_style(self, text_style: TextStyle) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.fallback_text_style(text_style)),
+            Self::Static(text) => {
+                Self::RichText(RichText::new(text).fallback_text_style(text_style))
+            }
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -511,6 +549,7 @@ impl WidgetText {
     pub fn color(self, color: impl Into<Color32>) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.color(color)),
+            Self::Static(text) => Self::RichText(RichText::new(text).color(color)),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -519,6 +558,7 @@ impl WidgetText {
     pub fn heading(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.heading()),
+            Self::Static(text) => Self::RichText(RichText::new(text).heading()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -527,6 +567,7 @@ impl WidgetText {
     pub fn monospace(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.monospace()),
+            Self::Static(text) => Self::RichText(RichText::new(text).monospace()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -535,6 +576,7 @@ impl WidgetText {
     pub fn code(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.code()),
+            Self::Static(text) => Self::RichText(RichText::new(text).code()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -543,6 +585,7 @@ impl WidgetText {
     pub fn strong(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.strong()),
+            Self::Static(text) => Self::RichText(RichText::new(text).strong()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -551,6 +594,7 @@ impl WidgetText {
     pub fn weak(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.weak()),
+            Self::Static(text) => Self::RichText(RichText::new(text).weak()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -559,6 +603,7 @@ impl WidgetText {
     pub fn underline(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.underline()),
+            Self::Static(text) => Self::RichText(RichText::new(text).underline()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -567,6 +612,7 @@ impl WidgetText {
     pub fn strikethrough(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.strikethrough()),
+            Self::Static(text) => Self::RichText(RichText::new(text).strikethrough()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -575,6 +621,7 @@ impl WidgetText {
     pub fn italics(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.italics()),
+            Self::Static(text) => Self::RichText(RichText::new(text).italics()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -583,6 +630,7 @@ impl WidgetText {
     pub fn small(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.small()),
+            Self::Static(text) => Self::RichText(RichText::new(text).small()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -591,6 +639,7 @@ impl WidgetText {
     pub fn small_raised(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.small_raised()),
+            Self::Static(text) => Self::RichText(RichText::new(text).small_raised()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -599,6 +648,7 @@ impl WidgetText {
     pub fn raised(self) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.raised()),
+            Self::Static(text) => Self::RichText(RichText::new(text).raised()),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -607,6 +657,27 @@ impl WidgetText {
     pub fn background_color(self, background_color: impl Into<Color32>) -> Self {
         match self {
             Self::RichText(text) => Self::RichText(text.background_color(background_color)),
+            Self::Static(text) => {
+                Self::RichText(RichText::new(text).background_color(background_color))
+            }
+            Self::LayoutJob(_) | Self::Galley(_) => self,
+        }
+    }
+
+    /// Prefer using [`RichText`] directly!
+    pub fn outline(self, outline: impl Into<crate::Stroke>) -> Self {
+        match self {
+            Self::RichText(text) => Self::RichText(text.outline(outline)),
+            Self::Static(text) => Self::RichText(RichText::new(text).outline(outline)),
+            Self::LayoutJob(_) | Self::Galley(_) => self,
+        }
+    }
+
+    /// Prefer using [`RichText`] directly!
+    pub fn shadow(self, offset: impl Into<crate::Vec2>, color: impl Into<Color32>) -> Self {
+        match self {
+            Self::RichText(text) => Self::RichText(text.shadow(offset, color)),
+            Self::Static(text) => Self::RichText(RichText::new(text).shadow(offset, color)),
             Self::LayoutJob(_) | Self::Galley(_) => self,
         }
     }
@@ -614,6 +685,7 @@ impl WidgetText {
     pub(crate) fn font_height(&self, fonts: &epaint::Fonts, style: &Style) -> f32 {
         match self {
             Self::RichText(text) => text.font_height(fonts, style),
+            Self::Static(_) => RichText::default().font_height(fonts, style),
             Self::LayoutJob(job) => job.font_height(fonts),
             Self::Galley(galley) => {
                 if let Some(row) = galley.rows.first() {
@@ -633,6 +705,9 @@ impl WidgetText {
     ) -> LayoutJob {
         match self {
             Self::RichText(text) => text.into_layout_job(style, fallback_font, default_valign),
+            Self::Static(text) => {
+                RichText::new(text).into_layout_job(style, fallback_font, default_valign)
+            }
             Self::LayoutJob(job) => job,
             Self::Galley(galley) => (*galley.job).clone(),
         }
@@ -658,6 +733,22 @@ impl WidgetText {
                 layout_job.wrap.max_width = wrap_width;
                 ui.fonts(|f| f.layout_job(layout_job))
             }
+            Self::Static(text) => {
+                let valign = ui.layout().vertical_align();
+                let (_, format) = RichText::default().into_text_and_format(
+                    ui.style(),
+                    fallback_font.into(),
+                    valign,
+                );
+                let key = (text, &format, wrap_width.to_bits());
+                ui.fonts(|f| {
+                    f.layout_interned(key, || {
+                        let mut job = LayoutJob::single_section(text.to_owned(), format.clone());
+                        job.wrap.max_width = wrap_width;
+                        job
+                    })
+                })
+            }
             Self::LayoutJob(mut job) => {
                 job.wrap.max_width = wrap_width;
                 ui.fonts(|f| f.layout_job(job))
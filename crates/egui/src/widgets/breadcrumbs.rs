@@ -0,0 +1,107 @@
+use crate::*;
+
+/// A path of places, e.g. folders, where the user can click a place to go back to it.
+///
+/// The last crumb is the current place, and can't be clicked.
+/// If the crumbs don't fit in the available width, the ones in the middle are put in a "…" menu.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut path = vec!["Home".to_owned(), "Documents".to_owned(), "Taxes".to_owned()];
+/// let response = egui::Breadcrumbs::new(path.iter().map(|s| s.as_str())).show(ui);
+/// if let Some(index) = response.inner {
+///     path.truncate(index + 1);
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Breadcrumbs {
+    crumbs: Vec<WidgetText>,
+    separator: WidgetText,
+}
+
+impl Breadcrumbs {
+    pub fn new(crumbs: impl IntoIterator<Item = impl Into<WidgetText>>) -> Self {
+        Self {
+            crumbs: crumbs.into_iter().map(Into::into).collect(),
+            separator: "⏵".into(),
+        }
+    }
+
+    /// Shown between the crumbs. Default: `⏵`.
+    #[inline]
+    pub fn separator(mut self, separator: impl Into<WidgetText>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Returns the index of the crumb that was clicked, if any.
+    pub fn show(self, ui: &mut Ui) -> InnerResponse<Option<usize>> {
+        let Self { crumbs, separator } = self;
+
+        ui.horizontal(|ui| {
+            let Some(last) = crumbs.len().checked_sub(1) else {
+                return None;
+            };
+
+            let spacing = ui.spacing().item_spacing.x;
+            let text_width = |text: &WidgetText| {
+                text.clone()
+                    .into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button)
+                    .size()
+                    .x
+            };
+            let separator_width = text_width(&separator.clone().weak()) + spacing;
+            let crumb_widths: Vec<f32> = crumbs
+                .iter()
+                .map(|crumb| text_width(crumb) + spacing + separator_width)
+                .collect();
+            let overflow_width = ui.spacing().interact_size.y + spacing + separator_width;
+
+            // Always show the first and last crumbs, and as many of the ones before the last as fit:
+            let available_width = ui.available_width();
+            let mut first_shown = 1;
+            if available_width < crumb_widths.iter().sum::<f32>() {
+                let mut width = crumb_widths[0] + crumb_widths[last] + overflow_width;
+                first_shown = last;
+                while 1 < first_shown && width + crumb_widths[first_shown - 1] <= available_width {
+                    first_shown -= 1;
+                    width += crumb_widths[first_shown];
+                }
+            }
+
+            let mut clicked = None;
+            for (index, crumb) in crumbs.iter().enumerate() {
+                if 0 < index && index < first_shown {
+                    if index == 1 {
+                        let menu = ui.menu_button("…", |ui| {
+                            for (hidden, crumb) in
+                                crumbs.iter().enumerate().take(first_shown).skip(1)
+                            {
+                                if ui.button(crumb.clone()).clicked() {
+                                    clicked = Some(hidden);
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                        menu.response.on_hover_text("Show hidden places");
+                        ui.label(separator.clone().weak());
+                    }
+                    continue;
+                }
+
+                if index == last {
+                    let response = ui.add(Label::new(crumb.clone().strong()));
+                    response
+                        .widget_info(|| WidgetInfo::selected(WidgetType::Link, true, crumb.text()));
+                } else {
+                    if ui.add(Link::new(crumb.clone())).clicked() {
+                        clicked = Some(index);
+                    }
+                    ui.label(separator.clone().weak());
+                }
+            }
+            clicked
+        })
+    }
+}
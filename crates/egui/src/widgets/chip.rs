@@ -0,0 +1,348 @@
+use crate::*;
+
+/// A small rounded pill, e.g. for showing tags, filters or recipients.
+///
+/// Use [`Self::show`] to find out if the remove button was clicked,
+/// or [`TagEdit`] for editing a list of strings as chips.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut tags = vec!["rust".to_owned(), "gui".to_owned()];
+/// tags.retain(|tag| !egui::Chip::new(tag).removable(true).show(ui).removed);
+///
+/// # let mut only_open = true;
+/// if ui.add(egui::Chip::new("Open").selected(only_open)).clicked() {
+///     only_open = !only_open;
+/// }
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct Chip {
+    text: WidgetText,
+    selected: bool,
+    removable: bool,
+    fill: Option<Color32>,
+}
+
+/// The result of [`Chip::show`].
+pub struct ChipResponse {
+    /// The response of the chip itself, excluding the remove button.
+    pub response: Response,
+
+    /// The remove button was clicked, or the chip had keyboard focus and
+    /// [`Key::Delete`] or [`Key::Backspace`] was pressed.
+    pub removed: bool,
+}
+
+impl Chip {
+    pub fn new(text: impl Into<WidgetText>) -> Self {
+        Self {
+            text: text.into(),
+            selected: false,
+            removable: false,
+            fill: None,
+        }
+    }
+
+    /// Show the chip as selected, e.g. for an active filter.
+    #[inline]
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Show a button for removing the chip.
+    #[inline]
+    pub fn removable(mut self, removable: bool) -> Self {
+        self.removable = removable;
+        self
+    }
+
+    /// The background color when not selected.
+    ///
+    /// Default: [`crate::style::WidgetVisuals::weak_bg_fill`].
+    #[inline]
+    pub fn fill(mut self, fill: Color32) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> ChipResponse {
+        let Self {
+            text,
+            selected,
+            removable,
+            fill,
+        } = self;
+
+        let button_padding = ui.spacing().button_padding;
+        let galley = text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button);
+        let height =
+            (galley.size().y + 2.0 * button_padding.y).at_least(ui.spacing().interact_size.y);
+        let remove_size = if removable { height } else { 0.0 };
+        let desired_size = vec2(
+            galley.size().x + 2.0 * button_padding.x + remove_size,
+            height,
+        );
+        let (id, rect) = ui.allocate_space(desired_size);
+        let (body_rect, remove_rect) = rect.split_left_right_at_x(rect.right() - remove_size);
+
+        // The remove button is registered first, so it wins where the two overlap.
+        let remove_response =
+            removable.then(|| ui.interact(remove_rect, id.with("remove"), Sense::click()));
+        let response = ui.interact(body_rect, id, Sense::click());
+
+        let mut removed = remove_response.as_ref().map_or(false, |r| r.clicked());
+        if removable && response.has_focus() {
+            removed |= ui.input(|i| i.key_pressed(Key::Delete) || i.key_pressed(Key::Backspace));
+        }
+
+        response.widget_info(|| {
+            WidgetInfo::selected(WidgetType::SelectableLabel, selected, galley.text())
+        });
+        if let Some(remove_response) = &remove_response {
+            remove_response.widget_info(|| {
+                WidgetInfo::labeled(WidgetType::Button, format!("Remove {}", galley.text()))
+            });
+        }
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact_selectable(&response, selected);
+            let painter = ui.painter();
+            let radius = 0.5 * height;
+            let bg_fill = if selected {
+                visuals.bg_fill
+            } else if response.hovered() || response.has_focus() {
+                visuals.weak_bg_fill
+            } else {
+                fill.unwrap_or(visuals.weak_bg_fill)
+            };
+            painter.rect(rect, radius, bg_fill, visuals.bg_stroke);
+
+            let text_pos = pos2(
+                body_rect.left() + button_padding.x,
+                body_rect.center().y - 0.5 * galley.size().y,
+            );
+            painter.galley(text_pos, galley, visuals.text_color());
+
+            if let Some(remove_response) = &remove_response {
+                let remove_visuals = ui.style().interact(remove_response);
+                let center = remove_rect.center() - vec2(0.25 * button_padding.x, 0.0);
+                if remove_response.hovered() {
+                    painter.circle_filled(center, 0.35 * height, remove_visuals.bg_fill);
+                }
+                let cross = Rect::from_center_size(center, Vec2::splat(0.3 * height));
+                let stroke = Stroke::new(remove_visuals.fg_stroke.width, visuals.text_color());
+                painter.line_segment([cross.left_top(), cross.right_bottom()], stroke);
+                painter.line_segment([cross.right_top(), cross.left_bottom()], stroke);
+            }
+
+            if response.has_focus() {
+                painter.rect_stroke(rect, radius, ui.visuals().selection.stroke);
+            }
+        }
+
+        ChipResponse { response, removed }
+    }
+}
+
+impl Widget for Chip {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Edit a list of strings as removable [`Chip`]s, followed by a text field for adding more.
+///
+/// Press enter to add the typed text, or backspace in the empty text field to remove the last chip.
+/// Empty and duplicate entries are not added, and duplicates play [`SoundKind::Beep`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut tags = vec!["rust".to_owned(), "gui".to_owned()];
+/// if ui.add(egui::TagEdit::new(&mut tags).hint_text("Add tag")).changed() {
+///     // …
+/// }
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct TagEdit<'a> {
+    tags: &'a mut Vec<String>,
+    id_source: Option<Id>,
+    hint_text: WidgetText,
+    entry_width: Option<f32>,
+}
+
+impl<'a> TagEdit<'a> {
+    pub fn new(tags: &'a mut Vec<String>) -> Self {
+        Self {
+            tags,
+            id_source: None,
+            hint_text: Default::default(),
+            entry_width: None,
+        }
+    }
+
+    /// Use this to keep the typed text apart from that of other [`TagEdit`]s.
+    #[inline]
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
+    /// Shown in the empty text field.
+    #[inline]
+    pub fn hint_text(mut self, hint_text: impl Into<WidgetText>) -> Self {
+        self.hint_text = hint_text.into();
+        self
+    }
+
+    /// Width of the text field.
+    ///
+    /// Default: four times [`crate::style::Spacing::interact_size`]`.x`.
+    #[inline]
+    pub fn entry_width(mut self, entry_width: f32) -> Self {
+        self.entry_width = Some(entry_width);
+        self
+    }
+}
+
+impl<'a> Widget for TagEdit<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            tags,
+            id_source,
+            hint_text,
+            entry_width,
+        } = self;
+
+        let id = ui.make_persistent_id(id_source.unwrap_or_else(|| Id::new("tag_edit")));
+        let entry_width = entry_width.unwrap_or(4.0 * ui.spacing().interact_size.x);
+
+        ui.horizontal_wrapped(|ui| {
+            let mut response = ui.allocate_response(Vec2::ZERO, Sense::hover());
+            let mut removed = None;
+            for (i, tag) in tags.iter().enumerate() {
+                let chip = ui.push_id(i, |ui| Chip::new(tag.as_str()).removable(true).show(ui));
+                if chip.inner.removed {
+                    removed = Some(i);
+                }
+                response |= chip.inner.response;
+            }
+
+            let mut entry = ui.data_mut(|d| d.get_temp::<String>(id).unwrap_or_default());
+            // Checked before the text edit, so that deleting the last character keeps the last tag:
+            let entry_was_empty = entry.is_empty();
+            let entry_response = ui.add(
+                TextEdit::singleline(&mut entry)
+                    .id(id)
+                    .hint_text(hint_text)
+                    .desired_width(entry_width),
+            );
+
+            if entry_response.has_focus()
+                && entry_was_empty
+                && ui.input(|i| i.key_pressed(Key::Backspace))
+                && removed.is_none()
+            {
+                removed = tags.len().checked_sub(1);
+            }
+            if let Some(i) = removed {
+                tags.remove(i);
+                response.mark_changed();
+            }
+
+            if entry_response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                let tag = entry.trim();
+                if tags.iter().any(|t| t == tag) {
+                    ui.ctx().play_system_sound(SoundKind::Beep);
+                } else if !tag.is_empty() {
+                    tags.push(tag.to_owned());
+                    response.mark_changed();
+                }
+                entry.clear();
+                entry_response.request_focus();
+            }
+
+            ui.data_mut(|d| d.insert_temp(id, entry));
+
+            // Only report a change when the tags change, not for every typed character:
+            let changed = response.changed();
+            let mut response = response | entry_response;
+            response.changed = changed;
+            response
+        })
+        .inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{click, run_in_panel};
+
+    fn show(ui: &mut Ui, tags: &mut Vec<String>) -> Response {
+        ui.add(TagEdit::new(tags))
+    }
+
+    fn run(ctx: &Context, input: RawInputBuilder, tags: &mut Vec<String>) -> Response {
+        run_in_panel(ctx, input, |ui| show(ui, tags))
+    }
+
+    fn press(ctx: &Context, key: Key, tags: &mut Vec<String>) -> Response {
+        run(ctx, RawInputBuilder::new().key(key, true), tags)
+    }
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|&tag| tag.to_owned()).collect()
+    }
+
+    #[test]
+    fn tag_edit_remove_chips() {
+        let ctx = Context::default();
+        let mut tags = tags(&["a", "b", "c"]);
+        run(&ctx, RawInputBuilder::new(), &mut tags);
+
+        // The remove button of the first chip comes first:
+        press(&ctx, Key::Tab, &mut tags);
+        let response = press(&ctx, Key::Enter, &mut tags);
+        assert_eq!(tags, self::tags(&["b", "c"]));
+        assert!(response.changed());
+
+        // Then the chip itself, which is removed with delete:
+        press(&ctx, Key::Tab, &mut tags);
+        press(&ctx, Key::Delete, &mut tags);
+        assert_eq!(tags, self::tags(&["c"]));
+    }
+
+    #[test]
+    fn tag_edit_add_and_backspace() {
+        let ctx = Context::default();
+        let mut tags = tags(&["a"]);
+        let rect = run(&ctx, RawInputBuilder::new(), &mut tags).rect;
+
+        // Focus the text field by clicking it:
+        let pos = rect.right_center() - vec2(10.0, 0.0);
+        click(pos, |input| {
+            run(&ctx, input, &mut tags);
+        });
+
+        // Typing doesn't change the tags until enter is pressed:
+        let response = run(&ctx, RawInputBuilder::new().text(" b "), &mut tags);
+        assert!(!response.changed());
+        let response = press(&ctx, Key::Enter, &mut tags);
+        assert!(response.changed());
+        assert_eq!(tags, self::tags(&["a", "b"]));
+
+        // Duplicates are not added:
+        run(&ctx, RawInputBuilder::new().text("a"), &mut tags);
+        press(&ctx, Key::Enter, &mut tags);
+        assert_eq!(tags, self::tags(&["a", "b"]));
+
+        // Backspace in the empty text field removes the last tag:
+        press(&ctx, Key::Backspace, &mut tags);
+        assert_eq!(tags, self::tags(&["a"]));
+    }
+}
@@ -490,7 +490,10 @@ pub fn color_edit_button_hsva(ui: &mut Ui, hsva: &mut Hsva, alpha: Alpha) -> Res
     if ui.memory(|mem| mem.is_popup_open(popup_id)) {
         let area_response = Area::new(popup_id)
             .order(Order::Foreground)
-            .fixed_pos(button_response.rect.max)
+            .anchored_to(
+                button_response.rect,
+                RectAlign::new(Align2::RIGHT_BOTTOM, Align2::LEFT_TOP),
+            )
             .constrain(true)
             .show(ui.ctx(), |ui| {
                 ui.spacing_mut().slider_width = COLOR_SLIDER_WIDTH;
@@ -149,6 +149,8 @@ impl<'a> DragValue<'a> {
     /// A custom formatter takes a `f64` for the numeric value and a `RangeInclusive<usize>` representing
     /// the decimal range i.e. minimum and maximum number of decimal places shown.
     ///
+    /// The default is [`crate::Style::number_formatter`].
+    ///
     /// See also: [`DragValue::custom_parser`]
     ///
     /// ```
@@ -193,6 +195,8 @@ impl<'a> DragValue<'a> {
     /// A custom parser takes an `&str` to parse into a number and returns a `f64` if it was successfully parsed
     /// or `None` otherwise.
     ///
+    /// The default is [`crate::Style::number_formatter`].
+    ///
     /// See also: [`DragValue::custom_formatter`]
     ///
     /// ```
@@ -454,7 +458,9 @@ impl<'a> Widget for DragValue<'a> {
                 if value == 0.0 {
                     "0".to_owned()
                 } else {
-                    emath::format_with_decimals_in_range(value, auto_decimals..=max_decimals)
+                    ui.style()
+                        .number_formatter
+                        .format(value, auto_decimals..=max_decimals)
                 }
             }
         };
@@ -468,7 +474,7 @@ impl<'a> Widget for DragValue<'a> {
                 // Make sure we applied the last text value:
                 let parsed_value = match &custom_parser {
                     Some(parser) => parser(&value_text),
-                    None => value_text.parse().ok(),
+                    None => ui.style().number_formatter.parse(&value_text),
                 };
                 if let Some(parsed_value) = parsed_value {
                     let parsed_value = clamp_to_range(parsed_value, clamp_range.clone());
@@ -505,7 +511,7 @@ impl<'a> Widget for DragValue<'a> {
             if update {
                 let parsed_value = match &custom_parser {
                     Some(parser) => parser(&value_text),
-                    None => value_text.parse().ok(),
+                    None => ui.style().number_formatter.parse(&value_text),
                 };
                 if let Some(parsed_value) = parsed_value {
                     let parsed_value = clamp_to_range(parsed_value, clamp_range.clone());
@@ -79,6 +79,8 @@ pub struct Hyperlink {
     url: String,
     text: WidgetText,
     new_tab: bool,
+    open_in_viewport: bool,
+    show_url_on_hover: bool,
 }
 
 impl Hyperlink {
@@ -89,6 +91,8 @@ impl Hyperlink {
             url: url.clone(),
             text: url.into(),
             new_tab: false,
+            open_in_viewport: false,
+            show_url_on_hover: true,
         }
     }
 
@@ -98,29 +102,66 @@ impl Hyperlink {
             url: url.to_string(),
             text: text.into(),
             new_tab: false,
+            open_in_viewport: false,
+            show_url_on_hover: true,
         }
     }
 
     /// Always open this hyperlink in a new browser tab.
+    ///
+    /// Middle-clicking the link, or clicking it with a modifier key held down, always opens it in a new tab.
+    /// What that means can be changed with [`Context::set_open_url_handler`].
     #[inline]
     pub fn open_in_new_tab(mut self, new_tab: bool) -> Self {
         self.new_tab = new_tab;
         self
     }
+
+    /// Open this hyperlink in a new viewport inside the app when clicked,
+    /// see [`Context::open_url_in_viewport`].
+    ///
+    /// This requires a hook that shows the contents, e.g. an embedded web view,
+    /// see [`Context::set_webview_hook`]. Without one the link is opened in a new browser tab.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn open_in_viewport(mut self, open_in_viewport: bool) -> Self {
+        self.open_in_viewport = open_in_viewport;
+        self
+    }
+
+    /// Show the target URL in a tooltip when the link is hovered?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn show_url_on_hover(mut self, show_url_on_hover: bool) -> Self {
+        self.show_url_on_hover = show_url_on_hover;
+        self
+    }
 }
 
 impl Widget for Hyperlink {
     fn ui(self, ui: &mut Ui) -> Response {
-        let Self { url, text, new_tab } = self;
+        let Self {
+            url,
+            text,
+            new_tab,
+            open_in_viewport,
+            show_url_on_hover,
+        } = self;
 
         let response = ui.add(Link::new(text));
 
         if response.clicked() {
             let modifiers = ui.ctx().input(|i| i.modifiers);
-            ui.ctx().open_url(crate::OpenUrl {
-                url: url.clone(),
-                new_tab: new_tab || modifiers.any(),
-            });
+            if open_in_viewport && !modifiers.any() {
+                ui.ctx().open_url_in_viewport(&url);
+            } else {
+                ui.ctx().open_url(crate::OpenUrl {
+                    url: url.clone(),
+                    new_tab: new_tab || modifiers.any(),
+                });
+            }
         }
         if response.middle_clicked() {
             ui.ctx().open_url(crate::OpenUrl {
@@ -128,6 +169,10 @@ impl Widget for Hyperlink {
                 new_tab: true,
             });
         }
-        response.on_hover_text(url)
+        if show_url_on_hover {
+            response.on_hover_text(url)
+        } else {
+            response
+        }
     }
 }
@@ -0,0 +1,493 @@
+use emath::Rot2;
+
+use super::image::{paint_texture_load_result, texture_load_result_response};
+use crate::{
+    load::{SizeHint, TexturePoll},
+    *,
+};
+
+/// Above this zoom (points per image pixel) the pixel grid and the hovered pixel are shown.
+const PIXEL_GRID_MIN_ZOOM: f32 = 8.0;
+
+/// Above this zoom the values of the pixels are written in them.
+const PIXEL_VALUES_MIN_ZOOM: f32 = 48.0;
+
+/// How an [`ImageViewer`] fits the image in its view, until the user pans or zooms.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageViewerFit {
+    /// Show the whole image, as large as possible.
+    #[default]
+    Fit,
+
+    /// Fill the whole view, cropping the image if its aspect ratio is different.
+    Fill,
+
+    /// One image pixel per point.
+    OriginalSize,
+}
+
+impl ImageViewerFit {
+    /// Points per image pixel for an image of the given size in a view of the given size.
+    fn zoom(self, view_size: Vec2, image_size: Vec2) -> f32 {
+        let scale = view_size / image_size.max(Vec2::splat(1.0));
+        match self {
+            Self::Fit => scale.min_elem(),
+            Self::Fill => scale.max_elem(),
+            Self::OriginalSize => 1.0,
+        }
+    }
+}
+
+/// Pan and zoom of an [`ImageViewer`].
+#[derive(Clone, Copy, Debug)]
+struct State {
+    /// Points per image pixel.
+    zoom: f32,
+
+    /// The image position (in pixels) at the center of the view.
+    center: Pos2,
+
+    /// Until the user pans or zooms, the image is fitted to the view whenever it is resized.
+    adjusted: bool,
+}
+
+/// The response of [`ImageViewer::show`].
+pub struct ImageViewerResponse {
+    pub response: Response,
+
+    /// The image pixel under the mouse cursor, as `[x, y]`.
+    pub hovered_pixel: Option<[usize; 2]>,
+}
+
+/// An image that the user can pan and zoom, e.g. in an asset browser or a debugging tool.
+///
+/// Drag or scroll to pan, and zoom with ctrl+scroll or a pinch gesture,
+/// centered on the mouse cursor. Double-click to fit the image again.
+///
+/// At high zoom you can show a grid between the pixels ([`Self::pixel_grid`]),
+/// the coordinates of the hovered pixel, and the values of the pixels ([`Self::pixel_values`]).
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// ui.add(
+///     egui::ImageViewer::new(egui::include_image!("../../assets/ferris.png"))
+///         .size(egui::vec2(400.0, 300.0))
+///         .pixel_grid(true),
+/// );
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct ImageViewer<'a> {
+    source: ImageSource<'a>,
+    texture_options: TextureOptions,
+    id_source: Option<Id>,
+    size: Option<Vec2>,
+    fit: ImageViewerFit,
+    rotation: f32,
+    pixel_grid: bool,
+    pixel_values: Option<&'a ColorImage>,
+    max_zoom: f32,
+}
+
+impl<'a> ImageViewer<'a> {
+    pub fn new(source: impl Into<ImageSource<'a>>) -> Self {
+        Self {
+            source: source.into(),
+            texture_options: TextureOptions {
+                magnification: TextureFilter::Nearest,
+                minification: TextureFilter::Linear,
+            },
+            id_source: None,
+            size: None,
+            fit: ImageViewerFit::default(),
+            rotation: 0.0,
+            pixel_grid: false,
+            pixel_values: None,
+            max_zoom: 128.0,
+        }
+    }
+
+    /// Needed if the viewer moves around in the ui, so that it remembers its pan and zoom.
+    #[inline]
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
+    /// The texture options used when loading the image from a uri or bytes.
+    ///
+    /// The default magnification is [`TextureFilter::Nearest`], to show sharp pixels when zoomed in.
+    #[inline]
+    pub fn texture_options(mut self, texture_options: TextureOptions) -> Self {
+        self.texture_options = texture_options;
+        self
+    }
+
+    /// The size of the view. Default: all of the available space.
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// How to fit the image in the view, until the user pans or zooms. Default: [`ImageViewerFit::Fit`].
+    #[inline]
+    pub fn fit(mut self, fit: ImageViewerFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Rotate the image around the center of the view, in radians. Positive is clockwise.
+    #[inline]
+    pub fn rotate(mut self, angle: f32) -> Self {
+        self.rotation = angle;
+        self
+    }
+
+    /// Draw lines between the pixels at high zoom. Default: `false`.
+    #[inline]
+    pub fn pixel_grid(mut self, pixel_grid: bool) -> Self {
+        self.pixel_grid = pixel_grid;
+        self
+    }
+
+    /// The pixels of the image, to show the value of the hovered pixel,
+    /// and write the value of each pixel in it at very high zoom.
+    #[inline]
+    pub fn pixel_values(mut self, image: &'a ColorImage) -> Self {
+        self.pixel_values = Some(image);
+        self
+    }
+
+    /// The largest zoom, in points per image pixel. Default: 128.
+    #[inline]
+    pub fn max_zoom(mut self, max_zoom: f32) -> Self {
+        self.max_zoom = max_zoom;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> ImageViewerResponse {
+        let size = self.size.unwrap_or_else(|| ui.available_size());
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+        let id = self
+            .id_source
+            .map_or(response.id, |id_source| ui.make_persistent_id(id_source));
+
+        let tlr = self
+            .source
+            .clone()
+            .load(ui.ctx(), self.texture_options, SizeHint::default());
+        let Ok(TexturePoll::Ready { texture }) = tlr else {
+            if ui.is_rect_visible(rect) {
+                paint_texture_load_result(ui, &tlr, rect, None, &ImageOptions::default());
+            }
+            return ImageViewerResponse {
+                response: texture_load_result_response(&self.source, &tlr, response),
+                hovered_pixel: None,
+            };
+        };
+
+        let image_size = texture.size;
+        let rot = Rot2::from_angle(self.rotation);
+        let (sin, cos) = self.rotation.sin_cos();
+        let rotated_size = vec2(
+            (image_size.x * cos).abs() + (image_size.y * sin).abs(),
+            (image_size.x * sin).abs() + (image_size.y * cos).abs(),
+        );
+        let fitted = State {
+            zoom: self.fit.zoom(rect.size(), rotated_size),
+            center: (image_size / 2.0).to_pos2(),
+            adjusted: false,
+        };
+        let min_zoom = (0.5 * ImageViewerFit::Fit.zoom(rect.size(), rotated_size)).min(1.0);
+
+        let mut state = ui
+            .data(|d| d.get_temp::<State>(id))
+            .filter(|state| state.adjusted && !response.double_clicked())
+            .unwrap_or(fitted);
+
+        let view_center = rect.center();
+        let from_screen = |state: &State, pos: Pos2| {
+            state.center + rot.inverse() * (pos - view_center) / state.zoom
+        };
+
+        if response.dragged() {
+            state.center -= rot.inverse() * response.drag_delta() / state.zoom;
+            state.adjusted = true;
+        }
+        if let Some(hover_pos) = response.hover_pos() {
+            let (zoom_delta, scroll_delta) = ui.input(|i| (i.zoom_delta(), i.scroll_delta));
+            if zoom_delta != 1.0 {
+                // Keep the pixel under the cursor in place:
+                let pixel = from_screen(&state, hover_pos);
+                state.zoom = (state.zoom * zoom_delta).clamp(min_zoom, self.max_zoom.max(min_zoom));
+                state.center = pixel - rot.inverse() * (hover_pos - view_center) / state.zoom;
+                state.adjusted = true;
+            }
+            if scroll_delta != Vec2::ZERO {
+                state.center -= rot.inverse() * scroll_delta / state.zoom;
+                state.adjusted = true;
+            }
+        }
+        state.center = state.center.clamp(Pos2::ZERO, image_size.to_pos2());
+        ui.data_mut(|d| d.insert_temp(id, state));
+
+        let to_screen = |pixel: Pos2| view_center + rot * ((pixel - state.center) * state.zoom);
+        let hovered_pixel = response
+            .hover_pos()
+            .map(|pos| from_screen(&state, pos))
+            .filter(|pixel| Rect::from_min_size(Pos2::ZERO, image_size).contains(*pixel))
+            .map(|pixel| [pixel.x as usize, pixel.y as usize]);
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter_at(rect);
+
+            let mut mesh = Mesh::with_texture(texture.id);
+            mesh.add_rect_with_uv(
+                Rect::from_min_size(
+                    view_center - state.center.to_vec2() * state.zoom,
+                    image_size * state.zoom,
+                ),
+                Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                Color32::WHITE,
+            );
+            mesh.rotate(rot, view_center);
+            painter.add(Shape::mesh(mesh));
+
+            // The pixels within the view:
+            let visible = Rect::from_points(
+                &[
+                    rect.left_top(),
+                    rect.right_top(),
+                    rect.left_bottom(),
+                    rect.right_bottom(),
+                ]
+                .map(|pos| from_screen(&state, pos)),
+            );
+            let [min_x, min_y] =
+                [visible.min.x, visible.min.y].map(|v| v.floor().max(0.0) as usize);
+            let max_x = visible.max.x.ceil().clamp(0.0, image_size.x) as usize;
+            let max_y = visible.max.y.ceil().clamp(0.0, image_size.y) as usize;
+
+            if self.pixel_grid && PIXEL_GRID_MIN_ZOOM <= state.zoom {
+                let stroke = Stroke::new(1.0, Color32::from_gray(128).gamma_multiply(0.5));
+                for x in min_x..=max_x {
+                    let x = x as f32;
+                    painter.line_segment(
+                        [
+                            to_screen(pos2(x, min_y as f32)),
+                            to_screen(pos2(x, max_y as f32)),
+                        ],
+                        stroke,
+                    );
+                }
+                for y in min_y..=max_y {
+                    let y = y as f32;
+                    painter.line_segment(
+                        [
+                            to_screen(pos2(min_x as f32, y)),
+                            to_screen(pos2(max_x as f32, y)),
+                        ],
+                        stroke,
+                    );
+                }
+            }
+
+            if let Some(image) = self.pixel_values {
+                if PIXEL_VALUES_MIN_ZOOM <= state.zoom {
+                    let font_id = TextStyle::Small.resolve(ui.style());
+                    for y in min_y..max_y.min(image.height()) {
+                        for x in min_x..max_x.min(image.width()) {
+                            let color = image[(x, y)];
+                            let [r, g, b, a] = color.to_srgba_unmultiplied();
+                            let text_color = if Rgba::from(color).intensity() < 0.5 {
+                                Color32::WHITE
+                            } else {
+                                Color32::BLACK
+                            };
+                            painter.text(
+                                to_screen(pos2(x as f32 + 0.5, y as f32 + 0.5)),
+                                Align2::CENTER_CENTER,
+                                format!("{r}\n{g}\n{b}\n{a}"),
+                                font_id.clone(),
+                                text_color,
+                            );
+                        }
+                    }
+                }
+            }
+
+            if let (Some([x, y]), true) = (hovered_pixel, PIXEL_GRID_MIN_ZOOM <= state.zoom) {
+                let mut text = format!("{x}, {y}");
+                if let Some(color) = self
+                    .pixel_values
+                    .filter(|image| x < image.width() && y < image.height())
+                    .map(|image| image[(x, y)])
+                {
+                    let [r, g, b, a] = color.to_srgba_unmultiplied();
+                    text += &format!(": rgba({r}, {g}, {b}, {a})");
+                }
+                let visuals = ui.visuals();
+                let galley = painter.layout_no_wrap(
+                    text,
+                    TextStyle::Small.resolve(ui.style()),
+                    visuals.text_color(),
+                );
+                let margin = Vec2::splat(ui.spacing().item_spacing.y);
+                let text_rect = Rect::from_min_size(
+                    rect.left_bottom() + vec2(2.0, -2.0) * margin - vec2(0.0, galley.size().y),
+                    galley.size(),
+                );
+                painter.rect_filled(
+                    text_rect.expand2(margin),
+                    visuals.widgets.noninteractive.rounding,
+                    visuals.extreme_bg_color.gamma_multiply(0.8),
+                );
+                painter.galley(text_rect.min, galley, visuals.text_color());
+            }
+        }
+
+        let cursor = if response.dragged() {
+            CursorIcon::Grabbing
+        } else {
+            CursorIcon::Grab
+        };
+        let response = response.on_hover_cursor(cursor);
+        response.widget_info(|| WidgetInfo::new(WidgetType::Other));
+
+        ImageViewerResponse {
+            response,
+            hovered_pixel,
+        }
+    }
+}
+
+impl<'a> Widget for ImageViewer<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load::SizedTexture, test_util::run_in_panel};
+
+    const VIEW_SIZE: Vec2 = Vec2::splat(200.0);
+
+    /// Shows a 100x50 image, and returns the response and the pan and zoom after the frame.
+    fn run(
+        ctx: &Context,
+        input: RawInputBuilder,
+        viewer: impl FnOnce(ImageViewer<'static>) -> ImageViewer<'static>,
+    ) -> (ImageViewerResponse, State) {
+        let texture = SizedTexture::new(TextureId::Managed(1), vec2(100.0, 50.0));
+        let response = run_in_panel(ctx, input, |ui| {
+            viewer(ImageViewer::new(texture).size(VIEW_SIZE)).show(ui)
+        });
+        let state = ctx
+            .data(|d| d.get_temp::<State>(response.response.id))
+            .unwrap();
+        (response, state)
+    }
+
+    #[test]
+    fn test_fit_zoom() {
+        let view_size = vec2(200.0, 200.0);
+        let image_size = vec2(100.0, 50.0);
+        assert_eq!(ImageViewerFit::Fit.zoom(view_size, image_size), 2.0);
+        assert_eq!(ImageViewerFit::Fill.zoom(view_size, image_size), 4.0);
+        assert_eq!(
+            ImageViewerFit::OriginalSize.zoom(view_size, image_size),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_fit_and_fill_transforms() {
+        let ctx = Context::default();
+        let (response, state) = run(&ctx, RawInputBuilder::new(), |viewer| viewer);
+        assert_eq!(state.zoom, 2.0);
+        assert_eq!(state.center, pos2(50.0, 25.0));
+        let center = response.response.rect.center();
+
+        // The whole image is shown, with empty space above and below it:
+        let hovered = |ctx: &Context, pos: Pos2, fit: ImageViewerFit| {
+            run(ctx, RawInputBuilder::new().pointer_moved(pos), |viewer| {
+                viewer.fit(fit)
+            })
+            .0
+            .hovered_pixel
+        };
+        let fit = ImageViewerFit::Fit;
+        assert_eq!(hovered(&ctx, center + vec2(-99.0, 0.0), fit), Some([0, 25]));
+        assert_eq!(
+            hovered(&ctx, center + vec2(99.0, 49.0), fit),
+            Some([99, 49])
+        );
+        assert_eq!(hovered(&ctx, center + vec2(0.0, -60.0), fit), None);
+
+        // The image fills the view, and its sides are cropped:
+        let ctx = Context::default();
+        let (_, state) = run(&ctx, RawInputBuilder::new(), |viewer| {
+            viewer.fit(ImageViewerFit::Fill)
+        });
+        assert_eq!(state.zoom, 4.0);
+        let fill = ImageViewerFit::Fill;
+        assert_eq!(
+            hovered(&ctx, center + vec2(-99.0, 0.0), fill),
+            Some([25, 25])
+        );
+        assert_eq!(
+            hovered(&ctx, center + vec2(0.0, -60.0), fill),
+            Some([50, 10])
+        );
+    }
+
+    #[test]
+    fn test_fit_rotated() {
+        // Rotated a quarter turn, the image is 50 wide and 100 high:
+        let ctx = Context::default();
+        let rotate = |viewer: ImageViewer<'static>| viewer.rotate(std::f32::consts::FRAC_PI_2);
+        let (response, state) = run(&ctx, RawInputBuilder::new(), rotate);
+        assert_eq!(state.zoom, 2.0);
+
+        // Clockwise, so the top left of the image is at the top right of the view:
+        let pos = response.response.rect.center() + vec2(49.0, -99.0);
+        let (response, _) = run(&ctx, RawInputBuilder::new().pointer_moved(pos), rotate);
+        assert_eq!(response.hovered_pixel, Some([0, 0]));
+    }
+
+    #[test]
+    fn test_zoom_around_cursor() {
+        let ctx = Context::default();
+        let (response, before) = run(&ctx, RawInputBuilder::new(), |viewer| viewer);
+        let view_center = response.response.rect.center();
+        let pos = view_center + vec2(40.0, 20.0);
+        run(&ctx, RawInputBuilder::new().pointer_moved(pos), |viewer| {
+            viewer
+        });
+
+        let (_, after) = run(
+            &ctx,
+            RawInputBuilder::new().pointer_moved(pos).zoom(2.0),
+            |viewer| viewer,
+        );
+        assert_eq!(after.zoom, 2.0 * before.zoom);
+        assert!(after.adjusted);
+
+        // The image point under the cursor stays in place:
+        let pixel = |state: State| state.center + (pos - view_center) / state.zoom;
+        assert!((pixel(after) - pixel(before)).length() < 1e-3);
+        assert_eq!(pixel(after), pos2(70.0, 35.0));
+
+        // Zoom is clamped to the largest zoom:
+        let (_, state) = run(
+            &ctx,
+            RawInputBuilder::new().pointer_moved(pos).zoom(1000.0),
+            |viewer| viewer.max_zoom(16.0),
+        );
+        assert_eq!(state.zoom, 16.0);
+        assert!((pixel(state) - pos2(70.0, 35.0)).length() < 1e-3);
+    }
+}
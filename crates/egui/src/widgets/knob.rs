@@ -0,0 +1,662 @@
+use std::f32::consts::TAU;
+use std::ops::RangeInclusive;
+
+use crate::*;
+
+// ----------------------------------------------------------------------------
+
+/// Combined into one function (rather than two) to make it easier
+/// for the borrow checker.
+type GetSetValue<'a> = Box<dyn 'a + FnMut(Option<f64>) -> f64>;
+
+fn get(get_set_value: &mut GetSetValue<'_>) -> f64 {
+    (get_set_value)(None)
+}
+
+fn set(get_set_value: &mut GetSetValue<'_>, value: f64) {
+    (get_set_value)(Some(value));
+}
+
+/// How much finer dragging and the arrow keys are while holding shift.
+const FINE_ADJUST: f32 = 0.1;
+
+/// The direction of an angle measured clockwise from straight up.
+fn clockwise_from_up(angle: f32) -> Vec2 {
+    vec2(angle.sin(), -angle.cos())
+}
+
+/// Points along a circular arc, for [`Shape::line`].
+fn arc_points(center: Pos2, radius: f32, start: f32, end: f32) -> Vec<Pos2> {
+    let n = ((end - start).abs() / TAU * 64.0).ceil().at_least(1.0) as usize;
+    (0..=n)
+        .map(|i| center + radius * clockwise_from_up(lerp(start..=end, i as f32 / n as f32)))
+        .collect()
+}
+
+/// Lock the arrow keys to the widget while focused, and return how many steps they moved.
+///
+/// Right and up are positive. The steps are [`FINE_ADJUST`] smaller while holding shift.
+fn arrow_key_steps(ui: &Ui, response: &Response) -> Vec2 {
+    if !response.has_focus() {
+        return Vec2::ZERO;
+    }
+    ui.memory_mut(|m| {
+        m.set_focus_lock_filter(
+            response.id,
+            EventFilter {
+                horizontal_arrows: true,
+                vertical_arrows: true,
+                ..Default::default()
+            },
+        );
+    });
+    ui.input(|i| {
+        let steps = vec2(
+            i.num_presses(Key::ArrowRight) as f32 - i.num_presses(Key::ArrowLeft) as f32,
+            i.num_presses(Key::ArrowUp) as f32 - i.num_presses(Key::ArrowDown) as f32,
+        );
+        if i.modifiers.shift_only() {
+            FINE_ADJUST * steps
+        } else {
+            steps
+        }
+    })
+}
+
+// ----------------------------------------------------------------------------
+
+/// A rotary knob for editing a number in a range, as seen in audio tools.
+///
+/// Drag up or right to increase the value, hold shift for fine adjustment,
+/// and double-click to go back to the [`Self::default_value`].
+/// When focused, the arrow keys change the value by 1% of the range (0.1% with shift),
+/// and Home/End go to the start and end of the range.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut volume = 0.5_f32;
+/// ui.add(egui::Knob::new(&mut volume, 0.0..=1.0).text("Volume"));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct Knob<'a> {
+    get_set_value: GetSetValue<'a>,
+    range: RangeInclusive<f64>,
+    step: Option<f64>,
+    default_value: Option<f64>,
+    text: WidgetText,
+    show_value: bool,
+    diameter: Option<f32>,
+    drag_distance: f32,
+    arc_start: f32,
+    arc_sweep: f32,
+    arc_width: Option<f32>,
+    arc_color: Option<Color32>,
+}
+
+impl<'a> Knob<'a> {
+    pub fn new<Num: emath::Numeric>(value: &'a mut Num, range: RangeInclusive<Num>) -> Self {
+        let range_f64 = range.start().to_f64()..=range.end().to_f64();
+        let slf = Self::from_get_set(range_f64, move |v: Option<f64>| {
+            if let Some(v) = v {
+                *value = Num::from_f64(v);
+            }
+            value.to_f64()
+        });
+
+        if Num::INTEGRAL {
+            slf.step(1.0)
+        } else {
+            slf
+        }
+    }
+
+    pub fn from_get_set(
+        range: RangeInclusive<f64>,
+        get_set_value: impl 'a + FnMut(Option<f64>) -> f64,
+    ) -> Self {
+        Self {
+            get_set_value: Box::new(get_set_value),
+            range,
+            step: None,
+            default_value: None,
+            text: Default::default(),
+            show_value: true,
+            diameter: None,
+            drag_distance: 200.0,
+            arc_start: -0.375 * TAU,
+            arc_sweep: 0.75 * TAU,
+            arc_width: None,
+            arc_color: None,
+        }
+    }
+
+    /// Show a label next to the knob.
+    #[inline]
+    pub fn text(mut self, text: impl Into<WidgetText>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Show the value in the middle of the knob. Default: `true`.
+    #[inline]
+    pub fn show_value(mut self, show_value: bool) -> Self {
+        self.show_value = show_value;
+        self
+    }
+
+    /// Only allow multiples of this step, counted from the start of the range.
+    #[inline]
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = (step != 0.0).then_some(step);
+        self
+    }
+
+    /// Double-clicking the knob sets it to this value.
+    #[inline]
+    pub fn default_value(mut self, default_value: f64) -> Self {
+        self.default_value = Some(default_value);
+        self
+    }
+
+    /// Default: three times [`crate::style::Spacing::interact_size`]`.y`.
+    #[inline]
+    pub fn diameter(mut self, diameter: f32) -> Self {
+        self.diameter = Some(diameter);
+        self
+    }
+
+    /// How many points to drag to go through the whole range. Default: 200.
+    #[inline]
+    pub fn drag_distance(mut self, drag_distance: f32) -> Self {
+        self.drag_distance = drag_distance;
+        self
+    }
+
+    /// Where the arc starts, in radians clockwise from straight up, and how far it goes.
+    ///
+    /// Default: from -135° to 135°, i.e. `arc(-0.375 * TAU, 0.75 * TAU)`.
+    #[inline]
+    pub fn arc(mut self, start: f32, sweep: f32) -> Self {
+        self.arc_start = start;
+        self.arc_sweep = sweep;
+        self
+    }
+
+    /// The thickness of the arc. Default: a tenth of the diameter.
+    #[inline]
+    pub fn arc_width(mut self, arc_width: f32) -> Self {
+        self.arc_width = Some(arc_width);
+        self
+    }
+
+    /// The color of the filled part of the arc. Default: [`style::Selection::bg_fill`].
+    #[inline]
+    pub fn arc_color(mut self, arc_color: impl Into<Color32>) -> Self {
+        self.arc_color = Some(arc_color.into());
+        self
+    }
+
+    fn set_value(&mut self, mut value: f64) {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        value = value.clamp(start.min(end), start.max(end));
+        if let Some(step) = self.step {
+            value = start + ((value - start) / step).round() * step;
+        }
+        set(&mut self.get_set_value, value);
+    }
+
+    fn normalized(&mut self) -> f32 {
+        let value = get(&mut self.get_set_value);
+        remap_clamp(value, self.range.clone(), 0.0..=1.0) as f32
+    }
+
+    fn set_normalized(&mut self, normalized: f32) {
+        let value = lerp(self.range.clone(), normalized.clamp(0.0, 1.0) as f64);
+        self.set_value(value);
+    }
+}
+
+impl<'a> Widget for Knob<'a> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let old_value = get(&mut self.get_set_value);
+        let diameter = self
+            .diameter
+            .unwrap_or_else(|| 3.0 * ui.spacing().interact_size.y);
+
+        let (rect, mut response) =
+            ui.allocate_exact_size(Vec2::splat(diameter), Sense::click_and_drag());
+
+        // While dragging we remember the unrounded position, so that small drags add up.
+        let precise_id = response.id.with("precise");
+        if response.dragged() {
+            let precise = ui
+                .data(|d| d.get_temp::<f32>(precise_id))
+                .unwrap_or_else(|| self.normalized());
+            let delta = response.drag_delta();
+            let mut change = (delta.x - delta.y) / self.drag_distance.at_least(1.0);
+            if ui.input(|i| i.modifiers.shift_only()) {
+                change *= FINE_ADJUST;
+            }
+            let precise = (precise + change).clamp(0.0, 1.0);
+            ui.data_mut(|d| d.insert_temp(precise_id, precise));
+            let prev_value = get(&mut self.get_set_value);
+            self.set_normalized(precise);
+            if self.step.is_some() && get(&mut self.get_set_value) != prev_value {
+                ui.ctx().haptic(HapticKind::Selection);
+            }
+        } else {
+            ui.data_mut(|d| d.remove::<f32>(precise_id));
+        }
+
+        if response.double_clicked() {
+            if let Some(default_value) = self.default_value {
+                self.set_value(default_value);
+            }
+        }
+
+        let mut kb_step = 0.0;
+        let steps = arrow_key_steps(ui, &response);
+        kb_step += steps.x + steps.y;
+        let (home, end) = if response.has_focus() {
+            ui.input(|i| (i.key_pressed(Key::Home), i.key_pressed(Key::End)))
+        } else {
+            (false, false)
+        };
+
+        #[cfg(feature = "accesskit")]
+        {
+            use accesskit::Action;
+            ui.input(|input| {
+                kb_step += input.num_accesskit_action_requests(response.id, Action::Increment)
+                    as f32
+                    - input.num_accesskit_action_requests(response.id, Action::Decrement) as f32;
+            });
+        }
+
+        if kb_step != 0.0 {
+            if let Some(step) = self.step {
+                // Holding shift can't make the change smaller than one step:
+                let steps = if kb_step.abs() < 1.0 {
+                    kb_step.signum()
+                } else {
+                    kb_step
+                };
+                let value = get(&mut self.get_set_value);
+                self.set_value(value + steps as f64 * step);
+            } else {
+                let normalized = self.normalized() + 0.01 * kb_step;
+                self.set_normalized(normalized);
+            }
+        }
+        if home {
+            self.set_normalized(0.0);
+        }
+        if end {
+            self.set_normalized(1.0);
+        }
+
+        #[cfg(feature = "accesskit")]
+        {
+            use accesskit::{Action, ActionData};
+            ui.input(|input| {
+                for request in input.accesskit_action_requests(response.id, Action::SetValue) {
+                    if let Some(ActionData::NumericValue(new_value)) = request.data {
+                        self.set_value(new_value);
+                    }
+                }
+            });
+        }
+
+        let value = get(&mut self.get_set_value);
+        response.changed = value != old_value;
+        response.widget_info(|| WidgetInfo::slider(value, self.text.text()));
+
+        #[cfg(feature = "accesskit")]
+        ui.ctx().accesskit_node_builder(response.id, |builder| {
+            use accesskit::Action;
+            builder.set_min_numeric_value(*self.range.start());
+            builder.set_max_numeric_value(*self.range.end());
+            if let Some(step) = self.step {
+                builder.set_numeric_value_step(step);
+            }
+            builder.add_action(Action::SetValue);
+            builder.add_action(Action::Increment);
+            builder.add_action(Action::Decrement);
+        });
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact(&response);
+            let painter = ui.painter();
+            let center = rect.center();
+            let arc_width = self.arc_width.unwrap_or(diameter / 10.0);
+            let radius = (diameter - arc_width) / 2.0;
+            let track_color = ui.visuals().widgets.inactive.bg_fill;
+            let arc_color = self.arc_color.unwrap_or(ui.visuals().selection.bg_fill);
+
+            let start = self.arc_start;
+            let end = self.arc_start + self.arc_sweep;
+            let current = lerp(start..=end, self.normalized());
+
+            painter.add(Shape::line(
+                arc_points(center, radius, start, end),
+                Stroke::new(arc_width, track_color),
+            ));
+            painter.add(Shape::line(
+                arc_points(center, radius, start, current),
+                Stroke::new(arc_width, arc_color),
+            ));
+
+            let knob_radius = radius - arc_width;
+            painter.circle(center, knob_radius, visuals.bg_fill, visuals.fg_stroke);
+            let direction = clockwise_from_up(current);
+            painter.line_segment(
+                [
+                    center + 0.4 * knob_radius * direction,
+                    center + knob_radius * direction,
+                ],
+                Stroke::new(
+                    arc_width.at_most(knob_radius / 4.0),
+                    visuals.fg_stroke.color,
+                ),
+            );
+
+            if self.show_value {
+                let text = ui.style().number_formatter.format(value, 0..=2);
+                painter.text(
+                    center,
+                    Align2::CENTER_CENTER,
+                    text,
+                    FontId::proportional(diameter / 5.0),
+                    visuals.text_color(),
+                );
+            }
+
+            if response.has_focus() {
+                painter.circle_stroke(center, diameter / 2.0, ui.visuals().selection.stroke);
+            }
+        }
+
+        if !self.text.is_empty() {
+            ui.add(Label::new(self.text));
+        }
+
+        response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A dial for picking a direction, e.g. the angle of a gradient or a light.
+///
+/// The angle is in radians, counter-clockwise from pointing right (like in math),
+/// and is kept in `0.0..TAU`.
+///
+/// Click or drag to point the dial, hold shift for fine adjustment.
+/// When focused, the arrow keys turn it by 1° (0.1° with shift).
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut angle = 0.0_f32;
+/// ui.add(egui::AnglePicker::new(&mut angle).snap_degrees(15.0));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct AnglePicker<'a> {
+    angle: &'a mut f32,
+    diameter: Option<f32>,
+    snap: Option<f32>,
+    show_value: bool,
+}
+
+impl<'a> AnglePicker<'a> {
+    pub fn new(angle: &'a mut f32) -> Self {
+        Self {
+            angle,
+            diameter: None,
+            snap: None,
+            show_value: true,
+        }
+    }
+
+    /// Default: twice [`crate::style::Spacing::interact_size`]`.y`.
+    #[inline]
+    pub fn diameter(mut self, diameter: f32) -> Self {
+        self.diameter = Some(diameter);
+        self
+    }
+
+    /// When dragging, snap to multiples of this many degrees, unless holding shift.
+    #[inline]
+    pub fn snap_degrees(mut self, degrees: f32) -> Self {
+        self.snap = (degrees > 0.0).then_some(degrees.to_radians());
+        self
+    }
+
+    /// Show the angle in degrees next to the dial. Default: `true`.
+    #[inline]
+    pub fn show_value(mut self, show_value: bool) -> Self {
+        self.show_value = show_value;
+        self
+    }
+}
+
+impl<'a> Widget for AnglePicker<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            angle,
+            diameter,
+            snap,
+            show_value,
+        } = self;
+        let old_angle = *angle;
+        let diameter = diameter.unwrap_or_else(|| 2.0 * ui.spacing().interact_size.y);
+
+        let mut response = ui
+            .horizontal(|ui| {
+                let (rect, response) =
+                    ui.allocate_exact_size(Vec2::splat(diameter), Sense::click_and_drag());
+
+                // The angle of a point around the center, counter-clockwise from the right:
+                let angle_at = |pos: Pos2| {
+                    let delta = pos - rect.center();
+                    (-delta.y).atan2(delta.x)
+                };
+
+                if let Some(pointer) = response.interact_pointer_pos() {
+                    let fine = ui.input(|i| i.modifiers.shift_only());
+                    if fine && response.dragged() {
+                        // Turn relative to the last pointer position, more slowly:
+                        let prev = pointer - response.drag_delta();
+                        let mut delta = angle_at(pointer) - angle_at(prev);
+                        if delta > TAU / 2.0 {
+                            delta -= TAU;
+                        } else if delta < -TAU / 2.0 {
+                            delta += TAU;
+                        }
+                        *angle += FINE_ADJUST * delta;
+                    } else if response.is_pointer_button_down_on() {
+                        *angle = angle_at(pointer);
+                        if let Some(snap) = snap {
+                            if !fine {
+                                *angle = (*angle / snap).round() * snap;
+                            }
+                        }
+                    }
+                }
+
+                let steps = arrow_key_steps(ui, &response);
+                *angle += (steps.x + steps.y).to_radians();
+
+                #[cfg(feature = "accesskit")]
+                {
+                    use accesskit::{Action, ActionData};
+                    ui.input(|input| {
+                        let increments = input
+                            .num_accesskit_action_requests(response.id, Action::Increment)
+                            as f32
+                            - input.num_accesskit_action_requests(response.id, Action::Decrement)
+                                as f32;
+                        *angle += increments.to_radians();
+                        for request in
+                            input.accesskit_action_requests(response.id, Action::SetValue)
+                        {
+                            if let Some(ActionData::NumericValue(degrees)) = request.data {
+                                *angle = (degrees as f32).to_radians();
+                            }
+                        }
+                    });
+                }
+
+                *angle = angle.rem_euclid(TAU);
+
+                if ui.is_rect_visible(rect) {
+                    let visuals = ui.style().interact(&response);
+                    let painter = ui.painter();
+                    let center = rect.center();
+                    let radius = diameter / 2.0 - visuals.fg_stroke.width;
+                    painter.circle(center, radius, visuals.bg_fill, visuals.bg_stroke);
+                    let direction = vec2(angle.cos(), -angle.sin());
+                    painter.line_segment(
+                        [center, center + radius * direction],
+                        Stroke::new(2.0, visuals.fg_stroke.color),
+                    );
+                    painter.circle_filled(center, 2.0, visuals.fg_stroke.color);
+                    if response.has_focus() {
+                        painter.circle_stroke(
+                            center,
+                            diameter / 2.0,
+                            ui.visuals().selection.stroke,
+                        );
+                    }
+                }
+
+                if show_value {
+                    let degrees = angle.to_degrees();
+                    ui.label(format!(
+                        "{}°",
+                        ui.style().number_formatter.format(degrees as f64, 0..=1)
+                    ));
+                }
+
+                response
+            })
+            .inner;
+
+        response.changed = *angle != old_angle;
+        let degrees = angle.to_degrees() as f64;
+        response.widget_info(|| WidgetInfo::slider(degrees, "angle"));
+
+        #[cfg(feature = "accesskit")]
+        ui.ctx().accesskit_node_builder(response.id, |builder| {
+            use accesskit::Action;
+            builder.set_min_numeric_value(0.0);
+            builder.set_max_numeric_value(360.0);
+            builder.add_action(Action::SetValue);
+            builder.add_action(Action::Increment);
+            builder.add_action(Action::Decrement);
+        });
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{press, run_in_panel};
+
+    /// Run a frame with a focused knob for `value` in `0..=100`, and return its rectangle.
+    fn run_knob(ctx: &Context, value: &mut f64, step: Option<f64>, input: RawInputBuilder) -> Rect {
+        run_in_panel(ctx, input, |ui| {
+            let mut knob = Knob::new(value, 0.0..=100.0).drag_distance(200.0);
+            if let Some(step) = step {
+                knob = knob.step(step);
+            }
+            let response = ui.add(knob);
+            response.request_focus();
+            response.rect
+        })
+    }
+
+    #[test]
+    fn knob_drag() {
+        let ctx = Context::default();
+        let mut value = 50.0;
+        let center = run_knob(&ctx, &mut value, None, RawInputBuilder::new()).center();
+
+        press(center, |input| {
+            run_knob(&ctx, &mut value, None, input);
+        });
+
+        // A quarter of the drag distance up is a quarter of the range:
+        let input = RawInputBuilder::new().pointer_moved(center - vec2(0.0, 50.0));
+        run_knob(&ctx, &mut value, None, input);
+        assert!((value - 75.0).abs() < 1e-3, "value: {value}");
+
+        // Fine adjustment while holding shift:
+        let input = RawInputBuilder::new()
+            .modifiers(Modifiers::SHIFT)
+            .pointer_moved(center - vec2(0.0, 100.0));
+        run_knob(&ctx, &mut value, None, input);
+        assert!((value - 77.5).abs() < 1e-3, "value: {value}");
+    }
+
+    #[test]
+    fn knob_keys() {
+        let ctx = Context::default();
+        let mut value = 50.0;
+        run_knob(&ctx, &mut value, None, RawInputBuilder::new());
+
+        // 1% of the range per press, and all presses in a frame count:
+        let input = RawInputBuilder::new()
+            .key(Key::ArrowRight, true)
+            .key(Key::ArrowRight, true)
+            .key(Key::ArrowUp, true);
+        run_knob(&ctx, &mut value, None, input);
+        assert!((value - 53.0).abs() < 1e-3, "value: {value}");
+
+        let input = RawInputBuilder::new()
+            .modifiers(Modifiers::SHIFT)
+            .key(Key::ArrowDown, true);
+        run_knob(&ctx, &mut value, None, input);
+        assert!((value - 52.9).abs() < 1e-3, "value: {value}");
+
+        run_knob(
+            &ctx,
+            &mut value,
+            None,
+            RawInputBuilder::new().key(Key::End, true),
+        );
+        assert_eq!(value, 100.0);
+        run_knob(
+            &ctx,
+            &mut value,
+            None,
+            RawInputBuilder::new().key(Key::Home, true),
+        );
+        assert_eq!(value, 0.0);
+    }
+
+    #[test]
+    fn knob_keys_with_step() {
+        let ctx = Context::default();
+        let mut value = 50.0;
+        let step = Some(0.1);
+        run_knob(&ctx, &mut value, step, RawInputBuilder::new());
+
+        let input = RawInputBuilder::new()
+            .key(Key::ArrowRight, true)
+            .key(Key::ArrowRight, true)
+            .key(Key::ArrowRight, true);
+        run_knob(&ctx, &mut value, step, input);
+        assert!((value - 50.3).abs() < 1e-9, "value: {value}");
+
+        // Fine adjustment still moves a whole step:
+        let input = RawInputBuilder::new()
+            .modifiers(Modifiers::SHIFT)
+            .key(Key::ArrowLeft, true);
+        run_knob(&ctx, &mut value, step, input);
+        assert!((value - 50.2).abs() < 1e-9, "value: {value}");
+    }
+}
@@ -22,6 +22,7 @@ pub struct Label {
     text: WidgetText,
     wrap: Option<bool>,
     truncate: bool,
+    truncate_mode: TruncateMode,
     sense: Option<Sense>,
 }
 
@@ -31,6 +32,7 @@ impl Label {
             text: text.into(),
             wrap: None,
             truncate: false,
+            truncate_mode: TruncateMode::End,
             sense: None,
         }
     }
@@ -73,6 +75,28 @@ impl Label {
         self
     }
 
+    /// Truncate the text (see [`Self::truncate`]), eliding the given part of it.
+    ///
+    /// Use [`TruncateMode::Middle`] to keep both the start and the end of e.g. file names visible:
+    /// `long_fi…me.txt`.
+    ///
+    /// The full text is shown on hover, and is what screen readers read out.
+    ///
+    /// Calling `truncate_mode` will override [`Self::wrap`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.add(egui::Label::new("a_very_long_file_name.txt").truncate_mode(egui::TruncateMode::Middle));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn truncate_mode(mut self, truncate_mode: TruncateMode) -> Self {
+        self.wrap = None;
+        self.truncate = true;
+        self.truncate_mode = truncate_mode;
+        self
+    }
+
     /// Make the label respond to clicks and/or drags.
     ///
     /// By default, a label is inert and does not respond to click or drags.
@@ -161,6 +185,7 @@ impl Label {
                 layout_job.wrap.max_width = available_width;
                 layout_job.wrap.max_rows = 1;
                 layout_job.wrap.break_anywhere = true;
+                layout_job.wrap.truncate_mode = self.truncate_mode;
             } else if wrap {
                 layout_job.wrap.max_width = available_width;
             } else {
@@ -0,0 +1,391 @@
+use crate::*;
+
+/// Typed characters this many seconds apart start a new type-to-select search.
+const TYPEAHEAD_TIMEOUT: f64 = 1.0;
+
+#[derive(Clone, Debug, Default)]
+struct State {
+    /// Where shift-selections extend from.
+    anchor: Option<usize>,
+
+    /// The item moved with the keyboard.
+    cursor: Option<usize>,
+
+    typeahead: String,
+    last_typed_time: f64,
+
+    /// Of the scroll area, last frame.
+    scroll_offset: f32,
+    viewport_height: f32,
+}
+
+/// What [`ListBox::show`] returns.
+pub struct ListBoxResponse {
+    /// The response of the whole list.
+    ///
+    /// [`Response::changed`] is set when the selection changed.
+    pub response: Response,
+
+    /// The item that was double-clicked, or focused when Enter was pressed.
+    pub activated: Option<usize>,
+}
+
+/// A scrollable list of items where one or more can be selected.
+///
+/// With [`Self::multi_select`], ctrl/cmd-click toggles an item and shift-click selects a range.
+///
+/// When focused, the arrow keys, Page Up/Down and Home/End move through the items,
+/// holding shift to extend the selection or ctrl/cmd to move without selecting,
+/// and space toggles the item. Typing the start of an item jumps to it.
+///
+/// Only the visible items are shown, so this works with long lists too.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let fruits = ["Apple", "Banana", "Cherry", "Durian"];
+/// let mut selection = vec![];
+/// let response = egui::ListBox::new(fruits.len(), |i| fruits[i].to_owned(), &mut selection)
+///     .multi_select(true)
+///     .show(ui);
+/// if response.response.changed() {
+///     // `selection` contains the selected indices, in the order they were selected.
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct ListBox<'a> {
+    id_source: Id,
+    num_items: usize,
+    item_text: Box<dyn 'a + Fn(usize) -> String>,
+    selection: &'a mut Vec<usize>,
+    multi_select: bool,
+    max_height: f32,
+}
+
+impl<'a> ListBox<'a> {
+    /// A list of `num_items` items with the given texts.
+    ///
+    /// The selected indices are kept in `selection`, in the order they were selected.
+    pub fn new(
+        num_items: usize,
+        item_text: impl 'a + Fn(usize) -> String,
+        selection: &'a mut Vec<usize>,
+    ) -> Self {
+        Self {
+            id_source: Id::new("list_box"),
+            num_items,
+            item_text: Box::new(item_text),
+            selection,
+            multi_select: false,
+            max_height: 200.0,
+        }
+    }
+
+    /// Needed if you show more than one [`ListBox`] in the same [`Ui`].
+    #[inline]
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Id::new(id_source);
+        self
+    }
+
+    /// Allow selecting more than one item. Default is OFF.
+    #[inline]
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// The list scrolls when it is taller than this. Default: 200.
+    #[inline]
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    fn select_only(&mut self, index: usize) {
+        self.selection.clear();
+        self.selection.push(index);
+    }
+
+    fn toggle(&mut self, index: usize) {
+        if let Some(position) = self.selection.iter().position(|&i| i == index) {
+            self.selection.remove(position);
+        } else {
+            self.selection.push(index);
+        }
+    }
+
+    /// Select the items from `anchor` to `index`, in that order.
+    fn select_range(&mut self, anchor: usize, index: usize, add: bool) {
+        if !add {
+            self.selection.clear();
+        }
+        let range: Box<dyn Iterator<Item = usize>> = if anchor <= index {
+            Box::new(anchor..=index)
+        } else {
+            Box::new((index..=anchor).rev())
+        };
+        for i in range {
+            if !self.selection.contains(&i) {
+                self.selection.push(i);
+            }
+        }
+    }
+
+    /// Move to `index` with the keyboard or a click.
+    fn select(&mut self, state: &mut State, index: usize, modifiers: Modifiers, is_click: bool) {
+        state.cursor = Some(index);
+        if self.multi_select && modifiers.shift {
+            let anchor = state.anchor.unwrap_or(index);
+            self.select_range(anchor, index, modifiers.command);
+        } else if self.multi_select && modifiers.command {
+            // Clicking toggles, the keyboard only moves the cursor:
+            if is_click {
+                self.toggle(index);
+            }
+            state.anchor = Some(index);
+        } else {
+            self.select_only(index);
+            state.anchor = Some(index);
+        }
+    }
+
+    /// The next item starting with what the user has typed, if anything.
+    ///
+    /// Typing the same letter repeatedly cycles through the items starting with it.
+    fn typeahead(&self, ui: &Ui, state: &mut State) -> Option<usize> {
+        let typed: String = ui.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect()
+        });
+        let now = ui.input(|i| i.time);
+        if TYPEAHEAD_TIMEOUT < now - state.last_typed_time {
+            state.typeahead.clear();
+        }
+        if typed.is_empty() || (state.typeahead.is_empty() && typed.starts_with(' ')) {
+            // A space on its own is for toggling the selection.
+            return None;
+        }
+        state.last_typed_time = now;
+        state.typeahead.push_str(&typed.to_lowercase());
+
+        let current = state.cursor.unwrap_or(self.num_items - 1);
+        let is_repeated_letter = {
+            let mut chars = state.typeahead.chars();
+            let first = chars.next();
+            chars.all(|c| Some(c) == first)
+        };
+        let (prefix, start) = if is_repeated_letter {
+            let first_len = state.typeahead.chars().next().map_or(0, char::len_utf8);
+            (&state.typeahead[..first_len], current + 1)
+        } else {
+            (state.typeahead.as_str(), current)
+        };
+        (0..self.num_items)
+            .map(|i| (start + i) % self.num_items)
+            .find(|&i| (self.item_text)(i).to_lowercase().starts_with(prefix))
+    }
+
+    pub fn show(mut self, ui: &mut Ui) -> ListBoxResponse {
+        let id = ui.make_persistent_id(self.id_source);
+        let mut state: State = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+        let old_selection = self.selection.clone();
+        if let Some(cursor) = state.cursor {
+            state.cursor = Some(cursor.min(self.num_items.saturating_sub(1)));
+        }
+
+        let row_height = ui.spacing().interact_size.y;
+        let row_stride = row_height + ui.spacing().item_spacing.y;
+        let mut activated = None;
+        let mut scroll_to_cursor = false;
+
+        if ui.memory(|m| m.has_focus(id)) && 0 < self.num_items {
+            ui.memory_mut(|m| {
+                m.set_focus_lock_filter(
+                    id,
+                    EventFilter {
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                );
+            });
+
+            let page = (state.viewport_height / row_stride).floor().at_least(1.0) as isize;
+            let (delta, home, end, space, enter, modifiers) = ui.input(|i| {
+                let delta = i.num_presses(Key::ArrowDown) as isize
+                    - i.num_presses(Key::ArrowUp) as isize
+                    + page
+                        * (i.num_presses(Key::PageDown) as isize
+                            - i.num_presses(Key::PageUp) as isize);
+                (
+                    delta,
+                    i.key_pressed(Key::Home),
+                    i.key_pressed(Key::End),
+                    i.key_pressed(Key::Space),
+                    i.key_pressed(Key::Enter),
+                    i.modifiers,
+                )
+            });
+
+            let last = self.num_items as isize - 1;
+            let mut target = match state.cursor {
+                Some(cursor) if delta != 0 => Some((cursor as isize + delta).clamp(0, last)),
+                None if delta != 0 => Some(0),
+                _ => None,
+            };
+            if home {
+                target = Some(0);
+            }
+            if end {
+                target = Some(last);
+            }
+            if let Some(index) = self.typeahead(ui, &mut state) {
+                target = Some(index as isize);
+            }
+            if let Some(target) = target {
+                self.select(&mut state, target as usize, modifiers, false);
+                scroll_to_cursor = true;
+            }
+
+            if let Some(cursor) = state.cursor {
+                if space {
+                    if self.multi_select && modifiers.command {
+                        self.toggle(cursor);
+                    } else {
+                        self.select_only(cursor);
+                    }
+                    state.anchor = Some(cursor);
+                }
+                if enter {
+                    activated = Some(cursor);
+                }
+            }
+        }
+
+        let mut scroll_area = ScrollArea::vertical()
+            .id_source(id)
+            .auto_shrink([false, true])
+            .max_height(self.max_height);
+        if let (true, Some(cursor)) = (scroll_to_cursor, state.cursor) {
+            let top = cursor as f32 * row_stride;
+            let bottom = top + row_height;
+            if top < state.scroll_offset {
+                scroll_area = scroll_area.vertical_scroll_offset(top);
+            } else if state.scroll_offset + state.viewport_height < bottom {
+                scroll_area = scroll_area.vertical_scroll_offset(bottom - state.viewport_height);
+            }
+        }
+
+        let has_focus = ui.memory(|m| m.has_focus(id));
+        let output = scroll_area.show_rows(ui, row_height, self.num_items, |ui, row_range| {
+            for index in row_range {
+                let selected = self.selection.contains(&index);
+                let text = (self.item_text)(index);
+                let sense = Sense {
+                    click: true,
+                    drag: false,
+                    focusable: false, // The list as a whole is focused
+                };
+                let (rect, row_response) =
+                    ui.allocate_exact_size(vec2(ui.available_width(), row_height), sense);
+                row_response.widget_info(|| {
+                    WidgetInfo::selected(WidgetType::SelectableLabel, selected, &text)
+                });
+
+                if row_response.clicked() {
+                    ui.memory_mut(|m| m.request_focus(id));
+                    let modifiers = ui.input(|i| i.modifiers);
+                    self.select(&mut state, index, modifiers, true);
+                }
+                if row_response.double_clicked() {
+                    activated = Some(index);
+                }
+
+                if ui.is_rect_visible(rect) {
+                    let selected = self.selection.contains(&index);
+                    let visuals = ui.style().interact_selectable(&row_response, selected);
+                    if selected || row_response.hovered() {
+                        ui.painter()
+                            .rect_filled(rect, visuals.rounding, visuals.weak_bg_fill);
+                    }
+                    if has_focus && state.cursor == Some(index) {
+                        ui.painter().rect_stroke(
+                            rect.shrink(1.0),
+                            visuals.rounding,
+                            ui.visuals().selection.stroke,
+                        );
+                    }
+                    ui.painter().text(
+                        rect.left_center() + vec2(ui.spacing().button_padding.x, 0.0),
+                        Align2::LEFT_CENTER,
+                        text,
+                        TextStyle::Button.resolve(ui.style()),
+                        visuals.text_color(),
+                    );
+                }
+            }
+        });
+
+        state.scroll_offset = output.state.offset.y;
+        state.viewport_height = output.inner_rect.height();
+        ui.data_mut(|d| d.insert_temp(id, state));
+
+        let mut response = ui.interact(output.inner_rect, id, Sense::focusable_noninteractive());
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::Other, ""));
+        let stroke = if response.has_focus() {
+            ui.visuals().selection.stroke
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        ui.painter().rect_stroke(
+            output.inner_rect,
+            ui.visuals().widgets.noninteractive.rounding,
+            stroke,
+        );
+
+        if *self.selection != old_selection {
+            response.mark_changed();
+        }
+        ListBoxResponse {
+            response,
+            activated,
+        }
+    }
+}
+
+impl<'a> Widget for ListBox<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_order() {
+        let mut selection = vec![];
+        let mut list = ListBox::new(10, |i| i.to_string(), &mut selection).multi_select(true);
+        let mut state = State::default();
+        let click = |list: &mut ListBox<'_>, state: &mut State, i, modifiers| {
+            list.select(state, i, modifiers, true);
+        };
+
+        click(&mut list, &mut state, 5, Modifiers::NONE);
+        click(&mut list, &mut state, 2, Modifiers::SHIFT);
+        assert_eq!(*list.selection, vec![5, 4, 3, 2]);
+
+        click(&mut list, &mut state, 8, Modifiers::COMMAND);
+        click(&mut list, &mut state, 4, Modifiers::COMMAND);
+        assert_eq!(*list.selection, vec![5, 3, 2, 8]);
+
+        click(&mut list, &mut state, 1, Modifiers::NONE);
+        assert_eq!(*list.selection, vec![1]);
+    }
+}
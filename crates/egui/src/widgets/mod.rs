@@ -6,30 +6,56 @@
 
 use crate::*;
 
+mod breadcrumbs;
 mod button;
+mod chip;
 pub mod color_picker;
 pub(crate) mod drag_value;
 mod hyperlink;
 mod image;
+mod image_viewer;
+mod knob;
 mod label;
+mod list_box;
+mod pagination;
 mod progress_bar;
+mod progress_status;
+mod segmented_control;
 mod selected_label;
 mod separator;
+mod shader_rect;
 mod slider;
 mod spinner;
+mod stepper;
 pub mod text_edit;
+mod toggle_switch;
+mod webview;
+mod xy_pad;
 
+pub use breadcrumbs::Breadcrumbs;
 pub use button::*;
+pub use chip::{Chip, ChipResponse, TagEdit};
 pub use drag_value::DragValue;
 pub use hyperlink::*;
 pub use image::{paint_texture_at, Image, ImageFit, ImageOptions, ImageSize, ImageSource};
+pub use image_viewer::{ImageViewer, ImageViewerFit, ImageViewerResponse};
+pub use knob::{AnglePicker, Knob};
 pub use label::*;
+pub use list_box::{ListBox, ListBoxResponse};
+pub use pagination::Pagination;
 pub use progress_bar::ProgressBar;
+pub use progress_status::ProgressStatus;
+pub use segmented_control::SegmentedControl;
 pub use selected_label::SelectableLabel;
 pub use separator::Separator;
+pub use shader_rect::ShaderRect;
 pub use slider::*;
 pub use spinner::*;
+pub use stepper::Stepper;
 pub use text_edit::{TextBuffer, TextEdit};
+pub use toggle_switch::ToggleSwitch;
+pub use webview::WebView;
+pub use xy_pad::XyPad;
 
 // ----------------------------------------------------------------------------
 
@@ -0,0 +1,163 @@
+use crate::*;
+
+/// The pages to show buttons for, with `None` for an ellipsis.
+///
+/// Shows the first and last page, and `siblings` pages on each side of the current one.
+/// Ellipses only replace two or more pages, since they take as much space as a page.
+fn visible_pages(num_pages: usize, current: usize, siblings: usize) -> Vec<Option<usize>> {
+    // first + last + current + siblings + two ellipses:
+    let max_buttons = 2 * siblings + 5;
+    if num_pages <= max_buttons {
+        return (0..num_pages).map(Some).collect();
+    }
+
+    let last = num_pages - 1;
+    // Keep the number of buttons constant, so the buttons don't move around when paging:
+    let start = current
+        .saturating_sub(siblings)
+        .clamp(2, last - 2 - 2 * siblings);
+    let end = start + 2 * siblings;
+
+    let mut pages = vec![Some(0)];
+    pages.push(if start == 2 { Some(1) } else { None });
+    pages.extend((start..=end).map(Some));
+    pages.push(if end == last - 2 {
+        Some(last - 1)
+    } else {
+        None
+    });
+    pages.push(Some(last));
+    pages
+}
+
+/// Buttons for going to the previous and next page, and to pages by number.
+///
+/// The pages are numbered from 1 for the user, but `current_page` starts at 0.
+/// When there are many pages, only those around the current one are shown,
+/// with "…" for the rest.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut page = 0;
+/// let response = ui.add(egui::Pagination::new(&mut page, 42).jump_to_page(true));
+/// if response.changed() {
+///     // Load the new page…
+/// }
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct Pagination<'a> {
+    current_page: &'a mut usize,
+    num_pages: usize,
+    siblings: usize,
+    jump_to_page: bool,
+}
+
+impl<'a> Pagination<'a> {
+    /// `current_page` is zero-based.
+    pub fn new(current_page: &'a mut usize, num_pages: usize) -> Self {
+        Self {
+            current_page,
+            num_pages,
+            siblings: 1,
+            jump_to_page: false,
+        }
+    }
+
+    /// How many pages to show on each side of the current page. Default: 1.
+    #[inline]
+    pub fn siblings(mut self, siblings: usize) -> Self {
+        self.siblings = siblings;
+        self
+    }
+
+    /// Show a field for entering the page to go to. Default is OFF.
+    #[inline]
+    pub fn jump_to_page(mut self, jump_to_page: bool) -> Self {
+        self.jump_to_page = jump_to_page;
+        self
+    }
+}
+
+impl<'a> Widget for Pagination<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            current_page,
+            num_pages,
+            siblings,
+            jump_to_page,
+        } = self;
+
+        let old_page = *current_page;
+        let last = num_pages.saturating_sub(1);
+        *current_page = (*current_page).min(last);
+
+        let mut response = ui
+            .horizontal(|ui| {
+                let previous = ui
+                    .add_enabled(0 < *current_page, Button::new("⏴"))
+                    .on_hover_text("Previous page");
+                if previous.clicked() {
+                    *current_page -= 1;
+                }
+
+                for page in visible_pages(num_pages, *current_page, siblings) {
+                    match page {
+                        Some(page) => {
+                            let selected = page == *current_page;
+                            if ui
+                                .add(SelectableLabel::new(selected, (page + 1).to_string()))
+                                .clicked()
+                            {
+                                *current_page = page;
+                            }
+                        }
+                        None => {
+                            ui.weak("…");
+                        }
+                    }
+                }
+
+                let next = ui
+                    .add_enabled(*current_page < last, Button::new("⏵"))
+                    .on_hover_text("Next page");
+                if next.clicked() {
+                    *current_page += 1;
+                }
+
+                if jump_to_page && 0 < num_pages {
+                    ui.label("Go to");
+                    let mut page = *current_page + 1;
+                    let response = ui.add(DragValue::new(&mut page).clamp_range(1..=num_pages));
+                    response
+                        .widget_info(|| WidgetInfo::labeled(WidgetType::DragValue, "Go to page"));
+                    *current_page = page - 1;
+                }
+            })
+            .response;
+
+        if *current_page != old_page {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+#[test]
+fn test_visible_pages() {
+    fn pages(num_pages: usize, current: usize) -> String {
+        visible_pages(num_pages, current, 1)
+            .iter()
+            .map(|page| page.map_or("…".to_owned(), |page| (page + 1).to_string()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    assert_eq!(pages(0, 0), "");
+    assert_eq!(pages(5, 2), "1 2 3 4 5");
+    assert_eq!(pages(7, 0), "1 2 3 4 5 6 7");
+    assert_eq!(pages(20, 0), "1 2 3 4 5 … 20");
+    assert_eq!(pages(20, 3), "1 2 3 4 5 … 20");
+    assert_eq!(pages(20, 9), "1 … 9 10 11 … 20");
+    assert_eq!(pages(20, 19), "1 … 16 17 18 19 20");
+}
@@ -0,0 +1,101 @@
+use crate::*;
+
+/// Shows the combined progress of the tasks reported with [`Context::progress`].
+///
+/// Shows a [`ProgressBar`] (or a [`Spinner`] if the progress is indeterminate)
+/// and the text of the oldest task. Hover it to see all the tasks.
+/// Shows nothing when there are no tasks.
+///
+/// This is meant for a status bar at the bottom of a window:
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+///     ui.add(egui::ProgressStatus::new());
+/// });
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+#[derive(Default)]
+pub struct ProgressStatus {
+    viewport_id: Option<ViewportId>,
+    desired_width: Option<f32>,
+}
+
+impl ProgressStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the tasks of this viewport.
+    ///
+    /// Default: the viewport the widget is shown in.
+    #[inline]
+    pub fn viewport(mut self, viewport_id: ViewportId) -> Self {
+        self.viewport_id = Some(viewport_id);
+        self
+    }
+
+    /// The width of the progress bar.
+    ///
+    /// Default: four times [`crate::style::Spacing::interact_size`]`.x`.
+    #[inline]
+    pub fn desired_width(mut self, desired_width: f32) -> Self {
+        self.desired_width = Some(desired_width);
+        self
+    }
+}
+
+impl Widget for ProgressStatus {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let viewport_id = self.viewport_id.unwrap_or_else(|| ui.ctx().viewport_id());
+        let Some(progress) = ui.ctx().aggregated_progress(viewport_id) else {
+            return ui.allocate_response(Vec2::ZERO, Sense::hover());
+        };
+        let desired_width = self
+            .desired_width
+            .unwrap_or(4.0 * ui.spacing().interact_size.x);
+
+        let response = ui
+            .horizontal(|ui| {
+                progress_ui(ui, progress.fraction, desired_width);
+                ui.label(&progress.text);
+                if 1 < progress.num_tasks {
+                    ui.weak(format!("+{} more", progress.num_tasks - 1));
+                }
+            })
+            .response;
+
+        response.widget_info(|| {
+            let mut info = WidgetInfo::labeled(WidgetType::Other, &progress.text);
+            info.value = progress.fraction.map(|fraction| fraction as f64);
+            info
+        });
+
+        response.on_hover_ui(|ui| {
+            let tasks = ui.ctx().progress_tasks();
+            Grid::new("tasks").show(ui, |ui| {
+                for task in tasks.iter().filter(|t| t.viewport_id == viewport_id) {
+                    progress_ui(ui, task.fraction, desired_width);
+                    ui.label(&task.text);
+                    ui.end_row();
+                }
+            });
+        })
+    }
+}
+
+fn progress_ui(ui: &mut Ui, fraction: Option<f32>, desired_width: f32) {
+    match fraction {
+        Some(fraction) => {
+            ui.add(
+                ProgressBar::new(fraction)
+                    .desired_width(desired_width)
+                    .show_percentage(),
+            );
+        }
+        None => {
+            ui.spinner();
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use crate::*;
+
+enum Selection<'a, Value> {
+    Single(&'a mut Value),
+    Multiple(&'a mut Vec<Value>),
+}
+
+/// A row of joined buttons of which one (or several) can be selected.
+///
+/// With [`Self::new`] exactly one segment is selected, like a group of [`RadioButton`]s,
+/// and the selection highlight slides over to the clicked segment.
+/// With [`Self::multiple`] each segment can be toggled on and off, like a group of [`Checkbox`]es.
+///
+/// All segments get the same width.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// #[derive(Clone, PartialEq)]
+/// enum Align { Left, Center, Right }
+/// let mut align = Align::Left;
+///
+/// ui.add(
+///     egui::SegmentedControl::new(&mut align)
+///         .segment(Align::Left, "Left")
+///         .segment(Align::Center, "Center")
+///         .segment(Align::Right, "Right"),
+/// );
+///
+/// let mut styles = vec!["Bold"];
+/// ui.add(
+///     egui::SegmentedControl::multiple(&mut styles)
+///         .segment("Bold", "B")
+///         .segment("Italic", "I")
+///         .segment("Underline", "U"),
+/// );
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct SegmentedControl<'a, Value> {
+    selection: Selection<'a, Value>,
+    segments: Vec<(Value, WidgetText)>,
+    selected_fill: Option<Color32>,
+}
+
+impl<'a, Value: Clone + PartialEq> SegmentedControl<'a, Value> {
+    /// Exactly one segment is selected: the one equal to `*current_value`.
+    pub fn new(current_value: &'a mut Value) -> Self {
+        Self {
+            selection: Selection::Single(current_value),
+            segments: Default::default(),
+            selected_fill: None,
+        }
+    }
+
+    /// Any number of segments can be selected: those contained in `selected_values`.
+    ///
+    /// Clicking a segment adds its value to, or removes it from, `selected_values`.
+    pub fn multiple(selected_values: &'a mut Vec<Value>) -> Self {
+        Self {
+            selection: Selection::Multiple(selected_values),
+            segments: Default::default(),
+            selected_fill: None,
+        }
+    }
+
+    /// Add a segment, which selects `value` when clicked.
+    #[inline]
+    pub fn segment(mut self, value: Value, text: impl Into<WidgetText>) -> Self {
+        self.segments.push((value, text.into()));
+        self
+    }
+
+    /// The background of the selected segments.
+    ///
+    /// Default: [`crate::style::Selection::bg_fill`].
+    #[inline]
+    pub fn selected_fill(mut self, selected_fill: Color32) -> Self {
+        self.selected_fill = Some(selected_fill);
+        self
+    }
+
+    fn is_selected(&self, value: &Value) -> bool {
+        match &self.selection {
+            Selection::Single(current) => *current == value,
+            Selection::Multiple(selected) => selected.contains(value),
+        }
+    }
+}
+
+impl<'a, Value: Clone + PartialEq> Widget for SegmentedControl<'a, Value> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let button_padding = ui.spacing().button_padding;
+        let segments = std::mem::take(&mut self.segments);
+        let galleys: Vec<_> = segments
+            .into_iter()
+            .map(|(value, text)| {
+                let galley = text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button);
+                (value, galley)
+            })
+            .collect();
+
+        let max_text_size = galleys
+            .iter()
+            .fold(Vec2::ZERO, |size, (_, galley)| size.max(galley.size()));
+        let segment_size = (max_text_size + 2.0 * button_padding)
+            .at_least(Vec2::splat(ui.spacing().interact_size.y));
+        let desired_size = vec2(segment_size.x * galleys.len() as f32, segment_size.y);
+        let (rect, mut response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        let segment_rect = |index: f32| {
+            Rect::from_min_size(rect.min + vec2(index * segment_size.x, 0.0), segment_size)
+        };
+
+        let mut segment_responses = Vec::with_capacity(galleys.len());
+        for (i, (value, galley)) in galleys.iter().enumerate() {
+            let mut segment_response =
+                ui.interact(segment_rect(i as f32), response.id.with(i), Sense::click());
+            if segment_response.clicked() {
+                match &mut self.selection {
+                    Selection::Single(current) => {
+                        if *current != value {
+                            **current = value.clone();
+                            segment_response.mark_changed();
+                        }
+                    }
+                    Selection::Multiple(selected) => {
+                        if selected.contains(value) {
+                            selected.retain(|v| v != value);
+                        } else {
+                            selected.push(value.clone());
+                        }
+                        segment_response.mark_changed();
+                    }
+                }
+            }
+            let selected = self.is_selected(value);
+            segment_response.widget_info(|| {
+                WidgetInfo::selected(WidgetType::SelectableLabel, selected, galley.text())
+            });
+            segment_responses.push(segment_response);
+        }
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter();
+            let widgets = &ui.visuals().widgets;
+            let rounding = widgets.inactive.rounding;
+            let selected_fill = self.selected_fill.unwrap_or(ui.visuals().selection.bg_fill);
+            painter.rect(
+                rect,
+                rounding,
+                widgets.inactive.weak_bg_fill,
+                widgets.inactive.bg_stroke,
+            );
+
+            for i in 1..galleys.len() {
+                painter.vline(
+                    segment_rect(i as f32).left(),
+                    rect.y_range().shrink(button_padding.y),
+                    widgets.noninteractive.bg_stroke,
+                );
+            }
+
+            for (i, segment_response) in segment_responses.iter().enumerate() {
+                if segment_response.hovered() || segment_response.has_focus() {
+                    let visuals = ui.style().interact(segment_response);
+                    painter.rect(
+                        segment_rect(i as f32),
+                        rounding,
+                        visuals.weak_bg_fill,
+                        visuals.bg_stroke,
+                    );
+                }
+            }
+
+            match &self.selection {
+                Selection::Single(current) => {
+                    if let Some(index) = galleys.iter().position(|(value, _)| value == *current) {
+                        let index = ui.ctx().animate_value_with_time(
+                            response.id,
+                            index as f32,
+                            ui.style().animation_time,
+                        );
+                        painter.rect_filled(segment_rect(index), rounding, selected_fill);
+                    }
+                }
+                Selection::Multiple(_) => {
+                    for (i, (value, _)) in galleys.iter().enumerate() {
+                        let how_on = ui
+                            .ctx()
+                            .animate_bool(response.id.with(i), self.is_selected(value));
+                        if how_on > 0.0 {
+                            painter.rect_filled(
+                                segment_rect(i as f32),
+                                rounding,
+                                selected_fill.gamma_multiply(how_on),
+                            );
+                        }
+                    }
+                }
+            }
+
+            for (i, ((value, galley), segment_response)) in
+                galleys.into_iter().zip(&segment_responses).enumerate()
+            {
+                let text_color = if self.is_selected(&value) {
+                    ui.visuals().selection.stroke.color
+                } else {
+                    ui.style().interact(segment_response).text_color()
+                };
+                let text_pos = segment_rect(i as f32).center() - 0.5 * galley.size();
+                painter.galley(text_pos, galley, text_color);
+
+                if segment_response.has_focus() {
+                    painter.rect_stroke(
+                        segment_rect(i as f32),
+                        rounding,
+                        ui.visuals().selection.stroke,
+                    );
+                }
+            }
+        }
+
+        for segment_response in segment_responses {
+            response |= segment_response;
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{click, run_in_panel};
+
+    /// The centers of the `n` segments of a control in this rectangle.
+    fn segment_centers(rect: Rect, n: usize) -> Vec<Pos2> {
+        let width = rect.width() / n as f32;
+        (0..n)
+            .map(|i| pos2(rect.left() + (i as f32 + 0.5) * width, rect.center().y))
+            .collect()
+    }
+
+    #[test]
+    fn segmented_control_exclusive() {
+        fn show(ui: &mut Ui, current: &mut i32) -> Response {
+            ui.add(
+                SegmentedControl::new(current)
+                    .segment(0, "A")
+                    .segment(1, "B")
+                    .segment(2, "C"),
+            )
+        }
+
+        let ctx = Context::default();
+        let mut current = 0;
+        let rect = run_in_panel(&ctx, RawInputBuilder::new(), |ui| show(ui, &mut current)).rect;
+        let centers = segment_centers(rect, 3);
+
+        click(centers[2], |input| {
+            run_in_panel(&ctx, input, |ui| show(ui, &mut current));
+        });
+        assert_eq!(current, 2);
+
+        // Clicking the selected segment keeps it selected:
+        click(centers[2], |input| {
+            run_in_panel(&ctx, input, |ui| show(ui, &mut current));
+        });
+        assert_eq!(current, 2);
+
+        click(centers[0], |input| {
+            run_in_panel(&ctx, input, |ui| show(ui, &mut current));
+        });
+        assert_eq!(current, 0);
+    }
+
+    #[test]
+    fn segmented_control_multiple() {
+        fn show(ui: &mut Ui, selected: &mut Vec<&'static str>) -> Response {
+            ui.add(
+                SegmentedControl::multiple(selected)
+                    .segment("Bold", "B")
+                    .segment("Italic", "I")
+                    .segment("Underline", "U"),
+            )
+        }
+
+        let ctx = Context::default();
+        let mut selected = vec!["Bold"];
+        let rect = run_in_panel(&ctx, RawInputBuilder::new(), |ui| show(ui, &mut selected)).rect;
+        let centers = segment_centers(rect, 3);
+
+        click(centers[2], |input| {
+            run_in_panel(&ctx, input, |ui| show(ui, &mut selected));
+        });
+        assert_eq!(selected, vec!["Bold", "Underline"]);
+
+        // Clicking a selected segment deselects it:
+        click(centers[0], |input| {
+            run_in_panel(&ctx, input, |ui| show(ui, &mut selected));
+        });
+        assert_eq!(selected, vec!["Underline"]);
+
+        click(centers[2], |input| {
+            run_in_panel(&ctx, input, |ui| show(ui, &mut selected));
+        });
+        assert!(selected.is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use epaint::{CustomShaderCallback, ShaderCode, MAX_SHADER_PARAMS};
+
+use crate::{Rect, Response, Sense, Ui, Vec2, Widget};
+
+/// A rectangle painted with your own fragment shader, e.g. a gradient, a plot or a visualizer.
+///
+/// The shader is given as [`ShaderCode`], with WGSL for `egui-wgpu` and optionally GLSL for `egui_glow`.
+/// Pass values to it with [`Self::param`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let time = 0.0;
+/// use std::sync::Arc;
+///
+/// // Create the code once and keep it around:
+/// let code = Arc::new(
+///     egui::epaint::ShaderCode::new(
+///         "fn shade(uv: vec2<f32>) -> vec4<f32> {
+///             return vec4<f32>(uv, 0.5 + 0.5 * sin(shader_rect.params[0].x), 1.0);
+///         }",
+///     )
+///     .with_glsl(
+///         "vec4 shade(vec2 uv) {
+///             return vec4(uv, 0.5 + 0.5 * sin(u_params[0].x), 1.0);
+///         }",
+///     ),
+/// );
+///
+/// ui.add(egui::ShaderRect::new(code, egui::vec2(200.0, 100.0)).param([time, 0.0, 0.0, 0.0]));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct ShaderRect {
+    code: Arc<ShaderCode>,
+    size: Vec2,
+    params: Vec<[f32; 4]>,
+    sense: Sense,
+}
+
+impl ShaderRect {
+    pub fn new(code: Arc<ShaderCode>, size: impl Into<Vec2>) -> Self {
+        Self {
+            code,
+            size: size.into(),
+            params: Vec::new(),
+            sense: Sense::hover(),
+        }
+    }
+
+    /// Add a parameter, available to the shader as `shader_rect.params[i]` (WGSL) or `u_params[i]` (GLSL).
+    ///
+    /// There can be at most [`MAX_SHADER_PARAMS`].
+    #[inline]
+    pub fn param(mut self, param: [f32; 4]) -> Self {
+        self.params.push(param);
+        self
+    }
+
+    /// Set all parameters at once, see [`Self::param`].
+    #[inline]
+    pub fn params(mut self, params: impl Into<Vec<[f32; 4]>>) -> Self {
+        self.params = params.into();
+        self
+    }
+
+    /// By default the rectangle only senses hovering.
+    #[inline]
+    pub fn sense(mut self, sense: Sense) -> Self {
+        self.sense = sense;
+        self
+    }
+
+    /// Paint the shader in the given rectangle.
+    pub fn paint_at(self, ui: &Ui, rect: Rect) {
+        crate::egui_assert!(
+            self.params.len() <= MAX_SHADER_PARAMS,
+            "A ShaderRect can have at most {MAX_SHADER_PARAMS} params"
+        );
+        if ui.is_rect_visible(rect) {
+            let callback = CustomShaderCallback {
+                code: self.code,
+                params: self.params,
+            };
+            ui.painter().add(callback.paint_callback(rect));
+        }
+    }
+}
+
+impl Widget for ShaderRect {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(self.size, self.sense);
+        self.paint_at(ui, rect);
+        response
+    }
+}
@@ -8,6 +8,16 @@ use crate::{style::HandleShape, *};
 
 type NumFormatter<'a> = Box<dyn 'a + Fn(f64, RangeInclusive<usize>) -> String>;
 type NumParser<'a> = Box<dyn 'a + Fn(&str) -> Option<f64>>;
+type TrailingFillPainter<'a> = Box<dyn 'a + Fn(&Painter, Rect, Rect)>;
+
+/// Ticks closer than this many points to each other are skipped.
+const MIN_TICK_SPACING: f32 = 6.0;
+
+/// Horizontal tick labels closer than this many points to each other are skipped.
+const MIN_TICK_LABEL_SPACING: f32 = 40.0;
+
+/// When dragging this many points or closer to a tick, the slider snaps to it.
+const TICK_DETENT_RADIUS: f32 = 4.0;
 
 // ----------------------------------------------------------------------------
 
@@ -45,6 +55,13 @@ pub enum SliderOrientation {
     Vertical,
 }
 
+enum SliderTicks {
+    /// Nice round values, or powers of ten for logarithmic sliders.
+    Auto,
+
+    Values(Vec<f64>),
+}
+
 /// Control a number with a slider.
 ///
 /// The slider range defines the values you get when pulling the slider to the far edges.
@@ -65,6 +82,23 @@ pub enum SliderOrientation {
 /// ```
 ///
 /// The default [`Slider`] size is set by [`crate::style::Spacing::slider_width`].
+///
+/// When focused, the arrow keys move the slider a little,
+/// Page Up/Page Down move it by [`Slider::page_step`] and Home/End move it to the ends of the range.
+///
+/// The slider can show tick marks, which the value snaps to when dragging close to them:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut volume: f32 = 0.0;
+/// ui.add(
+///     egui::Slider::new(&mut volume, 0.0..=100.0)
+///         .auto_ticks()
+///         .tick_labels(true)
+///         .text("Volume"),
+/// );
+/// # });
+/// ```
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct Slider<'a> {
     get_set_value: GetSetValue<'a>,
@@ -87,7 +121,13 @@ pub struct Slider<'a> {
     custom_formatter: Option<NumFormatter<'a>>,
     custom_parser: Option<NumParser<'a>>,
     trailing_fill: Option<bool>,
+    trailing_fill_painter: Option<TrailingFillPainter<'a>>,
     handle_shape: Option<HandleShape>,
+    ticks: Option<SliderTicks>,
+    tick_labels: bool,
+    snap_to_ticks: bool,
+    page_step: Option<f64>,
+    show_value_tooltip: bool,
 }
 
 impl<'a> Slider<'a> {
@@ -134,7 +174,13 @@ impl<'a> Slider<'a> {
             custom_formatter: None,
             custom_parser: None,
             trailing_fill: None,
+            trailing_fill_painter: None,
             handle_shape: None,
+            ticks: None,
+            tick_labels: false,
+            snap_to_ticks: false,
+            page_step: None,
+            show_value_tooltip: false,
         }
     }
 
@@ -304,6 +350,100 @@ impl<'a> Slider<'a> {
         self
     }
 
+    /// Paint the trailing fill yourself, e.g. with a gradient.
+    ///
+    /// The function is called with the painter, the part of the rail that should be filled,
+    /// and the whole rail. This turns on [`Self::trailing_fill`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut value: f32 = 0.0;
+    /// use egui::{epaint::Vertex, Color32, Mesh, Shape};
+    ///
+    /// ui.add(
+    ///     egui::Slider::new(&mut value, 0.0..=1.0).custom_trailing_fill(|painter, fill_rect, rail_rect| {
+    ///         // From green at the start of the rail to red at the end of it:
+    ///         let color_at = |x: f32| {
+    ///             let t = egui::remap(x, rail_rect.x_range(), 0.0..=1.0);
+    ///             egui::lerp(egui::Rgba::from(Color32::GREEN)..=egui::Rgba::from(Color32::RED), t).into()
+    ///         };
+    ///         let mut mesh = Mesh::default();
+    ///         for pos in [fill_rect.left_top(), fill_rect.right_top(), fill_rect.right_bottom(), fill_rect.left_bottom()] {
+    ///             mesh.colored_vertex(pos, color_at(pos.x));
+    ///         }
+    ///         mesh.add_triangle(0, 1, 2);
+    ///         mesh.add_triangle(0, 2, 3);
+    ///         painter.add(Shape::mesh(mesh));
+    ///     }),
+    /// );
+    /// # });
+    /// ```
+    #[inline]
+    pub fn custom_trailing_fill(mut self, painter: impl 'a + Fn(&Painter, Rect, Rect)) -> Self {
+        self.trailing_fill = Some(true);
+        self.trailing_fill_painter = Some(Box::new(painter));
+        self
+    }
+
+    /// Show tick marks at nice round values, or at the powers of ten for a [`Self::logarithmic`] slider.
+    ///
+    /// If the slider has a [`Self::step_by`], the ticks are on multiples of it.
+    /// Ticks that would be too close to each other are skipped.
+    #[inline]
+    pub fn auto_ticks(mut self) -> Self {
+        self.ticks = Some(SliderTicks::Auto);
+        self
+    }
+
+    /// Show tick marks at these values.
+    ///
+    /// Dragging the slider close to a tick snaps it to the tick,
+    /// and with [`Self::snap_to_ticks`] the value is always one of the ticks.
+    #[inline]
+    pub fn ticks(mut self, ticks: impl IntoIterator<Item = f64>) -> Self {
+        self.ticks = Some(SliderTicks::Values(ticks.into_iter().collect()));
+        self
+    }
+
+    /// Show the values of the ticks next to them. Default is OFF.
+    ///
+    /// The values are formatted like the slider value, e.g. with [`Self::custom_formatter`].
+    #[inline]
+    pub fn tick_labels(mut self, tick_labels: bool) -> Self {
+        self.tick_labels = tick_labels;
+        self
+    }
+
+    /// Only allow the values of the ticks, see [`Self::ticks`] and [`Self::auto_ticks`]. Default is OFF.
+    ///
+    /// The arrow keys then move the slider to the next tick.
+    /// Values entered in the value field are not snapped.
+    #[inline]
+    pub fn snap_to_ticks(mut self, snap_to_ticks: bool) -> Self {
+        self.snap_to_ticks = snap_to_ticks;
+        self
+    }
+
+    /// How much Page Up and Page Down change the value.
+    ///
+    /// Default: ten times [`Self::step_by`] if set, else a tenth of the slider.
+    #[inline]
+    pub fn page_step(mut self, page_step: f64) -> Self {
+        self.page_step = Some(page_step);
+        self
+    }
+
+    /// Show the value in a tooltip next to the handle when dragging it. Default is OFF.
+    ///
+    /// This is useful together with `.show_value(false)`.
+    /// The tooltip is shown below horizontal sliders and to the right of vertical ones,
+    /// so it does not cover the slider.
+    #[inline]
+    pub fn show_value_tooltip(mut self, show_value_tooltip: bool) -> Self {
+        self.show_value_tooltip = show_value_tooltip;
+        self
+    }
+
     /// Change the shape of the slider handle
     ///
     /// This setting can be enabled globally for all sliders with [`Visuals::handle_shape`].
@@ -319,6 +459,8 @@ impl<'a> Slider<'a> {
     /// A custom formatter takes a `f64` for the numeric value and a `RangeInclusive<usize>` representing
     /// the decimal range i.e. minimum and maximum number of decimal places shown.
     ///
+    /// The default is [`crate::Style::number_formatter`].
+    ///
     /// See also: [`DragValue::custom_parser`]
     ///
     /// ```
@@ -362,6 +504,8 @@ impl<'a> Slider<'a> {
     /// A custom parser takes an `&str` to parse into a number and returns `Some` if it was successfully parsed
     /// or `None` otherwise.
     ///
+    /// The default is [`crate::Style::number_formatter`].
+    ///
     /// See also: [`DragValue::custom_formatter`]
     ///
     /// ```
@@ -567,27 +711,176 @@ impl<'a> Slider<'a> {
         let normalized = normalized_from_value(value, self.range(), &self.spec);
         lerp(position_range, normalized as f32)
     }
+
+    /// The ticks to show, in increasing order, skipping those closer than `min_spacing` points to the previous one.
+    fn tick_values(&self, position_range: Rangef, min_spacing: f32) -> Vec<f64> {
+        let mut candidates = match &self.ticks {
+            None => return vec![],
+            Some(SliderTicks::Auto) => self.auto_tick_values(position_range, min_spacing),
+            Some(SliderTicks::Values(values)) => values.clone(),
+        };
+        candidates.sort_by(f64::total_cmp);
+
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let (min, max) = (start.min(end), start.max(end));
+        let mut ticks = vec![];
+        let mut last_position = None;
+        for value in candidates {
+            if value < min || max < value {
+                continue;
+            }
+            let position = self.position_from_value(value, position_range);
+            // Allow for round-off, so ticks exactly `min_spacing` apart are all kept:
+            let far_enough = |last: f32| min_spacing <= (position - last).abs() + 1e-3;
+            if last_position.map_or(true, far_enough) {
+                ticks.push(value);
+                last_position = Some(position);
+            }
+        }
+        ticks
+    }
+
+    fn auto_tick_values(&self, position_range: Rangef, min_spacing: f32) -> Vec<f64> {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let (min, max) = (start.min(end), start.max(end));
+        if min.is_nan() || max.is_nan() || max <= min {
+            return vec![];
+        }
+
+        if self.spec.logarithmic {
+            let mut ticks = vec![];
+            let mut add_powers_of_ten = |low: f64, high: f64, sign: f64| {
+                if low < high {
+                    let (low_log, high_log) = range_log10(low, high, &self.spec);
+                    // When the range starts at zero, its smallest power of ten is where zero is shown:
+                    let first = if low == 0.0 {
+                        low_log.floor() as i32 + 1
+                    } else {
+                        low_log.ceil() as i32
+                    };
+                    for exponent in first..=(high_log.floor() as i32) {
+                        ticks.push(sign * 10.0_f64.powi(exponent));
+                    }
+                }
+            };
+            add_powers_of_ten(min.max(0.0), max, 1.0);
+            add_powers_of_ten((-max).max(0.0), -min, -1.0);
+            if min <= 0.0 && 0.0 <= max {
+                ticks.push(0.0);
+            }
+            ticks
+        } else if !(max - min).is_finite() {
+            vec![]
+        } else {
+            let max_num_ticks = (position_range.span().abs() / min_spacing).floor().max(1.0);
+            let mut spacing = nice_tick_spacing((max - min) / max_num_ticks as f64);
+            let mut origin = 0.0;
+            if let Some(step) = self.step {
+                spacing = (spacing / step).ceil().max(1.0) * step;
+                origin = start;
+            }
+            let first = ((min - origin) / spacing).ceil() as i64;
+            let last = ((max - origin) / spacing).floor() as i64;
+            (first..=last)
+                .map(|i| origin + i as f64 * spacing)
+                .collect()
+        }
+    }
+
+    /// Snap to the closest tick if it is within [`TICK_DETENT_RADIUS`], or always with [`Self::snap_to_ticks`].
+    fn snap_to_tick(&self, value: f64, ticks: &[f64], position_range: Rangef) -> f64 {
+        let position = self.position_from_value(value, position_range);
+        let closest = ticks
+            .iter()
+            .map(|&tick| {
+                let distance = (self.position_from_value(tick, position_range) - position).abs();
+                (tick, distance)
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        match closest {
+            Some((tick, distance)) if self.snap_to_ticks || distance <= TICK_DETENT_RADIUS => tick,
+            _ => value,
+        }
+    }
+
+    /// The closest tick in the direction (sign) of `direction`, in ui points.
+    fn next_tick(
+        &self,
+        value: f64,
+        direction: f32,
+        ticks: &[f64],
+        position_range: Rangef,
+    ) -> Option<f64> {
+        let position = self.position_from_value(value, position_range);
+        ticks
+            .iter()
+            .map(|&tick| {
+                (
+                    tick,
+                    self.position_from_value(tick, position_range) - position,
+                )
+            })
+            .filter(|&(_, delta)| 0.0 < delta * direction)
+            .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+            .map(|(tick, _)| tick)
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        let max_decimals = self.max_decimals.unwrap_or(6).at_least(self.min_decimals);
+        let decimals = self.min_decimals..=max_decimals;
+        match &self.custom_formatter {
+            Some(custom_formatter) => custom_formatter(value, decimals),
+            None => emath::format_with_decimals_in_range(value, decimals),
+        }
+    }
+}
+
+/// The smallest of 1, 2 or 5 times a power of ten that is at least `min_spacing`.
+fn nice_tick_spacing(min_spacing: f64) -> f64 {
+    let magnitude = 10.0_f64.powf(min_spacing.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|&spacing| min_spacing <= spacing)
+        .unwrap_or(10.0 * magnitude)
 }
 
 impl<'a> Slider<'a> {
     /// Just the slider, no text
-    fn allocate_slider_space(&self, ui: &mut Ui, thickness: f32) -> Response {
-        let desired_size = match self.orientation {
+    fn slider_size(&self, ui: &Ui, thickness: f32) -> Vec2 {
+        match self.orientation {
             SliderOrientation::Horizontal => vec2(ui.spacing().slider_width, thickness),
             SliderOrientation::Vertical => vec2(thickness, ui.spacing().slider_width),
+        }
+    }
+
+    /// Just the slider and its tick labels, no text
+    fn allocate_slider_space(
+        &self,
+        ui: &mut Ui,
+        thickness: f32,
+        tick_label_size: Vec2,
+    ) -> Response {
+        let desired_size = match self.orientation {
+            SliderOrientation::Horizontal => {
+                self.slider_size(ui, thickness) + vec2(0.0, tick_label_size.y)
+            }
+            SliderOrientation::Vertical => {
+                self.slider_size(ui, thickness) + vec2(tick_label_size.x, 0.0)
+            }
         };
         ui.allocate_response(desired_size, Sense::drag())
     }
 
     /// Just the slider, no text
-    fn slider_ui(&mut self, ui: &Ui, response: &Response) {
-        let rect = &response.rect;
+    fn slider_ui(&mut self, ui: &Ui, response: &Response, rect: &Rect, ticks: &[f64]) {
         let handle_shape = self
             .handle_shape
             .unwrap_or_else(|| ui.style().visuals.handle_shape);
         let position_range = self.position_range(rect, &handle_shape);
 
         if let Some(pointer_position_2d) = response.interact_pointer_pos() {
+            let prev_value = self.get_value();
             let position = self.pointer_position(pointer_position_2d);
             let new_value = if self.smart_aim {
                 let aim_radius = ui.input(|i| i.aim_radius());
@@ -598,11 +891,16 @@ impl<'a> Slider<'a> {
             } else {
                 self.value_from_position(position, position_range)
             };
+            let new_value = self.snap_to_tick(new_value, ticks, position_range);
             self.set_value(new_value);
+            if (self.step.is_some() || !ticks.is_empty()) && self.get_value() != prev_value {
+                ui.ctx().haptic(HapticKind::Selection);
+            }
         }
 
         let mut decrement = 0usize;
         let mut increment = 0usize;
+        let mut pages = 0i32;
 
         if response.has_focus() {
             ui.ctx().memory_mut(|m| {
@@ -631,7 +929,16 @@ impl<'a> Slider<'a> {
             ui.input(|input| {
                 decrement += input.num_presses(dec_key);
                 increment += input.num_presses(inc_key);
+                pages += input.num_presses(Key::PageUp) as i32;
+                pages -= input.num_presses(Key::PageDown) as i32;
             });
+
+            if ui.input(|i| i.key_pressed(Key::Home)) {
+                self.set_value(*self.range.start());
+            }
+            if ui.input(|i| i.key_pressed(Key::End)) {
+                self.set_value(*self.range.end());
+            }
         }
 
         #[cfg(feature = "accesskit")]
@@ -651,6 +958,15 @@ impl<'a> Slider<'a> {
             let prev_position = self.position_from_value(prev_value, position_range);
             let new_position = prev_position + ui_point_per_step * kb_step;
             let new_value = match self.step {
+                _ if self.snap_to_ticks && !ticks.is_empty() => {
+                    let mut value = prev_value;
+                    for _ in 0..(kb_step.abs() as usize) {
+                        value = self
+                            .next_tick(value, kb_step, ticks, position_range)
+                            .unwrap_or(value);
+                    }
+                    value
+                }
                 Some(step) => prev_value + (kb_step as f64 * step),
                 None if self.smart_aim => {
                     let aim_radius = 0.49 * ui_point_per_step; // Chosen so we don't include `prev_value` in the search.
@@ -664,6 +980,23 @@ impl<'a> Slider<'a> {
             self.set_value(new_value);
         }
 
+        if pages != 0 {
+            let prev_value = self.get_value();
+            let page_step = self.page_step.or_else(|| self.step.map(|step| 10.0 * step));
+            let new_value = if let Some(page_step) = page_step {
+                prev_value + pages as f64 * page_step
+            } else {
+                let normalized = normalized_from_value(prev_value, self.range(), &self.spec);
+                value_from_normalized(normalized + 0.1 * pages as f64, self.range(), &self.spec)
+            };
+            let new_value = if self.snap_to_ticks {
+                self.snap_to_tick(new_value, ticks, position_range)
+            } else {
+                new_value
+            };
+            self.set_value(new_value);
+        }
+
         #[cfg(feature = "accesskit")]
         {
             use accesskit::{Action, ActionData};
@@ -692,6 +1025,22 @@ impl<'a> Slider<'a> {
                 widget_visuals.inactive.bg_fill,
             );
 
+            let tick_stroke = Stroke::new(1.0, ui.visuals().weak_text_color());
+            for &tick in ticks {
+                let position = self.position_from_value(tick, position_range);
+                let points = match self.orientation {
+                    SliderOrientation::Horizontal => [
+                        pos2(position, rail_rect.bottom() + 1.0),
+                        pos2(position, rect.bottom()),
+                    ],
+                    SliderOrientation::Vertical => [
+                        pos2(rail_rect.right() + 1.0, position),
+                        pos2(rect.right(), position),
+                    ],
+                };
+                ui.painter().line_segment(points, tick_stroke);
+            }
+
             let position_1d = self.position_from_value(value, position_range);
             let center = self.marker_center(position_1d, &rail_rect);
 
@@ -710,11 +1059,15 @@ impl<'a> Slider<'a> {
                     SliderOrientation::Horizontal => trailing_rail_rect.max.x = center.x,
                 };
 
-                ui.painter().rect_filled(
-                    trailing_rail_rect,
-                    widget_visuals.inactive.rounding,
-                    ui.visuals().selection.bg_fill,
-                );
+                if let Some(trailing_fill_painter) = &self.trailing_fill_painter {
+                    trailing_fill_painter(ui.painter(), trailing_rail_rect, rail_rect);
+                } else {
+                    ui.painter().rect_filled(
+                        trailing_rail_rect,
+                        widget_visuals.inactive.rounding,
+                        ui.visuals().selection.bg_fill,
+                    );
+                }
             }
 
             let radius = self.handle_radius(rect);
@@ -864,8 +1217,86 @@ impl<'a> Slider<'a> {
         let thickness = ui
             .text_style_height(&TextStyle::Body)
             .at_least(ui.spacing().interact_size.y);
-        let mut response = self.allocate_slider_space(ui, thickness);
-        self.slider_ui(ui, &response);
+        let handle_shape = self
+            .handle_shape
+            .unwrap_or_else(|| ui.style().visuals.handle_shape);
+
+        // The ticks only depend on the size of the slider, so we can find them before allocating it:
+        let tick_spacing = match self.orientation {
+            SliderOrientation::Horizontal if self.tick_labels => MIN_TICK_LABEL_SPACING,
+            SliderOrientation::Vertical if self.tick_labels => {
+                ui.text_style_height(&TextStyle::Small) + ui.spacing().item_spacing.y
+            }
+            _ => MIN_TICK_SPACING,
+        };
+        let ticks = self.tick_values(
+            self.position_range(
+                &Rect::from_min_size(Pos2::ZERO, self.slider_size(ui, thickness)),
+                &handle_shape,
+            ),
+            tick_spacing,
+        );
+        let tick_labels: Vec<_> = if self.tick_labels {
+            let font_id = TextStyle::Small.resolve(ui.style());
+            ticks
+                .iter()
+                .map(|&tick| {
+                    let galley = ui.painter().layout_no_wrap(
+                        self.format_value(tick),
+                        font_id.clone(),
+                        ui.visuals().weak_text_color(),
+                    );
+                    (tick, galley)
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        let tick_label_size = tick_labels
+            .iter()
+            .fold(Vec2::ZERO, |size, (_, galley)| size.max(galley.size()));
+
+        let mut response = self.allocate_slider_space(ui, thickness, tick_label_size);
+        let mut slider_rect = response.rect;
+        match self.orientation {
+            SliderOrientation::Horizontal => slider_rect.max.y -= tick_label_size.y,
+            SliderOrientation::Vertical => slider_rect.max.x -= tick_label_size.x,
+        }
+        self.slider_ui(ui, &response, &slider_rect, &ticks);
+
+        let position_range = self.position_range(&slider_rect, &handle_shape);
+        if ui.is_rect_visible(response.rect) {
+            for (tick, galley) in tick_labels {
+                let position = self.position_from_value(tick, position_range);
+                let rect = match self.orientation {
+                    SliderOrientation::Horizontal => Align2::CENTER_TOP.anchor_rect(
+                        Rect::from_min_size(pos2(position, slider_rect.bottom()), galley.size()),
+                    ),
+                    SliderOrientation::Vertical => Align2::LEFT_CENTER.anchor_rect(
+                        Rect::from_min_size(pos2(slider_rect.right(), position), galley.size()),
+                    ),
+                };
+                ui.painter()
+                    .galley(rect.min, galley, ui.visuals().weak_text_color());
+            }
+        }
+
+        if self.show_value_tooltip && response.dragged() {
+            let value = self.get_value();
+            let position = self.position_from_value(value, position_range);
+            let spacing = ui.spacing().item_spacing;
+            let tooltip_pos = match self.orientation {
+                SliderOrientation::Horizontal => pos2(position, response.rect.bottom() + spacing.y),
+                SliderOrientation::Vertical => pos2(response.rect.right() + spacing.x, position),
+            };
+            let text = format!("{}{}{}", self.prefix, self.format_value(value), self.suffix);
+            show_tooltip_at(
+                ui.ctx(),
+                response.id.with("value_tooltip"),
+                Some(tooltip_pos),
+                |ui| ui.label(text),
+            );
+        }
 
         let value = self.get_value();
         response.changed = value != old_value;
@@ -892,10 +1323,6 @@ impl<'a> Slider<'a> {
         let slider_response = response.clone();
 
         let value_response = if self.show_value {
-            let handle_shape = self
-                .handle_shape
-                .unwrap_or_else(|| ui.style().visuals.handle_shape);
-            let position_range = self.position_range(&response.rect, &handle_shape);
             let value_response = self.value_ui(ui, position_range);
             if value_response.gained_focus()
                 || value_response.has_focus()
@@ -1093,3 +1520,112 @@ fn logarithmic_zero_cutoff(min: f64, max: f64) -> f64 {
     crate::egui_assert!(0.0 <= cutoff && cutoff <= 1.0);
     cutoff
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_approx_eq(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() <= 1e-9 * e.abs().max(1.0),
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_nice_tick_spacing() {
+        assert_approx_eq(&[nice_tick_spacing(1.0)], &[1.0]);
+        assert_approx_eq(&[nice_tick_spacing(0.7)], &[1.0]);
+        assert_approx_eq(&[nice_tick_spacing(1.3)], &[2.0]);
+        assert_approx_eq(&[nice_tick_spacing(3.0)], &[5.0]);
+        assert_approx_eq(&[nice_tick_spacing(7.0)], &[10.0]);
+        assert_approx_eq(&[nice_tick_spacing(12.0)], &[20.0]);
+        assert_approx_eq(&[nice_tick_spacing(450.0)], &[500.0]);
+        assert_approx_eq(&[nice_tick_spacing(0.03)], &[0.05]);
+    }
+
+    #[test]
+    fn test_auto_ticks_linear() {
+        let mut value = 0.0;
+        let slider = Slider::new(&mut value, 0.0..=100.0).auto_ticks();
+        let position_range = Rangef::new(0.0, 100.0);
+
+        // At most one tick per 20 points, at nice round values:
+        assert_approx_eq(
+            &slider.tick_values(position_range, 20.0),
+            &[0.0, 20.0, 40.0, 60.0, 80.0, 100.0],
+        );
+        assert_approx_eq(
+            &slider.tick_values(position_range, 30.0),
+            &[0.0, 50.0, 100.0],
+        );
+    }
+
+    #[test]
+    fn test_auto_ticks_stepped() {
+        let position_range = Rangef::new(0.0, 100.0);
+
+        // Multiples of the step, counted from the start of the range:
+        let mut value = 0.0;
+        let slider = Slider::new(&mut value, 1.0..=100.0)
+            .step_by(3.0)
+            .auto_ticks();
+        assert_approx_eq(
+            &slider.tick_values(position_range, 20.0),
+            &[1.0, 22.0, 43.0, 64.0, 85.0],
+        );
+    }
+
+    #[test]
+    fn test_auto_ticks_logarithmic() {
+        let position_range = Rangef::new(0.0, 100.0);
+
+        let mut value = 1.0;
+        let slider = Slider::new(&mut value, 0.1..=1000.0)
+            .logarithmic(true)
+            .auto_ticks();
+        assert_approx_eq(
+            &slider.tick_values(position_range, 10.0),
+            &[0.1, 1.0, 10.0, 100.0, 1000.0],
+        );
+
+        // Both signs and zero:
+        let mut value = 0.0;
+        let slider = Slider::new(&mut value, -100.0..=100.0)
+            .logarithmic(true)
+            .smallest_positive(1.0)
+            .auto_ticks();
+        assert_approx_eq(
+            &slider.tick_values(position_range, 10.0),
+            &[-100.0, -10.0, 0.0, 10.0, 100.0],
+        );
+
+        // Ticks too close to the previous one are skipped:
+        assert_approx_eq(
+            &slider.tick_values(position_range, 30.0),
+            &[-100.0, 0.0, 100.0],
+        );
+    }
+
+    #[test]
+    fn test_snap_to_tick() {
+        let position_range = Rangef::new(0.0, 100.0);
+        let ticks = [0.0, 50.0, 100.0];
+
+        let mut value = 0.0;
+        let slider = Slider::new(&mut value, 0.0..=100.0).ticks(ticks);
+
+        // One value is one point here, so only values within the detent radius snap:
+        let near = 50.0 + TICK_DETENT_RADIUS as f64 - 0.5;
+        let far = 50.0 + TICK_DETENT_RADIUS as f64 + 0.5;
+        assert_eq!(slider.snap_to_tick(near, &ticks, position_range), 50.0);
+        assert_eq!(slider.snap_to_tick(far, &ticks, position_range), far);
+
+        // Unless we always snap:
+        let slider = slider.snap_to_ticks(true);
+        assert_eq!(slider.snap_to_tick(far, &ticks, position_range), 50.0);
+    }
+}
@@ -0,0 +1,275 @@
+#![allow(clippy::needless_pass_by_value)] // False positives with `impl ToString`
+
+use std::ops::RangeInclusive;
+
+use crate::*;
+
+// ----------------------------------------------------------------------------
+
+/// Combined into one function (rather than two) to make it easier
+/// for the borrow checker.
+type GetSetValue<'a> = Box<dyn 'a + FnMut(Option<f64>) -> f64>;
+
+fn get(get_set_value: &mut GetSetValue<'_>) -> f64 {
+    (get_set_value)(None)
+}
+
+fn set(get_set_value: &mut GetSetValue<'_>, value: f64) {
+    (get_set_value)(Some(value));
+}
+
+/// How long a button has to be held before it starts repeating, in seconds.
+const REPEAT_DELAY: f64 = 0.5;
+
+/// Steps per second when the repeat starts.
+const REPEAT_START_RATE: f64 = 8.0;
+
+/// Steps per second after holding for [`REPEAT_ACCELERATION_TIME`].
+const REPEAT_MAX_RATE: f64 = 40.0;
+
+/// How long it takes the repeat to go from [`REPEAT_START_RATE`] to [`REPEAT_MAX_RATE`], in seconds.
+const REPEAT_ACCELERATION_TIME: f64 = 2.0;
+
+/// How many steps a button has made after being held for `duration` seconds, including the first one.
+fn num_repeats(duration: f64) -> usize {
+    let t = duration - REPEAT_DELAY;
+    let repeats = if t <= 0.0 {
+        0.0
+    } else {
+        let acceleration = (REPEAT_MAX_RATE - REPEAT_START_RATE) / REPEAT_ACCELERATION_TIME;
+        let accelerating_time = t.min(REPEAT_ACCELERATION_TIME);
+        let accelerating_repeats = REPEAT_START_RATE * accelerating_time
+            + 0.5 * acceleration * accelerating_time * accelerating_time;
+        accelerating_repeats + REPEAT_MAX_RATE * (t - accelerating_time).max(0.0)
+    };
+    1 + repeats as usize
+}
+
+/// When a button was pressed, and how many steps it has made since.
+#[derive(Clone, Copy, Debug)]
+struct HoldState {
+    start_time: f64,
+    num_steps: usize,
+}
+
+// ----------------------------------------------------------------------------
+
+/// A number with buttons to decrease and increase it by a step.
+///
+/// Holding a button repeats the step, faster and faster.
+/// The value in the middle can be dragged or clicked to edit it, like a [`DragValue`].
+/// This is easier to use than a [`DragValue`] on touch screens, or for users without a keyboard.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut quantity: u32 = 1;
+/// ui.add(egui::Stepper::new(&mut quantity).clamp_range(1..=99));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct Stepper<'a> {
+    get_set_value: GetSetValue<'a>,
+    step: f64,
+    clamp_range: RangeInclusive<f64>,
+    wrap_around: bool,
+    prefix: String,
+    suffix: String,
+    min_decimals: usize,
+    max_decimals: Option<usize>,
+}
+
+impl<'a> Stepper<'a> {
+    pub fn new<Num: emath::Numeric>(value: &'a mut Num) -> Self {
+        let slf = Self::from_get_set(move |v: Option<f64>| {
+            if let Some(v) = v {
+                *value = Num::from_f64(v);
+            }
+            value.to_f64()
+        });
+
+        if Num::INTEGRAL {
+            slf.max_decimals(0).clamp_range(Num::MIN..=Num::MAX)
+        } else {
+            slf
+        }
+    }
+
+    pub fn from_get_set(get_set_value: impl 'a + FnMut(Option<f64>) -> f64) -> Self {
+        Self {
+            get_set_value: Box::new(get_set_value),
+            step: 1.0,
+            clamp_range: f64::NEG_INFINITY..=f64::INFINITY,
+            wrap_around: false,
+            prefix: Default::default(),
+            suffix: Default::default(),
+            min_decimals: 0,
+            max_decimals: None,
+        }
+    }
+
+    /// How much the buttons change the value. Default: 1.
+    #[inline]
+    pub fn step(mut self, step: impl Into<f64>) -> Self {
+        self.step = step.into();
+        self
+    }
+
+    /// Clamp incoming and outgoing values to this range.
+    #[inline]
+    pub fn clamp_range<Num: emath::Numeric>(mut self, clamp_range: RangeInclusive<Num>) -> Self {
+        self.clamp_range = clamp_range.start().to_f64()..=clamp_range.end().to_f64();
+        self
+    }
+
+    /// Stepping past one end of the [`Self::clamp_range`] goes to the other end,
+    /// e.g. for minutes or days of the week. Default is OFF.
+    ///
+    /// When OFF, the button that would step past the end is disabled.
+    #[inline]
+    pub fn wrap_around(mut self, wrap_around: bool) -> Self {
+        self.wrap_around = wrap_around;
+        self
+    }
+
+    /// Show a prefix before the number, e.g. "x: "
+    #[inline]
+    pub fn prefix(mut self, prefix: impl ToString) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Add a suffix to the number, this can be e.g. a unit ("°" or " m")
+    #[inline]
+    pub fn suffix(mut self, suffix: impl ToString) -> Self {
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    /// Set a minimum number of decimals to display.
+    #[inline]
+    pub fn min_decimals(mut self, min_decimals: usize) -> Self {
+        self.min_decimals = min_decimals;
+        self
+    }
+
+    /// Set a maximum number of decimals to display.
+    /// Values will also be rounded to this number of decimals.
+    #[inline]
+    pub fn max_decimals(mut self, max_decimals: usize) -> Self {
+        self.max_decimals = Some(max_decimals);
+        self
+    }
+
+    /// The value after stepping `steps` times (negative to decrease).
+    fn stepped_value(&self, value: f64, steps: f64) -> f64 {
+        let (min, max) = (*self.clamp_range.start(), *self.clamp_range.end());
+        let mut new_value = value + steps * self.step;
+        if let Some(max_decimals) = self.max_decimals {
+            new_value = emath::round_to_decimals(new_value, max_decimals);
+        }
+        if self.wrap_around && min.is_finite() && max.is_finite() {
+            if max < new_value {
+                min
+            } else if new_value < min {
+                max
+            } else {
+                new_value
+            }
+        } else {
+            new_value.clamp(min, max)
+        }
+    }
+
+    fn can_step(&self, value: f64, steps: f64) -> bool {
+        self.wrap_around || self.stepped_value(value, steps) != value
+    }
+}
+
+/// Show a step button, and return how many steps it made this frame.
+fn step_button(ui: &mut Ui, enabled: bool, text: &str, id: Id) -> usize {
+    let size = Vec2::splat(ui.spacing().interact_size.y);
+    let response = ui.add_enabled(enabled, Button::new(text).min_size(size));
+
+    let hold_state: Option<HoldState> = ui.data(|d| d.get_temp(id));
+    let mut num_steps = 0;
+    if response.is_pointer_button_down_on() {
+        let now = ui.input(|i| i.time);
+        let mut hold_state = hold_state.unwrap_or(HoldState {
+            start_time: now,
+            num_steps: 0,
+        });
+        let total_steps = num_repeats(now - hold_state.start_time);
+        num_steps = total_steps - hold_state.num_steps;
+        hold_state.num_steps = total_steps;
+        ui.data_mut(|d| d.insert_temp(id, hold_state));
+        ui.ctx().request_repaint();
+    } else if hold_state.is_some() {
+        ui.data_mut(|d| d.remove::<HoldState>(id));
+    } else if response.clicked() {
+        // Clicked with the keyboard, by a screen reader, or pressed and released within one frame:
+        num_steps = 1;
+    }
+    num_steps
+}
+
+impl<'a> Widget for Stepper<'a> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let id = ui.next_auto_id();
+        let old_value = get(&mut self.get_set_value);
+
+        let inner_response = ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = ui.spacing().item_spacing.y;
+
+            let decrease_enabled = self.can_step(old_value, -1.0);
+            let decrease = step_button(ui, decrease_enabled, "−", id.with("decrease"));
+
+            let mut value = old_value;
+            let value_response = ui.add(
+                DragValue::new(&mut value)
+                    .speed(self.step / 4.0)
+                    .clamp_range(self.clamp_range.clone())
+                    .min_decimals(self.min_decimals)
+                    .max_decimals_opt(self.max_decimals)
+                    .prefix(self.prefix.clone())
+                    .suffix(self.suffix.clone()),
+            );
+
+            let increase_enabled = self.can_step(old_value, 1.0);
+            let increase = step_button(ui, increase_enabled, "+", id.with("increase"));
+
+            let steps = increase as f64 - decrease as f64;
+            for _ in 0..(steps.abs() as usize) {
+                value = self.stepped_value(value, steps.signum());
+            }
+            if value != old_value {
+                set(&mut self.get_set_value, value);
+            }
+            value_response
+        });
+
+        let mut response = inner_response.inner | inner_response.response;
+        response.changed = get(&mut self.get_set_value) != old_value;
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_num_repeats() {
+        assert_eq!(num_repeats(0.0), 1);
+        assert_eq!(num_repeats(REPEAT_DELAY), 1);
+        assert_eq!(
+            num_repeats(REPEAT_DELAY + 1.0 / REPEAT_START_RATE + 0.01),
+            2
+        );
+
+        let accelerated = REPEAT_DELAY + REPEAT_ACCELERATION_TIME;
+        assert_eq!(
+            num_repeats(accelerated + 1.0) - num_repeats(accelerated),
+            REPEAT_MAX_RATE as usize
+        );
+    }
+}
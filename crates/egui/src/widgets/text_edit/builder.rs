@@ -685,6 +685,15 @@ impl<'t> TextEdit<'t> {
                     // the text galley has backgrounds (as e.g. `code` snippets in markup do).
                     paint_cursor_selection(ui, &painter, text_draw_pos, &galley, &cursor_range);
 
+                    if interactive && !cursor_range.is_empty() && ui.ctx().is_touch_mode() {
+                        // There is no shift-click on a touch screen, so let the user drag the ends of the selection instead:
+                        if let Some(new_range) =
+                            selection_handles(ui, id, text_draw_pos, &galley, cursor_range)
+                        {
+                            state.set_cursor_range(Some(new_range));
+                        }
+                    }
+
                     if text.is_mutable() {
                         let cursor_rect = paint_cursor_end(
                             ui,
@@ -702,8 +711,10 @@ impl<'t> TextEdit<'t> {
 
                         if interactive {
                             // For IME, so only set it when text is editable and visible!
+                            let virtual_keyboard = ui.ctx().is_touch_mode();
                             ui.ctx().output_mut(|o| {
                                 o.ime = Some(crate::output::IMEOutput { rect, cursor_rect });
+                                o.virtual_keyboard |= virtual_keyboard;
                             });
                         }
                     }
@@ -1194,6 +1205,46 @@ fn paint_cursor_end(
     cursor_pos
 }
 
+/// Paint draggable handles below both ends of the selection (used in touch mode).
+///
+/// Returns the new cursor range if a handle was dragged.
+fn selection_handles(
+    ui: &Ui,
+    id: Id,
+    pos: Pos2,
+    galley: &Galley,
+    mut cursor_range: CursorRange,
+) -> Option<CursorRange> {
+    let radius = 0.5 * ui.spacing().interact_size.y;
+    let color = ui.visuals().selection.stroke.color;
+    let mut changed = false;
+
+    for (i, cursor) in [&mut cursor_range.secondary, &mut cursor_range.primary]
+        .into_iter()
+        .enumerate()
+    {
+        let cursor_rect = galley.pos_from_cursor(cursor).translate(pos.to_vec2());
+        let center = cursor_rect.center_bottom() + vec2(0.0, radius);
+        let handle_rect = Rect::from_center_size(center, Vec2::splat(2.0 * radius));
+        let response = ui.interact(handle_rect, id.with(("selection_handle", i)), Sense::drag());
+
+        if response.dragged() {
+            if let Some(pointer_pos) = response.interact_pointer_pos() {
+                // The finger is on the handle, which is below the text:
+                let text_pos = pointer_pos - vec2(0.0, radius + 0.5 * cursor_rect.height());
+                *cursor = galley.cursor_from_pos(text_pos - pos);
+                changed = true;
+            }
+        }
+
+        let painter = ui.painter();
+        painter.line_segment([cursor_rect.center_top(), center], (2.0, color));
+        painter.circle_filled(center, 0.6 * radius, color);
+    }
+
+    changed.then_some(cursor_range)
+}
+
 // ----------------------------------------------------------------------------
 
 fn selected_str<'s>(text: &'s dyn TextBuffer, cursor_range: &CursorRange) -> &'s str {
@@ -0,0 +1,120 @@
+use crate::*;
+
+/// An iOS-style on/off switch, an alternative to [`Checkbox`].
+///
+/// The knob slides over when the value changes.
+///
+/// Usually you'd use [`Ui::toggle_switch`] instead.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut dark_mode = true;
+/// ui.toggle_switch(&mut dark_mode, "Dark mode");
+/// ui.add(egui::ToggleSwitch::new(&mut dark_mode, "Dark mode"));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct ToggleSwitch<'a> {
+    on: &'a mut bool,
+    text: WidgetText,
+    on_fill: Option<Color32>,
+}
+
+impl<'a> ToggleSwitch<'a> {
+    pub fn new(on: &'a mut bool, text: impl Into<WidgetText>) -> Self {
+        Self {
+            on,
+            text: text.into(),
+            on_fill: None,
+        }
+    }
+
+    pub fn without_text(on: &'a mut bool) -> Self {
+        Self::new(on, WidgetText::default())
+    }
+
+    /// The color of the track when the switch is on.
+    ///
+    /// Default: [`crate::style::Selection::bg_fill`].
+    #[inline]
+    pub fn on_fill(mut self, on_fill: Color32) -> Self {
+        self.on_fill = Some(on_fill);
+        self
+    }
+}
+
+impl<'a> Widget for ToggleSwitch<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self { on, text, on_fill } = self;
+
+        let spacing = &ui.spacing();
+        let track_size = spacing.interact_size.y * vec2(1.8, 1.0);
+        let icon_spacing = spacing.icon_spacing;
+
+        let (galley, desired_size) = if text.is_empty() {
+            (None, track_size)
+        } else {
+            let total_extra = vec2(track_size.x + icon_spacing, 0.0);
+            let wrap_width = ui.available_width() - total_extra.x;
+            let galley = text.into_galley(ui, None, wrap_width, TextStyle::Button);
+            let desired_size = (total_extra + galley.size()).at_least(track_size);
+            (Some(galley), desired_size)
+        };
+
+        let (rect, mut response) = ui.allocate_exact_size(desired_size, Sense::click());
+
+        if response.clicked() {
+            *on = !*on;
+            response.mark_changed();
+        }
+        response.widget_info(|| {
+            WidgetInfo::selected(
+                WidgetType::Checkbox,
+                *on,
+                galley.as_ref().map_or("", |x| x.text()),
+            )
+        });
+
+        if ui.is_rect_visible(rect) {
+            let how_on = ui.ctx().animate_bool(response.id, *on);
+            let visuals = ui.style().interact(&response);
+            let on_fill = on_fill.unwrap_or(ui.visuals().selection.bg_fill);
+            let track_fill = lerp(Rgba::from(visuals.bg_fill)..=Rgba::from(on_fill), how_on);
+
+            let track_rect = Rect::from_min_size(
+                pos2(rect.left(), rect.center().y - 0.5 * track_size.y),
+                track_size,
+            )
+            .expand(visuals.expansion);
+            let radius = 0.5 * track_rect.height();
+            ui.painter()
+                .rect(track_rect, radius, track_fill, visuals.bg_stroke);
+
+            let knob_x = lerp(
+                (track_rect.left() + radius)..=(track_rect.right() - radius),
+                how_on,
+            );
+            ui.painter().circle(
+                pos2(knob_x, track_rect.center().y),
+                0.75 * radius,
+                visuals.fg_stroke.color,
+                Stroke::NONE,
+            );
+
+            if response.has_focus() {
+                ui.painter()
+                    .rect_stroke(track_rect, radius, ui.visuals().selection.stroke);
+            }
+
+            if let Some(galley) = galley {
+                let text_pos = pos2(
+                    rect.min.x + track_size.x + icon_spacing,
+                    rect.center().y - 0.5 * galley.size().y,
+                );
+                ui.painter().galley(text_pos, galley, visuals.text_color());
+            }
+        }
+
+        response
+    }
+}
@@ -0,0 +1,110 @@
+use crate::*;
+
+/// A native web view showing a web page, placed over an area of the [`Ui`].
+///
+/// egui can't show web pages itself, so this needs an integration that can,
+/// like `eframe` with a `WebViewBackend` (e.g. from the `egui_webview` crate).
+/// Without one, the URL is shown in place of the page.
+///
+/// The web view is a native child window on top of egui, so it covers any egui content
+/// (e.g. popups) in the same area. It is clipped to the clip rectangle of the [`Ui`].
+/// It gets the mouse and keyboard input over it directly from the OS, not via egui.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// ui.add(egui::WebView::new("https://github.com/emilk/egui").size(egui::vec2(800.0, 600.0)));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct WebView {
+    url: String,
+    id_source: Option<Id>,
+    size: Option<Vec2>,
+}
+
+impl WebView {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            id_source: None,
+            size: None,
+        }
+    }
+
+    /// Needed if the web view moves around in the ui, so that it keeps its page.
+    #[inline]
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
+    /// The size of the web view. Default: all of the available space.
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+}
+
+impl Widget for WebView {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            url,
+            id_source,
+            size,
+        } = self;
+
+        let size = size.unwrap_or_else(|| ui.available_size());
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        let id = id_source.map_or(response.id, |id_source| ui.make_persistent_id(id_source));
+
+        if ui.is_rect_visible(rect) {
+            // Covered by the web view, if the integration shows one:
+            let visuals = ui.visuals();
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+            painter.text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                &url,
+                TextStyle::Body.resolve(ui.style()),
+                visuals.weak_text_color(),
+            );
+
+            ui.ctx().output_mut(|o| {
+                o.webviews.push(WebViewOutput {
+                    id,
+                    url: url.clone(),
+                    rect: rect.intersect(ui.clip_rect()),
+                });
+            });
+        }
+
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::Other, &url));
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webview_output() {
+        let ctx = Context::default();
+        let input = RawInputBuilder::new().screen_size(vec2(400.0, 400.0));
+        let output = ctx.run(input.build(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add(WebView::new("https://example.com").size(vec2(200.0, 100.0)));
+                ui.set_clip_rect(Rect::NOTHING);
+                ui.add(WebView::new("https://example.org"));
+            });
+        });
+
+        // Only the visible web view is shown:
+        let webviews = &output.platform_output.webviews;
+        assert_eq!(webviews.len(), 1);
+        assert_eq!(webviews[0].url, "https://example.com");
+        assert_eq!(webviews[0].rect.size(), vec2(200.0, 100.0));
+    }
+}
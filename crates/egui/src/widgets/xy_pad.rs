@@ -0,0 +1,254 @@
+use std::ops::RangeInclusive;
+
+use crate::*;
+
+/// How much finer dragging and the arrow keys are while holding shift.
+const FINE_ADJUST: f32 = 0.1;
+
+/// A 2D pad for editing two numbers at once, like a joystick or the XY pad of a synthesizer.
+///
+/// Click or drag to move the handle, hold shift to move it more slowly.
+/// The y value increases upwards.
+/// When focused, the arrow keys move the handle by 1% of the ranges (0.1% with shift).
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut pan = egui::Vec2::ZERO;
+/// ui.add(egui::XyPad::new(&mut pan, -1.0..=1.0, -1.0..=1.0).text("Pan"));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct XyPad<'a> {
+    value: &'a mut Vec2,
+    x_range: RangeInclusive<f32>,
+    y_range: RangeInclusive<f32>,
+    size: Option<Vec2>,
+    text: WidgetText,
+    spring_back: Option<Vec2>,
+}
+
+impl<'a> XyPad<'a> {
+    pub fn new(
+        value: &'a mut Vec2,
+        x_range: RangeInclusive<f32>,
+        y_range: RangeInclusive<f32>,
+    ) -> Self {
+        Self {
+            value,
+            x_range,
+            y_range,
+            size: None,
+            text: Default::default(),
+            spring_back: None,
+        }
+    }
+
+    /// Default: a square four times [`crate::style::Spacing::interact_size`]`.y`.
+    #[inline]
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    /// Used for accessibility.
+    #[inline]
+    pub fn text(mut self, text: impl Into<WidgetText>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Go back to this value when released, like a joystick.
+    #[inline]
+    pub fn spring_back(mut self, rest: Vec2) -> Self {
+        self.spring_back = Some(rest);
+        self
+    }
+
+    fn normalized_from_value(&self, value: Vec2) -> Vec2 {
+        vec2(
+            remap_clamp(value.x, self.x_range.clone(), 0.0..=1.0),
+            remap_clamp(value.y, self.y_range.clone(), 0.0..=1.0),
+        )
+    }
+
+    fn value_from_normalized(&self, normalized: Vec2) -> Vec2 {
+        vec2(
+            lerp(self.x_range.clone(), normalized.x.clamp(0.0, 1.0)),
+            lerp(self.y_range.clone(), normalized.y.clamp(0.0, 1.0)),
+        )
+    }
+}
+
+impl<'a> Widget for XyPad<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let old_value = *self.value;
+        let size = self
+            .size
+            .unwrap_or_else(|| Vec2::splat(4.0 * ui.spacing().interact_size.y));
+        let (rect, mut response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+        let handle_radius = ui.spacing().interact_size.y / 4.0;
+        let area = rect.shrink(handle_radius);
+
+        // Normalized, with y up:
+        let position_at = |pos: Pos2| {
+            vec2(
+                remap_clamp(pos.x, area.x_range(), 0.0..=1.0),
+                remap_clamp(pos.y, area.y_range(), 1.0..=0.0),
+            )
+        };
+
+        let mut normalized = self.normalized_from_value(*self.value);
+        if let (Some(rest), true) = (self.spring_back, response.drag_released()) {
+            normalized = self.normalized_from_value(rest);
+        } else if let Some(pointer) = response.interact_pointer_pos() {
+            let fine = ui.input(|i| i.modifiers.shift_only());
+            if fine && response.dragged() {
+                let delta = response.drag_delta();
+                normalized += FINE_ADJUST * vec2(delta.x / area.width(), -delta.y / area.height());
+            } else if response.is_pointer_button_down_on() {
+                normalized = position_at(pointer);
+            }
+        }
+
+        if response.has_focus() {
+            ui.memory_mut(|m| {
+                m.set_focus_lock_filter(
+                    response.id,
+                    EventFilter {
+                        horizontal_arrows: true,
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                );
+            });
+            let steps = ui.input(|i| {
+                let steps = vec2(
+                    i.num_presses(Key::ArrowRight) as f32 - i.num_presses(Key::ArrowLeft) as f32,
+                    i.num_presses(Key::ArrowUp) as f32 - i.num_presses(Key::ArrowDown) as f32,
+                );
+                if i.modifiers.shift_only() {
+                    FINE_ADJUST * steps
+                } else {
+                    steps
+                }
+            });
+            normalized += 0.01 * steps;
+        }
+
+        let new_value = self.value_from_normalized(normalized);
+        if new_value != old_value {
+            *self.value = new_value;
+            response.mark_changed();
+        }
+
+        let value = *self.value;
+        response.widget_info(|| {
+            let label = self.text.text();
+            let position = format!("x: {}, y: {}", value.x, value.y);
+            WidgetInfo {
+                current_text_value: Some(position),
+                ..WidgetInfo::labeled(WidgetType::Slider, label)
+            }
+        });
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact(&response);
+            let painter = ui.painter();
+            painter.rect(
+                rect,
+                visuals.rounding,
+                ui.visuals().extreme_bg_color,
+                visuals.bg_stroke,
+            );
+
+            let grid_stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+            painter.vline(area.center().x, area.y_range(), grid_stroke);
+            painter.hline(area.x_range(), area.center().y, grid_stroke);
+
+            let normalized = self.normalized_from_value(value);
+            let handle = pos2(
+                lerp(area.x_range(), normalized.x),
+                lerp(area.bottom()..=area.top(), normalized.y),
+            );
+            let crosshair_stroke = Stroke::new(1.0, ui.visuals().selection.bg_fill);
+            painter.vline(handle.x, area.y_range(), crosshair_stroke);
+            painter.hline(area.x_range(), handle.y, crosshair_stroke);
+            painter.circle(handle, handle_radius, visuals.bg_fill, visuals.fg_stroke);
+
+            if response.has_focus() {
+                painter.rect_stroke(rect, visuals.rounding, ui.visuals().selection.stroke);
+            }
+        }
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{press, run_in_panel};
+
+    /// Run a frame with a focused pad for `value` in `-1..=1` on both axes,
+    /// and return the area the handle can move in.
+    fn run_pad(ctx: &Context, value: &mut Vec2, input: RawInputBuilder) -> Rect {
+        run_in_panel(ctx, input, |ui| {
+            let response = ui.add(XyPad::new(value, -1.0..=1.0, -1.0..=1.0).size([100.0; 2]));
+            response.request_focus();
+            response.rect.shrink(ui.spacing().interact_size.y / 4.0)
+        })
+    }
+
+    #[test]
+    fn xy_pad_drag() {
+        let ctx = Context::default();
+        let mut value = Vec2::ZERO;
+        let area = run_pad(&ctx, &mut value, RawInputBuilder::new());
+
+        // Clicking moves the handle there, with y up:
+        let pos = area.right_top();
+        press(pos, |input| {
+            run_pad(&ctx, &mut value, input);
+        });
+        assert_eq!(value, vec2(1.0, 1.0));
+
+        let pos = area.center();
+        run_pad(&ctx, &mut value, RawInputBuilder::new().pointer_moved(pos));
+        assert!(value.length() < 1e-3, "value: {value:?}");
+
+        // Fine adjustment while holding shift:
+        let input = RawInputBuilder::new()
+            .modifiers(Modifiers::SHIFT)
+            .pointer_moved(pos + vec2(0.5 * area.width(), 0.0));
+        run_pad(&ctx, &mut value, input);
+        assert!((value.x - 0.1).abs() < 1e-3, "value: {value:?}");
+        assert!(value.y.abs() < 1e-3, "value: {value:?}");
+    }
+
+    #[test]
+    fn xy_pad_keys() {
+        let ctx = Context::default();
+        let mut value = Vec2::ZERO;
+        run_pad(&ctx, &mut value, RawInputBuilder::new());
+
+        // 1% of the ranges per press:
+        let input = RawInputBuilder::new()
+            .key(Key::ArrowRight, true)
+            .key(Key::ArrowRight, true)
+            .key(Key::ArrowDown, true);
+        run_pad(&ctx, &mut value, input);
+        assert!(
+            (value - vec2(0.04, -0.02)).length() < 1e-5,
+            "value: {value:?}"
+        );
+
+        let input = RawInputBuilder::new()
+            .modifiers(Modifiers::SHIFT)
+            .key(Key::ArrowUp, true);
+        run_pad(&ctx, &mut value, input);
+        assert!(
+            (value - vec2(0.04, -0.018)).length() < 1e-5,
+            "value: {value:?}"
+        );
+    }
+}
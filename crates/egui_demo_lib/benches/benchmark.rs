@@ -1,11 +1,154 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use criterion::{
+    criterion_group, criterion_main,
+    measurement::{Measurement, ValueFormatter},
+    Criterion, Throughput,
+};
 
 use egui::epaint::TextShape;
 use egui_demo_lib::LOREM_IPSUM_LONG;
 
+/// Counts allocations, so we can benchmark them with [`Allocations`].
+struct CountingAllocator;
+
+static NUM_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+#[allow(unsafe_code)]
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) };
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// A criterion [`Measurement`] of the number of allocations, instead of the wall time.
+struct Allocations;
+
+impl Measurement for Allocations {
+    type Intermediate = u64;
+    type Value = u64;
+
+    fn start(&self) -> u64 {
+        NUM_ALLOCATIONS.load(Ordering::Relaxed)
+    }
+
+    fn end(&self, start: u64) -> u64 {
+        NUM_ALLOCATIONS.load(Ordering::Relaxed) - start
+    }
+
+    fn add(&self, v1: &u64, v2: &u64) -> u64 {
+        v1 + v2
+    }
+
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn to_f64(&self, value: &u64) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        self
+    }
+}
+
+impl ValueFormatter for Allocations {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "allocations"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let elements = match throughput {
+            Throughput::Bytes(n) | Throughput::BytesDecimal(n) | Throughput::Elements(n) => *n,
+        };
+        for value in values {
+            *value /= elements as f64;
+        }
+        "allocations/element"
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "allocations"
+    }
+}
+
+/// Benchmark a ui, both the whole frame and each of its phases.
+fn bench_ui(c: &mut Criterion, name: &str, run_ui: fn(&egui::Context)) {
+    use egui::bench::Phase;
+
+    let mut harness = egui::bench::Harness::new(run_ui);
+    c.bench_function(name, |b| b.iter(|| harness.run_frame()));
+    for (phase_name, phase) in [
+        ("input", Phase::Input),
+        ("update", Phase::Update),
+        ("tessellate", Phase::Tessellate),
+    ] {
+        c.bench_function(&format!("{name} ({phase_name})"), |b| {
+            b.iter_custom(|iters| harness.time(iters, phase));
+        });
+    }
+}
+
+/// Count the allocations per frame of a ui.
+fn bench_ui_allocations(c: &mut Criterion<Allocations>, name: &str, run_ui: fn(&egui::Context)) {
+    let mut harness = egui::bench::Harness::new(run_ui);
+    c.bench_function(name, |b| b.iter(|| harness.run_frame()));
+}
+
+fn scroll_area_1000_rows(ctx: &egui::Context) {
+    egui::CentralPanel::default().show(ctx, |ui| {
+        egui::ScrollArea::vertical().show_rows(ui, 18.0, 1000, |ui, rows| {
+            for row in rows {
+                ui.label(format!("Row {row}"));
+            }
+        });
+    });
+}
+
+fn table_1000_rows(ctx: &egui::Context) {
+    use egui_extras::{Column, TableBuilder};
+
+    egui::CentralPanel::default().show(ctx, |ui| {
+        TableBuilder::new(ui)
+            .columns(Column::auto(), 3)
+            .body(|body| {
+                body.rows(18.0, 1000, |mut row| {
+                    let row_index = row.index();
+                    for column in 0..3 {
+                        row.col(|ui| {
+                            ui.label(format!("{row_index}:{column}"));
+                        });
+                    }
+                });
+            });
+    });
+}
+
+fn allocation_benchmark(c: &mut Criterion<Allocations>) {
+    bench_ui_allocations(c, "scroll_area_1000_rows", scroll_area_1000_rows);
+    bench_ui_allocations(c, "table_1000_rows", table_1000_rows);
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     use egui::RawInput;
 
+    bench_ui(c, "scroll_area_1000_rows", scroll_area_1000_rows);
+    bench_ui(c, "table_1000_rows", table_1000_rows);
+
     {
         let ctx = egui::Context::default();
         let mut demo_windows = egui_demo_lib::DemoWindows::default();
@@ -143,4 +286,9 @@ pub fn criterion_benchmark(c: &mut Criterion) {
 }
 
 criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
+criterion_group! {
+    name = allocations;
+    config = Criterion::default().with_measurement(Allocations);
+    targets = allocation_benchmark
+}
+criterion_main!(benches, allocations);
@@ -33,6 +33,7 @@ impl Default for Demos {
             Box::<super::multi_touch::MultiTouch>::default(),
             Box::<super::painting::Painting>::default(),
             Box::<super::plot_demo::PlotDemo>::default(),
+            Box::<super::property_grid::PropertyGrid>::default(),
             Box::<super::scrolling::Scrolling>::default(),
             Box::<super::sliders::Sliders>::default(),
             Box::<super::strip_demo::StripDemo>::default(),
@@ -161,10 +161,17 @@ impl super::View for DragAndDropDemo {
                     // do the drop:
                     let item = self.columns[source_col].remove(source_row);
                     self.columns[drop_col].push(item);
+                    ui.ctx().haptic(HapticKind::Impact);
                 }
             }
         }
 
+        ui.separator();
+        ui.label("Reorder the items of the first column by dragging their handles:");
+        ui.reorderable_list("reorderable_column", &mut self.columns[0], |ui, item| {
+            ui.label(item.as_str());
+        });
+
         ui.vertical_centered(|ui| {
             ui.add(crate::egui_github_link_file!());
         });
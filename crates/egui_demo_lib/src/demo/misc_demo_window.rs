@@ -16,6 +16,8 @@ pub struct MiscDemoWindow {
     dummy_bool: bool,
     dummy_usize: usize,
     checklist: [bool; 3],
+    wizard_name: String,
+    wizard_agree: bool,
 }
 
 impl Default for MiscDemoWindow {
@@ -32,6 +34,8 @@ impl Default for MiscDemoWindow {
             dummy_bool: false,
             dummy_usize: 0,
             checklist: std::array::from_fn(|i| i == 0),
+            wizard_name: String::new(),
+            wizard_agree: false,
         }
     }
 }
@@ -154,6 +158,40 @@ impl View for MiscDemoWindow {
                 });
             });
 
+        CollapsingHeader::new("Steps")
+            .default_open(false)
+            .show(ui, |ui| {
+                let response =
+                    Steps::new("wizard", ["Name", "Terms", "Done"]).show(ui, |ui, step| {
+                        match step {
+                            0 => {
+                                ui.horizontal(|ui| {
+                                    ui.label("Your name:");
+                                    ui.text_edit_singleline(&mut self.wizard_name);
+                                });
+                                if self.wizard_name.trim().is_empty() {
+                                    return Err("Enter your name first".to_owned());
+                                }
+                            }
+                            1 => {
+                                ui.checkbox(&mut self.wizard_agree, "I agree to be greeted");
+                                if !self.wizard_agree {
+                                    return Err("You must agree first".to_owned());
+                                }
+                            }
+                            _ => {
+                                ui.label(format!("Hello {}!", self.wizard_name.trim()));
+                            }
+                        }
+                        Ok(())
+                    });
+                if response.finished {
+                    self.wizard_name.clear();
+                    self.wizard_agree = false;
+                    StepsState::default().store(ui.ctx(), response.id);
+                }
+            });
+
         CollapsingHeader::new("Misc")
             .default_open(false)
             .show(ui, |ui| {
@@ -225,6 +263,12 @@ fn label_ui(ui: &mut egui::Ui) {
         )
         .truncate(true),
     );
+    ui.add(
+        egui::Label::new(
+            "The elided part can also be in the middle, which is useful for long paths like /home/user/projects/egui/crates/egui_demo_lib/src/demo/misc_demo_window.rs",
+        )
+        .truncate_mode(egui::TruncateMode::Middle),
+    );
 }
 
 // ----------------------------------------------------------------------------
@@ -458,15 +502,35 @@ impl Tree {
     }
 
     pub fn ui(&mut self, ui: &mut Ui) -> Action {
-        self.ui_impl(ui, 0, "root")
+        use egui::collapsing_header::CollapsingState;
+
+        let scope_id = ui.make_persistent_id("tree");
+        ui.horizontal(|ui| {
+            if ui.button("Expand all").clicked() {
+                CollapsingState::open_all(ui.ctx(), scope_id);
+            }
+            if ui.button("Collapse all").clicked() {
+                CollapsingState::close_all(ui.ctx(), scope_id);
+            }
+        });
+        CollapsingState::scope(ui, scope_id, |ui| self.ui_impl(ui, 0, "root"))
     }
 }
 
 impl Tree {
     fn ui_impl(&mut self, ui: &mut Ui, depth: usize, name: &str) -> Action {
+        let num_children = self.0.len();
         CollapsingHeader::new(name)
             .default_open(depth < 1)
-            .show(ui, |ui| self.children_ui(ui, depth))
+            .show_with_trailing(
+                ui,
+                |ui| {
+                    ui.weak(num_children.to_string())
+                        .on_hover_text("Number of children");
+                },
+                |ui| self.children_ui(ui, depth),
+            )
+            .0
             .body_returned
             .unwrap_or(Action::Keep)
     }
@@ -504,7 +568,7 @@ impl Tree {
 // ----------------------------------------------------------------------------
 
 fn text_layout_demo(ui: &mut Ui) {
-    use egui::text::LayoutJob;
+    use egui::text::{LayoutJob, TextShadow};
 
     let mut job = LayoutJob::default();
 
@@ -627,6 +691,40 @@ fn text_layout_demo(ui: &mut Ui) {
             ..Default::default()
         },
     );
+    job.append(
+        ", ",
+        0.0,
+        TextFormat {
+            color: default_color,
+            ..Default::default()
+        },
+    );
+    job.append(
+        "outlines",
+        0.0,
+        TextFormat {
+            color: Color32::WHITE,
+            outline: Stroke::new(1.0, Color32::BLACK),
+            ..Default::default()
+        },
+    );
+    job.append(
+        " and ",
+        0.0,
+        TextFormat {
+            color: default_color,
+            ..Default::default()
+        },
+    );
+    job.append(
+        "shadows",
+        0.0,
+        TextFormat {
+            color: default_color,
+            shadow: TextShadow::new(vec2(1.0, 1.0), Color32::from_black_alpha(160)),
+            ..Default::default()
+        },
+    );
     job.append(
         ". Of course, ",
         0.0,
@@ -21,6 +21,7 @@ pub mod paint_bezier;
 pub mod painting;
 pub mod password;
 pub mod plot_demo;
+pub mod property_grid;
 pub mod scrolling;
 pub mod sliders;
 pub mod strip_demo;
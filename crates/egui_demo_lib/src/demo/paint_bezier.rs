@@ -20,6 +20,9 @@ pub struct PaintBezier {
     aux_stroke: Stroke,
 
     bounding_box_stroke: Stroke,
+
+    /// Text painted along the curve.
+    text: String,
 }
 
 impl Default for PaintBezier {
@@ -36,6 +39,7 @@ impl Default for PaintBezier {
             fill: Color32::from_rgb(50, 100, 150).linear_multiply(0.25),
             aux_stroke: Stroke::new(1.0, Color32::RED.linear_multiply(0.25)),
             bounding_box_stroke: Stroke::new(0.0, Color32::LIGHT_GREEN.linear_multiply(0.25)),
+            text: "Text can follow the curve".to_owned(),
         }
     }
 }
@@ -59,6 +63,11 @@ impl PaintBezier {
                 .tessellation_options_mut(|to| *to = tessellation_options);
         });
 
+        ui.horizontal(|ui| {
+            ui.label("Text:");
+            ui.text_edit_singleline(&mut self.text);
+        });
+
         ui.radio_value(&mut self.degree, 3, "Quadratic Bézier");
         ui.radio_value(&mut self.degree, 4, "Cubic Bézier");
         ui.label("Move the points by dragging them.");
@@ -106,7 +115,7 @@ impl PaintBezier {
             .map(|p| to_screen * *p)
             .collect();
 
-        match self.degree {
+        let curve_points = match self.degree {
             3 => {
                 let points = points_in_screen.clone().try_into().unwrap();
                 let shape =
@@ -116,7 +125,9 @@ impl PaintBezier {
                     0.0,
                     self.bounding_box_stroke,
                 ));
+                let curve_points = shape.flatten(Some(0.1));
                 painter.add(shape);
+                curve_points
             }
             4 => {
                 let points = points_in_screen.clone().try_into().unwrap();
@@ -127,13 +138,18 @@ impl PaintBezier {
                     0.0,
                     self.bounding_box_stroke,
                 ));
+                let curve_points = shape.flatten(Some(0.1));
                 painter.add(shape);
+                curve_points
             }
             _ => {
                 unreachable!();
             }
         };
 
+        let text_format = TextFormat::simple(FontId::proportional(16.0), ui.visuals().text_color());
+        painter.text_on_path(&curve_points, &self.text, text_format);
+
         painter.add(PathShape::line(points_in_screen, self.aux_stroke));
         painter.extend(control_point_shapes);
 
@@ -0,0 +1,102 @@
+/// The kind of light, to show how enums are inspected.
+#[derive(Clone, PartialEq, egui::EguiInspect)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+enum LightKind {
+    Directional,
+    Point {
+        #[inspect(range = 0.0..=100.0)]
+        radius: f32,
+    },
+    Spot {
+        #[inspect(name = "angle (degrees)", range = 1.0..=180.0)]
+        angle: f32,
+        #[inspect(speed = 0.1)]
+        falloff: f32,
+    },
+}
+
+#[derive(Clone, PartialEq, egui::EguiInspect)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct Light {
+    name: String,
+
+    #[inspect(multiline)]
+    description: String,
+
+    enabled: bool,
+    kind: LightKind,
+
+    #[inspect(range = 0.0..=10.0)]
+    intensity: f32,
+
+    #[inspect(color)]
+    color: [f32; 3],
+
+    position: egui::Vec2,
+    shadow_bias: Option<f32>,
+    tags: Vec<String>,
+
+    #[inspect(read_only)]
+    id: u64,
+}
+
+impl Default for Light {
+    fn default() -> Self {
+        Self {
+            name: "Sun".to_owned(),
+            description: "Lights up the scene.\nDoesn't move.".to_owned(),
+            enabled: true,
+            kind: LightKind::Directional,
+            intensity: 1.0,
+            color: [1.0, 0.9, 0.7],
+            position: egui::vec2(10.0, 20.0),
+            shadow_bias: None,
+            tags: vec!["outdoor".to_owned(), "static".to_owned()],
+            id: 42,
+        }
+    }
+}
+
+/// Shows how `#[derive(EguiInspect)]` makes an editable property grid.
+#[derive(Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PropertyGrid {
+    light: Light,
+    num_changes: usize,
+}
+
+impl super::Demo for PropertyGrid {
+    fn name(&self) -> &'static str {
+        "🔧 Property Grid"
+    }
+
+    fn show(&mut self, ctx: &egui::Context, open: &mut bool) {
+        use super::View as _;
+        egui::Window::new(self.name())
+            .open(open)
+            .resizable(false)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for PropertyGrid {
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.label("This grid is generated with #[derive(EguiInspect)]:");
+        ui.add_space(4.0);
+
+        if ui.inspect(&mut self.light).changed() {
+            self.num_changes += 1;
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(format!("Changed {} times.", self.num_changes));
+            if ui.button("Reset").clicked() {
+                *self = Default::default();
+            }
+        });
+        ui.vertical_centered(|ui| {
+            ui.add(crate::egui_github_link_file!());
+        });
+    }
+}
@@ -18,6 +18,7 @@ pub struct Sliders {
     pub value: f64,
     pub trailing_fill: bool,
     pub handle_shape: HandleShape,
+    pub ticks: bool,
 }
 
 impl Default for Sliders {
@@ -35,6 +36,7 @@ impl Default for Sliders {
             value: 10.0,
             trailing_fill: false,
             handle_shape: HandleShape::Circle,
+            ticks: false,
         }
     }
 }
@@ -70,6 +72,7 @@ impl super::View for Sliders {
             value,
             trailing_fill,
             handle_shape,
+            ticks,
         } = self;
 
         ui.label("You can click a slider value to edit it with the keyboard.");
@@ -94,30 +97,34 @@ impl super::View for Sliders {
         let istep = if *use_steps { *step } else { 0.0 };
         if *integer {
             let mut value_i32 = *value as i32;
-            ui.add(
-                Slider::new(&mut value_i32, (*min as i32)..=(*max as i32))
-                    .logarithmic(*logarithmic)
-                    .clamp_to_range(*clamp_to_range)
-                    .smart_aim(*smart_aim)
-                    .orientation(orientation)
-                    .text("i32 demo slider")
-                    .step_by(istep)
-                    .trailing_fill(*trailing_fill)
-                    .handle_shape(*handle_shape),
-            );
+            let mut slider = Slider::new(&mut value_i32, (*min as i32)..=(*max as i32))
+                .logarithmic(*logarithmic)
+                .clamp_to_range(*clamp_to_range)
+                .smart_aim(*smart_aim)
+                .orientation(orientation)
+                .text("i32 demo slider")
+                .step_by(istep)
+                .trailing_fill(*trailing_fill)
+                .handle_shape(*handle_shape);
+            if *ticks {
+                slider = slider.auto_ticks().tick_labels(true);
+            }
+            ui.add(slider);
             *value = value_i32 as f64;
         } else {
-            ui.add(
-                Slider::new(value, (*min)..=(*max))
-                    .logarithmic(*logarithmic)
-                    .clamp_to_range(*clamp_to_range)
-                    .smart_aim(*smart_aim)
-                    .orientation(orientation)
-                    .text("f64 demo slider")
-                    .step_by(istep)
-                    .trailing_fill(*trailing_fill)
-                    .handle_shape(*handle_shape),
-            );
+            let mut slider = Slider::new(value, (*min)..=(*max))
+                .logarithmic(*logarithmic)
+                .clamp_to_range(*clamp_to_range)
+                .smart_aim(*smart_aim)
+                .orientation(orientation)
+                .text("f64 demo slider")
+                .step_by(istep)
+                .trailing_fill(*trailing_fill)
+                .handle_shape(*handle_shape);
+            if *ticks {
+                slider = slider.auto_ticks().tick_labels(true);
+            }
+            ui.add(slider);
 
             ui.label(
                 "Sliders will intelligently pick how many decimals to show. \
@@ -160,6 +167,11 @@ impl super::View for Sliders {
 
         ui.separator();
 
+        ui.checkbox(ticks, "Show ticks");
+        ui.label("Tick marks at round values, or at powers of ten for logarithmic sliders. Dragging close to a tick snaps to it.");
+
+        ui.separator();
+
         ui.checkbox(use_steps, "Use steps");
         ui.label("When enabled, the minimal value change would be restricted to a given step.");
         if *use_steps {
@@ -1,4 +1,4 @@
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 enum Enum {
     First,
@@ -14,9 +14,15 @@ pub struct WidgetGallery {
     boolean: bool,
     radio: Enum,
     scalar: f32,
+    pad: egui::Vec2,
     string: String,
+    tags: Vec<String>,
+    list_selection: Vec<usize>,
+    path: Vec<String>,
+    page: usize,
     color: egui::Color32,
     animate_progress_bar: bool,
+    report_progress: bool,
 
     #[cfg(feature = "chrono")]
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -31,9 +37,17 @@ impl Default for WidgetGallery {
             boolean: false,
             radio: Enum::First,
             scalar: 42.0,
+            pad: egui::Vec2::ZERO,
             string: Default::default(),
+            tags: vec!["egui".to_owned(), "rust".to_owned()],
+            list_selection: vec![],
+            path: ["Home", "Documents", "Projects", "egui"]
+                .map(ToOwned::to_owned)
+                .to_vec(),
+            page: 0,
             color: egui::Color32::LIGHT_BLUE.linear_multiply(0.5),
             animate_progress_bar: false,
+            report_progress: false,
             #[cfg(feature = "chrono")]
             date: None,
         }
@@ -102,9 +116,15 @@ impl WidgetGallery {
             boolean,
             radio,
             scalar,
+            pad,
             string,
+            tags,
+            list_selection,
+            path,
+            page,
             color,
             animate_progress_bar,
+            report_progress,
             #[cfg(feature = "chrono")]
             date,
         } = self;
@@ -125,12 +145,25 @@ impl WidgetGallery {
         ui.add(egui::TextEdit::singleline(string).hint_text("Write something here"));
         ui.end_row();
 
+        ui.add(doc_link_label("TagEdit", "TagEdit"));
+        ui.add(egui::TagEdit::new(tags).hint_text("Add a tag"));
+        ui.end_row();
+
         ui.add(doc_link_label("Button", "button"));
         if ui.button("Click me!").clicked() {
             *boolean = !*boolean;
         }
         ui.end_row();
 
+        ui.add(doc_link_label("Badge", "badge"));
+        ui.horizontal(|ui| {
+            ui.button("Inbox").badge(3, egui::Align2::RIGHT_TOP);
+            ui.add_space(ui.spacing().item_spacing.x);
+            ui.button("Updates")
+                .badge(egui::Badge::dot(), egui::Align2::RIGHT_TOP);
+        });
+        ui.end_row();
+
         ui.add(doc_link_label("Link", "link"));
         if ui.link("Click me!").clicked() {
             *boolean = !*boolean;
@@ -141,6 +174,10 @@ impl WidgetGallery {
         ui.checkbox(boolean, "Checkbox");
         ui.end_row();
 
+        ui.add(doc_link_label("ToggleSwitch", "toggle_switch"));
+        ui.toggle_switch(boolean, "Toggle switch");
+        ui.end_row();
+
         ui.add(doc_link_label("RadioButton", "radio"));
         ui.horizontal(|ui| {
             ui.radio_value(radio, Enum::First, "First");
@@ -157,6 +194,15 @@ impl WidgetGallery {
         });
         ui.end_row();
 
+        ui.add(doc_link_label("SegmentedControl", "SegmentedControl"));
+        ui.add(
+            egui::SegmentedControl::new(radio)
+                .segment(Enum::First, "First")
+                .segment(Enum::Second, "Second")
+                .segment(Enum::Third, "Third"),
+        );
+        ui.end_row();
+
         ui.add(doc_link_label("ComboBox", "ComboBox"));
 
         egui::ComboBox::from_label("Take your pick")
@@ -170,6 +216,29 @@ impl WidgetGallery {
             });
         ui.end_row();
 
+        ui.add(doc_link_label("ListBox", "ListBox"));
+        ui.add(
+            egui::ListBox::new(100, |i| format!("Item {i}"), list_selection)
+                .multi_select(true)
+                .max_height(80.0),
+        );
+        ui.end_row();
+
+        ui.add(doc_link_label("Breadcrumbs", "Breadcrumbs"));
+        ui.horizontal(|ui| {
+            if let Some(index) = egui::Breadcrumbs::new(path.iter()).show(ui).inner {
+                path.truncate(index + 1);
+            }
+            if path.len() < 4 && ui.small_button("↺").on_hover_text("Reset").clicked() {
+                *path = Self::default().path;
+            }
+        });
+        ui.end_row();
+
+        ui.add(doc_link_label("Pagination", "Pagination"));
+        ui.add(egui::Pagination::new(page, 20).jump_to_page(true));
+        ui.end_row();
+
         ui.add(doc_link_label("Slider", "Slider"));
         ui.add(egui::Slider::new(scalar, 0.0..=360.0).suffix("°"));
         ui.end_row();
@@ -178,6 +247,31 @@ impl WidgetGallery {
         ui.add(egui::DragValue::new(scalar).speed(1.0));
         ui.end_row();
 
+        ui.add(doc_link_label("Stepper", "Stepper"));
+        ui.add(
+            egui::Stepper::new(scalar)
+                .step(15.0)
+                .clamp_range(0.0..=360.0)
+                .wrap_around(true)
+                .suffix("°"),
+        );
+        ui.end_row();
+
+        ui.add(doc_link_label("Knob", "Knob"));
+        ui.add(egui::Knob::new(scalar, 0.0..=360.0).default_value(42.0));
+        ui.end_row();
+
+        ui.add(doc_link_label("AnglePicker", "AnglePicker"));
+        let mut angle = scalar.to_radians();
+        if ui.add(egui::AnglePicker::new(&mut angle)).changed() {
+            *scalar = angle.to_degrees();
+        }
+        ui.end_row();
+
+        ui.add(doc_link_label("XyPad", "XyPad"));
+        ui.add(egui::XyPad::new(pad, -1.0..=1.0, -1.0..=1.0).spring_back(egui::Vec2::ZERO));
+        ui.end_row();
+
         ui.add(doc_link_label("ProgressBar", "ProgressBar"));
         let progress = *scalar / 360.0;
         let progress_bar = egui::ProgressBar::new(progress)
@@ -189,6 +283,19 @@ impl WidgetGallery {
             .hovered();
         ui.end_row();
 
+        ui.add(doc_link_label("ProgressStatus", "ProgressStatus"));
+        ui.horizontal(|ui| {
+            let task = ui.ctx().progress("widget_gallery_task");
+            if ui.toggle_value(report_progress, "Report").changed() && !*report_progress {
+                task.finish();
+            }
+            if *report_progress {
+                task.set(*scalar / 360.0, "Following the slider");
+            }
+            ui.add(egui::ProgressStatus::new());
+        });
+        ui.end_row();
+
         ui.add(doc_link_label("Color picker", "color_edit"));
         ui.color_edit_button_srgba(color);
         ui.end_row();
@@ -198,6 +305,14 @@ impl WidgetGallery {
         ui.add(egui::Image::new(egui_icon.clone()));
         ui.end_row();
 
+        ui.add(doc_link_label("ImageViewer", "ImageViewer"));
+        ui.add(
+            egui::ImageViewer::new(egui_icon.clone())
+                .size(egui::vec2(128.0, 96.0))
+                .pixel_grid(true),
+        );
+        ui.end_row();
+
         ui.add(doc_link_label(
             "Button with image",
             "Button::image_and_text",
@@ -0,0 +1,511 @@
+use std::sync::Arc;
+
+use egui::{
+    text::{LayoutJob, TextFormat},
+    Color32, FontId, Galley, Pos2, Rect, Response, Sense, Stroke, TextStyle, Ui, Vec2,
+};
+
+/// Text attributes of a [`GridCell`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CellAttributes {
+    /// Shown with the strong text color when the cell has no foreground color.
+    pub bold: bool,
+    pub italics: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+
+    /// Swap the foreground and background colors.
+    pub inverse: bool,
+}
+
+/// One character in a [`CellGrid`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridCell {
+    pub ch: char,
+
+    /// `None` means the text color of the [`egui::Visuals`].
+    pub foreground: Option<Color32>,
+
+    /// `None` means no background.
+    pub background: Option<Color32>,
+
+    pub attributes: CellAttributes,
+}
+
+impl Default for GridCell {
+    fn default() -> Self {
+        Self::new(' ')
+    }
+}
+
+impl GridCell {
+    pub fn new(ch: char) -> Self {
+        Self {
+            ch,
+            foreground: None,
+            background: None,
+            attributes: Default::default(),
+        }
+    }
+
+    #[inline]
+    pub fn foreground(mut self, foreground: Color32) -> Self {
+        self.foreground = Some(foreground);
+        self
+    }
+
+    #[inline]
+    pub fn background(mut self, background: Color32) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    #[inline]
+    pub fn attributes(mut self, attributes: CellAttributes) -> Self {
+        self.attributes = attributes;
+        self
+    }
+}
+
+/// How the cursor of a [`CellGrid`] is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CursorShape {
+    /// Fill the whole cell, showing the character in the background color.
+    #[default]
+    Block,
+
+    /// A line under the cell.
+    Underline,
+
+    /// A line to the left of the cell.
+    Bar,
+}
+
+/// What a row was laid out with, so we know when to lay it out again.
+#[derive(Clone, Debug, PartialEq)]
+struct LayoutKey {
+    font_id: FontId,
+    pixels_per_point: f32,
+    text_color: Color32,
+    strong_text_color: Color32,
+}
+
+/// A laid out row.
+#[derive(Clone)]
+struct RowLayout {
+    galley: Arc<Galley>,
+
+    /// Runs of cells with the same background color: first column, number of columns, color.
+    backgrounds: Vec<(usize, usize, Color32)>,
+}
+
+/// A grid of monospace characters with their own colors and attributes,
+/// e.g. for terminal emulators or roguelikes.
+///
+/// Each row is only laid out again when it changes, so it is cheap to show a large grid
+/// that is only partially updated each frame.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_extras::{CellGrid, GridCell};
+///
+/// let mut grid = CellGrid::new(80, 24);
+/// grid.put_str(0, 0, "Hello", GridCell::default().foreground(egui::Color32::GREEN));
+/// grid.set(10, 5, GridCell::new('@').background(egui::Color32::DARK_BLUE));
+/// grid.set_cursor(Some((5, 0)));
+/// grid.ui(ui);
+/// # });
+/// ```
+pub struct CellGrid {
+    columns: usize,
+    rows: usize,
+    cells: Vec<GridCell>,
+
+    /// Rows that have changed since they were laid out.
+    dirty: Vec<bool>,
+
+    cursor: Option<(usize, usize)>,
+    cursor_shape: CursorShape,
+
+    text_style: TextStyle,
+    layout_key: Option<LayoutKey>,
+    layouts: Vec<Option<RowLayout>>,
+
+    /// Size of a cell the last time the grid was shown.
+    cell_size: Vec2,
+}
+
+impl CellGrid {
+    /// A grid of blank cells.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            cells: vec![GridCell::default(); columns * rows],
+            dirty: vec![true; rows],
+            cursor: None,
+            cursor_shape: CursorShape::default(),
+            text_style: TextStyle::Monospace,
+            layout_key: None,
+            layouts: vec![None; rows],
+            cell_size: Vec2::ZERO,
+        }
+    }
+
+    /// The font to use. It should be monospace.
+    ///
+    /// Default: [`TextStyle::Monospace`].
+    #[inline]
+    pub fn text_style(mut self, text_style: TextStyle) -> Self {
+        self.text_style = text_style;
+        self.layout_key = None;
+        self
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Change the size of the grid, keeping the cells that are still inside it.
+    pub fn resize(&mut self, columns: usize, rows: usize) {
+        if (columns, rows) == (self.columns, self.rows) {
+            return;
+        }
+        let mut cells = vec![GridCell::default(); columns * rows];
+        for row in 0..rows.min(self.rows) {
+            let keep = columns.min(self.columns);
+            cells[row * columns..row * columns + keep]
+                .copy_from_slice(&self.cells[row * self.columns..row * self.columns + keep]);
+        }
+        self.cells = cells;
+        self.columns = columns;
+        self.rows = rows;
+        self.dirty = vec![true; rows];
+        self.layouts = vec![None; rows];
+    }
+
+    /// The cell at this column and row, if inside the grid.
+    pub fn get(&self, column: usize, row: usize) -> Option<&GridCell> {
+        if column < self.columns && row < self.rows {
+            Some(&self.cells[row * self.columns + column])
+        } else {
+            None
+        }
+    }
+
+    /// Set the cell at this column and row. Does nothing if outside the grid.
+    pub fn set(&mut self, column: usize, row: usize, cell: GridCell) {
+        if column < self.columns && row < self.rows {
+            let old = &mut self.cells[row * self.columns + column];
+            if *old != cell {
+                *old = cell;
+                self.dirty[row] = true;
+            }
+        }
+    }
+
+    /// Write text starting at this column and row, using the colors and attributes of `style`.
+    ///
+    /// The text is cut off at the end of the row.
+    pub fn put_str(&mut self, column: usize, row: usize, text: &str, style: GridCell) {
+        for (i, ch) in text.chars().enumerate() {
+            self.set(column + i, row, GridCell { ch, ..style });
+        }
+    }
+
+    /// Set all cells to `cell`.
+    pub fn fill(&mut self, cell: GridCell) {
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                self.set(column, row, cell);
+            }
+        }
+    }
+
+    /// Set all cells to blanks.
+    pub fn clear(&mut self) {
+        self.fill(GridCell::default());
+    }
+
+    /// Move all rows up by `num_rows`, filling the bottom with blank rows, like a terminal does.
+    pub fn scroll_up(&mut self, num_rows: usize) {
+        let num_rows = num_rows.min(self.rows);
+        self.cells.drain(..num_rows * self.columns);
+        self.cells
+            .resize(self.columns * self.rows, GridCell::default());
+        self.dirty.rotate_left(num_rows);
+        self.layouts.rotate_left(num_rows);
+        for row in self.rows - num_rows..self.rows {
+            self.dirty[row] = true;
+        }
+    }
+
+    /// Has this row changed since the grid was last shown?
+    pub fn is_row_dirty(&self, row: usize) -> bool {
+        self.dirty.get(row).copied().unwrap_or(false)
+    }
+
+    /// Where to show the cursor, as column and row.
+    pub fn set_cursor(&mut self, cursor: Option<(usize, usize)>) {
+        self.cursor = cursor;
+    }
+
+    pub fn cursor(&self) -> Option<(usize, usize)> {
+        self.cursor
+    }
+
+    pub fn set_cursor_shape(&mut self, cursor_shape: CursorShape) {
+        self.cursor_shape = cursor_shape;
+    }
+
+    /// The size of a cell, in points, the last time the grid was shown.
+    pub fn cell_size(&self) -> Vec2 {
+        self.cell_size
+    }
+
+    /// The column and row of the cell at this position, e.g. [`Response::hover_pos`].
+    ///
+    /// `response` is what [`Self::ui`] returned.
+    pub fn cell_at(&self, response: &Response, pos: Pos2) -> Option<(usize, usize)> {
+        if self.cell_size.x <= 0.0 || self.cell_size.y <= 0.0 || !response.rect.contains(pos) {
+            return None;
+        }
+        let offset = pos - response.rect.min;
+        let column = (offset.x / self.cell_size.x) as usize;
+        let row = (offset.y / self.cell_size.y) as usize;
+        (column < self.columns && row < self.rows).then_some((column, row))
+    }
+
+    /// How many columns and rows fit in this size, e.g. [`Ui::available_size`].
+    ///
+    /// Use this with [`Self::resize`] to make the grid fill the available space.
+    pub fn size_in_cells(&self, ui: &Ui, size: Vec2) -> (usize, usize) {
+        let cell_size = cell_size(ui, &self.text_style.resolve(ui.style()));
+        (
+            (size.x / cell_size.x).floor().max(0.0) as usize,
+            (size.y / cell_size.y).floor().max(0.0) as usize,
+        )
+    }
+
+    /// Show the grid.
+    ///
+    /// Only the rows that have changed since the last call are laid out.
+    /// The response senses clicks and drags, so you can give the grid keyboard focus.
+    pub fn ui(&mut self, ui: &mut Ui) -> Response {
+        let font_id = self.text_style.resolve(ui.style());
+        let cell_size = cell_size(ui, &font_id);
+        self.cell_size = cell_size;
+
+        let layout_key = LayoutKey {
+            font_id: font_id.clone(),
+            pixels_per_point: ui.ctx().pixels_per_point(),
+            text_color: ui.visuals().text_color(),
+            strong_text_color: ui.visuals().strong_text_color(),
+        };
+        if self.layout_key.as_ref() != Some(&layout_key) {
+            self.dirty.fill(true);
+            self.layout_key = Some(layout_key);
+        }
+
+        let size = Vec2::new(
+            cell_size.x * self.columns as f32,
+            cell_size.y * self.rows as f32,
+        );
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        for row in 0..self.rows {
+            let row_top = rect.top() + row as f32 * cell_size.y;
+            let row_rect = Rect::from_min_size(
+                Pos2::new(rect.left(), row_top),
+                Vec2::new(rect.width(), cell_size.y),
+            );
+            if !ui.is_rect_visible(row_rect) {
+                continue;
+            }
+
+            if self.dirty[row] || self.layouts[row].is_none() {
+                self.layouts[row] = Some(self.layout_row(ui, row, &font_id));
+                self.dirty[row] = false;
+            }
+            let Some(layout) = &self.layouts[row] else {
+                continue;
+            };
+
+            let painter = ui.painter();
+            for &(column, count, color) in &layout.backgrounds {
+                let min = Pos2::new(rect.left() + column as f32 * cell_size.x, row_top);
+                painter.rect_filled(
+                    Rect::from_min_size(min, Vec2::new(count as f32 * cell_size.x, cell_size.y)),
+                    0.0,
+                    color,
+                );
+            }
+            painter.galley(
+                row_rect.min,
+                layout.galley.clone(),
+                ui.visuals().text_color(),
+            );
+        }
+
+        if let Some((column, row)) = self.cursor {
+            self.paint_cursor(ui, rect, column, row, &font_id);
+        }
+
+        response
+    }
+
+    fn layout_row(&self, ui: &Ui, row: usize, font_id: &FontId) -> RowLayout {
+        let visuals = ui.visuals();
+        let cells = &self.cells[row * self.columns..(row + 1) * self.columns];
+
+        let mut job = LayoutJob::default();
+        job.wrap.max_width = f32::INFINITY;
+        let mut backgrounds: Vec<(usize, usize, Color32)> = vec![];
+        let mut text = String::new();
+        let mut current_format: Option<TextFormat> = None;
+
+        for (column, cell) in cells.iter().enumerate() {
+            let (foreground, background) = cell_colors(cell, visuals);
+
+            if let Some(background) = background {
+                match backgrounds.last_mut() {
+                    Some((start, count, color))
+                        if *color == background && *start + *count == column =>
+                    {
+                        *count += 1;
+                    }
+                    _ => backgrounds.push((column, 1, background)),
+                }
+            }
+
+            let attributes = &cell.attributes;
+            let format = TextFormat {
+                font_id: font_id.clone(),
+                color: foreground,
+                italics: attributes.italics,
+                underline: if attributes.underline {
+                    Stroke::new(1.0, foreground)
+                } else {
+                    Stroke::NONE
+                },
+                strikethrough: if attributes.strikethrough {
+                    Stroke::new(1.0, foreground)
+                } else {
+                    Stroke::NONE
+                },
+                ..Default::default()
+            };
+
+            if current_format.as_ref() != Some(&format) {
+                if let Some(current_format) = current_format.take() {
+                    job.append(&text, 0.0, current_format);
+                    text.clear();
+                }
+                current_format = Some(format);
+            }
+            text.push(if cell.ch.is_control() { ' ' } else { cell.ch });
+        }
+        if let Some(current_format) = current_format {
+            job.append(&text, 0.0, current_format);
+        }
+
+        RowLayout {
+            galley: ui.fonts(|f| f.layout_job(job)),
+            backgrounds,
+        }
+    }
+
+    fn paint_cursor(&self, ui: &Ui, rect: Rect, column: usize, row: usize, font_id: &FontId) {
+        let Some(cell) = self.get(column, row) else {
+            return;
+        };
+        let cell_rect = Rect::from_min_size(
+            rect.min
+                + Vec2::new(
+                    column as f32 * self.cell_size.x,
+                    row as f32 * self.cell_size.y,
+                ),
+            self.cell_size,
+        );
+        let (foreground, background) = cell_colors(cell, ui.visuals());
+        let painter = ui.painter();
+        match self.cursor_shape {
+            CursorShape::Block => {
+                painter.rect_filled(cell_rect, 0.0, foreground);
+                let text_color = background.unwrap_or(ui.visuals().extreme_bg_color);
+                painter.text(
+                    cell_rect.left_top(),
+                    egui::Align2::LEFT_TOP,
+                    cell.ch,
+                    font_id.clone(),
+                    text_color,
+                );
+            }
+            CursorShape::Underline => {
+                painter.hline(
+                    cell_rect.x_range(),
+                    cell_rect.bottom() - 1.0,
+                    Stroke::new(2.0, foreground),
+                );
+            }
+            CursorShape::Bar => {
+                painter.vline(
+                    cell_rect.left() + 1.0,
+                    cell_rect.y_range(),
+                    Stroke::new(2.0, foreground),
+                );
+            }
+        }
+    }
+}
+
+/// The size of a cell in a monospace font.
+fn cell_size(ui: &Ui, font_id: &FontId) -> Vec2 {
+    ui.fonts(|f| Vec2::new(f.glyph_width(font_id, 'M'), f.row_height(font_id)))
+}
+
+/// The foreground and (optional) background color of a cell.
+fn cell_colors(cell: &GridCell, visuals: &egui::Visuals) -> (Color32, Option<Color32>) {
+    let default_foreground = if cell.attributes.bold {
+        visuals.strong_text_color()
+    } else {
+        visuals.text_color()
+    };
+    let foreground = cell.foreground.unwrap_or(default_foreground);
+    if cell.attributes.inverse {
+        let background = cell.background.unwrap_or(visuals.extreme_bg_color);
+        (background, Some(foreground))
+    } else {
+        (foreground, cell.background)
+    }
+}
+
+#[test]
+fn cell_grid_dirty_rows() {
+    let mut grid = CellGrid::new(4, 3);
+    egui::__run_test_ui(|ui| {
+        grid.ui(ui);
+    });
+    assert!(!grid.is_row_dirty(0));
+
+    grid.put_str(2, 1, "abc", GridCell::default());
+    assert!(grid.is_row_dirty(1));
+    assert!(!grid.is_row_dirty(0));
+    assert_eq!(grid.get(3, 1).map(|cell| cell.ch), Some('b')); // 'c' is cut off
+
+    grid.scroll_up(1);
+    assert!(grid.is_row_dirty(0));
+    assert!(grid.is_row_dirty(2));
+    assert_eq!(grid.get(3, 0).map(|cell| cell.ch), Some('b'));
+
+    grid.resize(2, 2);
+    assert_eq!(grid.get(1, 0).map(|cell| cell.ch), Some(' '));
+    assert_eq!(grid.get(3, 0), None);
+}
@@ -0,0 +1,743 @@
+use std::{collections::BTreeSet, ops::Range};
+
+use egui::{
+    emath::NumExt as _,
+    text::{LayoutJob, TextFormat},
+    Color32, CursorIcon, FontId, Rect, ScrollArea, Sense, TextStyle, Ui, Vec2, Visuals,
+};
+
+/// Diffs needing more edits than this are shown as one big change, to keep diffing fast.
+const MAX_EDIT_COST: usize = 1000;
+
+/// One step of turning the old sequence into the new one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edit {
+    /// The item at this index in the old and new sequence is the same.
+    Equal(usize, usize),
+
+    /// The item at this index in the old sequence was removed.
+    Delete(usize),
+
+    /// The item at this index in the new sequence was added.
+    Insert(usize),
+}
+
+/// The shortest edit script turning `old` into `new`, using Myers' algorithm.
+fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut edits: Vec<Edit> = (0..prefix).map(|i| Edit::Equal(i, i)).collect();
+    if let Some(middle) = myers(old_middle, new_middle) {
+        edits.extend(middle.into_iter().map(|edit| match edit {
+            Edit::Equal(a, b) => Edit::Equal(prefix + a, prefix + b),
+            Edit::Delete(a) => Edit::Delete(prefix + a),
+            Edit::Insert(b) => Edit::Insert(prefix + b),
+        }));
+    } else {
+        edits.extend((0..old_middle.len()).map(|a| Edit::Delete(prefix + a)));
+        edits.extend((0..new_middle.len()).map(|b| Edit::Insert(prefix + b)));
+    }
+    edits.extend((0..suffix).map(|i| Edit::Equal(old.len() - suffix + i, new.len() - suffix + i)));
+    edits
+}
+
+/// Returns `None` if more than [`MAX_EDIT_COST`] edits are needed.
+fn myers<T: PartialEq>(old: &[T], new: &[T]) -> Option<Vec<Edit>> {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    let max_d = (n + m).min(MAX_EDIT_COST as isize);
+
+    // The furthest x reached on each diagonal k = x - y, stored at k + offset.
+    let offset = max_d + 1;
+    let mut v = vec![0_isize; 2 * max_d as usize + 3];
+
+    // The diagonals -d-1..=d+1 of `v` before each step d, for backtracking.
+    let mut trace: Vec<Vec<isize>> = vec![];
+
+    for d in 0..=max_d {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+
+        for k in (-d..=d).step_by(2) {
+            let i = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, n, m));
+            }
+        }
+    }
+    None
+}
+
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<Edit> {
+    let mut edits = vec![];
+    let (mut x, mut y) = (n, m);
+
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let get = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && get(k - 1) < get(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = get(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push(Edit::Insert(prev_y as usize));
+            } else {
+                edits.push(Edit::Delete(prev_x as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+
+    edits.reverse();
+    edits
+}
+
+/// Split a line into words, runs of whitespace and single other characters, as byte ranges.
+fn tokenize(text: &str) -> Vec<Range<usize>> {
+    let kind = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+
+    let mut tokens: Vec<Range<usize>> = vec![];
+    let mut last_kind = None;
+    for (i, c) in text.char_indices() {
+        let c_kind = kind(c);
+        match tokens.last_mut() {
+            Some(token) if last_kind == Some(c_kind) && c_kind != 2 => token.end = i + c.len_utf8(),
+            _ => tokens.push(i..i + c.len_utf8()),
+        }
+        last_kind = Some(c_kind);
+    }
+    tokens
+}
+
+/// The byte ranges of the words that differ between two versions of a line.
+///
+/// Returns no ranges if the lines have no words in common, since highlighting everything doesn't help.
+fn word_diff(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    let old_words: Vec<&str> = old_tokens.iter().map(|r| &old[r.clone()]).collect();
+    let new_words: Vec<&str> = new_tokens.iter().map(|r| &new[r.clone()]).collect();
+
+    let edits = diff(&old_words, &new_words);
+    let has_common_word = edits.iter().any(|edit| match edit {
+        Edit::Equal(a, _) => !old_words[*a].trim().is_empty(),
+        _ => false,
+    });
+    if !has_common_word {
+        return (vec![], vec![]);
+    }
+
+    let push = |ranges: &mut Vec<Range<usize>>, range: &Range<usize>| match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range.clone()),
+    };
+    let (mut old_changed, mut new_changed) = (vec![], vec![]);
+    for edit in edits {
+        match edit {
+            Edit::Equal(..) => {}
+            Edit::Delete(a) => push(&mut old_changed, &old_tokens[a]),
+            Edit::Insert(b) => push(&mut new_changed, &new_tokens[b]),
+        }
+    }
+    (old_changed, new_changed)
+}
+
+// ----------------------------------------------------------------------------
+
+/// Whether a [`DiffLine`] was removed, added, or is in both texts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    Unchanged,
+    Removed,
+    Added,
+}
+
+/// A line of a diff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+
+    /// Line number in the old text, starting at 1. `None` for added lines.
+    pub old_line_nr: Option<usize>,
+
+    /// Line number in the new text, starting at 1. `None` for removed lines.
+    pub new_line_nr: Option<usize>,
+
+    pub text: String,
+
+    /// Byte ranges of the words that changed, compared to the matching line on the other side.
+    pub changed_words: Vec<Range<usize>>,
+}
+
+impl DiffLine {
+    fn layout_job(
+        &self,
+        line_nr: &str,
+        font_id: &FontId,
+        visuals: &Visuals,
+        colors: &DiffColors,
+    ) -> LayoutJob {
+        let (marker, word_background) = match self.kind {
+            DiffKind::Unchanged => (' ', Color32::TRANSPARENT),
+            DiffKind::Removed => ('-', colors.removed_word_background),
+            DiffKind::Added => ('+', colors.added_word_background),
+        };
+        let text_color = visuals.text_color();
+        let format = TextFormat::simple(font_id.clone(), text_color);
+
+        let mut job = LayoutJob::default();
+        job.wrap.max_width = f32::INFINITY;
+        job.append(
+            line_nr,
+            0.0,
+            TextFormat::simple(font_id.clone(), visuals.weak_text_color()),
+        );
+        job.append(&format!("{marker} "), 0.0, format.clone());
+
+        let mut end = 0;
+        for range in &self.changed_words {
+            job.append(&self.text[end..range.start], 0.0, format.clone());
+            job.append(
+                &self.text[range.clone()],
+                0.0,
+                TextFormat {
+                    background: word_background,
+                    ..format.clone()
+                },
+            );
+            end = range.end;
+        }
+        job.append(&self.text[end..], 0.0, format);
+        job
+    }
+}
+
+/// Turn an edit script of lines into [`DiffLine`]s, with the removed lines of each change before the added ones.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    let mut lines = vec![];
+    let mut removed: Vec<usize> = vec![];
+    let mut added: Vec<usize> = vec![];
+
+    let flush = |lines: &mut Vec<DiffLine>, removed: &mut Vec<usize>, added: &mut Vec<usize>| {
+        let mut removed_lines: Vec<DiffLine> = removed
+            .drain(..)
+            .map(|a| DiffLine {
+                kind: DiffKind::Removed,
+                old_line_nr: Some(a + 1),
+                new_line_nr: None,
+                text: old[a].to_owned(),
+                changed_words: vec![],
+            })
+            .collect();
+        let mut added_lines: Vec<DiffLine> = added
+            .drain(..)
+            .map(|b| DiffLine {
+                kind: DiffKind::Added,
+                old_line_nr: None,
+                new_line_nr: Some(b + 1),
+                text: new[b].to_owned(),
+                changed_words: vec![],
+            })
+            .collect();
+        for (removed, added) in removed_lines.iter_mut().zip(&mut added_lines) {
+            (removed.changed_words, added.changed_words) = word_diff(&removed.text, &added.text);
+        }
+        lines.append(&mut removed_lines);
+        lines.append(&mut added_lines);
+    };
+
+    for edit in diff(old, new) {
+        match edit {
+            Edit::Equal(a, b) => {
+                flush(&mut lines, &mut removed, &mut added);
+                lines.push(DiffLine {
+                    kind: DiffKind::Unchanged,
+                    old_line_nr: Some(a + 1),
+                    new_line_nr: Some(b + 1),
+                    text: old[a].to_owned(),
+                    changed_words: vec![],
+                });
+            }
+            Edit::Delete(a) => removed.push(a),
+            Edit::Insert(b) => added.push(b),
+        }
+    }
+    flush(&mut lines, &mut removed, &mut added);
+    lines
+}
+
+// ----------------------------------------------------------------------------
+
+/// How a [`DiffView`] shows the changes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DiffMode {
+    /// The old text on the left and the new text on the right.
+    #[default]
+    SideBySide,
+
+    /// One column, with removed lines before the added lines that replace them.
+    Unified,
+}
+
+/// A row of a [`DiffView`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Row {
+    /// Index into the lines, for [`DiffMode::Unified`].
+    Line(usize),
+
+    /// Indices into the lines of the left and right side, for [`DiffMode::SideBySide`].
+    Pair(Option<usize>, Option<usize>),
+
+    /// Hidden unchanged lines, which are shown when clicked.
+    Fold { first: usize, count: usize },
+}
+
+struct DiffColors {
+    added_background: Color32,
+    removed_background: Color32,
+    added_word_background: Color32,
+    removed_word_background: Color32,
+}
+
+impl DiffColors {
+    /// Removed lines use [`Visuals::error_fg_color`]. Visuals has no color for additions, so we pick a green.
+    fn new(visuals: &Visuals) -> Self {
+        let removed = visuals.error_fg_color;
+        let added = if visuals.dark_mode {
+            Color32::from_rgb(63, 185, 80)
+        } else {
+            Color32::from_rgb(26, 127, 55)
+        };
+        Self {
+            added_background: added.gamma_multiply(0.15),
+            removed_background: removed.gamma_multiply(0.15),
+            added_word_background: added.gamma_multiply(0.4),
+            removed_word_background: removed.gamma_multiply(0.4),
+        }
+    }
+
+    fn background(&self, kind: DiffKind) -> Color32 {
+        match kind {
+            DiffKind::Unchanged => Color32::TRANSPARENT,
+            DiffKind::Removed => self.removed_background,
+            DiffKind::Added => self.added_background,
+        }
+    }
+}
+
+/// Shows the differences between two texts, side by side or unified,
+/// with the changed words highlighted.
+///
+/// Long runs of unchanged lines are folded away, and only the visible rows are laid out,
+/// so this works for long files too.
+///
+/// The diff is computed when the texts are set, so keep the [`DiffView`] around between frames.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let mut diff_view = egui_extras::DiffView::new("one\ntwo\nthree", "one\n2\nthree\nfour");
+/// diff_view.ui(ui);
+/// # });
+/// ```
+pub struct DiffView {
+    lines: Vec<DiffLine>,
+    mode: DiffMode,
+    context_lines: usize,
+
+    /// The first line of every fold the user opened.
+    expanded: BTreeSet<usize>,
+
+    rows: Vec<Row>,
+    rows_dirty: bool,
+    max_line_len: usize,
+}
+
+impl Default for DiffView {
+    fn default() -> Self {
+        Self::new("", "")
+    }
+}
+
+impl DiffView {
+    pub fn new(old: &str, new: &str) -> Self {
+        let mut diff_view = Self {
+            lines: vec![],
+            mode: DiffMode::default(),
+            context_lines: 3,
+            expanded: Default::default(),
+            rows: vec![],
+            rows_dirty: true,
+            max_line_len: 0,
+        };
+        diff_view.set_texts(old, new);
+        diff_view
+    }
+
+    /// How to show the changes.
+    ///
+    /// Default: [`DiffMode::SideBySide`]. The user can also change it in the toolbar.
+    #[inline]
+    pub fn mode(mut self, mode: DiffMode) -> Self {
+        self.mode = mode;
+        self.rows_dirty = true;
+        self
+    }
+
+    /// How many unchanged lines to show around each change. The rest are folded.
+    ///
+    /// Default: 3.
+    #[inline]
+    pub fn context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self.rows_dirty = true;
+        self
+    }
+
+    /// Diff two new texts.
+    pub fn set_texts(&mut self, old: &str, new: &str) {
+        crate::profile_function!();
+        let old: Vec<&str> = old.lines().collect();
+        let new: Vec<&str> = new.lines().collect();
+        self.lines = diff_lines(&old, &new);
+        self.max_line_len = self
+            .lines
+            .iter()
+            .map(|line| line.text.chars().count())
+            .max()
+            .unwrap_or(0);
+        self.expanded.clear();
+        self.rows_dirty = true;
+    }
+
+    /// All the lines, with the removed lines of each change before the added ones.
+    pub fn lines(&self) -> &[DiffLine] {
+        &self.lines
+    }
+
+    /// The number of added lines.
+    pub fn num_added(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| line.kind == DiffKind::Added)
+            .count()
+    }
+
+    /// The number of removed lines.
+    pub fn num_removed(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| line.kind == DiffKind::Removed)
+            .count()
+    }
+
+    fn update_rows(&mut self) {
+        if !self.rows_dirty {
+            return;
+        }
+        self.rows_dirty = false;
+        self.rows.clear();
+
+        let mut i = 0;
+        while i < self.lines.len() {
+            let start = i;
+            if self.lines[i].kind == DiffKind::Unchanged {
+                while i < self.lines.len() && self.lines[i].kind == DiffKind::Unchanged {
+                    i += 1;
+                }
+                self.push_unchanged(start..i);
+            } else {
+                while i < self.lines.len() && self.lines[i].kind == DiffKind::Removed {
+                    i += 1;
+                }
+                let removed = start..i;
+                while i < self.lines.len() && self.lines[i].kind == DiffKind::Added {
+                    i += 1;
+                }
+                let added = removed.end..i;
+                match self.mode {
+                    DiffMode::Unified => self.rows.extend((start..i).map(Row::Line)),
+                    DiffMode::SideBySide => {
+                        for j in 0..removed.len().max(added.len()) {
+                            let left = (j < removed.len()).then_some(removed.start + j);
+                            let right = (j < added.len()).then_some(added.start + j);
+                            self.rows.push(Row::Pair(left, right));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add the rows for a run of unchanged lines, folding away the ones far from any change.
+    fn push_unchanged(&mut self, lines: Range<usize>) {
+        let context_before = if lines.start == 0 {
+            0
+        } else {
+            self.context_lines
+        };
+        let context_after = if lines.end == self.lines.len() {
+            0
+        } else {
+            self.context_lines
+        };
+
+        let mut fold = None;
+        if lines.len() > context_before + context_after + 1 {
+            let first = lines.start + context_before;
+            let count = lines.len() - context_before - context_after;
+            if !self.expanded.contains(&first) {
+                fold = Some(first..first + count);
+            }
+        }
+
+        for i in lines {
+            match &fold {
+                Some(fold) if fold.start == i => self.rows.push(Row::Fold {
+                    first: fold.start,
+                    count: fold.len(),
+                }),
+                Some(fold) if fold.contains(&i) => {}
+                _ => self.rows.push(match self.mode {
+                    DiffMode::Unified => Row::Line(i),
+                    DiffMode::SideBySide => Row::Pair(Some(i), Some(i)),
+                }),
+            }
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let mode = self.mode;
+            ui.selectable_value(&mut self.mode, DiffMode::SideBySide, "Side by side");
+            ui.selectable_value(&mut self.mode, DiffMode::Unified, "Unified");
+            if self.mode != mode {
+                self.rows_dirty = true;
+            }
+
+            if self.rows.iter().any(|row| matches!(row, Row::Fold { .. }))
+                && ui.button("Expand all").clicked()
+            {
+                self.expanded
+                    .extend(self.rows.iter().filter_map(|row| match row {
+                        Row::Fold { first, .. } => Some(*first),
+                        _ => None,
+                    }));
+                self.rows_dirty = true;
+            }
+
+            let colors = DiffColors::new(ui.visuals());
+            ui.colored_label(
+                colors.added_word_background.to_opaque(),
+                format!("+{}", self.num_added()),
+            );
+            ui.colored_label(
+                colors.removed_word_background.to_opaque(),
+                format!("−{}", self.num_removed()),
+            );
+        });
+        self.update_rows();
+
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        let (char_width, row_height) = ui.fonts(|f| {
+            (
+                f.glyph_width(&font_id, '0'),
+                f.row_height(&font_id).at_least(1.0),
+            )
+        });
+        let max_line_nr = self
+            .lines
+            .iter()
+            .map(|line| line.old_line_nr.max(line.new_line_nr).unwrap_or(0))
+            .max()
+            .unwrap_or(0);
+        let digits = max_line_nr.to_string().len();
+
+        let scroll_area = match self.mode {
+            DiffMode::SideBySide => ScrollArea::vertical(),
+            DiffMode::Unified => ScrollArea::both(),
+        };
+        let mut clicked_fold = None;
+        ui.scope(|ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            scroll_area.auto_shrink(false).show_rows(
+                ui,
+                row_height,
+                self.rows.len(),
+                |ui, row_range| {
+                    let visuals = ui.visuals().clone();
+                    let colors = DiffColors::new(&visuals);
+                    let line_nr = |nr: Option<usize>| match nr {
+                        Some(nr) => format!("{nr:>digits$} "),
+                        None => " ".repeat(digits + 1),
+                    };
+
+                    let text_width = match self.mode {
+                        DiffMode::SideBySide => 0.0,
+                        DiffMode::Unified => {
+                            (2 * (digits + 1) + 2 + self.max_line_len) as f32 * char_width
+                        }
+                    };
+                    let row_width = ui.available_width().max(text_width);
+
+                    for row in &self.rows[row_range] {
+                        let (rect, response) = ui
+                            .allocate_exact_size(Vec2::new(row_width, row_height), Sense::click());
+                        match *row {
+                            Row::Line(i) => {
+                                let line = &self.lines[i];
+                                let job = line.layout_job(
+                                    &format!(
+                                        "{}{}",
+                                        line_nr(line.old_line_nr),
+                                        line_nr(line.new_line_nr)
+                                    ),
+                                    &font_id,
+                                    &visuals,
+                                    &colors,
+                                );
+                                paint_line(ui, rect, Some(job), colors.background(line.kind));
+                            }
+                            Row::Pair(left, right) => {
+                                let half = rect.width() / 2.0;
+                                let left_rect =
+                                    Rect::from_min_size(rect.min, Vec2::new(half, row_height));
+                                let right_rect = left_rect.translate(Vec2::new(half, 0.0));
+                                for (side_rect, index, is_old) in
+                                    [(left_rect, left, true), (right_rect, right, false)]
+                                {
+                                    let line = index.map(|i| &self.lines[i]);
+                                    let job = line.map(|line| {
+                                        let nr = if is_old {
+                                            line.old_line_nr
+                                        } else {
+                                            line.new_line_nr
+                                        };
+                                        line.layout_job(&line_nr(nr), &font_id, &visuals, &colors)
+                                    });
+                                    let background = line.map_or(visuals.faint_bg_color, |line| {
+                                        colors.background(line.kind)
+                                    });
+                                    paint_line(ui, side_rect, job, background);
+                                }
+                                ui.painter().vline(
+                                    left_rect.right(),
+                                    rect.y_range(),
+                                    visuals.widgets.noninteractive.bg_stroke,
+                                );
+                            }
+                            Row::Fold { first, count } => {
+                                let response = response
+                                    .on_hover_cursor(CursorIcon::PointingHand)
+                                    .on_hover_text("Show the hidden lines");
+                                let color = if response.hovered() {
+                                    visuals.strong_text_color()
+                                } else {
+                                    visuals.weak_text_color()
+                                };
+                                let text = format!("⋯ {count} unchanged lines");
+                                let job =
+                                    LayoutJob::simple_singleline(text, font_id.clone(), color);
+                                paint_line(ui, rect, Some(job), visuals.faint_bg_color);
+                                if response.clicked() {
+                                    clicked_fold = Some(first);
+                                }
+                            }
+                        }
+                    }
+                },
+            );
+        });
+
+        if let Some(first) = clicked_fold {
+            self.expanded.insert(first);
+            self.rows_dirty = true;
+        }
+    }
+}
+
+/// Paint the background of a row and its text, clipped to the row.
+fn paint_line(ui: &Ui, rect: Rect, job: Option<LayoutJob>, background: Color32) {
+    if !ui.is_rect_visible(rect) {
+        return;
+    }
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, background);
+    if let Some(job) = job {
+        let galley = ui.fonts(|f| f.layout_job(job));
+        painter.galley(rect.min, galley, ui.visuals().text_color());
+    }
+}
+
+#[test]
+fn test_diff() {
+    let old = ["a", "b", "c", "d"];
+    let new = ["a", "c", "x", "d"];
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            Edit::Equal(0, 0),
+            Edit::Delete(1),
+            Edit::Equal(2, 1),
+            Edit::Insert(2),
+            Edit::Equal(3, 3),
+        ]
+    );
+
+    let (old_changed, new_changed) = word_diff("let x = 1;", "let y = 1;");
+    assert_eq!(old_changed, vec![4..5]);
+    assert_eq!(new_changed, vec![4..5]);
+
+    let old: String = (1..=20).map(|i| format!("{i}\n")).collect();
+    let new = old.replace("10\n", "ten\n");
+    let mut diff_view = DiffView::new(&old, &new).mode(DiffMode::Unified);
+    diff_view.update_rows();
+    assert_eq!(diff_view.num_added(), 1);
+    assert_eq!(diff_view.num_removed(), 1);
+    assert_eq!(diff_view.rows[0], Row::Fold { first: 0, count: 6 });
+    assert_eq!(
+        diff_view.rows.last(),
+        Some(&Row::Fold {
+            first: 14,
+            count: 7
+        })
+    );
+}
@@ -0,0 +1,388 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use egui::{
+    Color32, Id, Image, ImageSource, LayerId, Order, Pos2, Rect, Response, ScrollArea, Sense, Ui,
+    Vec2, WidgetInfo, WidgetType,
+};
+
+/// How many points per frame the view scrolls per point the pointer is outside of it,
+/// when dragging a selection band.
+const BAND_SCROLL_SPEED: f32 = 0.25;
+
+/// Where the thumbnails are.
+struct GridLayout {
+    /// Top left of the content.
+    origin: Pos2,
+    columns: usize,
+    cell_size: Vec2,
+    spacing: Vec2,
+    num_items: usize,
+}
+
+impl GridLayout {
+    /// As many columns as fit, sharing the remaining width.
+    fn new(
+        origin: Pos2,
+        width: f32,
+        thumbnail_size: Vec2,
+        spacing: Vec2,
+        num_items: usize,
+    ) -> Self {
+        let columns = ((width + spacing.x) / (thumbnail_size.x + spacing.x))
+            .floor()
+            .max(1.0) as usize;
+        let cell_width = (width + spacing.x) / columns as f32 - spacing.x;
+        Self {
+            origin,
+            columns,
+            cell_size: Vec2::new(cell_width.max(1.0), thumbnail_size.y),
+            spacing,
+            num_items,
+        }
+    }
+
+    fn stride(&self) -> Vec2 {
+        self.cell_size + self.spacing
+    }
+
+    fn num_rows(&self) -> usize {
+        (self.num_items + self.columns - 1) / self.columns
+    }
+
+    fn height(&self) -> f32 {
+        (self.num_rows() as f32 * self.stride().y - self.spacing.y).max(0.0)
+    }
+
+    fn item_rect(&self, index: usize) -> Rect {
+        let column = index % self.columns;
+        let row = index / self.columns;
+        Rect::from_min_size(
+            self.origin + self.stride() * Vec2::new(column as f32, row as f32),
+            self.cell_size,
+        )
+    }
+
+    /// The rows and columns of the items that intersect `rect`.
+    fn cells_in(&self, rect: Rect) -> (Range<usize>, Range<usize>) {
+        let rect = rect.translate(-self.origin.to_vec2());
+        let stride = self.stride();
+        let range = |min: f32, max: f32, stride: f32, len: usize| {
+            let start = (min / stride).floor().max(0.0) as usize;
+            let end = ((max / stride).ceil().max(0.0) as usize).min(len);
+            start.min(end)..end
+        };
+        (
+            range(rect.min.y, rect.max.y, stride.y, self.num_rows()),
+            range(rect.min.x, rect.max.x, stride.x, self.columns),
+        )
+    }
+
+    /// The items that intersect `rect`, in order.
+    fn items_in(&self, rect: Rect) -> impl Iterator<Item = usize> + '_ {
+        let (rows, columns) = self.cells_in(rect);
+        rows.flat_map(move |row| {
+            columns
+                .clone()
+                .map(move |column| row * self.columns + column)
+        })
+        .filter(move |&index| index < self.num_items && self.item_rect(index).intersects(rect))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct GalleryState {
+    /// Where shift-clicks extend the selection from.
+    anchor: Option<usize>,
+
+    /// Where the selection band started, relative to the top left of the content,
+    /// and the selection from before it.
+    band: Option<(Vec2, BTreeSet<usize>)>,
+}
+
+/// What [`Gallery::show`] returns.
+pub struct GalleryResponse {
+    /// The response of the space around the thumbnails.
+    pub response: Response,
+
+    /// Did the user change the selection this frame?
+    pub selection_changed: bool,
+
+    /// The item that was double-clicked, e.g. to open it.
+    pub activated: Option<usize>,
+
+    /// The items the user is dragging out of the gallery.
+    ///
+    /// Use this to highlight where they can be dropped.
+    pub dragging: Option<Vec<usize>>,
+
+    /// The items that were dragged out of the gallery, and where they were released.
+    ///
+    /// Check if the position is within the widget you want to drop them on.
+    pub dropped: Option<(Vec<usize>, Pos2)>,
+}
+
+/// A grid of image thumbnails, e.g. for an asset browser.
+///
+/// The thumbnails fill as many columns as fit in the available width.
+/// Only the visible ones are shown, and their images are loaded with [`egui::Image`]
+/// (using the installed image loaders) as they scroll into view, so this works for large collections too.
+///
+/// Click to select a thumbnail, ctrl/cmd-click to toggle it and shift-click to select a range.
+/// Drag in the space between the thumbnails to select them with a band (hold ctrl/cmd to add to the selection).
+/// Drag the selected thumbnails to drag them out, see [`GalleryResponse::dropped`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut selection = std::collections::BTreeSet::new();
+/// let uris: Vec<String> = (0..1000).map(|i| format!("file://assets/{i}.png")).collect();
+/// let response = egui_extras::Gallery::new(uris.len(), |i| uris[i].as_str().into(), &mut selection)
+///     .thumbnail_size(egui::vec2(64.0, 64.0))
+///     .show(ui);
+/// if let Some((items, pos)) = response.dropped {
+///     // …
+/// }
+/// # });
+/// ```
+pub struct Gallery<'a> {
+    id_source: Id,
+    num_items: usize,
+    image: Box<dyn Fn(usize) -> ImageSource<'a> + 'a>,
+    hover_text: Option<Box<dyn Fn(usize) -> String + 'a>>,
+    selection: &'a mut BTreeSet<usize>,
+    thumbnail_size: Vec2,
+}
+
+impl<'a> Gallery<'a> {
+    /// Show `num_items` thumbnails, with the images given by `image(index)`.
+    pub fn new(
+        num_items: usize,
+        image: impl Fn(usize) -> ImageSource<'a> + 'a,
+        selection: &'a mut BTreeSet<usize>,
+    ) -> Self {
+        Self {
+            id_source: Id::new("gallery"),
+            num_items,
+            image: Box::new(image),
+            hover_text: None,
+            selection,
+            thumbnail_size: Vec2::splat(96.0),
+        }
+    }
+
+    /// Needed if you show more than one [`Gallery`] in the same [`Ui`].
+    #[inline]
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Id::new(id_source);
+        self
+    }
+
+    /// The smallest size of each thumbnail. The width grows to fill the available width.
+    ///
+    /// Default: 96x96.
+    #[inline]
+    pub fn thumbnail_size(mut self, thumbnail_size: Vec2) -> Self {
+        self.thumbnail_size = thumbnail_size.max(Vec2::splat(1.0));
+        self
+    }
+
+    /// Shown when hovering a thumbnail, and to screen readers.
+    ///
+    /// Default: the uri of the image.
+    #[inline]
+    pub fn hover_text(mut self, hover_text: impl Fn(usize) -> String + 'a) -> Self {
+        self.hover_text = Some(Box::new(hover_text));
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> GalleryResponse {
+        crate::profile_function!();
+
+        let id = ui.make_persistent_id(self.id_source);
+        let mut state: GalleryState = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+        let spacing = ui.spacing().item_spacing;
+        let old_selection = self.selection.clone();
+        let mut activated = None;
+        let mut dragging = None;
+        let mut dropped = None;
+
+        let scroll_output = ScrollArea::vertical()
+            .id_source(id)
+            .auto_shrink([false, false])
+            .show_viewport(ui, |ui, viewport| {
+                let layout = GridLayout::new(
+                    ui.max_rect().min,
+                    ui.available_width(),
+                    self.thumbnail_size,
+                    spacing,
+                    self.num_items,
+                );
+                ui.set_height(layout.height());
+                let content_rect = ui.max_rect();
+                let (command, shift) = ui.input(|i| (i.modifiers.command, i.modifiers.shift));
+
+                // Behind the thumbnails, for the selection band:
+                let response = ui.interact(content_rect, id, Sense::click_and_drag());
+                let pointer = ui.input(|i| i.pointer.interact_pos());
+                if response.drag_started() {
+                    if let Some(pointer) = pointer {
+                        let base = if command {
+                            self.selection.clone()
+                        } else {
+                            BTreeSet::new()
+                        };
+                        state.band = Some((pointer - layout.origin, base));
+                    }
+                } else if response.clicked() && !command && !shift {
+                    self.selection.clear();
+                }
+                if !response.dragged() {
+                    state.band = None;
+                }
+                if let (Some((start, base)), Some(pointer)) = (&state.band, pointer) {
+                    let band = Rect::from_two_pos(layout.origin + *start, pointer);
+                    *self.selection = base.clone();
+                    self.selection.extend(layout.items_in(band));
+
+                    let selection_visuals = ui.visuals().selection;
+                    ui.painter().rect(
+                        band,
+                        0.0,
+                        selection_visuals.bg_fill.gamma_multiply(0.3),
+                        selection_visuals.stroke,
+                    );
+
+                    // Scroll when dragging outside the view:
+                    let clip_rect = ui.clip_rect();
+                    let outside = if pointer.y < clip_rect.top() {
+                        clip_rect.top() - pointer.y
+                    } else if clip_rect.bottom() < pointer.y {
+                        clip_rect.bottom() - pointer.y
+                    } else {
+                        0.0
+                    };
+                    if outside != 0.0 {
+                        ui.scroll_with_delta(Vec2::new(0.0, BAND_SCROLL_SPEED * outside));
+                        ui.ctx().request_repaint();
+                    }
+                }
+
+                let visible = viewport.translate(layout.origin.to_vec2());
+                for index in layout.items_in(visible) {
+                    let rect = layout.item_rect(index);
+                    let image = Image::new((self.image)(index)).show_loading_spinner(true);
+                    let hover_text = self.hover_text.as_ref().map_or_else(
+                        || image.source().uri().unwrap_or_default().to_owned(),
+                        |hover_text| hover_text(index),
+                    );
+
+                    let item_response = ui.interact(rect, id.with(index), Sense::click_and_drag());
+                    if item_response.clicked() || item_response.drag_started() {
+                        let anchor = state.anchor.filter(|_| shift).unwrap_or(index);
+                        let range = anchor.min(index)..=anchor.max(index);
+                        if command && !shift {
+                            if !self.selection.remove(&index) {
+                                self.selection.insert(index);
+                            }
+                        } else if shift {
+                            if !command {
+                                self.selection.clear();
+                            }
+                            self.selection.extend(range);
+                        } else if item_response.clicked() || !self.selection.contains(&index) {
+                            // Dragging a selected item drags the whole selection.
+                            self.selection.clear();
+                            self.selection.insert(index);
+                        }
+                        if !shift {
+                            state.anchor = Some(index);
+                        }
+                    }
+                    if item_response.double_clicked() {
+                        activated = Some(index);
+                    }
+                    if item_response.dragged() {
+                        let items: Vec<usize> = self.selection.iter().copied().collect();
+                        paint_drag_preview(ui, id, &image, items.len());
+                        dragging = Some(items);
+                    } else if item_response.drag_released() {
+                        if let Some(pos) = ui.input(|i| i.pointer.latest_pos()) {
+                            if !ui.clip_rect().contains(pos) {
+                                dropped = Some((self.selection.iter().copied().collect(), pos));
+                            }
+                        }
+                    }
+
+                    let selected = self.selection.contains(&index);
+                    item_response.widget_info(|| {
+                        WidgetInfo::selected(WidgetType::SelectableLabel, selected, &hover_text)
+                    });
+
+                    if ui.is_rect_visible(rect) {
+                        let visuals = ui.style().interact_selectable(&item_response, selected);
+                        if selected || item_response.hovered() {
+                            ui.painter().rect(
+                                rect,
+                                visuals.rounding,
+                                visuals.weak_bg_fill,
+                                visuals.bg_stroke,
+                            );
+                        }
+                        let image_rect = rect.shrink(ui.spacing().button_padding.y);
+                        let image_size = image
+                            .load_and_calc_size(ui, image_rect.size())
+                            .unwrap_or(image_rect.size());
+                        image.paint_at(ui, Rect::from_center_size(image_rect.center(), image_size));
+                    }
+                    if !hover_text.is_empty() && dragging.is_none() {
+                        item_response.on_hover_text(hover_text);
+                    }
+                }
+
+                response
+            });
+
+        ui.data_mut(|d| d.insert_temp(id, state));
+
+        let selection_changed = *self.selection != old_selection;
+        let mut response = scroll_output.inner;
+        if selection_changed {
+            response.mark_changed();
+        }
+        GalleryResponse {
+            response,
+            selection_changed,
+            activated,
+            dragging,
+            dropped,
+        }
+    }
+}
+
+/// Show the dragged thumbnail and how many items are dragged next to the mouse cursor.
+fn paint_drag_preview(ui: &mut Ui, id: Id, image: &Image<'_>, count: usize) {
+    let Some(pointer) = ui.input(|i| i.pointer.hover_pos()) else {
+        return;
+    };
+    let layer_id = LayerId::new(Order::Tooltip, id.with("drag_preview"));
+    ui.with_layer_id(layer_id, |ui| {
+        ui.set_clip_rect(ui.ctx().screen_rect());
+        let size = Vec2::splat(2.0 * ui.spacing().interact_size.y);
+        let rect = Rect::from_min_size(pointer + Vec2::splat(8.0), size);
+        image.paint_at(ui, rect);
+        if 1 < count {
+            let visuals = ui.visuals();
+            let font_id = egui::TextStyle::Small.resolve(ui.style());
+            let badge_center = rect.right_top();
+            ui.painter()
+                .circle_filled(badge_center, 0.25 * size.y, visuals.selection.bg_fill);
+            ui.painter().text(
+                badge_center,
+                egui::Align2::CENTER_CENTER,
+                count.to_string(),
+                font_id,
+                Color32::WHITE,
+            );
+        }
+    });
+}
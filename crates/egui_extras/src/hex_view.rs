@@ -0,0 +1,589 @@
+use std::ops::Range;
+
+use egui::{
+    text::{LayoutJob, TextFormat},
+    Color32, Event, EventFilter, FontId, Id, Key, NumExt as _, Rect, Response, ScrollArea, Sense,
+    Stroke, TextStyle, Ui, Vec2,
+};
+
+/// Bytes shown by a [`HexView`] that are not all in memory, e.g. a file or the memory of another process.
+pub trait HexSource {
+    /// The total number of bytes.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read the bytes starting at `offset` into `buf`, returning how many were read.
+    ///
+    /// Only the visible rows are read each frame.
+    fn read(&mut self, offset: usize, buf: &mut [u8]) -> usize;
+
+    /// Change the byte at `offset`, returning `false` if it can't be changed.
+    ///
+    /// The default implementation is read-only.
+    fn write(&mut self, offset: usize, byte: u8) -> bool {
+        _ = (offset, byte);
+        false
+    }
+}
+
+fn read_slice(bytes: &[u8], offset: usize, buf: &mut [u8]) -> usize {
+    let bytes = bytes.get(offset..).unwrap_or_default();
+    let n = bytes.len().min(buf.len());
+    buf[..n].copy_from_slice(&bytes[..n]);
+    n
+}
+
+enum HexData<'a> {
+    Bytes(&'a [u8]),
+    BytesMut(&'a mut [u8]),
+    Source(&'a mut dyn HexSource),
+}
+
+impl HexData<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Bytes(bytes) => bytes.len(),
+            Self::BytesMut(bytes) => bytes.len(),
+            Self::Source(source) => source.len(),
+        }
+    }
+
+    fn read(&mut self, offset: usize, buf: &mut [u8]) -> usize {
+        match self {
+            Self::Bytes(bytes) => read_slice(bytes, offset, buf),
+            Self::BytesMut(bytes) => read_slice(bytes, offset, buf),
+            Self::Source(source) => source.read(offset, buf),
+        }
+    }
+
+    fn read_byte(&mut self, offset: usize) -> Option<u8> {
+        let mut buf = [0];
+        (self.read(offset, &mut buf) == 1).then_some(buf[0])
+    }
+
+    fn write(&mut self, offset: usize, byte: u8) -> bool {
+        match self {
+            Self::Bytes(_) => false,
+            Self::BytesMut(bytes) => {
+                if let Some(b) = bytes.get_mut(offset) {
+                    *b = byte;
+                    true
+                } else {
+                    false
+                }
+            }
+            Self::Source(source) => source.write(offset, byte),
+        }
+    }
+}
+
+/// Which side of the view the cursor is in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Pane {
+    #[default]
+    Hex,
+    Ascii,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct HexViewState {
+    /// Where the selection started.
+    anchor: usize,
+
+    /// Where the selection ends, and where typing goes.
+    cursor: usize,
+
+    /// Is the next hex digit typed the low nibble of the byte at the cursor?
+    low_nibble: bool,
+
+    pane: Pane,
+}
+
+impl HexViewState {
+    fn selection(&self) -> Range<usize> {
+        self.anchor.min(self.cursor)..self.anchor.max(self.cursor) + 1
+    }
+
+    fn move_cursor(&mut self, cursor: usize, extend_selection: bool) {
+        self.cursor = cursor;
+        if !extend_selection {
+            self.anchor = cursor;
+        }
+        self.low_nibble = false;
+    }
+}
+
+/// Where things are in a row, in characters.
+struct RowColumns {
+    address_digits: usize,
+    bytes_per_row: usize,
+}
+
+impl RowColumns {
+    fn hex_start(&self) -> usize {
+        self.address_digits + 2
+    }
+
+    /// Column of the first nibble of the byte with this index in the row.
+    ///
+    /// There is an extra space after every 8 bytes.
+    fn hex(&self, i: usize) -> usize {
+        self.hex_start() + i * 3 + i / 8
+    }
+
+    fn ascii_start(&self) -> usize {
+        self.hex(self.bytes_per_row - 1) + 4
+    }
+
+    fn ascii(&self, i: usize) -> usize {
+        self.ascii_start() + i
+    }
+
+    fn width(&self) -> usize {
+        self.ascii(self.bytes_per_row)
+    }
+
+    /// The byte in the row, nibble and pane at this column.
+    fn hit(&self, column: f32) -> (usize, bool, Pane) {
+        if column >= self.ascii_start() as f32 - 1.0 {
+            let i = (column - self.ascii_start() as f32).max(0.0) as usize;
+            return (i.min(self.bytes_per_row - 1), false, Pane::Ascii);
+        }
+        for i in 0..self.bytes_per_row {
+            let start = self.hex(i) as f32;
+            if column < start + 2.5 {
+                return (i, column >= start + 1.0, Pane::Hex);
+            }
+        }
+        (self.bytes_per_row - 1, true, Pane::Hex)
+    }
+}
+
+/// What [`HexView::show`] returns.
+pub struct HexViewResponse {
+    /// The response of the rows.
+    pub response: Response,
+
+    /// The selected bytes.
+    pub selection: Range<usize>,
+
+    /// Was a byte changed by the user this frame?
+    pub changed: bool,
+}
+
+/// A hex editor: shows bytes as hex and ASCII, one row at a time.
+///
+/// Only the visible rows are read and shown, so this works for large data too.
+///
+/// Click or drag to select bytes (hold shift to extend the selection),
+/// and use the arrow keys, Home/End and PageUp/PageDown to move.
+/// Copying gives the selection as hex, or as text if the ASCII pane has the cursor.
+/// If the data is editable, type hex digits (or characters in the ASCII pane) to change it.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let mut bytes = b"Hello hex world!".to_vec();
+/// let original = bytes.clone();
+/// let response = egui_extras::HexView::editable(&mut bytes)
+///     .bytes_per_row(8)
+///     .compare_with(&original)
+///     .show(ui);
+/// if response.changed {
+///     // …
+/// }
+/// # });
+/// ```
+pub struct HexView<'a> {
+    id_source: Id,
+    data: HexData<'a>,
+    bytes_per_row: usize,
+    original: Option<&'a [u8]>,
+}
+
+impl<'a> HexView<'a> {
+    /// Show bytes that can't be changed.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_data(HexData::Bytes(bytes))
+    }
+
+    /// Show bytes that the user can change.
+    pub fn editable(bytes: &'a mut [u8]) -> Self {
+        Self::with_data(HexData::BytesMut(bytes))
+    }
+
+    /// Show bytes from a [`HexSource`], e.g. a file.
+    pub fn from_source(source: &'a mut dyn HexSource) -> Self {
+        Self::with_data(HexData::Source(source))
+    }
+
+    fn with_data(data: HexData<'a>) -> Self {
+        Self {
+            id_source: Id::new("hex_view"),
+            data,
+            bytes_per_row: 16,
+            original: None,
+        }
+    }
+
+    /// Needed if you show more than one [`HexView`] in the same [`Ui`].
+    #[inline]
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Id::new(id_source);
+        self
+    }
+
+    /// How many bytes to show on each row.
+    ///
+    /// Default: 16.
+    #[inline]
+    pub fn bytes_per_row(mut self, bytes_per_row: usize) -> Self {
+        self.bytes_per_row = bytes_per_row.max(1);
+        self
+    }
+
+    /// Highlight the bytes that differ from these, e.g. a copy from before editing.
+    #[inline]
+    pub fn compare_with(mut self, original: &'a [u8]) -> Self {
+        self.original = Some(original);
+        self
+    }
+
+    pub fn show(mut self, ui: &mut Ui) -> HexViewResponse {
+        let id = ui.make_persistent_id(self.id_source);
+        let mut state: HexViewState = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+
+        let len = self.data.len();
+        let bytes_per_row = self.bytes_per_row;
+        let num_rows = (len + bytes_per_row - 1) / bytes_per_row;
+        let max_offset = len.saturating_sub(1);
+        state.cursor = state.cursor.min(max_offset);
+        state.anchor = state.anchor.min(max_offset);
+
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        let (char_width, row_height) = ui.fonts(|f| {
+            (
+                f.glyph_width(&font_id, '0'),
+                f.row_height(&font_id).at_least(1.0),
+            )
+        });
+        let columns = RowColumns {
+            address_digits: format!("{max_offset:X}").len().max(8),
+            bytes_per_row,
+        };
+
+        let has_focus = ui.memory(|m| m.has_focus(id));
+        let mut changed = false;
+        let mut scroll_to_cursor = false;
+        if has_focus {
+            ui.memory_mut(|m| {
+                m.set_focus_lock_filter(
+                    id,
+                    EventFilter {
+                        horizontal_arrows: true,
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                );
+            });
+            let page_rows = (ui.available_height() / row_height).max(1.0) as usize;
+            (changed, scroll_to_cursor) =
+                self.handle_events(ui, &mut state, len, page_rows, &columns);
+        }
+
+        let scroll_output = ui
+            .scope(|ui| {
+                ui.spacing_mut().item_spacing.y = 0.0;
+                ScrollArea::both()
+                    .id_source(id)
+                    .auto_shrink([false, false])
+                    .show_rows(ui, row_height, num_rows, |ui, row_range| {
+                        let row_size = Vec2::new(columns.width() as f32 * char_width, row_height);
+                        let mut buf = vec![0; bytes_per_row];
+                        let mut rows_rect = Rect::NOTHING;
+                        let mut origin = ui.max_rect().min;
+
+                        for row in row_range {
+                            let (rect, _) = ui.allocate_exact_size(row_size, Sense::hover());
+                            origin = rect.min - Vec2::new(0.0, row as f32 * row_height);
+                            rows_rect = rows_rect.union(rect);
+
+                            let offset = row * bytes_per_row;
+                            let n = self.data.read(offset, &mut buf);
+                            self.paint_row(
+                                ui,
+                                rect,
+                                offset,
+                                &buf[..n],
+                                &state,
+                                has_focus,
+                                &columns,
+                                &font_id,
+                            );
+                        }
+
+                        if scroll_to_cursor {
+                            let row = state.cursor / bytes_per_row;
+                            let row_rect = Rect::from_min_size(
+                                origin + Vec2::new(0.0, row as f32 * row_height),
+                                row_size,
+                            );
+                            ui.scroll_to_rect(row_rect, None);
+                        }
+
+                        let response = ui.interact(rows_rect, id, Sense::click_and_drag());
+                        if len > 0 && response.is_pointer_button_down_on() {
+                            if let Some(pos) = ui.input(|i| i.pointer.interact_pos()) {
+                                let (pressed, shift) =
+                                    ui.input(|i| (i.pointer.primary_pressed(), i.modifiers.shift));
+                                let pos = pos - origin;
+                                let row = (pos.y / row_height).max(0.0) as usize;
+                                let (i, low_nibble, pane) = columns.hit(pos.x / char_width);
+                                let offset = (row * bytes_per_row + i).min(max_offset);
+                                state.move_cursor(offset, !pressed || shift);
+                                if pressed {
+                                    state.low_nibble = low_nibble && pane == Pane::Hex;
+                                    state.pane = pane;
+                                    response.request_focus();
+                                }
+                            }
+                        }
+                        response
+                    })
+            })
+            .inner;
+
+        ui.data_mut(|d| d.insert_temp(id, state));
+
+        let mut response = scroll_output.inner;
+        if changed {
+            response.mark_changed();
+        }
+        HexViewResponse {
+            response,
+            selection: if len == 0 { 0..0 } else { state.selection() },
+            changed,
+        }
+    }
+
+    /// Handle the keyboard while focused.
+    ///
+    /// Returns whether the data changed and whether the cursor moved.
+    fn handle_events(
+        &mut self,
+        ui: &Ui,
+        state: &mut HexViewState,
+        len: usize,
+        page_rows: usize,
+        columns: &RowColumns,
+    ) -> (bool, bool) {
+        let mut changed = false;
+        let mut moved = false;
+        if len == 0 {
+            return (changed, moved);
+        }
+        let bytes_per_row = columns.bytes_per_row;
+        let max_offset = len - 1;
+
+        for event in ui.input(|i| i.events.clone()) {
+            match event {
+                Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => {
+                    let cursor = state.cursor;
+                    let new_cursor = match key {
+                        Key::ArrowLeft => cursor.saturating_sub(1),
+                        Key::ArrowRight => (cursor + 1).min(max_offset),
+                        Key::ArrowUp => cursor.saturating_sub(bytes_per_row),
+                        Key::ArrowDown => (cursor + bytes_per_row).min(max_offset),
+                        Key::PageUp => cursor.saturating_sub(page_rows * bytes_per_row),
+                        Key::PageDown => (cursor + page_rows * bytes_per_row).min(max_offset),
+                        Key::Home if modifiers.command => 0,
+                        Key::End if modifiers.command => max_offset,
+                        Key::Home => cursor - cursor % bytes_per_row,
+                        Key::End => {
+                            (cursor - cursor % bytes_per_row + bytes_per_row - 1).min(max_offset)
+                        }
+                        _ => continue,
+                    };
+                    state.move_cursor(new_cursor, modifiers.shift);
+                    moved = true;
+                }
+                Event::Text(text) => {
+                    for ch in text.chars() {
+                        let cursor = state.cursor;
+                        let Some(old) = self.data.read_byte(cursor) else {
+                            break;
+                        };
+                        let new = match state.pane {
+                            Pane::Hex => {
+                                let Some(digit) = ch.to_digit(16) else {
+                                    continue;
+                                };
+                                if state.low_nibble {
+                                    (old & 0xF0) | digit as u8
+                                } else {
+                                    (old & 0x0F) | ((digit as u8) << 4)
+                                }
+                            }
+                            Pane::Ascii => {
+                                if !ch.is_ascii() || ch.is_ascii_control() {
+                                    continue;
+                                }
+                                ch as u8
+                            }
+                        };
+                        if !self.data.write(cursor, new) {
+                            break;
+                        }
+                        changed = true;
+                        moved = true;
+                        if state.pane == Pane::Hex && !state.low_nibble {
+                            state.anchor = cursor;
+                            state.low_nibble = true;
+                        } else {
+                            state.move_cursor((cursor + 1).min(max_offset), false);
+                        }
+                    }
+                }
+                Event::Copy => {
+                    let selection = state.selection();
+                    let mut bytes = vec![0; selection.len()];
+                    let n = self.data.read(selection.start, &mut bytes);
+                    bytes.truncate(n);
+                    let text = match state.pane {
+                        Pane::Hex => bytes
+                            .iter()
+                            .map(|b| format!("{b:02X}"))
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                        Pane::Ascii => bytes.iter().map(|&b| ascii_char(b)).collect(),
+                    };
+                    ui.ctx().copy_text(text);
+                }
+                _ => {}
+            }
+        }
+
+        (changed, moved)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn paint_row(
+        &self,
+        ui: &Ui,
+        rect: Rect,
+        offset: usize,
+        bytes: &[u8],
+        state: &HexViewState,
+        has_focus: bool,
+        columns: &RowColumns,
+        font_id: &FontId,
+    ) {
+        let visuals = ui.visuals();
+        let painter = ui.painter();
+        let char_width = rect.width() / columns.width() as f32;
+        let cell_rect = |column: usize, num_chars: usize| {
+            Rect::from_min_size(
+                rect.min + Vec2::new(column as f32 * char_width, 0.0),
+                Vec2::new(num_chars as f32 * char_width, rect.height()),
+            )
+        };
+
+        // Selection and cursor:
+        let selection = state.selection();
+        for (i, _) in bytes.iter().enumerate() {
+            let byte_offset = offset + i;
+            if selection.contains(&byte_offset) {
+                let fill = visuals.selection.bg_fill;
+                painter.rect_filled(cell_rect(columns.hex(i), 2), 0.0, fill);
+                painter.rect_filled(cell_rect(columns.ascii(i), 1), 0.0, fill);
+            }
+            if has_focus && byte_offset == state.cursor {
+                let cursor_rect = match state.pane {
+                    Pane::Hex => cell_rect(columns.hex(i) + usize::from(state.low_nibble), 1),
+                    Pane::Ascii => cell_rect(columns.ascii(i), 1),
+                };
+                painter.rect_stroke(
+                    cursor_rect,
+                    0.0,
+                    Stroke::new(1.0, visuals.strong_text_color()),
+                );
+            }
+        }
+
+        let format = |color: Color32| TextFormat::simple(font_id.clone(), color);
+        let byte_color = |i: usize, byte: u8| {
+            let original = self.original.map(|original| original.get(offset + i));
+            if original.map_or(false, |original| original != Some(&byte)) {
+                visuals.warn_fg_color
+            } else if byte == 0 {
+                visuals.weak_text_color()
+            } else {
+                visuals.text_color()
+            }
+        };
+
+        let mut job = LayoutJob::default();
+        job.wrap.max_width = f32::INFINITY;
+        job.append(
+            &format!("{offset:0width$X}", width = columns.address_digits),
+            0.0,
+            format(visuals.weak_text_color()),
+        );
+        let mut column = columns.address_digits;
+        for (i, &byte) in bytes.iter().enumerate() {
+            let spaces = columns.hex(i) - column;
+            job.append(
+                &format!("{:spaces$}{byte:02X}", ""),
+                0.0,
+                format(byte_color(i, byte)),
+            );
+            column = columns.hex(i) + 2;
+        }
+        let spaces = columns.ascii_start() - column;
+        job.append(&" ".repeat(spaces), 0.0, format(visuals.text_color()));
+        for (i, &byte) in bytes.iter().enumerate() {
+            job.append(
+                &ascii_char(byte).to_string(),
+                0.0,
+                format(byte_color(i, byte)),
+            );
+        }
+
+        let galley = ui.fonts(|f| f.layout_job(job));
+        painter.galley(rect.min, galley, visuals.text_color());
+    }
+}
+
+/// How a byte is shown in the ASCII pane.
+fn ascii_char(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+#[test]
+fn test_row_columns() {
+    let columns = RowColumns {
+        address_digits: 8,
+        bytes_per_row: 16,
+    };
+    // "00000000  00 01 02 03 04 05 06 07  08 09 0A 0B 0C 0D 0E 0F  ................"
+    assert_eq!(columns.hex(0), 10);
+    assert_eq!(columns.hex(8), 35);
+    assert_eq!(columns.ascii_start(), 60);
+    assert_eq!(columns.width(), 76);
+
+    assert_eq!(columns.hit(10.5), (0, false, Pane::Hex));
+    assert_eq!(columns.hit(11.5), (0, true, Pane::Hex));
+    assert_eq!(columns.hit(36.0), (8, true, Pane::Hex));
+    assert_eq!(columns.hit(62.5), (2, false, Pane::Ascii));
+}
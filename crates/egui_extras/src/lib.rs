@@ -11,8 +11,12 @@
 #![cfg_attr(feature = "puffin", deny(unsafe_code))]
 #![cfg_attr(not(feature = "puffin"), forbid(unsafe_code))]
 
+mod cell_grid;
 #[cfg(feature = "chrono")]
 mod datepicker;
+mod diff_view;
+mod gallery;
+mod hex_view;
 
 pub mod syntax_highlighting;
 
@@ -20,17 +24,23 @@ pub mod syntax_highlighting;
 pub mod image;
 mod layout;
 mod loaders;
+mod log_console;
 mod sizing;
 mod strip;
 mod table;
 
+pub use crate::cell_grid::{CellAttributes, CellGrid, CursorShape, GridCell};
 #[cfg(feature = "chrono")]
 pub use crate::datepicker::DatePickerButton;
+pub use crate::diff_view::{DiffKind, DiffLine, DiffMode, DiffView};
+pub use crate::gallery::{Gallery, GalleryResponse};
+pub use crate::hex_view::{HexSource, HexView, HexViewResponse};
 
 #[doc(hidden)]
 #[allow(deprecated)]
 pub use crate::image::RetainedImage;
 pub(crate) use crate::layout::StripLayout;
+pub use crate::log_console::{AnsiColor, AnsiStyle, LogConsole, LogLine};
 pub use crate::sizing::Size;
 pub use crate::strip::*;
 pub use crate::table::*;
@@ -0,0 +1,638 @@
+use std::collections::VecDeque;
+
+use egui::{
+    emath::NumExt as _,
+    text::{LayoutJob, TextFormat},
+    Color32, FontId, Label, ScrollArea, Sense, Stroke, TextEdit, TextStyle, Ui, Visuals,
+};
+
+/// A color set with an ANSI escape code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnsiColor {
+    /// One of the 256 colors of the xterm palette.
+    /// The first 16 are the standard and bright colors.
+    Indexed(u8),
+
+    /// A 24-bit color.
+    Rgb(u8, u8, u8),
+}
+
+impl AnsiColor {
+    pub fn to_color32(self) -> Color32 {
+        match self {
+            Self::Indexed(index) => palette_color(index),
+            Self::Rgb(r, g, b) => Color32::from_rgb(r, g, b),
+        }
+    }
+}
+
+/// The xterm 256-color palette.
+fn palette_color(index: u8) -> Color32 {
+    const STANDARD: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 49, 49),
+        (13, 188, 121),
+        (229, 229, 16),
+        (36, 114, 200),
+        (188, 63, 188),
+        (17, 168, 205),
+        (229, 229, 229),
+        (102, 102, 102),
+        (241, 76, 76),
+        (35, 209, 139),
+        (245, 245, 67),
+        (59, 142, 234),
+        (214, 112, 214),
+        (41, 184, 219),
+        (255, 255, 255),
+    ];
+    match index {
+        0..=15 => {
+            let (r, g, b) = STANDARD[index as usize];
+            Color32::from_rgb(r, g, b)
+        }
+        16..=231 => {
+            // 6x6x6 color cube
+            let level = |i: u8| if i == 0 { 0 } else { 55 + 40 * i };
+            let i = index - 16;
+            Color32::from_rgb(level(i / 36), level((i / 6) % 6), level(i % 6))
+        }
+        232..=255 => {
+            let gray = 8 + 10 * (index - 232);
+            Color32::from_gray(gray)
+        }
+    }
+}
+
+/// The text style set by ANSI "Select Graphic Rendition" escape codes, e.g. `ESC[1;31m`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    pub foreground: Option<AnsiColor>,
+    pub background: Option<AnsiColor>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italics: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    pub inverse: bool,
+}
+
+impl AnsiStyle {
+    /// Apply the parameters of an SGR escape code (the `1;31` in `ESC[1;31m`).
+    fn apply_sgr(&mut self, params: &str) {
+        let mut params = params
+            .split(';')
+            .map(|param| param.parse::<u8>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italics = true,
+                4 => self.underline = true,
+                7 => self.inverse = true,
+                9 => self.strikethrough = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italics = false,
+                24 => self.underline = false,
+                27 => self.inverse = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.foreground = Some(AnsiColor::Indexed(param - 30)),
+                38 => self.foreground = extended_color(&mut params),
+                39 => self.foreground = None,
+                40..=47 => self.background = Some(AnsiColor::Indexed(param - 40)),
+                48 => self.background = extended_color(&mut params),
+                49 => self.background = None,
+                90..=97 => self.foreground = Some(AnsiColor::Indexed(param - 90 + 8)),
+                100..=107 => self.background = Some(AnsiColor::Indexed(param - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+
+    fn text_format(&self, font_id: FontId, visuals: &Visuals) -> TextFormat {
+        let mut color = match self.foreground {
+            // Like most terminals, show bold text in the bright version of the standard colors:
+            Some(AnsiColor::Indexed(index)) if self.bold && index < 8 => palette_color(index + 8),
+            Some(color) => color.to_color32(),
+            None if self.bold => visuals.strong_text_color(),
+            None => visuals.text_color(),
+        };
+        let mut background = self
+            .background
+            .map_or(Color32::TRANSPARENT, AnsiColor::to_color32);
+        if self.inverse {
+            let inverse_color = if background == Color32::TRANSPARENT {
+                visuals.extreme_bg_color
+            } else {
+                background
+            };
+            background = color;
+            color = inverse_color;
+        }
+        if self.dim {
+            color = color.gamma_multiply(0.6);
+        }
+        TextFormat {
+            font_id,
+            color,
+            background,
+            italics: self.italics,
+            underline: if self.underline {
+                Stroke::new(1.0, color)
+            } else {
+                Stroke::NONE
+            },
+            strikethrough: if self.strikethrough {
+                Stroke::new(1.0, color)
+            } else {
+                Stroke::NONE
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// `5;n` (palette) or `2;r;g;b` (24-bit), following a `38` or `48`.
+fn extended_color(params: &mut impl Iterator<Item = u8>) -> Option<AnsiColor> {
+    match params.next()? {
+        5 => Some(AnsiColor::Indexed(params.next()?)),
+        2 => Some(AnsiColor::Rgb(
+            params.next()?,
+            params.next()?,
+            params.next()?,
+        )),
+        _ => None,
+    }
+}
+
+/// One line of a [`LogConsole`], with the escape codes removed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogLine {
+    /// The text, without escape codes.
+    pub text: String,
+
+    /// Where each style starts, as byte offsets into [`Self::text`], in order.
+    pub styles: Vec<(usize, AnsiStyle)>,
+}
+
+impl LogLine {
+    fn push(&mut self, c: char, style: AnsiStyle) {
+        if self.styles.last().map(|(_, last)| last) != Some(&style) {
+            self.styles.push((self.text.len(), style));
+        }
+        self.text.push(c);
+    }
+
+    /// The style at this byte offset.
+    fn style_at(&self, offset: usize) -> AnsiStyle {
+        let index = self.styles.partition_point(|&(start, _)| start <= offset);
+        index
+            .checked_sub(1)
+            .map_or_else(AnsiStyle::default, |i| self.styles[i].1)
+    }
+
+    fn layout_job(&self, search: &str, font_id: &FontId, visuals: &Visuals) -> LayoutJob {
+        let matches: Vec<[usize; 2]> = find_ignore_ascii_case(&self.text, search)
+            .map(|start| [start, start + search.len()])
+            .collect();
+
+        // Split the text wherever the style or the search highlighting changes:
+        let mut boundaries: Vec<usize> = self.styles.iter().map(|&(start, _)| start).collect();
+        boundaries.extend(matches.iter().flatten());
+        boundaries.push(0);
+        boundaries.push(self.text.len());
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut job = LayoutJob::default();
+        for range in boundaries.windows(2) {
+            let (start, end) = (range[0], range[1]);
+            let mut format = self.style_at(start).text_format(font_id.clone(), visuals);
+            if matches
+                .iter()
+                .any(|&[m_start, m_end]| m_start <= start && end <= m_end)
+            {
+                format.background = visuals.selection.bg_fill;
+                format.color = visuals.selection.stroke.color;
+            }
+            job.append(&self.text[start..end], 0.0, format);
+        }
+        job
+    }
+}
+
+/// Byte offsets of all non-overlapping matches of `needle`, ignoring ASCII case.
+fn find_ignore_ascii_case<'a>(
+    haystack: &'a str,
+    needle: &'a str,
+) -> impl Iterator<Item = usize> + 'a {
+    let mut next_start = 0;
+    haystack.char_indices().filter_map(move |(start, _)| {
+        let end = start + needle.len();
+        let is_match = !needle.is_empty()
+            && next_start <= start
+            && haystack.is_char_boundary(end.min(haystack.len()))
+            && haystack
+                .as_bytes()
+                .get(start..end)
+                .map_or(false, |bytes| bytes.eq_ignore_ascii_case(needle.as_bytes()));
+        if is_match {
+            next_start = end;
+            Some(start)
+        } else {
+            None
+        }
+    })
+}
+
+/// Where we are in an escape sequence.
+#[derive(Clone, Debug, Default)]
+enum ParseState {
+    #[default]
+    Text,
+
+    /// After `ESC`.
+    Escape,
+
+    /// In a "Control Sequence Introducer" sequence: `ESC[`, followed by parameters.
+    Csi(String),
+
+    /// In an "Operating System Command" sequence, e.g. setting the window title: `ESC]`.
+    Osc,
+
+    /// After `ESC` in an OSC sequence, which may be the `ESC\` terminating it.
+    OscEscape,
+}
+
+/// The line numbers of the lines matching a search, updated as lines are added.
+#[derive(Clone, Debug, Default)]
+struct SearchCache {
+    search: String,
+
+    /// We have checked all complete lines before this line number.
+    checked_to: u64,
+
+    /// Sorted line numbers of the complete lines matching the search.
+    matching: Vec<u64>,
+
+    /// Does the open last line match the search?
+    open_line_matches: bool,
+}
+
+/// A scrolling console showing lines of text colored with ANSI escape codes, e.g. the output of a process.
+///
+/// The console only keeps the last [`Self::max_lines`] lines, and only lays out the visible ones,
+/// so it can be used for very long logs.
+///
+/// It has a toolbar for searching, filtering the lines by the search text,
+/// following the end of the log as new lines arrive, and copying the lines.
+///
+/// Store the [`LogConsole`] in your app, add text to it with [`Self::push_str`]
+/// (or with [`write!`], since it implements [`std::fmt::Write`]), and show it with [`Self::ui`]:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let mut console = egui_extras::LogConsole::default();
+/// console.push_str("\x1b[32mINFO\x1b[0m Starting up\n");
+/// console.push_str("\x1b[1;31mERROR\x1b[0m Something went wrong\n");
+/// console.ui(ui);
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogConsole {
+    lines: VecDeque<LogLine>,
+
+    /// The line number of the first line in `lines`.
+    first_line_nr: u64,
+
+    /// Is the last line still being written (not ended with a newline)?
+    last_line_open: bool,
+
+    max_lines: usize,
+    parse_state: ParseState,
+    style: AnsiStyle,
+
+    search: String,
+    filter: bool,
+    follow_tail: bool,
+    search_cache: SearchCache,
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        Self {
+            lines: Default::default(),
+            first_line_nr: 0,
+            last_line_open: false,
+            max_lines: 100_000,
+            parse_state: Default::default(),
+            style: Default::default(),
+            search: Default::default(),
+            filter: false,
+            follow_tail: true,
+            search_cache: Default::default(),
+        }
+    }
+}
+
+impl LogConsole {
+    /// Only keep this many lines, dropping the oldest ones.
+    ///
+    /// Default: `100_000`.
+    #[inline]
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines.max(1);
+        self
+    }
+
+    /// The number of lines in the console.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// The lines in the console, oldest first.
+    pub fn lines(&self) -> impl ExactSizeIterator<Item = &LogLine> + '_ {
+        self.lines.iter()
+    }
+
+    /// Remove all lines.
+    pub fn clear(&mut self) {
+        self.first_line_nr += self.lines.len() as u64;
+        self.lines.clear();
+        self.last_line_open = false;
+    }
+
+    /// Add text, which may contain ANSI escape codes.
+    ///
+    /// The text doesn't need to consist of whole lines:
+    /// the last line stays open until a newline is pushed,
+    /// and escape codes may be split over several calls.
+    pub fn push_str(&mut self, text: &str) {
+        for c in text.chars() {
+            self.parse_state = match std::mem::take(&mut self.parse_state) {
+                ParseState::Text => match c {
+                    '\x1b' => ParseState::Escape,
+                    '\n' => {
+                        self.open_line();
+                        self.last_line_open = false;
+                        ParseState::Text
+                    }
+                    '\r' => ParseState::Text,
+                    c if c == '\t' || !c.is_control() => {
+                        self.open_line();
+                        if let Some(line) = self.lines.back_mut() {
+                            line.push(c, self.style);
+                        }
+                        ParseState::Text
+                    }
+                    _ => ParseState::Text,
+                },
+                ParseState::Escape => match c {
+                    '[' => ParseState::Csi(String::new()),
+                    ']' => ParseState::Osc,
+                    _ => ParseState::Text,
+                },
+                ParseState::Csi(mut params) => {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        // The final byte of the sequence. We only care about colors and such:
+                        if c == 'm' {
+                            self.style.apply_sgr(&params);
+                        }
+                        ParseState::Text
+                    } else if params.len() < 64 {
+                        params.push(c);
+                        ParseState::Csi(params)
+                    } else {
+                        ParseState::Text // Malformed
+                    }
+                }
+                ParseState::Osc => match c {
+                    '\x07' => ParseState::Text,
+                    '\x1b' => ParseState::OscEscape,
+                    _ => ParseState::Osc,
+                },
+                ParseState::OscEscape => match c {
+                    '\\' => ParseState::Text,
+                    _ => ParseState::Osc,
+                },
+            };
+        }
+    }
+
+    /// Add a line of text, which may contain ANSI escape codes.
+    pub fn push_line(&mut self, line: &str) {
+        self.push_str(line);
+        self.push_str("\n");
+    }
+
+    /// Make sure the last line is open for writing.
+    fn open_line(&mut self) {
+        if !self.last_line_open {
+            self.lines.push_back(LogLine::default());
+            self.last_line_open = true;
+            while self.max_lines < self.lines.len() {
+                self.lines.pop_front();
+                self.first_line_nr += 1;
+            }
+        }
+    }
+
+    /// The number of lines that are complete (ended with a newline).
+    fn num_complete_lines(&self) -> usize {
+        self.lines.len() - usize::from(self.last_line_open)
+    }
+
+    fn line(&self, line_nr: u64) -> Option<&LogLine> {
+        let index = line_nr.checked_sub(self.first_line_nr)?;
+        self.lines.get(index as usize)
+    }
+
+    /// Are we only showing the lines matching the search?
+    fn is_filtering(&self) -> bool {
+        self.filter && !self.search.is_empty()
+    }
+
+    /// Check the new lines against the search, if we are filtering.
+    fn update_search_cache(&mut self) {
+        if !self.is_filtering() {
+            return;
+        }
+
+        let complete_to = self.first_line_nr + self.num_complete_lines() as u64;
+        let cache = &mut self.search_cache;
+        if cache.search != self.search {
+            *cache = SearchCache {
+                search: self.search.clone(),
+                checked_to: self.first_line_nr,
+                matching: vec![],
+                open_line_matches: false,
+            };
+        }
+
+        // Forget lines that have been dropped:
+        let dropped = cache
+            .matching
+            .partition_point(|&line_nr| line_nr < self.first_line_nr);
+        cache.matching.drain(..dropped);
+
+        let matches = |line: &LogLine| {
+            find_ignore_ascii_case(&line.text, &self.search)
+                .next()
+                .is_some()
+        };
+        for line_nr in cache.checked_to.max(self.first_line_nr)..complete_to {
+            if matches(&self.lines[(line_nr - self.first_line_nr) as usize]) {
+                cache.matching.push(line_nr);
+            }
+        }
+        cache.checked_to = complete_to;
+
+        // The open line may still change, so check it every time:
+        cache.open_line_matches = self.last_line_open && self.lines.back().map_or(false, matches);
+    }
+
+    /// The number of lines to show.
+    fn num_rows(&self) -> usize {
+        if self.is_filtering() {
+            self.search_cache.matching.len() + usize::from(self.search_cache.open_line_matches)
+        } else {
+            self.lines.len()
+        }
+    }
+
+    /// The line number of the line to show in this row.
+    fn row_line_nr(&self, row: usize) -> u64 {
+        if self.is_filtering() {
+            self.search_cache.matching.get(row).copied().unwrap_or(
+                // The open line:
+                self.first_line_nr + self.lines.len() as u64 - 1,
+            )
+        } else {
+            self.first_line_nr + row as u64
+        }
+    }
+
+    /// Show the toolbar and the lines.
+    pub fn ui(&mut self, ui: &mut Ui) {
+        self.update_search_cache();
+        let mut scroll_to_bottom = false;
+
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.search)
+                    .hint_text("🔍 Search")
+                    .desired_width(200.0),
+            );
+            ui.checkbox(&mut self.filter, "Filter")
+                .on_hover_text("Only show the lines matching the search");
+            if ui
+                .checkbox(&mut self.follow_tail, "Follow")
+                .on_hover_text("Keep scrolling to the newest lines")
+                .changed()
+            {
+                scroll_to_bottom = self.follow_tail;
+            }
+            if ui
+                .button("📋")
+                .on_hover_text("Copy the shown lines")
+                .clicked()
+            {
+                let text = (0..self.num_rows())
+                    .filter_map(|row| self.line(self.row_line_nr(row)))
+                    .map(|line| line.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                ui.ctx().copy_text(text);
+            }
+            ui.weak(format!("{} lines", self.num_rows()));
+        });
+
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        let row_height = ui.fonts(|f| f.row_height(&font_id)).at_least(1.0);
+        let search = self.search.as_str();
+
+        let mut scroll_area = ScrollArea::both()
+            .auto_shrink(false)
+            .stick_to_bottom(self.follow_tail);
+        if scroll_to_bottom {
+            // The offset is clamped to the end of the content:
+            scroll_area = scroll_area.vertical_scroll_offset(row_height * self.num_rows() as f32);
+        }
+
+        let output = ui
+            .scope(|ui| {
+                ui.spacing_mut().item_spacing.y = 0.0;
+                scroll_area.show_rows(ui, row_height, self.num_rows(), |ui, row_range| {
+                    let visuals = ui.visuals().clone();
+                    for row in row_range {
+                        let Some(line) = self.line(self.row_line_nr(row)) else {
+                            continue;
+                        };
+                        let job = line.layout_job(search, &font_id, &visuals);
+                        ui.add(Label::new(job).wrap(false).sense(Sense::click()))
+                            .context_menu(|ui| {
+                                if ui.button("Copy line").clicked() {
+                                    ui.ctx().copy_text(line.text.clone());
+                                    ui.close_menu();
+                                }
+                            });
+                    }
+                })
+            })
+            .inner;
+
+        // Follow the tail while the user is at the bottom, and stop when they scroll up:
+        let max_offset = (output.content_size.y - output.inner_rect.height()).max(0.0);
+        self.follow_tail = max_offset - row_height <= output.state.offset.y;
+    }
+}
+
+impl std::fmt::Write for LogConsole {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_log_console() {
+    let mut console = LogConsole::default().max_lines(2);
+    console.push_str("plain\n\x1b[1;3");
+    console.push_str("1mred\x1b[0m normal\x1b]0;title\x07\n");
+    console.push_str("\x1b[38;2;1;2;3mrgb");
+
+    assert_eq!(console.len(), 2);
+    let lines: Vec<&LogLine> = console.lines().collect();
+    assert_eq!(lines[0].text, "red normal");
+    assert_eq!(lines[0].styles.len(), 2);
+    assert_eq!(lines[0].styles[0].1.foreground, Some(AnsiColor::Indexed(1)));
+    assert!(lines[0].styles[0].1.bold);
+    assert_eq!(lines[0].styles[1], (3, AnsiStyle::default()));
+    assert_eq!(lines[1].text, "rgb");
+    assert_eq!(
+        lines[1].styles[0].1.foreground,
+        Some(AnsiColor::Rgb(1, 2, 3))
+    );
+
+    console.search = "NORMAL".to_owned();
+    console.filter = true;
+    let rows = |console: &mut LogConsole| {
+        console.update_search_cache();
+        (0..console.num_rows())
+            .map(|row| console.row_line_nr(row))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(rows(&mut console), vec![1]);
+    console.push_str(" normal\nmore\n");
+    assert_eq!(rows(&mut console), vec![2]);
+    console.push_str("normal"); // An open line, dropping line 2
+    assert_eq!(rows(&mut console), vec![4]);
+}
@@ -232,6 +232,7 @@ pub struct TableBuilder<'a> {
     cell_layout: egui::Layout,
     scroll_options: TableScrollOptions,
     sense: egui::Sense,
+    size_id: Option<egui::Id>,
 }
 
 impl<'a> TableBuilder<'a> {
@@ -245,6 +246,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options: Default::default(),
             sense: egui::Sense::hover(),
+            size_id: None,
         }
     }
 
@@ -257,6 +259,18 @@ impl<'a> TableBuilder<'a> {
         self
     }
 
+    /// A hash of everything that affects the widths of the columns, i.e. the contents and the style.
+    ///
+    /// A table with [`Column::auto`] columns normally needs one frame to learn the widths of its columns,
+    /// and is invisible during that first frame.
+    /// If a table with the same size hash has been shown before,
+    /// its column widths are used right away instead.
+    #[inline]
+    pub fn size_hash(mut self, size_hash: impl std::hash::Hash) -> Self {
+        self.size_id = Some(egui::Id::new(("table_size", size_hash)));
+        self
+    }
+
     /// What should table cells sense for? (default: [`egui::Sense::hover()`]).
     #[inline]
     pub fn sense(mut self, sense: egui::Sense) -> Self {
@@ -408,6 +422,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            size_id,
         } = self;
 
         let striped = striped.unwrap_or(ui.visuals().striped);
@@ -417,7 +432,7 @@ impl<'a> TableBuilder<'a> {
         let initial_widths =
             to_sizing(&columns).to_lengths(available_width, ui.spacing().item_spacing.x);
         let mut max_used_widths = vec![0.0; initial_widths.len()];
-        let (had_state, state) = TableState::load(ui, initial_widths, state_id);
+        let (had_state, state) = TableState::load(ui, initial_widths, state_id, size_id);
         let is_first_frame = !had_state;
         let first_frame_auto_size_columns = is_first_frame && columns.iter().any(|c| c.is_auto());
 
@@ -457,6 +472,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            size_id,
         }
     }
 
@@ -475,6 +491,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            size_id,
         } = self;
 
         let striped = striped.unwrap_or(ui.visuals().striped);
@@ -484,7 +501,7 @@ impl<'a> TableBuilder<'a> {
         let initial_widths =
             to_sizing(&columns).to_lengths(available_width, ui.spacing().item_spacing.x);
         let max_used_widths = vec![0.0; initial_widths.len()];
-        let (had_state, state) = TableState::load(ui, initial_widths, state_id);
+        let (had_state, state) = TableState::load(ui, initial_widths, state_id, size_id);
         let is_first_frame = !had_state;
         let first_frame_auto_size_columns = is_first_frame && columns.iter().any(|c| c.is_auto());
 
@@ -504,6 +521,7 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            size_id,
         }
         .body(add_body_contents);
     }
@@ -518,11 +536,20 @@ struct TableState {
 
 impl TableState {
     /// Returns `true` if it did load.
-    fn load(ui: &egui::Ui, default_widths: Vec<f32>, state_id: egui::Id) -> (bool, Self) {
+    fn load(
+        ui: &egui::Ui,
+        default_widths: Vec<f32>,
+        state_id: egui::Id,
+        size_id: Option<egui::Id>,
+    ) -> (bool, Self) {
         let rect = Rect::from_min_size(ui.available_rect_before_wrap().min, Vec2::ZERO);
         ui.ctx().check_for_id_clash(state_id, rect, "Table");
 
-        if let Some(state) = ui.data_mut(|d| d.get_persisted::<Self>(state_id)) {
+        let state = ui.data_mut(|d| {
+            d.get_persisted::<Self>(state_id)
+                .or_else(|| size_id.and_then(|size_id| d.get_temp::<Self>(size_id)))
+        });
+        if let Some(state) = state {
             // make sure that the stored widths aren't out-dated
             if state.column_widths.len() == default_widths.len() {
                 return (true, state);
@@ -537,8 +564,13 @@ impl TableState {
         )
     }
 
-    fn store(self, ui: &egui::Ui, state_id: egui::Id) {
-        ui.data_mut(|d| d.insert_persisted(state_id, self));
+    fn store(self, ui: &egui::Ui, state_id: egui::Id, size_id: Option<egui::Id>) {
+        ui.data_mut(|d| {
+            if let Some(size_id) = size_id {
+                d.insert_temp(size_id, self.clone());
+            }
+            d.insert_persisted(state_id, self);
+        });
     }
 }
 
@@ -566,6 +598,9 @@ pub struct Table<'a> {
     scroll_options: TableScrollOptions,
 
     sense: egui::Sense,
+
+    /// See [`TableBuilder::size_hash`].
+    size_id: Option<egui::Id>,
 }
 
 impl<'a> Table<'a> {
@@ -595,6 +630,7 @@ impl<'a> Table<'a> {
             cell_layout,
             scroll_options,
             sense,
+            size_id,
         } = self;
 
         let TableScrollOptions {
@@ -763,7 +799,7 @@ impl<'a> Table<'a> {
             available_width -= *column_width + spacing_x;
         }
 
-        state.store(ui, state_id);
+        state.store(ui, state_id, size_id);
     }
 }
 
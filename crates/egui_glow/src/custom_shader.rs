@@ -0,0 +1,176 @@
+#![allow(unsafe_code)]
+
+use egui::epaint::MAX_SHADER_PARAMS;
+use glow::HasContext as _;
+
+use crate::check_for_gl_error;
+use crate::misc_util::{compile_shader, link_program};
+use crate::shader_version::ShaderVersion;
+use crate::vao;
+
+const VERT_SRC: &str = r#"
+#if NEW_SHADER_INTERFACE
+    in vec2 a_pos;
+    out vec2 v_uv;
+#else
+    attribute vec2 a_pos;
+    varying vec2 v_uv;
+#endif
+
+// A triangle strip covering the viewport, which is set to the rect.
+void main() {
+    gl_Position = vec4(2.0 * a_pos.x - 1.0, 1.0 - 2.0 * a_pos.y, 0.0, 1.0);
+    v_uv = a_pos;
+}
+"#;
+
+/// Put before the [`egui::epaint::ShaderCode::glsl`].
+const FRAG_PRELUDE: &str = r#"
+#ifdef GL_ES
+    precision mediump float;
+#endif
+
+uniform vec2 u_size;
+uniform float u_pixels_per_point;
+uniform vec4 u_params[MAX_SHADER_PARAMS];
+uniform bool u_linear_output;
+
+#if NEW_SHADER_INTERFACE
+    in vec2 v_uv;
+    out vec4 f_color;
+    #define gl_FragColor f_color
+    #define texture2D texture
+#else
+    varying vec2 v_uv;
+#endif
+"#;
+
+/// Put after the [`egui::epaint::ShaderCode::glsl`], calling its `shade` function.
+const FRAG_MAIN: &str = r#"
+void main() {
+    vec4 color = shade(v_uv);
+    if (u_linear_output) {
+        // `GL_FRAMEBUFFER_SRGB` is enabled, so we need to output linear colors:
+        bvec3 cutoff = lessThan(color.rgb, vec3(0.04045));
+        vec3 lower = color.rgb / vec3(12.92);
+        vec3 higher = pow((color.rgb + vec3(0.055)) / vec3(1.055), vec3(2.4));
+        color.rgb = mix(higher, lower, vec3(cutoff));
+    }
+    gl_FragColor = color;
+}
+"#;
+
+/// A compiled [`egui::epaint::ShaderCode`], with a quad to paint it on.
+pub(crate) struct CustomShaderProgram {
+    program: glow::Program,
+    u_size: Option<glow::UniformLocation>,
+    u_pixels_per_point: Option<glow::UniformLocation>,
+    u_params: Option<glow::UniformLocation>,
+    u_linear_output: Option<glow::UniformLocation>,
+    vbo: glow::Buffer,
+    vao: vao::VertexArrayObject,
+}
+
+impl CustomShaderProgram {
+    pub(crate) unsafe fn new(
+        gl: &glow::Context,
+        shader_version: ShaderVersion,
+        shader_prefix: &str,
+        glsl: &str,
+    ) -> Result<Self, String> {
+        crate::profile_function!();
+
+        let header = format!(
+            "{}\n#define NEW_SHADER_INTERFACE {}\n#define MAX_SHADER_PARAMS {MAX_SHADER_PARAMS}\n{}",
+            shader_version.version_declaration(),
+            shader_version.is_new_shader_interface() as i32,
+            shader_prefix,
+        );
+
+        unsafe {
+            let vert = compile_shader(gl, glow::VERTEX_SHADER, &format!("{header}\n{VERT_SRC}"))?;
+            let frag = compile_shader(
+                gl,
+                glow::FRAGMENT_SHADER,
+                &format!("{header}\n{FRAG_PRELUDE}\n{glsl}\n{FRAG_MAIN}"),
+            );
+            let frag = match frag {
+                Ok(frag) => frag,
+                Err(err) => {
+                    gl.delete_shader(vert);
+                    return Err(err);
+                }
+            };
+            let program = link_program(gl, [vert, frag].iter());
+            gl.delete_shader(vert);
+            gl.delete_shader(frag);
+            let program = program?;
+
+            let a_pos_loc = gl
+                .get_attrib_location(program, "a_pos")
+                .ok_or("Missing attribute a_pos")?;
+
+            let vbo = gl.create_buffer()?;
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
+            let corners: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+            gl.buffer_data_u8_slice(
+                glow::ARRAY_BUFFER,
+                bytemuck::cast_slice(&corners),
+                glow::STATIC_DRAW,
+            );
+            let vao = vao::VertexArrayObject::new(
+                gl,
+                vbo,
+                vec![vao::BufferInfo {
+                    location: a_pos_loc,
+                    vector_size: 2,
+                    data_type: glow::FLOAT,
+                    normalized: false,
+                    stride: std::mem::size_of::<[f32; 2]>() as i32,
+                    offset: 0,
+                }],
+            );
+            check_for_gl_error!(gl, "CustomShaderProgram::new");
+
+            Ok(Self {
+                program,
+                u_size: gl.get_uniform_location(program, "u_size"),
+                u_pixels_per_point: gl.get_uniform_location(program, "u_pixels_per_point"),
+                u_params: gl.get_uniform_location(program, "u_params"),
+                u_linear_output: gl.get_uniform_location(program, "u_linear_output"),
+                vbo,
+                vao,
+            })
+        }
+    }
+
+    /// Paint into the current viewport.
+    pub(crate) unsafe fn paint(
+        &self,
+        gl: &glow::Context,
+        size: egui::Vec2,
+        pixels_per_point: f32,
+        params: &[[f32; 4]; MAX_SHADER_PARAMS],
+        linear_output: bool,
+    ) {
+        unsafe {
+            gl.use_program(Some(self.program));
+            gl.uniform_2_f32(self.u_size.as_ref(), size.x, size.y);
+            gl.uniform_1_f32(self.u_pixels_per_point.as_ref(), pixels_per_point);
+            gl.uniform_4_f32_slice(self.u_params.as_ref(), bytemuck::cast_slice(params));
+            gl.uniform_1_i32(self.u_linear_output.as_ref(), linear_output as i32);
+
+            self.vao.bind(gl);
+            gl.draw_arrays(glow::TRIANGLE_STRIP, 0, 4);
+            self.vao.unbind(gl);
+        }
+        check_for_gl_error!(gl, "CustomShaderProgram::paint");
+    }
+
+    pub(crate) unsafe fn destroy(&self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_program(self.program);
+            gl.delete_buffer(self.vbo);
+        }
+    }
+}
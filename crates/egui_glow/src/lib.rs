@@ -14,7 +14,9 @@
 pub mod painter;
 pub use glow;
 pub use painter::{CallbackFn, Painter, PainterError};
+mod custom_shader;
 mod misc_util;
+mod msaa;
 mod shader_version;
 mod vao;
 
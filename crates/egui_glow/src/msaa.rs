@@ -0,0 +1,119 @@
+#![allow(unsafe_code)]
+
+use glow::HasContext as _;
+
+use crate::check_for_gl_error;
+
+/// A multisampled framebuffer that egui is painted into,
+/// and then resolved onto the screen, see [`crate::Painter::set_msaa_samples`].
+pub(crate) struct MsaaTarget {
+    fbo: glow::Framebuffer,
+    color: glow::Renderbuffer,
+    size: [u32; 2],
+    samples: u32,
+    srgb: bool,
+}
+
+impl MsaaTarget {
+    pub(crate) unsafe fn new(
+        gl: &glow::Context,
+        size: [u32; 2],
+        samples: u32,
+        srgb: bool,
+    ) -> Result<Self, String> {
+        unsafe {
+            let color = gl.create_renderbuffer()?;
+            gl.bind_renderbuffer(glow::RENDERBUFFER, Some(color));
+            gl.renderbuffer_storage_multisample(
+                glow::RENDERBUFFER,
+                samples as i32,
+                if srgb {
+                    glow::SRGB8_ALPHA8
+                } else {
+                    glow::RGBA8
+                },
+                size[0] as i32,
+                size[1] as i32,
+            );
+            gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+            check_for_gl_error!(gl, "renderbuffer_storage_multisample");
+
+            let fbo = match gl.create_framebuffer() {
+                Ok(fbo) => fbo,
+                Err(err) => {
+                    gl.delete_renderbuffer(color);
+                    return Err(err);
+                }
+            };
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+            gl.framebuffer_renderbuffer(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::RENDERBUFFER,
+                Some(color),
+            );
+            let status = gl.check_framebuffer_status(glow::FRAMEBUFFER);
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+
+            let target = Self {
+                fbo,
+                color,
+                size,
+                samples,
+                srgb,
+            };
+            if status == glow::FRAMEBUFFER_COMPLETE {
+                Ok(target)
+            } else {
+                target.destroy(gl);
+                Err(format!("Incomplete multisampled framebuffer: {status:#x}"))
+            }
+        }
+    }
+
+    /// Can this target be reused for a frame with these settings?
+    pub(crate) fn matches(&self, size: [u32; 2], samples: u32, srgb: bool) -> bool {
+        self.size == size && self.samples == samples && self.srgb == srgb
+    }
+
+    pub(crate) fn fbo(&self) -> glow::Framebuffer {
+        self.fbo
+    }
+
+    /// Resolve the samples into the screen framebuffer, and bind that.
+    pub(crate) unsafe fn resolve_to_screen(&self, gl: &glow::Context) {
+        let [width, height] = self.size.map(|side| side as i32);
+        unsafe {
+            // The scissor test applies to blits too:
+            gl.disable(glow::SCISSOR_TEST);
+            gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.fbo));
+            gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, None);
+            gl.blit_framebuffer(
+                0,
+                0,
+                width,
+                height,
+                0,
+                0,
+                width,
+                height,
+                glow::COLOR_BUFFER_BIT,
+                glow::NEAREST,
+            );
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+        }
+        check_for_gl_error!(gl, "resolve_to_screen");
+    }
+
+    pub(crate) unsafe fn destroy(&self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_framebuffer(self.fbo);
+            gl.delete_renderbuffer(self.color);
+        }
+    }
+}
+
+/// Multisampled renderbuffers and blitting came with OpenGL 3.0, OpenGL ES 3.0 and WebGL 2.
+pub(crate) fn supports_msaa(gl: &glow::Context, is_webgl_1: bool) -> bool {
+    !is_webgl_1 && 3 <= gl.version().major
+}
@@ -1,16 +1,20 @@
 #![allow(clippy::collapsible_else_if)]
 #![allow(unsafe_code)]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use egui::{
     emath::Rect,
-    epaint::{Mesh, PaintCallbackInfo, Primitive, Vertex},
+    epaint::{CustomShaderCallback, Mesh, PaintCallbackInfo, Primitive, Vertex},
 };
 use glow::HasContext as _;
 use memoffset::offset_of;
 
 use crate::check_for_gl_error;
+use crate::custom_shader::CustomShaderProgram;
 use crate::misc_util::{compile_shader, link_program};
 use crate::shader_version::ShaderVersion;
 use crate::vao;
@@ -69,6 +73,11 @@ pub struct Painter {
     program: glow::Program,
     u_screen_size: glow::UniformLocation,
     u_sampler: glow::UniformLocation,
+    u_distance_field: glow::UniformLocation,
+    u_gamma: glow::UniformLocation,
+    u_brightness: glow::UniformLocation,
+    u_linear_output: glow::UniformLocation,
+    color_management: egui::epaint::ColorManagement,
     is_webgl_1: bool,
     vao: crate::vao::VertexArrayObject,
     srgb_textures: bool,
@@ -77,11 +86,29 @@ pub struct Painter {
 
     textures: HashMap<egui::TextureId, glow::Texture>,
 
+    /// Font textures storing signed distance fields, see [`egui::epaint::FontImage::distance_field`].
+    distance_field_textures: HashSet<egui::TextureId>,
+
+    /// For compiling [`egui::epaint::ShaderCode`]s.
+    shader_version: ShaderVersion,
+    shader_prefix: String,
+
+    /// By [`egui::epaint::ShaderCode::id`], `None` if it failed to compile.
+    custom_shaders: HashMap<u64, Option<CustomShaderProgram>>,
+
     next_native_tex_id: u64,
 
     /// Stores outdated OpenGL textures that are yet to be deleted
     textures_to_destroy: Vec<glow::Texture>,
 
+    /// See [`Self::set_msaa_samples`].
+    msaa_samples: u32,
+    supports_msaa: bool,
+    msaa_target: Option<crate::msaa::MsaaTarget>,
+
+    /// Are we painting into [`Self::msaa_target`] this frame?
+    painting_to_msaa: bool,
+
     /// Used to make sure we are destroyed correctly.
     destroyed: bool,
 }
@@ -147,6 +174,7 @@ impl Painter {
         let max_texture_side = unsafe { gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) } as usize;
         let shader_version = shader_version.unwrap_or_else(|| ShaderVersion::get(&gl));
         let is_webgl_1 = shader_version == ShaderVersion::Es100;
+        let supports_msaa = crate::msaa::supports_msaa(&gl, is_webgl_1);
         let shader_version_declaration = shader_version.version_declaration();
         log::debug!("Shader header: {:?}.", shader_version_declaration);
 
@@ -190,6 +218,12 @@ impl Painter {
             gl.delete_shader(frag);
             let u_screen_size = gl.get_uniform_location(program, "u_screen_size").unwrap();
             let u_sampler = gl.get_uniform_location(program, "u_sampler").unwrap();
+            let u_distance_field = gl
+                .get_uniform_location(program, "u_distance_field")
+                .unwrap();
+            let u_gamma = gl.get_uniform_location(program, "u_gamma").unwrap();
+            let u_brightness = gl.get_uniform_location(program, "u_brightness").unwrap();
+            let u_linear_output = gl.get_uniform_location(program, "u_linear_output").unwrap();
 
             let vbo = gl.create_buffer()?;
 
@@ -236,14 +270,27 @@ impl Painter {
                 program,
                 u_screen_size,
                 u_sampler,
+                u_distance_field,
+                u_gamma,
+                u_brightness,
+                u_linear_output,
+                color_management: Default::default(),
                 is_webgl_1,
                 vao,
                 srgb_textures,
                 vbo,
                 element_array_buffer,
                 textures: Default::default(),
+                distance_field_textures: Default::default(),
+                shader_version,
+                shader_prefix: shader_prefix.to_owned(),
+                custom_shaders: Default::default(),
                 next_native_tex_id: 1 << 32,
                 textures_to_destroy: Vec::new(),
+                msaa_samples: 0,
+                supports_msaa,
+                msaa_target: None,
+                painting_to_msaa: false,
                 destroyed: false,
             })
         }
@@ -258,6 +305,91 @@ impl Painter {
         self.max_texture_side
     }
 
+    /// How egui's colors are turned into pixels.
+    pub fn color_management(&self) -> egui::epaint::ColorManagement {
+        self.color_management
+    }
+
+    /// Change how egui's colors are turned into pixels. Takes effect from the next frame.
+    ///
+    /// [`egui::epaint::Blending::Linear`] uses `GL_FRAMEBUFFER_SRGB`,
+    /// so it needs an `sRGB` capable framebuffer, and is not supported on the web.
+    pub fn set_color_management(&mut self, color_management: egui::epaint::ColorManagement) {
+        if cfg!(target_arch = "wasm32")
+            && color_management.blending == egui::epaint::Blending::Linear
+        {
+            log::warn!("Linear blending is not supported with WebGL; blending in gamma space");
+        }
+        self.color_management = color_management;
+    }
+
+    /// The number of samples used for multisampling anti-aliasing (MSAA), see [`Self::set_msaa_samples`].
+    pub fn msaa_samples(&self) -> u32 {
+        self.msaa_samples
+    }
+
+    /// Paint with multisampling anti-aliasing (MSAA), using a power-of-two number of samples.
+    /// `0` or `1` turns it off. Takes effect from the next frame.
+    ///
+    /// egui is then painted into a multisampled framebuffer, which is resolved onto the screen
+    /// at the end of [`Self::paint_and_update_textures`].
+    /// This only happens if you clear the frame with [`Self::clear`] first.
+    ///
+    /// This is independent of any multisampling of the screen framebuffer itself,
+    /// which is chosen when the OpenGL context is created.
+    /// It needs OpenGL 3.0, OpenGL ES 3.0 or WebGL 2.
+    pub fn set_msaa_samples(&mut self, samples: u32) {
+        if samples == self.msaa_samples {
+            return;
+        }
+        if 1 < samples && !self.supports_msaa {
+            log::warn!("Multisampling needs OpenGL 3.0, OpenGL ES 3.0 or WebGL 2");
+        }
+        self.msaa_samples = samples;
+    }
+
+    /// Bind the multisampled framebuffer for this frame, if [`Self::set_msaa_samples`] asks for it.
+    fn begin_msaa(&mut self, screen_size_in_pixels: [u32; 2]) {
+        self.painting_to_msaa = false;
+        if self.msaa_samples <= 1 || !self.supports_msaa {
+            return;
+        }
+
+        let max_samples = unsafe { self.gl.get_parameter_i32(glow::MAX_SAMPLES) }.max(1) as u32;
+        let samples = self.msaa_samples.min(max_samples);
+        let srgb = self.linear_blending();
+
+        let is_reusable = self.msaa_target.as_ref().map_or(false, |target| {
+            target.matches(screen_size_in_pixels, samples, srgb)
+        });
+        if !is_reusable {
+            if let Some(target) = self.msaa_target.take() {
+                unsafe { target.destroy(&self.gl) };
+            }
+            match unsafe {
+                crate::msaa::MsaaTarget::new(&self.gl, screen_size_in_pixels, samples, srgb)
+            } {
+                Ok(target) => self.msaa_target = Some(target),
+                Err(err) => {
+                    log::warn!("Failed to create multisampled framebuffer: {err}");
+                    return;
+                }
+            }
+        }
+
+        self.painting_to_msaa = true;
+        unsafe {
+            self.gl
+                .bind_framebuffer(glow::FRAMEBUFFER, self.intermediate_fbo());
+        }
+    }
+
+    /// Does [`Self::color_management`] ask for linear blending, and can we do it?
+    fn linear_blending(&self) -> bool {
+        !cfg!(target_arch = "wasm32")
+            && self.color_management.blending == egui::epaint::Blending::Linear
+    }
+
     /// The framebuffer we use as an intermediate render target,
     /// or `None` if we are painting to the screen framebuffer directly.
     ///
@@ -267,11 +399,14 @@ impl Painter {
     /// So if in a [`egui::Shape::Callback`] you need to use an offscreen FBO, you should
     /// then restore to this afterwards with
     /// `gl.bind_framebuffer(glow::FRAMEBUFFER, painter.intermediate_fbo());`
-    #[allow(clippy::unused_self)]
+    ///
+    /// This is the multisampled framebuffer while painting with [`Self::set_msaa_samples`].
     pub fn intermediate_fbo(&self) -> Option<glow::Framebuffer> {
-        // We don't currently ever render to an offscreen buffer,
-        // but we may want to start to in order to do anti-aliasing on web, for instance.
-        None
+        if self.painting_to_msaa {
+            self.msaa_target.as_ref().map(crate::msaa::MsaaTarget::fbo)
+        } else {
+            None
+        }
     }
 
     unsafe fn prepare_painting(
@@ -300,8 +435,13 @@ impl Painter {
                 glow::ONE,
             );
 
+            let linear_blending = self.linear_blending();
             if !cfg!(target_arch = "wasm32") {
-                self.gl.disable(glow::FRAMEBUFFER_SRGB);
+                if linear_blending {
+                    self.gl.enable(glow::FRAMEBUFFER_SRGB);
+                } else {
+                    self.gl.disable(glow::FRAMEBUFFER_SRGB);
+                }
                 check_for_gl_error!(&self.gl, "FRAMEBUFFER_SRGB");
             }
 
@@ -315,6 +455,12 @@ impl Painter {
             self.gl
                 .uniform_2_f32(Some(&self.u_screen_size), width_in_points, height_in_points);
             self.gl.uniform_1_i32(Some(&self.u_sampler), 0);
+            self.gl
+                .uniform_1_f32(Some(&self.u_gamma), self.color_management.gamma);
+            self.gl
+                .uniform_1_f32(Some(&self.u_brightness), self.color_management.brightness);
+            self.gl
+                .uniform_1_i32(Some(&self.u_linear_output), linear_blending as i32);
             self.gl.active_texture(glow::TEXTURE0);
 
             self.vao.bind(&self.gl);
@@ -325,7 +471,15 @@ impl Painter {
         check_for_gl_error!(&self.gl, "prepare_painting");
     }
 
-    pub fn clear(&self, screen_size_in_pixels: [u32; 2], clear_color: [f32; 4]) {
+    /// `clear_color` is in gamma space, like all colors of egui.
+    ///
+    /// This starts a new frame, so with [`Self::set_msaa_samples`] this clears the multisampled framebuffer.
+    pub fn clear(&mut self, screen_size_in_pixels: [u32; 2], clear_color: [f32; 4]) {
+        self.begin_msaa(screen_size_in_pixels);
+        if !cfg!(target_arch = "wasm32") {
+            // Don't convert the clear color, which is already in gamma space:
+            unsafe { self.gl.disable(glow::FRAMEBUFFER_SRGB) };
+        }
         clear(&self.gl, screen_size_in_pixels, clear_color);
     }
 
@@ -345,6 +499,12 @@ impl Painter {
 
         self.paint_primitives(screen_size_px, pixels_per_point, clipped_primitives);
 
+        if std::mem::take(&mut self.painting_to_msaa) {
+            if let Some(target) = &self.msaa_target {
+                unsafe { target.resolve_to_screen(&self.gl) };
+            }
+        }
+
         for &id in &textures_delta.free {
             self.free_texture(id);
         }
@@ -392,6 +552,10 @@ impl Painter {
                 Primitive::Mesh(mesh) => {
                     self.paint_mesh(mesh);
                 }
+                Primitive::Instanced(instanced) => {
+                    // No instancing in WebGL1 / GLES2, so expand on the CPU:
+                    self.paint_mesh(&instanced.to_mesh());
+                }
                 Primitive::Callback(callback) => {
                     if callback.rect.is_positive() {
                         crate::profile_scope!("callback");
@@ -415,6 +579,10 @@ impl Painter {
 
                         if let Some(callback) = callback.callback.downcast_ref::<CallbackFn>() {
                             (callback.f)(info, self);
+                        } else if let Some(custom_shader) =
+                            callback.callback.downcast_ref::<CustomShaderCallback>()
+                        {
+                            self.paint_custom_shader(custom_shader, &info);
                         } else {
                             log::warn!("Warning: Unsupported render callback. Expected egui_glow::CallbackFn");
                         }
@@ -438,6 +606,35 @@ impl Painter {
         }
     }
 
+    fn paint_custom_shader(&mut self, callback: &CustomShaderCallback, info: &PaintCallbackInfo) {
+        let linear_output = self.linear_blending();
+        let code = &callback.code;
+        let gl = &self.gl;
+        let program = self.custom_shaders.entry(code.id()).or_insert_with(|| {
+            let Some(glsl) = code.glsl() else {
+                log::warn!("A custom shader has no GLSL code, so it can't be painted with glow");
+                return None;
+            };
+            let program = unsafe {
+                CustomShaderProgram::new(gl, self.shader_version, &self.shader_prefix, glsl)
+            };
+            program
+                .map_err(|err| log::error!("Failed to compile a custom shader: {err}"))
+                .ok()
+        });
+        if let Some(program) = program {
+            unsafe {
+                program.paint(
+                    gl,
+                    info.viewport.size(),
+                    info.pixels_per_point,
+                    &callback.padded_params(),
+                    linear_output,
+                );
+            }
+        }
+    }
+
     #[inline(never)] // Easier profiling
     fn paint_mesh(&mut self, mesh: &Mesh) {
         debug_assert!(mesh.is_valid());
@@ -459,6 +656,10 @@ impl Painter {
                 );
 
                 self.gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+                self.gl.uniform_1_i32(
+                    Some(&self.u_distance_field),
+                    self.distance_field_textures.contains(&mesh.texture_id) as i32,
+                );
             }
 
             unsafe {
@@ -521,6 +722,14 @@ impl Painter {
                 self.upload_texture_srgb(delta.pos, image.size, delta.options, &data);
             }
         };
+
+        if delta.is_whole() {
+            if matches!(&delta.image, egui::ImageData::Font(image) if image.distance_field) {
+                self.distance_field_textures.insert(tex_id);
+            } else {
+                self.distance_field_textures.remove(&tex_id);
+            }
+        }
     }
 
     fn upload_texture_srgb(
@@ -614,6 +823,7 @@ impl Painter {
     }
 
     pub fn free_texture(&mut self, tex_id: egui::TextureId) {
+        self.distance_field_textures.remove(&tex_id);
         if let Some(old_tex) = self.textures.remove(&tex_id) {
             unsafe { self.gl.delete_texture(old_tex) };
         }
@@ -694,6 +904,12 @@ impl Painter {
             for t in &self.textures_to_destroy {
                 self.gl.delete_texture(*t);
             }
+            for program in self.custom_shaders.values().flatten() {
+                program.destroy(&self.gl);
+            }
+            if let Some(target) = &self.msaa_target {
+                target.destroy(&self.gl);
+            }
         }
     }
 
@@ -708,6 +924,14 @@ impl Painter {
         }
     }
 
+    /// Mark the [`Painter`] as destroyed without deleting its OpenGL objects.
+    ///
+    /// Use this instead of [`Self::destroy`] when the OpenGL context has been lost,
+    /// since the objects died with the context.
+    pub fn forget_gl_objects(&mut self) {
+        self.destroyed = true;
+    }
+
     fn assert_not_destroyed(&self) {
         assert!(!self.destroyed, "the egui glow has already been destroyed!");
     }
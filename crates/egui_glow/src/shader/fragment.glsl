@@ -1,8 +1,17 @@
+#if defined(GL_ES) && !NEW_SHADER_INTERFACE
+    // WebGL1 needs this for `fwidth`:
+    #extension GL_OES_standard_derivatives : enable
+#endif
+
 #ifdef GL_ES
     precision mediump float;
 #endif
 
 uniform sampler2D u_sampler;
+uniform bool u_distance_field;
+uniform float u_gamma;
+uniform float u_brightness;
+uniform bool u_linear_output;
 
 #if NEW_SHADER_INTERFACE
     in vec4 v_rgba_in_gamma;
@@ -24,18 +33,62 @@ vec3 srgb_gamma_from_linear(vec3 rgb) {
     return mix(higher, lower, vec3(cutoff));
 }
 
+// 0-1 linear  from  0-1 sRGB gamma
+vec3 linear_from_srgb_gamma(vec3 srgb) {
+    bvec3 cutoff = lessThan(srgb, vec3(0.04045));
+    vec3 lower = srgb / vec3(12.92);
+    vec3 higher = pow((srgb + vec3(0.055)) / vec3(1.055), vec3(2.4));
+    return mix(higher, lower, vec3(cutoff));
+}
+
 // 0-1 sRGBA gamma  from  0-1 linear
 vec4 srgba_gamma_from_linear(vec4 rgba) {
     return vec4(srgb_gamma_from_linear(rgba.rgb), rgba.a);
 }
 
+// Turn the alpha of a signed distance field into coverage, see `epaint::FontImage::distance_field`.
+float distance_field_coverage(float distance) {
+#if !defined(GL_ES) || NEW_SHADER_INTERFACE || defined(GL_OES_standard_derivatives)
+    // 0.5 is the edge. Dividing by the screen-space rate of change gives the distance in pixels:
+    float width = max(fwidth(distance), 1e-4);
+#else
+    // Without derivatives we don't know the scale, so use a fixed edge width:
+    float width = 0.05;
+#endif
+    return clamp((distance - 0.5) / width + 0.5, 0.0, 1.0);
+}
+
+// Apply `epaint::ColorManagement::gamma` and `brightness` to a premultiplied color in gamma space.
+vec4 adjust_color(vec4 color_in_gamma) {
+    if (color_in_gamma.a <= 0.0) {
+        return color_in_gamma;
+    }
+    vec3 unmultiplied = color_in_gamma.rgb / color_in_gamma.a;
+    vec3 adjusted = u_brightness * pow(unmultiplied, vec3(u_gamma));
+    return vec4(adjusted * color_in_gamma.a, color_in_gamma.a);
+}
+
 void main() {
+    vec4 texture_linear = texture2D(u_sampler, v_tc);
 #if SRGB_TEXTURES
-    vec4 texture_in_gamma = srgba_gamma_from_linear(texture2D(u_sampler, v_tc));
+    vec4 texture_in_gamma = srgba_gamma_from_linear(texture_linear);
 #else
-    vec4 texture_in_gamma = texture2D(u_sampler, v_tc);
+    vec4 texture_in_gamma = texture_linear;
 #endif
 
+    // Alpha is never sRGB encoded, so we can use it as is:
+    float coverage = distance_field_coverage(texture_linear.a);
+    if (u_distance_field) {
+        texture_in_gamma = vec4(coverage);
+    }
+
     // We multiply the colors in gamma space, because that's the only way to get text to look right.
-    gl_FragColor = v_rgba_in_gamma * texture_in_gamma;
+    vec4 color_in_gamma = adjust_color(v_rgba_in_gamma * texture_in_gamma);
+
+    if (u_linear_output) {
+        // `GL_FRAMEBUFFER_SRGB` is enabled, so the blending happens in linear space:
+        gl_FragColor = vec4(linear_from_srgb_gamma(color_in_gamma.rgb), color_in_gamma.a);
+    } else {
+        gl_FragColor = color_in_gamma;
+    }
 }
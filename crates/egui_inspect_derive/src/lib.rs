@@ -0,0 +1,291 @@
+//! `#[derive(EguiInspect)]`, see `egui::EguiInspect`.
+//!
+//! Use it through the `derive` feature of `egui` rather than depending on this crate directly.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned as _, Attribute, Data, DeriveInput, Expr, ExprRange,
+    Fields, LitStr, Path, RangeLimits,
+};
+
+/// Implement `egui::EguiInspect` for a struct or enum.
+///
+/// Structs are shown as a grid with a row per field.
+/// Enums are shown as a combo box for picking the variant, followed by the fields of the variant.
+/// Switching variant resets its fields to their [`Default`].
+///
+/// Field attributes:
+/// * `#[inspect(skip)]`: don't show the field.
+/// * `#[inspect(read_only)]`: show the field, but don't allow editing it.
+/// * `#[inspect(name = "Label")]`: the label of the field (also works on enum variants).
+/// * `#[inspect(range = 0.0..=1.0)]`: show a number with a slider in this range.
+/// * `#[inspect(speed = 0.1)]`: how fast a number changes when dragged.
+/// * `#[inspect(multiline)]`: edit a `String` with a multiline text edit.
+/// * `#[inspect(color)]`: edit a `[f32; 3]`, `[f32; 4]`, `[u8; 3]` or `[u8; 4]` as a color.
+///
+/// Container attributes:
+/// * `#[inspect(crate = "eframe::egui")]`: the path to `egui`, if not a direct dependency.
+#[proc_macro_derive(EguiInspect, attributes(inspect))]
+pub fn derive_egui_inspect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Attributes {
+    skip: bool,
+    read_only: bool,
+    multiline: bool,
+    color: bool,
+    name: Option<String>,
+    range: Option<ExprRange>,
+    speed: Option<Expr>,
+    crate_path: Option<Path>,
+}
+
+impl Attributes {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("inspect")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    attributes.skip = true;
+                } else if meta.path.is_ident("read_only") {
+                    attributes.read_only = true;
+                } else if meta.path.is_ident("multiline") {
+                    attributes.multiline = true;
+                } else if meta.path.is_ident("color") {
+                    attributes.color = true;
+                } else if meta.path.is_ident("name") {
+                    attributes.name = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("range") {
+                    attributes.range = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("speed") {
+                    attributes.speed = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("crate") {
+                    attributes.crate_path = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else {
+                    return Err(meta.error("unknown inspect attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(attributes)
+    }
+
+    /// An expression creating the `egui::InspectOptions` for a field.
+    fn options(&self, egui: &Path) -> syn::Result<TokenStream> {
+        let range = match &self.range {
+            Some(range) => {
+                let (Some(start), Some(end), RangeLimits::Closed(_)) =
+                    (&range.start, &range.end, &range.limits)
+                else {
+                    return Err(syn::Error::new(
+                        range.span(),
+                        "expected an inclusive range, like `0.0..=1.0`",
+                    ));
+                };
+                quote!(::std::option::Option::Some((#start) as f64..=(#end) as f64))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+        let speed = match &self.speed {
+            Some(speed) => quote!(::std::option::Option::Some((#speed) as f64)),
+            None => quote!(::std::option::Option::None),
+        };
+        let multiline = self.multiline;
+        let color = self.color;
+        Ok(quote! {
+            #egui::InspectOptions {
+                range: #range,
+                speed: #speed,
+                multiline: #multiline,
+                color: #color,
+            }
+        })
+    }
+}
+
+fn derive(mut input: DeriveInput) -> syn::Result<TokenStream> {
+    let container = Attributes::parse(&input.attrs)?;
+    let egui = container
+        .crate_path
+        .unwrap_or_else(|| syn::parse_quote!(::egui));
+
+    let type_params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#param: #egui::EguiInspect));
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, grid) = fields_ui(&egui, &data.fields)?;
+            quote! {
+                let Self #pattern = self;
+                #grid
+            }
+        }
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "EguiInspect can't be derived for enums without variants",
+                ));
+            }
+
+            let mut names = vec![];
+            let mut to_index = vec![];
+            let mut from_index = vec![];
+            let mut variant_uis = vec![];
+            for (index, variant) in data.variants.iter().enumerate() {
+                let attributes = Attributes::parse(&variant.attrs)?;
+                let ident = &variant.ident;
+                names.push(attributes.name.unwrap_or_else(|| ident.to_string()));
+
+                let default = quote!(::std::default::Default::default());
+                let constructor = match &variant.fields {
+                    Fields::Named(fields) => {
+                        let field_names = fields.named.iter().map(|field| &field.ident);
+                        quote!(Self::#ident { #(#field_names: #default),* })
+                    }
+                    Fields::Unnamed(fields) => {
+                        let defaults = fields.unnamed.iter().map(|_| &default);
+                        quote!(Self::#ident(#(#defaults),*))
+                    }
+                    Fields::Unit => quote!(Self::#ident),
+                };
+                from_index.push(quote!(#index => #constructor));
+                to_index.push(quote!(Self::#ident { .. } => #index));
+
+                if !variant.fields.is_empty() {
+                    let (pattern, grid) = fields_ui(&egui, &variant.fields)?;
+                    variant_uis.push(quote! {
+                        Self::#ident #pattern => {
+                            response |= { #grid };
+                        }
+                    });
+                }
+            }
+            let num_variants = names.len();
+
+            quote! {
+                ui.vertical(|ui| {
+                    const NAMES: [&str; #num_variants] = [#(#names),*];
+                    let mut selected: usize = match self {
+                        #(#to_index,)*
+                    };
+                    #[allow(unused_mut)]
+                    let mut response = #egui::ComboBox::from_id_source(ui.next_auto_id())
+                        .show_index(ui, &mut selected, NAMES.len(), |i| NAMES[i]);
+                    if response.changed() {
+                        *self = match selected {
+                            #(#from_index,)*
+                            _ => unreachable!(),
+                        };
+                    }
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #(#variant_uis)*
+                        _ => {}
+                    }
+                    response
+                })
+                .inner
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "EguiInspect can't be derived for unions",
+            ));
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #egui::EguiInspect for #name #ty_generics #where_clause {
+            fn inspect_ui(
+                &mut self,
+                ui: &mut #egui::Ui,
+                _options: &#egui::InspectOptions,
+            ) -> #egui::Response {
+                #body
+            }
+        }
+    })
+}
+
+/// A pattern binding the fields to `field_0`, `field_1`, …,
+/// and an expression showing them in a grid, evaluating to a `Response`.
+fn fields_ui(egui: &Path, fields: &Fields) -> syn::Result<(TokenStream, TokenStream)> {
+    let mut bindings = vec![];
+    let mut rows = vec![];
+
+    for (index, field) in fields.iter().enumerate() {
+        let attributes = Attributes::parse(&field.attrs)?;
+        let binding = format_ident!("field_{index}");
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(index);
+                quote!(#index)
+            }
+        };
+
+        if attributes.skip {
+            bindings.push(quote!(#member: _));
+            continue;
+        }
+        bindings.push(quote!(#member: #binding));
+
+        let label = attributes
+            .name
+            .clone()
+            .unwrap_or_else(|| match &field.ident {
+                Some(ident) => ident.to_string(),
+                None => index.to_string(),
+            });
+        let options = attributes.options(egui)?;
+        let field_ui = quote!(#egui::EguiInspect::inspect_ui(#binding, ui, &#options));
+        let field_ui = if attributes.read_only {
+            quote!(ui.add_enabled_ui(false, |ui| #field_ui).inner)
+        } else {
+            field_ui
+        };
+        rows.push(quote! {
+            ui.label(#label);
+            response |= #field_ui;
+            ui.end_row();
+        });
+    }
+
+    let pattern = if matches!(fields, Fields::Unit) {
+        quote!()
+    } else {
+        quote!({ #(#bindings),* })
+    };
+    let grid = quote! {
+        let grid = #egui::Grid::new(ui.next_auto_id())
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                #[allow(unused_mut)]
+                let mut response = ui.allocate_response(#egui::Vec2::ZERO, #egui::Sense::hover());
+                #(#rows)*
+                response
+            });
+        grid.response | grid.inner
+    };
+    Ok((pattern, grid))
+}
@@ -3,7 +3,7 @@ use std::{fmt::Debug, ops::RangeInclusive, sync::Arc};
 use egui::emath::{remap_clamp, round_to_decimals, Pos2, Rect};
 use egui::epaint::{Shape, TextShape};
 
-use crate::{Response, Sense, TextStyle, Ui, WidgetText};
+use crate::{NumberFormatter, Response, Sense, TextStyle, Ui, WidgetText};
 
 use super::{transform::PlotTransform, GridMark};
 
@@ -79,7 +79,8 @@ impl From<VPlacement> for Placement {
 #[derive(Clone)]
 pub struct AxisHints {
     pub(super) label: WidgetText,
-    pub(super) formatter: Arc<AxisFormatterFn>,
+    /// `None` means [`Self::default_formatter`].
+    pub(super) formatter: Option<Arc<AxisFormatterFn>>,
     pub(super) digits: usize,
     pub(super) placement: Placement,
 }
@@ -91,12 +92,12 @@ impl Default for AxisHints {
     /// Initializes a default axis configuration for the specified axis.
     ///
     /// `label` is empty.
-    /// `formatter` is default float to string formatter.
+    /// `formatter` is default float to string formatter, using [`egui::Style::number_formatter`].
     /// maximum `digits` on tick label is 5.
     fn default() -> Self {
         Self {
             label: Default::default(),
-            formatter: Arc::new(Self::default_formatter),
+            formatter: None,
             digits: 5,
             placement: Placement::LeftBottom,
         }
@@ -113,12 +114,16 @@ impl AxisHints {
         mut self,
         fmt: impl Fn(f64, usize, &RangeInclusive<f64>) -> String + 'static,
     ) -> Self {
-        self.formatter = Arc::new(fmt);
+        self.formatter = Some(Arc::new(fmt));
         self
     }
 
-    fn default_formatter(tick: f64, max_digits: usize, _range: &RangeInclusive<f64>) -> String {
-        if tick.abs() > 10.0_f64.powf(max_digits as f64) {
+    fn default_formatter(
+        number_formatter: &NumberFormatter,
+        tick: f64,
+        max_digits: usize,
+    ) -> String {
+        if tick.abs() > 10.0_f64.powf(max_digits as f64) && !number_formatter.engineering_notation {
             let tick_rounded = tick as isize;
             return format!("{tick_rounded:+e}");
         }
@@ -126,7 +131,7 @@ impl AxisHints {
         if tick.abs() < 10.0_f64.powf(-(max_digits as f64)) && tick != 0.0 {
             return format!("{tick_rounded:+e}");
         }
-        tick_rounded.to_string()
+        number_formatter.format(tick_rounded, 0..=max_digits)
     }
 
     /// Specify axis label.
@@ -258,7 +263,14 @@ impl AxisWidget {
             };
 
             for step in self.steps.iter() {
-                let text = (self.hints.formatter)(step.value, self.hints.digits, &self.range);
+                let text = match &self.hints.formatter {
+                    Some(formatter) => formatter(step.value, self.hints.digits, &self.range),
+                    None => AxisHints::default_formatter(
+                        &ui.style().number_formatter,
+                        step.value,
+                        self.hints.digits,
+                    ),
+                };
                 if !text.is_empty() {
                     const MIN_TEXT_SPACING: f32 = 20.0;
                     const FULL_CONTRAST_SPACING: f32 = 40.0;
@@ -652,7 +652,7 @@ impl Plot {
         fmt: impl Fn(f64, usize, &RangeInclusive<f64>) -> String + 'static,
     ) -> Self {
         if let Some(main) = self.x_axes.first_mut() {
-            main.formatter = Arc::new(fmt);
+            main.formatter = Some(Arc::new(fmt));
         }
         self
     }
@@ -668,7 +668,7 @@ impl Plot {
         fmt: impl Fn(f64, usize, &RangeInclusive<f64>) -> String + 'static,
     ) -> Self {
         if let Some(main) = self.y_axes.first_mut() {
-            main.formatter = Arc::new(fmt);
+            main.formatter = Some(Arc::new(fmt));
         }
         self
     }
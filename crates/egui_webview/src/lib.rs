@@ -0,0 +1,165 @@
+//! Native web views for [`egui::WebView`] in [`eframe`], using [`wry`](https://github.com/tauri-apps/wry).
+//!
+//! Useful for e.g. OAuth flows and HTML-rich content:
+//!
+//! ```no_run
+//! let options = eframe::NativeOptions {
+//!     webview_backend: Some(Box::new(egui_webview::WryBackend::new())),
+//!     ..Default::default()
+//! };
+//! eframe::run_simple_native("My app", options, |ctx, _frame| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         ui.add(egui::WebView::new("https://github.com/emilk/egui"));
+//!     });
+//! })
+//! .unwrap();
+//! ```
+//!
+//! The web views are child windows of the viewports, so they are shown on top of egui.
+//!
+//! ## Platform support
+//! * Windows: WebView2, which comes with Windows 11 and recent versions of Windows 10.
+//! * Mac: `WKWebView`.
+//! * Linux: WebKitGTK, on X11 only (`wry` can't put a web view in a Wayland window).
+//!   Since WebKitGTK runs on the gtk main loop, the viewports with web views are updated 60 times per second.
+//!
+//! ## Feature flags
+#![cfg_attr(feature = "document-features", doc = document_features::document_features!())]
+//!
+
+use std::collections::hash_map::Entry;
+
+use egui::{ahash::HashMap, Id, Rect, WebViewOutput};
+use winit::window::{Window, WindowId};
+
+pub use wry;
+
+/// A [`eframe::WebViewBackend`] that shows the web views with [`wry`].
+#[derive(Default)]
+pub struct WryBackend {
+    windows: HashMap<WindowId, HashMap<Id, NativeWebView>>,
+}
+
+/// A web view, and what it was last told to show.
+struct NativeWebView {
+    url: String,
+
+    /// In physical pixels.
+    rect: Rect,
+
+    webview: wry::WebView,
+}
+
+impl WryBackend {
+    pub fn new() -> Self {
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "openbsd",
+            target_os = "netbsd"
+        ))]
+        if let Err(err) = gtk::init() {
+            log::error!("Failed to initialize gtk, which is needed for web views: {err}");
+        }
+
+        Self::default()
+    }
+}
+
+impl eframe::WebViewBackend for WryBackend {
+    fn update(&mut self, window: &Window, pixels_per_point: f32, webviews: &[WebViewOutput]) {
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "openbsd",
+            target_os = "netbsd"
+        ))]
+        while gtk::events_pending() {
+            gtk::main_iteration_do(false);
+        }
+
+        let views = self.windows.entry(window.id()).or_default();
+        views.retain(|id, _| webviews.iter().any(|webview| webview.id == *id));
+
+        for WebViewOutput { id, url, rect } in webviews {
+            let rect = Rect::from_min_max(
+                (rect.min * pixels_per_point).round(),
+                (rect.max * pixels_per_point).round(),
+            );
+            match views.entry(*id) {
+                Entry::Occupied(entry) => {
+                    let view = entry.into_mut();
+                    if view.url != *url {
+                        if let Err(err) = view.webview.load_url(url) {
+                            log::warn!("Failed to load {url:?} in web view: {err}");
+                        }
+                        view.url = url.clone();
+                    }
+                    if view.rect != rect {
+                        if let Err(err) = view.webview.set_bounds(bounds(rect)) {
+                            log::warn!("Failed to move web view: {err}");
+                        }
+                        view.rect = rect;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    let webview = wry::WebViewBuilder::new()
+                        .with_url(url)
+                        .with_bounds(bounds(rect))
+                        .build_as_child(window);
+                    match webview {
+                        Ok(webview) => {
+                            entry.insert(NativeWebView {
+                                url: url.clone(),
+                                rect,
+                                webview,
+                            });
+                        }
+                        Err(err) => {
+                            log::warn!("Failed to create web view for {url:?}: {err}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn focus_parent(&mut self, window_id: WindowId) {
+        // All web views of a window share the same parent:
+        if let Some(view) = self
+            .windows
+            .get(&window_id)
+            .and_then(|views| views.values().next())
+        {
+            if let Err(err) = view.webview.focus_parent() {
+                log::debug!("Failed to move focus from web view to egui: {err}");
+            }
+        }
+    }
+
+    fn retain_windows(&mut self, window_ids: &[WindowId]) {
+        self.windows.retain(|id, _| window_ids.contains(id));
+    }
+
+    fn update_interval(&self) -> Option<std::time::Duration> {
+        let has_webviews = self.windows.values().any(|views| !views.is_empty());
+        let needs_main_loop = cfg!(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "openbsd",
+            target_os = "netbsd"
+        ));
+        (has_webviews && needs_main_loop).then(|| std::time::Duration::from_secs_f32(1.0 / 60.0))
+    }
+}
+
+/// The bounds of a web view within its window, from a rectangle in physical pixels.
+fn bounds(rect: Rect) -> wry::Rect {
+    wry::Rect {
+        position: wry::dpi::PhysicalPosition::new(rect.min.x as i32, rect.min.y as i32).into(),
+        size: wry::dpi::PhysicalSize::new(rect.width() as u32, rect.height() as u32).into(),
+    }
+}
@@ -50,6 +50,16 @@ impl Align {
         }
     }
 
+    /// Returns the opposite alignment: `Min <=> Max`, `Center` stays `Center`.
+    #[inline(always)]
+    pub fn flip(self) -> Self {
+        match self {
+            Self::Min => Self::Max,
+            Self::Center => Self::Center,
+            Self::Max => Self::Min,
+        }
+    }
+
     /// Returns a range of given size within a specified range.
     ///
     /// If the requested `size` is bigger than the size of `range`, then the returned
@@ -170,6 +180,24 @@ impl Align2 {
         vec2(self.x().to_sign(), self.y().to_sign())
     }
 
+    /// Flip the horizontal alignment, e.g. [`Self::LEFT_TOP`] becomes [`Self::RIGHT_TOP`].
+    #[inline(always)]
+    pub fn flip_x(self) -> Self {
+        Self([self.x().flip(), self.y()])
+    }
+
+    /// Flip the vertical alignment, e.g. [`Self::LEFT_TOP`] becomes [`Self::LEFT_BOTTOM`].
+    #[inline(always)]
+    pub fn flip_y(self) -> Self {
+        Self([self.x(), self.y().flip()])
+    }
+
+    /// Flip both alignments, e.g. [`Self::LEFT_TOP`] becomes [`Self::RIGHT_BOTTOM`].
+    #[inline(always)]
+    pub fn flip(self) -> Self {
+        Self([self.x().flip(), self.y().flip()])
+    }
+
     /// Used e.g. to anchor a piece of text to a part of the rectangle.
     /// Give a position within the rect, specified by the aligns
     pub fn anchor_rect(self, rect: Rect) -> Rect {
@@ -33,6 +33,7 @@ mod numeric;
 mod pos2;
 mod range;
 mod rect;
+mod rect_align;
 mod rect_transform;
 mod rot2;
 pub mod smart_aim;
@@ -46,6 +47,7 @@ pub use {
     pos2::*,
     range::Rangef,
     rect::*,
+    rect_align::RectAlign,
     rect_transform::*,
     rot2::*,
     vec2::*,
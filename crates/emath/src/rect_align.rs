@@ -0,0 +1,170 @@
+use crate::{Align, Align2, Pos2, Rect, Vec2};
+
+/// Position a child [`Rect`] relative to a parent [`Rect`],
+/// e.g. a popup relative to the button that opened it.
+///
+/// The point [`Self::child`] of the child rect is placed at the point [`Self::parent`] of the parent rect.
+/// For instance, [`RectAlign::BOTTOM_START`] places the left-top corner of the child
+/// at the left-bottom corner of the parent, i.e. the child is below the parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RectAlign {
+    /// The point on the parent rect.
+    pub parent: Align2,
+
+    /// The point on the child rect that is placed at [`Self::parent`].
+    pub child: Align2,
+}
+
+impl RectAlign {
+    /// Below the parent, aligned to its left side.
+    pub const BOTTOM_START: Self = Self::new(Align2::LEFT_BOTTOM, Align2::LEFT_TOP);
+
+    /// Below the parent, centered.
+    pub const BOTTOM: Self = Self::new(Align2::CENTER_BOTTOM, Align2::CENTER_TOP);
+
+    /// Below the parent, aligned to its right side.
+    pub const BOTTOM_END: Self = Self::new(Align2::RIGHT_BOTTOM, Align2::RIGHT_TOP);
+
+    /// Above the parent, aligned to its left side.
+    pub const TOP_START: Self = Self::new(Align2::LEFT_TOP, Align2::LEFT_BOTTOM);
+
+    /// Above the parent, centered.
+    pub const TOP: Self = Self::new(Align2::CENTER_TOP, Align2::CENTER_BOTTOM);
+
+    /// Above the parent, aligned to its right side.
+    pub const TOP_END: Self = Self::new(Align2::RIGHT_TOP, Align2::RIGHT_BOTTOM);
+
+    /// Left of the parent, aligned to its top.
+    pub const LEFT_START: Self = Self::new(Align2::LEFT_TOP, Align2::RIGHT_TOP);
+
+    /// Left of the parent, centered.
+    pub const LEFT: Self = Self::new(Align2::LEFT_CENTER, Align2::RIGHT_CENTER);
+
+    /// Left of the parent, aligned to its bottom.
+    pub const LEFT_END: Self = Self::new(Align2::LEFT_BOTTOM, Align2::RIGHT_BOTTOM);
+
+    /// Right of the parent, aligned to its top.
+    pub const RIGHT_START: Self = Self::new(Align2::RIGHT_TOP, Align2::LEFT_TOP);
+
+    /// Right of the parent, centered.
+    pub const RIGHT: Self = Self::new(Align2::RIGHT_CENTER, Align2::LEFT_CENTER);
+
+    /// Right of the parent, aligned to its bottom.
+    pub const RIGHT_END: Self = Self::new(Align2::RIGHT_BOTTOM, Align2::LEFT_BOTTOM);
+
+    #[inline]
+    pub const fn new(parent: Align2, child: Align2) -> Self {
+        Self { parent, child }
+    }
+
+    /// Mirror horizontally, e.g. [`Self::RIGHT_START`] becomes [`Self::LEFT_START`].
+    #[inline]
+    pub fn flip_x(self) -> Self {
+        Self::new(self.parent.flip_x(), self.child.flip_x())
+    }
+
+    /// Mirror vertically, e.g. [`Self::BOTTOM_START`] becomes [`Self::TOP_START`].
+    #[inline]
+    pub fn flip_y(self) -> Self {
+        Self::new(self.parent.flip_y(), self.child.flip_y())
+    }
+
+    /// Mirror both horizontally and vertically.
+    #[inline]
+    pub fn flip(self) -> Self {
+        Self::new(self.parent.flip(), self.child.flip())
+    }
+
+    /// The alternatives to try, in order, if `self` doesn't fit:
+    /// first flipped vertically, then horizontally, then both.
+    pub fn symmetries(self) -> [Self; 3] {
+        [self.flip_y(), self.flip_x(), self.flip()]
+    }
+
+    /// The point on the parent rect where the child is anchored.
+    #[inline]
+    pub fn anchor(self, parent_rect: Rect) -> Pos2 {
+        self.parent.pos_in_rect(&parent_rect)
+    }
+
+    /// Where to put a child of the given size.
+    ///
+    /// `gap` is the distance between the parent and the child
+    /// along the axes where they are placed next to each other.
+    pub fn align_rect(self, parent_rect: Rect, child_size: Vec2, gap: f32) -> Rect {
+        let gap_along = |parent: Align, child: Align| match (parent, child) {
+            (Align::Max, Align::Min) => gap,
+            (Align::Min, Align::Max) => -gap,
+            _ => 0.0,
+        };
+        let offset = Vec2::new(
+            gap_along(self.parent.x(), self.child.x()),
+            gap_along(self.parent.y(), self.child.y()),
+        );
+        let pos = self.anchor(parent_rect) + offset;
+        self.child.anchor_rect(Rect::from_min_size(pos, child_size))
+    }
+
+    /// Pick the first alignment for which the child fits within `content_rect` (e.g. the screen).
+    ///
+    /// If none of them fit, the first one is returned.
+    /// Returns `None` if `values` is empty.
+    ///
+    /// ```
+    /// # use emath::{pos2, vec2, Rect, RectAlign};
+    /// let screen = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0));
+    /// let button = Rect::from_min_size(pos2(10.0, 90.0), vec2(20.0, 10.0));
+    /// let align = RectAlign::BOTTOM_START;
+    /// let best = RectAlign::find_best_align(
+    ///     std::iter::once(align).chain(align.symmetries()),
+    ///     screen,
+    ///     button,
+    ///     0.0,
+    ///     vec2(50.0, 50.0),
+    /// );
+    /// assert_eq!(best, Some(RectAlign::TOP_START)); // No room below
+    /// ```
+    pub fn find_best_align(
+        values: impl IntoIterator<Item = Self>,
+        content_rect: Rect,
+        parent_rect: Rect,
+        gap: f32,
+        child_size: Vec2,
+    ) -> Option<Self> {
+        let fits = |align: &Self| {
+            content_rect.contains_rect(align.align_rect(parent_rect, child_size, gap))
+        };
+
+        let mut values = values.into_iter();
+        let first = values.next()?;
+        if fits(&first) {
+            Some(first)
+        } else {
+            values.find(fits).or(Some(first))
+        }
+    }
+}
+
+#[test]
+fn test_rect_align() {
+    use crate::{pos2, vec2};
+
+    let parent = Rect::from_min_size(pos2(10.0, 20.0), vec2(30.0, 10.0));
+    let size = vec2(50.0, 40.0);
+
+    assert_eq!(
+        RectAlign::BOTTOM_START.align_rect(parent, size, 2.0),
+        Rect::from_min_size(pos2(10.0, 32.0), size)
+    );
+    assert_eq!(
+        RectAlign::TOP_END.align_rect(parent, size, 2.0),
+        Rect::from_min_size(pos2(-10.0, -22.0), size)
+    );
+    assert_eq!(
+        RectAlign::RIGHT.align_rect(parent, size, 0.0),
+        Rect::from_min_size(pos2(40.0, 5.0), size)
+    );
+    assert_eq!(RectAlign::BOTTOM_START.flip_y(), RectAlign::TOP_START);
+    assert_eq!(RectAlign::RIGHT_START.flip_x(), RectAlign::LEFT_START);
+}
@@ -0,0 +1,451 @@
+//! Clipping of tessellated meshes against non-rectangular regions.
+
+use std::sync::Arc;
+
+use crate::{tessellator::path, CircleShape, Color32, Mesh, PathShape, RectShape, Shape, Vertex};
+use emath::*;
+
+/// A convex region that shapes can be clipped against,
+/// in addition to the rectangular clip rectangle.
+///
+/// This can be used to clip content to rounded rectangles or circles,
+/// e.g. for avatar crops or for scroll areas with rounded corners.
+///
+/// The clipping is done analytically on the tessellated triangles,
+/// so it works with all backends, including for text and images.
+/// The clipped edge is anti-aliased using the feathering of the tessellator.
+///
+/// Paint callbacks ([`Shape::Callback`]) can only be clipped to rectangles,
+/// so a callback that crosses the edge of a clip shape is not painted at all.
+///
+/// Create one with [`Self::from_shape`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClipShape {
+    /// Convex polygon, in clockwise order (in screen coordinates, where y goes down).
+    points: Arc<[Pos2]>,
+
+    /// Bounding rectangle of [`Self::points`].
+    bounding_rect: Rect,
+}
+
+impl ClipShape {
+    /// Clip to the given (possibly rounded) rectangle.
+    pub fn rect(rect: Rect, rounding: impl Into<crate::Rounding>) -> Self {
+        let mut points = vec![];
+        path::rounded_rectangle(&mut points, rect, rounding.into());
+        Self::from_convex_polygon(points)
+    }
+
+    /// Clip to the given circle.
+    pub fn circle(center: Pos2, radius: f32) -> Self {
+        Self::rect(
+            Rect::from_center_size(center, Vec2::splat(2.0 * radius)),
+            radius,
+        )
+    }
+
+    /// Clip to a convex polygon. The winding order does not matter.
+    ///
+    /// If the polygon isn't convex the results will be wrong.
+    pub fn from_convex_polygon(mut points: Vec<Pos2>) -> Self {
+        points.dedup();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        if signed_area(&points) < 0.0 {
+            points.reverse();
+        }
+        let bounding_rect = Rect::from_points(&points);
+        Self {
+            points: points.into(),
+            bounding_rect,
+        }
+    }
+
+    /// Use the filled area of the given [`Shape`] as the clip region.
+    ///
+    /// Supported are [`Shape::Rect`] (including rounding), [`Shape::Circle`]
+    /// and closed [`Shape::Path`]s (which are assumed to be convex).
+    /// For any other shape the visual bounding rectangle is used instead.
+    pub fn from_shape(shape: &Shape) -> Self {
+        match shape {
+            Shape::Rect(RectShape { rect, rounding, .. }) => Self::rect(*rect, *rounding),
+            Shape::Circle(CircleShape { center, radius, .. }) => Self::circle(*center, *radius),
+            Shape::Path(PathShape {
+                points,
+                closed: true,
+                ..
+            }) if points.len() >= 3 => Self::from_convex_polygon(points.clone()),
+            _ => Self::rect(shape.visual_bounding_rect(), 0.0),
+        }
+    }
+
+    /// The smallest rectangle containing the whole clip region.
+    #[inline]
+    pub fn bounding_rect(&self) -> Rect {
+        self.bounding_rect
+    }
+
+    /// The convex polygon we clip against.
+    #[inline]
+    pub fn points(&self) -> &[Pos2] {
+        &self.points
+    }
+
+    /// Move the clip region by this many points.
+    pub fn translate(&mut self, delta: Vec2) {
+        if delta != Vec2::ZERO {
+            self.points = self.points.iter().map(|&p| p + delta).collect();
+            self.bounding_rect = self.bounding_rect.translate(delta);
+        }
+    }
+
+    /// The region inside both `self` and `other`.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut polygon = other.points.to_vec();
+        let mut scratch = vec![];
+        self.clip_polygon(&mut polygon, &mut scratch, |p| *p, |a, b, t| a.lerp(b, t));
+        Self::from_convex_polygon(polygon)
+    }
+
+    /// Is the point inside (or on the edge of) the clip region?
+    pub fn contains(&self, pos: Pos2) -> bool {
+        if !self.bounding_rect.contains(pos) {
+            return false;
+        }
+        self.edges().all(|(a, b)| side(a, b, pos) >= 0.0)
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Pos2, Pos2)> + '_ {
+        let n = self.points.len();
+        (0..n).map(move |i| (self.points[i], self.points[(i + 1) % n]))
+    }
+
+    /// Append the part of `mesh` that falls inside the clip region to `out`.
+    ///
+    /// Triangles are split where they cross the edge of the clip region,
+    /// with positions, uv coordinates and colors interpolated linearly.
+    pub fn clip_mesh(&self, mesh: &Mesh, out: &mut Mesh) {
+        if out.is_empty() {
+            out.texture_id = mesh.texture_id;
+        }
+        crate::epaint_assert!(out.texture_id == mesh.texture_id);
+
+        if self.points.len() < 3 {
+            return;
+        }
+
+        let mut polygon = Vec::with_capacity(8);
+        let mut scratch = Vec::with_capacity(8);
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [
+                mesh.vertices[triangle[0] as usize],
+                mesh.vertices[triangle[1] as usize],
+                mesh.vertices[triangle[2] as usize],
+            ];
+
+            let triangle_rect = Rect::from_points(&vertices.map(|v| v.pos));
+            if !self.bounding_rect.intersects(triangle_rect) {
+                continue; // Fully outside
+            }
+
+            if vertices.iter().all(|v| self.contains(v.pos)) {
+                add_polygon(&vertices, out); // Fully inside
+                continue;
+            }
+
+            polygon.clear();
+            polygon.extend_from_slice(&vertices);
+            self.clip_polygon(&mut polygon, &mut scratch, |v| v.pos, lerp_vertex);
+            add_polygon(&polygon, out);
+        }
+    }
+
+    /// Like [`Self::clip_mesh`], but anti-aliases the clipped edge.
+    ///
+    /// The mesh fades out over a band `feathering` points wide,
+    /// centered on the edge of the clip region.
+    pub fn clip_mesh_feathered(&self, mesh: &Mesh, feathering: f32, out: &mut Mesh) {
+        if feathering <= 0.0 {
+            self.clip_mesh(mesh, out);
+            return;
+        }
+
+        if out.is_empty() {
+            out.texture_id = mesh.texture_id;
+        }
+        crate::epaint_assert!(out.texture_id == mesh.texture_id);
+
+        if self.points.len() < 3 {
+            return;
+        }
+
+        let outer = Self::from_convex_polygon(self.offset(0.5 * feathering));
+        let inner = self.offset(-0.5 * feathering);
+        // A clip region thinner than the feathering has no fully opaque part:
+        let inner = (signed_area(&inner) > 0.0).then(|| Self::from_convex_polygon(inner));
+
+        let mut polygon = Vec::with_capacity(8);
+        let mut scratch = Vec::with_capacity(8);
+        let mut band = Vec::with_capacity(8);
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [
+                mesh.vertices[triangle[0] as usize],
+                mesh.vertices[triangle[1] as usize],
+                mesh.vertices[triangle[2] as usize],
+            ];
+
+            let triangle_rect = Rect::from_points(&vertices.map(|v| v.pos));
+            if !outer.bounding_rect.intersects(triangle_rect) {
+                continue; // Fully outside
+            }
+
+            if let Some(inner) = &inner {
+                if vertices.iter().all(|v| inner.contains(v.pos)) {
+                    add_polygon(&vertices, out); // Fully opaque
+                    continue;
+                }
+            }
+
+            polygon.clear();
+            polygon.extend_from_slice(&vertices);
+            outer.clip_polygon(&mut polygon, &mut scratch, |v| v.pos, lerp_vertex);
+
+            let Some(inner) = &inner else {
+                self.fade_out(&mut polygon, feathering);
+                add_polygon(&polygon, out);
+                continue;
+            };
+
+            // Cut off the parts outside the inner polygon, which is where we fade out:
+            for (a, b) in inner.edges() {
+                if polygon.len() < 3 {
+                    break;
+                }
+                split_polygon(&polygon, a, b, &mut scratch, &mut band);
+                self.fade_out(&mut band, feathering);
+                add_polygon(&band, out);
+                std::mem::swap(&mut polygon, &mut scratch);
+            }
+            add_polygon(&polygon, out);
+        }
+    }
+
+    /// The polygon with each edge moved outwards by `distance` (inwards if negative).
+    fn offset(&self, distance: f32) -> Vec<Pos2> {
+        let n = self.points.len();
+        (0..n)
+            .map(|i| {
+                let prev = self.points[(i + n - 1) % n];
+                let p = self.points[i];
+                let next = self.points[(i + 1) % n];
+                let n0 = inward_normal(prev, p);
+                let n1 = inward_normal(p, next);
+                // Where the two offset edges meet:
+                p - (n0 + n1) * (distance / (1.0 + n0.dot(n1)).max(0.1))
+            })
+            .collect()
+    }
+
+    /// Scale the colors by how much of the feathered edge each vertex covers.
+    fn fade_out(&self, polygon: &mut [Vertex], feathering: f32) {
+        for vertex in polygon {
+            let distance = self
+                .edges()
+                .map(|(a, b)| (vertex.pos - a).dot(inward_normal(a, b)))
+                .fold(f32::INFINITY, f32::min);
+            let coverage = (distance / feathering + 0.5).clamp(0.0, 1.0);
+            vertex.color = vertex.color.gamma_multiply(coverage);
+        }
+    }
+
+    /// Sutherland–Hodgman: clip `polygon` against each edge of the (convex) clip polygon.
+    fn clip_polygon<T: Copy>(
+        &self,
+        polygon: &mut Vec<T>,
+        scratch: &mut Vec<T>,
+        pos: impl Fn(&T) -> Pos2,
+        lerp: impl Fn(T, T, f32) -> T,
+    ) {
+        for (a, b) in self.edges() {
+            let Some(&last) = polygon.last() else {
+                return;
+            };
+            std::mem::swap(polygon, scratch);
+            polygon.clear();
+
+            let mut prev = last;
+            let mut prev_side = side(a, b, pos(&prev));
+            for &vertex in &*scratch {
+                let vertex_side = side(a, b, pos(&vertex));
+                if vertex_side >= 0.0 {
+                    if prev_side < 0.0 {
+                        let t = prev_side / (prev_side - vertex_side);
+                        polygon.push(lerp(prev, vertex, t));
+                    }
+                    polygon.push(vertex);
+                } else if prev_side >= 0.0 {
+                    let t = prev_side / (prev_side - vertex_side);
+                    polygon.push(lerp(prev, vertex, t));
+                }
+                prev = vertex;
+                prev_side = vertex_side;
+            }
+        }
+    }
+}
+
+/// Positive if `p` is on the inside (right hand side in screen space) of the edge `a -> b`.
+#[inline(always)]
+fn side(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b - a).x * (p - a).y - (b - a).y * (p - a).x
+}
+
+/// Unit normal of the edge `a -> b`, pointing to the inside.
+#[inline]
+fn inward_normal(a: Pos2, b: Pos2) -> Vec2 {
+    let dir = (b - a).normalized();
+    vec2(-dir.y, dir.x)
+}
+
+/// Split a convex polygon by the line through `a -> b`
+/// into the part on the inside and the part on the outside.
+fn split_polygon(
+    polygon: &[Vertex],
+    a: Pos2,
+    b: Pos2,
+    inside: &mut Vec<Vertex>,
+    outside: &mut Vec<Vertex>,
+) {
+    inside.clear();
+    outside.clear();
+    let Some(&last) = polygon.last() else {
+        return;
+    };
+
+    let mut prev = last;
+    let mut prev_side = side(a, b, prev.pos);
+    for &vertex in polygon {
+        let vertex_side = side(a, b, vertex.pos);
+        if (prev_side >= 0.0) != (vertex_side >= 0.0) {
+            let t = prev_side / (prev_side - vertex_side);
+            let crossing = lerp_vertex(prev, vertex, t);
+            inside.push(crossing);
+            outside.push(crossing);
+        }
+        if vertex_side >= 0.0 {
+            inside.push(vertex);
+        } else {
+            outside.push(vertex);
+        }
+        prev = vertex;
+        prev_side = vertex_side;
+    }
+}
+
+/// Twice the signed area. Positive for clockwise polygons in screen space.
+fn signed_area(points: &[Pos2]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let a = points[i];
+            let b = points[(i + 1) % n];
+            a.x * b.y - b.x * a.y
+        })
+        .sum()
+}
+
+fn lerp_vertex(a: Vertex, b: Vertex, t: f32) -> Vertex {
+    Vertex {
+        pos: a.pos.lerp(b.pos, t),
+        uv: a.uv.lerp(b.uv, t),
+        color: lerp_color(a.color, b.color, t),
+    }
+}
+
+fn lerp_color(a: Color32, b: Color32, t: f32) -> Color32 {
+    let [ar, ag, ab, aa] = a.to_array();
+    let [br, bg, bb, ba] = b.to_array();
+    let l = |a: u8, b: u8| lerp(a as f32..=b as f32, t).round() as u8;
+    Color32::from_rgba_premultiplied(l(ar, br), l(ag, bg), l(ab, bb), l(aa, ba))
+}
+
+/// Add a convex polygon as a triangle fan.
+fn add_polygon(polygon: &[Vertex], out: &mut Mesh) {
+    if polygon.len() < 3 {
+        return;
+    }
+    let idx = out.vertices.len() as u32;
+    out.vertices.extend_from_slice(polygon);
+    for i in 2..polygon.len() as u32 {
+        out.add_triangle(idx, idx + i - 1, idx + i);
+    }
+}
+
+#[test]
+fn test_clip_mesh() {
+    let clip = ClipShape::circle(Pos2::ZERO, 10.0);
+
+    let mut mesh = Mesh::default();
+    mesh.add_colored_rect(
+        Rect::from_min_max(pos2(-20.0, -20.0), pos2(20.0, 20.0)),
+        Color32::WHITE,
+    );
+
+    let mut out = Mesh::default();
+    clip.clip_mesh(&mesh, &mut out);
+
+    assert!(out.is_valid());
+    assert!(!out.is_empty());
+    for vertex in &out.vertices {
+        assert!(vertex.pos.to_vec2().length() <= 10.0 + 1e-3);
+        assert_eq!(vertex.color, Color32::WHITE);
+    }
+
+    // Fully outside:
+    let mut mesh = Mesh::default();
+    mesh.add_colored_rect(
+        Rect::from_min_max(pos2(30.0, 30.0), pos2(40.0, 40.0)),
+        Color32::WHITE,
+    );
+    let mut out = Mesh::default();
+    clip.clip_mesh(&mesh, &mut out);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_clip_mesh_feathered() {
+    let clip = ClipShape::rect(
+        Rect::from_min_max(pos2(-10.0, -10.0), pos2(10.0, 10.0)),
+        0.0,
+    );
+
+    let mut mesh = Mesh::default();
+    mesh.add_colored_rect(
+        Rect::from_min_max(pos2(-20.0, -20.0), pos2(20.0, 20.0)),
+        Color32::WHITE,
+    );
+
+    let feathering = 1.0;
+    let mut out = Mesh::default();
+    clip.clip_mesh_feathered(&mesh, feathering, &mut out);
+
+    assert!(out.is_valid());
+    let mut any_opaque = false;
+    let mut any_faded = false;
+    for vertex in &out.vertices {
+        let distance_outside = vertex.pos.x.abs().max(vertex.pos.y.abs()) - 10.0;
+        assert!(distance_outside <= 0.5 * feathering + 1e-3);
+        if distance_outside < -0.5 * feathering - 1e-3 {
+            assert_eq!(vertex.color, Color32::WHITE);
+        }
+        if (distance_outside - 0.5 * feathering).abs() < 1e-3 {
+            assert_eq!(vertex.color.a(), 0, "The outer edge should be transparent");
+        }
+        any_opaque |= vertex.color == Color32::WHITE;
+        any_faded |= vertex.color != Color32::WHITE;
+    }
+    assert!(any_opaque && any_faded);
+}
@@ -0,0 +1,55 @@
+/// In which color space the painters blend semi-transparent shapes with what is below them.
+///
+/// egui's colors are in `sRGB` gamma space, and the painters always multiply
+/// vertex colors with texture colors in gamma space, since that is what makes text look right.
+/// What differs is the final blending with the framebuffer,
+/// which is what made the same app look different between painters and platforms in the past.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Blending {
+    /// Blend in gamma space, like web browsers and most image editors do.
+    ///
+    /// This is what egui is designed for, and what [`crate::Color32::gamma_multiply`] etc. assume.
+    #[default]
+    Gamma,
+
+    /// Blend in linear space, which is physically correct.
+    ///
+    /// Semi-transparent shapes and anti-aliased edges look lighter than with [`Self::Gamma`],
+    /// but this matches 3D content rendered into the same `sRGB` framebuffer.
+    ///
+    /// This requires an `sRGB` aware framebuffer, which is not available with WebGL.
+    Linear,
+}
+
+/// How the painters turn the colors of egui into pixels on screen.
+///
+/// This is respected by `egui_glow` and `egui-wgpu`,
+/// so that they give the same results for the same options.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ColorManagement {
+    /// In which color space to blend.
+    pub blending: Blending,
+
+    /// Raise the (gamma space) colors to this power before blending.
+    ///
+    /// `1.0` leaves them as they are, larger values darken, smaller values lighten.
+    pub gamma: f32,
+
+    /// Multiply the colors with this after applying [`Self::gamma`].
+    ///
+    /// `1.0` leaves them as they are.
+    pub brightness: f32,
+}
+
+impl Default for ColorManagement {
+    fn default() -> Self {
+        Self {
+            blending: Blending::default(),
+            gamma: 1.0,
+            brightness: 1.0,
+        }
+    }
+}
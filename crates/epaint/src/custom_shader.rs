@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use crate::{PaintCallback, Rect};
+
+/// How many `vec4` parameters a [`CustomShaderCallback`] can pass to its shader.
+pub const MAX_SHADER_PARAMS: usize = 16;
+
+/// The source code of a fragment shader that paints a rectangle, e.g. a gradient or a visualizer.
+///
+/// This is painted with a [`CustomShaderCallback`], which both `egui-wgpu` and `egui_glow` know how to paint,
+/// without any of the setup that a backend specific [`PaintCallback`] needs.
+///
+/// The code defines a function that returns the color for a position in the rectangle,
+/// going from `(0, 0)` in the top left corner to `(1, 1)` in the bottom right corner.
+/// The color is premultiplied and in gamma space, like all colors of egui.
+///
+/// In WGSL it has access to the uniform `shader_rect`:
+///
+/// ```wgsl
+/// fn shade(uv: vec2<f32>) -> vec4<f32> {
+///     // shader_rect.size: vec2<f32>, in points
+///     // shader_rect.pixels_per_point: f32
+///     // shader_rect.params: array<vec4<f32>, 16>
+///     return vec4<f32>(uv, shader_rect.params[0].x, 1.0);
+/// }
+/// ```
+///
+/// The GLSL version is used by `egui_glow`, and is written for all the GLSL versions it supports:
+///
+/// ```glsl
+/// // uniform vec2 u_size;
+/// // uniform float u_pixels_per_point;
+/// // uniform vec4 u_params[16];
+/// vec4 shade(vec2 uv) {
+///     return vec4(uv, u_params[0].x, 1.0);
+/// }
+/// ```
+///
+/// Create this once and keep it around, since the backends cache the compiled shader by [`Self::id`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderCode {
+    id: u64,
+    wgsl: String,
+    glsl: Option<String>,
+}
+
+impl ShaderCode {
+    /// Code for `egui-wgpu`.
+    ///
+    /// Use [`Self::with_glsl`] to also support `egui_glow`.
+    pub fn new(wgsl: impl Into<String>) -> Self {
+        let wgsl = wgsl.into();
+        Self {
+            id: crate::util::hash((&wgsl, None::<&str>)),
+            wgsl,
+            glsl: None,
+        }
+    }
+
+    /// Code for `egui_glow`. Without it the rectangle isn't painted by `egui_glow`.
+    #[inline]
+    pub fn with_glsl(mut self, glsl: impl Into<String>) -> Self {
+        let glsl = glsl.into();
+        self.id = crate::util::hash((&self.wgsl, Some(&glsl)));
+        self.glsl = Some(glsl);
+        self
+    }
+
+    /// Identifies the code, for caching compiled shaders.
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    #[inline]
+    pub fn wgsl(&self) -> &str {
+        &self.wgsl
+    }
+
+    #[inline]
+    pub fn glsl(&self) -> Option<&str> {
+        self.glsl.as_deref()
+    }
+}
+
+/// Paint a rectangle with a [`ShaderCode`].
+///
+/// Use this as [`PaintCallback::callback`], e.g. with [`Self::paint_callback`].
+/// If you are using egui, use `egui::ShaderRect` instead.
+#[derive(Clone, Debug)]
+pub struct CustomShaderCallback {
+    pub code: Arc<ShaderCode>,
+
+    /// Available to the shader as `shader_rect.params` (WGSL) or `u_params` (GLSL).
+    ///
+    /// At most [`MAX_SHADER_PARAMS`] are used, the rest are zero.
+    pub params: Vec<[f32; 4]>,
+}
+
+impl CustomShaderCallback {
+    pub fn new(code: Arc<ShaderCode>) -> Self {
+        Self {
+            code,
+            params: Vec::new(),
+        }
+    }
+
+    /// The [`Self::params`], padded with zeros to [`MAX_SHADER_PARAMS`].
+    pub fn padded_params(&self) -> [[f32; 4]; MAX_SHADER_PARAMS] {
+        #[cfg(feature = "log")]
+        if self.params.len() > MAX_SHADER_PARAMS {
+            log::warn!(
+                "A custom shader has {} params, but only the first {MAX_SHADER_PARAMS} are used",
+                self.params.len()
+            );
+        }
+        let mut params = [[0.0; 4]; MAX_SHADER_PARAMS];
+        for (param, value) in params.iter_mut().zip(&self.params) {
+            *param = *value;
+        }
+        params
+    }
+
+    /// Paint within the given rectangle.
+    pub fn paint_callback(self, rect: Rect) -> PaintCallback {
+        PaintCallback {
+            rect,
+            callback: Arc::new(self),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use crate::{
+    emath::{pos2, Rect},
+    mutex::RwLock,
+    textures::TextureOptions,
+    ColorImage, TextureHandle, TextureId, TextureManager,
+};
+
+/// A texture that changes every frame, e.g. the frames of a video or the trace of an oscilloscope.
+///
+/// Changes are written to a copy of the image in RAM,
+/// and [`Self::id`] uploads everything that changed since its last call as one region update.
+///
+/// Two textures are used in turn, so that the one being uploaded to
+/// is not the one the GPU may still be reading from for the previous frame.
+///
+/// If you are using egui, use `egui::Context::load_dynamic_texture`.
+#[must_use]
+pub struct DynamicTexture {
+    /// The latest contents.
+    image: ColorImage,
+
+    options: TextureOptions,
+
+    /// `textures[front]` is the one to paint, the other one is uploaded to next.
+    textures: [TextureHandle; 2],
+    front: usize,
+
+    /// What changed since the last upload.
+    dirty: Option<Region>,
+
+    /// What the back texture is missing, i.e. what changed in the last upload.
+    back_stale: Option<Region>,
+}
+
+impl DynamicTexture {
+    /// If you are using egui, use `egui::Context::load_dynamic_texture` instead.
+    pub fn new(
+        tex_mngr: &Arc<RwLock<TextureManager>>,
+        name: &str,
+        image: ColorImage,
+        options: TextureOptions,
+    ) -> Self {
+        let textures = ["front", "back"].map(|buffer| {
+            let id =
+                tex_mngr
+                    .write()
+                    .alloc(format!("{name} ({buffer})"), image.clone().into(), options);
+            TextureHandle::new(tex_mngr.clone(), id)
+        });
+        Self {
+            image,
+            options,
+            textures,
+            front: 0,
+            dirty: None,
+            back_stale: None,
+        }
+    }
+
+    /// Upload what changed since the last call, and return the texture to paint this frame.
+    ///
+    /// Call this once per frame after making your changes.
+    pub fn id(&mut self) -> TextureId {
+        if let Some(dirty) = self.dirty.take() {
+            let region = match self.back_stale {
+                Some(back_stale) => dirty.union(back_stale),
+                None => dirty,
+            };
+            let back = 1 - self.front;
+            let patch = self.image.region(&region.to_rect(), None);
+            self.textures[back].set_partial(region.min, patch, self.options);
+            self.front = back;
+            self.back_stale = Some(dirty);
+        }
+        self.textures[self.front].id()
+    }
+
+    /// width x height
+    #[inline]
+    pub fn size(&self) -> [usize; 2] {
+        self.image.size
+    }
+
+    /// width x height
+    #[inline]
+    pub fn size_vec2(&self) -> crate::Vec2 {
+        let [w, h] = self.size();
+        crate::Vec2::new(w as f32, h as f32)
+    }
+
+    /// The latest contents, including changes that are not yet uploaded.
+    #[inline]
+    pub fn image(&self) -> &ColorImage {
+        &self.image
+    }
+
+    /// Replace the whole image.
+    ///
+    /// Changing the size re-creates the textures.
+    pub fn set(&mut self, image: ColorImage) {
+        if image.size == self.image.size {
+            self.dirty = Some(Region::whole(image.size));
+        } else {
+            for texture in &mut self.textures {
+                texture.set(image.clone(), self.options);
+            }
+            self.dirty = None;
+            self.back_stale = None;
+        }
+        self.image = image;
+    }
+
+    /// Replace a region of the image, with its top left corner at `pos`.
+    ///
+    /// All changes are batched up until the next call to [`Self::id`].
+    pub fn set_partial(&mut self, pos: [usize; 2], image: &ColorImage) {
+        self.image.paste(pos, image);
+        let region = Region {
+            min: pos,
+            max: [pos[0] + image.width(), pos[1] + image.height()],
+        };
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => dirty.union(region),
+            None => region,
+        });
+    }
+}
+
+/// The pixels from `min` (inclusive) to `max` (exclusive).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Region {
+    min: [usize; 2],
+    max: [usize; 2],
+}
+
+impl Region {
+    fn whole(size: [usize; 2]) -> Self {
+        Self {
+            min: [0, 0],
+            max: size,
+        }
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            min: [self.min[0].min(other.min[0]), self.min[1].min(other.min[1])],
+            max: [self.max[0].max(other.max[0]), self.max[1].max(other.max[1])],
+        }
+    }
+
+    fn to_rect(self) -> Rect {
+        Rect::from_min_max(
+            pos2(self.min[0] as f32, self.min[1] as f32),
+            pos2(self.max[0] as f32, self.max[1] as f32),
+        )
+    }
+}
+
+#[test]
+fn test_dynamic_texture() {
+    use crate::{Color32, ImageData};
+
+    let tex_mngr = Arc::new(RwLock::new(TextureManager::default()));
+    let mut texture = DynamicTexture::new(
+        &tex_mngr,
+        "scope",
+        ColorImage::new([16, 8], Color32::BLACK),
+        Default::default(),
+    );
+    assert_eq!(tex_mngr.write().take_delta().set.len(), 2);
+
+    // Nothing changed:
+    let first = texture.id();
+    assert!(tex_mngr.write().take_delta().is_empty());
+
+    // Many changes result in one upload, to the other texture:
+    let pixel = ColorImage::new([1, 1], Color32::WHITE);
+    texture.set_partial([2, 1], &pixel);
+    texture.set_partial([4, 3], &pixel);
+    let second = texture.id();
+    assert_ne!(first, second);
+    let delta = tex_mngr.write().take_delta();
+    assert_eq!(delta.set.len(), 1);
+    let (id, image_delta) = &delta.set[0];
+    assert_eq!(*id, second);
+    assert_eq!(image_delta.pos, Some([2, 1]));
+    assert_eq!(image_delta.image.size(), [3, 3]);
+
+    // The first texture also gets the changes it missed:
+    texture.set_partial([10, 5], &pixel);
+    assert_eq!(texture.id(), first);
+    let delta = tex_mngr.write().take_delta();
+    assert_eq!(delta.set.len(), 1);
+    let (_, image_delta) = &delta.set[0];
+    assert_eq!(image_delta.pos, Some([2, 1]));
+    assert_eq!(image_delta.image.size(), [9, 5]);
+    let ImageData::Color(patch) = &image_delta.image else {
+        panic!("Expected a color image");
+    };
+    assert_eq!(patch[(0, 0)], Color32::WHITE);
+    assert_eq!(patch[(8, 4)], Color32::WHITE);
+    assert_eq!(patch[(1, 0)], Color32::BLACK);
+}
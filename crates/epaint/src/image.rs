@@ -39,6 +39,29 @@ impl ImageData {
             Self::Color(_) | Self::Font(_) => 4,
         }
     }
+
+    /// Clone a sub-region as a new image.
+    ///
+    /// Panics if the region is outside the image.
+    pub fn region(&self, pos: [usize; 2], size: [usize; 2]) -> Self {
+        if pos == [0, 0] && size == self.size() {
+            return self.clone();
+        }
+        match self {
+            Self::Color(image) => {
+                let [x, y] = pos;
+                let [w, h] = size;
+                assert!(x + w <= image.width() && y + h <= image.height());
+                let mut pixels = Vec::with_capacity(w * h);
+                for row in y..y + h {
+                    let offset = row * image.width() + x;
+                    pixels.extend_from_slice(&image.pixels[offset..offset + w]);
+                }
+                Self::Color(Arc::new(ColorImage { size, pixels }))
+            }
+            Self::Font(image) => Self::Font(image.region(pos, size)),
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -161,6 +184,18 @@ impl ColorImage {
         }
     }
 
+    /// Copy all pixels of `image` into this image, with its top left corner at `[x, y]`.
+    ///
+    /// Panics if `image` doesn't fit.
+    pub fn paste(&mut self, [x, y]: [usize; 2], image: &Self) {
+        let [w, h] = image.size;
+        assert!(x + w <= self.width() && y + h <= self.height());
+        for row in 0..h {
+            let offset = (y + row) * self.width() + x;
+            self.pixels[offset..offset + w].copy_from_slice(&image.pixels[row * w..(row + 1) * w]);
+        }
+    }
+
     /// Create a [`ColorImage`] from flat RGB data.
     ///
     /// This is what you want to use after having loaded an image file (and if
@@ -254,6 +289,10 @@ impl std::fmt::Debug for ColorImage {
 /// Each value represents "coverage", i.e. how much a texel is covered by a character.
 ///
 /// This is roughly interpreted as the opacity of a white image.
+///
+/// Color glyphs (emojis) are stored in [`Self::colors`] instead.
+///
+/// If [`Self::distance_field`] is set, each value is instead a signed distance to the edge of a character.
 #[derive(Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FontImage {
@@ -264,6 +303,20 @@ pub struct FontImage {
     ///
     /// Often you want to use [`Self::srgba_pixels`] instead.
     pub pixels: Vec<f32>,
+
+    /// Premultiplied colors of color glyphs, used instead of the coverage where not [`Color32::TRANSPARENT`].
+    ///
+    /// Empty until the first color glyph is added, see [`Self::set_color`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colors: Vec<Color32>,
+
+    /// If set, [`Self::pixels`] is a signed distance field:
+    /// 0.5 is the edge of a character, larger values are inside it, and smaller values outside.
+    ///
+    /// The backend must then turn the alpha of the texture into coverage in its fragment shader,
+    /// which keeps the edges crisp when the text is scaled up.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub distance_field: bool,
 }
 
 impl FontImage {
@@ -271,6 +324,8 @@ impl FontImage {
         Self {
             size,
             pixels: vec![0.0; size[0] * size[1]],
+            colors: vec![],
+            distance_field: false,
         }
     }
 
@@ -289,10 +344,22 @@ impl FontImage {
     /// `gamma` should normally be set to `None`.
     ///
     /// If you are having problems with text looking skinny and pixelated, try using a low gamma, e.g. `0.4`.
+    ///
+    /// The gamma is ignored for a [`Self::distance_field`].
     #[inline]
     pub fn srgba_pixels(&self, gamma: Option<f32>) -> impl ExactSizeIterator<Item = Color32> + '_ {
-        let gamma = gamma.unwrap_or(0.55); // TODO(emilk): this default coverage gamma is a magic constant, chosen by eye. I don't even know why we need it.
-        self.pixels.iter().map(move |coverage| {
+        let gamma = if self.distance_field {
+            1.0
+        } else {
+            gamma.unwrap_or(0.55) // TODO(emilk): this default coverage gamma is a magic constant, chosen by eye. I don't even know why we need it.
+        };
+        let colors = &self.colors;
+        self.pixels.iter().enumerate().map(move |(i, coverage)| {
+            if let Some(&color) = colors.get(i) {
+                if color != Color32::TRANSPARENT {
+                    return color;
+                }
+            }
             let alpha = coverage.powf(gamma);
             // We want to multiply with `vec4(alpha)` in the fragment shader:
             let a = fast_round(alpha * 255.0);
@@ -300,20 +367,39 @@ impl FontImage {
         })
     }
 
+    /// Set the premultiplied color of a texel of a color glyph.
+    pub fn set_color(&mut self, (x, y): (usize, usize), color: Color32) {
+        let [w, h] = self.size;
+        assert!(x < w && y < h);
+        if self.colors.is_empty() {
+            if color == Color32::TRANSPARENT {
+                return;
+            }
+            self.colors = vec![Color32::TRANSPARENT; self.pixels.len()];
+        }
+        self.colors[y * w + x] = color;
+    }
+
     /// Clone a sub-region as a new image.
     pub fn region(&self, [x, y]: [usize; 2], [w, h]: [usize; 2]) -> Self {
         assert!(x + w <= self.width());
         assert!(y + h <= self.height());
 
         let mut pixels = Vec::with_capacity(w * h);
+        let mut colors = Vec::with_capacity(if self.colors.is_empty() { 0 } else { w * h });
         for y in y..y + h {
             let offset = y * self.width() + x;
             pixels.extend(&self.pixels[offset..(offset + w)]);
+            if !self.colors.is_empty() {
+                colors.extend(&self.colors[offset..(offset + w)]);
+            }
         }
         assert_eq!(pixels.len(), w * h);
         Self {
             size: [w, h],
             pixels,
+            colors,
+            distance_field: self.distance_field,
         }
     }
 }
@@ -26,6 +26,10 @@
 #![cfg_attr(not(feature = "puffin"), forbid(unsafe_code))]
 
 mod bezier;
+mod clip_shape;
+mod color_management;
+mod custom_shader;
+mod dynamic_texture;
 pub mod image;
 mod mesh;
 pub mod mutex;
@@ -43,8 +47,12 @@ pub mod util;
 
 pub use {
     bezier::{CubicBezierShape, QuadraticBezierShape},
+    clip_shape::ClipShape,
+    color_management::{Blending, ColorManagement},
+    custom_shader::{CustomShaderCallback, ShaderCode, MAX_SHADER_PARAMS},
+    dynamic_texture::DynamicTexture,
     image::{ColorImage, FontImage, ImageData, ImageDelta},
-    mesh::{Mesh, Mesh16, Vertex},
+    mesh::{InstanceTransform, InstancedMesh, Mesh, Mesh16, Vertex},
     shadow::Shadow,
     shape::{
         CircleShape, PaintCallback, PaintCallbackInfo, PathShape, RectShape, Rounding, Shape,
@@ -54,7 +62,7 @@ pub use {
     stroke::Stroke,
     tessellator::{tessellate_shapes, TessellationOptions, Tessellator},
     text::{FontFamily, FontId, Fonts, Galley},
-    texture_atlas::TextureAtlas,
+    texture_atlas::{TextureAtlas, TextureAtlasStats},
     texture_handle::TextureHandle,
     textures::TextureManager,
 };
@@ -107,10 +115,32 @@ pub struct ClippedShape {
     /// Only show the part of the [`Shape`] that falls within this.
     pub clip_rect: emath::Rect,
 
+    /// Optional non-rectangular clip region, applied in addition to [`Self::clip_rect`].
+    ///
+    /// A [`Shape::Callback`] that crosses the edge of the clip shape is not painted.
+    pub clip_shape: Option<ClipShape>,
+
+    /// If set, these options are used instead of those of the [`Tessellator`],
+    /// e.g. to turn off feathering for pixel art.
+    pub tessellation_options: Option<TessellationOptions>,
+
     /// The shape
     pub shape: Shape,
 }
 
+impl ClippedShape {
+    /// A [`Shape`] clipped only by a clip rectangle.
+    #[inline]
+    pub fn new(clip_rect: emath::Rect, shape: Shape) -> Self {
+        Self {
+            clip_rect,
+            clip_shape: None,
+            tessellation_options: None,
+            shape,
+        }
+    }
+}
+
 /// A [`Mesh`] or [`PaintCallback`] within a clip rectangle.
 ///
 /// Everything is using logical points.
@@ -128,6 +158,12 @@ pub struct ClippedPrimitive {
 #[derive(Clone, Debug)]
 pub enum Primitive {
     Mesh(Mesh),
+
+    /// The same mesh painted many times.
+    ///
+    /// Render it with instancing, or expand it with [`InstancedMesh::to_mesh`].
+    Instanced(InstancedMesh),
+
     Callback(PaintCallback),
 }
 
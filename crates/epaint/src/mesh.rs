@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::*;
 use emath::*;
 
@@ -287,6 +289,105 @@ impl Mesh {
 
 // ----------------------------------------------------------------------------
 
+/// Where to paint one copy of an instanced [`Mesh`], see [`crate::Shape::Instanced`].
+///
+/// A vertex at `pos` in the mesh ends up at `translation + rotation * pos`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct InstanceTransform {
+    /// Rotation around the origin of the mesh.
+    ///
+    /// A non-normalized [`Rot2`] also scales the mesh.
+    pub rotation: Rot2,
+
+    /// Where the origin of the mesh ends up.
+    pub translation: Vec2,
+}
+
+impl InstanceTransform {
+    /// Move the mesh without rotating or scaling it.
+    #[inline]
+    pub fn from_translation(translation: Vec2) -> Self {
+        Self {
+            rotation: Rot2::IDENTITY,
+            translation,
+        }
+    }
+
+    /// Scale the mesh by this factor, then move it.
+    #[inline]
+    pub fn from_scale_translation(scale: f32, translation: Vec2) -> Self {
+        Self {
+            rotation: scale * Rot2::IDENTITY,
+            translation,
+        }
+    }
+
+    #[inline]
+    pub fn transform_pos(&self, pos: Pos2) -> Pos2 {
+        (self.translation + self.rotation * pos.to_vec2()).to_pos2()
+    }
+
+    /// The bounding rectangle of the transformed `rect`.
+    pub fn transform_rect(&self, rect: Rect) -> Rect {
+        let mut bounds = Rect::NOTHING;
+        for corner in [
+            rect.left_top(),
+            rect.right_top(),
+            rect.left_bottom(),
+            rect.right_bottom(),
+        ] {
+            bounds.extend_with(self.transform_pos(corner));
+        }
+        bounds
+    }
+}
+
+/// The same [`Mesh`] painted once for each transform.
+///
+/// This is what [`crate::Shape::Instanced`] is tessellated into.
+/// Render backends without support for instancing can use [`Self::to_mesh`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstancedMesh {
+    /// Shared by all instances.
+    pub mesh: Arc<Mesh>,
+
+    /// One per instance.
+    pub transforms: Vec<InstanceTransform>,
+}
+
+impl InstancedMesh {
+    /// Number of vertices if every instance got a copy of the mesh.
+    pub fn num_expanded_vertices(&self) -> usize {
+        self.mesh.vertices.len() * self.transforms.len()
+    }
+
+    /// One mesh with a transformed copy of the vertices and indices for each instance.
+    pub fn to_mesh(&self) -> Mesh {
+        let mut out = Mesh::with_texture(self.mesh.texture_id);
+        out.reserve_vertices(self.num_expanded_vertices());
+        out.indices
+            .reserve(self.mesh.indices.len() * self.transforms.len());
+
+        for transform in &self.transforms {
+            let index_offset = out.vertices.len() as u32;
+            out.indices
+                .extend(self.mesh.indices.iter().map(|index| index + index_offset));
+            out.vertices
+                .extend(self.mesh.vertices.iter().map(|vertex| Vertex {
+                    pos: transform.transform_pos(vertex.pos),
+                    ..*vertex
+                }));
+        }
+
+        out
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// A version of [`Mesh`] that uses 16-bit indices.
 ///
 /// This is produced by [`Mesh::split_to_u16`] and is meant to be used for legacy render backends.
@@ -4,7 +4,7 @@ use std::{any::Any, sync::Arc};
 
 use crate::{
     text::{FontId, Fonts, Galley},
-    Color32, Mesh, Stroke, TextureId,
+    Color32, InstanceTransform, Mesh, Stroke, TextureId,
 };
 use emath::*;
 
@@ -50,6 +50,16 @@ pub enum Shape {
     /// Can be used to display images.
     Mesh(Mesh),
 
+    /// The same [`Mesh`] painted once per transform,
+    /// e.g. the markers of a scatter plot or a grid of identical icons.
+    ///
+    /// Render backends that support instancing upload the mesh only once,
+    /// so the number of vertices doesn't grow with the number of instances.
+    Instanced {
+        mesh: Arc<Mesh>,
+        transforms: Vec<InstanceTransform>,
+    },
+
     /// A quadratic [Bézier Curve](https://en.wikipedia.org/wiki/B%C3%A9zier_curve).
     QuadraticBezier(QuadraticBezierShape),
 
@@ -300,6 +310,14 @@ impl Shape {
         Self::Mesh(mesh)
     }
 
+    /// Paint the same mesh once per transform, see [`Self::Instanced`].
+    #[inline]
+    pub fn instanced(mesh: impl Into<Arc<Mesh>>, transforms: Vec<InstanceTransform>) -> Self {
+        let mesh = mesh.into();
+        crate::epaint_assert!(mesh.is_valid());
+        Self::Instanced { mesh, transforms }
+    }
+
     /// An image at the given position.
     ///
     /// `uv` should normally be `Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0))`
@@ -335,6 +353,14 @@ impl Shape {
             Self::Rect(rect_shape) => rect_shape.visual_bounding_rect(),
             Self::Text(text_shape) => text_shape.visual_bounding_rect(),
             Self::Mesh(mesh) => mesh.calc_bounds(),
+            Self::Instanced { mesh, transforms } => {
+                let mesh_bounds = mesh.calc_bounds();
+                let mut rect = Rect::NOTHING;
+                for transform in transforms {
+                    rect = rect.union(transform.transform_rect(mesh_bounds));
+                }
+                rect
+            }
             Self::QuadraticBezier(bezier) => bezier.visual_bounding_rect(),
             Self::CubicBezier(bezier) => bezier.visual_bounding_rect(),
             Self::Callback(custom) => custom.rect,
@@ -348,6 +374,8 @@ impl Shape {
     pub fn texture_id(&self) -> super::TextureId {
         if let Self::Mesh(mesh) = self {
             mesh.texture_id
+        } else if let Self::Instanced { mesh, .. } = self {
+            mesh.texture_id
         } else if let Self::Rect(rect_shape) = self {
             rect_shape.fill_texture_id
         } else {
@@ -386,6 +414,11 @@ impl Shape {
             Self::Mesh(mesh) => {
                 mesh.translate(delta);
             }
+            Self::Instanced { transforms, .. } => {
+                for transform in transforms {
+                    transform.translation += delta;
+                }
+            }
             Self::QuadraticBezier(bezier_shape) => {
                 bezier_shape.points[0] += delta;
                 bezier_shape.points[1] += delta;
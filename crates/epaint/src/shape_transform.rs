@@ -84,6 +84,15 @@ pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
             }
         }
 
+        Shape::Instanced {
+            mesh,
+            transforms: _,
+        } => {
+            for v in &mut std::sync::Arc::make_mut(mesh).vertices {
+                adjust_color(&mut v.color);
+            }
+        }
+
         Shape::Callback(_) => {
             // Can't tint user callback code
         }
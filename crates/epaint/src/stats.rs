@@ -219,6 +219,9 @@ impl PaintStats {
             Shape::Mesh(mesh) => {
                 self.shape_mesh += AllocInfo::from_mesh(mesh);
             }
+            Shape::Instanced { mesh, .. } => {
+                self.shape_mesh += AllocInfo::from_mesh(mesh);
+            }
             Shape::Callback(_) => {
                 self.num_callbacks += 1;
             }
@@ -231,9 +234,16 @@ impl PaintStats {
     ) -> Self {
         self.clipped_primitives += AllocInfo::from_slice(clipped_primitives);
         for clipped_primitive in clipped_primitives {
-            if let Primitive::Mesh(mesh) = &clipped_primitive.primitive {
-                self.vertices += AllocInfo::from_slice(&mesh.vertices);
-                self.indices += AllocInfo::from_slice(&mesh.indices);
+            match &clipped_primitive.primitive {
+                Primitive::Mesh(mesh) => {
+                    self.vertices += AllocInfo::from_slice(&mesh.vertices);
+                    self.indices += AllocInfo::from_slice(&mesh.indices);
+                }
+                Primitive::Instanced(instanced) => {
+                    self.vertices += AllocInfo::from_slice(&instanced.mesh.vertices);
+                    self.indices += AllocInfo::from_slice(&instanced.mesh.indices);
+                }
+                Primitive::Callback(_) => {}
             }
         }
         self
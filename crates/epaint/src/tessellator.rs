@@ -640,6 +640,15 @@ pub struct TessellationOptions {
     /// This makes the text sharper on most platforms.
     pub round_text_to_pixels: bool,
 
+    /// If `true`, horizontal and vertical line segments and the strokes of rectangles
+    /// are snapped to the physical pixel grid, and their widths rounded to whole pixels.
+    ///
+    /// This avoids blurry hairlines (e.g. 1px borders) at fractional scale factors such as `1.25`,
+    /// at the cost of shapes moving by up to half a pixel.
+    ///
+    /// Default: `false`.
+    pub crisp_lines: bool,
+
     /// Output the clip rectangles to be painted.
     pub debug_paint_clip_rects: bool,
 
@@ -664,6 +673,7 @@ impl Default for TessellationOptions {
             coarse_tessellation_culling: true,
             prerasterized_discs: true,
             round_text_to_pixels: true,
+            crisp_lines: false,
             debug_paint_text_rects: false,
             debug_paint_clip_rects: false,
             debug_ignore_clip_rects: false,
@@ -673,6 +683,16 @@ impl Default for TessellationOptions {
     }
 }
 
+/// Size of feathering in points, or 0.0 if disabled.
+fn feathering_in_points(pixels_per_point: f32, options: &TessellationOptions) -> f32 {
+    if options.feathering {
+        let pixel_size = 1.0 / pixels_per_point;
+        options.feathering_size_in_pixels * pixel_size
+    } else {
+        0.0
+    }
+}
+
 fn cw_signed_area(path: &[PathPoint]) -> f64 {
     if let Some(last) = path.last() {
         let mut previous = last.pos;
@@ -1094,24 +1114,30 @@ impl Tessellator {
         font_tex_size: [usize; 2],
         prepared_discs: Vec<PreparedDisc>,
     ) -> Self {
-        let feathering = if options.feathering {
-            let pixel_size = 1.0 / pixels_per_point;
-            options.feathering_size_in_pixels * pixel_size
-        } else {
-            0.0
-        };
         Self {
             pixels_per_point,
             options,
             font_tex_size,
             prepared_discs,
-            feathering,
+            feathering: feathering_in_points(pixels_per_point, &options),
             clip_rect: Rect::EVERYTHING,
             scratchpad_points: Default::default(),
             scratchpad_path: Default::default(),
         }
     }
 
+    /// The options currently used for tessellation.
+    #[inline]
+    pub fn options(&self) -> &TessellationOptions {
+        &self.options
+    }
+
+    /// Change the options used for tessellating the following shapes.
+    pub fn set_options(&mut self, options: TessellationOptions) {
+        self.feathering = feathering_in_points(self.pixels_per_point, &options);
+        self.options = options;
+    }
+
     /// Set the [`Rect`] to use for culling.
     pub fn set_clip_rect(&mut self, clip_rect: Rect) {
         self.clip_rect = clip_rect;
@@ -1126,17 +1152,68 @@ impl Tessellator {
         }
     }
 
+    /// Round the stroke width to whole physical pixels if [`TessellationOptions::crisp_lines`] is set.
+    fn crisp_stroke(&self, stroke: Stroke) -> Stroke {
+        if self.options.crisp_lines && stroke.width > 0.0 {
+            let width_in_pixels = (stroke.width * self.pixels_per_point).round().max(1.0);
+            Stroke::new(width_in_pixels / self.pixels_per_point, stroke.color)
+        } else {
+            stroke
+        }
+    }
+
+    /// Snap a coordinate so that a line of the given (already crisp) width
+    /// centered on it covers whole physical pixels.
+    fn crisp_coordinate(&self, coordinate: f32, stroke_width: f32) -> f32 {
+        let ppp = self.pixels_per_point;
+        let width_in_pixels = (stroke_width * ppp).round() as i64;
+        if width_in_pixels % 2 == 1 {
+            // Odd widths are centered on a pixel center:
+            ((coordinate * ppp).floor() + 0.5) / ppp
+        } else {
+            // Even widths are centered on a pixel edge:
+            (coordinate * ppp).round() / ppp
+        }
+    }
+
     /// Tessellate a clipped shape into a list of primitives.
+    ///
+    /// A [`Shape::Callback`] that crosses the edge of its [`ClipShape`] is not painted,
+    /// since callbacks can only be clipped to rectangles.
     pub fn tessellate_clipped_shape(
         &mut self,
         clipped_shape: ClippedShape,
         out_primitives: &mut Vec<ClippedPrimitive>,
     ) {
         let ClippedShape {
-            clip_rect: new_clip_rect,
+            clip_rect: mut new_clip_rect,
+            clip_shape,
+            tessellation_options,
             shape: new_shape,
         } = clipped_shape;
 
+        if let Some(options) = tessellation_options {
+            if options != self.options {
+                let previous_options = self.options;
+                self.set_options(options);
+                self.tessellate_clipped_shape(
+                    ClippedShape {
+                        clip_rect: new_clip_rect,
+                        clip_shape,
+                        tessellation_options: None,
+                        shape: new_shape,
+                    },
+                    out_primitives,
+                );
+                self.set_options(previous_options);
+                return;
+            }
+        }
+
+        if let Some(clip_shape) = &clip_shape {
+            new_clip_rect = new_clip_rect.intersect(clip_shape.bounding_rect());
+        }
+
         if !new_clip_rect.is_positive() {
             return; // skip empty clip rectangles
         }
@@ -1146,6 +1223,8 @@ impl Tessellator {
                 self.tessellate_clipped_shape(
                     ClippedShape {
                         clip_rect: new_clip_rect,
+                        clip_shape: clip_shape.clone(),
+                        tessellation_options: None,
                         shape,
                     },
                     out_primitives,
@@ -1155,6 +1234,25 @@ impl Tessellator {
         }
 
         if let Shape::Callback(callback) = new_shape {
+            if let Some(clip_shape) = &clip_shape {
+                // Callbacks can only be clipped to a rectangle,
+                // so we only paint them if that rectangle is inside the clip shape.
+                let visible_rect = callback.rect.intersect(new_clip_rect);
+                if !visible_rect.is_positive() {
+                    return;
+                }
+                let corners = [
+                    visible_rect.left_top(),
+                    visible_rect.right_top(),
+                    visible_rect.left_bottom(),
+                    visible_rect.right_bottom(),
+                ];
+                if !corners.iter().all(|&corner| clip_shape.contains(corner)) {
+                    #[cfg(feature = "log")]
+                    warn_callback_crosses_clip_shape();
+                    return;
+                }
+            }
             out_primitives.push(ClippedPrimitive {
                 clip_rect: new_clip_rect,
                 primitive: Primitive::Callback(callback),
@@ -1162,6 +1260,36 @@ impl Tessellator {
             return;
         }
 
+        // With a clip shape we need to clip the actual triangles,
+        // so then the instances are expanded into a normal mesh below.
+        if clip_shape.is_none() {
+            if let Shape::Instanced {
+                mesh,
+                mut transforms,
+            } = new_shape
+            {
+                if !mesh.is_valid() {
+                    crate::epaint_assert!(false, "Invalid Mesh in Shape::Instanced");
+                    return;
+                }
+
+                if self.options.coarse_tessellation_culling {
+                    let mesh_bounds = mesh.calc_bounds();
+                    transforms.retain(|transform| {
+                        new_clip_rect.intersects(transform.transform_rect(mesh_bounds))
+                    });
+                }
+
+                if !mesh.is_empty() && !transforms.is_empty() {
+                    out_primitives.push(ClippedPrimitive {
+                        clip_rect: new_clip_rect,
+                        primitive: Primitive::Instanced(InstancedMesh { mesh, transforms }),
+                    });
+                }
+                return;
+            }
+        }
+
         let start_new_mesh = match out_primitives.last() {
             None => true,
             Some(output_clipped_primitive) => {
@@ -1170,7 +1298,7 @@ impl Tessellator {
                         Primitive::Mesh(output_mesh) => {
                             output_mesh.texture_id != new_shape.texture_id()
                         }
-                        Primitive::Callback(_) => true,
+                        Primitive::Instanced(_) | Primitive::Callback(_) => true,
                     }
             }
         };
@@ -1186,7 +1314,13 @@ impl Tessellator {
 
         if let Primitive::Mesh(out_mesh) = &mut out.primitive {
             self.clip_rect = new_clip_rect;
-            self.tessellate_shape(new_shape, out_mesh);
+            if let Some(clip_shape) = clip_shape {
+                let mut mesh = Mesh::with_texture(out_mesh.texture_id);
+                self.tessellate_shape(new_shape, &mut mesh);
+                clip_shape.clip_mesh_feathered(&mesh, self.feathering, out_mesh);
+            } else {
+                self.tessellate_shape(new_shape, out_mesh);
+            }
         } else {
             unreachable!();
         }
@@ -1222,6 +1356,9 @@ impl Tessellator {
                 }
                 out.append(mesh);
             }
+            Shape::Instanced { mesh, transforms } => {
+                self.tessellate_mesh(&InstancedMesh { mesh, transforms }.to_mesh(), out);
+            }
             Shape::LineSegment { points, stroke } => self.tessellate_line(points, stroke, out),
             Shape::Path(path_shape) => {
                 self.tessellate_path(&path_shape, out);
@@ -1328,11 +1465,27 @@ impl Tessellator {
     ///
     /// * `shape`: the mesh to tessellate.
     /// * `out`: triangles are appended to this.
-    pub fn tessellate_line(&mut self, points: [Pos2; 2], stroke: Stroke, out: &mut Mesh) {
+    pub fn tessellate_line(&mut self, mut points: [Pos2; 2], stroke: Stroke, out: &mut Mesh) {
         if stroke.is_empty() {
             return;
         }
 
+        let stroke = if self.options.crisp_lines {
+            let stroke = self.crisp_stroke(stroke);
+            if points[0].x == points[1].x {
+                let x = self.crisp_coordinate(points[0].x, stroke.width);
+                points[0].x = x;
+                points[1].x = x;
+            } else if points[0].y == points[1].y {
+                let y = self.crisp_coordinate(points[0].y, stroke.width);
+                points[0].y = y;
+                points[1].y = y;
+            }
+            stroke
+        } else {
+            stroke
+        };
+
         if self.options.coarse_tessellation_culling
             && !self
                 .clip_rect
@@ -1420,6 +1573,17 @@ impl Tessellator {
         rect.min = rect.min.at_least(pos2(-1e7, -1e7));
         rect.max = rect.max.at_most(pos2(1e7, 1e7));
 
+        let stroke = if self.options.crisp_lines && !stroke.is_empty() {
+            let stroke = self.crisp_stroke(stroke);
+            rect.min.x = self.crisp_coordinate(rect.min.x, stroke.width);
+            rect.min.y = self.crisp_coordinate(rect.min.y, stroke.width);
+            rect.max.x = self.crisp_coordinate(rect.max.x, stroke.width);
+            rect.max.y = self.crisp_coordinate(rect.max.y, stroke.width);
+            stroke
+        } else {
+            stroke
+        };
+
         if rect.width() < self.feathering {
             // Very thin - approximate by a vertical line-segment:
             let line = [rect.center_top(), rect.center_bottom()];
@@ -1710,6 +1874,9 @@ pub fn tessellate_shapes(
         p.clip_rect.is_positive()
             && match &p.primitive {
                 Primitive::Mesh(mesh) => !mesh.is_empty(),
+                Primitive::Instanced(instanced) => {
+                    !instanced.mesh.is_empty() && !instanced.transforms.is_empty()
+                }
                 Primitive::Callback(_) => true,
             }
     });
@@ -1723,6 +1890,15 @@ pub fn tessellate_shapes(
     clipped_primitives
 }
 
+/// Warn once, so we don't spam the log every frame.
+#[cfg(feature = "log")]
+fn warn_callback_crosses_clip_shape() {
+    static WARNED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+    if !WARNED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+        log::warn!("A paint callback crosses the edge of its clip shape, so it is not painted. Callbacks can only be clipped to rectangles.");
+    }
+}
+
 fn add_clip_rects(
     tessellator: &mut Tessellator,
     clipped_primitives: Vec<ClippedPrimitive>,
@@ -1768,10 +1944,7 @@ fn test_tessellator() {
     shapes.push(Shape::mesh(mesh));
 
     let shape = Shape::Vec(shapes);
-    let clipped_shapes = vec![ClippedShape {
-        clip_rect: rect,
-        shape,
-    }];
+    let clipped_shapes = vec![ClippedShape::new(rect, shape)];
 
     let font_tex_size = [1024, 1024]; // unused
     let prepared_discs = vec![]; // unused
@@ -1785,3 +1958,123 @@ fn test_tessellator() {
     );
     assert_eq!(primitives.len(), 2);
 }
+
+#[test]
+fn test_crisp_lines() {
+    let pixels_per_point = 1.25;
+    let options = TessellationOptions {
+        feathering: false,
+        crisp_lines: true,
+        ..Default::default()
+    };
+    let mut tessellator = Tessellator::new(pixels_per_point, options, [1, 1], vec![]);
+
+    let mut mesh = Mesh::default();
+    tessellator.tessellate_line(
+        [pos2(0.0, 10.3), pos2(100.0, 10.3)],
+        Stroke::new(1.0, Color32::WHITE),
+        &mut mesh,
+    );
+
+    // A one pixel wide line should cover exactly one row of physical pixels:
+    for vertex in &mesh.vertices {
+        let y_in_pixels = vertex.pos.y * pixels_per_point;
+        assert!((y_in_pixels - y_in_pixels.round()).abs() < 1e-4);
+    }
+    let bounds = mesh.calc_bounds();
+    assert!((bounds.height() * pixels_per_point - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn test_tessellation_options_override() {
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+    let shape = Shape::rect_filled(rect, 0.0, Color32::WHITE);
+    let without_feathering = TessellationOptions {
+        feathering: false,
+        ..Default::default()
+    };
+
+    let clipped_shapes = vec![
+        ClippedShape::new(Rect::EVERYTHING, shape.clone()),
+        ClippedShape {
+            tessellation_options: Some(without_feathering),
+            ..ClippedShape::new(Rect::EVERYTHING, shape.clone())
+        },
+        ClippedShape::new(Rect::EVERYTHING, shape),
+    ];
+    let mut tessellator = Tessellator::new(1.0, Default::default(), [1, 1], vec![]);
+    let mut primitives = vec![];
+    let mut num_vertices = vec![];
+    for clipped_shape in clipped_shapes {
+        tessellator.tessellate_clipped_shape(clipped_shape, &mut primitives);
+        let Primitive::Mesh(mesh) = &primitives[0].primitive else {
+            panic!("Expected a mesh");
+        };
+        num_vertices.push(mesh.vertices.len());
+    }
+
+    // A feathered rectangle has an extra ring of vertices:
+    assert_eq!(num_vertices, vec![8, 12, 20]);
+    assert_eq!(tessellator.options(), &TessellationOptions::default());
+}
+
+#[test]
+fn test_instanced() {
+    use std::sync::Arc;
+
+    let mut mesh = Mesh::default();
+    mesh.add_colored_rect(
+        Rect::from_center_size(Pos2::ZERO, vec2(2.0, 2.0)),
+        Color32::WHITE,
+    );
+    let mesh = Arc::new(mesh);
+    let transforms = vec![
+        InstanceTransform::from_translation(vec2(10.0, 10.0)),
+        InstanceTransform::from_scale_translation(2.0, vec2(20.0, 10.0)),
+        InstanceTransform::from_translation(vec2(1000.0, 10.0)), // culled
+    ];
+    let shape = Shape::instanced(mesh.clone(), transforms);
+    assert_eq!(
+        shape.visual_bounding_rect(),
+        Rect::from_min_max(pos2(9.0, 8.0), pos2(1001.0, 12.0))
+    );
+
+    let clip_rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+    let primitives = tessellate_shapes(
+        1.0,
+        Default::default(),
+        [1, 1],
+        vec![],
+        vec![ClippedShape::new(clip_rect, shape.clone())],
+    );
+    assert_eq!(primitives.len(), 1);
+    let Primitive::Instanced(instanced) = &primitives[0].primitive else {
+        panic!("Expected instanced mesh");
+    };
+    assert!(
+        Arc::ptr_eq(&instanced.mesh, &mesh),
+        "The mesh should not be copied"
+    );
+    assert_eq!(instanced.transforms.len(), 2);
+
+    let expanded = instanced.to_mesh();
+    assert_eq!(expanded.vertices.len(), 2 * mesh.vertices.len());
+    assert_eq!(expanded.indices.len(), 2 * mesh.indices.len());
+    assert_eq!(
+        expanded.calc_bounds(),
+        Rect::from_min_max(pos2(9.0, 8.0), pos2(22.0, 12.0))
+    );
+
+    // A clip shape needs the actual triangles, so then the instances are expanded on the CPU:
+    let primitives = tessellate_shapes(
+        1.0,
+        Default::default(),
+        [1, 1],
+        vec![],
+        vec![ClippedShape {
+            clip_shape: Some(ClipShape::rect(clip_rect, 4.0)),
+            ..ClippedShape::new(clip_rect, shape)
+        }],
+    );
+    assert!(matches!(primitives[0].primitive, Primitive::Mesh(_)));
+}
@@ -0,0 +1,306 @@
+//! Color glyphs, e.g. emojis.
+//!
+//! Supported formats:
+//! * `COLR` (version 0) and `CPAL`: layers of outline glyphs, each with a color from a palette.
+//!   Used by e.g. Twemoji and Segoe UI Emoji.
+//! * `sbix` and `CBDT`: PNG images, with the `bitmap_emoji` feature.
+//!   Used by e.g. Apple Color Emoji and Noto Color Emoji.
+
+use ecolor::Color32;
+use emath::{vec2, Vec2};
+
+/// A rasterized color glyph.
+pub struct ColorBitmap {
+    /// Width, height.
+    pub size: (usize, usize),
+
+    /// Premultiplied colors, row by row.
+    pub pixels: Vec<Color32>,
+
+    /// Where the top left corner of the bitmap is, relative to the origin of the glyph (on the baseline).
+    ///
+    /// Unit: pixels.
+    pub offset: Vec2,
+}
+
+// ----------------------------------------------------------------------------
+
+/// The `COLR` and `CPAL` tables of a font.
+pub struct ColorLayers {
+    /// Glyph id, index of the first layer, number of layers.
+    ///
+    /// Sorted by glyph id.
+    base_glyphs: Vec<(u16, u16, u16)>,
+
+    /// Glyph id and palette index of each layer.
+    layers: Vec<(u16, u16)>,
+
+    /// The colors of the first palette.
+    palette: Vec<Color32>,
+}
+
+impl ColorLayers {
+    /// Returns `None` if the font has no (version 0) color layers.
+    pub fn parse(font: &[u8], index: u32) -> Option<Self> {
+        let colr = find_table(font, index, b"COLR")?;
+        let cpal = find_table(font, index, b"CPAL")?;
+
+        let num_base_glyphs = read_u16(colr, 2)? as usize;
+        let base_glyphs_offset = read_u32(colr, 4)? as usize;
+        let layers_offset = read_u32(colr, 8)? as usize;
+        let num_layers = read_u16(colr, 12)? as usize;
+
+        let base_glyphs = (0..num_base_glyphs)
+            .map(|i| {
+                let offset = base_glyphs_offset + 6 * i;
+                Some((
+                    read_u16(colr, offset)?,
+                    read_u16(colr, offset + 2)?,
+                    read_u16(colr, offset + 4)?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let layers = (0..num_layers)
+            .map(|i| {
+                let offset = layers_offset + 4 * i;
+                Some((read_u16(colr, offset)?, read_u16(colr, offset + 2)?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let num_palette_entries = read_u16(cpal, 2)? as usize;
+        let color_records_offset = read_u32(cpal, 8)? as usize;
+        let first_color_index = read_u16(cpal, 12)? as usize;
+        let palette = (0..num_palette_entries)
+            .map(|i| {
+                let offset = color_records_offset + 4 * (first_color_index + i);
+                let [b, g, r, a]: [u8; 4] = cpal.get(offset..offset + 4)?.try_into().ok()?;
+                Some(Color32::from_rgba_unmultiplied(r, g, b, a))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        (!base_glyphs.is_empty()).then_some(Self {
+            base_glyphs,
+            layers,
+            palette,
+        })
+    }
+
+    /// Rasterize the layers of this glyph on top of each other.
+    ///
+    /// Returns `None` if the glyph has no layers.
+    pub fn rasterize(
+        &self,
+        font: &ab_glyph::FontArc,
+        glyph_id: ab_glyph::GlyphId,
+        scale_in_pixels: f32,
+    ) -> Option<ColorBitmap> {
+        use ab_glyph::Font as _;
+
+        let index = self
+            .base_glyphs
+            .binary_search_by_key(&glyph_id.0, |&(id, _, _)| id)
+            .ok()?;
+        let (_, first_layer, num_layers) = self.base_glyphs[index];
+        let first_layer = first_layer as usize;
+        let layers = self
+            .layers
+            .get(first_layer..first_layer + num_layers as usize)?;
+
+        let outlines: Vec<_> = layers
+            .iter()
+            .filter_map(|&(layer_glyph_id, palette_index)| {
+                let glyph = ab_glyph::GlyphId(layer_glyph_id)
+                    .with_scale_and_position(scale_in_pixels, ab_glyph::Point { x: 0.0, y: 0.0 });
+                // 0xFFFF means the text color, but the bitmap can't depend on it:
+                let color = self
+                    .palette
+                    .get(palette_index as usize)
+                    .copied()
+                    .unwrap_or(Color32::BLACK);
+                Some((font.outline_glyph(glyph)?, color))
+            })
+            .collect();
+
+        let (first, _) = outlines.first()?;
+        let mut bounds = first.px_bounds();
+        for (outline, _) in &outlines {
+            let bb = outline.px_bounds();
+            bounds.min.x = bounds.min.x.min(bb.min.x);
+            bounds.min.y = bounds.min.y.min(bb.min.y);
+            bounds.max.x = bounds.max.x.max(bb.max.x);
+            bounds.max.y = bounds.max.y.max(bb.max.y);
+        }
+
+        let size = (bounds.width() as usize, bounds.height() as usize);
+        let mut pixels = vec![Color32::TRANSPARENT; size.0 * size.1];
+        for (outline, color) in &outlines {
+            let bb = outline.px_bounds();
+            let left = (bb.min.x - bounds.min.x) as usize;
+            let top = (bb.min.y - bounds.min.y) as usize;
+            outline.draw(|x, y, coverage| {
+                let (x, y) = (left + x as usize, top + y as usize);
+                if 0.0 < coverage && x < size.0 && y < size.1 {
+                    let pixel = &mut pixels[y * size.0 + x];
+                    *pixel = blend_over(*pixel, color.gamma_multiply(coverage.min(1.0)));
+                }
+            });
+        }
+
+        Some(ColorBitmap {
+            size,
+            pixels,
+            offset: vec2(bounds.min.x, bounds.min.y),
+        })
+    }
+}
+
+/// Draw `src` on top of `dst`, both premultiplied.
+fn blend_over(dst: Color32, src: Color32) -> Color32 {
+    let inv_alpha = 255 - src.a() as u32;
+    let blend = |s: u8, d: u8| s.saturating_add(((d as u32 * inv_alpha + 127) / 255) as u8);
+    Color32::from_rgba_premultiplied(
+        blend(src.r(), dst.r()),
+        blend(src.g(), dst.g()),
+        blend(src.b(), dst.b()),
+        blend(src.a(), dst.a()),
+    )
+}
+
+// ----------------------------------------------------------------------------
+
+/// Decode the PNG image of this glyph (from the `sbix` or `CBDT` table) and scale it to size.
+///
+/// Returns `None` if the glyph has no such image.
+#[cfg(feature = "bitmap_emoji")]
+pub fn rasterize_image(
+    font: &ab_glyph::FontArc,
+    glyph_id: ab_glyph::GlyphId,
+    em_in_pixels: f32,
+) -> Option<ColorBitmap> {
+    use ab_glyph::Font as _;
+
+    let image = font.glyph_raster_image(glyph_id, em_in_pixels.ceil() as u16)?;
+    if !matches!(image.format, ab_glyph::GlyphImageFormat::Png) || image.scale <= 0.0 {
+        return None;
+    }
+    let ([width, height], source) = decode_png(image.data)?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    // The image is in units of its own strike, with `origin` at its bottom left corner (y up):
+    let scale = em_in_pixels / image.scale;
+    let size = (
+        ((width as f32 * scale).round() as usize).max(1),
+        ((height as f32 * scale).round() as usize).max(1),
+    );
+    let offset = scale * vec2(image.origin.x, -image.origin.y - height as f32);
+
+    // Average the source texels covered by each target texel:
+    let source_range = |target: usize, target_len: usize, source_len: usize| {
+        let start = target * source_len / target_len;
+        let end = ((target + 1) * source_len / target_len).max(start + 1);
+        start..end.min(source_len)
+    };
+    let mut pixels = Vec::with_capacity(size.0 * size.1);
+    for y in 0..size.1 {
+        let rows = source_range(y, size.1, height);
+        for x in 0..size.0 {
+            let columns = source_range(x, size.0, width);
+            let mut sum = [0_u32; 4];
+            let mut count = 0;
+            for sy in rows.clone() {
+                for sx in columns.clone() {
+                    for (sum, channel) in sum.iter_mut().zip(source[sy * width + sx].to_array()) {
+                        *sum += channel as u32;
+                    }
+                    count += 1;
+                }
+            }
+            let [r, g, b, a] = sum.map(|sum| ((sum + count / 2) / count) as u8);
+            pixels.push(Color32::from_rgba_premultiplied(r, g, b, a));
+        }
+    }
+
+    Some(ColorBitmap {
+        size,
+        pixels,
+        offset,
+    })
+}
+
+/// Returns the size and the premultiplied colors of the image.
+#[cfg(feature = "bitmap_emoji")]
+fn decode_png(data: &[u8]) -> Option<([usize; 2], Vec<Color32>)> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    let bytes = buffer.get(..info.buffer_size())?;
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => bytes
+            .chunks_exact(4)
+            .map(|p| Color32::from_rgba_unmultiplied(p[0], p[1], p[2], p[3]))
+            .collect(),
+        png::ColorType::Rgb => bytes
+            .chunks_exact(3)
+            .map(|p| Color32::from_rgb(p[0], p[1], p[2]))
+            .collect(),
+        png::ColorType::GrayscaleAlpha => bytes
+            .chunks_exact(2)
+            .map(|p| Color32::from_rgba_unmultiplied(p[0], p[0], p[0], p[1]))
+            .collect(),
+        png::ColorType::Grayscale => bytes.iter().map(|&g| Color32::from_gray(g)).collect(),
+        png::ColorType::Indexed => return None, // `EXPAND` turns these into RGB(A)
+    };
+
+    Some(([info.width as usize, info.height as usize], pixels))
+}
+
+// ----------------------------------------------------------------------------
+
+/// Find a table in a `.ttf`/`.otf` file, or in a font collection (`.ttc`).
+fn find_table<'a>(font: &'a [u8], index: u32, tag: &[u8; 4]) -> Option<&'a [u8]> {
+    let face = if font.get(0..4)? == b"ttcf" {
+        read_u32(font, 12 + 4 * index as usize)? as usize
+    } else {
+        0
+    };
+
+    let num_tables = read_u16(font, face + 4)? as usize;
+    (0..num_tables).find_map(|i| {
+        let record = face + 12 + 16 * i;
+        if font.get(record..record + 4)? != tag {
+            return None;
+        }
+        let offset = read_u32(font, record + 8)? as usize;
+        let length = read_u32(font, record + 12)? as usize;
+        font.get(offset..offset.checked_add(length)?)
+    })
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+#[test]
+fn test_blend_over() {
+    let red = Color32::from_rgb(255, 0, 0);
+    let half_blue = Color32::from_rgba_premultiplied(0, 0, 128, 128);
+    assert_eq!(blend_over(Color32::TRANSPARENT, red), red);
+    assert_eq!(blend_over(red, Color32::TRANSPARENT), red);
+    assert_eq!(
+        blend_over(red, half_blue),
+        Color32::from_rgba_premultiplied(127, 0, 128, 255)
+    );
+}
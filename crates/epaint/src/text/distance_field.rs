@@ -0,0 +1,145 @@
+//! Signed distance fields of glyphs, see [`crate::FontImage::distance_field`].
+//!
+//! We compute the distances from the anti-aliased coverage of the glyph,
+//! using the same approach as [TinySDF](https://github.com/mapbox/tiny-sdf).
+
+/// How far outside (and inside) of the edge of a glyph the distance field reaches, in texels.
+///
+/// This is also the padding around each glyph in the atlas.
+pub const SPREAD: usize = 4;
+
+/// Glyphs are rasterized with at least this many texels per em,
+/// so that they stay crisp when scaled up.
+pub const MIN_TEXELS_PER_EM: f32 = 32.0;
+
+/// Large enough to be "infinitely" far away, small enough to not overflow when squared distances are added.
+const FAR: f32 = 1e20;
+
+/// Turn the coverage of a glyph (0-1, row by row) into a signed distance field.
+///
+/// In the result 0.5 is the edge, and 1.0 (0.0) is [`SPREAD`] texels inside (outside) of it.
+/// Leave [`SPREAD`] texels of padding around the glyph for the distances to fall off.
+pub fn from_coverage((w, h): (usize, usize), coverage: &[f32]) -> Vec<f32> {
+    assert_eq!(w * h, coverage.len());
+
+    // Squared distances to the closest texel outside/inside of the glyph.
+    // Partially covered texels are approximated as being this far from the edge:
+    let mut outside = Vec::with_capacity(coverage.len());
+    let mut inside = Vec::with_capacity(coverage.len());
+    for &coverage in coverage {
+        if 1.0 <= coverage {
+            outside.push(0.0);
+            inside.push(FAR);
+        } else if coverage <= 0.0 {
+            outside.push(FAR);
+            inside.push(0.0);
+        } else {
+            outside.push((0.5 - coverage).max(0.0).powi(2));
+            inside.push((coverage - 0.5).max(0.0).powi(2));
+        }
+    }
+
+    distance_transform(&mut outside, (w, h));
+    distance_transform(&mut inside, (w, h));
+
+    outside
+        .iter()
+        .zip(&inside)
+        .map(|(outside, inside)| {
+            let distance = outside.sqrt() - inside.sqrt();
+            (0.5 - distance / (2.0 * SPREAD as f32)).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+/// Replace each squared distance with the smallest sum of it and the squared distance to any other texel.
+///
+/// Felzenszwalb & Huttenlocher: "Distance Transforms of Sampled Functions".
+fn distance_transform(grid: &mut [f32], (w, h): (usize, usize)) {
+    let n = w.max(h);
+    let mut f = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    let mut v = vec![0; n];
+    let mut z = vec![0.0; n + 1];
+
+    for x in 0..w {
+        for y in 0..h {
+            f[y] = grid[y * w + x];
+        }
+        distance_transform_1d(&f[..h], &mut d[..h], &mut v, &mut z);
+        for y in 0..h {
+            grid[y * w + x] = d[y];
+        }
+    }
+
+    for y in 0..h {
+        let row = &mut grid[y * w..(y + 1) * w];
+        distance_transform_1d(row, &mut d[..w], &mut v, &mut z);
+        row.copy_from_slice(&d[..w]);
+    }
+}
+
+/// `v` and `z` are scratch space, at least as long as `f` (plus one).
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    if f.is_empty() {
+        return;
+    }
+
+    // Where the parabola from `q` intersects the one from `r`:
+    let intersection =
+        |q: usize, r: usize| (f[q] - f[r] + (q * q) as f32 - (r * r) as f32) / (2 * (q - r)) as f32;
+
+    // Find the lower envelope of the parabolas:
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+    for q in 1..f.len() {
+        let mut s = intersection(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f32::INFINITY;
+    }
+
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let r = v[k];
+        *d = (q.abs_diff(r) as f32).powi(2) + f[r];
+    }
+}
+
+#[test]
+fn test_distance_field() {
+    // A 4x4 square in the middle of a 12x12 image:
+    let size = (12, 12);
+    let coverage: Vec<f32> = (0..12 * 12)
+        .map(|i| {
+            let (x, y) = (i % 12, i / 12);
+            if (4..8).contains(&x) && (4..8).contains(&y) {
+                1.0
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let field = from_coverage(size, &coverage);
+    let row = &field[6 * 12..7 * 12];
+
+    assert_eq!(row[0], 0.0, "Far outside");
+    assert!(row[3] < 0.5, "Just outside");
+    assert!(0.5 < row[4], "Just inside");
+    assert!(row[4] < row[5], "Deeper inside");
+    assert_eq!(row[3] + row[4], 1.0, "Symmetric around the edge");
+    assert!(
+        row.iter().eq(row.iter().rev()),
+        "Symmetric around the center"
+    );
+}
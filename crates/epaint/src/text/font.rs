@@ -1,6 +1,6 @@
 use crate::{
     mutex::{Mutex, RwLock},
-    text::FontTweak,
+    text::{color_glyph::ColorLayers, distance_field, FontTweak},
     TextureAtlas,
 };
 use emath::{vec2, Vec2};
@@ -24,6 +24,9 @@ pub struct UvRect {
 
     /// Bottom right corner (exclusive).
     pub max: [u16; 2],
+
+    /// The texels have their own colors (e.g. an emoji), so the glyph should not be tinted with the text color.
+    pub is_color: bool,
 }
 
 impl UvRect {
@@ -66,6 +69,9 @@ pub struct FontImpl {
     name: String,
     ab_glyph_font: ab_glyph::FontArc,
 
+    /// The `COLR` color glyphs of the font, if any.
+    color_layers: Option<Arc<ColorLayers>>,
+
     /// Maximum character height
     scale_in_pixels: u32,
 
@@ -86,6 +92,7 @@ impl FontImpl {
         pixels_per_point: f32,
         name: String,
         ab_glyph_font: ab_glyph::FontArc,
+        color_layers: Option<Arc<ColorLayers>>,
         scale_in_pixels: f32,
         tweak: FontTweak,
     ) -> Self {
@@ -125,6 +132,7 @@ impl FontImpl {
         Self {
             name,
             ab_glyph_font,
+            color_layers,
             scale_in_pixels,
             height_in_points: ascent - descent + line_gap,
             y_offset_in_points,
@@ -263,6 +271,11 @@ impl FontImpl {
         self.ascent
     }
 
+    /// Number of glyphs looked up so far, most of which are rasterized into the atlas.
+    pub(crate) fn num_cached_glyphs(&self) -> usize {
+        self.glyph_info_cache.read().len()
+    }
+
     fn allocate_glyph(&self, glyph_id: ab_glyph::GlyphId) -> GlyphInfo {
         assert!(glyph_id.0 != 0);
         use ab_glyph::{Font as _, ScaleFont};
@@ -272,40 +285,14 @@ impl FontImpl {
             ab_glyph::Point { x: 0.0, y: 0.0 },
         );
 
-        let uv_rect = self.ab_glyph_font.outline_glyph(glyph).map(|glyph| {
-            let bb = glyph.px_bounds();
-            let glyph_width = bb.width() as usize;
-            let glyph_height = bb.height() as usize;
-            if glyph_width == 0 || glyph_height == 0 {
-                UvRect::default()
-            } else {
-                let glyph_pos = {
-                    let atlas = &mut self.atlas.lock();
-                    let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
-                    glyph.draw(|x, y, v| {
-                        if 0.0 < v {
-                            let px = glyph_pos.0 + x as usize;
-                            let py = glyph_pos.1 + y as usize;
-                            image[(px, py)] = v;
-                        }
-                    });
-                    glyph_pos
-                };
-
-                let offset_in_pixels = vec2(bb.min.x, bb.min.y);
-                let offset =
-                    offset_in_pixels / self.pixels_per_point + self.y_offset_in_points * Vec2::Y;
-                UvRect {
-                    offset,
-                    size: vec2(glyph_width as f32, glyph_height as f32) / self.pixels_per_point,
-                    min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
-                    max: [
-                        (glyph_pos.0 + glyph_width) as u16,
-                        (glyph_pos.1 + glyph_height) as u16,
-                    ],
-                }
-            }
-        });
+        let distance_field = self.atlas.lock().is_distance_field();
+        let uv_rect = if distance_field {
+            // Color glyphs can't be stored as distance fields, so we only use the outlines:
+            self.allocate_distance_field_glyph(glyph_id)
+        } else {
+            self.allocate_color_glyph(glyph_id)
+                .or_else(|| self.allocate_coverage_glyph(glyph))
+        };
         let uv_rect = uv_rect.unwrap_or_default();
 
         let advance_width_in_points = self
@@ -320,6 +307,146 @@ impl FontImpl {
             uv_rect,
         }
     }
+
+    /// Rasterize the outline of the glyph.
+    fn allocate_coverage_glyph(&self, glyph: ab_glyph::Glyph) -> Option<UvRect> {
+        use ab_glyph::Font as _;
+
+        let glyph = self.ab_glyph_font.outline_glyph(glyph)?;
+        let bb = glyph.px_bounds();
+        let glyph_width = bb.width() as usize;
+        let glyph_height = bb.height() as usize;
+        if glyph_width == 0 || glyph_height == 0 {
+            return Some(UvRect::default());
+        }
+
+        let glyph_pos = {
+            let atlas = &mut self.atlas.lock();
+            let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+            glyph.draw(|x, y, v| {
+                if 0.0 < v {
+                    let px = glyph_pos.0 + x as usize;
+                    let py = glyph_pos.1 + y as usize;
+                    image[(px, py)] = v;
+                }
+            });
+            glyph_pos
+        };
+
+        let offset_in_pixels = vec2(bb.min.x, bb.min.y);
+        let offset = offset_in_pixels / self.pixels_per_point + self.y_offset_in_points * Vec2::Y;
+        Some(UvRect {
+            offset,
+            size: vec2(glyph_width as f32, glyph_height as f32) / self.pixels_per_point,
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [
+                (glyph_pos.0 + glyph_width) as u16,
+                (glyph_pos.1 + glyph_height) as u16,
+            ],
+            is_color: false,
+        })
+    }
+
+    /// Rasterize the outline of the glyph as a signed distance field, see [`crate::FontImage::distance_field`].
+    fn allocate_distance_field_glyph(&self, glyph_id: ab_glyph::GlyphId) -> Option<UvRect> {
+        use ab_glyph::Font as _;
+
+        // Small glyphs are rasterized larger, so they stay crisp when scaled up:
+        let texels_per_pixel = (distance_field::MIN_TEXELS_PER_EM / self.scale_in_pixels as f32)
+            .ceil()
+            .max(1.0);
+        let glyph = glyph_id.with_scale_and_position(
+            self.scale_in_pixels as f32 * texels_per_pixel,
+            ab_glyph::Point { x: 0.0, y: 0.0 },
+        );
+        let glyph = self.ab_glyph_font.outline_glyph(glyph)?;
+        let bb = glyph.px_bounds();
+        if bb.width() as usize == 0 || bb.height() as usize == 0 {
+            return Some(UvRect::default());
+        }
+
+        // Leave room for the distances to fall off around the glyph:
+        let padding = distance_field::SPREAD;
+        let (width, height) = (
+            bb.width() as usize + 2 * padding,
+            bb.height() as usize + 2 * padding,
+        );
+        let mut coverage = vec![0.0; width * height];
+        glyph.draw(|x, y, v| {
+            let (x, y) = (padding + x as usize, padding + y as usize);
+            if x < width && y < height {
+                coverage[y * width + x] = v;
+            }
+        });
+        let distances = distance_field::from_coverage((width, height), &coverage);
+
+        let glyph_pos = {
+            let atlas = &mut self.atlas.lock();
+            let (glyph_pos, image) = atlas.allocate((width, height));
+            for (i, &distance) in distances.iter().enumerate() {
+                image[(glyph_pos.0 + i % width, glyph_pos.1 + i / width)] = distance;
+            }
+            glyph_pos
+        };
+
+        let offset_in_pixels =
+            (vec2(bb.min.x, bb.min.y) - Vec2::splat(padding as f32)) / texels_per_pixel;
+        let offset = offset_in_pixels / self.pixels_per_point + self.y_offset_in_points * Vec2::Y;
+        Some(UvRect {
+            offset,
+            size: vec2(width as f32, height as f32) / (texels_per_pixel * self.pixels_per_point),
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [(glyph_pos.0 + width) as u16, (glyph_pos.1 + height) as u16],
+            is_color: false,
+        })
+    }
+
+    /// Rasterize the glyph in color, if the font has color data for it.
+    fn allocate_color_glyph(&self, glyph_id: ab_glyph::GlyphId) -> Option<UvRect> {
+        let scale_in_pixels = self.scale_in_pixels as f32;
+        let bitmap = self.color_layers.as_ref().and_then(|color_layers| {
+            color_layers.rasterize(&self.ab_glyph_font, glyph_id, scale_in_pixels)
+        });
+
+        #[cfg(feature = "bitmap_emoji")]
+        let bitmap = bitmap.or_else(|| {
+            use ab_glyph::{Font as _, ScaleFont as _};
+            let units_per_em = self.ab_glyph_font.units_per_em()?;
+            let em_in_pixels = units_per_em
+                * self
+                    .ab_glyph_font
+                    .as_scaled(scale_in_pixels)
+                    .h_scale_factor();
+            super::color_glyph::rasterize_image(&self.ab_glyph_font, glyph_id, em_in_pixels)
+        });
+
+        let bitmap = bitmap?;
+        let (width, height) = bitmap.size;
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let glyph_pos = {
+            let atlas = &mut self.atlas.lock();
+            let (glyph_pos, image) = atlas.allocate((width, height));
+            for y in 0..height {
+                for x in 0..width {
+                    let pos = (glyph_pos.0 + x, glyph_pos.1 + y);
+                    image[pos] = 0.0;
+                    image.set_color(pos, bitmap.pixels[y * width + x]);
+                }
+            }
+            glyph_pos
+        };
+
+        Some(UvRect {
+            offset: bitmap.offset / self.pixels_per_point + self.y_offset_in_points * Vec2::Y,
+            size: vec2(width as f32, height as f32) / self.pixels_per_point,
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [(glyph_pos.0 + width) as u16, (glyph_pos.1 + height) as u16],
+            is_color: true,
+        })
+    }
 }
 
 type FontIndex = usize;
@@ -3,10 +3,11 @@ use std::{collections::BTreeMap, sync::Arc};
 use crate::{
     mutex::{Mutex, MutexGuard},
     text::{
+        color_glyph::ColorLayers,
         font::{Font, FontImpl},
-        Galley, LayoutJob,
+        Galley, LayoutJob, LayoutSection, TextFormat, TextMetrics,
     },
-    TextureAtlas,
+    TextureAtlas, TextureAtlasStats,
 };
 use emath::NumExt as _;
 
@@ -229,6 +230,9 @@ fn ab_glyph_font_from_font_data(name: &str, data: &FontData) -> ab_glyph::FontAr
 ///
 /// egui_ctx.set_fonts(fonts);
 /// ```
+///
+/// Color emoji fonts are shown in color: those using `COLR` layers always,
+/// and those using PNG images (`sbix`, `CBDT`) with the `bitmap_emoji` feature.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
@@ -382,6 +386,7 @@ impl Fonts {
         let fonts_and_cache = FontsAndCache {
             fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
             galley_cache: Default::default(),
+            num_evictions: 0,
         };
         Self(Arc::new(Mutex::new(fonts_and_cache)))
     }
@@ -392,28 +397,64 @@ impl Fonts {
     /// Call after painting the previous frame, but before using [`Fonts`] for the new frame.
     ///
     /// This function will react to changes in `pixels_per_point` and `max_texture_side`,
-    /// as well as notice when the font atlas is getting full, and handle that.
+    /// as well as notice when the font atlas is getting full, and handle that
+    /// by evicting the glyphs that haven't been used recently (e.g. of old font sizes).
     pub fn begin_frame(&self, pixels_per_point: f32, max_texture_side: usize) {
         let mut fonts_and_cache = self.0.lock();
 
         let pixels_per_point_changed = fonts_and_cache.fonts.pixels_per_point != pixels_per_point;
         let max_texture_side_changed = fonts_and_cache.fonts.max_texture_side != max_texture_side;
         let font_atlas_almost_full = fonts_and_cache.fonts.atlas.lock().fill_ratio() > 0.8;
-        let needs_recreate =
-            pixels_per_point_changed || max_texture_side_changed || font_atlas_almost_full;
 
-        if needs_recreate {
+        if pixels_per_point_changed || max_texture_side_changed {
             let definitions = fonts_and_cache.fonts.definitions.clone();
+            let distance_field = fonts_and_cache.fonts.is_distance_field();
 
             *fonts_and_cache = FontsAndCache {
-                fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
+                fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions)
+                    .with_distance_field(distance_field),
                 galley_cache: Default::default(),
+                num_evictions: fonts_and_cache.num_evictions,
             };
+        } else if font_atlas_almost_full {
+            fonts_and_cache.evict_unused_glyphs();
         }
 
         fonts_and_cache.galley_cache.flush_cache();
     }
 
+    /// Render text using signed distance fields, which stay crisp when the text is scaled up
+    /// (e.g. by zooming), without having to rasterize the glyphs again for every scale.
+    ///
+    /// The backend must support it, see [`crate::FontImage::distance_field`].
+    /// Color glyphs (emojis) are painted using only their outlines in this mode.
+    ///
+    /// Changing this throws away the font atlas and all cached galleys.
+    pub fn set_distance_field(&self, distance_field: bool) {
+        let mut fonts_and_cache = self.0.lock();
+        if fonts_and_cache.fonts.is_distance_field() != distance_field {
+            let FontsImpl {
+                pixels_per_point,
+                max_texture_side,
+                definitions,
+                ..
+            } = &fonts_and_cache.fonts;
+
+            *fonts_and_cache = FontsAndCache {
+                fonts: FontsImpl::new(*pixels_per_point, *max_texture_side, definitions.clone())
+                    .with_distance_field(distance_field),
+                galley_cache: Default::default(),
+                num_evictions: fonts_and_cache.num_evictions,
+            };
+        }
+    }
+
+    /// See [`Self::set_distance_field`].
+    #[inline]
+    pub fn is_distance_field(&self) -> bool {
+        self.lock().fonts.is_distance_field()
+    }
+
     /// Call at the end of each frame (before painting) to get the change to the font texture since last call.
     pub fn font_image_delta(&self) -> Option<crate::ImageDelta> {
         self.lock().fonts.atlas.lock().take_delta()
@@ -500,6 +541,24 @@ impl Fonts {
         self.lock().layout_job(job)
     }
 
+    /// Like [`Self::layout_job`], but the [`LayoutJob`] is only created if there
+    /// is no galley cached for `key`.
+    ///
+    /// `key` must uniquely identify the job, e.g. by including the text, its
+    /// [`crate::text::TextFormat`] and the wrap width.
+    /// This lets a widget that shows the same text every frame skip building (and allocating)
+    /// a new [`LayoutJob`] each frame.
+    ///
+    /// Like all cached galleys, the galley is evicted if `key` is not used for a frame.
+    #[inline]
+    pub fn layout_interned(
+        &self,
+        key: impl std::hash::Hash,
+        job: impl FnOnce() -> LayoutJob,
+    ) -> Arc<Galley> {
+        self.lock().layout_interned(key, job)
+    }
+
     pub fn num_galleys_in_cache(&self) -> usize {
         self.lock().galley_cache.num_galleys_in_cache()
     }
@@ -512,6 +571,24 @@ impl Fonts {
         self.lock().fonts.atlas.lock().fill_ratio()
     }
 
+    /// How large and full the font atlas is, and how much is cached in it.
+    pub fn font_atlas_stats(&self) -> FontAtlasStats {
+        let fonts_and_cache = self.lock();
+        let fonts = &fonts_and_cache.fonts;
+        let atlas = fonts.atlas.lock().stats();
+        FontAtlasStats {
+            atlas,
+            num_font_sizes: fonts.font_impl_cache.cache.len(),
+            num_glyphs: fonts
+                .font_impl_cache
+                .cache
+                .values()
+                .map(|font_impl| font_impl.num_cached_glyphs())
+                .sum(),
+            num_evictions: fonts_and_cache.num_evictions,
+        }
+    }
+
     /// Will wrap text at the given width and line break at `\n`.
     ///
     /// The implementation uses memoization so repeated calls are cheap.
@@ -539,6 +616,18 @@ impl Fonts {
         self.layout_job(job)
     }
 
+    /// Measure the size of some text, without creating a [`Galley`].
+    ///
+    /// Will wrap text at the given width and line break at `\n`.
+    ///
+    /// The result is the same as the size of the [`Galley`] you would get from [`Self::layout_job`],
+    /// but it is not memoized, so prefer [`Self::layout_job`] if you are going to paint the text anyway.
+    pub fn measure(&self, text: &str, format: TextFormat, wrap_width: f32) -> TextMetrics {
+        let mut job = LayoutJob::single_section(text.to_owned(), format);
+        job.wrap.max_width = wrap_width;
+        super::measure(&mut self.lock().fonts, &job)
+    }
+
     /// Like [`Self::layout`], made for when you want to pick a color for the text later.
     ///
     /// The implementation uses memoization so repeated calls are cheap.
@@ -554,15 +643,89 @@ impl Fonts {
 
 // ----------------------------------------------------------------------------
 
+/// Statistics about the font atlas, see [`Fonts::font_atlas_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FontAtlasStats {
+    /// The texture atlas the glyphs are rasterized into.
+    pub atlas: TextureAtlasStats,
+
+    /// Number of fonts (including fallback fonts) rasterized at a specific size.
+    pub num_font_sizes: usize,
+
+    /// Number of glyphs rasterized into the atlas, over all font sizes.
+    pub num_glyphs: usize,
+
+    /// How many times the atlas was almost full,
+    /// so that it was recreated with only the glyphs that were in use.
+    pub num_evictions: usize,
+}
+
 pub struct FontsAndCache {
     pub fonts: FontsImpl,
     galley_cache: GalleyCache,
+
+    /// See [`FontAtlasStats::num_evictions`].
+    num_evictions: usize,
 }
 
 impl FontsAndCache {
     fn layout_job(&mut self, job: LayoutJob) -> Arc<Galley> {
         self.galley_cache.layout(&mut self.fonts, job)
     }
+
+    fn layout_interned(
+        &mut self,
+        key: impl std::hash::Hash,
+        job: impl FnOnce() -> LayoutJob,
+    ) -> Arc<Galley> {
+        let hash = crate::util::hash(key);
+        self.galley_cache
+            .layout_with_hash(&mut self.fonts, hash, job)
+    }
+
+    /// Recreate the font atlas with only the glyphs used by the recently laid out galleys.
+    ///
+    /// The atlas only ever grows, so without this, repeatedly changing font sizes at runtime
+    /// would fill it up with glyphs of sizes that are no longer used.
+    fn evict_unused_glyphs(&mut self) {
+        let mut used_chars: ahash::HashMap<(HashableF32, FontFamily), ahash::HashSet<char>> =
+            Default::default();
+        for cached in self.galley_cache.cache.values() {
+            let job = &cached.galley.job;
+            for section in &job.sections {
+                let FontId { size, family } = &section.format.font_id;
+                used_chars
+                    .entry((HashableF32(*size), family.clone()))
+                    .or_default()
+                    .extend(job.text[section.byte_range.clone()].chars());
+            }
+        }
+
+        #[cfg(feature = "log")]
+        log::debug!(
+            "epaint font atlas almost full; evicting all but {} font sizes",
+            used_chars.len()
+        );
+
+        let FontsImpl {
+            pixels_per_point,
+            max_texture_side,
+            definitions,
+            ..
+        } = &self.fonts;
+        let distance_field = self.fonts.is_distance_field();
+        self.fonts = FontsImpl::new(*pixels_per_point, *max_texture_side, definitions.clone())
+            .with_distance_field(distance_field);
+        for ((HashableF32(size), family), chars) in used_chars {
+            self.fonts
+                .font(&FontId::new(size, family))
+                .preload_characters(&chars.into_iter().collect::<String>());
+        }
+
+        // All cached galleys refer to glyphs in the old atlas:
+        self.galley_cache = Default::default();
+        self.num_evictions += 1;
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -626,6 +789,20 @@ impl FontsImpl {
         }
     }
 
+    /// Store the glyphs as signed distance fields, see [`Fonts::set_distance_field`].
+    ///
+    /// Must be called before any glyphs are used.
+    pub fn with_distance_field(self, distance_field: bool) -> Self {
+        self.atlas.lock().set_distance_field(distance_field);
+        self
+    }
+
+    /// See [`Fonts::set_distance_field`].
+    #[inline]
+    pub fn is_distance_field(&self) -> bool {
+        self.atlas.lock().is_distance_field()
+    }
+
     #[inline(always)]
     pub fn pixels_per_point(&self) -> f32 {
         self.pixels_per_point
@@ -682,6 +859,13 @@ impl FontsImpl {
 struct CachedGalley {
     /// When it was last used
     last_used: u32,
+
+    /// Hashes of the per-paragraph galleys this galley was concatenated from, if any.
+    ///
+    /// These are kept alive for as long as this galley is used,
+    /// so that an edit to the text only needs to lay out the changed paragraphs.
+    children: Option<Arc<[u64]>>,
+
     galley: Arc<Galley>,
 }
 
@@ -695,23 +879,76 @@ struct GalleyCache {
 impl GalleyCache {
     fn layout(&mut self, fonts: &mut FontsImpl, job: LayoutJob) -> Arc<Galley> {
         let hash = crate::util::hash(&job); // TODO(emilk): even faster hasher?
+        self.layout_with_hash(fonts, hash, move || job)
+    }
 
-        match self.cache.entry(hash) {
-            std::collections::hash_map::Entry::Occupied(entry) => {
-                let cached = entry.into_mut();
-                cached.last_used = self.generation;
-                cached.galley.clone()
-            }
-            std::collections::hash_map::Entry::Vacant(entry) => {
-                let galley = super::layout(fonts, job.into());
-                let galley = Arc::new(galley);
-                entry.insert(CachedGalley {
-                    last_used: self.generation,
-                    galley: galley.clone(),
-                });
-                galley
+    /// Look up the galley with the given hash, and only create and lay out the job if it is missing.
+    fn layout_with_hash(
+        &mut self,
+        fonts: &mut FontsImpl,
+        hash: u64,
+        job: impl FnOnce() -> LayoutJob,
+    ) -> Arc<Galley> {
+        if let Some(cached) = self.cache.get_mut(&hash) {
+            cached.last_used = self.generation;
+            let galley = cached.galley.clone();
+            if let Some(children) = cached.children.clone() {
+                for child_hash in &*children {
+                    if let Some(child) = self.cache.get_mut(child_hash) {
+                        child.last_used = self.generation;
+                    }
+                }
             }
+            return galley;
         }
+
+        let job = job();
+        let (galley, children) = if should_cache_each_paragraph_individually(&job) {
+            let (galley, children) = self.layout_each_paragraph_individually(fonts, job);
+            (galley, Some(children))
+        } else {
+            (super::layout(fonts, job.into()), None)
+        };
+
+        let galley = Arc::new(galley);
+        self.cache.insert(
+            hash,
+            CachedGalley {
+                last_used: self.generation,
+                children,
+                galley: galley.clone(),
+            },
+        );
+        galley
+    }
+
+    /// Lay out each paragraph (separated by `\n`) as its own cached galley, then concatenate them.
+    ///
+    /// This means that changing one paragraph of a huge text (e.g. a log or a code editor)
+    /// only requires laying out that one paragraph again.
+    fn layout_each_paragraph_individually(
+        &mut self,
+        fonts: &mut FontsImpl,
+        job: LayoutJob,
+    ) -> (Galley, Arc<[u64]>) {
+        let mut paragraphs = vec![];
+        let mut children = vec![];
+        let mut start = 0;
+        while start <= job.text.len() {
+            let end = job.text[start..]
+                .find('\n')
+                .map_or(job.text.len(), |i| start + i);
+
+            let (first_section_index, paragraph_job) = paragraph_job(&job, start, end);
+            children.push(crate::util::hash(&paragraph_job));
+            let galley = self.layout(fonts, paragraph_job);
+            paragraphs.push((first_section_index, galley));
+
+            start = end + 1;
+        }
+
+        let galley = Galley::concat(job.into(), &paragraphs, fonts.pixels_per_point());
+        (galley, children.into())
     }
 
     pub fn num_galleys_in_cache(&self) -> usize {
@@ -728,12 +965,82 @@ impl GalleyCache {
     }
 }
 
+/// Should we lay out each paragraph of this job separately?
+///
+/// This is only worth it for multi-paragraph text, and only valid if the paragraphs
+/// are independent of each other, i.e. with no row limit and well-ordered sections.
+fn should_cache_each_paragraph_individually(job: &LayoutJob) -> bool {
+    if !job.break_on_newline || job.wrap.max_rows != usize::MAX || !job.text.contains('\n') {
+        return false;
+    }
+
+    let mut expected_start = 0;
+    for section in &job.sections {
+        if section.byte_range.start != expected_start || section.byte_range.end < expected_start {
+            return false;
+        }
+        expected_start = section.byte_range.end;
+    }
+    !job.sections.is_empty() && expected_start == job.text.len()
+}
+
+/// The part of `job` for the paragraph in the byte range `start..end`, where `end` is the `\n`
+/// (or the end of the text).
+///
+/// Also returns the index of the first section of `job` that is part of the paragraph.
+fn paragraph_job(job: &LayoutJob, start: usize, end: usize) -> (usize, LayoutJob) {
+    // A section that started on an earlier paragraph (and contained the `\n` before this one)
+    // is still the section this paragraph starts with, even if it has no text left here.
+    let first_section_index = job
+        .sections
+        .iter()
+        .rposition(|section| section.byte_range.start < start && start <= section.byte_range.end)
+        .or_else(|| {
+            job.sections
+                .iter()
+                .position(|section| start <= section.byte_range.start)
+        })
+        .unwrap_or_default();
+
+    let sections = job.sections[first_section_index..]
+        .iter()
+        .take_while(|section| section.byte_range.start <= end)
+        .map(|section| LayoutSection {
+            // The leading space was already added to the paragraph the section started in:
+            leading_space: if section.byte_range.start < start {
+                0.0
+            } else {
+                section.leading_space
+            },
+            byte_range: section.byte_range.start.max(start) - start
+                ..section.byte_range.end.min(end) - start,
+            format: section.format.clone(),
+        })
+        .collect();
+
+    let paragraph_job = LayoutJob {
+        text: job.text[start..end].to_owned(),
+        sections,
+        wrap: job.wrap.clone(),
+        first_row_min_height: if start == 0 {
+            job.first_row_min_height
+        } else {
+            0.0
+        },
+        break_on_newline: job.break_on_newline,
+        halign: job.halign,
+        justify: job.justify,
+    };
+
+    (first_section_index, paragraph_job)
+}
+
 // ----------------------------------------------------------------------------
 
 struct FontImplCache {
     atlas: Arc<Mutex<TextureAtlas>>,
     pixels_per_point: f32,
-    ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc)>,
+    ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc, Option<Arc<ColorLayers>>)>,
 
     /// Map font pixel sizes and names to the cached [`FontImpl`].
     cache: ahash::HashMap<(u32, String), Arc<FontImpl>>,
@@ -750,7 +1057,9 @@ impl FontImplCache {
             .map(|(name, font_data)| {
                 let tweak = font_data.tweak;
                 let ab_glyph = ab_glyph_font_from_font_data(name, font_data);
-                (name.clone(), (tweak, ab_glyph))
+                let color_layers =
+                    ColorLayers::parse(&font_data.font, font_data.index).map(Arc::new);
+                (name.clone(), (tweak, ab_glyph, color_layers))
             })
             .collect();
 
@@ -765,7 +1074,7 @@ impl FontImplCache {
     pub fn font_impl(&mut self, scale_in_points: f32, font_name: &str) -> Arc<FontImpl> {
         use ab_glyph::Font as _;
 
-        let (tweak, ab_glyph_font) = self
+        let (tweak, ab_glyph_font, color_layers) = self
             .ab_glyph_fonts
             .get(font_name)
             .unwrap_or_else(|| panic!("No font data found for {font_name:?}"))
@@ -791,6 +1100,7 @@ impl FontImplCache {
                     self.pixels_per_point,
                     font_name.to_owned(),
                     ab_glyph_font,
+                    color_layers,
                     scale_in_pixels,
                     tweak,
                 ))
@@ -798,3 +1108,189 @@ impl FontImplCache {
             .clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evict_unused_font_sizes() {
+        let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+        let text = "The quick brown fox jumps over the lazy dog 0123456789";
+
+        // Keep changing the font size, like an animated zoom would:
+        let mut size = 8.0;
+        while fonts.font_atlas_stats().num_evictions == 0 {
+            assert!(size < 1000.0, "The font atlas never filled up");
+            fonts.begin_frame(1.0, 1024);
+            fonts.layout_no_wrap(
+                text.to_owned(),
+                FontId::proportional(size),
+                Default::default(),
+            );
+            size += 1.0;
+        }
+
+        let stats = fonts.font_atlas_stats();
+        assert!(!stats.atlas.overflowed);
+        assert!(stats.atlas.fill_ratio < 0.8, "{stats:?}");
+        assert!(
+            stats.num_glyphs > 0,
+            "Glyphs of the last frame should be kept"
+        );
+    }
+
+    #[test]
+    fn distance_field_keeps_layout() {
+        let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+        let layout =
+            || fonts.layout_no_wrap("Hello".to_owned(), FontId::default(), Default::default());
+        let coverage = layout();
+
+        fonts.set_distance_field(true);
+        assert!(fonts.is_distance_field());
+        let distance_field = layout();
+        assert_eq!(coverage.rect, distance_field.rect);
+
+        let delta = fonts.font_image_delta().unwrap();
+        let crate::ImageData::Font(image) = &delta.image else {
+            panic!("Expected a font image");
+        };
+        assert!(image.distance_field);
+
+        // The padding around the glyphs makes them larger:
+        let (coverage, distance_field) = (
+            &coverage.rows[0].glyphs[0],
+            &distance_field.rows[0].glyphs[0],
+        );
+        assert!(coverage.uv_rect.size.x < distance_field.uv_rect.size.x);
+
+        // Survives a change of scale:
+        fonts.begin_frame(2.0, 1024);
+        assert!(fonts.is_distance_field());
+    }
+
+    #[test]
+    fn measure_matches_layout() {
+        let fonts = Fonts::new(1.5, 1024, FontDefinitions::default());
+        let format = TextFormat::simple(FontId::proportional(17.0), crate::Color32::WHITE);
+
+        for (text, wrap_width) in [
+            ("", f32::INFINITY),
+            ("Hello world", f32::INFINITY),
+            ("Hello world", 30.0),
+            ("Hello\n\nwide world\n", 50.0),
+        ] {
+            let metrics = fonts.measure(text, format.clone(), wrap_width);
+            let mut job = LayoutJob::single_section(text.to_owned(), format.clone());
+            job.wrap.max_width = wrap_width;
+            let galley = fonts.layout_job(job);
+
+            // Multi-paragraph galleys are stitched together, so allow for rounding errors:
+            let size_error = (metrics.size - galley.size()).abs().max_elem();
+            assert!(size_error < 1e-3, "{text:?}");
+            assert_eq!(metrics.num_rows(), galley.rows.len(), "{text:?}");
+            for (width, row) in metrics.row_widths.iter().zip(&galley.rows) {
+                assert_eq!(*width, row.rect.width(), "{text:?}");
+            }
+            assert_eq!(
+                metrics.num_glyphs,
+                galley
+                    .rows
+                    .iter()
+                    .map(|row| row.glyphs.len())
+                    .sum::<usize>(),
+                "{text:?}"
+            );
+            if let Some(glyph) = galley.rows[0].glyphs.first() {
+                assert_eq!(metrics.ascent, glyph.pos.y, "{text:?}");
+            }
+            assert!(metrics.ascent <= metrics.size.y, "{text:?}");
+            assert!(0.0 < metrics.descent, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn layout_interned_only_creates_job_once() {
+        let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+        let job = || {
+            LayoutJob::simple_singleline(
+                "Hello".to_owned(),
+                FontId::default(),
+                crate::Color32::WHITE,
+            )
+        };
+
+        let mut num_jobs = 0;
+        for _ in 0..3 {
+            fonts.begin_frame(1.0, 1024);
+            let galley = fonts.layout_interned("hello", || {
+                num_jobs += 1;
+                job()
+            });
+            assert_eq!(galley.text(), "Hello");
+        }
+        assert_eq!(num_jobs, 1);
+    }
+
+    #[test]
+    fn paragraph_cache_matches_full_layout() {
+        use crate::{text::TextFormat, Color32};
+        use emath::Align;
+
+        let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+        let format = |size, color| TextFormat::simple(FontId::proportional(size), color);
+
+        let mut jobs = vec![];
+        for text in [
+            "Hello\nworld",
+            "\n\nfoo\n",
+            "One line",
+            "a\nb b b b b b b b b b b b\n",
+        ] {
+            for halign in [Align::LEFT, Align::Center] {
+                let mut job = LayoutJob::simple(
+                    text.to_owned(),
+                    FontId::monospace(12.0),
+                    Color32::WHITE,
+                    60.0,
+                );
+                job.halign = halign;
+                job.justify = halign == Align::LEFT;
+                jobs.push(job);
+            }
+        }
+        let mut job = LayoutJob {
+            first_row_min_height: 30.0,
+            ..Default::default()
+        };
+        job.append("Big\nsections", 5.0, format(20.0, Color32::RED));
+        job.append("", 3.0, format(8.0, Color32::GREEN));
+        job.append("\nand small\n", 2.0, format(10.0, Color32::BLUE));
+        job.append("end", 0.0, format(14.0, Color32::WHITE));
+        jobs.push(job);
+
+        for job in jobs {
+            let full = super::super::layout(&mut fonts.lock().fonts, job.clone().into());
+            let cached = fonts.layout_job(job.clone());
+            // Do it twice to make sure we also hit the cache:
+            let cached_again = fonts.layout_job(job.clone());
+            assert!(Arc::ptr_eq(&cached, &cached_again));
+
+            assert_eq!(full.rows.len(), cached.rows.len(), "{:?}", job.text);
+            assert_eq!(full.rect, cached.rect, "{:?}", job.text);
+            assert_eq!(full.num_vertices, cached.num_vertices);
+            for (a, b) in full.rows.iter().zip(&cached.rows) {
+                assert_eq!(a.ends_with_newline, b.ends_with_newline, "{:?}", job.text);
+                assert_eq!(a.section_index_at_start, b.section_index_at_start);
+                assert_eq!(a.rect, b.rect, "{:?}", job.text);
+                assert_eq!(a.glyphs.len(), b.glyphs.len());
+                for (a, b) in a.glyphs.iter().zip(&b.glyphs) {
+                    assert_eq!(a.chr, b.chr);
+                    assert_eq!(a.section_index, b.section_index);
+                    assert!((a.pos - b.pos).length() < 1e-3, "{:?}", job.text);
+                }
+            }
+        }
+    }
+}
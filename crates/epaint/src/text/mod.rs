@@ -1,6 +1,8 @@
 //! Everything related to text, fonts, text layout, cursors etc.
 
+mod color_glyph;
 pub mod cursor;
+mod distance_field;
 mod font;
 mod fonts;
 mod text_layout;
@@ -10,8 +12,10 @@ mod text_layout_types;
 pub const TAB_SIZE: usize = 4;
 
 pub use {
-    fonts::{FontData, FontDefinitions, FontFamily, FontId, FontTweak, Fonts, FontsImpl},
-    text_layout::layout,
+    fonts::{
+        FontAtlasStats, FontData, FontDefinitions, FontFamily, FontId, FontTweak, Fonts, FontsImpl,
+    },
+    text_layout::{layout, measure},
     text_layout_types::*,
 };
 
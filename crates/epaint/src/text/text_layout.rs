@@ -5,7 +5,10 @@ use emath::*;
 
 use crate::{text::font::Font, Color32, Mesh, Stroke, Vertex};
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
+use super::{
+    FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals, TextFormat, TextMetrics,
+    TextShadow, TruncateMode,
+};
 
 // ----------------------------------------------------------------------------
 
@@ -84,22 +87,81 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         };
     }
 
-    // For most of this we ignore the y coordinate:
+    let mut elided = false;
+    let rows = rows_from_job(fonts, &job, &mut elided);
 
-    let mut paragraphs = vec![Paragraph::from_section_index(0)];
-    for (section_index, section) in job.sections.iter().enumerate() {
-        layout_section(fonts, &job, section_index as u32, section, &mut paragraphs);
+    // Calculate the Y positions and tessellate the text:
+    let point_scale = PointScale::new(fonts.pixels_per_point());
+    galley_from_rows(point_scale, job, rows, elided)
+}
+
+/// Measure the size of some text without creating a [`Galley`].
+///
+/// This does the same layout as [`layout`] (wrapping, eliding, alignment, …),
+/// but skips positioning and tessellating the glyphs.
+///
+/// In most cases you should use [`crate::Fonts::measure`] instead.
+pub fn measure(fonts: &mut FontsImpl, job: &LayoutJob) -> TextMetrics {
+    if job.wrap.max_rows == 0 {
+        return TextMetrics::default();
     }
 
     let point_scale = PointScale::new(fonts.pixels_per_point());
 
     let mut elided = false;
-    let mut rows = rows_from_paragraphs(paragraphs, &job, &mut elided);
-    if elided {
-        if let Some(last_row) = rows.last_mut() {
-            replace_last_glyph_with_overflow_character(fonts, &job, last_row);
+    let rows = rows_from_job(fonts, job, &mut elided);
+
+    let mut metrics = TextMetrics {
+        num_glyphs: rows.iter().map(|row| row.glyphs.len()).sum(),
+        row_widths: rows.iter().map(|row| row.rect.width()).collect(),
+        ..Default::default()
+    };
+
+    let mut first_row_min_height = job.first_row_min_height;
+    let mut cursor_y = 0.0;
+    let mut min_x: f32 = 0.0;
+    let mut max_x: f32 = 0.0;
+    for (i, row) in rows.iter().enumerate() {
+        let (line_height, row_ascent) =
+            row_height_and_ascent(point_scale, row, first_row_min_height);
+        first_row_min_height = 0.0;
+
+        if i == 0 {
+            metrics.ascent = row_ascent;
         }
+        metrics.descent = line_height - row_ascent;
+
+        min_x = min_x.min(row.rect.min.x);
+        max_x = max_x.max(row.rect.max.x);
+        cursor_y += line_height;
+        cursor_y = point_scale.round_to_pixel(cursor_y);
     }
+    metrics.size = vec2(max_x - min_x, cursor_y);
+
+    metrics
+}
+
+/// Break the text of the job into rows, ignoring the Y coordinate.
+fn rows_from_job(fonts: &mut FontsImpl, job: &LayoutJob, elided: &mut bool) -> Vec<Row> {
+    let mut paragraphs = vec![Paragraph::from_section_index(0)];
+    for (section_index, section) in job.sections.iter().enumerate() {
+        layout_section(fonts, job, section_index as u32, section, &mut paragraphs);
+    }
+
+    let point_scale = PointScale::new(fonts.pixels_per_point());
+
+    let mut rows = if let Some(row) = truncate_start_or_middle(fonts, job, &paragraphs) {
+        *elided = true;
+        vec![row]
+    } else {
+        let mut rows = rows_from_paragraphs(paragraphs, job, elided);
+        if *elided {
+            if let Some(last_row) = rows.last_mut() {
+                replace_last_glyph_with_overflow_character(fonts, job, last_row);
+            }
+        }
+        rows
+    };
 
     let justify = job.justify && job.wrap.max_width.is_finite();
 
@@ -118,8 +180,7 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         }
     }
 
-    // Calculate the Y positions and tessellate the text:
-    galley_from_rows(point_scale, job, rows, elided)
+    rows
 }
 
 // Ignores the Y coordinate.
@@ -156,6 +217,7 @@ fn layout_section(
             out_paragraphs.push(Paragraph::from_section_index(section_index));
             paragraph = out_paragraphs.last_mut().unwrap();
             paragraph.empty_paragraph_height = line_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
+            last_glyph_id = None; // No kerning between paragraphs
         } else {
             let (font_impl, glyph_info) = font.font_impl_and_glyph_info(chr);
             if let Some(font_impl) = font_impl {
@@ -475,6 +537,113 @@ fn replace_last_glyph_with_overflow_character(
     }
 }
 
+/// Elide the start or middle of a single paragraph that doesn't fit on a single row,
+/// see [`TruncateMode`].
+///
+/// Returns `None` if the text should be laid out as usual.
+///
+/// Called before we have any Y coordinates.
+fn truncate_start_or_middle(
+    fonts: &mut FontsImpl,
+    job: &LayoutJob,
+    paragraphs: &[Paragraph],
+) -> Option<Row> {
+    let mode = job.wrap.truncate_mode;
+    let [paragraph] = paragraphs else {
+        return None;
+    };
+    let glyphs = &paragraph.glyphs;
+    let (first, last) = (glyphs.first()?, glyphs.last()?);
+    if mode == TruncateMode::End || job.wrap.max_rows != 1 || last.max_x() <= job.wrap.max_width {
+        return None;
+    }
+
+    let overflow_glyph = |fonts: &mut FontsImpl, section_index: u32, x: f32| {
+        let overflow_character = job.wrap.overflow_character?;
+        let format = &job.sections[section_index as usize].format;
+        let font = fonts.font(&format.font_id);
+        let line_height = format.line_height.unwrap_or_else(|| font.row_height());
+        let (font_impl, glyph_info) = font.font_impl_and_glyph_info(overflow_character);
+        Some(Glyph {
+            chr: overflow_character,
+            pos: pos2(x, f32::NAN),
+            size: vec2(glyph_info.advance_width, line_height),
+            ascent: font_impl.map_or(0.0, |font| font.ascent()),
+            uv_rect: glyph_info.uv_rect,
+            section_index,
+        })
+    };
+
+    // Keep `glyphs[..prefix]` and `glyphs[suffix..]`:
+    let start_x = first.pos.x; // leading space
+    let overflow_width = overflow_glyph(fonts, first.section_index, 0.0).map_or(0.0, |g| g.size.x);
+    let budget = job.wrap.max_width - start_x - overflow_width;
+    let prefix_width = |prefix: usize| {
+        if prefix == 0 {
+            0.0
+        } else {
+            glyphs[prefix - 1].max_x() - start_x
+        }
+    };
+    let suffix_width = |suffix: usize| {
+        if suffix == glyphs.len() {
+            0.0
+        } else {
+            last.max_x() - glyphs[suffix].pos.x
+        }
+    };
+
+    let (mut prefix, mut suffix) = (0, glyphs.len());
+    while prefix < suffix {
+        let fits = |prefix, suffix| prefix_width(prefix) + suffix_width(suffix) <= budget;
+        // For the middle, grow whichever side is shorter so far:
+        let grow_prefix = mode == TruncateMode::Middle
+            && prefix_width(prefix) <= suffix_width(suffix)
+            && fits(prefix + 1, suffix);
+        if grow_prefix {
+            prefix += 1;
+        } else if fits(prefix, suffix - 1) {
+            suffix -= 1;
+        } else if mode == TruncateMode::Middle && fits(prefix + 1, suffix) {
+            prefix += 1;
+        } else {
+            break;
+        }
+    }
+
+    let mut row_glyphs: Vec<Glyph> = glyphs[..prefix].to_vec();
+    let mut x = start_x + prefix_width(prefix);
+    let section_index = glyphs[prefix.min(glyphs.len() - 1)].section_index;
+    if let Some(overflow_glyph) = overflow_glyph(fonts, section_index, x) {
+        x += overflow_glyph.size.x;
+        row_glyphs.push(overflow_glyph);
+    }
+    if let Some(first_of_suffix) = glyphs.get(suffix) {
+        let dx = x - first_of_suffix.pos.x;
+        row_glyphs.extend(glyphs[suffix..].iter().map(|glyph| {
+            let mut glyph = *glyph;
+            glyph.pos.x += dx;
+            glyph
+        }));
+    }
+
+    let rect = match (row_glyphs.first(), row_glyphs.last()) {
+        (Some(first), Some(last)) => rect_from_x_range(first.pos.x..=last.max_x()),
+        _ => rect_from_x_range(start_x..=start_x),
+    };
+    Some(Row {
+        section_index_at_start: row_glyphs
+            .first()
+            .map_or(paragraph.section_index_at_start, |glyph| {
+                glyph.section_index
+            }),
+        glyphs: row_glyphs,
+        visuals: Default::default(),
+        rect,
+        ends_with_newline: false,
+    })
+}
+
 /// Horizontally aligned the text on a row.
 ///
 /// /// Ignores the Y coordinate.
@@ -568,6 +737,24 @@ fn halign_and_justify_row(
     row.rect.max.x = target_max_x;
 }
 
+/// The height of a row, and the distance from its top to its baseline.
+fn row_height_and_ascent(point_scale: PointScale, row: &Row, min_height: f32) -> (f32, f32) {
+    let mut line_height = min_height.max(row.rect.height());
+    let mut row_ascent = 0.0f32;
+
+    // take metrics from the highest font in this row
+    if let Some(glyph) = row
+        .glyphs
+        .iter()
+        .max_by(|a, b| a.size.y.partial_cmp(&b.size.y).unwrap())
+    {
+        line_height = glyph.size.y;
+        row_ascent = glyph.ascent;
+    }
+
+    (point_scale.round_to_pixel(line_height), row_ascent)
+}
+
 /// Calculate the Y positions and tessellate the text.
 fn galley_from_rows(
     point_scale: PointScale,
@@ -580,21 +767,10 @@ fn galley_from_rows(
     let mut min_x: f32 = 0.0;
     let mut max_x: f32 = 0.0;
     for row in &mut rows {
-        let mut line_height = first_row_min_height.max(row.rect.height());
-        let mut row_ascent = 0.0f32;
+        let (line_height, row_ascent) =
+            row_height_and_ascent(point_scale, row, first_row_min_height);
         first_row_min_height = 0.0;
 
-        // take metrics from the highest font in this row
-        if let Some(glyph) = row
-            .glyphs
-            .iter()
-            .max_by(|a, b| a.size.y.partial_cmp(&b.size.y).unwrap())
-        {
-            line_height = glyph.size.y;
-            row_ascent = glyph.ascent;
-        }
-        line_height = point_scale.round_to_pixel(line_height);
-
         // Now positions each glyph:
         for glyph in &mut row.glyphs {
             let format = &job.sections[glyph.section_index as usize].format;
@@ -649,6 +825,8 @@ struct FormatSummary {
     any_background: bool,
     any_underline: bool,
     any_strikethrough: bool,
+    any_outline: bool,
+    any_shadow: bool,
 }
 
 fn format_summary(job: &LayoutJob) -> FormatSummary {
@@ -657,6 +835,8 @@ fn format_summary(job: &LayoutJob) -> FormatSummary {
         format_summary.any_background |= section.format.background != Color32::TRANSPARENT;
         format_summary.any_underline |= section.format.underline != Stroke::NONE;
         format_summary.any_strikethrough |= section.format.strikethrough != Stroke::NONE;
+        format_summary.any_outline |= !section.format.outline.is_empty();
+        format_summary.any_shadow |= !section.format.shadow.is_empty();
     }
     format_summary
 }
@@ -680,6 +860,14 @@ fn tessellate_row(
         add_row_backgrounds(job, row, &mut mesh);
     }
 
+    // Shadows and outlines go below the glyphs, and keep their color (they are not part of `glyph_vertex_range`):
+    if format_summary.any_shadow {
+        tessellate_glyph_shadows(point_scale, job, row, &mut mesh);
+    }
+    if format_summary.any_outline {
+        tessellate_glyph_outlines(point_scale, job, row, &mut mesh);
+    }
+
     let glyph_vertex_start = mesh.vertices.len();
     tessellate_glyphs(point_scale, job, row, &mut mesh);
     let glyph_vertex_end = mesh.vertices.len();
@@ -758,54 +946,142 @@ fn add_row_backgrounds(job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
 
 fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
     for glyph in &row.glyphs {
-        let uv_rect = glyph.uv_rect;
-        if !uv_rect.is_nothing() {
-            let mut left_top = glyph.pos + uv_rect.offset;
-            left_top.x = point_scale.round_to_pixel(left_top.x);
-            left_top.y = point_scale.round_to_pixel(left_top.y);
-
-            let rect = Rect::from_min_max(left_top, left_top + uv_rect.size);
-            let uv = Rect::from_min_max(
-                pos2(uv_rect.min[0] as f32, uv_rect.min[1] as f32),
-                pos2(uv_rect.max[0] as f32, uv_rect.max[1] as f32),
-            );
+        let format = &job.sections[glyph.section_index as usize].format;
+        let color = if glyph.uv_rect.is_color {
+            Color32::WHITE // emojis have their own colors
+        } else {
+            format.color
+        };
+        add_glyph_quad(point_scale, glyph, format, Vec2::ZERO, color, mesh);
+    }
+}
 
-            let format = &job.sections[glyph.section_index as usize].format;
+fn tessellate_glyph_shadows(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
+    for glyph in &row.glyphs {
+        let format = &job.sections[glyph.section_index as usize].format;
+        let TextShadow { offset, color } = format.shadow;
+        if color == Color32::TRANSPARENT || glyph.uv_rect.is_color {
+            continue;
+        }
+        // The shadow has the shape of the outlined text:
+        for outline_offset in outline_offsets(point_scale, format.outline.width) {
+            add_glyph_quad(
+                point_scale,
+                glyph,
+                format,
+                offset + outline_offset,
+                color,
+                mesh,
+            );
+        }
+    }
+}
 
-            let color = format.color;
+fn tessellate_glyph_outlines(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
+    for glyph in &row.glyphs {
+        let format = &job.sections[glyph.section_index as usize].format;
+        if format.outline.is_empty() || glyph.uv_rect.is_color {
+            continue;
+        }
+        for offset in outline_offsets(point_scale, format.outline.width) {
+            add_glyph_quad(
+                point_scale,
+                glyph,
+                format,
+                offset,
+                format.outline.color,
+                mesh,
+            );
+        }
+    }
+}
 
-            if format.italics {
-                let idx = mesh.vertices.len() as u32;
-                mesh.add_triangle(idx, idx + 1, idx + 2);
-                mesh.add_triangle(idx + 2, idx + 1, idx + 3);
+/// Where to repeat a glyph to paint an outline of the given width around it.
+///
+/// Just `[Vec2::ZERO]` for no outline.
+fn outline_offsets(point_scale: PointScale, width: f32) -> Vec<Vec2> {
+    let width_in_pixels = width * point_scale.pixels_per_point();
+    if width_in_pixels <= 0.0 {
+        return vec![Vec2::ZERO];
+    }
 
-                let top_offset = rect.height() * 0.25 * Vec2::X;
+    // Wider outlines need more steps to stay round, and an inner ring to have no gaps:
+    let num_directions = if width_in_pixels <= 2.0 { 8 } else { 16 };
+    let rings: &[f32] = if width_in_pixels <= 2.0 {
+        &[1.0]
+    } else {
+        &[0.5, 1.0]
+    };
 
-                mesh.vertices.push(Vertex {
-                    pos: rect.left_top() + top_offset,
-                    uv: uv.left_top(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.right_top() + top_offset,
-                    uv: uv.right_top(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.left_bottom(),
-                    uv: uv.left_bottom(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.right_bottom(),
-                    uv: uv.right_bottom(),
-                    color,
-                });
-            } else {
-                mesh.add_rect_with_uv(rect, uv, color);
-            }
+    let mut offsets = Vec::with_capacity(num_directions * rings.len());
+    for &ring in rings {
+        for i in 0..num_directions {
+            let angle = i as f32 * std::f32::consts::TAU / num_directions as f32;
+            let offset = ring * width * Vec2::angled(angle);
+            offsets.push(vec2(
+                point_scale.round_to_pixel(offset.x),
+                point_scale.round_to_pixel(offset.y),
+            ));
         }
     }
+    offsets.dedup();
+    offsets
+}
+
+fn add_glyph_quad(
+    point_scale: PointScale,
+    glyph: &Glyph,
+    format: &TextFormat,
+    offset: Vec2,
+    color: Color32,
+    mesh: &mut Mesh,
+) {
+    let uv_rect = glyph.uv_rect;
+    if uv_rect.is_nothing() {
+        return;
+    }
+
+    let mut left_top = glyph.pos + uv_rect.offset;
+    left_top.x = point_scale.round_to_pixel(left_top.x);
+    left_top.y = point_scale.round_to_pixel(left_top.y);
+    left_top += offset;
+
+    let rect = Rect::from_min_max(left_top, left_top + uv_rect.size);
+    let uv = Rect::from_min_max(
+        pos2(uv_rect.min[0] as f32, uv_rect.min[1] as f32),
+        pos2(uv_rect.max[0] as f32, uv_rect.max[1] as f32),
+    );
+
+    if format.italics {
+        let idx = mesh.vertices.len() as u32;
+        mesh.add_triangle(idx, idx + 1, idx + 2);
+        mesh.add_triangle(idx + 2, idx + 1, idx + 3);
+
+        let top_offset = rect.height() * 0.25 * Vec2::X;
+
+        mesh.vertices.push(Vertex {
+            pos: rect.left_top() + top_offset,
+            uv: uv.left_top(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.right_top() + top_offset,
+            uv: uv.right_top(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.left_bottom(),
+            uv: uv.left_bottom(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.right_bottom(),
+            uv: uv.right_bottom(),
+            color,
+        });
+    } else {
+        mesh.add_rect_with_uv(rect, uv, color);
+    }
 }
 
 /// Add a horizontal line over a row of glyphs with a stroke and y decided by a callback.
@@ -1032,6 +1308,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_truncate_mode() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let text_format = TextFormat {
+            font_id: FontId::monospace(12.0),
+            ..Default::default()
+        };
+        let char_width = layout(
+            &mut fonts,
+            LayoutJob::single_section("x".into(), text_format.clone()).into(),
+        )
+        .size()
+        .x;
+
+        let truncate = |fonts: &mut FontsImpl, text: &str, num_chars: f32, mode| {
+            let mut layout_job = LayoutJob::single_section(text.into(), text_format.clone());
+            layout_job.wrap = TextWrapping {
+                truncate_mode: mode,
+                ..TextWrapping::truncate_at_width(num_chars * char_width + 0.1)
+            };
+            let galley = layout(fonts, layout_job.into());
+            assert_eq!(galley.rows.len(), 1);
+            (galley.elided, galley.rows[0].text())
+        };
+
+        let text = "long_file_name.txt";
+        assert_eq!(
+            truncate(&mut fonts, text, 100.0, TruncateMode::Middle),
+            (false, text.to_owned())
+        );
+        assert_eq!(
+            truncate(&mut fonts, text, 9.0, TruncateMode::End),
+            (true, "long_fil…".to_owned())
+        );
+        assert_eq!(
+            truncate(&mut fonts, text, 9.0, TruncateMode::Middle),
+            (true, "long….txt".to_owned())
+        );
+        assert_eq!(
+            truncate(&mut fonts, text, 9.0, TruncateMode::Start),
+            (true, "…name.txt".to_owned())
+        );
+        assert_eq!(
+            truncate(&mut fonts, text, 0.0, TruncateMode::Middle),
+            (true, "…".to_owned())
+        );
+
+        // Multiple paragraphs are elided at the end:
+        assert_eq!(
+            truncate(&mut fonts, "Hello\nworld", 100.0, TruncateMode::Start),
+            (true, "Hello…".to_owned())
+        );
+    }
+
     #[test]
     fn test_cjk() {
         let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
@@ -1061,4 +1391,33 @@ mod tests {
             vec!["日本語とEnglish", "の混在した文章"]
         );
     }
+
+    #[test]
+    fn test_outline_and_shadow() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let plain = layout(
+            &mut fonts,
+            LayoutJob::single_section("Hi".into(), TextFormat::default()).into(),
+        );
+        let fancy = layout(
+            &mut fonts,
+            LayoutJob::single_section(
+                "Hi".into(),
+                TextFormat {
+                    outline: Stroke::new(1.0, Color32::BLACK),
+                    shadow: TextShadow::new(Vec2::splat(2.0), Color32::BLACK),
+                    ..Default::default()
+                },
+            )
+            .into(),
+        );
+
+        let glyph_vertices = |galley: &Galley| galley.rows[0].visuals.glyph_vertex_range.len();
+        assert_eq!(glyph_vertices(&plain), glyph_vertices(&fancy));
+        // 8 outline quads and 8 shadow quads (of the outline) per glyph:
+        assert_eq!(
+            fancy.rows[0].visuals.mesh.vertices.len(),
+            17 * plain.rows[0].visuals.mesh.vertices.len()
+        );
+    }
 }
@@ -259,6 +259,13 @@ pub struct TextFormat {
     /// can get the effect of raised text.
     pub valign: Align,
     // TODO(emilk): lowered
+    /// Outline around each glyph, e.g. to keep text readable on top of images.
+    ///
+    /// Painted by repeating the glyph around itself, so keep the width to a few pixels.
+    pub outline: Stroke,
+
+    /// Drop shadow behind the text (and its outline).
+    pub shadow: TextShadow,
 }
 
 impl Default for TextFormat {
@@ -274,6 +281,8 @@ impl Default for TextFormat {
             underline: Stroke::NONE,
             strikethrough: Stroke::NONE,
             valign: Align::BOTTOM,
+            outline: Stroke::NONE,
+            shadow: TextShadow::NONE,
         }
     }
 }
@@ -291,6 +300,8 @@ impl std::hash::Hash for TextFormat {
             underline,
             strikethrough,
             valign,
+            outline,
+            shadow,
         } = self;
         font_id.hash(state);
         crate::f32_hash(state, *extra_letter_spacing);
@@ -303,6 +314,8 @@ impl std::hash::Hash for TextFormat {
         underline.hash(state);
         strikethrough.hash(state);
         valign.hash(state);
+        outline.hash(state);
+        shadow.hash(state);
     }
 }
 
@@ -319,6 +332,55 @@ impl TextFormat {
 
 // ----------------------------------------------------------------------------
 
+/// A drop shadow behind text, see [`TextFormat::shadow`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TextShadow {
+    /// How far the shadow is from the text, in points.
+    pub offset: Vec2,
+
+    pub color: Color32,
+}
+
+impl Default for TextShadow {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl TextShadow {
+    pub const NONE: Self = Self {
+        offset: Vec2::ZERO,
+        color: Color32::TRANSPARENT,
+    };
+
+    #[inline]
+    pub fn new(offset: impl Into<Vec2>, color: impl Into<Color32>) -> Self {
+        Self {
+            offset: offset.into(),
+            color: color.into(),
+        }
+    }
+
+    /// True if the shadow is invisible.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.color == Color32::TRANSPARENT
+    }
+}
+
+impl std::hash::Hash for TextShadow {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self { offset, color } = self;
+        crate::f32_hash(state, offset.x);
+        crate::f32_hash(state, offset.y);
+        color.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Controls the text wrapping and elision of a [`LayoutJob`].
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -364,6 +426,12 @@ pub struct TextWrapping {
     ///
     /// If not set, no character will be used (but the text will still be elided).
     pub overflow_character: Option<char>,
+
+    /// Which part of the text to elide when it doesn't fit.
+    ///
+    /// [`TruncateMode::Start`] and [`TruncateMode::Middle`] only apply when [`Self::max_rows`] is `1`
+    /// and the text is a single paragraph (has no `\n`). Otherwise the end of the text is elided.
+    pub truncate_mode: TruncateMode,
 }
 
 impl std::hash::Hash for TextWrapping {
@@ -374,11 +442,13 @@ impl std::hash::Hash for TextWrapping {
             max_rows,
             break_anywhere,
             overflow_character,
+            truncate_mode,
         } = self;
         crate::f32_hash(state, *max_width);
         max_rows.hash(state);
         break_anywhere.hash(state);
         overflow_character.hash(state);
+        truncate_mode.hash(state);
     }
 }
 
@@ -389,6 +459,7 @@ impl Default for TextWrapping {
             max_rows: usize::MAX,
             break_anywhere: false,
             overflow_character: Some('…'),
+            truncate_mode: TruncateMode::End,
         }
     }
 }
@@ -413,6 +484,54 @@ impl TextWrapping {
     }
 }
 
+/// Where to elide text that doesn't fit, see [`TextWrapping::truncate_mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TruncateMode {
+    /// `long_file_na…`
+    #[default]
+    End,
+
+    /// `long_fi…me.txt`, e.g. for file names and paths.
+    Middle,
+
+    /// `…e_name.txt`
+    Start,
+}
+
+// ----------------------------------------------------------------------------
+
+/// The size of some text, as measured by [`crate::Fonts::measure`].
+///
+/// This is cheaper to get than a [`Galley`], since the glyphs are not positioned nor tessellated,
+/// but the numbers are the same as those of the corresponding [`Galley`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TextMetrics {
+    /// Same as [`Galley::size`].
+    pub size: Vec2,
+
+    /// Distance from the top of the text to the baseline of the first row, in points.
+    pub ascent: f32,
+
+    /// Distance from the baseline of the last row to the bottom of the text, in points.
+    pub descent: f32,
+
+    /// The width of each row, after wrapping, in points.
+    pub row_widths: Vec<f32>,
+
+    /// Number of glyphs in all rows, including any overflow character.
+    pub num_glyphs: usize,
+}
+
+impl TextMetrics {
+    /// Number of rows, after wrapping.
+    #[inline]
+    pub fn num_rows(&self) -> usize {
+        self.row_widths.len()
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// Text that has been laid out, ready for painting.
@@ -632,6 +751,62 @@ impl Galley {
     pub fn size(&self) -> Vec2 {
         self.rect.size()
     }
+
+    /// Stack the galleys of the paragraphs of `job` on top of each other.
+    ///
+    /// Each paragraph comes with the index of its first section in `job.sections`.
+    pub(crate) fn concat(
+        job: Arc<LayoutJob>,
+        paragraphs: &[(usize, Arc<Self>)],
+        pixels_per_point: f32,
+    ) -> Self {
+        let mut rows = Vec::with_capacity(paragraphs.iter().map(|(_, g)| g.rows.len()).sum());
+        let mut rect = Rect::from_min_max(Pos2::ZERO, Pos2::ZERO);
+        let mut mesh_bounds = Rect::NOTHING;
+        let mut num_vertices = 0;
+        let mut num_indices = 0;
+        let mut elided = false;
+
+        for (i, (first_section_index, galley)) in paragraphs.iter().enumerate() {
+            let section_offset = *first_section_index as u32;
+            let translation = vec2(0.0, rect.bottom());
+            let is_last_paragraph = i + 1 == paragraphs.len();
+
+            let num_rows_before = rows.len();
+            for row in &galley.rows {
+                let mut row = row.clone();
+                row.section_index_at_start += section_offset;
+                for glyph in &mut row.glyphs {
+                    glyph.pos += translation;
+                    glyph.section_index += section_offset;
+                }
+                row.rect = row.rect.translate(translation);
+                row.visuals.mesh.translate(translation);
+                row.visuals.mesh_bounds = row.visuals.mesh_bounds.translate(translation);
+                rows.push(row);
+            }
+            if !is_last_paragraph && num_rows_before < rows.len() {
+                rows.last_mut().unwrap().ends_with_newline = true;
+            }
+
+            rect = rect.union(galley.rect.translate(translation));
+            mesh_bounds = mesh_bounds.union(galley.mesh_bounds.translate(translation));
+            num_vertices += galley.num_vertices;
+            num_indices += galley.num_indices;
+            elided |= galley.elided;
+        }
+
+        Self {
+            job,
+            rows,
+            elided,
+            rect,
+            mesh_bounds,
+            num_vertices,
+            num_indices,
+            pixels_per_point,
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -52,6 +52,25 @@ pub struct PreparedDisc {
     pub uv: Rect,
 }
 
+/// Statistics about a [`TextureAtlas`], see [`TextureAtlas::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextureAtlasStats {
+    /// Current size of the atlas texture, in texels.
+    pub size: [usize; 2],
+
+    /// The atlas texture will never grow taller than this.
+    pub max_height: usize,
+
+    /// See [`TextureAtlas::fill_ratio`].
+    pub fill_ratio: f32,
+
+    /// Number of rectangles (glyphs, discs, …) allocated in the atlas.
+    pub num_allocations: usize,
+
+    /// Set if we ran out of space, and started overwriting old glyphs.
+    pub overflowed: bool,
+}
+
 /// Contains font data in an atlas, where each character occupied a small rectangle.
 ///
 /// More characters can be added, possibly expanding the texture.
@@ -70,6 +89,9 @@ pub struct TextureAtlas {
     /// Set when someone requested more space than was available.
     overflowed: bool,
 
+    /// Number of calls to [`Self::allocate`].
+    num_allocations: usize,
+
     /// pre-rasterized discs of radii `2^i`, where `i` is the index.
     discs: Vec<PrerasterizedDisc>,
 }
@@ -83,6 +105,7 @@ impl TextureAtlas {
             cursor: (0, 0),
             row_height: 0,
             overflowed: false,
+            num_allocations: 0,
             discs: vec![], // will be filled in below
         };
 
@@ -172,6 +195,17 @@ impl TextureAtlas {
         }
     }
 
+    /// How large and how full the atlas is.
+    pub fn stats(&self) -> TextureAtlasStats {
+        TextureAtlasStats {
+            size: self.size(),
+            max_height: self.max_height(),
+            fill_ratio: self.fill_ratio(),
+            num_allocations: self.num_allocations,
+            overflowed: self.overflowed,
+        }
+    }
+
     /// The texture options suitable for a font texture
     #[inline]
     pub fn texture_options() -> crate::textures::TextureOptions {
@@ -184,6 +218,27 @@ impl TextureAtlas {
         &self.image
     }
 
+    /// Store glyphs as signed distance fields, see [`FontImage::distance_field`].
+    ///
+    /// The white texel and the discs are valid in both modes,
+    /// but this must be set before any glyphs are allocated.
+    pub fn set_distance_field(&mut self, distance_field: bool) {
+        self.image.distance_field = distance_field;
+        self.dirty = Rectu::EVERYTHING;
+    }
+
+    /// See [`Self::set_distance_field`].
+    #[inline]
+    pub fn is_distance_field(&self) -> bool {
+        self.image.distance_field
+    }
+
+    /// Make the next call to [`Self::take_delta`] return the whole image,
+    /// e.g. because the texture was lost together with the graphics context.
+    pub fn mark_all_dirty(&mut self) {
+        self.dirty = Rectu::EVERYTHING;
+    }
+
     /// Call to get the change to the image since last call.
     pub fn take_delta(&mut self) -> Option<ImageDelta> {
         let texture_options = Self::texture_options();
@@ -240,6 +295,16 @@ impl TextureAtlas {
 
         let pos = self.cursor;
         self.cursor.0 += w + PADDING;
+        self.num_allocations += 1;
+
+        if self.overflowed && !self.image.colors.is_empty() {
+            // We may be reusing the space of a color glyph:
+            for y in pos.1..pos.1 + h {
+                for x in pos.0..pos.0 + w {
+                    self.image.set_color((x, y), crate::Color32::TRANSPARENT);
+                }
+            }
+        }
 
         self.dirty.min_x = self.dirty.min_x.min(pos.0);
         self.dirty.min_y = self.dirty.min_y.min(pos.1);
@@ -257,6 +322,11 @@ fn resize_to_min_height(image: &mut FontImage, required_height: usize) -> bool {
 
     if image.width() * image.height() > image.pixels.len() {
         image.pixels.resize(image.width() * image.height(), 0.0);
+        if !image.colors.is_empty() {
+            image
+                .colors
+                .resize(image.width() * image.height(), crate::Color32::TRANSPARENT);
+        }
         true
     } else {
         false
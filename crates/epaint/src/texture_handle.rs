@@ -85,6 +85,8 @@ impl TextureHandle {
     }
 
     /// width x height
+    ///
+    /// This is the size of the whole image, even if it is split into tiles, see [`Self::num_tiles`].
     pub fn size(&self) -> [usize; 2] {
         self.tex_mngr
             .read()
@@ -92,6 +94,18 @@ impl TextureHandle {
             .map_or([0, 0], |tex| tex.size)
     }
 
+    /// Into how many textures the image is split, horizontally x vertically.
+    ///
+    /// This is `[1, 1]` unless the image is larger than the backend supports,
+    /// see [`crate::textures::TextureTiling`].
+    pub fn num_tiles(&self) -> [usize; 2] {
+        self.tex_mngr
+            .read()
+            .meta(self.id)
+            .and_then(|tex| tex.tiling.as_ref())
+            .map_or([1, 1], |tiling| tiling.num_tiles)
+    }
+
     /// width x height
     pub fn size_vec2(&self) -> crate::Vec2 {
         let [w, h] = self.size();
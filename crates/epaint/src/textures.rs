@@ -1,4 +1,10 @@
-use crate::{ImageData, ImageDelta, TextureId};
+use std::sync::Arc;
+
+use crate::{
+    emath::{pos2, Pos2, Rect, Vec2},
+    ClippedPrimitive, Color32, ImageData, ImageDelta, InstancedMesh, Mesh, Primitive, TextureId,
+    Vertex,
+};
 
 // ----------------------------------------------------------------------------
 
@@ -14,6 +20,9 @@ pub struct TextureManager {
     metas: ahash::HashMap<TextureId, TextureMeta>,
 
     delta: TexturesDelta,
+
+    /// Textures larger than this are split into tiles. `None` means no limit.
+    max_texture_side: Option<usize>,
 }
 
 impl TextureManager {
@@ -28,9 +37,13 @@ impl TextureManager {
     /// MUST have a white pixel at (0,0) ([`crate::WHITE_UV`]).
     ///
     /// The texture is given a retain-count of `1`, requiring one call to [`Self::free`] to free it.
+    ///
+    /// If the image is larger than [`Self::set_max_texture_side`], it is split into tiles.
     pub fn alloc(&mut self, name: String, image: ImageData, options: TextureOptions) -> TextureId {
-        let id = TextureId::Managed(self.next_id);
-        self.next_id += 1;
+        let id = self.next_id();
+
+        let tiling = TextureTiling::layout(image.size(), self.max_texture_side)
+            .map(|layout| self.alloc_tiling(image.size(), layout));
 
         self.metas.entry(id).or_insert_with(|| TextureMeta {
             name,
@@ -38,14 +51,89 @@ impl TextureManager {
             bytes_per_pixel: image.bytes_per_pixel(),
             retain_count: 1,
             options,
+            tiling: tiling.clone(),
         });
 
-        self.delta.set.push((id, ImageDelta::full(image, options)));
+        let delta = ImageDelta::full(image, options);
+        if let Some(tiling) = &tiling {
+            self.delta.set.extend(tiling.split_delta(&delta));
+        } else {
+            self.delta.set.push((id, delta));
+        }
+        id
+    }
+
+    /// Textures with a side larger than this are split into several textures, see [`TextureTiling`].
+    ///
+    /// This should be the largest texture side supported by the backend.
+    /// `egui::Context` sets this for you, unless `egui::Options::tile_large_textures` is off.
+    ///
+    /// This only affects images given to the manager after this call.
+    pub fn set_max_texture_side(&mut self, max_texture_side: Option<usize>) {
+        self.max_texture_side = max_texture_side;
+    }
+
+    fn next_id(&mut self) -> TextureId {
+        let id = TextureId::Managed(self.next_id);
+        self.next_id += 1;
         id
     }
 
+    fn alloc_tiling(
+        &mut self,
+        size: [usize; 2],
+        (tile_step, num_tiles): TileLayout,
+    ) -> TextureTiling {
+        let tile_ids = (0..num_tiles[0] * num_tiles[1])
+            .map(|_| self.next_id())
+            .collect();
+        TextureTiling {
+            size,
+            num_tiles,
+            tile_ids,
+            tile_step,
+        }
+    }
+
+    /// Free the textures of the tiles of a texture that no longer uses them.
+    fn free_tiling(&mut self, tiling: &TextureTiling) {
+        self.delta
+            .set
+            .retain(|(id, _)| !tiling.tile_ids.contains(id));
+        self.delta.free.extend(tiling.tile_ids.iter().copied());
+    }
+
+    /// The tiling to use for a new whole image of texture `id`, reusing `old_tiling` if possible.
+    fn retile(
+        &mut self,
+        id: TextureId,
+        old_tiling: Option<TextureTiling>,
+        size: [usize; 2],
+    ) -> Option<TextureTiling> {
+        let layout = TextureTiling::layout(size, self.max_texture_side);
+        match old_tiling {
+            Some(old_tiling)
+                if old_tiling.size == size
+                    && Some((old_tiling.tile_step, old_tiling.num_tiles)) == layout =>
+            {
+                return Some(old_tiling);
+            }
+            Some(old_tiling) => self.free_tiling(&old_tiling),
+            None if layout.is_some() => {
+                // The backend won't need the untiled texture anymore:
+                self.delta.set.retain(|(x, _)| x != &id);
+                self.delta.free.push(id);
+            }
+            None => {}
+        }
+        layout.map(|layout| self.alloc_tiling(size, layout))
+    }
+
     /// Assign a new image to an existing texture,
     /// or update a region of it.
+    ///
+    /// A region update that falls within an update of the same texture that is already enqueued
+    /// is written into that one, so that they are uploaded together.
     pub fn set(&mut self, id: TextureId, delta: ImageDelta) {
         if let Some(meta) = self.metas.get_mut(&id) {
             if let Some(pos) = delta.pos {
@@ -54,12 +142,41 @@ impl TextureManager {
                         && pos[1] + delta.image.height() <= meta.size[1],
                     "Partial texture update is outside the bounds of texture {id:?}",
                 );
+
+                if let Some(tiling) = &meta.tiling {
+                    self.delta.set.extend(tiling.split_delta(&delta));
+                    return;
+                }
+
+                if let Some(pending_index) = self.delta.set.iter().rposition(|(x, _)| x == &id) {
+                    let pending = &mut self.delta.set[pending_index].1;
+                    if merge_patch(pending, &delta) {
+                        return;
+                    }
+                    if covers(&delta, pending) {
+                        let _superseded = self.delta.set.remove(pending_index);
+                    }
+                }
             } else {
                 // whole update
                 meta.size = delta.image.size();
                 meta.bytes_per_pixel = delta.image.bytes_per_pixel();
+                let old_tiling = meta.tiling.take();
+
+                let tiling = self.retile(id, old_tiling, delta.image.size());
                 // since we update the whole image, we can discard all old enqueued deltas
-                self.delta.set.retain(|(x, _)| x != &id);
+                if let Some(tiling) = &tiling {
+                    self.delta.set.retain(|(x, _)| !tiling.tile_ids.contains(x));
+                    self.delta.set.extend(tiling.split_delta(&delta));
+                } else {
+                    self.delta.set.retain(|(x, _)| x != &id);
+                    self.delta.free.retain(|x| x != &id);
+                    self.delta.set.push((id, delta));
+                }
+                if let Some(meta) = self.metas.get_mut(&id) {
+                    meta.tiling = tiling;
+                }
+                return;
             }
             self.delta.set.push((id, delta));
         } else {
@@ -73,8 +190,11 @@ impl TextureManager {
             let meta = entry.get_mut();
             meta.retain_count -= 1;
             if meta.retain_count == 0 {
-                entry.remove();
-                self.delta.free.push(id);
+                if let Some(tiling) = entry.remove().tiling {
+                    self.delta.free.extend(tiling.tile_ids);
+                } else {
+                    self.delta.free.push(id);
+                }
             }
         } else {
             crate::epaint_assert!(false, "Tried freeing texture {id:?} which is not allocated");
@@ -116,6 +236,103 @@ impl TextureManager {
     pub fn num_allocated(&self) -> usize {
         self.metas.len()
     }
+
+    /// Replace the meshes that use a tiled texture with meshes using the textures of its tiles.
+    ///
+    /// This is done by `egui::Context::tessellate`, so the backends never see the tiled textures.
+    pub fn resolve_tiles(&self, clipped_primitives: &mut Vec<ClippedPrimitive>) {
+        let tiling_of = |primitive: &Primitive| {
+            let texture_id = match primitive {
+                Primitive::Mesh(mesh) => mesh.texture_id,
+                Primitive::Instanced(instanced) => instanced.mesh.texture_id,
+                Primitive::Callback(_) => return None,
+            };
+            self.metas.get(&texture_id)?.tiling.as_ref()
+        };
+        if !clipped_primitives
+            .iter()
+            .any(|clipped_primitive| tiling_of(&clipped_primitive.primitive).is_some())
+        {
+            return;
+        }
+
+        let mut resolved = Vec::with_capacity(clipped_primitives.len());
+        for clipped_primitive in clipped_primitives.drain(..) {
+            let Some(tiling) = tiling_of(&clipped_primitive.primitive) else {
+                resolved.push(clipped_primitive);
+                continue;
+            };
+            let clip_rect = clipped_primitive.clip_rect;
+            match clipped_primitive.primitive {
+                Primitive::Mesh(mesh) => {
+                    resolved.extend(tiling.split_mesh(&mesh).into_iter().map(|mesh| {
+                        ClippedPrimitive {
+                            clip_rect,
+                            primitive: Primitive::Mesh(mesh),
+                        }
+                    }));
+                }
+                Primitive::Instanced(instanced) => {
+                    // Keep the instancing, with one instanced mesh per tile:
+                    resolved.extend(tiling.split_mesh(&instanced.mesh).into_iter().map(|mesh| {
+                        ClippedPrimitive {
+                            clip_rect,
+                            primitive: Primitive::Instanced(InstancedMesh {
+                                mesh: Arc::new(mesh),
+                                transforms: instanced.transforms.clone(),
+                            }),
+                        }
+                    }));
+                }
+                Primitive::Callback(_) => {} // Never tiled
+            }
+        }
+        *clipped_primitives = resolved;
+    }
+}
+
+/// Is the region at `inner_pos` of size `inner_size` inside the one at `outer_pos` of size `outer_size`?
+fn region_contains(
+    [outer_x, outer_y]: [usize; 2],
+    [outer_w, outer_h]: [usize; 2],
+    [inner_x, inner_y]: [usize; 2],
+    [inner_w, inner_h]: [usize; 2],
+) -> bool {
+    outer_x <= inner_x
+        && outer_y <= inner_y
+        && inner_x + inner_w <= outer_x + outer_w
+        && inner_y + inner_h <= outer_y + outer_h
+}
+
+/// Does the partial update `newer` overwrite everything that the partial update `older` changes?
+fn covers(newer: &ImageDelta, older: &ImageDelta) -> bool {
+    let (Some(newer_pos), Some(older_pos)) = (newer.pos, older.pos) else {
+        return false;
+    };
+    newer.options == older.options
+        && region_contains(newer_pos, newer.image.size(), older_pos, older.image.size())
+}
+
+/// Write the partial update `patch` into the image of the `pending` update, if it falls within it.
+///
+/// Returns `false` if the two updates can't be merged.
+fn merge_patch(pending: &mut ImageDelta, patch: &ImageDelta) -> bool {
+    let (Some(pos), ImageData::Color(patch_image)) = (patch.pos, &patch.image) else {
+        return false;
+    };
+    let pending_pos = pending.pos.unwrap_or([0, 0]);
+    let ImageData::Color(pending_image) = &mut pending.image else {
+        return false;
+    };
+    if pending.options != patch.options
+        || !region_contains(pending_pos, pending_image.size, pos, patch_image.size)
+    {
+        return false;
+    }
+
+    let relative_pos = [pos[0] - pending_pos[0], pos[1] - pending_pos[1]];
+    Arc::make_mut(pending_image).paste(relative_pos, patch_image);
+    true
 }
 
 /// Meta-data about an allocated texture.
@@ -135,6 +352,9 @@ pub struct TextureMeta {
 
     /// The texture filtering mode to use when rendering.
     pub options: TextureOptions,
+
+    /// Set if the texture is too large for the backend, and therefore split into tiles.
+    pub tiling: Option<TextureTiling>,
 }
 
 impl TextureMeta {
@@ -147,6 +367,240 @@ impl TextureMeta {
 
 // ----------------------------------------------------------------------------
 
+/// How many texels each tile shares with its neighbors,
+/// so that linear filtering doesn't show any seams between them.
+const TILE_BORDER: usize = 1;
+
+/// Texels between the starts of two tiles, and the number of tiles, along each axis.
+type TileLayout = ([usize; 2], [usize; 2]);
+
+/// How a texture that is too large for the backend is split into several smaller textures.
+///
+/// The [`TextureId`] of the whole texture can still be used as usual:
+/// [`TextureManager::resolve_tiles`] paints the tiles in its place.
+///
+/// See [`TextureManager::set_max_texture_side`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextureTiling {
+    /// Size of the whole texture, width x height.
+    pub size: [usize; 2],
+
+    /// Number of tiles, horizontally x vertically.
+    pub num_tiles: [usize; 2],
+
+    /// The texture of each tile, row by row.
+    pub tile_ids: Vec<TextureId>,
+
+    /// Texels between the starts of two tiles along each axis.
+    tile_step: [usize; 2],
+}
+
+impl TextureTiling {
+    /// How to split a texture of the given size, or `None` if it fits in one texture.
+    fn layout(size: [usize; 2], max_texture_side: Option<usize>) -> Option<TileLayout> {
+        let max_texture_side = max_texture_side?;
+        if size[0] <= max_texture_side && size[1] <= max_texture_side {
+            return None;
+        }
+        let max_step = max_texture_side.saturating_sub(2 * TILE_BORDER).max(1);
+        let tile_step = size.map(|side| {
+            if side <= max_texture_side {
+                side.max(1)
+            } else {
+                max_step
+            }
+        });
+        let num_tiles =
+            [0, 1].map(|axis| ((size[axis] + tile_step[axis] - 1) / tile_step[axis]).max(1));
+        Some((tile_step, num_tiles))
+    }
+
+    /// The texels `[min, max)` along `axis` painted from the tile at `index`, without its border.
+    fn core_range(&self, axis: usize, index: usize) -> (usize, usize) {
+        let min = index * self.tile_step[axis];
+        (min, (min + self.tile_step[axis]).min(self.size[axis]))
+    }
+
+    /// Position and size of the texels of the whole texture stored in the tile at `[x, y]`,
+    /// including its border.
+    fn stored_region(&self, [x, y]: [usize; 2]) -> ([usize; 2], [usize; 2]) {
+        let [(min_x, max_x), (min_y, max_y)] = [(0, x), (1, y)].map(|(axis, index)| {
+            let (min, max) = self.core_range(axis, index);
+            (
+                min.saturating_sub(TILE_BORDER),
+                (max + TILE_BORDER).min(self.size[axis]),
+            )
+        });
+        ([min_x, min_y], [max_x - min_x, max_y - min_y])
+    }
+
+    /// The texels painted from the tile at `[x, y]`.
+    ///
+    /// The tiles at the edges extend to infinity, so that all texture coordinates are painted.
+    fn core_rect(&self, [x, y]: [usize; 2]) -> Rect {
+        let [(min_x, max_x), (min_y, max_y)] = [(0, x), (1, y)].map(|(axis, index)| {
+            let (min, max) = self.core_range(axis, index);
+            let min = if index == 0 {
+                f32::NEG_INFINITY
+            } else {
+                min as f32
+            };
+            let max = if index + 1 == self.num_tiles[axis] {
+                f32::INFINITY
+            } else {
+                max as f32
+            };
+            (min, max)
+        });
+        Rect::from_min_max(pos2(min_x, min_y), pos2(max_x, max_y))
+    }
+
+    /// The index of the tile painting the given texel, along each axis.
+    fn tile_at(&self, texel: Pos2) -> [usize; 2] {
+        [0, 1].map(|axis| {
+            let index = (texel[axis] / self.tile_step[axis] as f32).max(0.0) as usize;
+            index.min(self.num_tiles[axis] - 1)
+        })
+    }
+
+    /// Split an update of the whole texture into updates of the tiles it touches.
+    fn split_delta<'a>(
+        &'a self,
+        delta: &'a ImageDelta,
+    ) -> impl Iterator<Item = (TextureId, ImageDelta)> + 'a {
+        let [x, y] = delta.pos.unwrap_or([0, 0]);
+        let [w, h] = delta.image.size();
+        self.tile_ids
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, &tile_id)| {
+                let (tile_pos, tile_size) =
+                    self.stored_region([index % self.num_tiles[0], index / self.num_tiles[0]]);
+                let min = [x.max(tile_pos[0]), y.max(tile_pos[1])];
+                let max = [
+                    (x + w).min(tile_pos[0] + tile_size[0]),
+                    (y + h).min(tile_pos[1] + tile_size[1]),
+                ];
+                if max[0] <= min[0] || max[1] <= min[1] {
+                    return None;
+                }
+                let image = delta
+                    .image
+                    .region([min[0] - x, min[1] - y], [max[0] - min[0], max[1] - min[1]]);
+                let tile_delta = if delta.pos.is_some() {
+                    ImageDelta::partial(
+                        [min[0] - tile_pos[0], min[1] - tile_pos[1]],
+                        image,
+                        delta.options,
+                    )
+                } else {
+                    ImageDelta::full(image, delta.options)
+                };
+                Some((tile_id, tile_delta))
+            })
+    }
+
+    /// Split a mesh using the whole texture into meshes using the tiles,
+    /// by clipping its triangles to the part of the texture each tile paints.
+    pub fn split_mesh(&self, mesh: &Mesh) -> Vec<Mesh> {
+        let texture_size = Vec2::new(self.size[0] as f32, self.size[1] as f32);
+        let texel =
+            |vertex: &Vertex| pos2(vertex.uv.x * texture_size.x, vertex.uv.y * texture_size.y);
+
+        let mut tile_meshes: Vec<Mesh> = self
+            .tile_ids
+            .iter()
+            .map(|&tile_id| Mesh::with_texture(tile_id))
+            .collect();
+        let mut polygon = Vec::new();
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize]);
+            let bounds = Rect::from_points(&vertices.map(|vertex| texel(&vertex)));
+            let [min_x, min_y] = self.tile_at(bounds.min);
+            let [max_x, max_y] = self.tile_at(bounds.max);
+
+            for y in min_y..=max_y {
+                for x in min_x..=max_x {
+                    let core_rect = self.core_rect([x, y]);
+                    polygon.clear();
+                    polygon.extend(vertices);
+                    if !core_rect.contains_rect(bounds) {
+                        clip_polygon(&mut polygon, core_rect, texture_size);
+                    }
+                    if polygon.len() < 3 {
+                        continue;
+                    }
+
+                    let (tile_pos, tile_size) = self.stored_region([x, y]);
+                    let tile_mesh = &mut tile_meshes[y * self.num_tiles[0] + x];
+                    let first = tile_mesh.vertices.len() as u32;
+                    tile_mesh.vertices.extend(polygon.iter().map(|vertex| {
+                        let texel = texel(vertex);
+                        Vertex {
+                            pos: vertex.pos,
+                            uv: pos2(
+                                (texel.x - tile_pos[0] as f32) / tile_size[0] as f32,
+                                (texel.y - tile_pos[1] as f32) / tile_size[1] as f32,
+                            ),
+                            color: vertex.color,
+                        }
+                    }));
+                    for i in 1..polygon.len() as u32 - 1 {
+                        tile_mesh.add_triangle(first, first + i, first + i + 1);
+                    }
+                }
+            }
+        }
+
+        tile_meshes.retain(|tile_mesh| !tile_mesh.is_empty());
+        tile_meshes
+    }
+}
+
+/// Clip a convex polygon to where its texels (`uv * texture_size`) are within `rect`.
+fn clip_polygon(polygon: &mut Vec<Vertex>, rect: Rect, texture_size: Vec2) {
+    let edges = [
+        (0, rect.min.x, 1.0),
+        (0, rect.max.x, -1.0),
+        (1, rect.min.y, 1.0),
+        (1, rect.max.y, -1.0),
+    ];
+    for (axis, limit, sign) in edges {
+        if !limit.is_finite() {
+            continue;
+        }
+        // Positive inside the edge:
+        let distance = |vertex: &Vertex| sign * (vertex.uv[axis] * texture_size[axis] - limit);
+
+        let input = std::mem::take(polygon);
+        for (i, a) in input.iter().enumerate() {
+            let b = &input[(i + 1) % input.len()];
+            let (distance_a, distance_b) = (distance(a), distance(b));
+            if 0.0 <= distance_a {
+                polygon.push(*a);
+            }
+            if (0.0 <= distance_a) != (0.0 <= distance_b) {
+                let t = distance_a / (distance_a - distance_b);
+                polygon.push(lerp_vertex(a, b, t));
+            }
+        }
+    }
+}
+
+fn lerp_vertex(a: &Vertex, b: &Vertex, t: f32) -> Vertex {
+    let [a_color, b_color] = [a.color, b.color].map(|color| color.to_array());
+    let color =
+        [0, 1, 2, 3].map(|i| emath::lerp(a_color[i] as f32..=b_color[i] as f32, t).round() as u8);
+    Vertex {
+        pos: a.pos.lerp(b.pos, t),
+        uv: a.uv.lerp(b.uv, t),
+        color: Color32::from_rgba_premultiplied(color[0], color[1], color[2], color[3]),
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// How the texture texels are filtered.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -257,3 +711,80 @@ impl std::fmt::Debug for TexturesDelta {
         debug_struct.finish()
     }
 }
+
+#[test]
+fn test_partial_updates_are_merged() {
+    use crate::{Color32, ColorImage};
+
+    let mut tex_mngr = TextureManager::default();
+    let id = tex_mngr.alloc(
+        "test".to_owned(),
+        ColorImage::new([8, 8], Color32::BLACK).into(),
+        Default::default(),
+    );
+    let _ = tex_mngr.take_delta();
+
+    let partial = |pos, size| {
+        ImageDelta::partial(
+            pos,
+            ColorImage::new(size, Color32::WHITE),
+            Default::default(),
+        )
+    };
+
+    // A patch within an enqueued one is written into it:
+    tex_mngr.set(id, partial([0, 0], [4, 4]));
+    tex_mngr.set(id, partial([1, 1], [2, 2]));
+    assert_eq!(tex_mngr.take_delta().set.len(), 1);
+
+    // A patch that covers an enqueued one replaces it:
+    tex_mngr.set(id, partial([1, 1], [2, 2]));
+    tex_mngr.set(id, partial([0, 0], [4, 4]));
+    let delta = tex_mngr.take_delta();
+    assert_eq!(delta.set.len(), 1);
+    assert_eq!(delta.set[0].1.image.size(), [4, 4]);
+
+    // Unrelated patches are kept apart:
+    tex_mngr.set(id, partial([0, 0], [2, 2]));
+    tex_mngr.set(id, partial([4, 4], [2, 2]));
+    assert_eq!(tex_mngr.take_delta().set.len(), 2);
+}
+
+#[test]
+fn test_large_textures_are_tiled() {
+    use crate::{Color32, ColorImage};
+
+    let mut tex_mngr = TextureManager::default();
+    tex_mngr.set_max_texture_side(Some(10));
+    let id = tex_mngr.alloc(
+        "large".to_owned(),
+        ColorImage::new([20, 5], Color32::WHITE).into(),
+        Default::default(),
+    );
+
+    let tiling = tex_mngr.meta(id).unwrap().tiling.clone().unwrap();
+    assert_eq!(tiling.num_tiles, [3, 1]);
+    let delta = tex_mngr.take_delta();
+    assert_eq!(delta.set.len(), 3);
+    assert!(delta.set.iter().all(|(tile_id, tile_delta)| {
+        tiling.tile_ids.contains(tile_id) && tile_delta.image.width() <= 10
+    }));
+
+    // A rectangle across the whole texture is painted with all the tiles:
+    let mut mesh = Mesh::with_texture(id);
+    mesh.add_rect_with_uv(
+        Rect::from_min_max(pos2(0.0, 0.0), pos2(200.0, 50.0)),
+        Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+        Color32::WHITE,
+    );
+    let mut clipped_primitives = vec![ClippedPrimitive {
+        clip_rect: Rect::EVERYTHING,
+        primitive: Primitive::Mesh(mesh),
+    }];
+    tex_mngr.resolve_tiles(&mut clipped_primitives);
+    assert_eq!(clipped_primitives.len(), 3);
+
+    // Freeing the texture frees the tiles:
+    tex_mngr.free(id);
+    assert_eq!(tex_mngr.take_delta().free, tiling.tile_ids);
+}